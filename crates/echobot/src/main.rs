@@ -6,7 +6,7 @@ use ircnet::client::{
     commands::JoinCommand,
 };
 use irctext::{
-    CaseMapping, ClientMessage, Message, Payload, Source, TrailingParam,
+    CaseMapping, CaseSet, ClientMessage, Message, Payload, Source, TrailingParam,
     clientmsgs::{PrivMsg, Quit},
     ctcp::CtcpParams,
     types::{Channel, ISupportParam, MsgTarget},
@@ -79,6 +79,9 @@ async fn main() -> anyhow::Result<()> {
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
@@ -110,13 +113,13 @@ async fn main() -> anyhow::Result<()> {
     let me = login_output.my_nick;
 
     let delay = profile.echobot.delay();
-    let mut canon_channels = ChannelCanonicalizer::new(casemapping);
+    let mut canon_channels = CaseSet::<Channel>::new(casemapping);
     for chan in profile.echobot.channels {
         tracing::info!("Joining {chan} …");
         let output = client.run(JoinCommand::new(chan.clone())).await?;
         let chan = output.channel;
         tracing::info!("Joined {chan}");
-        canon_channels.add(chan);
+        canon_channels.insert(chan);
     }
 
     let mut pending = JoinSet::new();
@@ -236,40 +239,6 @@ enum Event {
     Stopped,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct ChannelCanonicalizer {
-    casemapping: CaseMapping,
-    lower2canon: HashMap<Channel, Channel>,
-}
-
-impl ChannelCanonicalizer {
-    fn new(casemapping: CaseMapping) -> Self {
-        Self {
-            casemapping,
-            lower2canon: HashMap::new(),
-        }
-    }
-
-    fn add(&mut self, channel: Channel) {
-        let lower = channel.to_lowercase(self.casemapping);
-        self.lower2canon.insert(lower, channel);
-    }
-
-    fn get(&self, channel: &Channel) -> Option<&Channel> {
-        let lower = channel.to_lowercase(self.casemapping);
-        self.lower2canon.get(&lower)
-    }
-
-    fn remove(&mut self, channel: &Channel) {
-        let lower = channel.to_lowercase(self.casemapping);
-        self.lower2canon.remove(&lower);
-    }
-
-    fn is_empty(&self) -> bool {
-        self.lower2canon.is_empty()
-    }
-}
-
 fn strip_nick<'a>(nickname: &str, message: &'a str) -> Option<&'a str> {
     let (target, msg) = message.split_once(": ")?;
     let msg = msg.trim_start_matches(' ');
@@ -4,23 +4,32 @@ use either::Either;
 use ircnet::client::{
     SessionBuilder, SessionParams,
     autoresponders::{CtcpQueryResponder, PingResponder},
-    commands::JoinCommand,
+    commands::{ChatHistory, HistoricalMessage, JoinCommand},
 };
 use irctext::{
-    CaseMapping, ClientMessage, FinalParam, Message, Payload,
-    clientmsgs::{Away, Quit},
+    CaseMapping, ClientMessage, FinalParam, Message, MessageTags, Payload, Reply, Source,
+    clientmsgs::{Away, Capability, ChatHistory as ChatHistoryMsg, MessageRef, Quit},
     ctcp::CtcpParams,
-    types::{Channel, ISupportParam, MsgTarget},
+    types::{Channel, ChannelSyntax, MsgTarget},
 };
 use patharg::OutputArg;
 use serde_jsonlines::JsonLinesWriter;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufWriter, IsTerminal, stderr};
-use std::path::PathBuf;
-use tokio::select;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::{select, time::sleep};
 use tracing::Level;
 use tracing_subscriber::{filter::Targets, fmt::time::OffsetTime, prelude::*};
 
+/// Starting delay before the first reconnect attempt, absent
+/// `ProgramParams::reconnect_base_delay`.
+const DEFAULT_RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Ceiling the exponentially-growing reconnect delay backs off to, absent
+/// `ProgramParams::reconnect_max_delay`.
+const DEFAULT_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(300);
+
 #[derive(Clone, Debug, Eq, Parser, PartialEq)]
 struct Arguments {
     #[arg(short = 'c', long, default_value = "ircbits.toml")]
@@ -32,6 +41,33 @@ struct Arguments {
     #[arg(short = 'P', long, default_value = "irc")]
     profile: String,
 
+    /// Rotate the output file onto a calendar boundary, in addition to
+    /// writing to it continuously.  Only takes effect when `outfile` is a
+    /// real path, not `-`/stdout.  Overrides `rotate_interval` in the
+    /// profile config if both are given.
+    #[arg(long)]
+    rotate_interval: Option<RotateInterval>,
+
+    /// Compress each rotated log file in the background after rotation,
+    /// removing the uncompressed copy.  Only takes effect alongside
+    /// `rotate_interval`.  Overrides `compress` in the profile config if
+    /// both are given.
+    #[arg(long)]
+    compress: Option<CompressFormat>,
+
+    /// Maximum number of consecutive reconnect attempts to make before
+    /// giving up and exiting with an error.  Unset means retry forever.
+    /// Overrides `reconnect_max_attempts` in the profile config if both are
+    /// given.
+    #[arg(long)]
+    max_reconnect_attempts: Option<u32>,
+
+    /// Ceiling, in seconds, that the reconnect delay doubles up to after
+    /// each failed attempt.  Overrides `reconnect_max_delay` in the profile
+    /// config if both are given.
+    #[arg(long)]
+    reconnect_max_delay: Option<u64>,
+
     #[arg(long)]
     trace: bool,
 }
@@ -47,8 +83,71 @@ struct Profile {
 
 #[derive(Clone, Debug, Default, serde::Deserialize, Eq, PartialEq)]
 struct ProgramParams {
-    channels: Vec<Channel>,
+    /// Channels to join, kept as raw strings rather than pre-parsed
+    /// `Channel`s so they can be validated against the server's actual
+    /// `CHANTYPES`/`CHANNELLEN` ISUPPORT tokens once login completes,
+    /// instead of this library's context-free `#`/`&` default.
+    channels: Vec<String>,
     away: Option<FinalParam>,
+
+    /// Page size for `CHATHISTORY` requests issued on each join, backfilling
+    /// the gap since the logger was last online: `LATEST` the first time the
+    /// output file has no prior history for a channel, then `AFTER` the last
+    /// logged message's timestamp (paginating until a page comes back short)
+    /// on every run after that.  Only takes effect if the server advertises
+    /// `draft/chathistory`; has no effect otherwise.
+    #[serde(default = "default_chathistory_limit")]
+    chathistory_limit: u32,
+
+    /// Delay, in seconds, before the first reconnect attempt after an
+    /// unexpected disconnect or transport error.
+    reconnect_base_delay: Option<u64>,
+
+    /// Ceiling, in seconds, that the reconnect delay doubles up to after
+    /// each failed attempt.
+    reconnect_max_delay: Option<u64>,
+
+    /// Maximum number of consecutive reconnect attempts to make before
+    /// giving up and exiting with an error.  Absent means retry forever.
+    reconnect_max_attempts: Option<u32>,
+
+    /// Shell command to run at startup whose trimmed stdout is used as the
+    /// server password, instead of putting it in `password` in cleartext.
+    /// Takes precedence over `password` if both are set.
+    password_command: Option<String>,
+
+    /// Shell command to run at startup whose trimmed stdout is used as the
+    /// SASL password, instead of putting it in `sasl.password` in
+    /// cleartext.  Takes precedence over `sasl.password` if both are set;
+    /// has no effect if `sasl` isn't configured.
+    sasl_password_command: Option<String>,
+
+    /// Rotate the output file onto a calendar boundary; see
+    /// [`Arguments::rotate_interval`], which takes precedence over this if
+    /// both are given.
+    #[serde(default)]
+    rotate_interval: Option<RotateInterval>,
+
+    /// Compress rotated log files; see [`Arguments::compress`], which takes
+    /// precedence over this if both are given.
+    #[serde(default)]
+    compress: Option<CompressFormat>,
+}
+
+fn default_chathistory_limit() -> u32 {
+    50
+}
+
+impl ProgramParams {
+    fn reconnect_base_delay(&self) -> Duration {
+        self.reconnect_base_delay
+            .map_or(DEFAULT_RECONNECT_BASE_DELAY, Duration::from_secs)
+    }
+
+    fn reconnect_max_delay(&self) -> Duration {
+        self.reconnect_max_delay
+            .map_or(DEFAULT_RECONNECT_MAX_DELAY, Duration::from_secs)
+    }
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -88,129 +187,315 @@ async fn main() -> anyhow::Result<()> {
         anyhow::bail!("No channels configured for profile {network:?}");
     }
 
-    let outfile = match args.outfile {
-        OutputArg::Stdout => Either::Left(io::stdout().lock()),
-        OutputArg::Path(p) => Either::Right(
-            std::fs::File::options()
-                .create(true)
-                .append(true)
-                .open(p)
-                .context("failed to open output file")?,
-        ),
+    let resume_state = match &args.outfile {
+        OutputArg::Path(p) if p.exists() => {
+            load_resume_state(p).context("failed to scan existing log for resume state")?
+        }
+        _ => HashMap::new(),
     };
-    let mut log = EventLogger::new(outfile);
-
-    tracing::info!("Connecting to IRC …");
-    let (mut client, login_output) = SessionBuilder::new(profile.session_params)
-        .with_autoresponder(PingResponder::new())
-        .with_autoresponder(
-            CtcpQueryResponder::new()
-                .with_version(
-                    env!("CARGO_CRATE_NAME")
-                        .parse::<CtcpParams>()
-                        .expect("Crate name should be valid CTCP params"),
-                )
-                .with_source(
-                    env!("CARGO_PKG_REPOSITORY")
-                        .parse::<CtcpParams>()
-                        .expect("Project repository URL should be valid CTCP params"),
+
+    let rotate_interval = args.rotate_interval.or(profile.ircevents.rotate_interval);
+    let compress = args.compress.or(profile.ircevents.compress);
+    let mut log = match (&args.outfile, rotate_interval) {
+        (OutputArg::Path(p), Some(interval)) => {
+            Logger::Rotating(RotatingEventLogger::open(p.clone(), interval, compress)?)
+        }
+        _ => {
+            let outfile = match args.outfile {
+                OutputArg::Stdout => Either::Left(io::stdout().lock()),
+                OutputArg::Path(p) => Either::Right(
+                    std::fs::File::options()
+                        .create(true)
+                        .append(true)
+                        .open(p)
+                        .context("failed to open output file")?,
                 ),
-        )
-        .build()
-        .await?;
-
-    let casemapping = login_output
-        .isupport
-        .iter()
-        .find_map(|param| {
-            if let ISupportParam::Eq(key, value) = param
-                && key == "CASEMAPPING"
-                && let Ok(cm) = value.as_str().parse::<CaseMapping>()
-            {
-                Some(cm)
-            } else {
-                None
-            }
-        })
-        .unwrap_or_default();
-    let me = login_output.my_nick;
+            };
+            Logger::Plain(EventLogger::new(outfile))
+        }
+    };
 
-    if let Some(p) = profile.ircevents.away {
-        client.send(Away::new(p).into()).await?;
-    }
+    let mut session_params = profile.session_params;
+    session_params
+        .login
+        .capabilities
+        .extend([server_time_cap(), chathistory_cap()]);
 
-    let mut canon_channels = ChannelCanonicalizer::new(casemapping);
-    for chan in profile.ircevents.channels {
-        tracing::info!("Joining {chan} …");
-        let output = client.run(JoinCommand::new(chan.clone())).await?;
-        let chan = output.channel;
-        log.log(Event::new(
-            &network,
-            Some(chan.clone().into_inner()),
-            "joined",
-        ))?;
-        canon_channels.add(chan);
+    if let Some(ref cmd) = profile.ircevents.password_command {
+        let password = run_password_command(cmd).context("failed to run password_command")?;
+        session_params.login.password = password
+            .parse()
+            .context("password_command output is not a valid password")?;
+    }
+    if let Some(ref cmd) = profile.ircevents.sasl_password_command {
+        let password = run_password_command(cmd).context("failed to run sasl_password_command")?;
+        if let Some(sasl) = session_params.login.sasl.as_mut() {
+            sasl.password = password;
+        }
     }
 
-    loop {
-        select! {
-            r = client.recv() => {
-                match r {
-                    Ok(Some(Message {payload: Payload::ClientMessage(climsg), ..})) => {
-                        match climsg {
-                            ClientMessage::PrivMsg(m) => {
-                                for t in m.targets() {
-                                    if let MsgTarget::Channel(c0) = t && let Some(c) = canon_channels.get(c0).cloned() {
-                                        log.log(Event::new(&network, Some(c.into_inner()), "message"))?;
-                                    }
+    let base_delay = profile.ircevents.reconnect_base_delay();
+    let max_delay = args.reconnect_max_delay.map_or_else(
+        || profile.ircevents.reconnect_max_delay(),
+        Duration::from_secs,
+    );
+    let max_attempts = args
+        .max_reconnect_attempts
+        .or(profile.ircevents.reconnect_max_attempts);
+    let mut attempt: u32 = 0;
+    let mut backoff = base_delay;
+
+    'sessions: loop {
+        if attempt > 0 {
+            let delay = jittered(backoff);
+            tracing::info!(attempt, ?delay, "Reconnecting after disconnect …");
+            sleep(delay).await;
+            backoff = (backoff * 2).min(max_delay);
+        }
+
+        tracing::info!("Connecting to IRC …");
+        let built = SessionBuilder::new(session_params.clone())
+            .with_autoresponder(PingResponder::new())
+            .with_autoresponder(
+                CtcpQueryResponder::new()
+                    .with_clientinfo()
+                    .with_ping()
+                    .with_time()
+                    .with_version(
+                        env!("CARGO_CRATE_NAME")
+                            .parse::<CtcpParams>()
+                            .expect("Crate name should be valid CTCP params"),
+                    )
+                    .with_source(
+                        env!("CARGO_PKG_REPOSITORY")
+                            .parse::<CtcpParams>()
+                            .expect("Project repository URL should be valid CTCP params"),
+                    ),
+            )
+            .build()
+            .await;
+        let (mut client, login_output) = match built {
+            Ok(pair) => pair,
+            Err(e) => {
+                attempt += 1;
+                if max_attempts.is_some_and(|max| attempt > max) {
+                    return Err(e.into());
+                }
+                tracing::warn!(error = %e, attempt, "Login failed; will retry");
+                continue 'sessions;
+            }
+        };
+
+        if attempt > 0 {
+            log.log(Event::new(&network, None, "reconnected", None))?;
+        }
+        attempt = 0;
+        backoff = base_delay;
+
+        let casemapping = login_output.isupport.casemapping();
+        let chan_syntax = ChannelSyntax::from_isupport(&login_output.isupport);
+        let me = login_output.my_nick;
+        let chathistory_enabled = login_output
+            .capabilities
+            .iter()
+            .any(|(cap, _)| *cap == chathistory_cap());
+        let chathistory_limit = profile.ircevents.chathistory_limit;
+
+        if let Some(p) = profile.ircevents.away.clone() {
+            client.send(Away::new(p).into()).await?;
+        }
+
+        let mut canon_channels = ChannelCanonicalizer::new(casemapping);
+        for chan in &profile.ircevents.channels {
+            let chan = Channel::parse_with(chan, &chan_syntax)
+                .with_context(|| format!("{chan:?} is not a valid channel on this network"))?;
+            tracing::info!("Joining {chan} …");
+            let output = client.run(JoinCommand::new(chan.clone())).await?;
+            let chan = output.channel;
+            log.log(Event::new(
+                &network,
+                Some(chan.clone().into_inner()),
+                "joined",
+                None,
+            ))?;
+            canon_channels.add(chan.clone());
+
+            if chathistory_enabled {
+                tracing::info!("Backfilling history for {chan} …");
+                let target = MsgTarget::Channel(chan.clone());
+                match resume_state.get(chan.as_str()) {
+                    Some(resume) => {
+                        let mut seen = resume.seen_msgids.clone();
+                        let mut reference = MessageRef::timestamp(resume.last_time.clone());
+                        loop {
+                            let request =
+                                ChatHistoryMsg::after(target.clone(), reference, chathistory_limit);
+                            let history = client.run(ChatHistory::new(request)).await?;
+                            let got = history.len();
+                            let mut latest_time = None;
+                            for HistoricalMessage { time, msgid, .. } in history {
+                                if let Some(id) = &msgid
+                                    && !seen.insert(id.clone())
+                                {
+                                    continue;
                                 }
+                                log.log(Event::message(
+                                    &network,
+                                    chan.clone().into_inner(),
+                                    time.as_deref(),
+                                    msgid,
+                                ))?;
+                                if let Some(t) = time {
+                                    latest_time = Some(t);
+                                }
+                            }
+                            let Some(t) = latest_time else { break };
+                            if (got as u32) < chathistory_limit {
+                                break;
                             }
-                            ClientMessage::Notice(m) => {
-                                for t in m.targets() {
-                                    if let MsgTarget::Channel(c0) = t && let Some(c) = canon_channels.get(c0).cloned() {
-                                        log.log(Event::new(&network, Some(c.into_inner()), "message"))?;
+                            reference = MessageRef::timestamp(t);
+                        }
+                    }
+                    None => {
+                        let request = ChatHistoryMsg::latest(target, None, chathistory_limit);
+                        let history = client.run(ChatHistory::new(request)).await?;
+                        for HistoricalMessage { time, msgid, .. } in history {
+                            log.log(Event::message(
+                                &network,
+                                chan.clone().into_inner(),
+                                time.as_deref(),
+                                msgid,
+                            ))?;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut quitting = false;
+        let failure = 'events: loop {
+            select! {
+                r = client.recv() => {
+                    match r {
+                        Ok(Some(Message {payload: Payload::ClientMessage(climsg), source, tags, ..})) => {
+                            let server_time = tags.as_ref().and_then(MessageTags::time);
+                            match climsg {
+                                ClientMessage::PrivMsg(m) => {
+                                    for t in m.targets() {
+                                        if let MsgTarget::Channel(c0) = t && let Some(c) = canon_channels.get(c0).cloned() {
+                                            log.log(Event::new(&network, Some(c.into_inner()), "message", server_time))?;
+                                        }
                                     }
                                 }
-                            }
-                            ClientMessage::Kick(m) => {
-                                if let Some(chan) = canon_channels.get(m.channel()) && m.users().iter().any(|nick| nick == &me) {
-                                    tracing::info!(comment = m.comment().map(ToString::to_string), "Kicked from {chan}");
-                                    log.log(Event::new(&network, Some(chan.as_str().to_owned()), "kicked"))?;
-                                    let chan = chan.to_owned(); // Stop borrowing from canon_channels so we can mutate it
-                                    canon_channels.remove(&chan);
-                                    if canon_channels.is_empty() {
-                                        tracing::info!("No channels left; quitting");
-                                        client.send(Quit::new().into()).await?;
+                                ClientMessage::Notice(m) => {
+                                    for t in m.targets() {
+                                        if let MsgTarget::Channel(c0) = t && let Some(c) = canon_channels.get(c0).cloned() {
+                                            log.log(Event::new(&network, Some(c.into_inner()), "message", server_time))?;
+                                        }
                                     }
                                 }
+                                ClientMessage::Kick(m) => {
+                                    if let Some(chan) = canon_channels.get(m.channel()) && m.users().iter().any(|nick| nick == &me) {
+                                        tracing::info!(comment = m.comment().map(ToString::to_string), "Kicked from {chan}");
+                                        log.log(Event::new(&network, Some(chan.as_str().to_owned()), "kicked", server_time))?;
+                                        let chan = chan.to_owned(); // Stop borrowing from canon_channels so we can mutate it
+                                        canon_channels.remove(&chan);
+                                        if canon_channels.is_empty() {
+                                            tracing::info!("No channels left; quitting");
+                                            quitting = true;
+                                            client.send(Quit::new().into()).await?;
+                                        }
+                                    }
+                                }
+                                ClientMessage::Topic(m) => {
+                                    if let Some(c) = canon_channels.get(m.channel()).cloned() {
+                                        let setter = match &source {
+                                            Some(Source::Client(cs)) => Some(cs.nickname.to_string()),
+                                            _ => None,
+                                        };
+                                        log.log(Event::topic_change(
+                                            &network,
+                                            c.into_inner(),
+                                            m.topic().map(ToString::to_string),
+                                            setter,
+                                            server_time,
+                                        ))?;
+                                    }
+                                }
+                                ClientMessage::Error(m) => {
+                                    tracing::info!("Server sent ERROR message: {}", m.reason());
+                                }
+                                _ => (),
                             }
-                            ClientMessage::Error(m) => {
-                                tracing::info!("Server sent ERROR message: {}", m.reason());
+                        }
+                        Ok(Some(Message {payload: Payload::Reply(reply), tags, ..})) => {
+                            let server_time = tags.as_ref().and_then(MessageTags::time);
+                            match reply {
+                                Reply::Topic(r) => {
+                                    if let Some(c) = canon_channels.get(r.channel()).cloned() {
+                                        log.log(Event::topic_change(
+                                            &network,
+                                            c.into_inner(),
+                                            Some(r.topic().to_owned()),
+                                            None,
+                                            server_time,
+                                        ))?;
+                                    }
+                                }
+                                Reply::NoTopic(r) => {
+                                    if let Some(c) = canon_channels.get(r.channel()).cloned() {
+                                        log.log(Event::topic_change(
+                                            &network,
+                                            c.into_inner(),
+                                            None,
+                                            None,
+                                            server_time,
+                                        ))?;
+                                    }
+                                }
+                                _ => (),
                             }
-                            _ => (),
+                        }
+                        Ok(None) => {
+                            tracing::info!("Connection closed");
+                            log.log(Event::new(&network, None, "disconnected", None))?;
+                            break 'events if quitting {
+                                None
+                            } else {
+                                Some(anyhow::anyhow!("Connection closed unexpectedly"))
+                            };
+                        }
+                        Err(e) => {
+                            let e = anyhow::Error::new(e);
+                            tracing::error!(?e, "Error communicating with server");
+                            log.log(Event::new(&network, None, "error", None))?;
+                            break 'events if quitting { None } else { Some(e) };
                         }
                     }
-                    Ok(Some(_)) => (),
-                    Ok(None) => {
-                        tracing::info!("Connection closed");
-                        log.log(Event::new(&network, None, "disconnected"))?;
-                        break;
-                    }
-                    Err(e) => {
-                        let e = anyhow::Error::new(e);
-                        tracing::error!(?e, "Error communicating with server");
-                        log.log(Event::new(&network, None, "error"))?;
-                        return Err(e);
-                    }
+                }
+                () = recv_stop_signal() => {
+                    tracing::info!("Signal received; quitting");
+                    quitting = true;
+                    client.send(Quit::new_with_reason("Terminated".parse::<FinalParam>().expect(r#""Terminated" should be valid FinalParam"#)).into()).await?;
                 }
             }
-            () = recv_stop_signal() => {
-                tracing::info!("Signal received; quitting");
-                client.send(Quit::new_with_reason("Terminated".parse::<FinalParam>().expect(r#""Terminated" should be valid FinalParam"#)).into()).await?;
+        };
+
+        match failure {
+            None => {
+                log.shutdown().await?;
+                return Ok(());
+            }
+            Some(e) => {
+                attempt += 1;
+                if max_attempts.is_some_and(|max| attempt > max) {
+                    log.shutdown().await?;
+                    return Err(e);
+                }
+                tracing::warn!(error = %e, attempt, "Connection lost; will retry");
             }
         }
     }
-    Ok(())
 }
 
 #[cfg(unix)]
@@ -248,25 +533,438 @@ impl<W: io::Write> EventLogger<W> {
     }
 }
 
+/// Either a plain [`EventLogger`] (used for stdout output, or a file output
+/// with no `rotate_interval` configured) or one that rotates its output
+/// file onto calendar boundaries; see [`RotatingEventLogger`].
+#[derive(Debug)]
+enum Logger {
+    Plain(EventLogger<Either<io::StdoutLock<'static>, std::fs::File>>),
+    Rotating(RotatingEventLogger),
+}
+
+impl Logger {
+    fn log(&mut self, event: Event) -> anyhow::Result<()> {
+        match self {
+            Logger::Plain(l) => l.log(event),
+            Logger::Rotating(l) => l.log(event),
+        }
+    }
+
+    /// Waits for any background compression tasks spawned by a
+    /// [`RotatingEventLogger`] to finish; a no-op for [`Logger::Plain`].
+    async fn shutdown(self) -> anyhow::Result<()> {
+        match self {
+            Logger::Plain(_) => Ok(()),
+            Logger::Rotating(l) => l.shutdown().await,
+        }
+    }
+}
+
+/// How often a [`RotatingEventLogger`] rotates its output file onto a
+/// calendar boundary.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RotateInterval {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl std::str::FromStr for RotateInterval {
+    type Err = ParseRotateIntervalError;
+
+    fn from_str(s: &str) -> Result<RotateInterval, ParseRotateIntervalError> {
+        match s {
+            "hourly" => Ok(RotateInterval::Hourly),
+            "daily" => Ok(RotateInterval::Daily),
+            "weekly" => Ok(RotateInterval::Weekly),
+            _ => Err(ParseRotateIntervalError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error(r#"invalid rotation interval (expected "hourly", "daily", or "weekly")"#)]
+struct ParseRotateIntervalError;
+
+/// Which external compression tool to run on a rotated log file; see
+/// [`compress_in_background`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CompressFormat {
+    Gzip,
+    Zstd,
+}
+
+impl std::str::FromStr for CompressFormat {
+    type Err = ParseCompressFormatError;
+
+    fn from_str(s: &str) -> Result<CompressFormat, ParseCompressFormatError> {
+        match s {
+            "gzip" => Ok(CompressFormat::Gzip),
+            "zstd" => Ok(CompressFormat::Zstd),
+            _ => Err(ParseCompressFormatError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error(r#"invalid compression format (expected "gzip" or "zstd")"#)]
+struct ParseCompressFormatError;
+
+/// Compresses `path` in place using the external `gzip`/`zstd` binary
+/// (neither of which this workspace depends on as a Rust crate), removing
+/// the uncompressed original and leaving `path` with `.gz`/`.zst` appended.
+fn compress_file(path: &Path, format: CompressFormat) -> anyhow::Result<()> {
+    let mut command = match format {
+        CompressFormat::Gzip => std::process::Command::new("gzip"),
+        CompressFormat::Zstd => {
+            let mut c = std::process::Command::new("zstd");
+            c.arg("--rm");
+            c
+        }
+    };
+    let status = command
+        .arg(path)
+        .status()
+        .context("failed to spawn compression command")?;
+    if !status.success() {
+        anyhow::bail!("compression command exited with {status}");
+    }
+    Ok(())
+}
+
+/// Runs [`compress_file`] on a `tokio` blocking thread so a large rotated
+/// file doesn't stall live event writing.  Failures are logged rather than
+/// propagated, since the task is detached from the logging hot path.
+fn compress_in_background(path: PathBuf, format: CompressFormat) -> tokio::task::JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = compress_file(&path, format) {
+            tracing::warn!(error = %e, path = %path.display(), "Failed to compress rotated log file");
+        }
+    })
+}
+
+impl RotateInterval {
+    /// Returns the key identifying which period `timestamp` (an RFC 3339
+    /// string) falls into, such that two timestamps in the same period
+    /// produce the same key: the hour for [`RotateInterval::Hourly`], the
+    /// calendar day (UTC) for [`RotateInterval::Daily`], and the
+    /// Monday-starting calendar week (UTC) for [`RotateInterval::Weekly`].
+    fn period_key(self, timestamp: &str) -> anyhow::Result<String> {
+        let ts: jiff::Timestamp = timestamp
+            .parse()
+            .context("event timestamp is not a valid timestamp")?;
+        let zoned = ts.to_zoned(jiff::tz::TimeZone::UTC);
+        let period_start = match self {
+            RotateInterval::Hourly | RotateInterval::Daily => zoned,
+            RotateInterval::Weekly => {
+                let back = i64::from(zoned.weekday().to_monday_zero_offset());
+                zoned
+                    .checked_sub(jiff::Span::new().days(back))
+                    .context("failed to compute start of week")?
+            }
+        };
+        let format = match self {
+            RotateInterval::Hourly => "%Y%m%d%H",
+            RotateInterval::Daily | RotateInterval::Weekly => "%Y%m%d",
+        };
+        jiff::fmt::strtime::format(format, &period_start)
+            .context("failed to format rotation period key")
+    }
+}
+
+/// A [`JsonLinesWriter`]-backed event log that rotates its output file when
+/// an event's timestamp crosses into a new `rotate_interval` period: the
+/// current file is renamed with the outgoing period's start appended (via
+/// [`insert_extension`]) before a fresh one is opened at the original path.
+#[derive(Debug)]
+struct RotatingEventLogger {
+    path: PathBuf,
+    interval: RotateInterval,
+    compress: Option<CompressFormat>,
+    period: Option<String>,
+    writer: JsonLinesWriter<BufWriter<std::fs::File>>,
+    pending: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl RotatingEventLogger {
+    fn open(
+        path: PathBuf,
+        interval: RotateInterval,
+        compress: Option<CompressFormat>,
+    ) -> anyhow::Result<Self> {
+        let writer = Self::open_writer(&path)?;
+        Ok(RotatingEventLogger {
+            path,
+            interval,
+            compress,
+            period: None,
+            writer,
+            pending: Vec::new(),
+        })
+    }
+
+    fn open_writer(path: &Path) -> anyhow::Result<JsonLinesWriter<BufWriter<std::fs::File>>> {
+        let file = std::fs::File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context("failed to open output file")?;
+        Ok(JsonLinesWriter::new(BufWriter::new(file)))
+    }
+
+    fn log(&mut self, event: Event) -> anyhow::Result<()> {
+        let period = self.interval.period_key(&event.timestamp)?;
+        if self
+            .period
+            .as_deref()
+            .is_some_and(|current| current != period)
+        {
+            let outgoing = self.period.take().expect("just checked to be Some");
+            self.rotate(outgoing)?;
+        }
+        self.period = Some(period);
+        self.writer
+            .write(&event)
+            .context("failed to write event to log")?;
+        self.writer
+            .flush()
+            .context("failed to write event to log")?;
+        Ok(())
+    }
+
+    fn rotate(&mut self, outgoing_period: String) -> anyhow::Result<()> {
+        self.writer
+            .flush()
+            .context("failed to flush log before rotating")?;
+        let rotated = insert_extension(&self.path, &outgoing_period);
+        std::fs::rename(&self.path, &rotated).context("failed to rotate log file")?;
+        self.writer = Self::open_writer(&self.path)?;
+        if let Some(format) = self.compress {
+            self.pending.push(compress_in_background(rotated, format));
+        }
+        Ok(())
+    }
+
+    /// Waits for any background compression tasks spawned by [`Self::rotate`]
+    /// to finish, so a pending compression isn't cut short by process exit.
+    async fn shutdown(self) -> anyhow::Result<()> {
+        for handle in self.pending {
+            handle.await.context("compression task panicked")?;
+        }
+        Ok(())
+    }
+}
+
+/// Inserts `infix` into `path`'s file name just before its extension (or at
+/// the end, if it has none), e.g. `ircbits.jsonl` with infix `20260731`
+/// becomes `ircbits.20260731.jsonl`.
+fn insert_extension(path: &Path, infix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default();
+    let mut name = stem.to_os_string();
+    name.push(".");
+    name.push(infix);
+    if let Some(ext) = path.extension() {
+        name.push(".");
+        name.push(ext);
+    }
+    path.with_file_name(name)
+}
+
 #[allow(clippy::struct_field_names)]
-#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 struct Event {
     network: String,
     channel: Option<String>,
     event: String,
     timestamp: String,
+    time_source: TimeSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    setter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    msgid: Option<String>,
+}
+
+/// Whether an [`Event`]'s `timestamp` came from the originating message's
+/// `server-time` tag or was assigned locally at receive time (because the
+/// tag was absent, unparseable, or the event has no originating message at
+/// all, e.g. a disconnect), so downstream consumers can tell replayed
+/// history from live traffic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TimeSource {
+    Server,
+    Local,
 }
 
 impl Event {
-    fn new(network: &str, channel: Option<String>, event: &str) -> Event {
-        let timestamp = jiff::Timestamp::now().to_string();
+    /// Builds an event, preferring `server_time` (the IRCv3 `time` message
+    /// tag off the `Message` that triggered this event, if any) over the
+    /// current time as the timestamp, since a replayed or otherwise delayed
+    /// message's receive time isn't when it actually happened.  Pass `None`
+    /// for events with no originating tagged message (joins, disconnects).
+    fn new(
+        network: &str,
+        channel: Option<String>,
+        event: &str,
+        server_time: Option<&str>,
+    ) -> Event {
+        let (timestamp, time_source) = resolve_timestamp(server_time);
         Event {
             network: network.to_owned(),
             channel,
             event: event.to_owned(),
             timestamp,
+            time_source,
+            topic: None,
+            setter: None,
+            msgid: None,
+        }
+    }
+
+    /// Builds a `"message"` event for a `PRIVMSG`/`NOTICE` backfilled via
+    /// `CHATHISTORY`, carrying the message's `msgid` tag (if any) so future
+    /// runs can recognize and skip it if the same window gets re-requested.
+    fn message(
+        network: &str,
+        channel: String,
+        server_time: Option<&str>,
+        msgid: Option<String>,
+    ) -> Event {
+        let (timestamp, time_source) = resolve_timestamp(server_time);
+        Event {
+            network: network.to_owned(),
+            channel: Some(channel),
+            event: "message".to_owned(),
+            timestamp,
+            time_source,
+            topic: None,
+            setter: None,
+            msgid,
+        }
+    }
+
+    /// Builds a `"topic"` event for a channel's topic being set (`topic =
+    /// Some(..)`) or cleared (`topic = None`), optionally naming the client
+    /// who set it (unknown for topics learned from `RPL_TOPIC`/`RPL_NOTOPIC`
+    /// on join, since those replies carry no source).
+    fn topic_change(
+        network: &str,
+        channel: String,
+        topic: Option<String>,
+        setter: Option<String>,
+        server_time: Option<&str>,
+    ) -> Event {
+        let (timestamp, time_source) = resolve_timestamp(server_time);
+        Event {
+            network: network.to_owned(),
+            channel: Some(channel),
+            event: "topic".to_owned(),
+            timestamp,
+            time_source,
+            topic,
+            setter,
+            msgid: None,
+        }
+    }
+}
+
+fn resolve_timestamp(server_time: Option<&str>) -> (String, TimeSource) {
+    match server_time.and_then(|t| t.parse::<jiff::Timestamp>().ok()) {
+        Some(ts) => (ts.to_string(), TimeSource::Server),
+        None => (jiff::Timestamp::now().to_string(), TimeSource::Local),
+    }
+}
+
+/// The most recently logged `"message"` event's timestamp for a channel,
+/// together with the `msgid`s sharing that exact timestamp (since
+/// `CHATHISTORY AFTER`'s reference point is exclusive, messages sharing it
+/// with the last logged one would otherwise be replayed on resume).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct ResumeState {
+    last_time: String,
+    seen_msgids: HashSet<String>,
+}
+
+/// Scans an existing event log for the most recent `"message"` timestamp
+/// per channel, so a restarted logger can resume backfilling via
+/// `CHATHISTORY AFTER` from where it left off instead of re-requesting (and
+/// re-logging) just the latest `chathistory_limit` messages every time.
+fn load_resume_state(path: &Path) -> anyhow::Result<HashMap<String, ResumeState>> {
+    let mut states: HashMap<String, ResumeState> = HashMap::new();
+    for event in serde_jsonlines::json_lines::<Event, _>(path)
+        .context("failed to open existing output file for resume scan")?
+    {
+        let event =
+            event.context("failed to parse existing log line while scanning for resume state")?;
+        if event.event != "message" {
+            continue;
         }
+        let Some(channel) = event.channel else {
+            continue;
+        };
+        let state = states.entry(channel).or_default();
+        if state.last_time != event.timestamp {
+            state.seen_msgids.clear();
+            state.last_time = event.timestamp;
+        }
+        if let Some(id) = event.msgid {
+            state.seen_msgids.insert(id);
+        }
+    }
+    Ok(states)
+}
+
+/// The `server-time` capability, requested so the server tags messages with
+/// their actual occurrence time instead of leaving event timestamps to
+/// depend on when this bot happened to receive them.
+fn server_time_cap() -> Capability {
+    "server-time"
+        .parse()
+        .expect(r#""server-time" should be a valid Capability"#)
+}
+
+/// The `draft/chathistory` capability, requested so `CHATHISTORY` requests
+/// are meaningful; see [`ChatHistory`].
+fn chathistory_cap() -> Capability {
+    "draft/chathistory"
+        .parse()
+        .expect(r#""draft/chathistory" should be a valid Capability"#)
+}
+
+/// Applies "equal jitter" to `delay` (half the delay, plus a random amount
+/// up to the other half), so that a long-running archiver reconnecting
+/// after a server-wide netsplit doesn't retry in lockstep with every other
+/// client.  Uses [`std::collections::hash_map::RandomState`]'s
+/// per-process-random seed as a source of entropy rather than pulling in a
+/// `rand` crate dependency.
+fn jittered(delay: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+    let r = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    #[expect(clippy::cast_precision_loss)]
+    let frac = (r as f64) / (u64::MAX as f64);
+    delay.mul_f64(0.5 + frac * 0.5)
+}
+
+/// Runs `cmd` through the user's shell and returns its trimmed stdout, for
+/// pulling a password out of `pass`, `gpg`, a keyring helper, or a cloud
+/// secrets CLI instead of storing it in the config file.
+fn run_password_command(cmd: &str) -> anyhow::Result<String> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .output()
+        .context("failed to spawn command")?;
+    if !output.status.success() {
+        anyhow::bail!("command exited with {}", output.status);
     }
+    let stdout = String::from_utf8(output.stdout).context("command output is not valid UTF-8")?;
+    Ok(stdout.trim().to_owned())
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
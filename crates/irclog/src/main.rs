@@ -108,6 +108,9 @@ async fn irc(profile: Profile, sender: mpsc::Sender<Event>) -> anyhow::Result<()
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
@@ -144,10 +147,10 @@ async fn irc(profile: Profile, sender: mpsc::Sender<Event>) -> anyhow::Result<()
         select! {
             r = client.recv() => {
                 match r {
-                    Ok(Some(Message {source, payload: Payload::ClientMessage(msg)})) => {
+                    Ok(Some(Message {source, payload: Payload::ClientMessage(msg), ..})) => {
                         sender.send(Event::Message {timestamp: Zoned::now(), source, msg}).await?;
                     }
-                    Ok(Some(Message {source, payload: Payload::Reply(reply)})) => {
+                    Ok(Some(Message {source, payload: Payload::Reply(reply), ..})) => {
                         sender.send(Event::Reply {timestamp: Zoned::now(), source, reply}).await?;
                     }
                     Ok(None) => {
@@ -0,0 +1,152 @@
+//! A [`ReplyAggregator`] folds the start/row/end numeric bursts covered by
+//! this module -- `RPL_MOTDSTART`/`RPL_MOTD`/`RPL_ENDOFMOTD`,
+//! `RPL_INFO`/`RPL_ENDOFINFO`, `RPL_BANLIST`/`RPL_ENDOFBANLIST`, and
+//! `RPL_LINKS`/`RPL_ENDOFLINKS` -- into single composed values, so a client
+//! read loop can feed it every parsed [`Reply`] and work with a whole MOTD,
+//! INFO text, ban list, or link map at a time instead of reassembling each
+//! line by hand.
+//!
+//! Each burst is tracked separately by its own key (the replying server's
+//! [`ReplyTarget`] for MOTD/INFO/LINKS, the [`Channel`] for BAN LIST), so
+//! interleaved bursts for different targets don't interfere with each
+//! other. A terminator with no matching opener still yields an `Aggregate`
+//! -- possibly with empty contents -- rather than being dropped, and an
+//! opener that arrives while a prior burst for the same key is still open
+//! silently discards the stale buffer in favor of the new one.
+
+use irctext::replies::{
+    BanList, EndOfBanList, EndOfInfo, EndOfLinks, EndOfMotd, Info, Links, Motd, MotdStart, Reply,
+};
+use irctext::types::{Channel, ReplyTarget};
+use std::collections::HashMap;
+
+/// One row of a folded `RPL_BANLIST`/`RPL_ENDOFBANLIST` burst.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BanListEntry {
+    pub mask: String,
+    pub who: Option<String>,
+    pub set_ts: Option<u64>,
+}
+
+/// One row of a folded `RPL_LINKS`/`RPL_ENDOFLINKS` burst.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinksEntry {
+    pub server1: String,
+    pub server2: String,
+    pub hopcount: u32,
+    pub server_info: String,
+}
+
+/// A burst of replies folded into a single composed value by
+/// [`ReplyAggregator::push`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Aggregate {
+    Motd { client: ReplyTarget, lines: Vec<String> },
+    Info { client: ReplyTarget, lines: Vec<String> },
+    BanList { channel: Channel, entries: Vec<BanListEntry> },
+    Links { client: ReplyTarget, entries: Vec<LinksEntry> },
+}
+
+/// Accumulates in-flight bursts keyed by `ReplyTarget`'s string form, since
+/// `ReplyTarget` itself isn't `Hash`.
+type MotdBuf = HashMap<String, (ReplyTarget, Vec<String>)>;
+type LinksBuf = HashMap<String, (ReplyTarget, Vec<LinksEntry>)>;
+
+#[derive(Clone, Debug, Default)]
+pub struct ReplyAggregator {
+    motd: MotdBuf,
+    info: MotdBuf,
+    banlist: HashMap<Channel, Vec<BanListEntry>>,
+    links: LinksBuf,
+}
+
+impl ReplyAggregator {
+    pub fn new() -> ReplyAggregator {
+        ReplyAggregator::default()
+    }
+
+    /// Feeds one reply into the aggregator. Returns `Some(Aggregate)` once
+    /// a terminating numeric closes out a burst; every other reply
+    /// (including ones this module doesn't track) returns `None`.
+    pub fn push(&mut self, reply: &Reply) -> Option<Aggregate> {
+        match reply {
+            Reply::MotdStart(r) => {
+                let key = r.client().to_string();
+                self.motd.insert(key, (r.client().clone(), Vec::new()));
+                None
+            }
+            Reply::Motd(r) => {
+                let key = r.client().to_string();
+                self.motd
+                    .entry(key)
+                    .or_insert_with(|| (r.client().clone(), Vec::new()))
+                    .1
+                    .push(r.message().to_owned());
+                None
+            }
+            Reply::EndOfMotd(r) => {
+                let (client, lines) = self
+                    .motd
+                    .remove(&r.client().to_string())
+                    .unwrap_or_else(|| (r.client().clone(), Vec::new()));
+                Some(Aggregate::Motd { client, lines })
+            }
+            Reply::Info(r) => {
+                let key = r.client().to_string();
+                self.info
+                    .entry(key)
+                    .or_insert_with(|| (r.client().clone(), Vec::new()))
+                    .1
+                    .push(r.message().to_owned());
+                None
+            }
+            Reply::EndOfInfo(r) => {
+                let (client, lines) = self
+                    .info
+                    .remove(&r.client().to_string())
+                    .unwrap_or_else(|| (r.client().clone(), Vec::new()));
+                Some(Aggregate::Info { client, lines })
+            }
+            Reply::BanList(r) => {
+                self.banlist
+                    .entry(r.channel().clone())
+                    .or_default()
+                    .push(BanListEntry {
+                        mask: r.mask().to_owned(),
+                        who: r.who().map(str::to_owned),
+                        set_ts: r.set_ts(),
+                    });
+                None
+            }
+            Reply::EndOfBanList(r) => {
+                let entries = self.banlist.remove(r.channel()).unwrap_or_default();
+                Some(Aggregate::BanList {
+                    channel: r.channel().clone(),
+                    entries,
+                })
+            }
+            Reply::Links(r) => {
+                let key = r.client().to_string();
+                self.links
+                    .entry(key)
+                    .or_insert_with(|| (r.client().clone(), Vec::new()))
+                    .1
+                    .push(LinksEntry {
+                        server1: r.server1().to_owned(),
+                        server2: r.server2().to_owned(),
+                        hopcount: r.hopcount(),
+                        server_info: r.server_info().to_owned(),
+                    });
+                None
+            }
+            Reply::EndOfLinks(r) => {
+                let (client, entries) = self
+                    .links
+                    .remove(&r.client().to_string())
+                    .unwrap_or_else(|| (r.client().clone(), Vec::new()));
+                Some(Aggregate::Links { client, entries })
+            }
+            _ => None,
+        }
+    }
+}
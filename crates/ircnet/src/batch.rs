@@ -0,0 +1,124 @@
+//! Reassembly of IRCv3 `BATCH`-grouped messages, per
+//! <https://ircv3.net/specs/extensions/batch>.
+//!
+//! A batch is opened by a `BATCH +<reference-tag> <type> [params...]` line
+//! and closed by a matching `BATCH -<reference-tag>` line; every message in
+//! between carries a `batch=<reference-tag>` tag.  [`BatchReassembler`]
+//! consumes a stream of [`Message`]s one at a time and, once a batch's close
+//! line is seen, yields the batch type and the messages it contained.
+//! Messages that are not part of any batch are passed through unchanged.
+use irctext::clientmsgs::Batch;
+use irctext::{ClientMessage, Message, Payload};
+use std::collections::HashMap;
+
+/// A fully reassembled batch: the type given in its opening `BATCH` line,
+/// plus every tagged message received between the open and close lines (the
+/// `BATCH` lines themselves are not included).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchUnit {
+    pub batch_type: String,
+    pub messages: Vec<Message>,
+}
+
+/// The result of feeding a single [`Message`] to a [`BatchReassembler`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Reassembled {
+    /// A message that was not part of any batch.
+    Passthrough(Message),
+    /// A batch that has just been closed.
+    Batch(BatchUnit),
+}
+
+/// Incrementally reassembles `BATCH`-tagged messages.  Nested batches are
+/// not supported: a `BATCH` line received while a batch with the same
+/// reference tag is already open replaces the earlier one.
+#[derive(Clone, Debug, Default)]
+pub struct BatchReassembler {
+    open: HashMap<String, BatchUnit>,
+}
+
+impl BatchReassembler {
+    pub fn new() -> BatchReassembler {
+        BatchReassembler::default()
+    }
+
+    /// Feed a single incoming message through the reassembler.  Returns
+    /// `None` if the message was a `BATCH` open line or was absorbed into a
+    /// still-open batch.
+    pub fn feed(&mut self, msg: Message) -> Option<Reassembled> {
+        if let Payload::ClientMessage(ClientMessage::Batch(batch)) = &msg.payload {
+            return self.handle_batch_line(batch);
+        }
+        if let Some(reference) = batch_tag(&msg) {
+            if let Some(open) = self.open.get_mut(reference) {
+                open.messages.push(msg);
+                return None;
+            }
+        }
+        Some(Reassembled::Passthrough(msg))
+    }
+
+    fn handle_batch_line(&mut self, batch: &Batch) -> Option<Reassembled> {
+        match batch {
+            Batch::Start(start) => {
+                self.open.insert(
+                    start.reference_tag().as_str().to_string(),
+                    BatchUnit {
+                        batch_type: start.batch_type().as_str().to_string(),
+                        messages: Vec::new(),
+                    },
+                );
+                None
+            }
+            Batch::End(end) => self
+                .open
+                .remove(end.reference_tag().as_str())
+                .map(Reassembled::Batch),
+        }
+    }
+}
+
+fn batch_tag(msg: &Message) -> Option<&str> {
+    msg.tags.as_ref()?.batch()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passthrough_untagged_message() {
+        let mut r = BatchReassembler::new();
+        let msg: Message = "PING :hello".parse().unwrap();
+        assert_eq!(
+            r.feed(msg.clone()),
+            Some(Reassembled::Passthrough(msg))
+        );
+    }
+
+    #[test]
+    fn test_simple_batch() {
+        let mut r = BatchReassembler::new();
+        assert_eq!(
+            r.feed("BATCH +123 chathistory #foo".parse().unwrap()),
+            None
+        );
+        let line1: Message = "@batch=123 :nick!u@h PRIVMSG #foo :hi".parse().unwrap();
+        let line2: Message = "@batch=123 :nick!u@h PRIVMSG #foo :there".parse().unwrap();
+        assert_eq!(r.feed(line1.clone()), None);
+        assert_eq!(r.feed(line2.clone()), None);
+        assert_eq!(
+            r.feed("BATCH -123".parse().unwrap()),
+            Some(Reassembled::Batch(BatchUnit {
+                batch_type: "chathistory".to_string(),
+                messages: vec![line1, line2],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_close_without_open_is_dropped() {
+        let mut r = BatchReassembler::new();
+        assert_eq!(r.feed("BATCH -999".parse().unwrap()), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! A [`Decoder`]/[`Encoder`] pair that archives full [`Message`]s as
+//! length-prefixed MessagePack frames instead of IRC lines.
+//!
+//! Unlike [`MessageCodec`](crate::codecs::message::MessageCodec), this
+//! codec is not meant to talk to a server: it gives a recorded session a
+//! compact, lossless on-disk representation (e.g. for `irclog`-style replay
+//! tooling) that is decoupled from wire syntax, so messages that would be
+//! ambiguous to re-lex from text round-trip exactly.
+use bytes::{Buf, BufMut, BytesMut};
+use irctext::Message;
+use std::io;
+use thiserror::Error;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Size, in bytes, of the big-endian length prefix in front of each frame.
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// Default cap, in bytes, on a single encoded [`Message`] frame.  (Unlike
+/// this codec, [`LinesCodec::new`](crate::connect::codecs::lines::LinesCodec::new)
+/// imposes no length limit by default.)
+const DEFAULT_MAX_FRAME_LENGTH: usize = 65536;
+
+/// A codec that reads and writes [`Message`]s as `u32`-length-prefixed
+/// MessagePack frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryMessageCodec {
+    max_frame_length: usize,
+}
+
+impl BinaryMessageCodec {
+    pub fn new() -> BinaryMessageCodec {
+        BinaryMessageCodec {
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+        }
+    }
+
+    pub fn new_with_max_frame_length(max_frame_length: usize) -> BinaryMessageCodec {
+        BinaryMessageCodec { max_frame_length }
+    }
+}
+
+impl Default for BinaryMessageCodec {
+    fn default() -> BinaryMessageCodec {
+        BinaryMessageCodec::new()
+    }
+}
+
+impl Decoder for BinaryMessageCodec {
+    type Item = Message;
+    type Error = BinaryMessageCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Message>, BinaryMessageCodecError> {
+        if buf.len() < LENGTH_PREFIX_SIZE {
+            return Ok(None);
+        }
+        let length = u32::from_be_bytes(buf[..LENGTH_PREFIX_SIZE].try_into().expect(
+            "slice of length LENGTH_PREFIX_SIZE should convert to an array of the same length",
+        )) as usize;
+        if length > self.max_frame_length {
+            return Err(BinaryMessageCodecError::MaxFrameLengthExceeded(
+                length,
+                self.max_frame_length,
+            ));
+        }
+        if buf.len() < LENGTH_PREFIX_SIZE + length {
+            buf.reserve(LENGTH_PREFIX_SIZE + length - buf.len());
+            return Ok(None);
+        }
+        buf.advance(LENGTH_PREFIX_SIZE);
+        let frame = buf.split_to(length);
+        let msg = rmp_serde::from_slice(&frame)?;
+        Ok(Some(msg))
+    }
+
+    fn decode_eof(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<Option<Message>, BinaryMessageCodecError> {
+        match self.decode(buf)? {
+            Some(msg) => Ok(Some(msg)),
+            None if buf.is_empty() => Ok(None),
+            None => Err(BinaryMessageCodecError::UnexpectedEof(buf.len())),
+        }
+    }
+}
+
+impl Encoder<Message> for BinaryMessageCodec {
+    type Error = BinaryMessageCodecError;
+
+    fn encode(&mut self, msg: Message, buf: &mut BytesMut) -> Result<(), BinaryMessageCodecError> {
+        let frame = rmp_serde::to_vec(&msg)?;
+        if frame.len() > self.max_frame_length {
+            return Err(BinaryMessageCodecError::MaxFrameLengthExceeded(
+                frame.len(),
+                self.max_frame_length,
+            ));
+        }
+        let length = u32::try_from(frame.len()).map_err(|_| {
+            BinaryMessageCodecError::MaxFrameLengthExceeded(frame.len(), self.max_frame_length)
+        })?;
+        buf.reserve(LENGTH_PREFIX_SIZE + frame.len());
+        buf.put_u32(length);
+        buf.put_slice(&frame);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BinaryMessageCodecError {
+    #[error("frame length {0} exceeds maximum of {1}")]
+    MaxFrameLengthExceeded(usize, usize),
+
+    #[error("unexpected EOF with {0} trailing byte(s) in buffer")]
+    UnexpectedEof(usize),
+
+    #[error("I/O error reading/writing archive")]
+    Io(#[from] io::Error),
+
+    #[error("failed to encode message as MessagePack")]
+    Encode(#[from] rmp_serde::encode::Error),
+
+    #[error("failed to decode MessagePack frame")]
+    Decode(#[from] rmp_serde::decode::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_message() -> Message {
+        "@msgid=123 :jess!~user@localhost PRIVMSG #ircbits :hello there"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let msg = sample_message();
+        let mut codec = BinaryMessageCodec::new();
+        let mut buf = BytesMut::new();
+        codec.encode(msg.clone(), &mut buf).unwrap();
+        let decoded = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(decoded, msg);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn incomplete_frame_returns_none() {
+        let mut codec = BinaryMessageCodec::new();
+        let mut buf = BytesMut::new();
+        codec.encode(sample_message(), &mut buf).unwrap();
+        buf.truncate(buf.len() - 1);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn oversized_frame_is_rejected() {
+        let mut codec = BinaryMessageCodec::new_with_max_frame_length(4);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode(sample_message(), &mut buf),
+            Err(BinaryMessageCodecError::MaxFrameLengthExceeded(_, 4))
+        ));
+    }
+}
@@ -0,0 +1,708 @@
+//! A state machine driving the IRCv3 capability negotiation handshake, per
+//! <https://ircv3.net/specs/extensions/capability-negotiation.html>.
+//!
+//! [`CapNegotiator`] owns the whole LS → REQ → ACK/NAK → END exchange: the
+//! caller gives it the set of capabilities it wants, feeds it every incoming
+//! `CAP` message, and after each one calls [`CapNegotiator::get_output`] to
+//! learn what (if anything) to send back. Negotiation is considered over
+//! once [`CapNegotiator::is_done`] returns `true`, which happens right after
+//! the `CAP END` message has been queued.
+//!
+//! [`CapLsBuffer`] and [`CapListBuffer`] separately handle reassembling a
+//! `CAP LS`/`CAP LIST` response that's been split across multiple `* `
+//! continuation fragments, for callers that would rather work with a single
+//! merged capability list than deal with the continuation flag themselves.
+use irctext::clientmsgs::{
+    Cap, CapEnd, CapLsRequest, CapReq, Capability, CapabilityRequest, CapabilityValue,
+};
+use irctext::types::ReplyTarget;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Negotiates IRCv3 capabilities with a server.
+///
+/// Construction immediately queues the opening `CAP LS 302`; call
+/// [`get_output`](Self::get_output) to retrieve it. From then on, feed every
+/// `CAP` message received from the server to [`handle_message`](Self::handle_message)
+/// and call [`get_output`](Self::get_output) afterwards to collect the
+/// negotiator's response, if any.
+#[derive(Clone, Debug)]
+pub struct CapNegotiator {
+    wanted: HashSet<Capability>,
+    available: HashMap<Capability, Option<CapabilityValue>>,
+    enabled: HashSet<Capability>,
+    pending: HashSet<Capability>,
+    outgoing: Vec<Cap>,
+    req_sent: bool,
+    ended: bool,
+}
+
+impl CapNegotiator {
+    /// Starts a negotiation that will request whichever of `wanted` turn out
+    /// to be advertised by the server.
+    pub fn new<I: IntoIterator<Item = Capability>>(wanted: I) -> CapNegotiator {
+        CapNegotiator {
+            wanted: wanted.into_iter().collect(),
+            available: HashMap::new(),
+            enabled: HashSet::new(),
+            pending: HashSet::new(),
+            outgoing: vec![Cap::from(CapLsRequest::new_with_version(302))],
+            req_sent: false,
+            ended: false,
+        }
+    }
+
+    /// Returns every capability advertised by the server so far, along with
+    /// its value, if any. Populated by `CAP LS` and kept up to date by
+    /// `CAP NEW`/`CAP DEL`.
+    pub fn available(&self) -> &HashMap<Capability, Option<CapabilityValue>> {
+        &self.available
+    }
+
+    /// Returns the capabilities that are currently enabled, as acknowledged
+    /// by `CAP ACK` (and kept up to date by `CAP DEL`).
+    pub fn enabled(&self) -> &HashSet<Capability> {
+        &self.enabled
+    }
+
+    /// Tests whether `capability` is currently enabled.
+    pub fn is_enabled(&self, capability: &Capability) -> bool {
+        self.enabled.contains(capability)
+    }
+
+    /// Queues a `CAP REQ` for whichever of `capabilities` the server has
+    /// advertised (via `CAP LS`/`CAP NEW`) and that aren't already enabled or
+    /// awaiting an `ACK`/`NAK`, skipping the rest. Intended for requesting
+    /// additional capabilities — e.g. ones newly advertised by a `CAP NEW`
+    /// — after the initial handshake has already ended; the request is
+    /// resolved the same way as the initial `CAP REQ`, via
+    /// [`handle_message`](Self::handle_message) on the server's `CAP
+    /// ACK`/`CAP NAK` reply.
+    ///
+    /// Returns the `CapReq` that was queued, or an empty `Vec` if none of
+    /// `capabilities` turned out to need requesting — either because the
+    /// server hasn't advertised them, or because they're already enabled or
+    /// already pending from an earlier request.
+    pub fn request_all(&mut self, capabilities: &[Capability]) -> Vec<CapReq> {
+        let requests = self.requestable(capabilities.iter().cloned());
+        if requests.is_empty() {
+            return Vec::new();
+        }
+        self.pending
+            .extend(requests.iter().map(|req| req.capability.clone()));
+        let req = CapReq {
+            capabilities: requests,
+        };
+        self.outgoing.push(Cap::from(req.clone()));
+        vec![req]
+    }
+
+    /// Returns `true` once `CAP END` has been queued and negotiation is
+    /// complete. [`handle_message`](Self::handle_message) may still be
+    /// called afterwards to keep [`available`](Self::available) and
+    /// [`enabled`](Self::enabled) up to date as `CAP NEW`/`CAP DEL` messages
+    /// arrive mid-session.
+    pub fn is_done(&self) -> bool {
+        self.ended
+    }
+
+    /// Drains and returns the `CAP` messages that should be sent to the
+    /// server since the last call.
+    pub fn get_output(&mut self) -> Vec<Cap> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    /// Feeds an incoming `CAP` message to the negotiator, updating its
+    /// tracked state and queuing any reply for the next
+    /// [`get_output`](Self::get_output) call.
+    pub fn handle_message(&mut self, cap: Cap) {
+        match cap {
+            Cap::LsResponse(resp) => {
+                self.available.extend(resp.capabilities);
+                if !resp.continued {
+                    self.send_req();
+                }
+            }
+            Cap::Ack(ack) => {
+                for req in ack.capabilities {
+                    self.pending.remove(&req.capability);
+                    if req.disable {
+                        self.enabled.remove(&req.capability);
+                    } else {
+                        self.enabled.insert(req.capability);
+                    }
+                }
+                self.maybe_end();
+            }
+            Cap::Nak(nak) => {
+                for capability in nak.capabilities {
+                    self.pending.remove(&capability);
+                }
+                self.maybe_end();
+            }
+            Cap::New(new) => {
+                for capability in new.capabilities {
+                    self.available.entry(capability).or_insert(None);
+                }
+            }
+            Cap::Del(del) => {
+                for capability in del.capabilities {
+                    self.available.remove(&capability);
+                    self.enabled.remove(&capability);
+                }
+            }
+            Cap::LsRequest(_)
+            | Cap::ListRequest(_)
+            | Cap::ListResponse(_)
+            | Cap::Req(_)
+            | Cap::End(_) => {}
+        }
+    }
+
+    /// Computes the intersection of `wanted` with whatever's been advertised
+    /// so far and queues a `CAP REQ` for it, or, if nothing overlaps, skips
+    /// straight to `CAP END`.
+    fn send_req(&mut self) {
+        let requests = self.requestable(self.wanted.clone());
+        self.req_sent = true;
+        if !requests.is_empty() {
+            self.pending
+                .extend(requests.iter().map(|req| req.capability.clone()));
+            self.outgoing.push(Cap::from(CapReq {
+                capabilities: requests,
+            }));
+        }
+        // `pending` may already be non-empty here from a request_all() call
+        // made while the LS response was still being accumulated, so don't
+        // end unconditionally — let maybe_end() decide.
+        self.maybe_end();
+    }
+
+    /// Filters `capabilities` down to those the server has advertised and
+    /// that aren't already enabled or awaiting an `ACK`/`NAK`, deduplicating
+    /// along the way, and turns the result into enable-requests for a
+    /// `CAP REQ`.
+    fn requestable<I: IntoIterator<Item = Capability>>(
+        &self,
+        capabilities: I,
+    ) -> Vec<CapabilityRequest> {
+        capabilities
+            .into_iter()
+            .filter(|capability| {
+                self.available.contains_key(capability)
+                    && !self.pending.contains(capability)
+                    && !self.enabled.contains(capability)
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(CapabilityRequest::enable)
+            .collect()
+    }
+
+    /// Ends negotiation once every capability from the `CAP REQ` we sent has
+    /// been resolved by an ACK or NAK. Guarded on `req_sent` so a stray
+    /// ACK/NAK arriving before `send_req` has run (e.g. out of order, or
+    /// from a misbehaving server) can't be mistaken for an already-empty
+    /// `pending` set and end negotiation before it's begun.
+    fn maybe_end(&mut self) {
+        if self.req_sent && self.pending.is_empty() {
+            self.end();
+        }
+    }
+
+    fn end(&mut self) {
+        if !self.ended {
+            self.outgoing.push(Cap::from(CapEnd));
+            self.ended = true;
+        }
+    }
+}
+
+/// Accumulates a run of continuation fragments (as marked by the `*`
+/// parameter on `CAP LS`/`CAP LIST` responses) sharing a common
+/// [`ReplyTarget`], yielding the merged items once the terminal,
+/// non-continued fragment arrives.
+#[derive(Clone, Debug)]
+struct ContinuationBuffer<T> {
+    pending: Option<(ReplyTarget, Vec<T>)>,
+}
+
+impl<T> Default for ContinuationBuffer<T> {
+    fn default() -> ContinuationBuffer<T> {
+        ContinuationBuffer { pending: None }
+    }
+}
+
+impl<T> ContinuationBuffer<T> {
+    fn feed(
+        &mut self,
+        target: ReplyTarget,
+        continued: bool,
+        mut items: Vec<T>,
+    ) -> Result<Option<Vec<T>>, CapBufferError> {
+        let acc = match self.pending.take() {
+            Some((expected, mut acc)) if expected == target => {
+                acc.append(&mut items);
+                acc
+            }
+            Some((expected, _)) => {
+                return Err(CapBufferError::TargetMismatch {
+                    expected,
+                    got: target,
+                })
+            }
+            None => items,
+        };
+        if continued {
+            self.pending = Some((target, acc));
+            Ok(None)
+        } else {
+            Ok(Some(acc))
+        }
+    }
+
+    /// Called when a message arrives that isn't a continuation fragment of
+    /// the kind this buffer reassembles. Returns an error if a continuation
+    /// was left open, since that means its terminal fragment will now never
+    /// arrive.
+    fn abandon(&mut self) -> Result<(), CapBufferError> {
+        if self.pending.take().is_some() {
+            Err(CapBufferError::AbandonedContinuation)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Reassembles a `CAP LS` advertisement that's been split across multiple
+/// `CAP * LS * ...` continuation fragments into a single capability list.
+///
+/// Feed every incoming [`Cap`] message to [`feed`](Self::feed). A message
+/// other than `CAP LS` is treated as ending any in-progress continuation
+/// without a terminal fragment, which is reported as an error.
+#[derive(Clone, Debug, Default)]
+pub struct CapLsBuffer {
+    inner: ContinuationBuffer<(Capability, Option<CapabilityValue>)>,
+}
+
+impl CapLsBuffer {
+    pub fn new() -> CapLsBuffer {
+        CapLsBuffer::default()
+    }
+
+    pub fn feed(
+        &mut self,
+        cap: Cap,
+    ) -> Result<Option<Vec<(Capability, Option<CapabilityValue>)>>, CapBufferError> {
+        match cap {
+            Cap::LsResponse(resp) => {
+                self.inner
+                    .feed(resp.target, resp.continued, resp.capabilities)
+            }
+            _ => self.inner.abandon().map(|()| None),
+        }
+    }
+}
+
+/// Reassembles a `CAP LIST` response that's been split across multiple
+/// `CAP * LIST * ...` continuation fragments into a single capability list.
+///
+/// Feed every incoming [`Cap`] message to [`feed`](Self::feed). A message
+/// other than `CAP LIST` is treated as ending any in-progress continuation
+/// without a terminal fragment, which is reported as an error.
+#[derive(Clone, Debug, Default)]
+pub struct CapListBuffer {
+    inner: ContinuationBuffer<Capability>,
+}
+
+impl CapListBuffer {
+    pub fn new() -> CapListBuffer {
+        CapListBuffer::default()
+    }
+
+    pub fn feed(&mut self, cap: Cap) -> Result<Option<Vec<Capability>>, CapBufferError> {
+        match cap {
+            Cap::ListResponse(resp) => {
+                self.inner
+                    .feed(resp.target, resp.continued, resp.capabilities)
+            }
+            _ => self.inner.abandon().map(|()| None),
+        }
+    }
+}
+
+/// Error returned by [`CapLsBuffer::feed`]/[`CapListBuffer::feed`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum CapBufferError {
+    #[error("continuation was open for target {expected}, but got a response for {got}")]
+    TargetMismatch {
+        expected: ReplyTarget,
+        got: ReplyTarget,
+    },
+    #[error("a CAP LS/LIST continuation was left unterminated by another CAP subcommand")]
+    AbandonedContinuation,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use irctext::clientmsgs::{CapAck, CapDel, CapLsResponse, CapNak, CapNew};
+    use irctext::types::ReplyTarget;
+
+    fn target() -> ReplyTarget {
+        ReplyTarget::try_from(String::from("*")).unwrap()
+    }
+
+    fn cap(s: &str) -> Capability {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn starts_with_ls_request() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        assert_eq!(
+            neg.get_output(),
+            [Cap::from(CapLsRequest::new_with_version(302))]
+        );
+        assert!(neg.get_output().is_empty());
+    }
+
+    #[test]
+    fn full_handshake() {
+        let mut neg = CapNegotiator::new([cap("sasl"), cap("batch")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![
+                (cap("sasl"), Some("PLAIN".parse().unwrap())),
+                (cap("multi-prefix"), None),
+            ],
+        }));
+        assert_eq!(
+            neg.available().get(&cap("sasl")),
+            Some(&Some("PLAIN".parse().unwrap()))
+        );
+        let out = neg.get_output();
+        assert_eq!(
+            out,
+            [Cap::from(CapReq {
+                capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+            })]
+        );
+        assert!(!neg.is_done());
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+        }));
+        assert_eq!(neg.get_output(), [Cap::from(CapEnd)]);
+        assert!(neg.is_done());
+        assert!(neg.enabled().contains(&cap("sasl")));
+    }
+
+    #[test]
+    fn nothing_wanted_is_available_skips_req() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("batch"), None)],
+        }));
+        assert_eq!(neg.get_output(), [Cap::from(CapEnd)]);
+        assert!(neg.is_done());
+    }
+
+    #[test]
+    fn multipart_ls_response_is_accumulated() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: true,
+            capabilities: vec![(cap("batch"), None)],
+        }));
+        assert!(neg.get_output().is_empty());
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None)],
+        }));
+        assert_eq!(
+            neg.get_output(),
+            [Cap::from(CapReq {
+                capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+            })]
+        );
+    }
+
+    #[test]
+    fn nak_is_not_enabled() {
+        let mut neg = CapNegotiator::new([cap("sasl"), cap("batch")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None), (cap("batch"), None)],
+        }));
+        neg.get_output();
+        neg.handle_message(Cap::from(CapNak {
+            target: target(),
+            capabilities: vec![cap("sasl")],
+        }));
+        assert!(neg.get_output().is_empty());
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("batch"))],
+        }));
+        assert_eq!(neg.get_output(), [Cap::from(CapEnd)]);
+        assert!(neg.enabled().contains(&cap("batch")));
+        assert!(!neg.enabled().contains(&cap("sasl")));
+    }
+
+    #[test]
+    fn new_and_del_update_tracked_state() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None)],
+        }));
+        neg.get_output();
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+        }));
+        neg.get_output();
+        assert!(neg.is_done());
+
+        neg.handle_message(Cap::from(CapNew {
+            target: target(),
+            capabilities: vec![cap("batch")],
+        }));
+        assert!(neg.available().contains_key(&cap("batch")));
+
+        neg.handle_message(Cap::from(CapDel {
+            target: target(),
+            capabilities: vec![cap("sasl")],
+        }));
+        assert!(!neg.available().contains_key(&cap("sasl")));
+        assert!(!neg.enabled().contains(&cap("sasl")));
+    }
+
+    #[test]
+    fn is_enabled_reflects_enabled_set() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None)],
+        }));
+        neg.get_output();
+        assert!(!neg.is_enabled(&cap("sasl")));
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+        }));
+        assert!(neg.is_enabled(&cap("sasl")));
+    }
+
+    #[test]
+    fn request_all_requests_only_advertised_capabilities() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None)],
+        }));
+        neg.get_output();
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+        }));
+        neg.get_output();
+        assert!(neg.is_done());
+
+        neg.handle_message(Cap::from(CapNew {
+            target: target(),
+            capabilities: vec![cap("batch")],
+        }));
+        let req = neg.request_all(&[cap("batch"), cap("away-notify")]);
+        assert_eq!(
+            req,
+            [CapReq {
+                capabilities: vec![CapabilityRequest::enable(cap("batch"))],
+            }]
+        );
+        assert_eq!(
+            neg.get_output(),
+            [Cap::from(CapReq {
+                capabilities: vec![CapabilityRequest::enable(cap("batch"))],
+            })]
+        );
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("batch"))],
+        }));
+        assert!(neg.is_enabled(&cap("batch")));
+        // Already-ended negotiation shouldn't requeue a second CAP END.
+        assert!(neg.get_output().is_empty());
+    }
+
+    #[test]
+    fn request_all_with_nothing_advertised_is_empty() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        assert_eq!(neg.request_all(&[cap("batch")]), []);
+        assert!(neg.get_output().is_empty());
+    }
+
+    #[test]
+    fn request_all_skips_already_enabled_capabilities() {
+        let mut neg = CapNegotiator::new([cap("sasl")]);
+        neg.get_output();
+        neg.handle_message(Cap::from(CapLsResponse {
+            target: target(),
+            continued: false,
+            capabilities: vec![(cap("sasl"), None)],
+        }));
+        neg.get_output();
+        neg.handle_message(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("sasl"))],
+        }));
+        neg.get_output();
+        assert!(neg.is_enabled(&cap("sasl")));
+
+        assert_eq!(neg.request_all(&[cap("sasl")]), []);
+        assert!(neg.get_output().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod buffer_tests {
+    use super::*;
+    use irctext::clientmsgs::{CapAck, CapListResponse, CapLsResponse};
+
+    fn target() -> ReplyTarget {
+        ReplyTarget::try_from(String::from("*")).unwrap()
+    }
+
+    fn cap(s: &str) -> Capability {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ls_single_fragment() {
+        let mut buf = CapLsBuffer::new();
+        let out = buf
+            .feed(Cap::from(CapLsResponse {
+                target: target(),
+                continued: false,
+                capabilities: vec![(cap("sasl"), None)],
+            }))
+            .unwrap();
+        assert_eq!(out, Some(vec![(cap("sasl"), None)]));
+    }
+
+    #[test]
+    fn ls_multiple_fragments_are_merged() {
+        let mut buf = CapLsBuffer::new();
+        let out = buf
+            .feed(Cap::from(CapLsResponse {
+                target: target(),
+                continued: true,
+                capabilities: vec![(cap("sasl"), None)],
+            }))
+            .unwrap();
+        assert_eq!(out, None);
+        let out = buf
+            .feed(Cap::from(CapLsResponse {
+                target: target(),
+                continued: false,
+                capabilities: vec![(cap("batch"), None)],
+            }))
+            .unwrap();
+        assert_eq!(out, Some(vec![(cap("sasl"), None), (cap("batch"), None)]));
+    }
+
+    #[test]
+    fn ls_mismatched_target_is_an_error() {
+        let mut buf = CapLsBuffer::new();
+        buf.feed(Cap::from(CapLsResponse {
+            target: target(),
+            continued: true,
+            capabilities: vec![(cap("sasl"), None)],
+        }))
+        .unwrap();
+        let other = ReplyTarget::try_from(String::from("modernclient")).unwrap();
+        let err = buf
+            .feed(Cap::from(CapLsResponse {
+                target: other.clone(),
+                continued: false,
+                capabilities: vec![(cap("batch"), None)],
+            }))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CapBufferError::TargetMismatch {
+                expected: target(),
+                got: other,
+            }
+        );
+    }
+
+    #[test]
+    fn ls_abandoned_continuation_is_an_error() {
+        let mut buf = CapLsBuffer::new();
+        buf.feed(Cap::from(CapLsResponse {
+            target: target(),
+            continued: true,
+            capabilities: vec![(cap("sasl"), None)],
+        }))
+        .unwrap();
+        let err = buf
+            .feed(Cap::from(CapAck {
+                target: target(),
+                capabilities: vec![],
+            }))
+            .unwrap_err();
+        assert_eq!(err, CapBufferError::AbandonedContinuation);
+    }
+
+    #[test]
+    fn unrelated_message_with_no_pending_continuation_is_ignored() {
+        let mut buf = CapLsBuffer::new();
+        let out = buf
+            .feed(Cap::from(CapAck {
+                target: target(),
+                capabilities: vec![],
+            }))
+            .unwrap();
+        assert_eq!(out, None);
+    }
+
+    #[test]
+    fn list_multiple_fragments_are_merged() {
+        let mut buf = CapListBuffer::new();
+        let out = buf
+            .feed(Cap::from(CapListResponse {
+                target: target(),
+                continued: true,
+                capabilities: vec![cap("sasl")],
+            }))
+            .unwrap();
+        assert_eq!(out, None);
+        let out = buf
+            .feed(Cap::from(CapListResponse {
+                target: target(),
+                continued: false,
+                capabilities: vec![cap("batch")],
+            }))
+            .unwrap();
+        assert_eq!(out, Some(vec![cap("sasl"), cap("batch")]));
+    }
+}
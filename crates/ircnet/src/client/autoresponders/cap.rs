@@ -0,0 +1,131 @@
+use super::AutoResponder;
+use crate::cap::CapNegotiator;
+use irctext::{
+    ClientMessage, Message, Payload,
+    clientmsgs::{Cap, Capability},
+};
+
+/// Requests capabilities advertised via `CAP NEW` after registration has
+/// already completed.
+///
+/// The initial `CAP LS`/`CAP REQ`/`CAP END` handshake (including SASL
+/// authentication) happens during registration and is already handled by
+/// [`Login`](crate::client::commands::Login); `CapResponder` instead picks up
+/// where that leaves off, watching for `CAP NEW` advertisements of whichever
+/// capabilities the caller is interested in and requesting them automatically,
+/// using the same [`CapNegotiator`] state machine that drives registration.
+#[derive(Clone, Debug)]
+pub struct CapResponder {
+    negotiator: CapNegotiator,
+    wanted: Vec<Capability>,
+}
+
+impl CapResponder {
+    /// Watches for any of `wanted` being newly advertised via `CAP NEW` and
+    /// requests them automatically.
+    pub fn new<I: IntoIterator<Item = Capability>>(wanted: I) -> CapResponder {
+        let mut negotiator = CapNegotiator::new(std::iter::empty());
+        // Discard the `CAP LS` that `CapNegotiator::new()` always queues;
+        // it's of no use here, since negotiation has already happened by the
+        // time this autoresponder is installed.
+        negotiator.get_output();
+        CapResponder {
+            negotiator,
+            wanted: wanted.into_iter().collect(),
+        }
+    }
+
+    /// Tests whether `capability` has been enabled, either by this
+    /// `CapResponder` or (if fed its `CAP ACK` reply) by registration
+    pub fn is_enabled(&self, capability: &Capability) -> bool {
+        self.negotiator.is_enabled(capability)
+    }
+}
+
+impl AutoResponder for CapResponder {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
+        self.negotiator
+            .get_output()
+            .into_iter()
+            .map(Message::from)
+            .collect()
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if let Payload::ClientMessage(ClientMessage::Cap(cap)) = &msg.payload {
+            let is_new = matches!(cap, Cap::New(_));
+            self.negotiator.handle_message(cap.clone());
+            if is_new {
+                self.negotiator.request_all(&self.wanted);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use irctext::clientmsgs::{Cap, CapAck, CapNew, CapabilityRequest};
+    use irctext::types::ReplyTarget;
+
+    fn target() -> ReplyTarget {
+        ReplyTarget::try_from(String::from("*")).unwrap()
+    }
+
+    fn cap(s: &str) -> Capability {
+        s.parse().unwrap()
+    }
+
+    fn msg(c: Cap) -> Message {
+        Message::from(c)
+    }
+
+    #[test]
+    fn requests_newly_advertised_wanted_capability() {
+        let mut resp = CapResponder::new([cap("away-notify")]);
+        assert!(resp.get_outgoing_messages().is_empty());
+
+        assert!(resp.handle_message(&msg(Cap::from(CapNew {
+            target: target(),
+            capabilities: vec![cap("away-notify")],
+        }))));
+        assert_eq!(
+            resp.get_outgoing_messages()
+                .into_iter()
+                .map(|m| m.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["CAP REQ :away-notify"]
+        );
+
+        assert!(resp.handle_message(&msg(Cap::from(CapAck {
+            target: target(),
+            capabilities: vec![CapabilityRequest::enable(cap("away-notify"))],
+        }))));
+        assert!(resp.is_enabled(&cap("away-notify")));
+        assert!(!resp.is_done());
+    }
+
+    #[test]
+    fn ignores_unwanted_new_capability() {
+        let mut resp = CapResponder::new([cap("away-notify")]);
+        resp.handle_message(&msg(Cap::from(CapNew {
+            target: target(),
+            capabilities: vec![cap("batch")],
+        })));
+        assert!(resp.get_outgoing_messages().is_empty());
+    }
+
+    #[test]
+    fn non_cap_message_is_not_handled() {
+        let mut resp = CapResponder::new([cap("away-notify")]);
+        let ping = "PING :server".parse::<Message>().unwrap();
+        assert!(!resp.handle_message(&ping));
+    }
+}
@@ -0,0 +1,291 @@
+use super::AutoResponder;
+use crate::sasl::{SaslFlow, SaslMachine, SaslMechanism};
+use irctext::types::Nickname;
+use irctext::{
+    ClientMessage, Message, Payload, Reply, Verb,
+    clientmsgs::{Authenticate, Cap, CapEnd, CapLsRequest, CapReq, Capability, CapabilityRequest},
+};
+use thiserror::Error;
+
+/// Credentials for authenticating via SASL once `sasl` has been negotiated;
+/// see [`CapSaslResponder::new`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaslCredentials {
+    pub mechanism: SaslMechanism,
+    /// The authorization identity to request, if different from `nickname`
+    /// (the authentication identity).  Only consulted by `PLAIN`.
+    pub authzid: Option<String>,
+    pub nickname: Nickname,
+    /// For `EXTERNAL`, this is instead the authzid to request via the
+    /// `AUTHENTICATE` payload; see [`SaslMechanism::new_flow`].
+    pub password: String,
+}
+
+/// Runs IRCv3 capability negotiation (`CAP LS`/`CAP REQ`/`CAP ACK`/`CAP NAK`/
+/// `CAP END`) and, if requested, SASL authentication via the `PLAIN` or
+/// `EXTERNAL` mechanism, as an auto-responder to be installed before
+/// registration completes.
+///
+/// Construction immediately queues `CAP LS 302`; call
+/// [`get_outgoing_messages`](AutoResponder::get_outgoing_messages) to
+/// retrieve it. From then on, feed every message received from the server
+/// to [`handle_message`](AutoResponder::handle_message). Once
+/// [`is_done`](AutoResponder::is_done) returns `true`, negotiation (and, if
+/// requested, authentication) has finished, successfully or not; see
+/// [`enabled_capabilities`](Self::enabled_capabilities) and
+/// [`failure`](Self::failure).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapSaslResponder {
+    outgoing: Vec<Message>,
+    requested: Vec<Capability>,
+    sasl: Option<SaslCredentials>,
+    enabled: Vec<Capability>,
+    state: State,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum State {
+    AwaitingLs {
+        offered: Vec<Capability>,
+    },
+    AwaitingAck {
+        wanted: Vec<Capability>,
+    },
+    AwaitingSasl {
+        machine: SaslMachine,
+    },
+    Done,
+    Failed(CapSaslError),
+}
+
+impl CapSaslResponder {
+    /// Begins negotiating `requested`, plus `sasl` (automatically) if `sasl`
+    /// credentials are given.
+    pub fn new<I: IntoIterator<Item = Capability>>(
+        requested: I,
+        sasl: Option<SaslCredentials>,
+    ) -> CapSaslResponder {
+        let cap_ls = Message::from(CapLsRequest::new_with_version(302));
+        CapSaslResponder {
+            outgoing: vec![cap_ls],
+            requested: requested.into_iter().collect(),
+            sasl,
+            enabled: Vec::new(),
+            state: State::AwaitingLs {
+                offered: Vec::new(),
+            },
+        }
+    }
+
+    /// The capabilities enabled by negotiation, populated once `is_done()`.
+    pub fn enabled_capabilities(&self) -> &[Capability] {
+        &self.enabled
+    }
+
+    /// The reason SASL authentication failed, if it did.
+    pub fn failure(&self) -> Option<&CapSaslError> {
+        match &self.state {
+            State::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    fn wants_sasl(&self, offered: &[Capability]) -> bool {
+        self.sasl.is_some() && offered.iter().any(|c| c.as_str() == "sasl")
+    }
+
+    fn start_sasl(&mut self) -> bool {
+        let Some(creds) = &self.sasl else {
+            return false;
+        };
+        match creds
+            .mechanism
+            .new_flow(creds.authzid.as_deref(), &creds.nickname, &creds.password, None)
+        {
+            Ok((machine, msgs)) => {
+                self.outgoing
+                    .extend(msgs.into_iter().map(ClientMessage::from).map(Message::from));
+                self.state = State::AwaitingSasl { machine };
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn finish(&mut self) {
+        self.outgoing.push(Message::from(CapEnd));
+        self.state = State::Done;
+    }
+}
+
+impl AutoResponder for CapSaslResponder {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if matches!(self.state, State::Done | State::Failed(_)) {
+            return false;
+        }
+        match &msg.payload {
+            Payload::Reply(Reply::UnknownCommand(r)) if *r.command() == Verb::Cap => {
+                // Server doesn't support CAP at all; give up on negotiation
+                // without treating it as a failure.
+                self.state = State::Done;
+                true
+            }
+            Payload::ClientMessage(ClientMessage::Cap(cap)) => self.handle_cap(cap),
+            Payload::ClientMessage(ClientMessage::Authenticate(auth)) => {
+                self.handle_authenticate(auth)
+            }
+            Payload::Reply(rpl) => self.handle_reply(rpl),
+            _ => false,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(self.state, State::Done | State::Failed(_))
+    }
+}
+
+impl CapSaslResponder {
+    fn handle_cap(&mut self, cap: &Cap) -> bool {
+        match (&mut self.state, cap) {
+            (State::AwaitingLs { offered }, Cap::LsResponse(r)) => {
+                offered.extend(r.capabilities.iter().map(|(c, _)| c.clone()));
+                if r.continued {
+                    return true;
+                }
+                let offered = std::mem::take(offered);
+                let mut wanted: Vec<Capability> = offered
+                    .iter()
+                    .filter(|c| self.requested.contains(c))
+                    .cloned()
+                    .collect();
+                if self.wants_sasl(&offered) {
+                    wanted.extend(offered.iter().find(|c| c.as_str() == "sasl").cloned());
+                }
+                if wanted.is_empty() {
+                    self.finish();
+                } else {
+                    self.outgoing.push(Message::from(CapReq {
+                        capabilities: wanted
+                            .iter()
+                            .cloned()
+                            .map(CapabilityRequest::enable)
+                            .collect(),
+                    }));
+                    self.state = State::AwaitingAck { wanted };
+                }
+                true
+            }
+            (State::AwaitingAck { wanted }, Cap::Ack(_)) => {
+                let wanted = std::mem::take(wanted);
+                let wants_sasl = wanted.iter().any(|c| c.as_str() == "sasl");
+                self.enabled = wanted;
+                if wants_sasl && self.start_sasl() {
+                    // AwaitingSasl was set by start_sasl(); nothing more to do.
+                } else {
+                    self.finish();
+                }
+                true
+            }
+            (State::AwaitingAck { .. }, Cap::Nak(_)) => {
+                self.finish();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_authenticate(&mut self, auth: &Authenticate) -> bool {
+        let State::AwaitingSasl { machine } = &mut self.state else {
+            return false;
+        };
+        match machine.handle_message(auth.clone()) {
+            Ok(()) => {
+                self.outgoing.extend(
+                    machine
+                        .get_output()
+                        .into_iter()
+                        .map(ClientMessage::from)
+                        .map(Message::from),
+                );
+                true
+            }
+            Err(e) => {
+                self.state = State::Failed(CapSaslError::SaslFlow(e.to_string()));
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+        }
+    }
+
+    fn handle_reply(&mut self, rpl: &Reply) -> bool {
+        if !matches!(self.state, State::AwaitingSasl { .. }) {
+            return false;
+        }
+        match rpl {
+            Reply::SaslSuccess(_) => {
+                self.finish();
+                true
+            }
+            Reply::NickLocked(r) => {
+                self.state = State::Failed(CapSaslError::NickLocked {
+                    message: r.message().to_string(),
+                });
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+            Reply::SaslFail(r) => {
+                self.state = State::Failed(CapSaslError::SaslFail {
+                    message: r.message().to_string(),
+                });
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+            Reply::SaslTooLong(r) => {
+                self.state = State::Failed(CapSaslError::SaslTooLong {
+                    message: r.message().to_string(),
+                });
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+            Reply::SaslAborted(r) => {
+                self.state = State::Failed(CapSaslError::SaslAborted {
+                    message: r.message().to_string(),
+                });
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+            Reply::SaslAlready(r) => {
+                self.state = State::Failed(CapSaslError::SaslAlready {
+                    message: r.message().to_string(),
+                });
+                self.outgoing.push(Message::from(CapEnd));
+                true
+            }
+            // `RPL_LOGGEDIN`/`RPL_LOGGEDOUT` merely report the resulting
+            // account name and don't affect negotiation; consumed but
+            // otherwise ignored.
+            Reply::LoggedIn(_) | Reply::LoggedOut(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum CapSaslError {
+    #[error("SASL exchange failed: {0}")]
+    SaslFlow(String),
+    #[error("nick is locked to a different account: {message}")]
+    NickLocked { message: String },
+    #[error("SASL authentication failed: {message}")]
+    SaslFail { message: String },
+    #[error("SASL message too long: {message}")]
+    SaslTooLong { message: String },
+    #[error("SASL authentication aborted: {message}")]
+    SaslAborted { message: String },
+    #[error("already authenticated via SASL: {message}")]
+    SaslAlready { message: String },
+}
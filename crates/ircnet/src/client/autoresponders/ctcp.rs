@@ -2,18 +2,51 @@ use super::AutoResponder;
 use irctext::{
     ClientMessage, ClientSource, Message, Payload, Source,
     clientmsgs::Notice,
-    ctcp::{CtcpMessage, CtcpParams},
+    ctcp::{CtcpCommand, CtcpMessage, CtcpParams, DccMessage, DccOffer},
+    types::Nickname,
 };
 use jiff::{Timestamp, Zoned, tz::TimeZone};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::time::{Duration, Instant};
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+type CustomResponder = Box<dyn Fn(&CtcpParams) -> Option<CtcpParams> + Send>;
+
+#[derive(Default)]
 pub struct CtcpQueryResponder {
-    outgoing: Vec<ClientMessage>,
+    outgoing: Vec<Message>,
+    clientinfo: bool,
     finger: Option<CtcpParams>,
+    ping: bool,
     source: Option<CtcpParams>,
+    time: bool,
     userinfo: Option<CtcpParams>,
     version: Option<CtcpParams>,
     utc_time: bool,
+    dcc_offers: VecDeque<(ClientSource, DccOffer)>,
+    custom: BTreeMap<CtcpCommand, CustomResponder>,
+    disabled: BTreeSet<CtcpCommand>,
+    rate_limit: Option<(u32, Duration)>,
+    buckets: HashMap<Nickname, TokenBucket>,
+}
+
+impl std::fmt::Debug for CtcpQueryResponder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CtcpQueryResponder")
+            .field("outgoing", &self.outgoing)
+            .field("clientinfo", &self.clientinfo)
+            .field("finger", &self.finger)
+            .field("ping", &self.ping)
+            .field("source", &self.source)
+            .field("time", &self.time)
+            .field("userinfo", &self.userinfo)
+            .field("version", &self.version)
+            .field("utc_time", &self.utc_time)
+            .field("dcc_offers", &self.dcc_offers)
+            .field("custom", &self.custom.keys().collect::<Vec<_>>())
+            .field("disabled", &self.disabled)
+            .field("rate_limit", &self.rate_limit)
+            .finish_non_exhaustive()
+    }
 }
 
 impl CtcpQueryResponder {
@@ -21,16 +54,38 @@ impl CtcpQueryResponder {
         CtcpQueryResponder::default()
     }
 
+    /// Enables answering `CLIENTINFO` queries with the set of CTCP commands
+    /// this responder has been configured to answer.
+    pub fn with_clientinfo(mut self) -> Self {
+        self.clientinfo = true;
+        self
+    }
+
     pub fn with_finger(mut self, finger: CtcpParams) -> Self {
         self.finger = Some(finger);
         self
     }
 
+    /// Enables answering `PING` queries by echoing the query's payload back
+    /// verbatim.
+    pub fn with_ping(mut self) -> Self {
+        self.ping = true;
+        self
+    }
+
     pub fn with_source(mut self, source: CtcpParams) -> Self {
         self.source = Some(source);
         self
     }
 
+    /// Enables answering `TIME` queries with the current local time (or UTC,
+    /// if [`with_utc_time()`][Self::with_utc_time] was also enabled),
+    /// formatted per the CTCP spec.
+    pub fn with_time(mut self) -> Self {
+        self.time = true;
+        self
+    }
+
     pub fn with_userinfo(mut self, userinfo: CtcpParams) -> Self {
         self.userinfo = Some(userinfo);
         self
@@ -45,10 +100,84 @@ impl CtcpQueryResponder {
         self.utc_time = utc_time;
         self
     }
+
+    /// Registers a handler for a non-standard CTCP query (e.g. `AVATAR`),
+    /// which is also listed alongside the standard queries in response to a
+    /// `CLIENTINFO` query.  `responder` is called with the incoming query's
+    /// parameters (if any) and should return the response parameters to
+    /// send back, or `None` to send a response with no parameters.
+    pub fn with_custom<F>(mut self, command: CtcpCommand, responder: F) -> Self
+    where
+        F: Fn(&CtcpParams) -> Option<CtcpParams> + Send + 'static,
+    {
+        self.custom.insert(command, Box::new(responder));
+        self
+    }
+
+    /// Suppresses automatic replies to `command` (including the built-in
+    /// `CLIENTINFO`, `FINGER`, `PING`, `SOURCE`, `TIME`, `USERINFO`, and
+    /// `VERSION` queries), so `handle_message` ignores it entirely rather
+    /// than responding or even considering the message handled.
+    pub fn with_disabled(mut self, command: CtcpCommand) -> Self {
+        self.disabled.insert(command);
+        self
+    }
+
+    /// Limits how often each distinct sender's CTCP queries are answered,
+    /// using a token bucket that holds up to `capacity` tokens and refills
+    /// at a rate of one token per `refill_interval / capacity`.  Once a
+    /// sender's bucket runs dry, their CTCP queries are silently ignored
+    /// until it refills (`handle_message` returns `false` for them, as if
+    /// this responder hadn't recognized the message at all).
+    pub fn with_rate_limit(mut self, capacity: u32, refill_interval: Duration) -> Self {
+        self.rate_limit = Some((capacity, refill_interval));
+        self
+    }
+
+    /// Retrieve all incoming DCC offers received so far, each paired with
+    /// the `ClientSource` that sent it, removing them from the responder's
+    /// internal queue.
+    pub fn take_dcc_offers(&mut self) -> VecDeque<(ClientSource, DccOffer)> {
+        std::mem::take(&mut self.dcc_offers)
+    }
+}
+
+/// A continuously-refilling token bucket used to rate-limit a single
+/// sender's CTCP queries; see [`CtcpQueryResponder::with_rate_limit`].
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32) -> TokenBucket {
+        TokenBucket {
+            tokens: f64::from(capacity),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Refills the bucket based on time elapsed since the last refill (up
+    /// to `capacity` tokens) and consumes a single token if one is
+    /// available, returning whether the consumption succeeded.
+    fn try_consume(&mut self, capacity: u32, refill_interval: Duration) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill);
+        self.last_refill = now;
+        let rate = f64::from(capacity) / refill_interval.as_secs_f64();
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(f64::from(capacity));
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl AutoResponder for CtcpQueryResponder {
-    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
         std::mem::take(&mut self.outgoing)
     }
 
@@ -56,18 +185,46 @@ impl AutoResponder for CtcpQueryResponder {
         let Some(source) = &msg.source else {
             return false;
         };
-        let Source::Client(ClientSource {
-            nickname: sender, ..
-        }) = source
-        else {
+        let Source::Client(client_source) = source else {
             return false;
         };
+        let sender = &client_source.nickname;
         let Payload::ClientMessage(ClientMessage::PrivMsg(privmsg)) = &msg.payload else {
             return false;
         };
         let ctcp = CtcpMessage::from(privmsg.text().clone());
+        if let Some(command) = ctcp_command_name(&ctcp)
+            && self
+                .disabled
+                .iter()
+                .any(|c| c.as_str().eq_ignore_ascii_case(command))
+        {
+            return false;
+        }
+        if !ctcp.is_plain()
+            && let Some((capacity, refill_interval)) = self.rate_limit
+        {
+            let bucket = self
+                .buckets
+                .entry(sender.clone())
+                .or_insert_with(|| TokenBucket::new(capacity));
+            if !bucket.try_consume(capacity, refill_interval) {
+                tracing::info!(
+                    source = source.to_string(),
+                    "Ignoring CTCP query; sender has exceeded the rate limit"
+                );
+                return false;
+            }
+        }
         let resp = match ctcp {
             CtcpMessage::ClientInfo(None) => {
+                if !self.clientinfo {
+                    tracing::info!(
+                        source = source.to_string(),
+                        "Received CLIENTINFO CTCP query, but no response defined"
+                    );
+                    return true;
+                }
                 tracing::info!(
                     source = source.to_string(),
                     "Received CLIENTINFO CTCP query; responding ..."
@@ -76,17 +233,25 @@ impl AutoResponder for CtcpQueryResponder {
                 if self.finger.is_some() {
                     s.push_str(" FINGER");
                 }
-                s.push_str(" PING");
+                if self.ping {
+                    s.push_str(" PING");
+                }
                 if self.source.is_some() {
                     s.push_str(" SOURCE");
                 }
-                s.push_str(" TIME");
+                if self.time {
+                    s.push_str(" TIME");
+                }
                 if self.userinfo.is_some() {
                     s.push_str(" USERINFO");
                 }
                 if self.version.is_some() {
                     s.push_str(" VERSION");
                 }
+                for command in self.custom.keys() {
+                    s.push(' ');
+                    s.push_str(command.as_str());
+                }
                 match CtcpParams::try_from(s) {
                     Ok(ps) => CtcpMessage::ClientInfo(Some(ps)),
                     Err(e) => {
@@ -114,6 +279,13 @@ impl AutoResponder for CtcpQueryResponder {
                 }
             }
             m @ CtcpMessage::Ping(_) => {
+                if !self.ping {
+                    tracing::info!(
+                        source = source.to_string(),
+                        "Received PING CTCP query, but no response defined"
+                    );
+                    return true;
+                }
                 tracing::info!(
                     source = source.to_string(),
                     "Received PING CTCP query; responding ..."
@@ -136,6 +308,13 @@ impl AutoResponder for CtcpQueryResponder {
                 }
             }
             CtcpMessage::Time(None) => {
+                if !self.time {
+                    tracing::info!(
+                        source = source.to_string(),
+                        "Received TIME CTCP query, but no response defined"
+                    );
+                    return true;
+                }
                 tracing::info!(
                     source = source.to_string(),
                     "Received TIME CTCP query; responding ..."
@@ -196,6 +375,42 @@ impl AutoResponder for CtcpQueryResponder {
                     return true;
                 }
             }
+            CtcpMessage::Dcc(Some(ref msg)) => {
+                match msg {
+                    DccMessage::Offer(offer) => {
+                        tracing::info!(
+                            source = source.to_string(),
+                            offer = ?offer,
+                            "Received DCC offer"
+                        );
+                        self.dcc_offers
+                            .push_back((client_source.clone(), offer.clone()));
+                    }
+                    _ => {
+                        tracing::warn!(
+                            source = source.to_string(),
+                            "Received unsupported DCC sub-command"
+                        );
+                    }
+                }
+                return true;
+            }
+            CtcpMessage::Other {
+                ref command,
+                ref params,
+            } if self.custom.contains_key(command) => {
+                tracing::info!(
+                    source = source.to_string(),
+                    command = command.as_str(),
+                    "Received custom CTCP query; responding ..."
+                );
+                let handler = &self.custom[command];
+                let resp_params = params.as_ref().and_then(|p| handler(p));
+                CtcpMessage::Other {
+                    command: command.clone(),
+                    params: resp_params,
+                }
+            }
             _ => return false,
         };
         self.outgoing
@@ -207,3 +422,20 @@ impl AutoResponder for CtcpQueryResponder {
         false
     }
 }
+
+/// Returns the CTCP command name `ctcp` was parsed from, for comparing
+/// against [`CtcpQueryResponder::with_disabled`]'s suppression list.
+fn ctcp_command_name(ctcp: &CtcpMessage) -> Option<&str> {
+    match ctcp {
+        CtcpMessage::ClientInfo(_) => Some("CLIENTINFO"),
+        CtcpMessage::Dcc(_) => Some("DCC"),
+        CtcpMessage::Finger(_) => Some("FINGER"),
+        CtcpMessage::Ping(_) => Some("PING"),
+        CtcpMessage::Source(_) => Some("SOURCE"),
+        CtcpMessage::Time(_) => Some("TIME"),
+        CtcpMessage::UserInfo(_) => Some("USERINFO"),
+        CtcpMessage::Version(_) => Some("VERSION"),
+        CtcpMessage::Other { command, .. } => Some(command.as_str()),
+        CtcpMessage::Action(_) | CtcpMessage::Plain(_) => None,
+    }
+}
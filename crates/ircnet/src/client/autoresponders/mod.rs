@@ -0,0 +1,159 @@
+//! Automatic responders for unsolicited messages received by [`Client`](super::Client)
+mod cap;
+mod cap_sasl;
+mod ctcp;
+mod ping;
+mod registration;
+pub use self::cap::*;
+pub use self::cap_sasl::*;
+pub use self::ctcp::*;
+pub use self::ping::*;
+pub use self::registration::*;
+use irctext::Message;
+
+/// A handler/observer for automatically responding to messages received over IRC
+///
+/// An `AutoResponder` is intended to be used as follows whenever a message is
+/// received from an IRC server:
+///
+/// - Pass the message to `handle_message()`.
+///
+/// - Call `get_outgoing_messages()` and send any returned messages back to
+///   the server.
+///
+/// - If `is_done()` returns `true`, discard the autoresponder.
+///
+/// - If the call to `handle_message()` returned `false`, use the message
+///   separately from the autoresponder as you desire.
+pub trait AutoResponder {
+    /// Returns outgoing messages to send back to the server.
+    ///
+    /// Users SHOULD call this method after each call to `handle_message()`.
+    ///
+    /// If `is_done()` is true, this method SHOULD return an empty `Vec`.
+    fn get_outgoing_messages(&mut self) -> Vec<Message>;
+
+    /// Handle an incoming message received from the server.  Returns `true` if
+    /// the message should be considered "handled" by the autoresponder and not to be
+    /// processed by any non-autoresponders.
+    ///
+    /// After calling this method, users SHOULD call `get_outgoing_messages()`
+    /// to receive any new outgoing messages from the autoresponder.
+    ///
+    /// If `is_done()` is true, this method SHOULD be a no-op.
+    fn handle_message(&mut self, msg: &Message) -> bool;
+
+    /// Returns `true` when the autoresponder has completed its tasks and is
+    /// not interested in any more incoming messages.
+    fn is_done(&self) -> bool;
+}
+
+impl<T: AutoResponder + ?Sized> AutoResponder for Box<T> {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
+        (**self).get_outgoing_messages()
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        (**self).handle_message(msg)
+    }
+
+    fn is_done(&self) -> bool {
+        (**self).is_done()
+    }
+}
+
+/// How an [`AutoResponderSet`] dispatches an incoming message to its
+/// members; see [`AutoResponderSet::with_dispatch_policy`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DispatchPolicy {
+    /// Feed the message to every responder, regardless of whether an
+    /// earlier one claimed it. This is the default, preserving the set's
+    /// original behavior.
+    #[default]
+    Broadcast,
+    /// Feed the message to responders in priority order, stopping as soon
+    /// as one returns `true` from `handle_message()`.
+    FirstMatch,
+}
+
+/// A collection of [`AutoResponder`]s, driven together as a single
+/// `AutoResponder`.
+///
+/// Responders are normally dispatched in priority order (highest first,
+/// ties broken by registration order; see
+/// [`push_with_priority`](Self::push_with_priority)), but by default every
+/// responder sees every message (see [`DispatchPolicy`]).
+#[allow(missing_debug_implementations)]
+#[derive(Default)]
+pub struct AutoResponderSet {
+    responders: Vec<(i32, Box<dyn AutoResponder + Send>)>,
+    policy: DispatchPolicy,
+}
+
+impl AutoResponderSet {
+    pub fn new() -> AutoResponderSet {
+        AutoResponderSet::default()
+    }
+
+    /// Sets the policy used to dispatch incoming messages to the set's
+    /// responders.
+    pub fn with_dispatch_policy(mut self, policy: DispatchPolicy) -> AutoResponderSet {
+        self.policy = policy;
+        self
+    }
+
+    /// Registers `handler` with the default priority of `0`.
+    pub fn push<H: AutoResponder + Send + 'static>(&mut self, handler: H) {
+        self.push_with_priority(handler, 0);
+    }
+
+    /// Registers `handler` with the given priority. Higher-priority
+    /// responders are dispatched to first; responders with equal priority
+    /// are dispatched to in the order they were registered.
+    pub fn push_with_priority<H: AutoResponder + Send + 'static>(
+        &mut self,
+        handler: H,
+        priority: i32,
+    ) {
+        self.responders.push((priority, Box::new(handler)));
+        self.responders.sort_by_key(|(priority, _)| -priority);
+    }
+
+    fn cleanup(&mut self) {
+        self.responders.retain(|(_, h)| !h.is_done());
+    }
+}
+
+impl AutoResponder for AutoResponderSet {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
+        let msgs = self
+            .responders
+            .iter_mut()
+            .flat_map(|(_, h)| h.get_outgoing_messages())
+            .collect();
+        self.cleanup();
+        msgs
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        match self.policy {
+            DispatchPolicy::Broadcast => {
+                let mut handled = false;
+                for (_, h) in &mut self.responders {
+                    if h.handle_message(msg) {
+                        handled = true;
+                    }
+                }
+                handled
+            }
+            DispatchPolicy::FirstMatch => self
+                .responders
+                .iter_mut()
+                .any(|(_, h)| h.handle_message(msg)),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.responders.is_empty()
+    }
+}
@@ -0,0 +1,165 @@
+use super::AutoResponder;
+use irctext::types::{Nickname, Username};
+use irctext::{
+    ClientMessage, FinalParam, Message, Payload, Reply, ReplyParts,
+    clientmsgs::{Nick, Pass, User},
+    types::ReplyTarget,
+};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// Builder for a [`RegistrationResponder`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationBuilder {
+    nickname: Nickname,
+    username: Username,
+    realname: FinalParam,
+    password: Option<FinalParam>,
+    fallback_nicks: VecDeque<Nickname>,
+}
+
+impl RegistrationBuilder {
+    pub fn new(nickname: Nickname, username: Username, realname: FinalParam) -> RegistrationBuilder {
+        RegistrationBuilder {
+            nickname,
+            username,
+            realname,
+            password: None,
+            fallback_nicks: VecDeque::new(),
+        }
+    }
+
+    pub fn with_password(mut self, password: FinalParam) -> RegistrationBuilder {
+        self.password = Some(password);
+        self
+    }
+
+    /// Appends `nick` to the list of nicknames to try, in order, if the
+    /// current candidate is rejected with `ERR_NICKNAMEINUSE` (433) or
+    /// `ERR_NICKCOLLISION` (436) before registration completes.
+    pub fn with_fallback_nick(mut self, nick: Nickname) -> RegistrationBuilder {
+        self.fallback_nicks.push_back(nick);
+        self
+    }
+
+    pub fn build(self) -> RegistrationResponder {
+        let pass = self.password.map(Pass::new).map(Message::from);
+        let nick = Message::from(Nick::new(self.nickname.clone()));
+        let user = Message::from(User::new(self.username, self.realname));
+        RegistrationResponder {
+            outgoing: pass.into_iter().chain([nick, user]).collect(),
+            current_nick: self.nickname,
+            fallback_nicks: self.fallback_nicks,
+            status: Status::Pending,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Status {
+    Pending,
+    Done(Nickname),
+    Failed(RegistrationError),
+}
+
+/// Performs the `PASS`/`NICK`/`USER` registration handshake, without the CAP
+/// negotiation and SASL machinery of [`Login`](crate::client::commands::Login),
+/// for bots built on top of autoresponders rather than a [`Client::run`]-driven
+/// [`Command`](crate::client::commands::Command).
+///
+/// Configured via [`RegistrationBuilder`], it emits `PASS` (if a password was
+/// given), `NICK`, and `USER` on the first `get_outgoing_messages()` call,
+/// then watches incoming numerics: on `ERR_NICKNAMEINUSE` (433) or
+/// `ERR_NICKCOLLISION` (436) it tries the next fallback nick, on
+/// `RPL_WELCOME` (001) it records the confirmed nick and becomes done, and on
+/// an `ERROR` received before then it fails with
+/// [`RegistrationError::ErrorMessage`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationResponder {
+    outgoing: Vec<Message>,
+    current_nick: Nickname,
+    fallback_nicks: VecDeque<Nickname>,
+    status: Status,
+}
+
+impl RegistrationResponder {
+    pub fn builder(
+        nickname: Nickname,
+        username: Username,
+        realname: FinalParam,
+    ) -> RegistrationBuilder {
+        RegistrationBuilder::new(nickname, username, realname)
+    }
+
+    /// The nickname confirmed by `RPL_WELCOME`, once registration has
+    /// completed successfully.
+    pub fn my_nick(&self) -> Option<&Nickname> {
+        match &self.status {
+            Status::Done(nick) => Some(nick),
+            _ => None,
+        }
+    }
+
+    /// The reason registration failed, if it did.
+    pub fn failure(&self) -> Option<&RegistrationError> {
+        match &self.status {
+            Status::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl AutoResponder for RegistrationResponder {
+    fn get_outgoing_messages(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if !matches!(self.status, Status::Pending) {
+            return false;
+        }
+        match &msg.payload {
+            Payload::Reply(rpl @ (Reply::NicknameInUse(_) | Reply::NickCollision(_))) => {
+                if let Some(next) = self.fallback_nicks.pop_front() {
+                    self.current_nick = next.clone();
+                    self.outgoing.push(Message::from(Nick::new(next)));
+                } else {
+                    self.status = Status::Failed(RegistrationError::NicksExhausted {
+                        message: rpl.parameters().last().map_or_else(
+                            String::new,
+                            |p| p.as_str().to_string(),
+                        ),
+                    });
+                }
+                true
+            }
+            Payload::Reply(Reply::Welcome(welcome)) => {
+                let nick = match welcome.client() {
+                    ReplyTarget::User(nick) => nick.clone(),
+                    ReplyTarget::Star => self.current_nick.clone(),
+                };
+                self.status = Status::Done(nick);
+                true
+            }
+            Payload::ClientMessage(ClientMessage::Error(err)) => {
+                self.status = Status::Failed(RegistrationError::ErrorMessage {
+                    reason: err.reason().to_string(),
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        !matches!(self.status, Status::Pending)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum RegistrationError {
+    #[error("no fallback nicknames left to try: {message}")]
+    NicksExhausted { message: String },
+    #[error("server sent ERROR during registration: {reason}")]
+    ErrorMessage { reason: String },
+}
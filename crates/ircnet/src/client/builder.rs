@@ -1,6 +1,7 @@
 use super::autoresponders::{AutoResponder, AutoResponderSet};
-use super::commands::{Login, LoginOutput, LoginParams};
+use super::commands::{Login, LoginOutput, LoginParams, SaslCredentials};
 use super::{Client, ClientError, ConnectionParams};
+use crate::sasl::SaslMechanism;
 use tracing::Instrument;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -36,12 +37,25 @@ impl SessionBuilder {
 
     pub async fn build(self) -> Result<(Client, LoginOutput), ClientError> {
         let host = self.connect.host.clone();
+        let mut login = self.login;
+        if self.connect.tls && self.connect.client_cert.is_some() && login.sasl.is_none() {
+            // A client certificate was configured but no SASL mechanism was
+            // requested explicitly; authenticate via CertFP using SASL
+            // EXTERNAL, letting the server derive the authzid from the
+            // certificate, rather than falling back to plain login.
+            login.sasl = Some(SaslCredentials {
+                mechanisms: vec![SaslMechanism::External],
+                password: String::new(),
+                authzid: None,
+            });
+        }
         let mut client = Client::connect(self.connect).await?;
         client.set_autoresponders(self.autoresponders);
-        let span = tracing::info_span!("login", host, nickname = self.login.nickname.as_str());
+        let channel_binding = client.channel_binding();
+        let span = tracing::info_span!("login", host, nickname = login.nickname.as_str());
         let login_output = async {
             tracing::info!("Logging in to IRC network …");
-            let r = client.run(Login::new(self.login)).await;
+            let r = client.run(Login::new(login, channel_binding)).await;
             if r.is_ok() {
                 tracing::info!("Login successful");
             }
@@ -49,6 +63,7 @@ impl SessionBuilder {
         }
         .instrument(span)
         .await?;
+        client.set_capabilities(login_output.capabilities.clone());
         Ok((client, login_output))
     }
 }
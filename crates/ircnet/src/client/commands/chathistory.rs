@@ -0,0 +1,216 @@
+use super::Command;
+use irctext::{
+    ClientMessage, MedialParam, Message, MessageTags, Payload,
+    clientmsgs::{Batch, ChatHistory as ChatHistoryMsg},
+};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Sends a `CHATHISTORY` request and collects the `PRIVMSG`/`NOTICE`/`TAGMSG`
+/// lines the server sends back wrapped in a `chathistory`-type `BATCH`,
+/// per <https://ircv3.net/specs/extensions/chathistory>, pairing each message
+/// with its `time` and `msgid` tags for convenience.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChatHistory {
+    outgoing: Vec<ClientMessage>,
+    state: State,
+}
+
+impl ChatHistory {
+    pub fn new(request: ChatHistoryMsg) -> ChatHistory {
+        ChatHistory {
+            outgoing: vec![ClientMessage::from(request)],
+            state: State::AwaitingBatchStart,
+        }
+    }
+}
+
+impl Command for ChatHistory {
+    type Output = Vec<HistoricalMessage>;
+    type Error = ChatHistoryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if let Payload::ClientMessage(ClientMessage::Error(err)) = &msg.payload {
+            self.state = State::Done(Some(Err(ChatHistoryError::ErrorMessage {
+                reason: err.reason().to_string(),
+            })));
+            return true;
+        }
+        let state = std::mem::replace(&mut self.state, State::Void);
+        let (state, handled) = state.handle(msg);
+        self.state = state;
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        matches!(self.state, State::Done(_))
+    }
+
+    fn get_output(&mut self) -> Result<Vec<HistoricalMessage>, ChatHistoryError> {
+        if let State::Done(ref mut r) = self.state {
+            r.take()
+                .expect("get_output() should not be called more than once")
+        } else {
+            panic!("get_output() should only be called when is_done() is true");
+        }
+    }
+}
+
+/// An error that occurred while running a [`ChatHistory`] command.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ChatHistoryError {
+    /// The server sent an `ERROR` message, closing the connection.
+    #[error("server sent ERROR: {reason}")]
+    ErrorMessage { reason: String },
+}
+
+const CHATHISTORY_BATCH_TYPE: &str = "chathistory";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum State {
+    AwaitingBatchStart,
+    InBatch {
+        reference_tag: MedialParam,
+        messages: Vec<HistoricalMessage>,
+    },
+    Done(Option<Result<Vec<HistoricalMessage>, ChatHistoryError>>),
+    Void,
+}
+
+impl State {
+    fn handle(self, msg: &Message) -> (State, bool) {
+        match (self, &msg.payload) {
+            (
+                State::AwaitingBatchStart,
+                Payload::ClientMessage(ClientMessage::Batch(Batch::Start(start))),
+            ) if start.batch_type().as_ref() == CHATHISTORY_BATCH_TYPE => (
+                State::InBatch {
+                    reference_tag: start.reference_tag().clone(),
+                    messages: Vec::new(),
+                },
+                true,
+            ),
+            (
+                State::InBatch {
+                    reference_tag,
+                    messages,
+                },
+                Payload::ClientMessage(ClientMessage::Batch(Batch::End(end))),
+            ) if *end.reference_tag() == reference_tag => (State::Done(Some(Ok(messages))), true),
+            (
+                State::InBatch {
+                    reference_tag,
+                    mut messages,
+                },
+                _,
+            ) if batch_tag(msg).is_some_and(|tag| tag == reference_tag.as_str()) => {
+                messages.push(HistoricalMessage::from(msg.clone()));
+                (
+                    State::InBatch {
+                        reference_tag,
+                        messages,
+                    },
+                    true,
+                )
+            }
+            (st @ State::InBatch { .. }, _) => (st, false),
+            (st @ (State::Done(_) | State::Void), _) => (st, false),
+            (State::AwaitingBatchStart, _) => (State::AwaitingBatchStart, false),
+        }
+    }
+}
+
+/// Returns the value of `msg`'s `batch` tag, identifying which open `BATCH`
+/// (by reference tag) it belongs to, if any.
+fn batch_tag(msg: &Message) -> Option<&str> {
+    msg.tags.as_ref()?.batch()
+}
+
+/// A single historical message returned inside a `chathistory`-type `BATCH`,
+/// with its `time` and `msgid` tags (if present) pulled out for convenience;
+/// the full, untouched message (tags included) is still available via
+/// [`HistoricalMessage::message`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoricalMessage {
+    pub time: Option<String>,
+    pub msgid: Option<String>,
+    pub message: Message,
+}
+
+impl From<Message> for HistoricalMessage {
+    fn from(message: Message) -> HistoricalMessage {
+        let tag = |get: fn(&MessageTags) -> Option<&str>| {
+            message.tags.as_ref().and_then(get).map(str::to_owned)
+        };
+        HistoricalMessage {
+            time: tag(MessageTags::time),
+            msgid: tag(MessageTags::msgid),
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use irctext::ClientMessageParts;
+    use irctext::clientmsgs::ChatHistory as ChatHistoryMsg;
+    use irctext::types::MsgTarget;
+
+    #[test]
+    fn collects_batched_messages() {
+        let target = "#chan".parse::<MsgTarget>().unwrap();
+        let mut cmd = ChatHistory::new(ChatHistoryMsg::latest(target, None, 50));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|m| m.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["CHATHISTORY LATEST #chan * 50"]
+        );
+        assert!(!cmd.is_done());
+
+        let incoming = [
+            ":irc.example.com BATCH +ref1 chathistory #chan",
+            "@batch=ref1;time=2023-01-01T00:00:00.000Z;msgid=abc123 :nick!user@host PRIVMSG #chan :hi",
+            ":irc.example.com BATCH -ref1",
+        ];
+        for (i, m) in incoming.iter().enumerate() {
+            let msg = m.parse::<Message>().unwrap();
+            assert!(cmd.handle_message(&msg), "message {i} should be handled");
+        }
+        assert!(cmd.is_done());
+
+        let output = cmd.get_output().unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].time.as_deref(), Some("2023-01-01T00:00:00.000Z"));
+        assert_eq!(output[0].msgid.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn server_error_aborts_with_chathistoryerror() {
+        let target = "#chan".parse::<MsgTarget>().unwrap();
+        let mut cmd = ChatHistory::new(ChatHistoryMsg::latest(target, None, 50));
+        let msg = "ERROR :Closing link: (user@host) [Ping timeout]"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.is_done());
+        assert_eq!(
+            cmd.get_output(),
+            Err(ChatHistoryError::ErrorMessage {
+                reason: "Closing link: (user@host) [Ping timeout]".to_string()
+            })
+        );
+    }
+}
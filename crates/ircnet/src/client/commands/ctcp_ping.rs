@@ -0,0 +1,204 @@
+use super::Command;
+use irctext::{
+    ClientMessage, ClientSource, Message, Payload, Source,
+    clientmsgs::PrivMsg,
+    ctcp::{CtcpMessage, CtcpParams},
+    types::Nickname,
+};
+use jiff::Timestamp;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// Actively measures round-trip time to another client via CTCP PING, the
+/// way an ICMP pinger measures a host.
+///
+/// Unlike [`CtcpQueryResponder`][super::super::autoresponders::CtcpQueryResponder],
+/// which only answers CTCP PING queries sent *to* this client, `CtcpPing`
+/// sends one or more CTCP PING queries to a target nick and times how long
+/// each takes to be echoed back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CtcpPing {
+    target: Nickname,
+    probe_count: u32,
+    probe_timeout: Duration,
+    outgoing: Vec<ClientMessage>,
+    next_seq: u32,
+    outstanding: Option<Outstanding>,
+    probes: Vec<ProbeOutcome>,
+    result: Option<PingSummary>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Outstanding {
+    token: CtcpParams,
+    sent_at: Instant,
+    deadline: Instant,
+}
+
+impl CtcpPing {
+    /// Send a single CTCP PING probe to `target`, waiting up to
+    /// `probe_timeout` for a reply.
+    pub fn new(target: Nickname, probe_timeout: Duration) -> CtcpPing {
+        CtcpPing::with_probe_count(target, probe_timeout, 1)
+    }
+
+    /// Send `probe_count` CTCP PING probes to `target` in sequence, each
+    /// disambiguated by an increasing sequence number and waiting up to
+    /// `probe_timeout` for a reply before being counted as lost.
+    ///
+    /// `probe_count` must be nonzero.
+    pub fn with_probe_count(
+        target: Nickname,
+        probe_timeout: Duration,
+        probe_count: u32,
+    ) -> CtcpPing {
+        assert!(probe_count > 0, "probe_count must be nonzero");
+        let mut cmd = CtcpPing {
+            target,
+            probe_count,
+            probe_timeout,
+            outgoing: Vec::new(),
+            next_seq: 0,
+            outstanding: None,
+            probes: Vec::new(),
+            result: None,
+        };
+        cmd.send_probe();
+        cmd
+    }
+
+    fn send_probe(&mut self) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let token = CtcpParams::try_from(format!("{seq} {}", Timestamp::now().as_nanosecond()))
+            .expect("ping token should be valid CtcpParams");
+        let ctcp = CtcpMessage::Ping(Some(token.clone()));
+        self.outgoing
+            .push(PrivMsg::new(self.target.clone(), ctcp.into()).into());
+        let now = Instant::now();
+        self.outstanding = Some(Outstanding {
+            token,
+            sent_at: now,
+            deadline: now + self.probe_timeout,
+        });
+    }
+
+    fn advance(&mut self) {
+        if (self.probes.len() as u32) < self.probe_count {
+            self.send_probe();
+        } else {
+            self.result = Some(PingSummary::from_probes(std::mem::take(&mut self.probes)));
+        }
+    }
+}
+
+impl Command for CtcpPing {
+    type Output = PingSummary;
+    type Error = Infallible;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        let Some(Source::Client(ClientSource { nickname, .. })) = &msg.source else {
+            return false;
+        };
+        if nickname != &self.target {
+            return false;
+        }
+        let Payload::ClientMessage(ClientMessage::Notice(notice)) = &msg.payload else {
+            return false;
+        };
+        let Some(outstanding) = &self.outstanding else {
+            return false;
+        };
+        let CtcpMessage::Ping(Some(token)) = CtcpMessage::from(notice.text().clone()) else {
+            return false;
+        };
+        if token != outstanding.token {
+            // Reply to some other, already-resolved probe; ignore it so it
+            // doesn't corrupt the measurement.
+            return false;
+        }
+        let rtt = outstanding.sent_at.elapsed();
+        self.probes.push(ProbeOutcome::Reply(rtt));
+        self.outstanding = None;
+        self.advance();
+        true
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        self.outstanding
+            .as_ref()
+            .map(|o| o.deadline.saturating_duration_since(Instant::now()))
+    }
+
+    fn handle_timeout(&mut self) {
+        if self.outstanding.take().is_some() {
+            self.probes.push(ProbeOutcome::Lost);
+            self.advance();
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.result.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<PingSummary, Infallible> {
+        Ok(self
+            .result
+            .take()
+            .expect("get_output() should not be called more than once"))
+    }
+}
+
+/// The outcome of a single CTCP PING probe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProbeOutcome {
+    /// A reply was received, after the given round-trip time.
+    Reply(Duration),
+    /// No reply was received before the probe's deadline.
+    Lost,
+}
+
+/// A summary of the probes sent by a [`CtcpPing`] command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PingSummary {
+    pub probes: Vec<ProbeOutcome>,
+    pub min: Option<Duration>,
+    pub avg: Option<Duration>,
+    pub max: Option<Duration>,
+    pub lost: u32,
+}
+
+impl PingSummary {
+    fn from_probes(probes: Vec<ProbeOutcome>) -> PingSummary {
+        let rtts = probes
+            .iter()
+            .filter_map(|p| match p {
+                ProbeOutcome::Reply(d) => Some(*d),
+                ProbeOutcome::Lost => None,
+            })
+            .collect::<Vec<_>>();
+        let lost = u32::try_from(probes.len() - rtts.len())
+            .expect("number of lost probes should fit in u32");
+        let min = rtts.iter().copied().min();
+        let max = rtts.iter().copied().max();
+        let avg = if rtts.is_empty() {
+            None
+        } else {
+            Some(
+                rtts.iter().sum::<Duration>()
+                    / u32::try_from(rtts.len()).expect("rtts.len() should fit in u32"),
+            )
+        };
+        PingSummary {
+            probes,
+            min,
+            avg,
+            max,
+            lost,
+        }
+    }
+}
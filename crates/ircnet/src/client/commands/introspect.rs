@@ -0,0 +1,627 @@
+use super::Command;
+use super::LuserStats;
+use irctext::{
+    ClientMessage, Message, Payload, Reply, ReplyParts, Verb,
+    clientmsgs::{
+        Admin, Cap, Capability, CapabilityValue, CapLsRequest, Info, Links, Lusers, Version,
+    },
+};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// An error shared by every query in this module, covering the two ways a
+/// server can fail to answer one: disconnecting with an `ERROR` message, or
+/// returning some error reply the query has no more specific handling for.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ServerQueryError {
+    #[error("server sent ERROR message: {reason:?}")]
+    ErrorMessage { reason: String },
+    #[error("server returned unexpected error reply {code:03}: {reply:?}")]
+    UnexpectedError { code: u16, reply: String },
+}
+
+/// Checks `msg` for the two failure modes common to every query in this
+/// module, returning the resulting error if either applies.
+fn common_failure(msg: &Message) -> Option<ServerQueryError> {
+    match &msg.payload {
+        Payload::ClientMessage(ClientMessage::Error(err)) => Some(ServerQueryError::ErrorMessage {
+            reason: err.reason().to_string(),
+        }),
+        Payload::Reply(rpl) if rpl.is_error() => Some(ServerQueryError::UnexpectedError {
+            code: rpl.code(),
+            reply: msg.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Sends a `CAP LS` query and collects the capabilities the server
+/// advertises, the same way [`SessionBuilder`](super::super::SessionBuilder)
+/// does during login.
+///
+/// Unlike [`LusersQuery`], [`VersionQuery`], and [`AdminQuery`], this query
+/// never times out; it waits until the server either finishes the (possibly
+/// multiline) `CAP LS` response or rejects `CAP` outright.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapLsQuery {
+    outgoing: Vec<ClientMessage>,
+    capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+    done: Option<Result<Option<Vec<(Capability, Option<CapabilityValue>)>>, ServerQueryError>>,
+}
+
+impl CapLsQuery {
+    pub fn new(version: u32) -> CapLsQuery {
+        CapLsQuery {
+            outgoing: vec![CapLsRequest::new_with_version(version).into()],
+            capabilities: Vec::new(),
+            done: None,
+        }
+    }
+}
+
+impl Command for CapLsQuery {
+    type Output = Option<Vec<(Capability, Option<CapabilityValue>)>>;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        match &msg.payload {
+            Payload::ClientMessage(ClientMessage::Cap(Cap::LsResponse(r))) => {
+                self.capabilities.extend(r.capabilities.clone());
+                if !r.continued {
+                    self.done = Some(Ok(Some(std::mem::take(&mut self.capabilities))));
+                }
+                true
+            }
+            Payload::Reply(Reply::UnknownCommand(r)) if r.command() == &Verb::Cap => {
+                self.done = Some(Ok(None));
+                true
+            }
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<Self::Output, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+/// Sends a `LUSERS` query and collects whichever of the usual reply lines
+/// the server sends back within `timeout` of the most recent one, since
+/// servers vary in which lines they include and send no marker for the end
+/// of the set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LusersQuery {
+    outgoing: Vec<ClientMessage>,
+    timeout: Duration,
+    deadline: Instant,
+    stats: LuserStats,
+    done: Option<Result<LuserStats, ServerQueryError>>,
+}
+
+impl LusersQuery {
+    pub fn new(timeout: Duration) -> LusersQuery {
+        LusersQuery {
+            outgoing: vec![Lusers.into()],
+            timeout,
+            deadline: Instant::now() + timeout,
+            stats: LuserStats::default(),
+            done: None,
+        }
+    }
+}
+
+impl Command for LusersQuery {
+    type Output = LuserStats;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        let handled = match &msg.payload {
+            Payload::Reply(Reply::LuserClient(r)) => {
+                self.stats.luserclient_msg = Some(r.message().to_owned());
+                true
+            }
+            Payload::Reply(Reply::LuserOp(r)) => {
+                self.stats.operators = Some(r.ops());
+                true
+            }
+            Payload::Reply(Reply::LuserUnknown(r)) => {
+                self.stats.unknown_connections = Some(r.connections());
+                true
+            }
+            Payload::Reply(Reply::LuserChannels(r)) => {
+                self.stats.channels = Some(r.channels());
+                true
+            }
+            Payload::Reply(Reply::LuserMe(r)) => {
+                self.stats.luserme_msg = Some(r.message().to_owned());
+                true
+            }
+            Payload::Reply(Reply::LocalUsers(r)) => {
+                self.stats.local_clients = r.current_users();
+                self.stats.max_local_clients = r.max_users();
+                true
+            }
+            Payload::Reply(Reply::GlobalUsers(r)) => {
+                self.stats.global_clients = r.current_users();
+                self.stats.max_global_clients = r.max_users();
+                true
+            }
+            Payload::Reply(Reply::StatsConn(r)) => {
+                self.stats.statsconn_msg = Some(r.message().to_owned());
+                true
+            }
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    return true;
+                }
+                false
+            }
+        };
+        if handled {
+            self.deadline = Instant::now() + self.timeout;
+        }
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        if self.done.is_some() {
+            None
+        } else {
+            Some(self.deadline.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    fn handle_timeout(&mut self) {
+        if self.done.is_none() {
+            self.done = Some(Ok(std::mem::take(&mut self.stats)));
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<LuserStats, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+/// Sends a `VERSION` query and collects the reply, giving the server
+/// `timeout` of silence to answer (and to send along any trailing
+/// `RPL_ISUPPORT` lines, which are swallowed) before concluding it never
+/// will.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionQuery {
+    outgoing: Vec<ClientMessage>,
+    timeout: Duration,
+    deadline: Instant,
+    version: Option<VersionInfo>,
+    done: Option<Result<Option<VersionInfo>, ServerQueryError>>,
+}
+
+impl VersionQuery {
+    pub fn new(timeout: Duration) -> VersionQuery {
+        VersionQuery {
+            outgoing: vec![Version::new().into()],
+            timeout,
+            deadline: Instant::now() + timeout,
+            version: None,
+            done: None,
+        }
+    }
+}
+
+impl Command for VersionQuery {
+    type Output = Option<VersionInfo>;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        let handled = match &msg.payload {
+            Payload::Reply(Reply::Version(r)) => {
+                self.version = Some(VersionInfo {
+                    version: r.version().to_owned(),
+                    server: r.server().to_owned(),
+                    comments: r.comments().to_owned(),
+                });
+                true
+            }
+            Payload::Reply(Reply::ISupport(_)) => true,
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    return true;
+                }
+                false
+            }
+        };
+        if handled {
+            self.deadline = Instant::now() + self.timeout;
+        }
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        if self.done.is_some() {
+            None
+        } else {
+            Some(self.deadline.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    fn handle_timeout(&mut self) {
+        if self.done.is_none() {
+            self.done = Some(Ok(self.version.take()));
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<Option<VersionInfo>, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+/// The data collected by a [`VersionQuery`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct VersionInfo {
+    pub version: String,
+    pub server: String,
+    pub comments: String,
+}
+
+/// Sends an `ADMIN` query and collects the reply lines the server sends
+/// back within `timeout` of the most recent one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminQuery {
+    outgoing: Vec<ClientMessage>,
+    timeout: Duration,
+    deadline: Instant,
+    admin: AdminInfo,
+    done: Option<Result<Option<AdminInfo>, ServerQueryError>>,
+}
+
+impl AdminQuery {
+    pub fn new(timeout: Duration) -> AdminQuery {
+        AdminQuery {
+            outgoing: vec![Admin::new().into()],
+            timeout,
+            deadline: Instant::now() + timeout,
+            admin: AdminInfo::default(),
+            done: None,
+        }
+    }
+}
+
+impl Command for AdminQuery {
+    type Output = Option<AdminInfo>;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        let handled = match &msg.payload {
+            Payload::Reply(Reply::AdminMe(_)) => true,
+            Payload::Reply(Reply::AdminLoc1(r)) => {
+                self.admin.loc1 = Some(r.message().to_owned());
+                true
+            }
+            Payload::Reply(Reply::AdminLoc2(r)) => {
+                self.admin.loc2 = Some(r.message().to_owned());
+                true
+            }
+            Payload::Reply(Reply::AdminEmail(r)) => {
+                self.admin.email = Some(r.message().to_owned());
+                true
+            }
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    return true;
+                }
+                false
+            }
+        };
+        if handled {
+            self.deadline = Instant::now() + self.timeout;
+        }
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        if self.done.is_some() {
+            None
+        } else {
+            Some(self.deadline.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    fn handle_timeout(&mut self) {
+        if self.done.is_none() {
+            let admin = std::mem::take(&mut self.admin);
+            self.done = Some(Ok((admin != AdminInfo::default()).then_some(admin)));
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<Option<AdminInfo>, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+/// The data collected by an [`AdminQuery`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AdminInfo {
+    pub loc1: Option<String>,
+    pub loc2: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Sends a `LINKS` query and collects the server list, terminated by
+/// `RPL_ENDOFLINKS`, or `None` if the server doesn't recognize `LINKS`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinksQuery {
+    outgoing: Vec<ClientMessage>,
+    links: Vec<Link>,
+    done: Option<Result<Option<Vec<Link>>, ServerQueryError>>,
+}
+
+impl LinksQuery {
+    pub fn new() -> LinksQuery {
+        LinksQuery {
+            outgoing: vec![Links.into()],
+            links: Vec::new(),
+            done: None,
+        }
+    }
+}
+
+impl Default for LinksQuery {
+    fn default() -> LinksQuery {
+        LinksQuery::new()
+    }
+}
+
+impl Command for LinksQuery {
+    type Output = Option<Vec<Link>>;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        match &msg.payload {
+            Payload::Reply(Reply::Links(r)) => {
+                self.links.push(Link {
+                    server1: r.server1().to_owned(),
+                    server2: r.server2().to_owned(),
+                    hopcount: r.hopcount(),
+                    server_info: r.server_info().to_owned(),
+                });
+                true
+            }
+            Payload::Reply(Reply::EndOfLinks(_)) => {
+                self.done = Some(Ok(Some(std::mem::take(&mut self.links))));
+                true
+            }
+            Payload::Reply(Reply::UnknownCommand(r)) if r.command() == &Verb::Links => {
+                self.done = Some(Ok(None));
+                true
+            }
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<Option<Vec<Link>>, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+/// A single server listed in the reply to a [`LinksQuery`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Link {
+    pub server1: String,
+    pub server2: String,
+    pub hopcount: u32,
+    pub server_info: String,
+}
+
+/// Sends an `INFO` query and collects the message lines, terminated by
+/// `RPL_ENDOFINFO`, or `None` if the server doesn't recognize `INFO`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InfoQuery {
+    outgoing: Vec<ClientMessage>,
+    lines: Vec<String>,
+    done: Option<Result<Option<Vec<String>>, ServerQueryError>>,
+}
+
+impl InfoQuery {
+    pub fn new() -> InfoQuery {
+        InfoQuery {
+            outgoing: vec![Info.into()],
+            lines: Vec::new(),
+            done: None,
+        }
+    }
+}
+
+impl Default for InfoQuery {
+    fn default() -> InfoQuery {
+        InfoQuery::new()
+    }
+}
+
+impl Command for InfoQuery {
+    type Output = Option<Vec<String>>;
+    type Error = ServerQueryError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.done.is_some() {
+            return false;
+        }
+        match &msg.payload {
+            Payload::Reply(Reply::Info(r)) => {
+                self.lines.push(r.message().to_owned());
+                true
+            }
+            Payload::Reply(Reply::EndOfInfo(_)) => {
+                self.done = Some(Ok(Some(std::mem::take(&mut self.lines))));
+                true
+            }
+            Payload::Reply(Reply::UnknownCommand(r)) if r.command() == &Verb::Info => {
+                self.done = Some(Ok(None));
+                true
+            }
+            _ => {
+                if let Some(e) = common_failure(msg) {
+                    self.done = Some(Err(e));
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        self.done.is_some()
+    }
+
+    fn get_output(&mut self) -> Result<Option<Vec<String>>, ServerQueryError> {
+        self.done
+            .take()
+            .expect("get_output() should only be called when is_done() is true")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_query_collects_until_endoflinks() {
+        let mut cmd = LinksQuery::new();
+        assert!(!cmd.is_done());
+        let start = ":irc.example.com 001 me :Hi"
+            .parse::<Message>()
+            .unwrap();
+        assert!(!cmd.handle_message(&start));
+        let link = ":irc.example.com 364 me irc.example.com irc.example.com :1 Example server"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&link));
+        assert!(!cmd.is_done());
+        let end = ":irc.example.com 365 me * :End of LINKS list"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&end));
+        assert!(cmd.is_done());
+        let output = cmd.get_output().unwrap().unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].server1, "irc.example.com");
+        assert_eq!(output[0].hopcount, 1);
+    }
+
+    #[test]
+    fn links_query_reports_none_when_unsupported() {
+        let mut cmd = LinksQuery::new();
+        let unknown = ":irc.example.com 421 me LINKS :Unknown command"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&unknown));
+        assert!(cmd.is_done());
+        assert_eq!(cmd.get_output().unwrap(), None);
+    }
+}
@@ -1,9 +1,10 @@
 use super::Command;
 use irctext::{
-    ClientMessage, ClientSource, Message, Payload, Reply, ReplyParts,
+    ClientMessage, Message, Payload, Reply, ReplyParts,
     clientmsgs::Join,
     types::{Channel, ChannelStatus, Key, Nickname},
 };
+use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -15,30 +16,58 @@ pub struct JoinCommand {
 
 impl JoinCommand {
     pub fn new(channel: Channel) -> JoinCommand {
-        JoinCommand {
-            outgoing: vec![Join::new(channel).into()],
-            state: State::Start,
-        }
+        JoinCommand::new_many(std::iter::once(channel))
+            .expect("iterator of one channel should not be empty")
     }
 
     pub fn new_with_key(channel: Channel, key: Key) -> JoinCommand {
-        JoinCommand {
-            outgoing: vec![Join::new_with_key(channel, key).into()],
-            state: State::Start,
-        }
+        JoinCommand::new_many_with_keys(std::iter::once((channel, key)))
+            .expect("iterator of one channel should not be empty")
+    }
+
+    /// Join several channels at once via a single `JOIN` command with a
+    /// comma-separated channel list, tracking each channel's progress
+    /// independently.  Returns `None` if `channels` is empty.
+    pub fn new_many<I: IntoIterator<Item = Channel>>(channels: I) -> Option<JoinCommand> {
+        let channels = channels.into_iter().collect::<Vec<_>>();
+        let join = Join::new_multi(channels.iter().cloned())?;
+        Some(JoinCommand {
+            outgoing: vec![join.into()],
+            state: State::new(channels),
+        })
+    }
+
+    /// Join several channels at once, each with its own key, via a single
+    /// `JOIN` command with comma-separated channel and key lists, tracking
+    /// each channel's progress independently.  Returns `None` if `channels`
+    /// is empty.
+    pub fn new_many_with_keys<I: IntoIterator<Item = (Channel, Key)>>(
+        channels: I,
+    ) -> Option<JoinCommand> {
+        let pairs = channels.into_iter().collect::<Vec<_>>();
+        let channels = pairs.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>();
+        let join = Join::new_multi_with_keys(pairs)?;
+        Some(JoinCommand {
+            outgoing: vec![join.into()],
+            state: State::new(channels),
+        })
     }
 }
 
-// Order of replies on sucessful JOIN:
+// Order of replies on sucessful JOIN, per channel:
 //  - JOIN
 //  - One of:
 //     - RPL_TOPIC (332) + optional RPL_TOPICWHOTIME (333)
 //     - no replies
 //  - one or more RPL_NAMREPLY (353)
 //  - RPL_ENDOFNAMES (366)
+//
+// When joining several channels at once, the above sequences interleave
+// freely between channels, and a channel that fails to join produces one of
+// the error replies below instead of a JOIN echo and is dropped from the
+// sequence.
 
-// Possible error replies:
-//  - ERROR message
+// Possible per-channel error replies:
 //  - ERR_NOSUCHCHANNEL (403)
 //  - ERR_TOOMANYCHANNELS (405)
 //  - ERR_CHANNELISFULL (471)
@@ -46,6 +75,10 @@ impl JoinCommand {
 //  - ERR_BANNEDFROMCHAN (474)
 //  - ERR_BADCHANNELKEY (475)
 //  - ERR_BADCHANMASK (476)
+
+// Possible whole-command error replies (only possible before any channel has
+// received its JOIN echo):
+//  - ERROR message
 //  - RPL_TRYAGAIN (263)
 //  - ERR_INPUTTOOLONG (417)
 //  - ERR_UNKNOWNCOMMAND (421)
@@ -53,7 +86,7 @@ impl JoinCommand {
 //  - ERR_NEEDMOREPARAMS (461) ?
 
 impl Command for JoinCommand {
-    type Output = JoinOutput;
+    type Output = HashMap<Channel, Result<JoinOutput, JoinError>>;
     type Error = JoinError;
 
     fn get_client_messages(&mut self) -> Vec<ClientMessage> {
@@ -61,66 +94,7 @@ impl Command for JoinCommand {
     }
 
     fn handle_message(&mut self, msg: &Message) -> bool {
-        match &msg.payload {
-            Payload::Reply(rpl) => {
-                if rpl.is_error() && !matches!(rpl, Reply::NoMotd(_)) {
-                    if self.state != State::Start {
-                        return false;
-                    }
-                    let e = match rpl {
-                        Reply::NoSuchChannel(r) => JoinError::NoSuchChannel {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::TooManyChannels(r) => JoinError::TooManyChannels {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::ChannelIsFull(r) => JoinError::ChannelIsFull {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::InviteOnlyChan(r) => JoinError::InviteOnly {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::BannedFromChan(r) => JoinError::Banned {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::BadChannelKey(r) => JoinError::BadChannelKey {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::TryAgain(r) => JoinError::TryAgain {
-                            message: r.message().to_owned(),
-                        },
-                        Reply::InputTooLong(r) => JoinError::InputTooLong {
-                            message: r.message().to_string(),
-                        },
-                        Reply::UnknownCommand(r) => JoinError::UnknownCommand {
-                            command: r.command().to_string(),
-                            message: r.message().to_string(),
-                        },
-                        Reply::NotRegistered(r) => JoinError::NotRegistered {
-                            message: r.message().to_string(),
-                        },
-                        unexpected => JoinError::UnexpectedError {
-                            code: unexpected.code(),
-                            reply: msg.to_string(),
-                        },
-                    };
-                    self.state = State::Done(Some(Err(e)));
-                    true
-                } else {
-                    self.state.in_place(|state| state.handle_reply(rpl))
-                }
-            }
-            Payload::ClientMessage(ClientMessage::Error(err)) => {
-                self.state = State::Done(Some(Err(JoinError::ErrorMessage {
-                    reason: err.reason().to_string(),
-                })));
-                true
-            }
-            Payload::ClientMessage(ClientMessage::Join(_)) => {
-                self.state.in_place(State::handle_join)
-            }
-            Payload::ClientMessage(_) => false,
-        }
+        self.state.in_place(|state| state.handle(msg))
     }
 
     fn get_timeout(&mut self) -> Option<Duration> {
@@ -133,7 +107,7 @@ impl Command for JoinCommand {
         matches!(self.state, State::Done(_))
     }
 
-    fn get_output(&mut self) -> Result<JoinOutput, JoinError> {
+    fn get_output(&mut self) -> Result<Self::Output, JoinError> {
         if let State::Done(ref mut r) = self.state {
             r.take()
                 .expect("get_output() should not be called more than once")
@@ -143,24 +117,29 @@ impl Command for JoinCommand {
     }
 }
 
+type Results = HashMap<Channel, Result<JoinOutput, JoinError>>;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum State {
-    Start,
-    GotJoin,
-    GotTopic {
-        topic: String,
-    },
-    GotTopicWho {
-        topic: String,
-        topic_setter: ClientSource,
-        topic_set_at: u64,
+    Active {
+        pending: HashMap<Channel, ChannelState>,
+        results: Results,
     },
-    GotNamReply(JoinOutput),
-    Done(Option<Result<JoinOutput, JoinError>>),
+    Done(Option<Result<Results, JoinError>>),
     Void,
 }
 
 impl State {
+    fn new(channels: Vec<Channel>) -> State {
+        State::Active {
+            pending: channels
+                .into_iter()
+                .map(|c| (c, ChannelState::AwaitingJoin))
+                .collect(),
+            results: HashMap::new(),
+        }
+    }
+
     fn in_place<F>(&mut self, f: F) -> bool
     where
         F: FnOnce(Self) -> (State, bool),
@@ -171,84 +150,257 @@ impl State {
         b
     }
 
-    fn handle_reply(self, rpl: &Reply) -> (State, bool) {
-        match (self, rpl) {
-            (State::GotJoin, Reply::Topic(r)) => (
-                State::GotTopic {
-                    topic: r.topic().to_owned(),
-                },
+    fn handle(self, msg: &Message) -> (State, bool) {
+        let State::Active { pending, results } = self else {
+            return (self, false);
+        };
+        match &msg.payload {
+            Payload::Reply(rpl) if rpl.is_error() && !matches!(rpl, Reply::NoMotd(_)) => {
+                handle_error_reply(pending, results, rpl, msg)
+            }
+            Payload::Reply(rpl) => {
+                let mut pending = pending;
+                let mut results = results;
+                let handled = handle_reply(&mut pending, &mut results, rpl);
+                finish_or_continue(pending, results, handled)
+            }
+            Payload::ClientMessage(ClientMessage::Error(err)) => (
+                State::Done(Some(Err(JoinError::ErrorMessage {
+                    reason: err.reason().to_string(),
+                }))),
                 true,
             ),
-            (State::GotTopic { topic }, Reply::TopicWhoTime(r)) => (
-                State::GotTopicWho {
+            Payload::ClientMessage(ClientMessage::Join(join)) => {
+                let mut pending = pending;
+                let mut handled = false;
+                for channel in join.channels() {
+                    if matches!(pending.get(channel), Some(ChannelState::AwaitingJoin)) {
+                        pending.insert(channel.clone(), ChannelState::GotJoin);
+                        handled = true;
+                    }
+                }
+                finish_or_continue(pending, results, handled)
+            }
+            Payload::ClientMessage(_) => (State::Active { pending, results }, false),
+        }
+    }
+}
+
+fn finish_or_continue(
+    pending: HashMap<Channel, ChannelState>,
+    results: Results,
+    handled: bool,
+) -> (State, bool) {
+    if pending.is_empty() {
+        (State::Done(Some(Ok(results))), handled)
+    } else {
+        (State::Active { pending, results }, handled)
+    }
+}
+
+fn handle_reply(
+    pending: &mut HashMap<Channel, ChannelState>,
+    results: &mut Results,
+    rpl: &Reply,
+) -> bool {
+    let channel = match rpl {
+        Reply::Topic(r) => r.channel(),
+        Reply::TopicWhoTime(r) => r.channel(),
+        Reply::NamReply(r) => r.channel(),
+        Reply::EndOfNames(r) => r.channel(),
+        _ => return false,
+    };
+    let Some(st) = pending.remove(channel) else {
+        return false;
+    };
+    match (st, rpl) {
+        (ChannelState::GotJoin, Reply::Topic(r)) => {
+            pending.insert(
+                channel.clone(),
+                ChannelState::GotTopic {
+                    topic: r.topic().to_owned(),
+                },
+            );
+            true
+        }
+        (ChannelState::GotTopic { topic }, Reply::TopicWhoTime(r)) => {
+            pending.insert(
+                channel.clone(),
+                ChannelState::GotTopicWho {
                     topic,
-                    topic_setter: r.user().clone(),
+                    topic_setter: r.nickname().clone(),
                     topic_set_at: r.setat(),
                 },
-                true,
-            ),
-            (State::GotJoin, Reply::NamReply(r)) => (
-                State::GotNamReply(JoinOutput {
+            );
+            true
+        }
+        (ChannelState::GotJoin, Reply::NamReply(r)) => {
+            pending.insert(
+                channel.clone(),
+                ChannelState::GotNamReply(JoinOutput {
                     topic: None,
                     topic_setter: None,
                     topic_set_at: None,
-                    channel_status: r.channel_status(),
+                    channel_status: r.channel_status().clone(),
                     users: r.clients().to_vec(),
                 }),
-                true,
-            ),
-            (State::GotTopic { topic }, Reply::NamReply(r)) => (
-                State::GotNamReply(JoinOutput {
+            );
+            true
+        }
+        (ChannelState::GotTopic { topic }, Reply::NamReply(r)) => {
+            pending.insert(
+                channel.clone(),
+                ChannelState::GotNamReply(JoinOutput {
                     topic: Some(topic),
                     topic_setter: None,
                     topic_set_at: None,
-                    channel_status: r.channel_status(),
+                    channel_status: r.channel_status().clone(),
                     users: r.clients().to_vec(),
                 }),
-                true,
-            ),
-            (
-                State::GotTopicWho {
-                    topic,
-                    topic_setter,
-                    topic_set_at,
-                },
-                Reply::NamReply(r),
-            ) => (
-                State::GotNamReply(JoinOutput {
+            );
+            true
+        }
+        (
+            ChannelState::GotTopicWho {
+                topic,
+                topic_setter,
+                topic_set_at,
+            },
+            Reply::NamReply(r),
+        ) => {
+            pending.insert(
+                channel.clone(),
+                ChannelState::GotNamReply(JoinOutput {
                     topic: Some(topic),
                     topic_setter: Some(topic_setter),
                     topic_set_at: Some(topic_set_at),
-                    channel_status: r.channel_status(),
+                    channel_status: r.channel_status().clone(),
                     users: r.clients().to_vec(),
                 }),
-                true,
-            ),
-            (State::GotNamReply(mut output), Reply::NamReply(r)) => {
-                output.users.extend(r.clients().to_vec());
-                (State::GotNamReply(output), true)
-            }
-            (State::GotNamReply(output), Reply::EndOfNames(_)) => {
-                (State::Done(Some(Ok(output))), true)
-            }
-            (State::Void, _) => panic!("handle_reply() called on Void join state"),
-            (st, _) => (st, false),
+            );
+            true
+        }
+        (ChannelState::GotNamReply(mut output), Reply::NamReply(r)) => {
+            output.users.extend(r.clients().to_vec());
+            pending.insert(channel.clone(), ChannelState::GotNamReply(output));
+            true
+        }
+        (ChannelState::GotNamReply(output), Reply::EndOfNames(_)) => {
+            results.insert(channel.clone(), Ok(output));
+            true
+        }
+        (st, _) => {
+            pending.insert(channel.clone(), st);
+            false
         }
     }
+}
 
-    fn handle_join(self) -> (State, bool) {
-        match self {
-            State::Start => (State::GotJoin, true),
-            State::Void => panic!("handle_join() called on Void join state"),
-            st => (st, false),
+fn handle_error_reply(
+    mut pending: HashMap<Channel, ChannelState>,
+    mut results: Results,
+    rpl: &Reply,
+    msg: &Message,
+) -> (State, bool) {
+    let channel_error = match rpl {
+        Reply::NoSuchChannel(r) => Some((
+            r.channel().clone(),
+            JoinError::NoSuchChannel {
+                message: r.message().to_owned(),
+            },
+        )),
+        Reply::TooManyChannels(r) => Some((
+            r.channel().clone(),
+            JoinError::TooManyChannels {
+                message: r.message().to_owned(),
+            },
+        )),
+        Reply::ChannelIsFull(r) => Some((
+            r.channel().clone(),
+            JoinError::ChannelIsFull {
+                message: r.message().to_owned(),
+            },
+        )),
+        Reply::InviteOnlyChan(r) => Some((
+            r.channel().clone(),
+            JoinError::InviteOnly {
+                message: r.message().to_owned(),
+            },
+        )),
+        Reply::BannedFromChan(r) => Some((
+            r.channel().clone(),
+            JoinError::Banned {
+                message: r.message().to_owned(),
+            },
+        )),
+        Reply::BadChannelKey(r) => Some((
+            r.channel().clone(),
+            JoinError::BadChannelKey {
+                message: r.message().to_owned(),
+            },
+        )),
+        _ => None,
+    };
+    if let Some((channel, e)) = channel_error {
+        if matches!(pending.get(&channel), Some(ChannelState::AwaitingJoin)) {
+            pending.remove(&channel);
+            results.insert(channel, Err(e));
+            return finish_or_continue(pending, results, true);
         }
+        return (State::Active { pending, results }, false);
+    }
+
+    // These replies carry no channel of their own, so they can only be
+    // attributed to the command as a whole, and only make sense before any
+    // channel has received its JOIN echo.
+    let all_awaiting = results.is_empty()
+        && pending
+            .values()
+            .all(|st| matches!(st, ChannelState::AwaitingJoin));
+    if !all_awaiting {
+        return (State::Active { pending, results }, false);
     }
+    let e = match rpl {
+        Reply::TryAgain(r) => JoinError::TryAgain {
+            message: r.message().to_owned(),
+        },
+        Reply::InputTooLong(r) => JoinError::InputTooLong {
+            message: r.message().to_string(),
+        },
+        Reply::UnknownCommand(r) => JoinError::UnknownCommand {
+            command: r.command().to_string(),
+            message: r.message().to_string(),
+        },
+        Reply::NotRegistered(r) => JoinError::NotRegistered {
+            message: r.message().to_string(),
+        },
+        unexpected => JoinError::UnexpectedError {
+            code: unexpected.code(),
+            reply: msg.to_string(),
+        },
+    };
+    (State::Done(Some(Err(e))), true)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum ChannelState {
+    AwaitingJoin,
+    GotJoin,
+    GotTopic {
+        topic: String,
+    },
+    GotTopicWho {
+        topic: String,
+        topic_setter: Nickname,
+        topic_set_at: u64,
+    },
+    GotNamReply(JoinOutput),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct JoinOutput {
     pub topic: Option<String>,
-    pub topic_setter: Option<ClientSource>,
+    pub topic_setter: Option<Nickname>,
     pub topic_set_at: Option<u64>,
     pub channel_status: ChannelStatus,
     pub users: Vec<(Option<char>, Nickname)>,
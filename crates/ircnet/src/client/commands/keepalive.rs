@@ -0,0 +1,126 @@
+use super::Command;
+use irctext::{ClientMessage, FinalParam, Message, Payload, clientmsgs::Ping};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// A long-lived `Command` that watches the connection for lag by sending a
+/// server `PING` at a fixed interval and expecting the matching `PONG` back
+/// within a deadline.
+///
+/// Unlike most `Command`s, `KeepAlive` never completes on its own while the
+/// connection is healthy; it only reports itself done — via `is_done()` —
+/// once a `PONG` fails to arrive in time, at which point `get_output()`
+/// signals the dead connection so the caller can reconnect.  In the
+/// meantime, callers can poll [`KeepAlive::last_rtt`] after each handled
+/// message to track measured latency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeepAlive {
+    interval: Duration,
+    timeout: Duration,
+    outgoing: Vec<ClientMessage>,
+    next_seq: u64,
+    state: State,
+    last_rtt: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum State {
+    WaitingInterval {
+        deadline: Instant,
+    },
+    AwaitingPong {
+        token: FinalParam,
+        sent_at: Instant,
+        deadline: Instant,
+    },
+    Dead,
+}
+
+impl KeepAlive {
+    /// Ping the server every `interval`, giving it up to `timeout` to send
+    /// back the matching `PONG` before considering the connection dead.
+    pub fn new(interval: Duration, timeout: Duration) -> KeepAlive {
+        KeepAlive {
+            interval,
+            timeout,
+            outgoing: Vec::new(),
+            next_seq: 0,
+            state: State::WaitingInterval {
+                deadline: Instant::now() + interval,
+            },
+            last_rtt: None,
+        }
+    }
+
+    /// Returns the round-trip time of the most recently completed `PING`,
+    /// or `None` if none has completed yet.
+    pub fn last_rtt(&self) -> Option<Duration> {
+        self.last_rtt
+    }
+
+    fn send_ping(&mut self) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let token = FinalParam::try_from(format!("keepalive-{seq}"))
+            .expect("keepalive token should be valid FinalParam");
+        self.outgoing.push(Ping::new(token.clone()).into());
+        let now = Instant::now();
+        self.state = State::AwaitingPong {
+            token,
+            sent_at: now,
+            deadline: now + self.timeout,
+        };
+    }
+}
+
+impl Command for KeepAlive {
+    type Output = ();
+    type Error = Infallible;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        let State::AwaitingPong { token, sent_at, .. } = &self.state else {
+            return false;
+        };
+        let Payload::ClientMessage(ClientMessage::Pong(pong)) = &msg.payload else {
+            return false;
+        };
+        if pong.token() != token {
+            // Reply to some stale, already-timed-out ping; ignore it.
+            return false;
+        }
+        self.last_rtt = Some(sent_at.elapsed());
+        self.state = State::WaitingInterval {
+            deadline: Instant::now() + self.interval,
+        };
+        true
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        match self.state {
+            State::WaitingInterval { deadline } | State::AwaitingPong { deadline, .. } => {
+                Some(deadline.saturating_duration_since(Instant::now()))
+            }
+            State::Dead => None,
+        }
+    }
+
+    fn handle_timeout(&mut self) {
+        match self.state {
+            State::WaitingInterval { .. } => self.send_ping(),
+            State::AwaitingPong { .. } => self.state = State::Dead,
+            State::Dead => (),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(self.state, State::Dead)
+    }
+
+    fn get_output(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
@@ -18,6 +18,24 @@ impl ListCommand {
             state: State::new(),
         }
     }
+
+    /// Like `new`, but first checks every `ELIST` filter in `msg` against
+    /// `elist_flags` (the server's `ELIST` ISUPPORT token, as returned by
+    /// [`ISupport::elist`](irctext::types::ISupport::elist)), returning
+    /// `UnsupportedFilter` instead of silently sending a filter the server
+    /// never advertised support for.
+    pub fn new_validated(msg: List, elist_flags: &str) -> Result<ListCommand, ListError> {
+        for cond in msg.elistconds() {
+            let flag = cond.flag();
+            if !elist_flags.contains(flag) {
+                return Err(ListError::UnsupportedFilter {
+                    flag,
+                    condition: cond.as_str().to_owned(),
+                });
+            }
+        }
+        Ok(ListCommand::new(msg))
+    }
 }
 
 impl Command for ListCommand {
@@ -162,4 +180,8 @@ pub enum ListError {
         expecting: &'static str,
         msg: String,
     },
+    #[error(
+        "ELIST filter {condition:?} requires flag {flag:?}, which the server did not advertise support for"
+    )]
+    UnsupportedFilter { flag: char, condition: String },
 }
@@ -1,15 +1,27 @@
 use super::Command;
+use crate::sasl::{SaslError, SaslFlow, SaslMachine, SaslMechanism};
+use bytes::Bytes;
 use irctext::{
-    ClientMessage, ClientMessageParts, FinalParam, Message, Payload, Reply, ReplyParts,
-    clientmsgs::{Mode, Nick, Pass, User},
-    types::{ISupportParam, ModeString, Nickname, ReplyTarget, Username},
+    ClientMessage, ClientMessageParts, FinalParam, Message, Payload, Reply, ReplyParts, Verb,
+    clientmsgs::{
+        Authenticate, Cap, CapEnd, CapLsRequest, CapReq, Capability, CapabilityRequest,
+        CapabilityValue, Mode, Nick, Pass, User,
+    },
+    types::{ISupport, ISupportParam, ModeString, Nickname, ReplyTarget, Username},
 };
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
-/// How long to wait for an optional `MODE` or `RPL_UMODEIS` (221) message
-/// after receiving the MOTD
-const MODE_TIMEOUT: Duration = Duration::from_secs(1);
+/// The version of `CAP LS` sent when [`LoginParams::sasl`] or
+/// [`LoginParams::capabilities`] is set
+const CAP_VERSION: u32 = 302;
+
+/// The maximum number of nicknames to synthesize via
+/// [`LoginParams::nick_fallback_policy`] after [`LoginParams::alt_nicknames`]
+/// is exhausted, to put a bound on retries if the server keeps rejecting
+/// every candidate
+const MAX_GENERATED_NICKS: u32 = 9;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -18,30 +30,213 @@ pub struct LoginParams {
     pub nickname: Nickname,
     pub username: Username,
     pub realname: FinalParam,
+
+    /// Nicknames to try, in order, if `nickname` (or a later candidate) is
+    /// rejected by the server with `ERR_ERRONEUSNICKNAME` (432),
+    /// `ERR_NICKNAMEINUSE` (433), or `ERR_NICKCOLLISION` (436) before
+    /// registration completes.  [`LoginOutput::my_nick`] records whichever
+    /// candidate was ultimately accepted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alt_nicknames: Vec<Nickname>,
+
+    /// If set, once `alt_nicknames` is exhausted, keep synthesizing further
+    /// candidates according to this policy (up to [`MAX_GENERATED_NICKS`]
+    /// of them) instead of immediately giving up.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nick_fallback_policy: Option<NickFallbackPolicy>,
+
+    /// If set, negotiate the `sasl` capability via `CAP` and authenticate as
+    /// `nickname` using the given mechanism before completing registration.
+    /// If the server doesn't support `CAP` or doesn't advertise `sasl`,
+    /// login falls back to plain `PASS`/`NICK`/`USER` registration.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sasl: Option<SaslCredentials>,
+
+    /// Additional capabilities (beyond `sasl`, which is requested
+    /// automatically whenever [`LoginParams::sasl`] is set) to request via
+    /// `CAP REQ` if the server advertises them.  Setting this triggers `CAP`
+    /// negotiation even when `sasl` is unset.  Capabilities that the server
+    /// doesn't advertise are silently skipped rather than treated as an
+    /// error, and the negotiation as a whole degrades to plain
+    /// `PASS`/`NICK`/`USER` registration if the server doesn't support `CAP`
+    /// at all (`ERR_UNKNOWNCOMMAND` (421)).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capabilities: Vec<Capability>,
+
+    /// The overall deadline for registration to complete, covering CAP
+    /// negotiation, SASL, and the numeric welcome sequence up through the
+    /// MOTD.  If this elapses first, login fails with
+    /// [`LoginError::RegistrationTimedOut`].
+    pub registration_timeout: Duration,
+
+    /// How long to wait for an optional `MODE` or `RPL_UMODEIS` (221)
+    /// message after receiving the MOTD before giving up and returning
+    /// whatever output has been gathered so far
+    pub mode_timeout: Duration,
+}
+
+/// A policy for synthesizing further nickname candidates once
+/// [`LoginParams::alt_nicknames`] has been exhausted
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NickFallbackPolicy {
+    /// Append an underscore to the previous candidate: `nick_`, `nick__`,
+    /// etc.
+    AppendUnderscore,
+    /// Append an increasing numeric suffix to the original nickname:
+    /// `nick1`, `nick2`, etc.
+    AppendNumber,
+}
+
+/// Credentials for authenticating via SASL, used alongside [`LoginParams::nickname`]
+/// as the authentication (and authorization) identity
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SaslCredentials {
+    /// Mechanisms to try, strongest/most-preferred first.  Login intersects
+    /// this list with the mechanisms the server advertises in its `sasl=`
+    /// `CAP LS` value (if given) and attempts the surviving candidates in
+    /// order, moving on to the next one whenever the server replies with
+    /// `ERR_SASLFAIL` (904) or `ERR_SASLTOOLONG` (905) instead of aborting
+    /// the whole login.  Login fails once every candidate has been tried
+    /// (or, if the server didn't advertise a mechanism list at all, once
+    /// every mechanism in this list has failed).
+    ///
+    /// Must be nonempty.
+    pub mechanisms: Vec<SaslMechanism>,
+    pub password: String,
+
+    /// The authorization identity to request, if different from
+    /// [`LoginParams::nickname`] (the authentication identity).  Only
+    /// consulted by mechanisms that distinguish the two, currently just
+    /// `PLAIN`; `None` requests no particular authorization identity,
+    /// letting the server derive one from the authentication identity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub authzid: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Login {
     outgoing: Vec<ClientMessage>,
     state: State,
+    nick_fallback: NickFallback,
+    mode_timeout: Duration,
+    deadline: Instant,
+    /// RFC 5929 `tls-server-end-point` channel-binding data for the
+    /// underlying connection, forwarded to a `-PLUS` SCRAM mechanism if one
+    /// is requested via [`LoginParams::sasl`]
+    channel_binding: Option<Bytes>,
 }
 
 impl Login {
-    pub fn new(params: LoginParams) -> Login {
+    /// `channel_binding` should be the value of
+    /// [`Client::channel_binding`](crate::client::Client::channel_binding)
+    /// for the connection this command will run on, for use by a `-PLUS`
+    /// SCRAM mechanism if [`LoginParams::sasl`] requests one.
+    pub fn new(params: LoginParams, channel_binding: Option<Bytes>) -> Login {
+        let nickname = params.nickname.clone();
+        let nick_fallback = NickFallback::new(
+            params.nickname.clone(),
+            params.alt_nicknames,
+            params.nick_fallback_policy,
+        );
+        let mode_timeout = params.mode_timeout;
+        let deadline = Instant::now() + params.registration_timeout;
         let pass = ClientMessage::from(Pass::new(params.password));
         let nick = ClientMessage::from(Nick::new(params.nickname));
         let user = ClientMessage::from(User::new(params.username, params.realname));
-        Login {
-            outgoing: vec![pass, nick, user],
-            state: State::Start,
+        if params.sasl.is_some() || !params.capabilities.is_empty() {
+            let cap_ls = ClientMessage::from(CapLsRequest::new_with_version(CAP_VERSION));
+            Login {
+                outgoing: vec![cap_ls, pass, nick, user],
+                state: State::AwaitingCapLs {
+                    sasl: params.sasl,
+                    nickname,
+                    requested: params.capabilities,
+                    offered: Vec::new(),
+                },
+                nick_fallback,
+                mode_timeout,
+                deadline,
+                channel_binding,
+            }
+        } else {
+            Login {
+                outgoing: vec![pass, nick, user],
+                state: State::Start {
+                    capabilities: Vec::new(),
+                    account: None,
+                },
+                nick_fallback,
+                mode_timeout,
+                deadline,
+                channel_binding,
+            }
+        }
+    }
+}
+
+/// Tracks the ordered queue of nicknames still to be tried during
+/// registration, falling back from [`LoginParams::alt_nicknames`] to
+/// policy-generated candidates once the former is exhausted
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct NickFallback {
+    base: Nickname,
+    queue: VecDeque<Nickname>,
+    policy: Option<NickFallbackPolicy>,
+    generated: u32,
+    current: Nickname,
+}
+
+impl NickFallback {
+    fn new(
+        nickname: Nickname,
+        alt_nicknames: Vec<Nickname>,
+        policy: Option<NickFallbackPolicy>,
+    ) -> NickFallback {
+        NickFallback {
+            base: nickname.clone(),
+            queue: alt_nicknames.into_iter().collect(),
+            policy,
+            generated: 0,
+            current: nickname,
+        }
+    }
+
+    /// Returns the next nickname candidate to try, recording it as the new
+    /// `current` candidate, or `None` if `alt_nicknames` and (if
+    /// applicable) the policy-generated candidates are exhausted
+    #[expect(clippy::missing_panics_doc)]
+    fn next(&mut self) -> Option<Nickname> {
+        if let Some(nick) = self.queue.pop_front() {
+            self.current = nick.clone();
+            return Some(nick);
+        }
+        let policy = self.policy?;
+        if self.generated >= MAX_GENERATED_NICKS {
+            return None;
         }
+        self.generated += 1;
+        let candidate = match policy {
+            NickFallbackPolicy::AppendUnderscore => format!("{}_", self.current),
+            NickFallbackPolicy::AppendNumber => format!("{}{}", self.base, self.generated),
+        };
+        let nick = candidate
+            .parse::<Nickname>()
+            .expect("appending to a valid nickname should still be valid");
+        self.current = nick.clone();
+        Some(nick)
     }
 }
 
 // Order of replies on successful login:
 // - With SASL:
+//     - CAP * LS (one or more, the last with no trailing "*" parameter)
+//     - CAP * ACK :sasl
+//     - AUTHENTICATE + (mechanism continuation prompt)
 //     - RPL_LOGGEDIN (900)
 //     - RPL_SASLSUCCESS (903)
+//     - CAP END (sent by us)
 // - RPL_WELCOME (001)
 // - RPL_YOURHOST (002)
 // - RPL_CREATED (003)
@@ -62,12 +257,15 @@ impl Login {
 //  - ERROR message
 //  - ERR_INPUTTOOLONG (417)
 //  - ERR_UNKNOWNCOMMAND (421)
-//      - When using SASL, this may be sent in reply to CAP if the server
-//        doesn't support the command, in which case we should gracefully fall
-//        back to plain login.
+//      - When negotiating capabilities, this may be sent in reply to CAP if
+//        the server doesn't support the command, in which case we should
+//        gracefully fall back to plain login.
 //  - ERR_ERRONEUSNICKNAME (432)
 //  - ERR_NICKNAMEINUSE (433)
 //  - ERR_NICKCOLLISION (436) ?
+//      - If received before RPL_WELCOME, these instead trigger a retry with
+//        the next candidate from `NickFallback`, unless candidates are
+//        exhausted.
 //  - ERR_PASSWDMISMATCH (464)
 //  - ERR_YOUREBANNEDCREEP (465)
 //  - With SASL:
@@ -76,6 +274,8 @@ impl Login {
 //      - ERR_SASLTOOLONG (905) ?
 //      - ERR_SASLABORTED (906) ?
 //      - ERR_SASLALREADY (907)
+//  - LoginParams::registration_timeout elapsing before registration
+//    completes (get_timeout()/handle_timeout())
 
 impl Command for Login {
     type Output = LoginOutput;
@@ -88,6 +288,41 @@ impl Command for Login {
     fn handle_message(&mut self, msg: &Message) -> bool {
         match &msg.payload {
             Payload::Reply(rpl) => {
+                if matches!(self.state, State::AwaitingCapLs { .. })
+                    && matches!(rpl, Reply::UnknownCommand(r) if *r.command() == Verb::Cap)
+                {
+                    // The server doesn't support CAP at all, so gracefully
+                    // fall back to plain login; PASS/NICK/USER were already
+                    // queued alongside CAP LS.
+                    self.state = State::Start {
+                        capabilities: Vec::new(),
+                        account: None,
+                    };
+                    return true;
+                }
+                if self.state.is_pre_welcome()
+                    && matches!(
+                        rpl,
+                        Reply::ErroneousNickname(_)
+                            | Reply::NicknameInUse(_)
+                            | Reply::NickCollision(_)
+                    )
+                {
+                    if let Some(next_nick) = self.nick_fallback.next() {
+                        self.state.update_sasl_nickname(next_nick.clone());
+                        self.outgoing
+                            .push(ClientMessage::from(Nick::new(next_nick)));
+                        return true;
+                    }
+                }
+                if matches!(self.state, State::AwaitingSasl { .. })
+                    && matches!(rpl, Reply::SaslFail(_) | Reply::SaslTooLong(_))
+                {
+                    let channel_binding = self.channel_binding.clone();
+                    return self.state.in_place(&mut self.outgoing, |state, outgoing| {
+                        state.retry_sasl(rpl, outgoing, channel_binding)
+                    });
+                }
                 if rpl.is_error() && !matches!(rpl, Reply::NoMotd(_)) {
                     let e = match rpl {
                         Reply::InputTooLong(r) => LoginError::InputTooLong {
@@ -115,6 +350,18 @@ impl Command for Login {
                         Reply::YoureBannedCreep(r) => LoginError::Banned {
                             message: r.message().to_string(),
                         },
+                        Reply::NickLocked(r) => LoginError::NickLocked {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslFail(r) => LoginError::SaslFail {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslTooLong(r) => LoginError::SaslTooLong {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslAlready(r) => LoginError::SaslAlready {
+                            message: r.message().to_string(),
+                        },
                         unexpected => LoginError::UnexpectedError {
                             code: unexpected.code(),
                             reply: msg.to_string(),
@@ -123,7 +370,10 @@ impl Command for Login {
                     self.state = State::Done(Some(Err(e)));
                     true
                 } else {
-                    self.state.in_place(|state| state.handle_reply(rpl))
+                    let mode_timeout = self.mode_timeout;
+                    self.state.in_place(&mut self.outgoing, |state, outgoing| {
+                        state.handle_reply(rpl, outgoing, mode_timeout)
+                    })
                 }
             }
             Payload::ClientMessage(climsg) => match climsg {
@@ -133,7 +383,20 @@ impl Command for Login {
                     })));
                     true
                 }
-                ClientMessage::Mode(mode) => self.state.in_place(|state| state.handle_mode(mode)),
+                ClientMessage::Cap(cap) => {
+                    let channel_binding = self.channel_binding.clone();
+                    self.state.in_place(&mut self.outgoing, |state, outgoing| {
+                        state.handle_cap(cap, outgoing, channel_binding)
+                    })
+                }
+                ClientMessage::Authenticate(auth) => {
+                    self.state.in_place(&mut self.outgoing, |state, outgoing| {
+                        state.handle_authenticate(auth, outgoing)
+                    })
+                }
+                ClientMessage::Mode(mode) => self
+                    .state
+                    .in_place(&mut self.outgoing, |state, _| state.handle_mode(mode)),
                 ClientMessage::Ping(_) | ClientMessage::PrivMsg(_) | ClientMessage::Notice(_) => {
                     false
                 }
@@ -148,6 +411,8 @@ impl Command for Login {
         } = self.state
         {
             timeout.take()
+        } else if self.state.tracks_registration_deadline() {
+            Some(self.deadline.saturating_duration_since(Instant::now()))
         } else {
             None
         }
@@ -155,11 +420,15 @@ impl Command for Login {
 
     fn handle_timeout(&mut self) {
         let state = std::mem::replace(&mut self.state, State::Void);
+        let expecting = state.expecting();
         self.state = match state {
             State::AwaitingMode {
                 timeout: None,
                 output,
             } => State::Done(Some(Ok(output))),
+            other if other.tracks_registration_deadline() => {
+                State::Done(Some(Err(LoginError::RegistrationTimedOut { expecting })))
+            }
             other => other,
         };
     }
@@ -180,15 +449,59 @@ impl Command for Login {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum State {
-    Start,
+    AwaitingCapLs {
+        sasl: Option<SaslCredentials>,
+        nickname: Nickname,
+        /// Capabilities requested via [`LoginParams::capabilities`]; `sasl`
+        /// is requested in addition to these if offered
+        requested: Vec<Capability>,
+        /// Capabilities (with values, if any) accumulated across all `CAP
+        /// LS` response lines so far
+        offered: Vec<(Capability, Option<CapabilityValue>)>,
+    },
+    AwaitingCapAck {
+        sasl: Option<SaslCredentials>,
+        nickname: Nickname,
+        /// The capabilities (with their advertised values) requested via
+        /// `CAP REQ`, to be recorded as enabled once acknowledged
+        wanted: Vec<(Capability, Option<CapabilityValue>)>,
+        /// [`SaslCredentials::mechanisms`], filtered down to (and reordered
+        /// to match the preference order of) whatever the server's `sasl=`
+        /// `CAP LS` value advertised, if it gave one; otherwise the
+        /// unfiltered list, since we have no way to know which of our
+        /// candidates the server supports ahead of time
+        sasl_candidates: VecDeque<SaslMechanism>,
+    },
+    AwaitingSasl {
+        machine: SaslMachine,
+        /// Remaining mechanisms to fall back to, strongest-first, if the
+        /// current attempt fails with `ERR_SASLFAIL` (904) or
+        /// `ERR_SASLTOOLONG` (905)
+        candidates: VecDeque<SaslMechanism>,
+        authzid: Option<String>,
+        nickname: Nickname,
+        password: String,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
+    },
+    Start {
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
+    },
     Got001 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got002 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got003 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got004(LoginOutput),
     Got005(LoginOutput),
@@ -203,12 +516,83 @@ enum State {
 }
 
 impl State {
-    fn in_place<F>(&mut self, f: F) -> bool
+    /// Returns true if this state is reached before `RPL_WELCOME` (001), and
+    /// thus a nickname-rejection error reply should be handled via
+    /// `NickFallback` rather than as a terminal error
+    fn is_pre_welcome(&self) -> bool {
+        matches!(
+            self,
+            State::AwaitingCapLs { .. }
+                | State::AwaitingCapAck { .. }
+                | State::AwaitingSasl { .. }
+                | State::Start { .. }
+        )
+    }
+
+    /// Updates the nickname that will be used to authenticate via SASL once
+    /// `CAP ACK` is received, so that a nick retried via `NickFallback`
+    /// before SASL begins is the one actually authenticated
+    fn update_sasl_nickname(&mut self, nick: Nickname) {
+        match self {
+            State::AwaitingCapLs { nickname, .. }
+            | State::AwaitingCapAck { nickname, .. }
+            | State::AwaitingSasl { nickname, .. } => {
+                *nickname = nick;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns true if this state is subject to [`LoginParams::registration_timeout`]
+    /// (i.e. it is reached before `AwaitingMode`, which has its own
+    /// [`LoginParams::mode_timeout`])
+    fn tracks_registration_deadline(&self) -> bool {
+        matches!(
+            self,
+            State::AwaitingCapLs { .. }
+                | State::AwaitingCapAck { .. }
+                | State::AwaitingSasl { .. }
+                | State::Start { .. }
+                | State::Got001 { .. }
+                | State::Got002 { .. }
+                | State::Got003 { .. }
+                | State::Got004(_)
+                | State::Got005(_)
+                | State::Lusers(_)
+                | State::Motd(_)
+        )
+    }
+
+    /// Pops candidates off `candidates` in order, trying to build a
+    /// [`SaslMachine`] for each, until one succeeds or the queue is
+    /// exhausted.  A candidate that fails to build (e.g. a `-PLUS` mechanism
+    /// with no channel binding data available) is treated the same as one
+    /// the server didn't support: skipped in favor of the next one, rather
+    /// than aborting the whole fallback chain.
+    fn start_sasl_flow(
+        mut candidates: VecDeque<SaslMechanism>,
+        authzid: Option<&str>,
+        nickname: &Nickname,
+        password: &str,
+        channel_binding: Option<&[u8]>,
+    ) -> Option<(SaslMachine, Vec<ClientMessage>, VecDeque<SaslMechanism>)> {
+        while let Some(mechanism) = candidates.pop_front() {
+            if let Ok((machine, msgs)) =
+                mechanism.new_flow(authzid, nickname, password, channel_binding)
+            {
+                let msgs = msgs.into_iter().map(ClientMessage::from).collect();
+                return Some((machine, msgs, candidates));
+            }
+        }
+        None
+    }
+
+    fn in_place<F>(&mut self, outgoing: &mut Vec<ClientMessage>, f: F) -> bool
     where
-        F: FnOnce(Self) -> Result<(State, bool), LoginError>,
+        F: FnOnce(Self, &mut Vec<ClientMessage>) -> Result<(State, bool), LoginError>,
     {
         let state = std::mem::replace(self, State::Void);
-        match f(state) {
+        match f(state, outgoing) {
             Ok((st, b)) => {
                 *self = st;
                 b
@@ -220,21 +604,378 @@ impl State {
         }
     }
 
-    fn handle_reply(self, rpl: &Reply) -> Result<(State, bool), LoginError> {
+    fn handle_cap(
+        self,
+        cap: &Cap,
+        outgoing: &mut Vec<ClientMessage>,
+        channel_binding: Option<Bytes>,
+    ) -> Result<(State, bool), LoginError> {
+        match (self, cap) {
+            (
+                State::AwaitingCapLs {
+                    sasl,
+                    nickname,
+                    requested,
+                    mut offered,
+                },
+                Cap::LsResponse(r),
+            ) => {
+                offered.extend(r.capabilities.iter().cloned());
+                if r.continued {
+                    Ok((
+                        State::AwaitingCapLs {
+                            sasl,
+                            nickname,
+                            requested,
+                            offered,
+                        },
+                        true,
+                    ))
+                } else {
+                    // The server's `sasl=` value, if given, lists the
+                    // mechanisms it supports; intersect it with our
+                    // preference list, preserving our strongest-first order.
+                    // If the server didn't give a value at all (pre-3.2
+                    // `CAP`), we can't know which of our candidates it
+                    // supports, so keep the whole list and let each attempt
+                    // fail or succeed on its own.
+                    let offered_sasl_mechanisms = offered
+                        .iter()
+                        .find(|(c, _)| c.as_str() == "sasl")
+                        .and_then(|(_, v)| v.as_ref())
+                        .map(|v| {
+                            v.as_str()
+                                .split(',')
+                                .filter_map(|s| s.parse::<SaslMechanism>().ok())
+                                .collect::<Vec<_>>()
+                        });
+                    let sasl_candidates: VecDeque<SaslMechanism> =
+                        match (&sasl, &offered_sasl_mechanisms) {
+                            (Some(sasl), Some(offered)) => sasl
+                                .mechanisms
+                                .iter()
+                                .copied()
+                                .filter(|m| offered.contains(m))
+                                .collect(),
+                            (Some(sasl), None) => sasl.mechanisms.iter().copied().collect(),
+                            (None, _) => VecDeque::new(),
+                        };
+                    let wants_sasl = sasl.is_some() && !sasl_candidates.is_empty();
+                    let wanted: Vec<(Capability, Option<CapabilityValue>)> = offered
+                        .into_iter()
+                        .filter(|(c, _)| {
+                            (wants_sasl && c.as_str() == "sasl") || requested.contains(c)
+                        })
+                        .collect();
+                    if wanted.is_empty() {
+                        // Server doesn't advertise anything we want; fall
+                        // back to plain login.
+                        outgoing.push(ClientMessage::from(CapEnd));
+                        Ok((
+                            State::Start {
+                                capabilities: Vec::new(),
+                                account: None,
+                            },
+                            true,
+                        ))
+                    } else {
+                        let req = CapReq {
+                            capabilities: wanted
+                                .iter()
+                                .map(|(c, _)| CapabilityRequest::enable(c.clone()))
+                                .collect(),
+                        };
+                        outgoing.push(ClientMessage::from(req));
+                        Ok((
+                            State::AwaitingCapAck {
+                                sasl,
+                                nickname,
+                                wanted,
+                                sasl_candidates,
+                            },
+                            true,
+                        ))
+                    }
+                }
+            }
+            (
+                State::AwaitingCapAck {
+                    sasl: Some(sasl),
+                    nickname,
+                    wanted,
+                    mut sasl_candidates,
+                },
+                Cap::Ack(_),
+            ) if wanted.iter().any(|(c, _)| c.as_str() == "sasl") => {
+                let Some((machine, msgs, sasl_candidates)) = State::start_sasl_flow(
+                    sasl_candidates,
+                    sasl.authzid.as_deref(),
+                    &nickname,
+                    &sasl.password,
+                    channel_binding.as_deref(),
+                ) else {
+                    // None of our candidates survived intersection with the
+                    // server's advertised mechanisms (or could be built at
+                    // all); skip SASL.
+                    outgoing.push(ClientMessage::from(CapEnd));
+                    return Ok((
+                        State::Start {
+                            capabilities: wanted,
+                            account: None,
+                        },
+                        true,
+                    ));
+                };
+                outgoing.extend(msgs);
+                Ok((
+                    State::AwaitingSasl {
+                        machine,
+                        candidates: sasl_candidates,
+                        authzid: sasl.authzid,
+                        nickname,
+                        password: sasl.password,
+                        capabilities: wanted,
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (State::AwaitingCapAck { wanted, .. }, Cap::Ack(_)) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities: wanted,
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (State::AwaitingCapAck { .. }, Cap::Nak(_)) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities: Vec::new(),
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (State::Void, _) => panic!("handle_cap() called on Void login state"),
+            (st, other) => {
+                let expecting = st.expecting();
+                let msg = other.to_irc_line();
+                Err(LoginError::Unexpected { expecting, msg })
+            }
+        }
+    }
+
+    fn handle_authenticate(
+        self,
+        auth: &Authenticate,
+        outgoing: &mut Vec<ClientMessage>,
+    ) -> Result<(State, bool), LoginError> {
+        match self {
+            State::AwaitingSasl {
+                mut machine,
+                candidates,
+                authzid,
+                nickname,
+                password,
+                capabilities,
+                account,
+            } => {
+                machine
+                    .handle_message(auth.clone())
+                    .map_err(LoginError::from_sasl_error)?;
+                outgoing.extend(machine.get_output().into_iter().map(ClientMessage::from));
+                Ok((
+                    State::AwaitingSasl {
+                        machine,
+                        candidates,
+                        authzid,
+                        nickname,
+                        password,
+                        capabilities,
+                        account,
+                    },
+                    true,
+                ))
+            }
+            State::Void => panic!("handle_authenticate() called on Void login state"),
+            st => {
+                let expecting = st.expecting();
+                let msg = auth.to_irc_line();
+                Err(LoginError::Unexpected { expecting, msg })
+            }
+        }
+    }
+
+    /// Handles an `ERR_SASLFAIL` (904) or `ERR_SASLTOOLONG` (905) reply
+    /// received while [`State::AwaitingSasl`]: falls back to the next
+    /// candidate mechanism in `candidates` if one remains, restarting the
+    /// `AUTHENTICATE` exchange with it, or else treats `rpl` as a terminal
+    /// [`LoginError`] once candidates are exhausted.
+    fn retry_sasl(
+        self,
+        rpl: &Reply,
+        outgoing: &mut Vec<ClientMessage>,
+        channel_binding: Option<Bytes>,
+    ) -> Result<(State, bool), LoginError> {
+        match self {
+            State::AwaitingSasl {
+                mut candidates,
+                authzid,
+                nickname,
+                password,
+                capabilities,
+                account,
+                ..
+            } => {
+                let Some((machine, msgs, candidates)) = State::start_sasl_flow(
+                    candidates,
+                    authzid.as_deref(),
+                    &nickname,
+                    &password,
+                    channel_binding.as_deref(),
+                ) else {
+                    let e = match rpl {
+                        Reply::SaslFail(r) => LoginError::SaslFail {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslTooLong(r) => LoginError::SaslTooLong {
+                            message: r.message().to_string(),
+                        },
+                        _ => unreachable!(
+                            "retry_sasl() should only be called for SaslFail/SaslTooLong replies"
+                        ),
+                    };
+                    return Ok((State::Done(Some(Err(e))), true));
+                };
+                outgoing.extend(msgs);
+                Ok((
+                    State::AwaitingSasl {
+                        machine,
+                        candidates,
+                        authzid,
+                        nickname,
+                        password,
+                        capabilities,
+                        account,
+                    },
+                    true,
+                ))
+            }
+            State::Void => panic!("retry_sasl() called on Void login state"),
+            st => Ok((st, false)),
+        }
+    }
+
+    fn handle_reply(
+        self,
+        rpl: &Reply,
+        outgoing: &mut Vec<ClientMessage>,
+        mode_timeout: Duration,
+    ) -> Result<(State, bool), LoginError> {
         match (self, rpl) {
-            (State::Start, Reply::Welcome(r)) => {
+            (
+                State::AwaitingSasl {
+                    machine,
+                    candidates,
+                    authzid,
+                    nickname,
+                    password,
+                    capabilities,
+                    account: None,
+                },
+                Reply::LoggedIn(r),
+            ) => Ok((
+                State::AwaitingSasl {
+                    machine,
+                    candidates,
+                    authzid,
+                    nickname,
+                    password,
+                    capabilities,
+                    account: Some(r.account().to_owned()),
+                },
+                true,
+            )),
+            (
+                State::AwaitingSasl {
+                    capabilities,
+                    account,
+                    ..
+                },
+                Reply::SaslSuccess(_),
+            ) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities,
+                        account,
+                    },
+                    true,
+                ))
+            }
+            (
+                State::Start {
+                    capabilities,
+                    account,
+                },
+                Reply::Welcome(r),
+            ) => {
                 if let ReplyTarget::Nick(nick) = r.client() {
                     let my_nick = nick.clone();
-                    Ok((State::Got001 { my_nick }, true))
+                    Ok((
+                        State::Got001 {
+                            my_nick,
+                            capabilities,
+                            account,
+                        },
+                        true,
+                    ))
                 } else {
                     Err(LoginError::StarWelcome)
                 }
             }
-            (State::Got001 { my_nick }, Reply::YourHost(_)) => {
-                Ok((State::Got002 { my_nick }, true))
-            }
-            (State::Got002 { my_nick }, Reply::Created(_)) => Ok((State::Got003 { my_nick }, true)),
-            (State::Got003 { my_nick }, Reply::MyInfo(r)) => {
+            (
+                State::Got001 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::YourHost(_),
+            ) => Ok((
+                State::Got002 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                true,
+            )),
+            (
+                State::Got002 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::Created(_),
+            ) => Ok((
+                State::Got003 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                true,
+            )),
+            (
+                State::Got003 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::MyInfo(r),
+            ) => {
                 let server_info = ServerInfo {
                     server_name: r.servername().to_owned(),
                     version: r.version().to_owned(),
@@ -244,8 +985,10 @@ impl State {
                 };
                 let output = LoginOutput {
                     my_nick,
+                    capabilities,
+                    account,
                     server_info,
-                    isupport: Vec::new(),
+                    isupport: ISupport::new(),
                     luser_stats: LuserStats::default(),
                     motd: None,
                     mode: None,
@@ -253,7 +996,9 @@ impl State {
                 Ok((State::Got004(output), true))
             }
             (State::Got004(mut output) | State::Got005(mut output), Reply::ISupport(r)) => {
-                output.isupport.extend(r.tokens().iter().cloned());
+                for token in r.tokens() {
+                    output.isupport.apply(token.clone());
+                }
                 Ok((State::Got005(output), true))
             }
             (State::Got005(output) | State::Lusers(output), Reply::StatsConn(_)) => {
@@ -296,7 +1041,7 @@ impl State {
                 Ok((
                     State::AwaitingMode {
                         output,
-                        timeout: Some(MODE_TIMEOUT),
+                        timeout: Some(mode_timeout),
                     },
                     true,
                 ))
@@ -317,7 +1062,7 @@ impl State {
                 Ok((
                     State::AwaitingMode {
                         output,
-                        timeout: Some(MODE_TIMEOUT),
+                        timeout: Some(mode_timeout),
                     },
                     true,
                 ))
@@ -380,7 +1125,12 @@ impl State {
 
     fn expecting(&self) -> &'static str {
         match self {
-            State::Start => "RPL_WELCOME (001) reply",
+            State::AwaitingCapLs { .. } => "CAP LS response or ERR_UNKNOWNCOMMAND (421)",
+            State::AwaitingCapAck { .. } => "CAP ACK or CAP NAK response",
+            State::AwaitingSasl { .. } => {
+                "AUTHENTICATE continuation, RPL_LOGGEDIN (900), or RPL_SASLSUCCESS (903) reply"
+            }
+            State::Start { .. } => "RPL_WELCOME (001) reply",
             State::Got001 { .. } => "RPL_YOURHOST (002) reply",
             State::Got002 { .. } => "RPL_CREATED (003) reply",
             State::Got003 { .. } => "RPL_MYINFO (004) reply",
@@ -397,10 +1147,24 @@ impl State {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoginOutput {
-    // SASL: CAP LS
     pub my_nick: Nickname,
+
+    /// The capabilities (with their advertised values, e.g. `sasl` mapping
+    /// to `PLAIN,EXTERNAL`) negotiated via `CAP REQ`/`CAP ACK`.  Empty unless
+    /// [`LoginParams::sasl`] or [`LoginParams::capabilities`] was set and
+    /// the server both supported `CAP` and acknowledged the request.
+    pub capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+
+    /// The account name returned by `RPL_LOGGEDIN` (900) after a successful
+    /// SASL authentication, if any.  This is also how a client authenticating
+    /// via `SaslMechanism::External` (e.g. CertFP, via
+    /// [`ConnectionParams::client_cert`](crate::client::ConnectionParams::client_cert))
+    /// learns whether services recognized its certificate and which account
+    /// it was bound to.
+    pub account: Option<String>,
+
     pub server_info: ServerInfo,
-    pub isupport: Vec<ISupportParam>,
+    pub isupport: ISupport,
     pub luser_stats: LuserStats,
     pub motd: Option<String>, // None if the server reports no MOTD was set
     pub mode: Option<ModeString>,
@@ -442,6 +1206,14 @@ pub enum LoginError {
     Password { message: String },
     #[error("login failed because client is banned: {message:?}")]
     Banned { message: String },
+    #[error("login failed because the requested nickname is locked by services: {message:?}")]
+    NickLocked { message: String },
+    #[error("login failed because SASL authentication failed: {message:?}")]
+    SaslFail { message: String },
+    #[error("login failed because the SASL message was too long: {message:?}")]
+    SaslTooLong { message: String },
+    #[error("login failed because client has already authenticated via SASL: {message:?}")]
+    SaslAlready { message: String },
     #[error("login failed with unexpected error reply {code:03}: {reply:?}")]
     UnexpectedError { code: u16, reply: String },
     #[error("server sent ERROR message during login: {reason:?}")]
@@ -457,6 +1229,21 @@ pub enum LoginError {
     },
     #[error("login failed because server sent unparseable mode string in RPL_UMODEIS: {msg:?}")]
     InvalidMode { msg: String },
+    #[error("login failed because the server's SCRAM signature did not match the one we computed")]
+    SaslServerSignatureMismatch,
+    #[error("SASL authentication flow failed: {0}")]
+    SaslFlow(String),
+    #[error("login timed out: still waiting on {expecting}")]
+    RegistrationTimedOut { expecting: &'static str },
+}
+
+impl LoginError {
+    fn from_sasl_error(e: SaslError) -> LoginError {
+        match e {
+            SaslError::Signature => LoginError::SaslServerSignatureMismatch,
+            e => LoginError::SaslFlow(e.to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -470,8 +1257,14 @@ mod tests {
             nickname: "jwodder".parse::<Nickname>().unwrap(),
             username: "jwuser".parse::<Username>().unwrap(),
             realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: Vec::new(),
+            nick_fallback_policy: None,
+            sasl: None,
+            capabilities: Vec::new(),
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
         };
-        let mut cmd = Login::new(params);
+        let mut cmd = Login::new(params, None);
         let outgoing = cmd.get_client_messages();
         let outgoing = outgoing
             .into_iter()
@@ -573,6 +1366,8 @@ mod tests {
             output,
             LoginOutput {
                 my_nick: "jwodder".parse::<Nickname>().unwrap(),
+                capabilities: Vec::new(),
+                account: None,
                 server_info: ServerInfo {
                     server_name: "molybdenum.libera.chat".into(),
                     version: "solanum-1.0-dev".into(),
@@ -610,9 +1405,10 @@ mod tests {
                     "EXTBAN=$,agjrxz",
                 ]
                 .into_iter()
-                .map(str::parse::<ISupportParam>)
-                .collect::<Result<Vec<_>, _>>()
-                .unwrap(),
+                .fold(ISupport::new(), |mut isupport, token| {
+                    isupport.apply(token.parse::<ISupportParam>().unwrap());
+                    isupport
+                }),
                 luser_stats: LuserStats {
                     operators: Some(40),
                     unknown_connections: Some(66),
@@ -670,4 +1466,334 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn cap_negotiation_without_sasl() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: Vec::new(),
+            nick_fallback_policy: None,
+            sasl: None,
+            capabilities: vec![
+                "server-time".parse::<Capability>().unwrap(),
+                "multi-prefix".parse::<Capability>().unwrap(),
+            ],
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
+        };
+        let mut cmd = Login::new(params, None);
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outgoing,
+            [
+                "CAP LS 302",
+                "PASS :hunter2",
+                "NICK jwodder",
+                "USER jwuser 0 * :Just this guy, you know?"
+            ]
+        );
+
+        let msg = "CAP * LS :server-time multi-prefix account-notify"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["CAP REQ :server-time multi-prefix"]);
+        assert!(!cmd.is_done());
+
+        let msg = "CAP * ACK :server-time multi-prefix"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["CAP END"]);
+        assert!(!cmd.is_done());
+
+        let incoming = [
+            ":irc.example.com 001 jwodder :Welcome",
+            ":irc.example.com 002 jwodder :Your host is irc.example.com",
+            ":irc.example.com 003 jwodder :This server was created today",
+            ":irc.example.com 004 jwodder irc.example.com test-1.0 ai bi",
+            ":irc.example.com 005 jwodder NETWORK=Test :are supported by this server",
+            ":irc.example.com 422 jwodder :MOTD File is missing",
+        ];
+        for m in incoming {
+            let msg = m.parse::<Message>().unwrap();
+            assert!(cmd.handle_message(&msg));
+            assert!(cmd.get_client_messages().is_empty());
+            assert!(!cmd.is_done());
+        }
+
+        let msg = ":jwodder MODE jwodder :+i".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.is_done());
+
+        let output = cmd.get_output().unwrap();
+        assert_eq!(
+            output.capabilities,
+            [
+                ("server-time".parse::<Capability>().unwrap(), None),
+                ("multi-prefix".parse::<Capability>().unwrap(), None),
+            ]
+        );
+        assert_eq!(output.my_nick, "jwodder".parse::<Nickname>().unwrap());
+        assert_eq!(output.account, None);
+    }
+
+    #[test]
+    fn nick_fallback_retry() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: vec!["jwodder_".parse::<Nickname>().unwrap()],
+            nick_fallback_policy: None,
+            sasl: None,
+            capabilities: Vec::new(),
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
+        };
+        let mut cmd = Login::new(params, None);
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            [
+                "PASS :hunter2",
+                "NICK jwodder",
+                "USER jwuser 0 * :Just this guy, you know?"
+            ]
+        );
+
+        let msg = ":irc.example.com 433 * jwodder :Nickname is already in use."
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["NICK jwodder_"]
+        );
+        assert!(!cmd.is_done());
+
+        let incoming = [
+            ":irc.example.com 001 jwodder_ :Welcome",
+            ":irc.example.com 002 jwodder_ :Your host is irc.example.com",
+            ":irc.example.com 003 jwodder_ :This server was created today",
+            ":irc.example.com 004 jwodder_ irc.example.com test-1.0 ai bi",
+            ":irc.example.com 005 jwodder_ NETWORK=Test :are supported by this server",
+            ":irc.example.com 422 jwodder_ :MOTD File is missing",
+        ];
+        for m in incoming {
+            let msg = m.parse::<Message>().unwrap();
+            assert!(cmd.handle_message(&msg));
+            assert!(cmd.get_client_messages().is_empty());
+            assert!(!cmd.is_done());
+        }
+
+        let msg = ":jwodder_ MODE jwodder_ :+i".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.is_done());
+
+        let output = cmd.get_output().unwrap();
+        assert_eq!(output.my_nick, "jwodder_".parse::<Nickname>().unwrap());
+    }
+
+    #[test]
+    fn nick_fallback_exhausted_fails() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: Vec::new(),
+            nick_fallback_policy: None,
+            sasl: None,
+            capabilities: Vec::new(),
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
+        };
+        let mut cmd = Login::new(params, None);
+        cmd.get_client_messages();
+
+        let msg = ":irc.example.com 433 * jwodder :Nickname is already in use."
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.get_client_messages().is_empty());
+        assert!(cmd.is_done());
+        assert!(matches!(
+            cmd.get_output(),
+            Err(LoginError::NicknameInUse { .. })
+        ));
+    }
+
+    #[test]
+    fn sasl_plain_login() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: Vec::new(),
+            nick_fallback_policy: None,
+            sasl: Some(SaslCredentials {
+                mechanisms: vec![SaslMechanism::Plain],
+                password: "hunter2".to_owned(),
+                authzid: None,
+            }),
+            capabilities: Vec::new(),
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
+        };
+        let mut cmd = Login::new(params, None);
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            [
+                "CAP LS 302",
+                "PASS :hunter2",
+                "NICK jwodder",
+                "USER jwuser 0 * :Just this guy, you know?"
+            ]
+        );
+
+        let msg = "CAP * LS :sasl=PLAIN".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["CAP REQ :sasl"]
+        );
+
+        let msg = "CAP * ACK :sasl".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["AUTHENTICATE :PLAIN"]
+        );
+        assert!(!cmd.is_done());
+
+        let msg = "AUTHENTICATE +".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["AUTHENTICATE :andvZGRlcgBqd29kZGVyAGh1bnRlcjI="]
+        );
+        assert!(!cmd.is_done());
+
+        let msg = ":irc.example.com 900 jwodder jwodder!jwuser@example.com jwodder :You are now logged in as jwodder"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.get_client_messages().is_empty());
+        assert!(!cmd.is_done());
+
+        let msg = ":irc.example.com 903 jwodder :SASL authentication successful"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert_eq!(
+            cmd.get_client_messages()
+                .into_iter()
+                .map(|msg| msg.to_irc_line())
+                .collect::<Vec<_>>(),
+            ["CAP END"]
+        );
+        assert!(!cmd.is_done());
+
+        let incoming = [
+            ":irc.example.com 001 jwodder :Welcome",
+            ":irc.example.com 002 jwodder :Your host is irc.example.com",
+            ":irc.example.com 003 jwodder :This server was created today",
+            ":irc.example.com 004 jwodder irc.example.com test-1.0 ai bi",
+            ":irc.example.com 005 jwodder NETWORK=Test :are supported by this server",
+            ":irc.example.com 422 jwodder :MOTD File is missing",
+        ];
+        for m in incoming {
+            let msg = m.parse::<Message>().unwrap();
+            assert!(cmd.handle_message(&msg));
+            assert!(cmd.get_client_messages().is_empty());
+            assert!(!cmd.is_done());
+        }
+
+        let msg = ":jwodder MODE jwodder :+r".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.is_done());
+
+        let output = cmd.get_output().unwrap();
+        assert_eq!(output.account, Some("jwodder".to_owned()));
+        assert_eq!(output.my_nick, "jwodder".parse::<Nickname>().unwrap());
+    }
+
+    #[test]
+    fn sasl_fail_with_no_fallback_mechanism_fails_login() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            alt_nicknames: Vec::new(),
+            nick_fallback_policy: None,
+            sasl: Some(SaslCredentials {
+                mechanisms: vec![SaslMechanism::Plain],
+                password: "hunter2".to_owned(),
+                authzid: None,
+            }),
+            capabilities: Vec::new(),
+            registration_timeout: Duration::from_secs(30),
+            mode_timeout: Duration::from_secs(1),
+        };
+        let mut cmd = Login::new(params, None);
+        cmd.get_client_messages();
+
+        let msg = "CAP * LS :sasl=PLAIN".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        cmd.get_client_messages();
+
+        let msg = "CAP * ACK :sasl".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        cmd.get_client_messages();
+
+        let msg = "AUTHENTICATE +".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        cmd.get_client_messages();
+
+        let msg = ":irc.example.com 904 jwodder :SASL authentication failed"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(cmd.get_client_messages().is_empty());
+        assert!(cmd.is_done());
+        assert!(matches!(cmd.get_output(), Err(LoginError::SaslFail { .. })));
+    }
 }
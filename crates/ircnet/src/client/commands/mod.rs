@@ -1,9 +1,19 @@
+mod chathistory;
+mod ctcp_ping;
+mod introspect;
 mod join;
+mod keepalive;
 mod list;
 mod login;
+mod sequence;
+pub use self::chathistory::*;
+pub use self::ctcp_ping::*;
+pub use self::introspect::*;
 pub use self::join::*;
+pub use self::keepalive::*;
 pub use self::list::*;
 pub use self::login::*;
+pub use self::sequence::*;
 use irctext::{ClientMessage, Message};
 use std::time::Duration;
 
@@ -0,0 +1,311 @@
+use super::Command;
+use irctext::{ClientMessage, Message};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Drives a heterogeneous tuple of [`Command`]s to completion one after
+/// another — the first command's turn, then the next, and so on — and
+/// collects their outputs into a tuple, letting multi-step IRC interactions
+/// (e.g. the chain of introspection queries in
+/// [`ircnet::client::commands`](super)) be scripted as a single value
+/// instead of a bespoke loop.
+///
+/// `Sequence` itself implements [`Command`], so it can be driven through
+/// [`Client::run`](crate::client::Client::run) exactly like any of its
+/// members.  If a member command fails, the sequence stops there (later
+/// commands never run) and [`SequenceError`] reports which step failed.
+///
+/// Supports tuples of 1 to 8 commands; construct with [`Sequence::new`].
+pub struct Sequence<T: SequenceCommands> {
+    commands: T,
+    step: usize,
+    outputs: T::Outputs,
+    error: Option<SequenceError>,
+}
+
+impl<T: SequenceCommands> Sequence<T> {
+    pub fn new(commands: T) -> Sequence<T> {
+        Sequence {
+            commands,
+            step: 0,
+            outputs: T::Outputs::default(),
+            error: None,
+        }
+    }
+
+    /// Advance past every step that's already done, recording its output
+    /// (or erroring out the whole sequence) as we go.
+    fn advance(&mut self) {
+        while self.error.is_none()
+            && self.step < self.commands.step_count()
+            && self.commands.is_step_done(self.step)
+        {
+            match self.commands.take_step_output(self.step, &mut self.outputs) {
+                Ok(()) => self.step += 1,
+                Err(source) => {
+                    self.error = Some(SequenceError {
+                        index: self.step,
+                        source,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<T: SequenceCommands> Command for Sequence<T> {
+    type Output = T::Output;
+    type Error = SequenceError;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        if self.is_done() {
+            Vec::new()
+        } else {
+            self.commands.get_client_messages(self.step)
+        }
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let handled = self.commands.handle_message(self.step, msg);
+        if handled {
+            self.advance();
+        }
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        if self.is_done() {
+            None
+        } else {
+            self.commands.get_timeout(self.step)
+        }
+    }
+
+    fn handle_timeout(&mut self) {
+        if !self.is_done() {
+            self.commands.handle_timeout(self.step);
+            self.advance();
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.error.is_some() || self.step >= self.commands.step_count()
+    }
+
+    fn get_output(&mut self) -> Result<Self::Output, SequenceError> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        Ok(T::finish(std::mem::take(&mut self.outputs)))
+    }
+}
+
+/// The error returned by a [`Sequence`] when one of its member commands
+/// fails, identifying which step (0-indexed) failed.
+#[derive(Debug, Error)]
+#[error("command sequence failed at step {index}: {source}")]
+pub struct SequenceError {
+    pub index: usize,
+    #[source]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+/// Implemented for tuples of 1 to 8 [`Command`]s, giving [`Sequence`] a
+/// uniform way to drive whichever member is currently active by position.
+///
+/// This trait is only meant to be implemented by the blanket tuple impls in
+/// this module; there's no reason to implement it yourself.
+pub trait SequenceCommands {
+    /// The tuple of every member command's `Output`.
+    type Output;
+
+    /// The tuple of `Option<Output>`, one per member command, used to
+    /// accumulate results as each step finishes.
+    type Outputs: Default;
+
+    /// The number of commands in the tuple.
+    fn step_count(&self) -> usize;
+
+    fn get_client_messages(&mut self, step: usize) -> Vec<ClientMessage>;
+    fn handle_message(&mut self, step: usize, msg: &Message) -> bool;
+    fn get_timeout(&mut self, step: usize) -> Option<Duration>;
+    fn handle_timeout(&mut self, step: usize);
+    fn is_step_done(&self, step: usize) -> bool;
+
+    /// Takes `step`'s output via `Command::get_output()` and stores it in
+    /// `outputs`, or returns the step's error, erased to a trait object so
+    /// that every step's (possibly distinct) `Error` type can share one
+    /// [`SequenceError`].
+    fn take_step_output(
+        &mut self,
+        step: usize,
+        outputs: &mut Self::Outputs,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Unwraps a fully-populated `Outputs` into the final `Output` tuple.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any slot of `outputs` is `None`, which should be
+    /// impossible by the time `Sequence::get_output()` calls this.
+    fn finish(outputs: Self::Outputs) -> Self::Output;
+}
+
+macro_rules! impl_sequence_commands {
+    ($len:literal; $( $c:ident : $idx:tt ),+ $(,)?) => {
+        impl<$($c: Command),+> SequenceCommands for ($($c,)+) {
+            type Output = ($($c::Output,)+);
+            type Outputs = ($(Option<$c::Output>,)+);
+
+            fn step_count(&self) -> usize {
+                $len
+            }
+
+            fn get_client_messages(&mut self, step: usize) -> Vec<ClientMessage> {
+                match step {
+                    $( $idx => self.$idx.get_client_messages(), )+
+                    _ => Vec::new(),
+                }
+            }
+
+            fn handle_message(&mut self, step: usize, msg: &Message) -> bool {
+                match step {
+                    $( $idx => self.$idx.handle_message(msg), )+
+                    _ => false,
+                }
+            }
+
+            fn get_timeout(&mut self, step: usize) -> Option<Duration> {
+                match step {
+                    $( $idx => self.$idx.get_timeout(), )+
+                    _ => None,
+                }
+            }
+
+            fn handle_timeout(&mut self, step: usize) {
+                match step {
+                    $( $idx => self.$idx.handle_timeout(), )+
+                    _ => (),
+                }
+            }
+
+            fn is_step_done(&self, step: usize) -> bool {
+                match step {
+                    $( $idx => self.$idx.is_done(), )+
+                    _ => true,
+                }
+            }
+
+            fn take_step_output(
+                &mut self,
+                step: usize,
+                outputs: &mut Self::Outputs,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                match step {
+                    $(
+                        $idx => {
+                            outputs.$idx = Some(
+                                self.$idx.get_output().map_err(|e| Box::new(e) as _)?,
+                            );
+                        }
+                    )+
+                    _ => {}
+                }
+                Ok(())
+            }
+
+            fn finish(outputs: Self::Outputs) -> Self::Output {
+                (
+                    $(
+                        outputs.$idx.expect(
+                            "every Sequence step output should be populated by the time finish() is called",
+                        ),
+                    )+
+                )
+            }
+        }
+    };
+}
+
+impl_sequence_commands!(1; A:0);
+impl_sequence_commands!(2; A:0, B:1);
+impl_sequence_commands!(3; A:0, B:1, C:2);
+impl_sequence_commands!(4; A:0, B:1, C:2, D:3);
+impl_sequence_commands!(5; A:0, B:1, C:2, D:3, E:4);
+impl_sequence_commands!(6; A:0, B:1, C:2, D:3, E:4, F:5);
+impl_sequence_commands!(7; A:0, B:1, C:2, D:3, E:4, F:5, G:6);
+impl_sequence_commands!(8; A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    struct Echo {
+        outgoing: Vec<ClientMessage>,
+        done: bool,
+    }
+
+    impl Echo {
+        fn new(msg: ClientMessage) -> Echo {
+            Echo {
+                outgoing: vec![msg],
+                done: false,
+            }
+        }
+    }
+
+    impl Command for Echo {
+        type Output = ();
+        type Error = Infallible;
+
+        fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+            std::mem::take(&mut self.outgoing)
+        }
+
+        fn handle_message(&mut self, _msg: &Message) -> bool {
+            self.done = true;
+            true
+        }
+
+        fn get_timeout(&mut self) -> Option<Duration> {
+            None
+        }
+
+        fn handle_timeout(&mut self) {}
+
+        fn is_done(&self) -> bool {
+            self.done
+        }
+
+        fn get_output(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn runs_steps_in_order() {
+        let ping: ClientMessage = irctext::clientmsgs::Ping::new(
+            "1".parse().unwrap(),
+        )
+        .into();
+        let mut seq = Sequence::new((Echo::new(ping.clone()), Echo::new(ping)));
+        assert_eq!(seq.get_client_messages().len(), 1);
+        assert!(!seq.is_done());
+
+        let reply = ":irc.example.com PONG irc.example.com :1"
+            .parse::<Message>()
+            .unwrap();
+        assert!(seq.handle_message(&reply));
+        assert!(!seq.is_done());
+        assert_eq!(seq.get_client_messages().len(), 1);
+
+        assert!(seq.handle_message(&reply));
+        assert!(seq.is_done());
+        assert_eq!(seq.get_output().unwrap(), ((), ()));
+    }
+}
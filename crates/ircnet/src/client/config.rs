@@ -0,0 +1,74 @@
+//! Declarative bot configuration, for standing up a [`Client`] from a
+//! single deserialized config file instead of imperative setup code, the
+//! way the `irc` crate's TOML/JSON bot configs do.
+use super::autoresponders::{CtcpQueryResponder, PingResponder};
+use super::commands::{JoinCommand, LoginOutput};
+use super::{Client, ClientError, SessionBuilder, SessionParams};
+use irctext::ctcp::CtcpParams;
+use irctext::types::Channel;
+
+/// One entry in [`ClientConfig::autoresponders`], selected by its `type`
+/// tag when deserializing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "kebab-case"))]
+pub enum AutoResponderConfig {
+    /// Answers CTCP `VERSION` queries with the given version string.
+    CtcpVersion { version: CtcpParams },
+
+    /// Automatically replies to `PING` with `PONG`.
+    Ping,
+}
+
+impl AutoResponderConfig {
+    fn install(self, client: &mut Client) {
+        match self {
+            AutoResponderConfig::CtcpVersion { version } => {
+                client.add_autoresponder(CtcpQueryResponder::new().with_version(version));
+            }
+            AutoResponderConfig::Ping => client.add_autoresponder(PingResponder::new()),
+        }
+    }
+}
+
+/// A declarative description of a bot: the connection & registration
+/// details already captured by [`SessionParams`], plus the channels to join
+/// once logged in and the autoresponders to install beforehand, so that
+/// [`Client::from_config`] can stand up a fully working client from a
+/// single config file with no imperative setup code.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClientConfig {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub session: SessionParams,
+
+    /// Channels to join, in order, once registration completes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub channels: Vec<Channel>,
+
+    /// Autoresponders to install once logged in.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub autoresponders: Vec<AutoResponderConfig>,
+}
+
+impl Client {
+    /// Connects, performs registration, installs `config`'s autoresponders
+    /// via [`Client::add_autoresponder`], and joins `config`'s channels, so
+    /// a user can stand up a working client from a single [`ClientConfig`]
+    /// with no imperative setup code.
+    pub async fn from_config(config: ClientConfig) -> Result<(Client, LoginOutput), ClientError> {
+        let ClientConfig {
+            session,
+            channels,
+            autoresponders,
+        } = config;
+        let (mut client, login_output) = SessionBuilder::new(session).build().await?;
+        for ar in autoresponders {
+            ar.install(&mut client);
+        }
+        if let Some(join) = JoinCommand::new_many(channels) {
+            client.run(join).await?;
+        }
+        Ok((client, login_output))
+    }
+}
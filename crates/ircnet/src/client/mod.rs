@@ -1,33 +1,162 @@
 //! High-level IRC client
+//!
+//! [`Client`] is the driver that executes [`Command`] state machines against
+//! a live connection: it sends each command's outgoing messages, reads and
+//! parses incoming lines, demultiplexes unsolicited server traffic (handled
+//! by an [`AutoResponder`] or otherwise left for later `recv()` calls) away
+//! from the command in flight, honors the command's requested timeout, and
+//! resolves with the command's output once it reports itself done — see
+//! [`Client::run`]. Callers that would rather be notified of traffic than
+//! poll for it can register observers via [`Client::on_any`] and its
+//! type-filtered variants instead of matching on every `recv()` result by
+//! hand, or call [`Client::subscribe`] for a `Stream` of every message seen
+//! while some other call to `recv()`/`run()` is driving the connection.
+//! `Client` also directly implements [`Stream`] and
+//! [`Sink`](futures_util::Sink) for use with `StreamExt`/`SinkExt`
+//! combinators.
 pub mod autoresponders;
 mod builder;
 pub mod commands;
+mod config;
+mod configwatcher;
+mod mux;
+mod reconnect;
+pub mod split;
 pub use self::autoresponders::AutoResponder;
 use self::autoresponders::AutoResponderSet;
 pub use self::builder::*;
 pub use self::commands::Command;
+pub use self::config::*;
+pub use self::configwatcher::*;
+pub use self::mux::*;
+pub use self::reconnect::*;
+pub use self::split::{ClientReceiver, ClientSender};
 use crate::connect::{
-    ConnectionError, LinesChannel,
+    ClientCert, ConnectionError, LinesChannel, Socks5Proxy,
     codecs::{LinesCodec, LinesCodecError},
     connect,
-    consts::{MAX_LINE_LENGTH_WITH_TAGS, PLAIN_PORT, TLS_PORT},
+    consts::{MAX_LINE_LENGTH, MAX_TAG_LENGTH, PLAIN_PORT, TLS_PORT},
+    tls_server_end_point,
 };
-use futures_util::{SinkExt, TryStreamExt};
-use irctext::{Message, ParseMessageError, TryFromStringError};
+use bytes::Bytes;
+use futures_util::{Sink, SinkExt, Stream, TryStreamExt, stream};
+use irctext::{
+    FinalParam, MedialParam, Message, MessageTags, ParseMessageError, Payload, Reply, Source,
+    TryFromStringError,
+    clientmsgs::{
+        Batch, Capability, CapabilityValue, ClientMessage, Error as ErrorMessage, Kick,
+        KnownCapability, Notice, PrivMsg, Quit,
+    },
+    types::{ISupport, TagKey, TagValue},
+};
+use socket2::{SockRef, TcpKeepalive};
 use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 use thiserror::Error;
-use tokio::time::{Instant, timeout_at};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{Instant, sleep_until, timeout_at};
+use tokio_rustls::rustls::client::danger::ServerCertVerifier;
 use tokio_util::codec::Framed;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Capacity of the broadcast channel backing [`Client::subscribe`]. A
+/// subscriber that falls this far behind the fastest-arriving traffic will
+/// observe a [`Lagged`][broadcast::error::RecvError::Lagged] gap rather than
+/// unbounded memory growth.
+const SUBSCRIBE_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ConnectionParams {
     pub host: String,
     pub port: Option<u16>,
     #[cfg_attr(feature = "serde", serde(default = "default_tls"))]
     pub tls: bool,
+
+    /// TCP keepalive settings to apply to the connection once established.
+    /// If `None`, the OS default keepalive behavior (typically disabled) is
+    /// left in place.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub keepalive: Option<KeepaliveParams>,
+
+    /// How long `run()` will wait for server activity when the active
+    /// command has no timeout of its own, so that a silently-dead
+    /// connection doesn't block forever.  If `None`, `run()` will wait
+    /// indefinitely in that case.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub read_timeout: Option<Duration>,
+
+    /// A TLS client certificate to present during the handshake (only
+    /// meaningful when `tls` is true), for CertFP-based authentication with
+    /// network services.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub client_cert: Option<ClientCert>,
+
+    /// A custom verifier to use in place of the native root store (only
+    /// meaningful when `tls` is true), for pinning a specific certificate or
+    /// trusting a self-signed server; see [`FingerprintVerifier`] and
+    /// [`TrustedCert`].  Not (de)serializable, since it's behavior rather
+    /// than configuration data.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub server_cert_verifier: Option<Arc<dyn ServerCertVerifier>>,
+
+    /// A SOCKS5 proxy to dial the server through, instead of connecting to
+    /// it directly.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub socks5_proxy: Option<Socks5Proxy>,
+
+    /// Token-bucket rate limit applied to messages sent via `Client::send`,
+    /// to avoid "excess flood" disconnections on servers that kill clients
+    /// sending faster than roughly one line every two seconds.  Messages
+    /// queued by an autoresponder and sent via `flush_queue` (e.g. a `PONG`
+    /// keepalive reply) bypass the bucket, so they're never delayed by it.
+    /// If `None`, outgoing messages are sent as fast as the caller calls
+    /// `send()`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rate_limit: Option<RateLimit>,
+
+    /// A non-UTF-8 text encoding to use for the connection instead of the
+    /// default UTF-8-then-Latin-1 fallback, for servers (and their MOTDs,
+    /// PRIVMSG/NOTICE text, and realnames) known to emit a legacy charset.
+    /// See [`LinesCodec::with_encoding`]. Not (de)serializable, since
+    /// `encoding_rs::Encoding` has no serde support of its own.
+    #[cfg(feature = "encoding_rs")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub encoding: Option<&'static encoding_rs::Encoding>,
+}
+
+impl PartialEq for ConnectionParams {
+    fn eq(&self, other: &Self) -> bool {
+        self.host == other.host
+            && self.port == other.port
+            && self.tls == other.tls
+            && self.keepalive == other.keepalive
+            && self.read_timeout == other.read_timeout
+            && self.client_cert == other.client_cert
+            && self.rate_limit == other.rate_limit
+            && match (&self.server_cert_verifier, &other.server_cert_verifier) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && self.socks5_proxy == other.socks5_proxy
+            && {
+                #[cfg(feature = "encoding_rs")]
+                {
+                    self.encoding.map(|e| e as *const _) == other.encoding.map(|e| e as *const _)
+                }
+                #[cfg(not(feature = "encoding_rs"))]
+                {
+                    true
+                }
+            }
+    }
 }
 
+impl Eq for ConnectionParams {}
+
 impl ConnectionParams {
     pub fn port(&self) -> u16 {
         match (self.port, self.tls) {
@@ -43,6 +172,78 @@ fn default_tls() -> bool {
     false
 }
 
+/// TCP keepalive parameters; see [`ConnectionParams::keepalive`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeepaliveParams {
+    /// How long the connection must be idle before a keepalive probe is
+    /// sent.
+    pub time: Duration,
+    /// How long to wait between unacknowledged keepalive probes.
+    pub interval: Duration,
+    /// How many unacknowledged keepalive probes to send before considering
+    /// the connection dead.
+    pub retries: u32,
+}
+
+impl KeepaliveParams {
+    fn to_tcp_keepalive(self) -> TcpKeepalive {
+        TcpKeepalive::new()
+            .with_time(self.time)
+            .with_interval(self.interval)
+            .with_retries(self.retries)
+    }
+}
+
+/// Token-bucket rate limit parameters; see [`ConnectionParams::rate_limit`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RateLimit {
+    /// How many messages may be sent back-to-back before the steady rate
+    /// kicks in.
+    pub burst: u32,
+    /// The steady-state interval between messages once the burst is spent.
+    pub per_message: Duration,
+}
+
+/// Runtime state for a [`RateLimit`], continuously refilling at
+/// `per_message` granularity up to `burst` capacity.
+struct RateLimitBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimitBucket {
+    fn new(limit: RateLimit) -> RateLimitBucket {
+        RateLimitBucket {
+            limit,
+            tokens: f64::from(limit.burst),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Refills the bucket based on time elapsed since the last refill (up to
+    /// `burst` tokens) and consumes a token if one is available, returning
+    /// `None`. Otherwise, returns the `Instant` at which a token will next
+    /// become available, without blocking the caller itself.
+    fn next_available(&mut self) -> Option<Instant> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill);
+        self.last_refill = now;
+        let rate = 1.0 / self.limit.per_message.as_secs_f64();
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(f64::from(self.limit.burst));
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let wait = Duration::from_secs_f64((1.0 - self.tokens) * self.limit.per_message.as_secs_f64());
+            self.tokens = 0.0;
+            Some(now + wait)
+        }
+    }
+}
+
 #[allow(missing_debug_implementations)]
 pub struct Client {
     /// Name of remote host; used in log messages
@@ -54,6 +255,17 @@ pub struct Client {
     /// Set of `AutoResponder`s installed on this client
     autoresponders: AutoResponderSet,
 
+    /// Handlers registered via `on_any`/`on_privmsg`/`on_notice`/`on_kick`/
+    /// `on_error`, invoked in registration order on every message passed to
+    /// `dispatch()`
+    dispatch_handlers: Vec<Box<dyn Fn(&ClientMessage, Option<&Source>) + Send>>,
+
+    /// Fan-out hub backing [`Client::subscribe`]; every message `recv_new()`
+    /// parses off the wire is broadcast here, regardless of whether an
+    /// autoresponder or the command in flight ends up claiming it.  Dropped
+    /// (never sent) if no subscribers are currently listening.
+    hub: broadcast::Sender<Arc<Message>>,
+
     /// Outgoing client messages emitted by `autoresponders` that have not yet
     /// been sent to the server
     queued: VecDeque<Message>,
@@ -66,26 +278,225 @@ pub struct Client {
     /// Messages received during execution of a `Command` that were not handled
     /// by the command
     unhandled: VecDeque<Message>,
+
+    /// See [`ConnectionParams::read_timeout`]
+    read_timeout: Option<Duration>,
+
+    /// RFC 5929 `tls-server-end-point` channel-binding data for the
+    /// connection, if it's over TLS and the server presented a certificate;
+    /// see [`Client::channel_binding`]
+    channel_binding: Option<Bytes>,
+
+    /// The capabilities (with their advertised values, if any) enabled via
+    /// `CAP` negotiation during login, if any; see [`Client::capabilities`]
+    capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+
+    /// The `RPL_ISUPPORT` (005) tokens accumulated across every such reply
+    /// seen so far, kept up to date after login completes in case a server
+    /// sends further `RPL_ISUPPORT` replies mid-session; see
+    /// [`Client::isupport`]
+    isupport: ISupport,
+
+    /// Monotonically increasing counter used to mint `label` tags for
+    /// `labeled-response`; see [`Client::next_label`]
+    next_label: u64,
+
+    /// The `label` (and, once opened, `labeled-response` `BATCH` reference
+    /// tag) of the command currently being run via [`Client::run`], used to
+    /// route replies to it unambiguously instead of relying solely on
+    /// per-command heuristics; see [`Client::route_labeled`]
+    label_state: Option<LabelState>,
+
+    /// Receiving end of the channel fed by every clone of a
+    /// [`ClientSender`](split::ClientSender) handed out by
+    /// [`Client::split`], if any. Drained into `queued` once per loop
+    /// iteration of `recv_new()`, between `try_next` polls of the
+    /// connection.
+    inbox: Option<mpsc::Receiver<Message>>,
+
+    /// Commands submitted via [`Client::spawn`], advanced concurrently by
+    /// [`Client::drive`] instead of the one-at-a-time [`Client::run`].
+    mux: CommandMux,
+
+    /// See [`Client::set_max_in_flight`]
+    max_in_flight: Option<usize>,
+
+    /// See [`ConnectionParams::rate_limit`]
+    rate_bucket: Option<RateLimitBucket>,
+
+    /// See [`Client::state`]
+    state: ConnectionState,
+}
+
+/// The lifecycle stage of a [`Client`]'s connection; see [`Client::state`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// The TCP/TLS connection is being established. A `Client` never
+    /// actually observes this state itself, since `Client::connect` only
+    /// returns once the connection exists, but it's included for
+    /// completeness and for callers tracking a reconnect loop from outside.
+    Connecting,
+    /// Connected, but `RPL_WELCOME` (001) hasn't been seen yet.
+    Registering,
+    /// `RPL_WELCOME` has been received; registration is complete.
+    Ready,
+    /// [`Client::quit`] has sent a `QUIT` and is draining remaining server
+    /// messages until the socket closes.
+    Closing,
+    /// The connection is closed, whether via a clean `Client::quit()` or an
+    /// `ERROR` from the server or abrupt I/O drop.
+    Closed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LabelState {
+    label: MedialParam,
+    batch_ref: Option<MedialParam>,
+}
+
+/// Where [`Client::route_labeled`] decided a received message should go.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Route {
+    /// Offer the message to the running command.
+    ToCommand,
+    /// The message is unrelated to the running command; push it straight to
+    /// `unhandled` without offering it.
+    ToUnhandled,
+    /// The message was `labeled-response` `BATCH` framing, not part of the
+    /// command's output; drop it without offering it or queuing it.
+    Consumed,
 }
 
 impl Client {
     /// Create a new `Client` connected to the given server & port.  If `tls`
     /// is true, the connection will use SSL/TLS.
     pub async fn connect(params: ConnectionParams) -> Result<Client, ClientError> {
-        let conn = connect(&params.host, params.port(), params.tls).await?;
-        let codec = LinesCodec::new_with_max_length(MAX_LINE_LENGTH_WITH_TAGS);
+        let conn = connect(
+            &params.host,
+            params.port(),
+            params.tls,
+            params.client_cert.as_ref(),
+            params.server_cert_verifier.clone(),
+            params.socks5_proxy.as_ref(),
+        )
+        .await?;
+        if let Some(ka) = params.keepalive {
+            SockRef::from(&conn)
+                .set_tcp_keepalive(&ka.to_tcp_keepalive())
+                .map_err(ClientError::Keepalive)?;
+        }
+        let channel_binding = tls_server_end_point(&conn);
+        let codec = LinesCodec::new_with_tag_budget(MAX_LINE_LENGTH, MAX_TAG_LENGTH);
+        #[cfg(feature = "encoding_rs")]
+        let codec = match params.encoding {
+            Some(encoding) => codec.with_encoding(encoding),
+            None => codec,
+        };
         let channel = Framed::new(conn, codec);
         let autoresponders = AutoResponderSet::new();
+        let (hub, _) = broadcast::channel(SUBSCRIBE_CHANNEL_CAPACITY);
+        let rate_bucket = params.rate_limit.map(RateLimitBucket::new);
         Ok(Client {
             host: params.host,
             channel,
             autoresponders,
+            dispatch_handlers: Vec::new(),
+            hub,
             queued: VecDeque::new(),
             recved: None,
             unhandled: VecDeque::new(),
+            read_timeout: params.read_timeout,
+            channel_binding,
+            capabilities: Vec::new(),
+            isupport: ISupport::new(),
+            next_label: 0,
+            label_state: None,
+            inbox: None,
+            mux: CommandMux::new(),
+            max_in_flight: None,
+            rate_bucket,
+            state: ConnectionState::Registering,
         })
     }
 
+    /// The current stage of this `Client`'s connection lifecycle.
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// [Private] Updates `state` based on an observed message, transitioning
+    /// `Registering` to `Ready` on `RPL_WELCOME` and anything still open to
+    /// `Closed` on a server-sent `ERROR`.
+    fn observe_state(&mut self, msg: &Message) {
+        match &msg.payload {
+            Payload::Reply(Reply::Welcome(_)) if self.state == ConnectionState::Registering => {
+                self.state = ConnectionState::Ready;
+            }
+            Payload::ClientMessage(ClientMessage::Error(_)) => {
+                self.state = ConnectionState::Closed;
+            }
+            _ => (),
+        }
+    }
+
+    /// Sends a `QUIT` (with `reason`, if given), then drains remaining
+    /// server messages — running autoresponders as usual — until the
+    /// connection closes, instead of surfacing a bare
+    /// [`ClientError::Disconnect`] the way an in-flight `run()`/`recv()`
+    /// would. Returns once the socket closes normally; an I/O error while
+    /// draining is still reported, distinguishable from a clean close by
+    /// `state()` having already reached [`ConnectionState::Closing`]/
+    /// [`ConnectionState::Closed`] beforehand.
+    pub async fn quit(&mut self, reason: Option<FinalParam>) -> Result<(), ClientError> {
+        self.state = ConnectionState::Closing;
+        let quit = match reason {
+            Some(reason) => Quit::new_with_reason(reason),
+            None => Quit::new(),
+        };
+        self.send(quit).await?;
+        while self.recv_new().await?.is_some() {}
+        self.state = ConnectionState::Closed;
+        Ok(())
+    }
+
+    /// Splits this `Client` into a cheaply-cloneable, multi-producer
+    /// [`ClientSender`](split::ClientSender) and a single-consumer
+    /// [`ClientReceiver`](split::ClientReceiver), mirroring `mpsc` channel
+    /// semantics.
+    ///
+    /// The receiver keeps the existing `recv`/`recv_new`/`run`/autoresponder
+    /// behavior (via [`Deref`](std::ops::Deref) to `Client`), draining
+    /// messages queued by `ClientSender::send` into the outgoing queue
+    /// between `try_next` polls of the connection. This lets a caller hold a
+    /// clonable writer in a timer, command handler, etc. while a single
+    /// other task drives `recv()`/`run()` in a loop, without wrapping the
+    /// whole `Client` in a mutex.
+    pub fn split(mut self) -> (split::ClientSender, split::ClientReceiver) {
+        let (tx, rx) = mpsc::channel(split::SEND_CHANNEL_CAPACITY);
+        self.inbox = Some(rx);
+        (split::ClientSender::new(tx), split::ClientReceiver::new(self))
+    }
+
+    /// [Private] Moves every message currently queued by a `ClientSender`
+    /// clone (without blocking) onto the outgoing queue flushed by
+    /// `flush_queue()`.
+    fn drain_inbox(&mut self) {
+        let Some(inbox) = &mut self.inbox else {
+            return;
+        };
+        while let Ok(msg) = inbox.try_recv() {
+            self.queued.push_back(msg);
+        }
+    }
+
+    /// Returns the RFC 5929 `tls-server-end-point` channel-binding data for
+    /// this connection, for use with the `-PLUS` SCRAM mechanisms.  `None`
+    /// if the connection isn't over TLS or the server presented no
+    /// certificate.
+    pub fn channel_binding(&self) -> Option<Bytes> {
+        self.channel_binding.clone()
+    }
+
     /// Install the given `AutoResponder` in the client
     pub fn add_autoresponder<T: AutoResponder + Send + 'static>(&mut self, ar: T) {
         self.autoresponders.push(ar);
@@ -95,13 +506,171 @@ impl Client {
         self.autoresponders = set;
     }
 
+    /// Registers a handler to be invoked with every `ClientMessage` (and its
+    /// `Source`, if any) received by `recv`/`recv_new`, in registration
+    /// order, regardless of message type and whether an autoresponder or a
+    /// running `Command` also handles the message. Unlike an
+    /// [`AutoResponder`], a dispatch handler cannot mark a message handled
+    /// or send replies of its own; it's purely an observer, for building
+    /// bots declaratively instead of hand-writing a `match` over every
+    /// `recv()` result. See `on_privmsg`/`on_notice`/`on_kick`/`on_error`
+    /// for handlers restricted to a single message type.
+    pub fn on_any<F>(&mut self, f: F)
+    where
+        F: Fn(&ClientMessage, Option<&Source>) + Send + 'static,
+    {
+        self.dispatch_handlers.push(Box::new(f));
+    }
+
+    /// Registers a handler invoked only for `PRIVMSG` messages. See
+    /// [`Client::on_any`].
+    pub fn on_privmsg<F>(&mut self, f: F)
+    where
+        F: Fn(&PrivMsg, Option<&Source>) + Send + 'static,
+    {
+        self.on_any(move |msg, source| {
+            if let ClientMessage::PrivMsg(m) = msg {
+                f(m, source);
+            }
+        });
+    }
+
+    /// Registers a handler invoked only for `NOTICE` messages. See
+    /// [`Client::on_any`].
+    pub fn on_notice<F>(&mut self, f: F)
+    where
+        F: Fn(&Notice, Option<&Source>) + Send + 'static,
+    {
+        self.on_any(move |msg, source| {
+            if let ClientMessage::Notice(m) = msg {
+                f(m, source);
+            }
+        });
+    }
+
+    /// Registers a handler invoked only for `KICK` messages. See
+    /// [`Client::on_any`].
+    pub fn on_kick<F>(&mut self, f: F)
+    where
+        F: Fn(&Kick, Option<&Source>) + Send + 'static,
+    {
+        self.on_any(move |msg, source| {
+            if let ClientMessage::Kick(m) = msg {
+                f(m, source);
+            }
+        });
+    }
+
+    /// Registers a handler invoked only for `ERROR` messages. See
+    /// [`Client::on_any`].
+    pub fn on_error<F>(&mut self, f: F)
+    where
+        F: Fn(&ErrorMessage, Option<&Source>) + Send + 'static,
+    {
+        self.on_any(move |msg, source| {
+            if let ClientMessage::Error(m) = msg {
+                f(m, source);
+            }
+        });
+    }
+
+    /// [Private] Invokes every handler registered via `on_any`/`on_privmsg`/
+    /// etc. whose message type matches `msg`'s payload, in registration
+    /// order. A no-op for messages whose payload isn't a `ClientMessage`
+    /// (e.g. a numeric reply).
+    fn dispatch(&self, msg: &Message) {
+        if let Payload::ClientMessage(climsg) = &msg.payload {
+            for handler in &self.dispatch_handlers {
+                handler(climsg, msg.source.as_ref());
+            }
+        }
+    }
+
+    /// Returns a `Stream` of every message parsed off the wire by `recv()`/
+    /// `recv_new()`/`run()`, independent of whether it ends up handled by an
+    /// autoresponder, claimed by the command currently in flight, or left
+    /// unhandled — so a caller can, e.g., log all traffic from a background
+    /// task while a separate call to `run()` drives a command to completion.
+    ///
+    /// A subscription only observes messages while *some* call to `recv()`/
+    /// `recv_new()`/`run()` is actively polling the connection; unlike a
+    /// fully backgrounded reader, nothing is read off the wire on this
+    /// `Client`'s behalf when no such call is outstanding. A subscriber that
+    /// falls more than [`SUBSCRIBE_CHANNEL_CAPACITY`] messages behind the
+    /// fastest one is notified via a [`Lagged`](broadcast::error::RecvError::Lagged)
+    /// error and resumes from the oldest message still buffered, rather than
+    /// growing the channel without bound.
+    pub fn subscribe(
+        &self,
+    ) -> impl Stream<Item = Result<Arc<Message>, broadcast::error::RecvError>> {
+        stream::unfold(self.hub.subscribe(), |mut rx| async move {
+            match rx.recv().await {
+                Ok(msg) => Some((Ok(msg), rx)),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    Some((Err(broadcast::error::RecvError::Lagged(n)), rx))
+                }
+                Err(broadcast::error::RecvError::Closed) => None,
+            }
+        })
+    }
+
+    /// Returns the capabilities (with their advertised values, if any)
+    /// enabled via `CAP` negotiation during login, so that code holding the
+    /// `Client` after login completes doesn't need to keep the
+    /// `LoginOutput` around just to check what was negotiated.  Empty if
+    /// login never negotiated capabilities (e.g. no `SaslCredentials` or
+    /// `LoginParams::capabilities` were given, or the server doesn't
+    /// support `CAP`).
+    pub fn capabilities(&self) -> &[(Capability, Option<CapabilityValue>)] {
+        &self.capabilities
+    }
+
+    pub(crate) fn set_capabilities(&mut self, caps: Vec<(Capability, Option<CapabilityValue>)>) {
+        self.capabilities = caps;
+    }
+
+    /// Returns the `RPL_ISUPPORT` (005) tokens accumulated across every such
+    /// reply seen on this connection so far (including, but not limited to,
+    /// those seen during login), for casemapping-sensitive nick/channel
+    /// comparisons and the like.  See [`ISupport`] for typed accessors over
+    /// specific tokens.
+    pub fn isupport(&self) -> &ISupport {
+        &self.isupport
+    }
+
+    /// Returns whether `known` was negotiated via `CAP` during login.
+    fn has_cap(&self, known: KnownCapability) -> bool {
+        self.capabilities
+            .iter()
+            .any(|(c, _)| c.known() == Some(known))
+    }
+
+    /// Mints a fresh, unique `label` tag value for `labeled-response`.
+    fn next_label(&mut self) -> MedialParam {
+        let label = self.next_label;
+        self.next_label += 1;
+        MedialParam::try_from(label.to_string())
+            .expect("a decimal integer should be a valid MedialParam")
+    }
+
     /// Send a client message to the server.
     ///
+    /// If [`ConnectionParams::rate_limit`] was configured, this waits for a
+    /// token to become available before sending, so a long-running bot
+    /// doesn't need to sleep between calls itself to avoid an "excess flood"
+    /// kill.  Autoresponder output sent via `flush_queue` (e.g. a `PONG`
+    /// keepalive reply) bypasses this wait entirely.
+    ///
     /// # Cancellation safety
     ///
     /// If this method is cancelled, it is guaranteed that the message was not
     /// sent, but the message itself is lost.
     pub async fn send<M: Into<Message>>(&mut self, msg: M) -> Result<(), ClientError> {
+        if let Some(bucket) = &mut self.rate_bucket
+            && let Some(at) = bucket.next_available()
+        {
+            sleep_until(at).await;
+        }
         let line = msg.into().to_string();
         tracing::trace!(host = self.host, line, "Sending message to remote server");
         self.channel.send(line).await.map_err(ClientError::Send)
@@ -149,6 +718,7 @@ impl Client {
     /// `recv_new()`.
     pub async fn recv_new(&mut self) -> Result<Option<Message>, ClientError> {
         loop {
+            self.drain_inbox();
             self.flush_queue().await?;
             if let Some(msg) = self.recved.take() {
                 return Ok(Some(msg));
@@ -161,6 +731,14 @@ impl Client {
                     "Received message from remote server"
                 );
                 let msg = Message::try_from(line)?;
+                // Dropping this send on the error case just means nobody is
+                // currently subscribed; that isn't a failure.
+                let _ = self.hub.send(Arc::new(msg.clone()));
+                if let Payload::Reply(Reply::ISupport(r)) = &msg.payload {
+                    self.isupport.extend(r.tokens().iter().cloned());
+                }
+                self.observe_state(&msg);
+                self.dispatch(&msg);
                 // Store outgoing client messages and the received message on
                 // self in order to not lose data on cancellation
                 let handled = self.autoresponders.handle_message(&msg);
@@ -171,6 +749,7 @@ impl Client {
                 }
                 self.flush_queue().await?;
             } else {
+                self.state = ConnectionState::Closed;
                 return Ok(None);
             }
         }
@@ -204,6 +783,23 @@ impl Client {
         std::mem::take(&mut self.unhandled)
     }
 
+    /// Converts this `Client` into a `Stream` that yields every message
+    /// returned by `recv()`, for callers that would rather poll a stream
+    /// than call `recv()` in a loop by hand.  The stream ends once `recv()`
+    /// returns `Ok(None)` (i.e. the connection was closed); an error from
+    /// `recv()` is yielded as a single `Err` item and then also ends the
+    /// stream.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Message, ClientError>> {
+        stream::unfold(Some(self), |state| async move {
+            let mut client = state?;
+            match client.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(client))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
     /// Run a `Command` to completion, sending scripted client messages and
     /// handling replies, and return the command's output.
     ///
@@ -216,14 +812,26 @@ impl Client {
     /// `recv()` or `recv_new()`.  Any messages not marked handled will be
     /// returned by future calls to `recv()` but not `recv_new()`.
     ///
+    /// If the server has negotiated both the `labeled-response` and `batch`
+    /// capabilities, each outgoing message is stamped with a unique `label`
+    /// tag, and replies are routed to `cmd` by matching that label (directly,
+    /// or via an enclosing `labeled-response` `BATCH`) instead of relying
+    /// solely on `cmd`'s own heuristic matching; a reply carrying a *different*
+    /// label is necessarily a stray response to an earlier command and is
+    /// never offered to `cmd`. Without those capabilities, every received
+    /// message is offered to `cmd` exactly as before.
+    ///
     /// # Cancellation safety
     ///
     /// This method is not cancellation-safe.
     pub async fn run<C: Command>(&mut self, mut cmd: C) -> Result<C::Output, ClientError> {
-        for climsg in cmd.get_client_messages() {
-            self.send(climsg).await?;
-        }
-        let mut deadline = cmd.get_timeout().map(|d| Instant::now() + d);
+        let use_labels =
+            self.has_cap(KnownCapability::LabeledResponse) && self.has_cap(KnownCapability::Batch);
+        self.label_state = None;
+        self.send_scripted_messages(cmd.get_client_messages(), use_labels)
+            .await?;
+        let mut cmd_timeout = true;
+        let mut deadline = self.next_deadline(cmd.get_timeout(), &mut cmd_timeout);
         while !cmd.is_done() {
             let fut = self.recv_new();
             let r = if let Some(dl) = deadline {
@@ -233,27 +841,322 @@ impl Client {
             };
             match r {
                 Some(Ok(None)) => return Err(ClientError::Disconnect),
-                Some(Ok(Some(msg))) => {
-                    if !cmd.handle_message(&msg) {
-                        self.unhandled.push_back(msg);
+                Some(Ok(Some(msg))) => match self.route_labeled(&msg) {
+                    Route::ToCommand => {
+                        if !cmd.handle_message(&msg) {
+                            self.unhandled.push_back(msg);
+                        }
                     }
-                }
+                    Route::ToUnhandled => self.unhandled.push_back(msg),
+                    Route::Consumed => (),
+                },
                 Some(Err(e)) => return Err(e),
                 None => {
                     deadline = None;
-                    cmd.handle_timeout();
+                    if cmd_timeout {
+                        cmd.handle_timeout();
+                    } else {
+                        return Err(ClientError::ReadTimeout);
+                    }
                 }
             }
-            for climsg in cmd.get_client_messages() {
-                self.send(climsg).await?;
-            }
-            if let Some(d) = cmd.get_timeout() {
-                deadline = Some(Instant::now() + d);
-            }
+            self.send_scripted_messages(cmd.get_client_messages(), use_labels)
+                .await?;
+            deadline = self.next_deadline(cmd.get_timeout(), &mut cmd_timeout);
         }
         cmd.get_output()
             .map_err(|e| ClientError::Command(Box::new(e)))
     }
+
+    /// Caps the number of commands that may be in flight at once via
+    /// [`spawn`](Self::spawn); `spawn()` fails with
+    /// [`ClientError::TooManyInFlight`] once this many are already running.
+    /// `None` (the default) means no cap.
+    pub fn set_max_in_flight(&mut self, max: Option<usize>) {
+        self.max_in_flight = max;
+    }
+
+    /// The number of commands currently in flight via [`spawn`](Self::spawn).
+    pub fn in_flight(&self) -> usize {
+        self.mux.len()
+    }
+
+    /// Submits `cmd` to run concurrently with any other commands already in
+    /// flight via `spawn()`, returning a [`Ticket`] that resolves once `cmd`
+    /// completes.
+    ///
+    /// Unlike `run()`, submitting a command doesn't advance it; call
+    /// [`drive`](Self::drive) in a loop to make progress on every in-flight
+    /// command (including this one) and deliver results to their tickets.
+    /// Fails with [`ClientError::TooManyInFlight`] if
+    /// [`set_max_in_flight`](Self::set_max_in_flight) was used and the cap
+    /// has already been reached.
+    pub fn spawn<C>(&mut self, cmd: C) -> Result<Ticket<C>, ClientError>
+    where
+        C: Command + Send + 'static,
+        C::Output: Send,
+        C::Error: Send,
+    {
+        if self.max_in_flight.is_some_and(|max| self.mux.len() >= max) {
+            return Err(ClientError::TooManyInFlight);
+        }
+        Ok(self.mux.submit(cmd))
+    }
+
+    /// Advances every command currently in flight via `spawn()`: sends each
+    /// one's scripted outgoing messages, waits for either the next message
+    /// from the server or the soonest in-flight deadline (whichever comes
+    /// first), and offers a received message to the in-flight commands in
+    /// submission order until one claims it, pushing it to `unhandled`
+    /// if none do. A command whose deadline elapses instead has its
+    /// `handle_timeout()` fired. Completed commands deliver their output to
+    /// their `Ticket` automatically.
+    ///
+    /// # Cancellation safety
+    ///
+    /// This method is not cancellation-safe.
+    pub async fn drive(&mut self) -> Result<(), ClientError> {
+        self.send_scripted_messages(self.mux.get_client_messages(), false)
+            .await?;
+        let deadline = self.mux.get_timeout().map(|d| Instant::now() + d);
+        let fut = self.recv_new();
+        let r = if let Some(dl) = deadline {
+            timeout_at(dl, fut).await.ok()
+        } else {
+            Some(fut.await)
+        };
+        match r {
+            Some(Ok(None)) => return Err(ClientError::Disconnect),
+            Some(Ok(Some(msg))) => {
+                if !self.mux.handle_message(&msg) {
+                    self.unhandled.push_back(msg);
+                }
+            }
+            Some(Err(e)) => return Err(e),
+            None => self.mux.handle_timeout(),
+        }
+        self.send_scripted_messages(self.mux.get_client_messages(), false)
+            .await?;
+        Ok(())
+    }
+
+    /// [Private] Sends a `Command`'s scripted outgoing messages, stamping
+    /// each with a fresh `label` tag and recording it as the currently
+    /// awaited label (see [`Client::route_labeled`]) when `use_labels` is
+    /// set. Only the label of the *last* message sent is tracked, since a
+    /// `Client` only ever runs one `Command` at a time.
+    async fn send_scripted_messages(
+        &mut self,
+        climsgs: Vec<ClientMessage>,
+        use_labels: bool,
+    ) -> Result<(), ClientError> {
+        for climsg in climsgs {
+            let mut msg = Message::from(climsg);
+            if use_labels {
+                let label = self.next_label();
+                let key = TagKey::try_from("label".to_owned())
+                    .expect(r#""label" should be a valid TagKey"#);
+                let value = TagValue::try_from(label.to_string())
+                    .expect("a decimal integer should be a valid TagValue");
+                msg.tags
+                    .get_or_insert_with(MessageTags::new)
+                    .insert(key, Some(value));
+                self.label_state = Some(LabelState {
+                    label,
+                    batch_ref: None,
+                });
+            }
+            self.send(msg).await?;
+        }
+        Ok(())
+    }
+
+    /// [Private] Decides whether a received message should be offered to the
+    /// running command, routing by the awaited `label`/`labeled-response`
+    /// `BATCH` set up by [`Client::send_scripted_messages`] when one is
+    /// outstanding, and falling back to offering every message to the
+    /// command (today's heuristic-only behavior) otherwise.
+    fn route_labeled(&mut self, msg: &Message) -> Route {
+        let Some(state) = &mut self.label_state else {
+            return Route::ToCommand;
+        };
+        if let Payload::ClientMessage(ClientMessage::Batch(Batch::Start(start))) = &msg.payload {
+            if start.batch_type().as_ref() == "labeled-response"
+                && msg.tags.as_ref().and_then(MessageTags::label) == Some(state.label.as_str())
+            {
+                state.batch_ref = Some(start.reference_tag().clone());
+                return Route::Consumed;
+            }
+        }
+        if let Payload::ClientMessage(ClientMessage::Batch(Batch::End(end))) = &msg.payload {
+            if state.batch_ref.as_ref() == Some(end.reference_tag()) {
+                self.label_state = None;
+                return Route::Consumed;
+            }
+        }
+        if let Some(batch_ref) = &state.batch_ref {
+            return if msg.tags.as_ref().and_then(MessageTags::batch) == Some(batch_ref.as_str()) {
+                Route::ToCommand
+            } else {
+                Route::ToUnhandled
+            };
+        }
+        match msg.tags.as_ref().and_then(MessageTags::label) {
+            Some(label) if label == state.label.as_str() => Route::ToCommand,
+            Some(_) => Route::ToUnhandled,
+            None => Route::ToCommand,
+        }
+    }
+
+    /// [Private] Combine a command's requested timeout with the client's
+    /// overall `read_timeout` fallback, recording in `cmd_timeout` whether
+    /// the resulting deadline (if any) belongs to the command (so that its
+    /// `handle_timeout()` should be called) or is just the read-timeout
+    /// backstop (so that a silent connection should be reported as dead
+    /// instead).
+    fn next_deadline(
+        &self,
+        cmd_timeout: Option<Duration>,
+        cmd_timeout_flag: &mut bool,
+    ) -> Option<Instant> {
+        match (cmd_timeout, self.read_timeout) {
+            (Some(d), _) => {
+                *cmd_timeout_flag = true;
+                Some(Instant::now() + d)
+            }
+            (None, Some(d)) => {
+                *cmd_timeout_flag = false;
+                Some(Instant::now() + d)
+            }
+            (None, None) => {
+                *cmd_timeout_flag = true;
+                None
+            }
+        }
+    }
+}
+
+/// Yields every message returned by `recv()`/`recv_new()` — running
+/// autoresponders and flushing the `queued`/`recved` state exactly as
+/// `recv_new()` does — so a `Client` can be driven with `StreamExt`
+/// combinators (`filter`, `forward`, `split`, buffering adapters, …)
+/// instead of a hand-written `loop { recv().await }`. Unlike
+/// [`into_stream`](Client::into_stream), this doesn't consume the `Client`.
+impl Stream for Client {
+    type Item = Result<Message, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            this.drain_inbox();
+            match this.poll_flush_queued(cx) {
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+            if let Some(msg) = this.recved.take() {
+                return Poll::Ready(Some(Ok(msg)));
+            }
+            match Pin::new(&mut this.channel).poll_next(cx) {
+                Poll::Ready(Some(Ok(line))) => {
+                    tracing::trace!(
+                        host = this.host,
+                        line,
+                        "Received message from remote server"
+                    );
+                    let msg = match Message::try_from(line) {
+                        Ok(msg) => msg,
+                        Err(e) => return Poll::Ready(Some(Err(e.into()))),
+                    };
+                    // Dropping this send on the error case just means
+                    // nobody is currently subscribed; that isn't a failure.
+                    let _ = this.hub.send(Arc::new(msg.clone()));
+                    if let Payload::Reply(Reply::ISupport(r)) = &msg.payload {
+                        this.isupport.extend(r.tokens().iter().cloned());
+                    }
+                    this.observe_state(&msg);
+                    this.dispatch(&msg);
+                    let handled = this.autoresponders.handle_message(&msg);
+                    this.queued
+                        .extend(this.autoresponders.get_outgoing_messages());
+                    if !handled {
+                        this.recved = Some(msg);
+                    }
+                    // Loop back around to flush the queue and re-check
+                    // `recved`/poll the connection again.
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(ClientError::Recv(e)))),
+                Poll::Ready(None) => {
+                    this.state = ConnectionState::Closed;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Client {
+    /// [Private] Poll-based equivalent of `flush_queue()`, for use by the
+    /// `Stream` impl, which can't `.await` it directly.
+    fn poll_flush_queued(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), ClientError>> {
+        while let Some(msg) = self.queued.front() {
+            match Pin::new(&mut self.channel).poll_ready(cx) {
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(ClientError::Send(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+            let line = msg.to_string();
+            tracing::trace!(
+                host = self.host,
+                line,
+                "Sending autoresponse to remote server"
+            );
+            let r = Pin::new(&mut self.channel)
+                .start_send(line)
+                .map_err(ClientError::Send);
+            let _ = self.queued.pop_front();
+            if let Err(e) = r {
+                return Poll::Ready(Err(e));
+            }
+        }
+        Pin::new(&mut self.channel)
+            .poll_flush(cx)
+            .map_err(ClientError::Send)
+    }
+}
+
+/// Forwards to the underlying connection, with the same cancellation
+/// guarantees as [`Client::send`].
+impl Sink<ClientMessage> for Client {
+    type Error = ClientError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().channel)
+            .poll_ready(cx)
+            .map_err(ClientError::Send)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: ClientMessage) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let line = Message::from(item).to_string();
+        tracing::trace!(host = this.host, line, "Sending message to remote server");
+        Pin::new(&mut this.channel)
+            .start_send(line)
+            .map_err(ClientError::Send)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().channel)
+            .poll_flush(cx)
+            .map_err(ClientError::Send)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().channel)
+            .poll_close(cx)
+            .map_err(ClientError::Send)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -268,6 +1171,23 @@ pub enum ClientError {
     Parse(#[from] TryFromStringError<ParseMessageError>),
     #[error("command failed")]
     Command(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The connection closed while a command was awaiting a reply.
+    ///
+    /// This is returned whenever the connection reaches end-of-stream,
+    /// whether or not the server sent an `ERROR` first.
+    /// [`Client::state()`](super::Client::state) is
+    /// [`Closed`](ConnectionState::Closed) afterwards in either case, so it
+    /// does not tell a clean server-initiated close from an unannounced one.
+    /// An I/O error on the socket is reported as [`ClientError::Recv`]
+    /// instead.
     #[error("connection terminated while running command")]
     Disconnect,
+    #[error("failed to configure TCP keepalive")]
+    Keepalive(#[source] std::io::Error),
+    #[error("no server activity within the configured read timeout")]
+    ReadTimeout,
+    #[error("the ClientReceiver half of a split Client has been dropped")]
+    ReceiverDropped,
+    #[error("too many commands already in flight")]
+    TooManyInFlight,
 }
@@ -0,0 +1,210 @@
+use super::commands::Command;
+use irctext::{ClientMessage, Message};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// A handle for the eventual output of a [`Command`] submitted to a
+/// [`CommandMux`] via [`CommandMux::submit`].
+///
+/// Resolves to `None` if the mux is dropped before the command completes.
+pub struct Ticket<C: Command> {
+    rx: oneshot::Receiver<Result<C::Output, C::Error>>,
+}
+
+impl<C: Command> Ticket<C> {
+    pub async fn wait(self) -> Option<Result<C::Output, C::Error>> {
+        self.rx.await.ok()
+    }
+}
+
+trait MuxEntry: Send {
+    fn get_client_messages(&mut self) -> Vec<ClientMessage>;
+    fn handle_message(&mut self, msg: &Message) -> bool;
+    fn get_timeout(&mut self) -> Option<Duration>;
+    fn handle_timeout(&mut self);
+    fn is_done(&self) -> bool;
+    fn finish(self: Box<Self>);
+}
+
+struct Entry<C: Command> {
+    cmd: C,
+    sender: Option<oneshot::Sender<Result<C::Output, C::Error>>>,
+}
+
+impl<C> MuxEntry for Entry<C>
+where
+    C: Command + Send,
+    C::Output: Send,
+    C::Error: Send,
+{
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        self.cmd.get_client_messages()
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        self.cmd.handle_message(msg)
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        self.cmd.get_timeout()
+    }
+
+    fn handle_timeout(&mut self) {
+        self.cmd.handle_timeout();
+    }
+
+    fn is_done(&self) -> bool {
+        self.cmd.is_done()
+    }
+
+    fn finish(mut self: Box<Self>) {
+        let output = self.cmd.get_output();
+        if let Some(tx) = self.sender.take() {
+            // If the caller dropped their Ticket, there's nothing to do
+            // with the output.
+            let _ = tx.send(output);
+        }
+    }
+}
+
+struct Slot {
+    entry: Box<dyn MuxEntry>,
+    deadline: Option<Instant>,
+}
+
+/// Multiplexes any number of concurrently in-flight [`Command`]s over a
+/// single connection, with id-correlated delivery of each command's result
+/// back to its submitter — the same request/response routing a JSON-RPC peer
+/// does for its callers.
+///
+/// `CommandMux` exposes the same send/receive/timeout shape as [`Command`]
+/// itself, minus `is_done()`/`get_output()` (a mux never "finishes"), so it
+/// can be driven by the same loop used to run a single `Command`:
+///
+/// - `get_client_messages()` concatenates every child's outgoing messages.
+/// - `handle_message()` offers an incoming message to each child, in
+///   submission order, until one claims it by returning `true`.
+/// - `get_timeout()` returns the soonest of the children's deadlines.
+/// - `handle_timeout()` fires only the child(ren) whose own deadline has
+///   actually elapsed, leaving the others waiting.
+///
+/// Completed children are harvested automatically (after every call above
+/// except `get_timeout()`)
+/// and their output delivered via the [`Ticket`] returned by
+/// [`submit`][CommandMux::submit], or discarded for commands submitted via
+/// [`notify`][CommandMux::notify] as fire-and-forget.
+#[derive(Default)]
+pub struct CommandMux {
+    slots: Vec<Slot>,
+}
+
+impl CommandMux {
+    pub fn new() -> CommandMux {
+        CommandMux::default()
+    }
+
+    /// Submit a command that expects a reply, returning a ticket the caller
+    /// can await for its eventual output.
+    pub fn submit<C>(&mut self, cmd: C) -> Ticket<C>
+    where
+        C: Command + Send + 'static,
+        C::Output: Send,
+        C::Error: Send,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.slots.push(Slot {
+            entry: Box::new(Entry {
+                cmd,
+                sender: Some(tx),
+            }),
+            deadline: None,
+        });
+        Ticket { rx }
+    }
+
+    /// Submit a fire-and-forget command: it runs to completion like any
+    /// other, but its output is discarded instead of delivered anywhere.
+    pub fn notify<C>(&mut self, cmd: C)
+    where
+        C: Command + Send + 'static,
+        C::Output: Send,
+        C::Error: Send,
+    {
+        self.slots.push(Slot {
+            entry: Box::new(Entry { cmd, sender: None }),
+            deadline: None,
+        });
+    }
+
+    /// Concatenate the outgoing messages of every in-flight command.
+    pub fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        let mut out = Vec::new();
+        for slot in &mut self.slots {
+            out.extend(slot.entry.get_client_messages());
+        }
+        self.harvest();
+        out
+    }
+
+    /// Offer an incoming message to each in-flight command in submission
+    /// order until one claims it.
+    pub fn handle_message(&mut self, msg: &Message) -> bool {
+        let mut handled = false;
+        for slot in &mut self.slots {
+            if slot.entry.handle_message(msg) {
+                handled = true;
+                break;
+            }
+        }
+        self.harvest();
+        handled
+    }
+
+    /// Returns the duration until the soonest of the in-flight commands'
+    /// deadlines, if any have one.
+    pub fn get_timeout(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let mut soonest = None;
+        for slot in &mut self.slots {
+            slot.deadline = slot.entry.get_timeout().map(|d| now + d);
+            if let Some(dl) = slot.deadline {
+                soonest = Some(soonest.map_or(dl, |s: Instant| s.min(dl)));
+            }
+        }
+        soonest.map(|dl| dl.saturating_duration_since(now))
+    }
+
+    /// Fire `handle_timeout()` on every in-flight command whose own deadline
+    /// (as of the last `get_timeout()` call) has elapsed.
+    pub fn handle_timeout(&mut self) {
+        let now = Instant::now();
+        for slot in &mut self.slots {
+            if slot.deadline.is_some_and(|dl| dl <= now) {
+                slot.entry.handle_timeout();
+                slot.deadline = None;
+            }
+        }
+        self.harvest();
+    }
+
+    /// The number of commands still in flight.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    fn harvest(&mut self) {
+        let mut i = 0;
+        while i < self.slots.len() {
+            if self.slots[i].entry.is_done() {
+                let slot = self.slots.remove(i);
+                slot.entry.finish();
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Reconnection with exponential backoff, for recovering from a dropped or
+//! self-terminated connection
+use super::commands::{JoinCommand, Login, LoginOutput, LoginParams};
+use super::{Client, ClientError, ConnectionParams, SessionParams};
+use irctext::types::Channel;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::time::Duration;
+
+/// A schedule of retry delays to use when (re)establishing a session fails,
+/// inspired by distant's retry policy: a bounded number of attempts, each
+/// waiting longer than the last (up to a cap), to avoid hammering a server
+/// that's down or self-terminating connections under load.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ReconnectPolicy {
+    /// The maximum number of attempts to make (including the first), or
+    /// `None` to retry forever.
+    pub max_attempts: Option<u32>,
+
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+
+    /// The factor by which the delay grows after each failed attempt.
+    pub multiplier: f64,
+
+    /// The maximum delay between attempts, regardless of how many attempts
+    /// have already failed.
+    pub max_delay: Duration,
+
+    /// If true, each computed delay is scaled by a random factor in `0.5..=1.0`
+    /// so that multiple clients backing off at once don't all retry in
+    /// lockstep.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub jitter: bool,
+}
+
+impl ReconnectPolicy {
+    /// Returns the delay to wait before the retry numbered `attempt`
+    /// (0-based; `attempt` is one less than the number of attempts that
+    /// have already failed, so the first retry waits `initial_delay`).
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .powi(i32::try_from(attempt).unwrap_or(i32::MAX));
+        let secs = self.initial_delay.as_secs_f64() * factor;
+        let mut delay = Duration::try_from_secs_f64(secs).unwrap_or(self.max_delay);
+        if delay > self.max_delay {
+            delay = self.max_delay;
+        }
+        if self.jitter {
+            let mut rng = StdRng::from_os_rng();
+            delay = delay.mul_f64(rng.random_range(0.5..=1.0));
+        }
+        delay
+    }
+
+    /// Returns true if `attempts` failed attempts means no further retries
+    /// should be made.
+    fn exhausted(&self, attempts: u32) -> bool {
+        self.max_attempts.is_some_and(|max| attempts >= max)
+    }
+}
+
+/// Wraps a [`SessionParams`] and [`ReconnectPolicy`] so that a dropped
+/// session (a [`ClientError::Disconnect`] or [`ClientError::Recv`] from
+/// [`Client::run`]/[`Client::recv`]) can be re-established by calling
+/// [`ReconnectingSession::reconnect`] again with the channels the caller had
+/// joined, rather than hand-rolling a retry loop around
+/// [`SessionBuilder`](super::SessionBuilder) at every call site.
+///
+/// Since [`LoginParams`] already fully captures the `NICK`/`USER`/`CAP`/SASL
+/// registration messages to send, "replaying" a session after a reconnect is
+/// just running [`Login`] again with the same params; [`ReconnectingSession`]
+/// doesn't need to separately record them.  It has no way to know on its own
+/// which channels were joined, though, so callers that want them automatically
+/// rejoined after a reconnect need to track that themselves and pass the list
+/// in to [`ReconnectingSession::reconnect`].
+#[allow(missing_debug_implementations)]
+pub struct ReconnectingSession {
+    connect: ConnectionParams,
+    login: LoginParams,
+    policy: ReconnectPolicy,
+}
+
+impl ReconnectingSession {
+    pub fn new(params: SessionParams, policy: ReconnectPolicy) -> ReconnectingSession {
+        ReconnectingSession {
+            connect: params.connect,
+            login: params.login,
+            policy,
+        }
+    }
+
+    /// (Re)establishes the session: connects, logs in, and rejoins
+    /// `channels` (if any), retrying according to `self`'s [`ReconnectPolicy`]
+    /// if an attempt fails.  Returns the last error once the policy's
+    /// `max_attempts` is exhausted.
+    ///
+    /// This is also the right method to call to establish the *first*
+    /// connection of a session that's meant to auto-reconnect for its whole
+    /// lifetime, since a dropped connection during login is handled the same
+    /// way as one dropped afterwards.
+    pub async fn reconnect(
+        &self,
+        channels: Vec<Channel>,
+    ) -> Result<(Client, LoginOutput), ClientError> {
+        let mut attempts = 0;
+        loop {
+            match self.try_connect(channels.clone()).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    attempts += 1;
+                    if self.policy.exhausted(attempts) {
+                        return Err(e);
+                    }
+                    tokio::time::sleep(self.policy.delay_for(attempts - 1)).await;
+                }
+            }
+        }
+    }
+
+    async fn try_connect(
+        &self,
+        channels: Vec<Channel>,
+    ) -> Result<(Client, LoginOutput), ClientError> {
+        let mut client = Client::connect(self.connect.clone()).await?;
+        let channel_binding = client.channel_binding();
+        let login_output = client
+            .run(Login::new(self.login.clone(), channel_binding))
+            .await?;
+        client.set_capabilities(login_output.capabilities.clone());
+        if let Some(join) = JoinCommand::new_many(channels) {
+            client.run(join).await?;
+        }
+        Ok((client, login_output))
+    }
+}
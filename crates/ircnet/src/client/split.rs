@@ -0,0 +1,73 @@
+//! [`Client::split`](super::Client::split)'s sender/receiver halves
+use super::{Client, ClientError};
+use irctext::Message;
+use std::ops::{Deref, DerefMut};
+use tokio::sync::mpsc;
+
+/// Capacity of the bounded channel backing a split `Client`'s
+/// [`ClientSender`] clones.  A `ClientSender::send()` call blocks once this
+/// many messages are queued and not yet drained by the `ClientReceiver`.
+pub(super) const SEND_CHANNEL_CAPACITY: usize = 256;
+
+/// The cheaply-cloneable, multi-producer half of a [`Client`] split via
+/// [`Client::split`].
+///
+/// Each clone sends onto the same bounded queue, which the corresponding
+/// [`ClientReceiver`] drains between polls of the connection.  Holding a
+/// `ClientSender` (e.g. in a timer task or a command handler) lets that code
+/// send messages without needing `&mut` access to the `Client` driving
+/// `recv()`/`run()`.
+#[derive(Clone, Debug)]
+pub struct ClientSender(mpsc::Sender<Message>);
+
+impl ClientSender {
+    pub(super) fn new(sender: mpsc::Sender<Message>) -> ClientSender {
+        ClientSender(sender)
+    }
+
+    /// Queues a message to be sent to the server by the `ClientReceiver`
+    /// half. Returns [`ClientError::ReceiverDropped`] if that half (and thus
+    /// the connection) is gone.
+    pub async fn send<M: Into<Message>>(&self, msg: M) -> Result<(), ClientError> {
+        self.0
+            .send(msg.into())
+            .await
+            .map_err(|_| ClientError::ReceiverDropped)
+    }
+}
+
+/// The single-consumer half of a [`Client`] split via [`Client::split`].
+///
+/// `ClientReceiver` derefs to the underlying [`Client`], so every method
+/// other than `split()` itself (`recv`, `recv_new`, `run`, `subscribe`,
+/// `add_autoresponder`, etc.) is still available and behaves exactly as it
+/// does on an unsplit `Client`; `recv`/`recv_new`/`run` additionally drain
+/// whatever's been queued by any live [`ClientSender`] clone before each
+/// poll of the connection.
+#[allow(missing_debug_implementations)]
+pub struct ClientReceiver(Client);
+
+impl ClientReceiver {
+    pub(super) fn new(client: Client) -> ClientReceiver {
+        ClientReceiver(client)
+    }
+
+    /// Consumes this `ClientReceiver`, returning the underlying `Client`.
+    pub fn into_inner(self) -> Client {
+        self.0
+    }
+}
+
+impl Deref for ClientReceiver {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.0
+    }
+}
+
+impl DerefMut for ClientReceiver {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.0
+    }
+}
@@ -16,6 +16,11 @@ impl MessageCodec {
     pub fn new_with_max_length(max_length: usize) -> MessageCodec {
         MessageCodec(LinesCodec::new_with_max_length(max_length))
     }
+
+    /// See [`LinesCodec::new_with_tag_budget`].
+    pub fn new_with_tag_budget(max_length: usize, tag_budget: usize) -> MessageCodec {
+        MessageCodec(LinesCodec::new_with_tag_budget(max_length, tag_budget))
+    }
 }
 
 impl Decoder for MessageCodec {
@@ -47,6 +52,17 @@ impl<T: ClientMessageParts> Encoder<T> for MessageCodec {
     }
 }
 
+/// Encodes a full [`Message`], tags and source included, unlike the
+/// [`ClientMessageParts`] impl above, which only ever renders an outgoing
+/// client command with no prefix.
+impl Encoder<Message> for MessageCodec {
+    type Error = MessageCodecError;
+
+    fn encode(&mut self, msg: Message, buf: &mut BytesMut) -> Result<(), MessageCodecError> {
+        self.0.encode(msg.to_string(), buf).map_err(Into::into)
+    }
+}
+
 impl From<LinesCodec> for MessageCodec {
     fn from(value: LinesCodec) -> MessageCodec {
         MessageCodec(value)
@@ -64,6 +80,12 @@ pub enum MessageCodecError {
     #[error("maximum incoming line length exceeded")]
     MaxLineLengthExceeded,
 
+    #[error("message tag section exceeded the configured tag budget")]
+    TagSectionTooLong,
+
+    #[error("line contained a NUL or embedded CR")]
+    IllegalByte,
+
     #[error("I/O error communicating with server")]
     Io(#[from] io::Error),
 
@@ -75,6 +97,8 @@ impl From<LinesCodecError> for MessageCodecError {
     fn from(e: LinesCodecError) -> MessageCodecError {
         match e {
             LinesCodecError::MaxLineLengthExceeded => MessageCodecError::MaxLineLengthExceeded,
+            LinesCodecError::TagSectionTooLong => MessageCodecError::TagSectionTooLong,
+            LinesCodecError::IllegalByte => MessageCodecError::IllegalByte,
             LinesCodecError::Io(inner) => MessageCodecError::Io(inner),
         }
     }
@@ -1,20 +1,135 @@
-use super::lines::{LinesCodec, LinesCodecError};
-use bytes::BytesMut;
+use bytes::{Buf, BufMut, BytesMut};
 use irctext::{ParseRawMessageError, RawMessage, TryFromStringError};
-use std::io;
+use std::{cmp, io};
 use thiserror::Error;
 use tokio_util::codec::{Decoder, Encoder};
 
+/// Decodes/encodes [`RawMessage`]s directly against a connection's
+/// `BytesMut` read buffer.
+///
+/// Rather than going through [`LinesCodec`](super::super::connect::codecs::lines::LinesCodec)
+/// (which first splits off a line as an owned `String`, and only then
+/// re-scans that string for the leading `@tags` boundary), `RawMessageCodec`
+/// locates the line terminator and the end of the tag section in a single
+/// forward pass over the buffer, and enforces `max_length`/the tag budget as
+/// that scan proceeds instead of after the line has already been copied
+/// out. A `String` is still allocated for the line that was found, since
+/// [`RawMessage`]'s parser works over `&str`, but unlike the old
+/// `LinesCodec`-wrapping implementation, the line is never rescanned just to
+/// locate the tag section a second time.
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct RawMessageCodec(LinesCodec);
+pub struct RawMessageCodec {
+    // Stored index of the next index to examine for a `\n` character, as in
+    // `LinesCodec`.
+    next_index: usize,
+
+    /// The maximum length for the non-tag portion of a line. If
+    /// `usize::MAX`, lines will be read until a `\n` character is reached.
+    max_length: usize,
+
+    /// If set, `max_length` only applies to the non-tag portion of the
+    /// line, and this field caps the leading `@tags ` section on its own;
+    /// see [`RawMessageCodec::new_with_tag_budget`].
+    tag_budget: Option<usize>,
+
+    /// Are we currently discarding the remainder of a line which was over
+    /// the length limit?
+    is_discarding: bool,
+}
 
 impl RawMessageCodec {
     pub fn new() -> RawMessageCodec {
-        RawMessageCodec(LinesCodec::new())
+        RawMessageCodec {
+            next_index: 0,
+            max_length: usize::MAX,
+            tag_budget: None,
+            is_discarding: false,
+        }
     }
 
     pub fn new_with_max_length(max_length: usize) -> RawMessageCodec {
-        RawMessageCodec(LinesCodec::new_with_max_length(max_length))
+        RawMessageCodec {
+            max_length,
+            ..RawMessageCodec::new()
+        }
+    }
+
+    /// Returns a `RawMessageCodec` that enforces the IRCv3 message-tags
+    /// length limits separately from the rest of the line, per
+    /// <https://modern.ircdocs.horse/#size-limits>: the leading `@tags `
+    /// section (if present) may use up to `tag_budget` bytes, while the
+    /// remainder of the line, including the terminating CR LF, is capped at
+    /// `max_length` bytes, matching plain IRC's long-standing per-message
+    /// limit. [`RawMessageCodecError::MaxLineLengthExceeded`] is returned if
+    /// the non-tag portion overflows, and
+    /// [`RawMessageCodecError::TagSectionTooLong`] if the tag portion does.
+    pub fn new_with_tag_budget(max_length: usize, tag_budget: usize) -> RawMessageCodec {
+        RawMessageCodec {
+            tag_budget: Some(tag_budget),
+            ..RawMessageCodec::new_with_max_length(max_length)
+        }
+    }
+
+    fn cap(&self) -> usize {
+        match self.tag_budget {
+            Some(tag_budget) => self.max_length.saturating_add(tag_budget),
+            None => self.max_length,
+        }
+    }
+
+    /// Scans `buf[self.next_index..read_to]` for the line terminator,
+    /// tracking the first space seen along the way so that, if the line
+    /// turns out to start with `@`, the tag section's end is already known
+    /// without a second scan. Returns `(newline_index, first_space)`, both
+    /// relative to the start of `buf`.
+    fn scan(buf: &BytesMut, start: usize, read_to: usize) -> Option<(usize, Option<usize>)> {
+        let mut first_space = None;
+        for (offset, &b) in buf[start..read_to].iter().enumerate() {
+            let i = start + offset;
+            match b {
+                b' ' if first_space.is_none() => first_space = Some(i),
+                b'\n' => return Some((i, first_space)),
+                _ => (),
+            }
+        }
+        None
+    }
+
+    /// Given the just-split-off `line` (including its terminating CR LF)
+    /// and the first space found while scanning for it, returns the number
+    /// of leading bytes that make up the `@tags ` section, or `0` if `line`
+    /// doesn't start with `@`.
+    fn tag_len(line: &[u8], first_space: Option<usize>) -> usize {
+        if line.first() == Some(&b'@') {
+            match first_space {
+                Some(pos) if pos < line.len() => pos + 1,
+                _ => line.len(),
+            }
+        } else {
+            0
+        }
+    }
+
+    fn finish_line(
+        &mut self,
+        buf: &mut BytesMut,
+        newline_index: usize,
+        first_space: Option<usize>,
+    ) -> Result<RawMessage, RawMessageCodecError> {
+        self.next_index = 0;
+        let line = buf.split_to(newline_index + 1);
+        if let Some(tag_budget) = self.tag_budget {
+            let tag_len = Self::tag_len(&line, first_space);
+            if tag_len > tag_budget {
+                return Err(RawMessageCodecError::TagSectionTooLong);
+            }
+            if line.len() - tag_len > self.max_length {
+                return Err(RawMessageCodecError::MaxLineLengthExceeded);
+            }
+        }
+        let line = chomp(&line);
+        let line = decode_utf8_latin1(line.to_vec());
+        Ok(RawMessage::try_from(line)?)
     }
 }
 
@@ -23,10 +138,41 @@ impl Decoder for RawMessageCodec {
     type Error = RawMessageCodecError;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<RawMessage>, RawMessageCodecError> {
-        match self.0.decode(buf) {
-            Ok(Some(line)) => Ok(Some(RawMessage::try_from(line)?)),
-            Ok(None) => Ok(None),
-            Err(e) => Err(e.into()),
+        loop {
+            let cap = self.cap();
+            let read_to = cmp::min(cap, buf.len());
+            let found = Self::scan(buf, self.next_index, read_to);
+            match (self.is_discarding, found) {
+                (true, Some((newline_index, _))) => {
+                    buf.advance(newline_index + 1);
+                    self.is_discarding = false;
+                    self.next_index = 0;
+                }
+                (true, None) => {
+                    buf.advance(read_to);
+                    self.next_index = 0;
+                    if buf.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some((newline_index, first_space))) => {
+                    return self.finish_line(buf, newline_index, first_space).map(Some);
+                }
+                (false, None) if buf.len() >= cap => {
+                    self.is_discarding = true;
+                    if self.tag_budget.is_some()
+                        && buf.first() == Some(&b'@')
+                        && !buf[..read_to].contains(&b' ')
+                    {
+                        return Err(RawMessageCodecError::TagSectionTooLong);
+                    }
+                    return Err(RawMessageCodecError::MaxLineLengthExceeded);
+                }
+                (false, None) => {
+                    self.next_index = read_to;
+                    return Ok(None);
+                }
+            }
         }
     }
 
@@ -34,10 +180,17 @@ impl Decoder for RawMessageCodec {
         &mut self,
         buf: &mut BytesMut,
     ) -> Result<Option<RawMessage>, RawMessageCodecError> {
-        match self.0.decode_eof(buf) {
-            Ok(Some(line)) => Ok(Some(RawMessage::try_from(line)?)),
-            Ok(None) => Ok(None),
-            Err(e) => Err(e.into()),
+        match self.decode(buf)? {
+            Some(msg) => Ok(Some(msg)),
+            None => {
+                if buf.is_empty() || buf == &b"\r"[..] {
+                    Ok(None)
+                } else {
+                    let newline_index = buf.len() - 1;
+                    let first_space = buf.iter().position(|&b| b == b' ');
+                    self.finish_line(buf, newline_index, first_space).map(Some)
+                }
+            }
         }
     }
 }
@@ -46,13 +199,12 @@ impl Encoder<RawMessage> for RawMessageCodec {
     type Error = RawMessageCodecError;
 
     fn encode(&mut self, msg: RawMessage, buf: &mut BytesMut) -> Result<(), RawMessageCodecError> {
-        self.0.encode(msg.to_string(), buf).map_err(Into::into)
-    }
-}
-
-impl From<LinesCodec> for RawMessageCodec {
-    fn from(value: LinesCodec) -> RawMessageCodec {
-        RawMessageCodec(value)
+        let line = msg.to_string();
+        buf.reserve(line.len() + 2);
+        buf.put(line.as_bytes());
+        buf.put_u8(b'\r');
+        buf.put_u8(b'\n');
+        Ok(())
     }
 }
 
@@ -67,6 +219,9 @@ pub enum RawMessageCodecError {
     #[error("maximum incoming line length exceeded")]
     MaxLineLengthExceeded,
 
+    #[error("message tag section exceeded the configured tag budget")]
+    TagSectionTooLong,
+
     #[error("I/O error communicating with server")]
     Io(#[from] io::Error),
 
@@ -74,11 +229,82 @@ pub enum RawMessageCodecError {
     Parse(#[from] TryFromStringError<ParseRawMessageError>),
 }
 
-impl From<LinesCodecError> for RawMessageCodecError {
-    fn from(e: LinesCodecError) -> RawMessageCodecError {
-        match e {
-            LinesCodecError::MaxLineLengthExceeded => RawMessageCodecError::MaxLineLengthExceeded,
-            LinesCodecError::Io(inner) => RawMessageCodecError::Io(inner),
-        }
+fn chomp(mut s: &[u8]) -> &[u8] {
+    if s.last() == Some(&b'\n') {
+        s = &s[..s.len() - 1];
+    }
+    if s.last() == Some(&b'\r') {
+        s = &s[..s.len() - 1];
+    }
+    s
+}
+
+fn decode_utf8_latin1(bs: Vec<u8>) -> String {
+    match String::from_utf8(bs) {
+        Ok(s) => s,
+        Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_splits_off_leading_tags() {
+        let mut codec = RawMessageCodec::new();
+        let mut buf = BytesMut::from(&b"@id=123;+example=foo :nick!u@h PRIVMSG #chan :hi\r\n"[..]);
+        let msg = codec.decode(&mut buf).unwrap().unwrap();
+        let tags = msg.tags.as_ref().unwrap();
+        assert_eq!(tags.get("id").unwrap().unwrap().as_str(), "123");
+        assert_eq!(tags.get("+example").unwrap().unwrap().as_str(), "foo");
+    }
+
+    #[test]
+    fn decode_without_tags_leaves_tags_none() {
+        let mut codec = RawMessageCodec::new();
+        let mut buf = BytesMut::from(&b"PING :server\r\n"[..]);
+        let msg = codec.decode(&mut buf).unwrap().unwrap();
+        assert!(msg.tags.is_none());
+    }
+
+    #[test]
+    fn encode_reproduces_tag_prefix() {
+        let mut codec = RawMessageCodec::new();
+        let mut buf = BytesMut::from(&b"@id=123 PING :server\r\n"[..]);
+        let msg = codec.decode(&mut buf).unwrap().unwrap();
+        let mut out = BytesMut::new();
+        codec.encode(msg, &mut out).unwrap();
+        assert_eq!(&out[..], &b"@id=123 PING :server\r\n"[..]);
+    }
+
+    #[test]
+    fn decode_rejects_oversized_tag_section() {
+        let mut codec = RawMessageCodec::new_with_tag_budget(512, 10);
+        let mut buf = BytesMut::from(&b"@id=abcdefghijklmnop PING\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(RawMessageCodecError::TagSectionTooLong)
+        ));
+    }
+
+    #[test]
+    fn decode_rejects_oversized_message_with_tags() {
+        let mut codec = RawMessageCodec::new_with_tag_budget(16, 512);
+        let mut buf = BytesMut::from(&b"@id=1 PRIVMSG #chan :a rather long message\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(RawMessageCodecError::MaxLineLengthExceeded)
+        ));
+    }
+
+    #[test]
+    fn decode_buffers_partial_line_across_calls() {
+        let mut codec = RawMessageCodec::new();
+        let mut buf = BytesMut::from(&b"PING :serv"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.put(&b"er\r\n"[..]);
+        let msg = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(msg.to_string(), "PING :server");
     }
 }
@@ -0,0 +1,550 @@
+//! Stateful accumulators that fold a burst of related numeric replies into
+//! one composed record, so a caller can work with e.g. a whole `Whois`
+//! instead of re-implementing the numeric bookkeeping itself.
+//!
+//! Each collector exposes an incremental `push()` that feeds it one
+//! [`Reply`] at a time (returning whether the reply belonged to this burst),
+//! an `is_done()` for when the conventional terminating numeric has been
+//! seen, and a `finish()` that consumes the collector into its record
+//! regardless of whether `is_done()` returned `true` — fragments that never
+//! arrived are simply absent from the result, so a flushed-early or
+//! interrupted burst still yields a best-effort record instead of nothing.
+
+use irctext::replies::{
+    Away, GlobalUsers, LocalUsers, LuserChannels, LuserClient, LuserMe, LuserOp, LuserUnknown,
+    Reply, WhoIsAccount, WhoIsActually, WhoIsChannels, WhoIsIdle, WhoIsServer, WhoIsUser,
+};
+use irctext::types::{Channel, Nickname};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Accumulates the numeric replies that make up one `WHOIS` response for a
+/// single nickname, keyed by that nickname so a collector can be fed a
+/// shared incoming stream and ignore replies about other targets.
+#[derive(Clone, Debug)]
+pub struct WhoisCollector {
+    nickname: Nickname,
+    user: Option<WhoIsUser>,
+    server: Option<WhoIsServer>,
+    operator: bool,
+    idle: Option<WhoIsIdle>,
+    channels: Option<WhoIsChannels>,
+    account: Option<WhoIsAccount>,
+    actually: Option<WhoIsActually>,
+    certfp: bool,
+    secure: bool,
+    away_message: Option<String>,
+    registered_nick: bool,
+    special: Vec<String>,
+    ended: bool,
+}
+
+impl WhoisCollector {
+    pub fn new(nickname: Nickname) -> WhoisCollector {
+        WhoisCollector {
+            nickname,
+            user: None,
+            server: None,
+            operator: false,
+            idle: None,
+            channels: None,
+            account: None,
+            actually: None,
+            certfp: false,
+            secure: false,
+            away_message: None,
+            registered_nick: false,
+            special: Vec::new(),
+            ended: false,
+        }
+    }
+
+    pub fn nickname(&self) -> &Nickname {
+        &self.nickname
+    }
+
+    /// Feeds `reply` to the collector. Returns `true` if `reply` was about
+    /// this collector's nickname and was folded in, `false` if it was
+    /// irrelevant (a different target, or a reply type this collector
+    /// doesn't track) and should be handled elsewhere.
+    pub fn push(&mut self, reply: &Reply) -> bool {
+        match reply {
+            Reply::WhoIsUser(r) if self.matches(r.nickname()) => {
+                self.user = Some(r.clone());
+                true
+            }
+            Reply::WhoIsServer(r) if self.matches(r.nickname()) => {
+                self.server = Some(r.clone());
+                true
+            }
+            Reply::WhoIsOperator(r) if self.matches(r.nickname()) => {
+                self.operator = true;
+                true
+            }
+            Reply::WhoIsIdle(r) if self.matches(r.nickname()) => {
+                self.idle = Some(r.clone());
+                true
+            }
+            Reply::WhoIsChannels(r) if self.matches(r.nickname()) => {
+                self.channels = Some(r.clone());
+                true
+            }
+            Reply::WhoIsAccount(r) if self.matches(r.nickname()) => {
+                self.account = Some(r.clone());
+                true
+            }
+            Reply::WhoIsActually(r) if self.matches(r.nickname()) => {
+                self.actually = Some(r.clone());
+                true
+            }
+            Reply::WhoIsCertFP(r) if self.matches(r.nickname()) => {
+                self.certfp = true;
+                true
+            }
+            Reply::WhoIsSecure(r) if self.matches(r.nickname()) => {
+                self.secure = true;
+                true
+            }
+            Reply::Away(r) if self.matches(r.nickname()) => {
+                self.away_message = Some(r.message().to_owned());
+                true
+            }
+            Reply::WhoIsRegNick(r) if self.matches(r.nickname()) => {
+                self.registered_nick = true;
+                true
+            }
+            Reply::WhoIsSpecial(r) if self.matches(r.nickname()) => {
+                self.special.push(r.message().to_owned());
+                true
+            }
+            Reply::EndOfWhoIs(r) if self.matches(r.nickname()) => {
+                self.ended = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn matches(&self, nickname: &Nickname) -> bool {
+        self.nickname
+            .as_str()
+            .eq_ignore_ascii_case(nickname.as_str())
+    }
+
+    /// Returns `true` once `RPL_ENDOFWHOIS` has been seen for this
+    /// nickname.
+    pub fn is_done(&self) -> bool {
+        self.ended
+    }
+
+    /// Consumes the collector into a [`Whois`] record, regardless of
+    /// whether [`WhoisCollector::is_done`] has returned `true`; fragments
+    /// that never arrived are simply `None` in the result.
+    pub fn finish(self) -> Whois {
+        Whois {
+            nickname: self.nickname,
+            user: self.user,
+            server: self.server,
+            operator: self.operator,
+            idle: self.idle,
+            channels: self.channels,
+            account: self.account,
+            actually: self.actually,
+            certfp: self.certfp,
+            secure: self.secure,
+            away_message: self.away_message,
+            registered_nick: self.registered_nick,
+            special: self.special,
+        }
+    }
+}
+
+/// A composed `WHOIS` response, assembled by [`WhoisCollector`] from
+/// whichever of the `RPL_WHOIS*`/`RPL_AWAY` fragments a server actually
+/// sent.
+#[derive(Clone, Debug)]
+pub struct Whois {
+    pub nickname: Nickname,
+    pub user: Option<WhoIsUser>,
+    pub server: Option<WhoIsServer>,
+    pub operator: bool,
+    pub idle: Option<WhoIsIdle>,
+    pub channels: Option<WhoIsChannels>,
+    pub account: Option<WhoIsAccount>,
+    pub actually: Option<WhoIsActually>,
+    pub certfp: bool,
+    pub secure: bool,
+    pub away_message: Option<String>,
+    pub registered_nick: bool,
+    pub special: Vec<String>,
+}
+
+/// Multiplexes [`WhoisCollector`]s across however many `WHOIS` bursts a
+/// connection has in flight at once, keyed by nickname, so a caller can feed
+/// it every incoming [`Reply`] without first sorting replies by which
+/// outstanding `WHOIS` they belong to.
+///
+/// A `RPL_ENDOFWHOIS` for a nickname with no open burst (a reply for a
+/// `WHOIS` this accumulator never saw the start of, or a duplicate `ENDOF`
+/// line) is simply ignored, the same way [`WhoisCollector::push`] ignores a
+/// reply for a nickname it isn't tracking.
+#[derive(Clone, Debug, Default)]
+pub struct WhoisAccumulator {
+    bursts: HashMap<String, WhoisCollector>,
+}
+
+impl WhoisAccumulator {
+    pub fn new() -> WhoisAccumulator {
+        WhoisAccumulator::default()
+    }
+
+    /// Feeds `reply` to whichever in-flight burst matches its nickname,
+    /// starting a new burst on the first reply seen for a nickname. Returns
+    /// the completed [`Whois`] once that burst's `RPL_ENDOFWHOIS` arrives.
+    pub fn push(&mut self, reply: &Reply) -> Option<Whois> {
+        let nickname = whois_nickname(reply)?;
+        let key = nickname.as_str().to_ascii_lowercase();
+        if matches!(reply, Reply::EndOfWhoIs(_)) && !self.bursts.contains_key(&key) {
+            return None;
+        }
+        let collector = self
+            .bursts
+            .entry(key.clone())
+            .or_insert_with(|| WhoisCollector::new(nickname.clone()));
+        collector.push(reply);
+        if collector.is_done() {
+            let collector = self.bursts.remove(&key).expect("just inserted above");
+            Some(collector.finish())
+        } else {
+            None
+        }
+    }
+
+    /// Consumes the accumulator, flushing every burst still in flight (one
+    /// that never saw its `RPL_ENDOFWHOIS`) into a best-effort [`Whois`] via
+    /// [`WhoisCollector::finish`]. Useful when a connection drops or a
+    /// caller is shutting down mid-burst and partial results are still
+    /// worth keeping.
+    pub fn finish_all(self) -> Vec<Whois> {
+        self.bursts
+            .into_values()
+            .map(WhoisCollector::finish)
+            .collect()
+    }
+}
+
+/// The nickname a `WHOIS`-burst reply is about, or `None` if `reply` isn't
+/// one of the numerics [`WhoisCollector`] tracks.
+fn whois_nickname(reply: &Reply) -> Option<&Nickname> {
+    match reply {
+        Reply::WhoIsUser(r) => Some(r.nickname()),
+        Reply::WhoIsServer(r) => Some(r.nickname()),
+        Reply::WhoIsOperator(r) => Some(r.nickname()),
+        Reply::WhoIsIdle(r) => Some(r.nickname()),
+        Reply::WhoIsChannels(r) => Some(r.nickname()),
+        Reply::WhoIsAccount(r) => Some(r.nickname()),
+        Reply::WhoIsActually(r) => Some(r.nickname()),
+        Reply::WhoIsCertFP(r) => Some(r.nickname()),
+        Reply::WhoIsSecure(r) => Some(r.nickname()),
+        Reply::Away(r) => Some(r.nickname()),
+        Reply::WhoIsRegNick(r) => Some(r.nickname()),
+        Reply::WhoIsSpecial(r) => Some(r.nickname()),
+        Reply::EndOfWhoIs(r) => Some(r.nickname()),
+        _ => None,
+    }
+}
+
+/// Accumulates the numeric replies that make up one `LUSERS` burst.
+/// There's no dedicated terminating numeric for `LUSERS`, so
+/// [`LusersCollector::is_done`] treats the conventional final line,
+/// `RPL_LUSERME`, as the signal that the burst is complete.
+#[derive(Clone, Debug, Default)]
+pub struct LusersCollector {
+    client: Option<LuserClient>,
+    op: Option<LuserOp>,
+    unknown: Option<LuserUnknown>,
+    channels: Option<LuserChannels>,
+    me: Option<LuserMe>,
+    local_users: Option<LocalUsers>,
+    global_users: Option<GlobalUsers>,
+}
+
+impl LusersCollector {
+    pub fn new() -> LusersCollector {
+        LusersCollector::default()
+    }
+
+    /// Feeds `reply` to the collector. Returns `true` if `reply` was one of
+    /// the `LUSERS` numerics and was folded in, `false` otherwise.
+    pub fn push(&mut self, reply: &Reply) -> bool {
+        match reply {
+            Reply::LuserClient(r) => {
+                self.client = Some(r.clone());
+                true
+            }
+            Reply::LuserOp(r) => {
+                self.op = Some(r.clone());
+                true
+            }
+            Reply::LuserUnknown(r) => {
+                self.unknown = Some(r.clone());
+                true
+            }
+            Reply::LuserChannels(r) => {
+                self.channels = Some(r.clone());
+                true
+            }
+            Reply::LuserMe(r) => {
+                self.me = Some(r.clone());
+                true
+            }
+            Reply::LocalUsers(r) => {
+                self.local_users = Some(r.clone());
+                true
+            }
+            Reply::GlobalUsers(r) => {
+                self.global_users = Some(r.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` once `RPL_LUSERME` has been seen.
+    pub fn is_done(&self) -> bool {
+        self.me.is_some()
+    }
+
+    /// Consumes the collector into a [`Lusers`] record, regardless of
+    /// whether [`LusersCollector::is_done`] has returned `true`.
+    pub fn finish(self) -> Lusers {
+        Lusers {
+            client: self.client,
+            op: self.op,
+            unknown: self.unknown,
+            channels: self.channels,
+            me: self.me,
+            local_users: self.local_users,
+            global_users: self.global_users,
+        }
+    }
+}
+
+/// A composed `LUSERS` burst, assembled by [`LusersCollector`] from
+/// whichever of the `RPL_LUSER*` numerics a server actually sent.
+#[derive(Clone, Debug, Default)]
+pub struct Lusers {
+    pub client: Option<LuserClient>,
+    pub op: Option<LuserOp>,
+    pub unknown: Option<LuserUnknown>,
+    pub channels: Option<LuserChannels>,
+    pub me: Option<LuserMe>,
+    pub local_users: Option<LocalUsers>,
+    pub global_users: Option<GlobalUsers>,
+}
+
+/// One row of a `/LIST` response, as collected by [`ChannelListing`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChannelListEntry {
+    pub channel: Channel,
+    pub clients: u64,
+    pub topic: String,
+}
+
+/// Accumulates the `RPL_LISTSTART`/`RPL_LIST`/`RPL_LISTEND` stream a `LIST`
+/// command produces into one ordered set of rows, instead of every caller
+/// reimplementing the start/many-rows/end state machine itself.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelListing {
+    started: bool,
+    ended: bool,
+    entries: Vec<ChannelListEntry>,
+}
+
+impl ChannelListing {
+    pub fn new() -> ChannelListing {
+        ChannelListing::default()
+    }
+
+    /// Feeds `reply` to the collector. Returns `true` if `reply` was part of
+    /// the `LIST` family and was folded in, `false` otherwise.
+    ///
+    /// Returns a [`ChannelListingError::RowBeforeStart`] if a `RPL_LIST` row
+    /// arrives before `RPL_LISTSTART`, and ignores anything fed in after
+    /// `RPL_LISTEND` (including a duplicate `RPL_LISTEND`).
+    pub fn push(&mut self, reply: &Reply) -> Result<bool, ChannelListingError> {
+        match reply {
+            Reply::ListStart(_) if !self.ended => {
+                self.started = true;
+                Ok(true)
+            }
+            Reply::List(r) if !self.ended => {
+                if !self.started {
+                    return Err(ChannelListingError::RowBeforeStart);
+                }
+                self.entries.push(ChannelListEntry {
+                    channel: r.channel().clone(),
+                    clients: r.clients(),
+                    topic: r.topic().to_owned(),
+                });
+                Ok(true)
+            }
+            Reply::ListEnd(_) if !self.ended => {
+                self.ended = true;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns `true` once `RPL_LISTEND` has been seen.
+    pub fn is_done(&self) -> bool {
+        self.ended
+    }
+
+    /// Consumes the collector into its rows, in arrival order. Returns
+    /// [`ChannelListingError::Unterminated`] if `RPL_LISTEND` was never
+    /// seen, since an interrupted listing can't be told apart from one that
+    /// simply has no rows yet.
+    pub fn finish(self) -> Result<Vec<ChannelListEntry>, ChannelListingError> {
+        if !self.ended {
+            return Err(ChannelListingError::Unterminated);
+        }
+        Ok(self.entries)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ChannelListingError {
+    #[error("RPL_LIST row arrived before RPL_LISTSTART")]
+    RowBeforeStart,
+
+    #[error("RPL_LISTEND was never seen")]
+    Unterminated,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use irctext::replies::EndOfWhoIs;
+    use irctext::types::ReplyTarget;
+    use irctext::FinalParam;
+
+    fn target() -> ReplyTarget {
+        "me".parse().unwrap()
+    }
+
+    fn nick() -> Nickname {
+        "Alice".parse().unwrap()
+    }
+
+    fn msg(s: &str) -> FinalParam {
+        FinalParam::try_from(s.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn whois_collector_ignores_other_nicknames() {
+        let mut collector = WhoisCollector::new(nick());
+        let other: Nickname = "Bob".parse().unwrap();
+        let reply = Reply::from(WhoIsOperatorFor(other));
+        assert!(!collector.push(&reply));
+    }
+
+    // Helper constructing a WhoIsOperator for an arbitrary nickname, kept
+    // local to this test module since no other test needs it.
+    #[allow(non_snake_case)]
+    fn WhoIsOperatorFor(nickname: Nickname) -> irctext::replies::WhoIsOperator {
+        irctext::replies::WhoIsOperator::new(target(), nickname, msg("is an IRC operator"))
+    }
+
+    #[test]
+    fn whois_collector_folds_fragments_and_finishes_on_end() {
+        let mut collector = WhoisCollector::new(nick());
+        assert!(!collector.is_done());
+        let user = WhoIsUser::new(
+            target(),
+            nick(),
+            "alice".parse().unwrap(),
+            "host.example".parse().unwrap(),
+            msg("Alice Example"),
+        );
+        assert!(collector.push(&Reply::from(user)));
+        assert!(collector.push(&Reply::from(WhoIsOperatorFor(nick()))));
+        assert!(!collector.is_done());
+        let end = EndOfWhoIs::new(target(), nick(), msg("End of /WHOIS list."));
+        assert!(collector.push(&Reply::from(end)));
+        assert!(collector.is_done());
+        let whois = collector.finish();
+        assert!(whois.user.is_some());
+        assert!(whois.operator);
+    }
+
+    #[test]
+    fn lusers_collector_done_after_luserme() {
+        let mut collector = LusersCollector::new();
+        assert!(!collector.is_done());
+        collector.push(&Reply::from(LuserClient::new(
+            target(),
+            msg("There are 1 users and 0 invisible on 1 servers"),
+        )));
+        assert!(!collector.is_done());
+        collector.push(&Reply::from(LuserMe::new(
+            target(),
+            msg("I have 1 clients and 1 servers"),
+        )));
+        assert!(collector.is_done());
+        let lusers = collector.finish();
+        assert!(lusers.client.is_some());
+        assert!(lusers.me.is_some());
+    }
+
+    #[test]
+    fn channel_listing_rejects_row_before_start() {
+        use irctext::replies::List;
+        use irctext::types::Channel;
+
+        let mut listing = ChannelListing::new();
+        let chan: Channel = "#rust".parse().unwrap();
+        let row = Reply::from(List::new(target(), chan, 5, msg("talk about rust")));
+        assert_eq!(
+            listing.push(&row),
+            Err(ChannelListingError::RowBeforeStart)
+        );
+    }
+
+    #[test]
+    fn channel_listing_collects_rows_between_start_and_end() {
+        use irctext::replies::{List, ListEnd, ListStart};
+        use irctext::types::Channel;
+        use irctext::MedialParam;
+
+        let mut listing = ChannelListing::new();
+        let client = MedialParam::try_from("me".to_owned()).unwrap();
+        assert!(listing
+            .push(&Reply::from(ListStart::new(client.clone())))
+            .unwrap());
+
+        let chan: Channel = "#rust".parse().unwrap();
+        listing
+            .push(&Reply::from(List::new(
+                target(),
+                chan,
+                5,
+                msg("talk about rust"),
+            )))
+            .unwrap();
+        assert!(!listing.is_done());
+
+        listing
+            .push(&Reply::from(ListEnd::new(
+                client,
+                msg("End of /LIST"),
+            )))
+            .unwrap();
+        assert!(listing.is_done());
+
+        let entries = listing.finish().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].clients, 5);
+    }
+}
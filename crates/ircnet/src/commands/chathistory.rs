@@ -0,0 +1,113 @@
+use super::Command;
+use irctext::{
+    ClientMessage, MedialParam, Message, Payload,
+    clientmsgs::{Batch, ChatHistory as ChatHistoryMsg},
+};
+use std::time::Duration;
+
+/// Sends a `CHATHISTORY` request and collects the `PRIVMSG`/`NOTICE`/`TAGMSG`
+/// lines the server sends back wrapped in a `chathistory`-type `BATCH`,
+/// per <https://ircv3.net/specs/extensions/chathistory>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChatHistory {
+    outgoing: Vec<ClientMessage>,
+    state: State,
+}
+
+impl ChatHistory {
+    pub fn new(request: ChatHistoryMsg) -> ChatHistory {
+        ChatHistory {
+            outgoing: vec![ClientMessage::from(request)],
+            state: State::AwaitingBatchStart,
+        }
+    }
+}
+
+impl Command for ChatHistory {
+    type Output = Vec<Message>;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        let state = std::mem::replace(&mut self.state, State::Void);
+        let (state, handled) = state.handle(msg);
+        self.state = state;
+        handled
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        matches!(self.state, State::Done(_))
+    }
+
+    fn get_output(&mut self) -> Self::Output {
+        if let State::Done(ref mut r) = self.state {
+            r.take()
+                .expect("get_output() should not be called more than once")
+        } else {
+            panic!("get_output() should only be called when is_done() is true");
+        }
+    }
+}
+
+const CHATHISTORY_BATCH_TYPE: &str = "chathistory";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum State {
+    AwaitingBatchStart,
+    InBatch {
+        reference_tag: MedialParam,
+        messages: Vec<Message>,
+    },
+    Done(Option<Vec<Message>>),
+    Void,
+}
+
+impl State {
+    fn handle(self, msg: &Message) -> (State, bool) {
+        match (self, &msg.payload) {
+            (
+                State::AwaitingBatchStart,
+                Payload::ClientMessage(ClientMessage::Batch(Batch::Start(start))),
+            ) if start.batch_type().as_ref() == CHATHISTORY_BATCH_TYPE => (
+                State::InBatch {
+                    reference_tag: start.reference_tag().clone(),
+                    messages: Vec::new(),
+                },
+                true,
+            ),
+            (
+                State::InBatch {
+                    reference_tag,
+                    messages,
+                },
+                Payload::ClientMessage(ClientMessage::Batch(Batch::End(end))),
+            ) if *end.reference_tag() == reference_tag => (State::Done(Some(messages)), true),
+            (
+                State::InBatch {
+                    reference_tag,
+                    mut messages,
+                },
+                _,
+            ) => {
+                messages.push(msg.clone());
+                (
+                    State::InBatch {
+                        reference_tag,
+                        messages,
+                    },
+                    true,
+                )
+            }
+            (st @ (State::Done(_) | State::Void), _) => (st, false),
+            (State::AwaitingBatchStart, _) => (State::AwaitingBatchStart, false),
+        }
+    }
+}
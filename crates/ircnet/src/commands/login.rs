@@ -1,12 +1,20 @@
 use super::Command;
+use crate::sasl::{SaslError, SaslFlow, SaslMachine, SaslMechanism};
 use irctext::{
-    ClientMessage, ClientMessageParts, FinalParam, Message, Payload, Reply, ReplyParts,
-    clientmsgs::{Mode, Nick, Pass, User},
+    ClientMessage, ClientMessageParts, FinalParam, Message, Payload, Reply, ReplyParts, Verb,
+    clientmsgs::{
+        Authenticate, Cap, CapEnd, CapLsRequest, CapReq, Capability, CapabilityRequest,
+        CapabilityValue, Mode, Nick, Pass, User,
+    },
     types::{ISupportParam, ModeString, Nickname, ReplyTarget, Username},
 };
 use std::time::Duration;
 use thiserror::Error;
 
+/// The version of `CAP LS` sent when [`LoginParams::sasl`] or
+/// [`LoginParams::capabilities`] is set
+const CAP_VERSION: u32 = 302;
+
 /// How long to wait for an optional `MODE` or `RPL_UMODEIS` (221) message
 /// after receiving the MOTD
 const MODE_TIMEOUT: Duration = Duration::from_secs(1);
@@ -17,6 +25,30 @@ pub struct LoginParams {
     pub nickname: Nickname,
     pub username: Username,
     pub realname: FinalParam,
+
+    /// If set, negotiate the `sasl` capability via `CAP` and authenticate as
+    /// `nickname` using the given mechanism before completing registration.
+    /// If the server doesn't support `CAP` or doesn't advertise `sasl`,
+    /// login falls back to plain `PASS`/`NICK`/`USER` registration.
+    pub sasl: Option<SaslCredentials>,
+
+    /// Additional capabilities (beyond `sasl`, which is requested
+    /// automatically whenever [`LoginParams::sasl`] is set) to request via
+    /// `CAP REQ` if the server advertises them.  Setting this triggers `CAP`
+    /// negotiation even when `sasl` is unset.  Capabilities that the server
+    /// doesn't advertise are silently skipped rather than treated as an
+    /// error, and the negotiation as a whole degrades to plain
+    /// `PASS`/`NICK`/`USER` registration if the server doesn't support `CAP`
+    /// at all (`ERR_UNKNOWNCOMMAND` (421)).
+    pub capabilities: Vec<Capability>,
+}
+
+/// Credentials for authenticating via SASL, used alongside [`LoginParams::nickname`]
+/// as the authentication (and authorization) identity
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaslCredentials {
+    pub mechanism: SaslMechanism,
+    pub password: String,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -27,20 +59,41 @@ pub struct Login {
 
 impl Login {
     pub fn new(params: LoginParams) -> Login {
+        let nickname = params.nickname.clone();
         let pass = ClientMessage::from(Pass::new(params.password));
         let nick = ClientMessage::from(Nick::new(params.nickname));
         let user = ClientMessage::from(User::new(params.username, params.realname));
-        Login {
-            outgoing: vec![pass, nick, user],
-            state: State::Start,
+        if params.sasl.is_some() || !params.capabilities.is_empty() {
+            let cap_ls = ClientMessage::from(CapLsRequest::new_with_version(CAP_VERSION));
+            Login {
+                outgoing: vec![cap_ls, pass, nick, user],
+                state: State::AwaitingCapLs {
+                    sasl: params.sasl,
+                    nickname,
+                    requested: params.capabilities,
+                    offered: Vec::new(),
+                },
+            }
+        } else {
+            Login {
+                outgoing: vec![pass, nick, user],
+                state: State::Start {
+                    capabilities: Vec::new(),
+                    account: None,
+                },
+            }
         }
     }
 }
 
 // Order of replies on successful login:
 // - With SASL:
+//     - CAP * LS (one or more, the last with no trailing "*" parameter)
+//     - CAP * ACK :sasl
+//     - AUTHENTICATE + (mechanism continuation prompt)
 //     - RPL_LOGGEDIN (900)
 //     - RPL_SASLSUCCESS (903)
+//     - CAP END (sent by us)
 // - RPL_WELCOME (001)
 // - RPL_YOURHOST (002)
 // - RPL_CREATED (003)
@@ -59,9 +112,9 @@ impl Login {
 // Possible error replies on login:
 //  - ERR_INPUTTOOLONG (417)
 //  - ERR_UNKNOWNCOMMAND (421)
-//      - When using SASL, this may be sent in reply to CAP if the server
-//        doesn't support the command, in which case we should gracefully fall
-//        back to plain login.
+//      - When negotiating capabilities, this may be sent in reply to CAP if
+//        the server doesn't support the command, in which case we should
+//        gracefully fall back to plain login.
 //  - ERR_ERRONEUSNICKNAME (432)
 //  - ERR_NICKNAMEINUSE (433)
 //  - ERR_NICKCOLLISION (436) ?
@@ -84,6 +137,18 @@ impl Command for Login {
     fn handle_message(&mut self, msg: &Message) -> bool {
         match &msg.payload {
             Payload::Reply(rpl) => {
+                if matches!(self.state, State::AwaitingCapLs { .. })
+                    && matches!(rpl, Reply::UnknownCommand(r) if *r.command() == Verb::Cap)
+                {
+                    // The server doesn't support CAP at all, so gracefully
+                    // fall back to plain login; PASS/NICK/USER were already
+                    // queued alongside CAP LS.
+                    self.state = State::Start {
+                        capabilities: Vec::new(),
+                        account: None,
+                    };
+                    return true;
+                }
                 if rpl.is_error() && !matches!(rpl, Reply::NoMotd(_)) {
                     let e = match rpl {
                         Reply::InputTooLong(r) => LoginError::InputTooLong {
@@ -111,19 +176,61 @@ impl Command for Login {
                         Reply::YoureBannedCreep(r) => LoginError::Banned {
                             message: r.message().to_string(),
                         },
+                        Reply::NickLocked(r) => LoginError::NickLocked {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslFail(r) => LoginError::SaslFail {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslTooLong(r) => LoginError::SaslTooLong {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslAborted(r) => LoginError::SaslAborted {
+                            message: r.message().to_string(),
+                        },
+                        Reply::SaslAlready(r) => LoginError::SaslAlready {
+                            message: r.message().to_string(),
+                        },
                         unexpected => LoginError::UnexpectedError {
                             code: unexpected.code(),
                             reply: msg.to_string(),
                         },
                     };
+                    if matches!(
+                        self.state,
+                        State::AwaitingCapLs { .. }
+                            | State::AwaitingCapAck { .. }
+                            | State::AwaitingSasl { .. }
+                    ) {
+                        // The server holds registration open until it sees
+                        // CAP END, so send it even though we're giving up,
+                        // to avoid leaving the connection stuck mid-negotiation.
+                        self.outgoing.push(ClientMessage::from(CapEnd));
+                    }
                     self.state = State::Done(Some(Err(e)));
                     true
                 } else {
-                    self.state.in_place(|state| state.handle_reply(rpl))
+                    self.state
+                        .in_place(&mut self.outgoing, |state, outgoing| {
+                            state.handle_reply(rpl, outgoing)
+                        })
                 }
             }
             Payload::ClientMessage(climsg) => match climsg {
-                ClientMessage::Mode(mode) => self.state.in_place(|state| state.handle_mode(mode)),
+                ClientMessage::Cap(cap) => self
+                    .state
+                    .in_place(&mut self.outgoing, |state, outgoing| {
+                        state.handle_cap(cap, outgoing)
+                    }),
+                ClientMessage::Authenticate(auth) => {
+                    self.state
+                        .in_place(&mut self.outgoing, |state, outgoing| {
+                            state.handle_authenticate(auth, outgoing)
+                        })
+                }
+                ClientMessage::Mode(mode) => self.state.in_place(&mut self.outgoing, |state, _| {
+                    state.handle_mode(mode)
+                }),
                 ClientMessage::Ping(_) | ClientMessage::PrivMsg(_) | ClientMessage::Notice(_) => {
                     false
                 }
@@ -170,15 +277,46 @@ impl Command for Login {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum State {
-    Start,
+    AwaitingCapLs {
+        sasl: Option<SaslCredentials>,
+        nickname: Nickname,
+        /// Capabilities requested via [`LoginParams::capabilities`]; `sasl`
+        /// is requested in addition to these if offered
+        requested: Vec<Capability>,
+        /// Capabilities (with values, if any) accumulated across all `CAP
+        /// LS` response lines so far
+        offered: Vec<(Capability, Option<CapabilityValue>)>,
+    },
+    AwaitingCapAck {
+        sasl: Option<SaslCredentials>,
+        nickname: Nickname,
+        /// The capabilities (with their advertised values) requested via
+        /// `CAP REQ`, to be recorded as enabled once acknowledged
+        wanted: Vec<(Capability, Option<CapabilityValue>)>,
+    },
+    AwaitingSasl {
+        machine: SaslMachine,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
+    },
+    Start {
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
+    },
     Got001 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got002 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got003 {
         my_nick: Nickname,
+        capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+        account: Option<String>,
     },
     Got004(LoginOutput),
     Got005(LoginOutput),
@@ -193,12 +331,12 @@ enum State {
 }
 
 impl State {
-    fn in_place<F>(&mut self, f: F) -> bool
+    fn in_place<F>(&mut self, outgoing: &mut Vec<ClientMessage>, f: F) -> bool
     where
-        F: FnOnce(Self) -> Result<(State, bool), LoginError>,
+        F: FnOnce(Self, &mut Vec<ClientMessage>) -> Result<(State, bool), LoginError>,
     {
         let state = std::mem::replace(self, State::Void);
-        match f(state) {
+        match f(state, outgoing) {
             Ok((st, b)) => {
                 *self = st;
                 b
@@ -210,21 +348,254 @@ impl State {
         }
     }
 
-    fn handle_reply(self, rpl: &Reply) -> Result<(State, bool), LoginError> {
+    fn handle_cap(
+        self,
+        cap: &Cap,
+        outgoing: &mut Vec<ClientMessage>,
+    ) -> Result<(State, bool), LoginError> {
+        match (self, cap) {
+            (
+                State::AwaitingCapLs {
+                    sasl,
+                    nickname,
+                    requested,
+                    mut offered,
+                },
+                Cap::LsResponse(r),
+            ) => {
+                offered.extend(r.capabilities.iter().cloned());
+                if r.continued {
+                    Ok((
+                        State::AwaitingCapLs {
+                            sasl,
+                            nickname,
+                            requested,
+                            offered,
+                        },
+                        true,
+                    ))
+                } else {
+                    let wants_sasl = sasl.is_some();
+                    let wanted: Vec<(Capability, Option<CapabilityValue>)> = offered
+                        .into_iter()
+                        .filter(|(c, _)| {
+                            (wants_sasl && c.as_str() == "sasl") || requested.contains(c)
+                        })
+                        .collect();
+                    if wanted.is_empty() {
+                        // Server doesn't advertise anything we want; fall
+                        // back to plain login.
+                        outgoing.push(ClientMessage::from(CapEnd));
+                        Ok((
+                            State::Start {
+                                capabilities: Vec::new(),
+                                account: None,
+                            },
+                            true,
+                        ))
+                    } else {
+                        let req = CapReq {
+                            capabilities: wanted
+                                .iter()
+                                .map(|(c, _)| CapabilityRequest::enable(c.clone()))
+                                .collect(),
+                        };
+                        outgoing.push(ClientMessage::from(req));
+                        Ok((
+                            State::AwaitingCapAck {
+                                sasl,
+                                nickname,
+                                wanted,
+                            },
+                            true,
+                        ))
+                    }
+                }
+            }
+            (
+                State::AwaitingCapAck {
+                    sasl: Some(sasl),
+                    nickname,
+                    wanted,
+                },
+                Cap::Ack(_),
+            ) if wanted.iter().any(|(c, _)| c.as_str() == "sasl") => {
+                let (machine, msgs) = sasl
+                    .mechanism
+                    // This baseline login state machine has no connection
+                    // to the TLS layer, so `-PLUS` SCRAM mechanisms (which
+                    // require channel-binding data) aren't usable here; see
+                    // `client::commands::login` for the mature
+                    // implementation that supports them.
+                    .new_flow(None, &nickname, &sasl.password, None)
+                    .map_err(LoginError::from_sasl_error)?;
+                outgoing.extend(msgs.into_iter().map(ClientMessage::from));
+                Ok((
+                    State::AwaitingSasl {
+                        machine,
+                        capabilities: wanted,
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (State::AwaitingCapAck { wanted, .. }, Cap::Ack(_)) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities: wanted,
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (State::AwaitingCapAck { .. }, Cap::Nak(_)) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities: Vec::new(),
+                        account: None,
+                    },
+                    true,
+                ))
+            }
+            (st, other) => {
+                let expecting = st.expecting();
+                let msg = other.to_irc_line();
+                Err(LoginError::Unexpected { expecting, msg })
+            }
+        }
+    }
+
+    fn handle_authenticate(
+        self,
+        auth: &Authenticate,
+        outgoing: &mut Vec<ClientMessage>,
+    ) -> Result<(State, bool), LoginError> {
+        match self {
+            State::AwaitingSasl {
+                mut machine,
+                capabilities,
+                account,
+            } => {
+                machine
+                    .handle_message(auth.clone())
+                    .map_err(LoginError::from_sasl_error)?;
+                outgoing.extend(machine.get_output().into_iter().map(ClientMessage::from));
+                Ok((
+                    State::AwaitingSasl {
+                        machine,
+                        capabilities,
+                        account,
+                    },
+                    true,
+                ))
+            }
+            st => {
+                let expecting = st.expecting();
+                let msg = auth.to_irc_line();
+                Err(LoginError::Unexpected { expecting, msg })
+            }
+        }
+    }
+
+    fn handle_reply(
+        self,
+        rpl: &Reply,
+        outgoing: &mut Vec<ClientMessage>,
+    ) -> Result<(State, bool), LoginError> {
         match (self, rpl) {
-            (State::Start, Reply::Welcome(r)) => {
+            (
+                State::AwaitingSasl {
+                    machine,
+                    capabilities,
+                    account: None,
+                },
+                Reply::LoggedIn(r),
+            ) => Ok((
+                State::AwaitingSasl {
+                    machine,
+                    capabilities,
+                    account: Some(r.account().to_owned()),
+                },
+                true,
+            )),
+            (
+                State::AwaitingSasl {
+                    capabilities,
+                    account,
+                    ..
+                },
+                Reply::SaslSuccess(_),
+            ) => {
+                outgoing.push(ClientMessage::from(CapEnd));
+                Ok((
+                    State::Start {
+                        capabilities,
+                        account,
+                    },
+                    true,
+                ))
+            }
+            (
+                State::Start {
+                    capabilities,
+                    account,
+                },
+                Reply::Welcome(r),
+            ) => {
                 if let ReplyTarget::Nick(nick) = r.client() {
                     let my_nick = nick.clone();
-                    Ok((State::Got001 { my_nick }, true))
+                    Ok((
+                        State::Got001 {
+                            my_nick,
+                            capabilities,
+                            account,
+                        },
+                        true,
+                    ))
                 } else {
                     Err(LoginError::StarWelcome)
                 }
             }
-            (State::Got001 { my_nick }, Reply::YourHost(_)) => {
-                Ok((State::Got002 { my_nick }, true))
-            }
-            (State::Got002 { my_nick }, Reply::Created(_)) => Ok((State::Got003 { my_nick }, true)),
-            (State::Got003 { my_nick }, Reply::MyInfo(r)) => {
+            (
+                State::Got001 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::YourHost(_),
+            ) => Ok((
+                State::Got002 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                true,
+            )),
+            (
+                State::Got002 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::Created(_),
+            ) => Ok((
+                State::Got003 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                true,
+            )),
+            (
+                State::Got003 {
+                    my_nick,
+                    capabilities,
+                    account,
+                },
+                Reply::MyInfo(r),
+            ) => {
                 let server_info = ServerInfo {
                     server_name: r.servername().to_owned(),
                     version: r.version().to_owned(),
@@ -234,6 +605,8 @@ impl State {
                 };
                 let output = LoginOutput {
                     my_nick,
+                    capabilities,
+                    account,
                     server_info,
                     isupport: Vec::new(),
                     luser_stats: LuserStats::default(),
@@ -361,7 +734,12 @@ impl State {
 
     fn expecting(&self) -> &'static str {
         match self {
-            State::Start => "RPL_WELCOME (001) reply",
+            State::AwaitingCapLs { .. } => "CAP LS response or ERR_UNKNOWNCOMMAND (421)",
+            State::AwaitingCapAck { .. } => "CAP ACK or CAP NAK response",
+            State::AwaitingSasl { .. } => {
+                "AUTHENTICATE continuation, RPL_LOGGEDIN (900), or RPL_SASLSUCCESS (903) reply"
+            }
+            State::Start { .. } => "RPL_WELCOME (001) reply",
             State::Got001 { .. } => "RPL_YOURHOST (002) reply",
             State::Got002 { .. } => "RPL_CREATED (003) reply",
             State::Got003 { .. } => "RPL_MYINFO (004) reply",
@@ -378,33 +756,43 @@ impl State {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoginOutput {
-    // SASL: CAP LS
-    my_nick: Nickname,
-    server_info: ServerInfo,
-    isupport: Vec<ISupportParam>,
-    luser_stats: LuserStats,
-    motd: Option<String>, // None if the server reports no MOTD was set
-    mode: Option<ModeString>,
+    pub my_nick: Nickname,
+
+    /// The capabilities (with their advertised values, e.g. `sasl` mapping
+    /// to `PLAIN,EXTERNAL`) negotiated via `CAP REQ`/`CAP ACK`.  Empty unless
+    /// [`LoginParams::sasl`] or [`LoginParams::capabilities`] was set and
+    /// the server both supported `CAP` and acknowledged the request.
+    pub capabilities: Vec<(Capability, Option<CapabilityValue>)>,
+
+    /// The account name returned by `RPL_LOGGEDIN` (900) after a successful
+    /// SASL authentication, if any.
+    pub account: Option<String>,
+
+    pub server_info: ServerInfo,
+    pub isupport: Vec<ISupportParam>,
+    pub luser_stats: LuserStats,
+    pub motd: Option<String>, // None if the server reports no MOTD was set
+    pub mode: Option<ModeString>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ServerInfo {
-    server_name: String,
-    version: String,
-    user_modes: String,
-    channel_modes: String,
-    param_channel_modes: Option<String>,
+    pub server_name: String,
+    pub version: String,
+    pub user_modes: String,
+    pub channel_modes: String,
+    pub param_channel_modes: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct LuserStats {
-    operators: Option<u64>,
-    unknown_connections: Option<u64>,
-    channels: Option<u64>,
-    local_clients: Option<u64>,
-    max_local_clients: Option<u64>,
-    global_clients: Option<u64>,
-    max_global_clients: Option<u64>,
+    pub operators: Option<u64>,
+    pub unknown_connections: Option<u64>,
+    pub channels: Option<u64>,
+    pub local_clients: Option<u64>,
+    pub max_local_clients: Option<u64>,
+    pub global_clients: Option<u64>,
+    pub max_global_clients: Option<u64>,
 }
 
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -423,6 +811,16 @@ pub enum LoginError {
     Password { message: String },
     #[error("login failed because client is banned: {message:?}")]
     Banned { message: String },
+    #[error("login failed because the requested nickname is locked by services: {message:?}")]
+    NickLocked { message: String },
+    #[error("login failed because SASL authentication failed: {message:?}")]
+    SaslFail { message: String },
+    #[error("login failed because the SASL message was too long: {message:?}")]
+    SaslTooLong { message: String },
+    #[error("login failed because the SASL authentication was aborted: {message:?}")]
+    SaslAborted { message: String },
+    #[error("login failed because client has already authenticated via SASL: {message:?}")]
+    SaslAlready { message: String },
     #[error("login failed with unexpected error reply {code:03}: {reply:?}")]
     UnexpectedError { code: u16, reply: String },
     #[error("login failed because RPL_WELCOME was addressed to * instead of client nickname")]
@@ -436,4 +834,137 @@ pub enum LoginError {
     },
     #[error("login failed because server sent unparseable mode string in RPL_UMODEIS: {msg:?}")]
     InvalidMode { msg: String },
+    #[error(
+        "login failed because the server's SCRAM signature did not match the one we computed"
+    )]
+    SaslServerSignatureMismatch,
+    #[error("SASL authentication flow failed: {0}")]
+    SaslFlow(String),
+}
+
+impl LoginError {
+    fn from_sasl_error(e: SaslError) -> LoginError {
+        match e {
+            SaslError::Signature => LoginError::SaslServerSignatureMismatch,
+            e => LoginError::SaslFlow(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_login() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            sasl: None,
+            capabilities: Vec::new(),
+        };
+        let mut cmd = Login::new(params);
+        let outgoing = cmd.get_client_messages();
+        let outgoing = outgoing
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outgoing,
+            [
+                "PASS :hunter2",
+                "NICK jwodder",
+                "USER jwuser 0 * :Just this guy, you know?"
+            ]
+        );
+
+        let m = ":molybdenum.libera.chat 001 jwodder :Welcome to the Libera.Chat Internet Relay Chat Network jwodder";
+        let msg = m.parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(!cmd.is_done());
+    }
+
+    #[test]
+    fn sasl_plain_login() {
+        let params = LoginParams {
+            password: "hunter2".parse::<FinalParam>().unwrap(),
+            nickname: "jwodder".parse::<Nickname>().unwrap(),
+            username: "jwuser".parse::<Username>().unwrap(),
+            realname: "Just this guy, you know?".parse::<FinalParam>().unwrap(),
+            sasl: Some(SaslCredentials {
+                mechanism: SaslMechanism::Plain,
+                password: "hunter2".to_owned(),
+            }),
+            capabilities: vec!["server-time".parse::<Capability>().unwrap()],
+        };
+        let mut cmd = Login::new(params);
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outgoing,
+            [
+                "CAP LS 302",
+                "PASS :hunter2",
+                "NICK jwodder",
+                "USER jwuser 0 * :Just this guy, you know?"
+            ]
+        );
+
+        let msg = "CAP * LS :sasl server-time account-notify"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["CAP REQ :sasl server-time"]);
+        assert!(!cmd.is_done());
+
+        let msg = "CAP * ACK :sasl server-time"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["AUTHENTICATE :PLAIN"]);
+        assert!(!cmd.is_done());
+
+        let msg = "AUTHENTICATE +".parse::<Message>().unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["AUTHENTICATE :andvZGRlcgBqd29kZGVyAGh1bnRlcjI="]);
+        assert!(!cmd.is_done());
+
+        let msg = ":irc.example.com 900 jwodder jwodder!jwuser@localhost jwodder :You are now logged in as jwodder"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        assert!(!cmd.is_done());
+
+        let msg = ":irc.example.com 903 jwodder :SASL authentication successful"
+            .parse::<Message>()
+            .unwrap();
+        assert!(cmd.handle_message(&msg));
+        let outgoing = cmd
+            .get_client_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["CAP END"]);
+        assert!(!cmd.is_done());
+    }
 }
@@ -1,6 +1,10 @@
 use irctext::{ClientMessage, Message};
 use std::time::Duration;
 
+pub mod chathistory;
+pub mod login;
+pub mod starttls;
+
 /// A trait for sending messages to an IRC server and handling the replies.
 ///
 /// A `Command` is intended to be used as follows:
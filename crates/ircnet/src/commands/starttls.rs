@@ -0,0 +1,93 @@
+use super::Command;
+use irctext::{ClientMessage, Message, Payload, Reply, clientmsgs::StartTls as StartTlsMsg};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Sends `STARTTLS` and waits for the server's `RPL_STARTTLS` (670) or
+/// `ERR_STARTTLSERROR` (691) response.
+///
+/// This command only negotiates the protocol handshake; it does not perform
+/// the TLS handshake itself.  On success, the caller is responsible for
+/// swapping the underlying connection for a TLS stream (see
+/// [`starttls_upgrade`](crate::starttls_upgrade)) before sending or
+/// receiving any further messages.  Any unrelated messages the server sends
+/// before replying are left unhandled, so callers using [`Client::run`](crate::Client::run)
+/// will have them buffered and replayed afterwards rather than lost.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StartTlsNegotiation {
+    outgoing: Vec<ClientMessage>,
+    state: State,
+}
+
+impl StartTlsNegotiation {
+    pub fn new() -> StartTlsNegotiation {
+        StartTlsNegotiation {
+            outgoing: vec![ClientMessage::from(StartTlsMsg)],
+            state: State::Awaiting,
+        }
+    }
+}
+
+impl Default for StartTlsNegotiation {
+    fn default() -> StartTlsNegotiation {
+        StartTlsNegotiation::new()
+    }
+}
+
+impl Command for StartTlsNegotiation {
+    type Output = Result<(), StartTlsError>;
+
+    fn get_client_messages(&mut self) -> Vec<ClientMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    fn handle_message(&mut self, msg: &Message) -> bool {
+        if !matches!(self.state, State::Awaiting) {
+            return false;
+        }
+        match &msg.payload {
+            Payload::Reply(Reply::StartTLS(_)) => {
+                self.state = State::Done(Ok(()));
+                true
+            }
+            Payload::Reply(Reply::StartTLSError(r)) => {
+                self.state = State::Done(Err(StartTlsError::Rejected {
+                    message: r.message().to_owned(),
+                }));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn get_timeout(&mut self) -> Option<Duration> {
+        None
+    }
+
+    fn handle_timeout(&mut self) {}
+
+    fn is_done(&self) -> bool {
+        matches!(self.state, State::Done(_))
+    }
+
+    fn get_output(&mut self) -> Self::Output {
+        if let State::Done(ref mut r) = self.state {
+            std::mem::replace(r, Ok(()))
+        } else {
+            panic!("get_output() should only be called when is_done() is true");
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum State {
+    Awaiting,
+    Done(Result<(), StartTlsError>),
+}
+
+/// The server rejected a `STARTTLS` request.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum StartTlsError {
+    #[error("server rejected STARTTLS: {message}")]
+    Rejected { message: String },
+}
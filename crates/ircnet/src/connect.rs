@@ -1,12 +1,28 @@
 use crate::codecs::{MessageCodec, RawMessageCodec};
+use bytes::Bytes;
+use irctext::replies::Bounce;
 use itertools::Itertools; // join
-use rustls_pki_types::{InvalidDnsNameError, ServerName};
+use rustls_pki_types::{CertificateDer, InvalidDnsNameError, PrivateKeyDer, ServerName, UnixTime};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::time::sleep;
 use tokio_rustls::{
-    rustls::{ClientConfig, RootCertStore},
     TlsConnector,
+    rustls::{
+        ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+        client::{
+            VerifierBuilderError, WebPkiServerVerifier,
+            danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        },
+        crypto::CryptoProvider,
+    },
 };
 use tokio_util::{codec::Framed, either::Either};
 
@@ -18,17 +34,258 @@ pub type RawMessageChannel = Framed<Connection, RawMessageCodec>;
 
 pub type MessageChannel = Framed<Connection, MessageCodec>;
 
-pub async fn connect(server: &str, port: u16, tls: bool) -> Result<Connection, ConnectionError> {
-    log::trace!("Connecting to {server:?} on port {port} ...");
-    let conn = TcpStream::connect((server, port))
-        .await
-        .map_err(ConnectionError::Connect)?;
-    match conn.peer_addr() {
-        Ok(addr) => log::trace!("Connected to {addr}"),
-        Err(e) => log::trace!("Failed to determine remote peer address: {e}"),
+/// A TLS client certificate (and matching private key) to present during the
+/// handshake, for servers/services that support CertFP-based authentication
+/// (e.g. Libera.Chat's and OFTC's NickServ `CERT ADD`).  Pair this with
+/// [`SaslMechanism::External`](crate::SaslMechanism::External) so that login
+/// authenticates via the certificate instead of a password.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClientCert {
+    /// PEM-encoded certificate chain, leaf certificate first
+    pub cert_pem: String,
+    /// PEM-encoded private key matching the leaf certificate
+    pub key_pem: String,
+}
+
+impl ClientCert {
+    /// Reads the certificate chain and private key PEM files at the given
+    /// paths into a [`ClientCert`], for callers that keep CertFP credentials
+    /// on disk rather than embedded in configuration.
+    pub fn from_files(
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> Result<ClientCert, ConnectionError> {
+        let cert_pem = std::fs::read_to_string(cert_path).map_err(ConnectionError::ReadCertFile)?;
+        let key_pem = std::fs::read_to_string(key_path).map_err(ConnectionError::ReadCertFile)?;
+        Ok(ClientCert { cert_pem, key_pem })
     }
-    if tls {
-        log::trace!("Initializing TLS ...");
+
+    fn load(
+        &self,
+    ) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), ConnectionError> {
+        let certs = rustls_pemfile::certs(&mut self.cert_pem.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ConnectionError::LoadCert)?;
+        if certs.is_empty() {
+            return Err(ConnectionError::NoCert);
+        }
+        let key = rustls_pemfile::private_key(&mut self.key_pem.as_bytes())
+            .map_err(ConnectionError::LoadCert)?
+            .ok_or(ConnectionError::NoKey)?;
+        Ok((certs, key))
+    }
+
+    /// Computes the SHA-256 fingerprint of the leaf certificate — the
+    /// lowercase hex digest of its DER encoding — as used by IRC services
+    /// for CertFP matching.
+    pub fn fingerprint(&self) -> Result<String, ConnectionError> {
+        let (certs, _) = self.load()?;
+        let leaf = certs.first().ok_or(ConnectionError::NoCert)?;
+        Ok(fingerprint_of(leaf))
+    }
+}
+
+fn fingerprint_of(cert: &CertificateDer<'_>) -> String {
+    Sha256::digest(cert.as_ref())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// A [`ServerCertVerifier`] that accepts a connection if and only if the
+/// server's leaf certificate has the given SHA-256 fingerprint (as computed
+/// by [`fingerprint_of`]/[`ClientCert::fingerprint`]), ignoring the native
+/// root store entirely.  Use this to pin a specific certificate on networks
+/// with self-signed or otherwise CA-unverifiable IRC servers.
+#[derive(Debug)]
+pub struct FingerprintVerifier {
+    expected: String,
+}
+
+impl FingerprintVerifier {
+    /// `expected_fingerprint` is matched case-insensitively against the
+    /// lowercase hex digest produced by [`fingerprint_of`].
+    pub fn new(expected_fingerprint: impl Into<String>) -> FingerprintVerifier {
+        FingerprintVerifier {
+            expected: expected_fingerprint.into().to_lowercase(),
+        }
+    }
+}
+
+impl ServerCertVerifier for FingerprintVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        if fingerprint_of(end_entity) == self.expected {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(tokio_rustls::rustls::Error::General(format!(
+                "server certificate fingerprint did not match pinned fingerprint {:?}",
+                self.expected
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls12_signature_with_default_provider(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls13_signature_with_default_provider(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        supported_verify_schemes_of_default_provider()
+    }
+}
+
+/// A [`ServerCertVerifier`] that accepts a connection if and only if the
+/// server's leaf certificate is byte-for-byte identical to a single
+/// explicitly-trusted certificate, ignoring the native root store entirely.
+/// Use this to connect to a self-signed IRC server whose certificate you
+/// already have on hand (as opposed to [`FingerprintVerifier`], which only
+/// needs the fingerprint).
+#[derive(Debug)]
+pub struct TrustedCert {
+    expected: CertificateDer<'static>,
+}
+
+impl TrustedCert {
+    pub fn new(expected: CertificateDer<'static>) -> TrustedCert {
+        TrustedCert { expected }
+    }
+}
+
+impl ServerCertVerifier for TrustedCert {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        if *end_entity == self.expected {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(tokio_rustls::rustls::Error::General(
+                "server certificate did not match the explicitly-trusted certificate".to_owned(),
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls12_signature_with_default_provider(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls13_signature_with_default_provider(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        supported_verify_schemes_of_default_provider()
+    }
+}
+
+fn default_crypto_provider() -> Arc<CryptoProvider> {
+    CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(tokio_rustls::rustls::crypto::ring::default_provider()))
+}
+
+fn verify_tls12_signature_with_default_provider(
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+    tokio_rustls::rustls::crypto::verify_tls12_signature(
+        message,
+        cert,
+        dss,
+        &default_crypto_provider().signature_verification_algorithms,
+    )
+}
+
+fn verify_tls13_signature_with_default_provider(
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+    tokio_rustls::rustls::crypto::verify_tls13_signature(
+        message,
+        cert,
+        dss,
+        &default_crypto_provider().signature_verification_algorithms,
+    )
+}
+
+fn supported_verify_schemes_of_default_provider() -> Vec<SignatureScheme> {
+    default_crypto_provider()
+        .signature_verification_algorithms
+        .supported_schemes()
+}
+
+/// Computes the RFC 5929 `tls-server-end-point` channel-binding data for a
+/// connection: the SHA-256 hash of the server's leaf certificate, for use
+/// with the `-PLUS` SCRAM mechanisms (see
+/// [`SaslMechanism::ScramSha256Plus`](crate::SaslMechanism::ScramSha256Plus)
+/// and friends).  Returns `None` for a plaintext connection or if the server
+/// presented no certificate.
+///
+/// Per RFC 5929, the hash algorithm should match the one used to sign the
+/// certificate (with MD5 or SHA-1 signatures mapped to SHA-256); this always
+/// uses SHA-256, which covers the overwhelming majority of certificates seen
+/// in practice.
+pub fn tls_server_end_point(conn: &Connection) -> Option<Bytes> {
+    match conn {
+        Either::Left(_) => None,
+        Either::Right(tls) => {
+            let (_, conn) = tls.get_ref();
+            let cert = conn.peer_certificates()?.first()?;
+            Some(Bytes::from_iter(Sha256::digest(cert.as_ref())))
+        }
+    }
+}
+
+/// Builds the [`TlsConnector`] used by [`connect`] and [`starttls_upgrade`],
+/// either from a custom `server_cert_verifier` (e.g. [`FingerprintVerifier`]
+/// or [`TrustedCert`]) or, absent one, from the native system root store.
+fn build_tls_connector(
+    client_cert: Option<&ClientCert>,
+    server_cert_verifier: Option<Arc<dyn ServerCertVerifier>>,
+) -> Result<TlsConnector, ConnectionError> {
+    let builder = if let Some(verifier) = server_cert_verifier {
+        log::info!("Using custom server certificate verifier instead of the native root store");
+        ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(verifier)
+    } else {
         let certs = rustls_native_certs::load_native_certs();
         if !certs.errors.is_empty() {
             let msg = certs.errors.into_iter().join("; ");
@@ -39,22 +296,352 @@ pub async fn connect(server: &str, port: u16, tls: bool) -> Result<Connection, C
         if good == 0 {
             return Err(ConnectionError::AddCerts { bad });
         }
-        let config = ClientConfig::builder()
-            .with_root_certificates(root_cert_store)
-            .with_no_client_auth();
-        let connector = TlsConnector::from(Arc::new(config));
-        let dnsname = ServerName::try_from(server)?.to_owned();
-        let tls_conn = connector
-            .connect(dnsname, conn)
+        ClientConfig::builder().with_root_certificates(root_cert_store)
+    };
+    let config = if let Some(cert) = client_cert {
+        let (chain, key) = cert.load()?;
+        if let Some(leaf) = chain.first() {
+            let fingerprint = fingerprint_of(leaf);
+            log::info!("Presenting TLS client certificate with CertFP fingerprint {fingerprint}");
+        }
+        builder
+            .with_client_auth_cert(chain, key)
+            .map_err(ConnectionError::ClientAuth)?
+    } else {
+        builder.with_no_client_auth()
+    };
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Builds a [`ServerCertVerifier`] that checks the server's certificate
+/// against `roots` via the standard WebPKI path-building algorithm, for
+/// callers who want to supply their own root store (e.g. a bundled set from
+/// [`webpki_roots_store`]) instead of the native system one used by
+/// [`connect`] when no `server_cert_verifier` is given.
+pub fn verifier_for_roots(
+    roots: RootCertStore,
+) -> Result<Arc<dyn ServerCertVerifier>, ConnectionError> {
+    let verifier = WebPkiServerVerifier::builder(Arc::new(roots))
+        .build()
+        .map_err(ConnectionError::BuildVerifier)?;
+    Ok(verifier)
+}
+
+/// Returns a [`RootCertStore`] populated with the Mozilla root CA bundle
+/// embedded in the `webpki-roots` crate, for callers who want a fixed,
+/// platform-independent trust anchor instead of [`connect`]'s default of the
+/// native system root store.
+#[cfg(feature = "webpki-roots")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webpki-roots")))]
+pub fn webpki_roots_store() -> RootCertStore {
+    RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.into(),
+    }
+}
+
+/// A SOCKS5 proxy (per RFC 1928) to dial through instead of connecting to the
+/// IRC server directly, for use with [`connect`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Socks5Proxy {
+    /// The proxy's hostname or IP address
+    pub host: String,
+    /// The proxy's port
+    pub port: u16,
+    /// Username/password credentials to authenticate to the proxy with (RFC
+    /// 1929), or `None` to request no authentication
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub credentials: Option<(String, String)>,
+}
+
+/// Dials `target_host`/`target_port` through `proxy` using the SOCKS5
+/// protocol (RFC 1928), requesting that the proxy itself resolve
+/// `target_host` (so that proxy-side DNS and `.onion` addresses work).
+async fn dial_socks5(
+    proxy: &Socks5Proxy,
+    target_host: &str,
+    target_port: u16,
+) -> Result<TcpStream, ConnectionError> {
+    log::trace!(
+        "Connecting to SOCKS5 proxy {:?} on port {} ...",
+        proxy.host,
+        proxy.port
+    );
+    let mut conn = TcpStream::connect((proxy.host.as_str(), proxy.port))
+        .await
+        .map_err(ConnectionError::Socks5)?;
+    let method = if proxy.credentials.is_some() {
+        0x02
+    } else {
+        0x00
+    };
+    conn.write_all(&[0x05, 0x01, method])
+        .await
+        .map_err(ConnectionError::Socks5)?;
+    let mut reply = [0u8; 2];
+    conn.read_exact(&mut reply)
+        .await
+        .map_err(ConnectionError::Socks5)?;
+    if reply[0] != 0x05 {
+        return Err(ConnectionError::Socks5Protocol(
+            "proxy did not speak SOCKS5",
+        ));
+    }
+    if reply[1] != method {
+        return Err(ConnectionError::Socks5Auth);
+    }
+    if let Some((username, password)) = &proxy.credentials {
+        let mut req = vec![0x01, username.len() as u8];
+        req.extend_from_slice(username.as_bytes());
+        req.push(password.len() as u8);
+        req.extend_from_slice(password.as_bytes());
+        conn.write_all(&req)
+            .await
+            .map_err(ConnectionError::Socks5)?;
+        let mut auth_reply = [0u8; 2];
+        conn.read_exact(&mut auth_reply)
             .await
-            .map_err(ConnectionError::Tls)?;
-        log::trace!("TLS established");
+            .map_err(ConnectionError::Socks5)?;
+        if auth_reply[1] != 0x00 {
+            return Err(ConnectionError::Socks5Auth);
+        }
+    }
+    let host_bytes = target_host.as_bytes();
+    if host_bytes.len() > 255 {
+        return Err(ConnectionError::Socks5Protocol(
+            "target hostname is too long",
+        ));
+    }
+    let mut req = vec![0x05, 0x01, 0x00, 0x03, host_bytes.len() as u8];
+    req.extend_from_slice(host_bytes);
+    req.extend_from_slice(&target_port.to_be_bytes());
+    conn.write_all(&req)
+        .await
+        .map_err(ConnectionError::Socks5)?;
+    let mut head = [0u8; 4];
+    conn.read_exact(&mut head)
+        .await
+        .map_err(ConnectionError::Socks5)?;
+    if head[0] != 0x05 {
+        return Err(ConnectionError::Socks5Protocol(
+            "proxy did not speak SOCKS5",
+        ));
+    }
+    if head[1] != 0x00 {
+        return Err(ConnectionError::Socks5Connect(head[1]));
+    }
+    match head[3] {
+        0x01 => {
+            let mut addr = [0u8; 4 + 2];
+            conn.read_exact(&mut addr)
+                .await
+                .map_err(ConnectionError::Socks5)?;
+        }
+        0x03 => {
+            let mut len = [0u8; 1];
+            conn.read_exact(&mut len)
+                .await
+                .map_err(ConnectionError::Socks5)?;
+            let mut addr = vec![0u8; usize::from(len[0]) + 2];
+            conn.read_exact(&mut addr)
+                .await
+                .map_err(ConnectionError::Socks5)?;
+        }
+        0x04 => {
+            let mut addr = [0u8; 16 + 2];
+            conn.read_exact(&mut addr)
+                .await
+                .map_err(ConnectionError::Socks5)?;
+        }
+        _ => {
+            return Err(ConnectionError::Socks5Protocol(
+                "unrecognized bound address type",
+            ));
+        }
+    }
+    log::trace!("SOCKS5 proxy established connection to {target_host:?}:{target_port}");
+    Ok(conn)
+}
+
+/// How long [`happy_eyeballs_connect`] waits for one connection attempt to
+/// succeed before racing the next candidate address in parallel, per RFC
+/// 8305's recommended default.
+const HAPPY_EYEBALLS_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Reorders `addrs` so that IPv6 and IPv4 addresses alternate, preferring an
+/// initial IPv6 address, as recommended by RFC 8305 for Happy Eyeballs
+/// dialing.
+fn interleave_by_family(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let mut v6 = VecDeque::new();
+    let mut v4 = VecDeque::new();
+    for addr in addrs {
+        if addr.is_ipv6() {
+            v6.push_back(addr);
+        } else {
+            v4.push_back(addr);
+        }
+    }
+    let mut out = Vec::with_capacity(v6.len() + v4.len());
+    loop {
+        match (v6.pop_front(), v4.pop_front()) {
+            (None, None) => break,
+            (Some(a), None) => out.push(a),
+            (None, Some(a)) => out.push(a),
+            (Some(a), Some(b)) => {
+                out.push(a);
+                out.push(b);
+            }
+        }
+    }
+    out
+}
+
+/// Resolves `server` and races connection attempts to the resulting
+/// addresses using the Happy Eyeballs algorithm (RFC 8305): candidates are
+/// interleaved by address family (see [`interleave_by_family`]) and dialed
+/// one at a time, with up to `attempt_delay` given for each attempt before
+/// the next candidate is started in parallel; the first successful
+/// connection wins and all other in-flight attempts are dropped.
+async fn happy_eyeballs_connect(
+    server: &str,
+    port: u16,
+    attempt_delay: Duration,
+) -> Result<TcpStream, ConnectionError> {
+    let addrs = tokio::net::lookup_host((server, port))
+        .await
+        .map_err(ConnectionError::Connect)?
+        .collect::<Vec<_>>();
+    let addrs = interleave_by_family(addrs);
+    if addrs.is_empty() {
+        return Err(ConnectionError::Connect(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no addresses found for host",
+        )));
+    }
+    let mut attempts = tokio::task::JoinSet::new();
+    let mut remaining = addrs.into_iter();
+    let mut last_err = None;
+    if let Some(addr) = remaining.next() {
+        attempts.spawn(async move { (addr, TcpStream::connect(addr).await) });
+    }
+    loop {
+        let timer = sleep(attempt_delay);
+        tokio::select! {
+            () = timer, if remaining.len() > 0 => {
+                if let Some(addr) = remaining.next() {
+                    attempts.spawn(async move { (addr, TcpStream::connect(addr).await) });
+                }
+            }
+            res = attempts.join_next() => {
+                match res {
+                    Some(Ok((addr, Ok(stream)))) => {
+                        log::trace!("Connected to {addr}");
+                        return Ok(stream);
+                    }
+                    Some(Ok((addr, Err(e)))) => {
+                        log::trace!("Failed to connect to {addr}: {e}");
+                        last_err = Some(e);
+                        if let Some(addr) = remaining.next() {
+                            attempts.spawn(async move { (addr, TcpStream::connect(addr).await) });
+                        } else if attempts.is_empty() {
+                            break;
+                        }
+                    }
+                    Some(Err(_)) => continue,
+                    None => break,
+                }
+            }
+        }
+    }
+    Err(ConnectionError::Connect(last_err.unwrap_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "no addresses found for host")
+    })))
+}
+
+/// A pluggable DNS resolver for turning a [`Bounce`] redirect's hostname
+/// into one or more connectable [`SocketAddr`]s, so that callers can supply
+/// an async-runtime-specific resolver or a caching layer instead of always
+/// paying for a fresh lookup via [`TokioResolver`].
+pub trait BounceResolver {
+    /// Resolves `host`, returning every candidate address (both `A` and
+    /// `AAAA` results, where applicable) in the order the resolver prefers.
+    async fn resolve(&self, host: &str, port: u16) -> std::io::Result<Vec<SocketAddr>>;
+}
+
+/// The default [`BounceResolver`], backed by [`tokio::net::lookup_host`]
+/// (the same resolution primitive [`happy_eyeballs_connect`] uses), which
+/// already short-circuits DNS lookup when `host` is an IP literal.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TokioResolver;
+
+impl BounceResolver for TokioResolver {
+    async fn resolve(&self, host: &str, port: u16) -> std::io::Result<Vec<SocketAddr>> {
+        Ok(tokio::net::lookup_host((host, port)).await?.collect())
+    }
+}
+
+/// Resolves the hostname & port carried by an `RPL_BOUNCE` redirect into an
+/// ordered list of candidate [`SocketAddr`]s that a connection layer can
+/// dial directly (e.g. via [`happy_eyeballs_connect`]'s own strategy, or
+/// one attempt at a time), using `resolver` to perform the lookup.
+pub async fn resolve_bounce<R: BounceResolver>(
+    bounce: &Bounce,
+    resolver: &R,
+) -> Result<Vec<SocketAddr>, ConnectionError> {
+    resolver
+        .resolve(bounce.hostname(), bounce.port())
+        .await
+        .map_err(ConnectionError::Connect)
+}
+
+pub async fn connect(
+    server: &str,
+    port: u16,
+    tls: bool,
+    client_cert: Option<&ClientCert>,
+    server_cert_verifier: Option<Arc<dyn ServerCertVerifier>>,
+    proxy: Option<&Socks5Proxy>,
+) -> Result<Connection, ConnectionError> {
+    log::trace!("Connecting to {server:?} on port {port} ...");
+    let conn = if let Some(proxy) = proxy {
+        dial_socks5(proxy, server, port).await?
+    } else {
+        happy_eyeballs_connect(server, port, HAPPY_EYEBALLS_ATTEMPT_DELAY).await?
+    };
+    match conn.peer_addr() {
+        Ok(addr) => log::trace!("Connected to {addr}"),
+        Err(e) => log::trace!("Failed to determine remote peer address: {e}"),
+    }
+    if tls {
+        let tls_conn = starttls_upgrade(conn, server, client_cert, server_cert_verifier).await?;
         Ok(Either::Right(tls_conn))
     } else {
         Ok(Either::Left(conn))
     }
 }
 
+/// Performs a TLS handshake on an already-open, still-plaintext [`TcpStream`]
+/// (e.g. one returned by `connect(..., tls: false, ...)`), for protocols like
+/// IRC's `STARTTLS`/`STLS` extensions where the handshake happens in-band
+/// partway through an existing connection rather than immediately upon
+/// dialing.
+pub async fn starttls_upgrade(
+    conn: TcpStream,
+    server: &str,
+    client_cert: Option<&ClientCert>,
+    server_cert_verifier: Option<Arc<dyn ServerCertVerifier>>,
+) -> Result<TlsStream, ConnectionError> {
+    log::trace!("Upgrading connection to {server:?} to TLS ...");
+    let connector = build_tls_connector(client_cert, server_cert_verifier)?;
+    let dnsname = ServerName::try_from(server)?.to_owned();
+    let tls_conn = connector
+        .connect(dnsname, conn)
+        .await
+        .map_err(ConnectionError::Tls)?;
+    log::trace!("TLS established");
+    Ok(tls_conn)
+}
+
 #[derive(Debug, Error)]
 pub enum ConnectionError {
     #[error("failed to connect to server")]
@@ -65,6 +652,26 @@ pub enum ConnectionError {
     AddCerts { bad: usize },
     #[error("invalid TLS server name")]
     ServerName(#[from] InvalidDnsNameError),
+    #[error("failed to parse client certificate or private key")]
+    LoadCert(#[source] std::io::Error),
+    #[error("failed to read client certificate or private key file")]
+    ReadCertFile(#[source] std::io::Error),
+    #[error("no certificates found in client certificate PEM")]
+    NoCert,
+    #[error("no private key found in client certificate's key PEM")]
+    NoKey,
+    #[error("failed to configure TLS client certificate")]
+    ClientAuth(#[source] tokio_rustls::rustls::Error),
     #[error("failed to establish TLS connection")]
     Tls(#[source] std::io::Error),
+    #[error("failed to build certificate verifier from root store")]
+    BuildVerifier(#[source] VerifierBuilderError),
+    #[error("SOCKS5 proxy I/O error")]
+    Socks5(#[source] std::io::Error),
+    #[error("SOCKS5 protocol error: {0}")]
+    Socks5Protocol(&'static str),
+    #[error("SOCKS5 proxy rejected authentication credentials")]
+    Socks5Auth,
+    #[error("SOCKS5 proxy refused to connect (reply code {0})")]
+    Socks5Connect(u8),
 }
@@ -3,11 +3,30 @@
 //!
 //! - The encoder appends the line ending CR LF, not LF.
 //!
+//! - Encoder: `max_length` is now also enforced, so that a caller cannot
+//!   silently ship a line that a server would truncate or reject.
+//!
 //! - Text decoding is first attempted using UTF-8; if that fails, it falls
 //!   back to Latin-1.
 //!
 //! - Decoder: `max_length` now includes the terminating line ending.
 //!
+//! - With the `encoding_rs` feature enabled, [`LinesCodec::with_encoding`]
+//!   can be used to decode/encode lines with a specific non-UTF-8 text
+//!   encoding instead of the UTF-8-then-Latin-1 fallback. Decoding still
+//!   tries UTF-8 first, falling back to the configured encoding only when a
+//!   line isn't valid UTF-8, so servers that mix UTF-8 and legacy-charset
+//!   clients don't get their UTF-8 traffic mangled.
+//!
+//! - [`LinesCodec::new_with_tag_budget`] enforces IRCv3 message tags'
+//!   separate length limit instead of lumping the whole line under a single
+//!   `max_length`, on both decode and encode.
+//!
+//! - Decoder: a frame containing a NUL or an embedded CR, once its
+//!   terminating `\r\n`/`\n` has been stripped, is rejected instead of
+//!   passed through, so a malformed or malicious peer can't smuggle a
+//!   second command into what's supposed to be a single line.
+//!
 //! [1]: https://github.com/tokio-rs/tokio/blob/a03e0420249d1740668f608a5a16f1fa614be2c7/tokio-util/src/codec/lines_codec.rs
 
 // Copyright (c) 2022 Tokio Contributors
@@ -42,7 +61,11 @@ use thiserror::Error;
 use tokio_util::codec::{Decoder, Encoder};
 
 /// A simple [`Decoder`] and [`Encoder`] implementation that splits up data into lines.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    not(feature = "encoding_rs"),
+    derive(Eq, Hash, Ord, PartialEq, PartialOrd)
+)]
 pub struct LinesCodec {
     // Stored index of the next index to examine for a `\n` character.
     // This is used to optimize searching.
@@ -59,8 +82,33 @@ pub struct LinesCodec {
     /// Are we currently discarding the remainder of a line which was over
     /// the length limit?
     is_discarding: bool,
+
+    /// If set, `max_length` only applies to the non-tag portion of the
+    /// line (everything after the leading `@tags ` section, if any), and
+    /// this field caps the tag section on its own; see
+    /// [`LinesCodec::new_with_tag_budget`].
+    tag_budget: Option<usize>,
+
+    /// The text encoding to use instead of the default UTF-8-then-Latin-1
+    /// fallback, set via [`LinesCodec::with_encoding`].
+    #[cfg(feature = "encoding_rs")]
+    encoding: Option<&'static encoding_rs::Encoding>,
 }
 
+#[cfg(feature = "encoding_rs")]
+impl PartialEq for LinesCodec {
+    fn eq(&self, other: &Self) -> bool {
+        self.next_index == other.next_index
+            && self.max_length == other.max_length
+            && self.is_discarding == other.is_discarding
+            && self.tag_budget == other.tag_budget
+            && self.encoding.map(|e| e as *const _) == other.encoding.map(|e| e as *const _)
+    }
+}
+
+#[cfg(feature = "encoding_rs")]
+impl Eq for LinesCodec {}
+
 impl LinesCodec {
     /// Returns a `LinesCodec` for splitting up data into lines.
     ///
@@ -74,6 +122,9 @@ impl LinesCodec {
             next_index: 0,
             max_length: usize::MAX,
             is_discarding: false,
+            tag_budget: None,
+            #[cfg(feature = "encoding_rs")]
+            encoding: None,
         }
     }
 
@@ -93,6 +144,43 @@ impl LinesCodec {
             ..LinesCodec::new()
         }
     }
+
+    /// Returns a `LinesCodec` that enforces the IRCv3 message-tags length
+    /// limits separately from the rest of the line, per
+    /// <https://modern.ircdocs.horse/#size-limits>: the leading `@tags `
+    /// section (if present) may use up to `tag_budget` bytes, while the
+    /// remainder of the line, including the terminating CR LF, is capped at
+    /// `max_length` bytes, matching plain IRC's long-standing per-message
+    /// limit.  [`LinesCodecError::MaxLineLengthExceeded`] is returned if the
+    /// non-tag portion overflows, and
+    /// [`LinesCodecError::TagSectionTooLong`] if the tag portion does.  The
+    /// same limits are enforced on encode as well as decode.
+    pub fn new_with_tag_budget(max_length: usize, tag_budget: usize) -> Self {
+        LinesCodec {
+            tag_budget: Some(tag_budget),
+            ..LinesCodec::new_with_max_length(max_length)
+        }
+    }
+
+    /// Uses `encoding` instead of the default UTF-8-then-Latin-1 fallback
+    /// for decoding incoming lines and encoding outgoing ones.
+    #[cfg(feature = "encoding_rs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding_rs")))]
+    pub fn with_encoding(mut self, encoding: &'static encoding_rs::Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    fn decode_line(&self, bs: Vec<u8>) -> String {
+        #[cfg(feature = "encoding_rs")]
+        if let Some(encoding) = self.encoding {
+            return match String::from_utf8(bs) {
+                Ok(s) => s,
+                Err(e) => encoding.decode(e.as_bytes()).0.into_owned(),
+            };
+        }
+        decode_utf8_latin1(bs)
+    }
 }
 
 impl Decoder for LinesCodec {
@@ -103,7 +191,14 @@ impl Decoder for LinesCodec {
         loop {
             // Determine how far into the buffer we'll search for a newline. If
             // there's no max_length set, we'll read to the end of the buffer.
-            let read_to = cmp::min(self.max_length, buf.len());
+            // With a tag budget configured, the search window is widened to
+            // cover both the tag section and the message section, since a
+            // line's tag section doesn't count against `max_length`.
+            let cap = match self.tag_budget {
+                Some(tag_budget) => self.max_length.saturating_add(tag_budget),
+                None => self.max_length,
+            };
+            let read_to = cmp::min(cap, buf.len());
             let newline_offset = buf[self.next_index..read_to]
                 .iter()
                 .position(|b| *b == b'\n');
@@ -131,15 +226,35 @@ impl Decoder for LinesCodec {
                     let newline_index = offset + self.next_index;
                     self.next_index = 0;
                     let line = buf.split_to(newline_index + 1);
+                    if let Some(tag_budget) = self.tag_budget {
+                        let tag_len = tag_section_len(&line);
+                        if tag_len > tag_budget {
+                            return Err(LinesCodecError::TagSectionTooLong);
+                        }
+                        if line.len() - tag_len > self.max_length {
+                            return Err(LinesCodecError::MaxLineLengthExceeded);
+                        }
+                    }
                     let line = chomp(&line);
-                    let line = decode_utf8_latin1(line.into());
+                    check_no_illegal_bytes(line)?;
+                    let line = self.decode_line(line.into());
                     return Ok(Some(line));
                 }
-                (false, None) if buf.len() >= self.max_length => {
+                (false, None) if buf.len() >= cap => {
                     // Reached the maximum length without finding a
                     // newline, return an error and start discarding on the
-                    // next call.
+                    // next call. If a tag budget is configured and the
+                    // buffered data still looks like an unterminated tag
+                    // section (starts with `@` and no space has been seen
+                    // yet), attribute the overflow to the tag section
+                    // instead of the message body.
                     self.is_discarding = true;
+                    if self.tag_budget.is_some()
+                        && buf.first() == Some(&b'@')
+                        && !buf[..read_to].contains(&b' ')
+                    {
+                        return Err(LinesCodecError::TagSectionTooLong);
+                    }
                     return Err(LinesCodecError::MaxLineLengthExceeded);
                 }
                 (false, None) => {
@@ -161,8 +276,18 @@ impl Decoder for LinesCodec {
                     Ok(None)
                 } else {
                     let line = buf.split_to(buf.len());
+                    if let Some(tag_budget) = self.tag_budget {
+                        let tag_len = tag_section_len(&line);
+                        if tag_len > tag_budget {
+                            return Err(LinesCodecError::TagSectionTooLong);
+                        }
+                        if line.len() - tag_len > self.max_length {
+                            return Err(LinesCodecError::MaxLineLengthExceeded);
+                        }
+                    }
                     let line = chomp(&line);
-                    let line = decode_utf8_latin1(line.into());
+                    check_no_illegal_bytes(line)?;
+                    let line = self.decode_line(line.into());
                     self.next_index = 0;
                     Ok(Some(line))
                 }
@@ -179,6 +304,28 @@ where
 
     fn encode(&mut self, line: T, buf: &mut BytesMut) -> Result<(), LinesCodecError> {
         let line = line.as_ref();
+        if let Some(tag_budget) = self.tag_budget {
+            let tag_len = tag_section_len(line.as_bytes());
+            if tag_len > tag_budget {
+                return Err(LinesCodecError::TagSectionTooLong);
+            }
+            // `+ 2` accounts for the terminating CR LF that isn't part of
+            // `line` yet.
+            if line.len() - tag_len + 2 > self.max_length {
+                return Err(LinesCodecError::MaxLineLengthExceeded);
+            }
+        } else if line.len().saturating_add(2) > self.max_length {
+            return Err(LinesCodecError::MaxLineLengthExceeded);
+        }
+        #[cfg(feature = "encoding_rs")]
+        if let Some(encoding) = self.encoding {
+            let (bytes, _, _) = encoding.encode(line);
+            buf.reserve(bytes.len() + 2);
+            buf.put(&*bytes);
+            buf.put_u8(b'\r');
+            buf.put_u8(b'\n');
+            return Ok(());
+        }
         buf.reserve(line.len() + 2);
         buf.put(line.as_bytes());
         buf.put_u8(b'\r');
@@ -193,6 +340,33 @@ impl Default for LinesCodec {
     }
 }
 
+/// Checks a frame that's already had its terminating `\r\n`/`\n` stripped
+/// for a NUL or embedded CR, either of which would let a malformed or
+/// malicious peer smuggle a second command into what's supposed to be a
+/// single line.
+fn check_no_illegal_bytes(s: &[u8]) -> Result<(), LinesCodecError> {
+    if s.contains(&0) || s.contains(&b'\r') {
+        Err(LinesCodecError::IllegalByte)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the length in bytes of `line`'s leading IRCv3 message-tags
+/// section (the `@tags ` prefix, including the trailing space), or `0` if
+/// `line` doesn't start with `@`.  If no space is found, the whole line is
+/// treated as (an incomplete) tag section.
+fn tag_section_len(line: &[u8]) -> usize {
+    if line.first() == Some(&b'@') {
+        match line.iter().position(|&b| b == b' ') {
+            Some(pos) => pos + 1,
+            None => line.len(),
+        }
+    } else {
+        0
+    }
+}
+
 fn chomp(mut s: &[u8]) -> &[u8] {
     if s.last() == Some(&b'\n') {
         s = &s[..s.len() - 1];
@@ -216,6 +390,12 @@ pub enum LinesCodecError {
     #[error("maximum incoming line length exceeded")]
     MaxLineLengthExceeded,
 
+    #[error("message tag section exceeded the configured tag budget")]
+    TagSectionTooLong,
+
+    #[error("line contained a NUL or embedded CR")]
+    IllegalByte,
+
     #[error("I/O error communicating with server")]
     Io(#[from] io::Error),
 }
@@ -235,4 +415,124 @@ mod tests {
         let bs = b"Snow\xC3\xA9mon: \xE2\x98!".to_vec();
         assert_eq!(decode_utf8_latin1(bs), "Snow\u{c3}\u{a9}mon: \u{e2}\u{98}!");
     }
+
+    #[test]
+    fn test_tag_section_len_present() {
+        assert_eq!(tag_section_len(b"@id=1;time=2 PRIVMSG #chan :hi\r\n"), 13);
+    }
+
+    #[test]
+    fn test_tag_section_len_absent() {
+        assert_eq!(tag_section_len(b"PRIVMSG #chan :hi\r\n"), 0);
+    }
+
+    #[test]
+    fn test_encode_under_limit() {
+        let mut codec = LinesCodec::new_with_max_length(10);
+        let mut buf = BytesMut::new();
+        codec.encode("ab", &mut buf).unwrap();
+        assert_eq!(&buf[..], b"ab\r\n");
+    }
+
+    #[test]
+    fn test_encode_at_limit() {
+        let mut codec = LinesCodec::new_with_max_length(4);
+        let mut buf = BytesMut::new();
+        codec.encode("ab", &mut buf).unwrap();
+        assert_eq!(&buf[..], b"ab\r\n");
+    }
+
+    #[test]
+    fn test_encode_over_limit() {
+        let mut codec = LinesCodec::new_with_max_length(3);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode("ab", &mut buf),
+            Err(LinesCodecError::MaxLineLengthExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_tag_budget_exceeded() {
+        let mut codec = LinesCodec::new_with_tag_budget(512, 10);
+        let mut buf = BytesMut::from(&b"@id=abcdefghijklmnop PING\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(LinesCodecError::TagSectionTooLong)
+        ));
+    }
+
+    #[test]
+    fn test_message_budget_exceeded_with_tags() {
+        let mut codec = LinesCodec::new_with_tag_budget(16, 512);
+        let mut buf = BytesMut::from(&b"@id=1 PRIVMSG #chan :a rather long message\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(LinesCodecError::MaxLineLengthExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_encode_rejects_oversized_tag_section() {
+        let mut codec = LinesCodec::new_with_tag_budget(512, 10);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode("@id=abcdefghijklmnop PING", &mut buf),
+            Err(LinesCodecError::TagSectionTooLong)
+        ));
+    }
+
+    #[test]
+    fn test_encode_rejects_oversized_message_with_tags() {
+        let mut codec = LinesCodec::new_with_tag_budget(16, 512);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode("@id=1 PRIVMSG #chan :a rather long message", &mut buf),
+            Err(LinesCodecError::MaxLineLengthExceeded)
+        ));
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_decode_with_encoding_prefers_utf8() {
+        let mut codec = LinesCodec::new().with_encoding(encoding_rs::WINDOWS_1252);
+        let mut buf = BytesMut::from("Snow\u{e9}mon\r\n".as_bytes());
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some("Snow\u{e9}mon".to_string()));
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_decode_with_encoding_falls_back_on_invalid_utf8() {
+        let mut codec = LinesCodec::new().with_encoding(encoding_rs::WINDOWS_1252);
+        let mut buf = BytesMut::from(&b"Snow\x93mon\r\n"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some("Snow\u{201c}mon".to_string()));
+    }
+
+    #[test]
+    fn test_decode_rejects_embedded_nul() {
+        let mut codec = LinesCodec::new();
+        let mut buf = BytesMut::from(&b"PRIVMSG #chan :hi\0there\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(LinesCodecError::IllegalByte)
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_embedded_cr() {
+        let mut codec = LinesCodec::new();
+        let mut buf = BytesMut::from(&b"PRIVMSG #chan :QUIT\r:bye\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(LinesCodecError::IllegalByte)
+        ));
+    }
+
+    #[test]
+    fn test_encode_accepts_tagged_line_within_budget() {
+        let mut codec = LinesCodec::new_with_tag_budget(512, 8191);
+        let mut buf = BytesMut::new();
+        codec.encode("@id=1 PING :server", &mut buf).unwrap();
+        assert_eq!(&buf[..], &b"@id=1 PING :server\r\n"[..]);
+    }
 }
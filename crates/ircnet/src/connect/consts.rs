@@ -7,3 +7,8 @@ pub const TLS_PORT: u16 = 6697;
 pub const MAX_LINE_LENGTH: usize = 512;
 
 pub const MAX_LINE_LENGTH_WITH_TAGS: usize = MAX_LINE_LENGTH + 8191;
+
+// <https://ircv3.net/specs/extensions/message-tags.html> caps the leading
+// `@tags ` section of a line at 8191 bytes, separately from the 512-byte
+// limit on the rest of the line.
+pub const MAX_TAG_LENGTH: usize = 8191;
@@ -0,0 +1,157 @@
+//! DCC (Direct Client Connection) CHAT/SEND negotiation and transfer.
+//!
+//! A DCC offer (see [`irctext::ctcp::DccOffer`]) is sent as the parameters of
+//! a CTCP DCC message: the offering side listens on a TCP port and
+//! advertises its address and that port, while the accepting side connects
+//! out to it.  This module handles the raw TCP side of that exchange —
+//! [`DccListener`] for offering, [`accept_offer`] for accepting — applying a
+//! read timeout and TCP keepalive (via [`DccOptions`]) to the resulting
+//! [`DccConnection`], and [`send_file`] for streaming a DCC SEND transfer
+//! while tracking the receiver's acknowledged byte count.
+use socket2::{SockRef, TcpKeepalive};
+use std::io;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+/// Connection parameters applied to every DCC connection, whether offered or
+/// accepted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DccOptions {
+    /// How long to wait for the peer to send data before giving up on the
+    /// transfer.
+    pub read_timeout: Duration,
+    /// How long the connection must be idle before a keepalive probe is
+    /// sent.
+    pub keepalive_time: Duration,
+    /// How long to wait between unacknowledged keepalive probes.
+    pub keepalive_interval: Duration,
+    /// How many unacknowledged keepalive probes to send before considering
+    /// the connection dead.
+    pub keepalive_retries: u32,
+}
+
+impl DccOptions {
+    fn apply(&self, stream: &TcpStream) -> io::Result<()> {
+        let ka = TcpKeepalive::new()
+            .with_time(self.keepalive_time)
+            .with_interval(self.keepalive_interval)
+            .with_retries(self.keepalive_retries);
+        SockRef::from(stream).set_tcp_keepalive(&ka)
+    }
+}
+
+/// An established DCC connection (CHAT or SEND), with a read timeout and
+/// keepalive already applied.
+#[derive(Debug)]
+pub struct DccConnection {
+    stream: TcpStream,
+    read_timeout: Duration,
+}
+
+impl DccConnection {
+    fn new(stream: TcpStream, opts: &DccOptions) -> io::Result<DccConnection> {
+        opts.apply(&stream)?;
+        Ok(DccConnection {
+            stream,
+            read_timeout: opts.read_timeout,
+        })
+    }
+
+    pub fn peer_addr(&self) -> io::Result<std::net::SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    /// Read the next chunk of data, failing with [`DccError::ReadTimeout`] if
+    /// the peer sends nothing before the configured read timeout elapses.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, DccError> {
+        timeout(self.read_timeout, self.stream.read(buf))
+            .await
+            .map_err(|_| DccError::ReadTimeout)?
+            .map_err(DccError::Io)
+    }
+
+    /// Read exactly `buf.len()` bytes, failing with [`DccError::ReadTimeout`]
+    /// if the peer stalls before the configured read timeout elapses.
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), DccError> {
+        timeout(self.read_timeout, self.stream.read_exact(buf))
+            .await
+            .map_err(|_| DccError::ReadTimeout)?
+            .map_err(DccError::Io)?;
+        Ok(())
+    }
+
+    pub async fn write_all(&mut self, buf: &[u8]) -> Result<(), DccError> {
+        self.stream.write_all(buf).await.map_err(DccError::Io)
+    }
+}
+
+/// A TCP listener for offering a DCC CHAT or DCC SEND connection to a peer.
+///
+/// Bind a listener, advertise its port (and the caller's externally-visible
+/// address) in a [`DccOffer`][irctext::ctcp::DccOffer], then `accept()` the
+/// peer's incoming connection.
+#[derive(Debug)]
+pub struct DccListener {
+    listener: TcpListener,
+}
+
+impl DccListener {
+    /// Bind a listener on an OS-assigned port of the given local address
+    /// (typically `Ipv4Addr::UNSPECIFIED` to listen on all interfaces).
+    pub async fn bind(local_address: Ipv4Addr) -> io::Result<DccListener> {
+        let listener = TcpListener::bind((local_address, 0)).await?;
+        Ok(DccListener { listener })
+    }
+
+    /// The port that was assigned by the OS, to be advertised in the DCC
+    /// offer sent to the peer.
+    pub fn port(&self) -> io::Result<u16> {
+        Ok(self.listener.local_addr()?.port())
+    }
+
+    /// Accept the peer's connection to the offered port.
+    pub async fn accept(&self, opts: &DccOptions) -> io::Result<DccConnection> {
+        let (stream, _) = self.listener.accept().await?;
+        DccConnection::new(stream, opts)
+    }
+}
+
+/// Connect out to a peer's DCC offer, as the accepting side of the exchange.
+pub async fn accept_offer(
+    offer: &irctext::ctcp::DccOffer,
+    opts: &DccOptions,
+) -> io::Result<DccConnection> {
+    let stream = TcpStream::connect((offer.address(), offer.port())).await?;
+    DccConnection::new(stream, opts)
+}
+
+/// Stream `data` to the peer over an established DCC SEND connection,
+/// returning once the receiver has acknowledged all of it.
+///
+/// Per the DCC SEND protocol, the receiver periodically writes back the
+/// total number of bytes it has received so far, as a 4-byte big-endian
+/// integer; this function reads and discards those acks until the running
+/// total reaches `data.len()`.
+pub async fn send_file(conn: &mut DccConnection, data: &[u8]) -> Result<u64, DccError> {
+    conn.write_all(data).await?;
+    let total = u64::try_from(data.len()).expect("file size should fit in u64");
+    let mut acked = 0u64;
+    let mut ack_buf = [0u8; 4];
+    while acked < total {
+        conn.read_exact(&mut ack_buf).await?;
+        acked = u64::from(u32::from_be_bytes(ack_buf));
+    }
+    Ok(acked)
+}
+
+#[derive(Debug, Error)]
+pub enum DccError {
+    #[error("peer did not send data within the read timeout")]
+    ReadTimeout,
+    #[error("I/O error on DCC connection")]
+    Io(#[source] io::Error),
+}
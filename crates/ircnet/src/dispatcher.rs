@@ -0,0 +1,80 @@
+//! A [`ReplyDispatcher`] fans decoded [`Reply`] values out to any number of
+//! independent consumers over a bounded multi-producer/multi-consumer
+//! channel, so e.g. one task can await the `RPL_TIME` reply for a `/TIME`
+//! request while another watches for `ERR_NOSUCHNICK`/`ERR_NOSUCHCHANNEL`
+//! without either stealing events meant for the other. Built on
+//! [`tokio::sync::broadcast`]'s fixed-capacity ring buffer: a subscriber that
+//! falls behind observes a [`Lagged`](broadcast::error::RecvError::Lagged)
+//! gap rather than blocking the publisher or any other subscriber.
+
+use irctext::{Reply, ReplyParts};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// A fanout point for decoded replies. Cloning a `ReplyDispatcher` is cheap
+/// and yields another handle to the same underlying channel.
+#[derive(Clone, Debug)]
+pub struct ReplyDispatcher {
+    hub: broadcast::Sender<Arc<Reply>>,
+}
+
+impl ReplyDispatcher {
+    /// Creates a dispatcher whose channel can hold up to `capacity` replies
+    /// per subscriber before the oldest unread one is overwritten.
+    pub fn new(capacity: usize) -> ReplyDispatcher {
+        let (hub, _) = broadcast::channel(capacity);
+        ReplyDispatcher { hub }
+    }
+
+    /// Publishes `reply` to every current subscriber. A reply published
+    /// while there are no subscribers is simply dropped, same as any other
+    /// event with nothing watching for it.
+    pub fn publish(&self, reply: Reply) {
+        let _ = self.hub.send(Arc::new(reply));
+    }
+
+    /// Subscribes to every reply published from this point on.
+    pub fn subscribe(&self) -> ReplySubscriber {
+        ReplySubscriber {
+            rx: self.hub.subscribe(),
+            codes: None,
+        }
+    }
+
+    /// Subscribes to only those replies whose numeric
+    /// [`code()`](ReplyParts::code) is in `codes`, so a caller doesn't have
+    /// to drain and discard events destined for other consumers.
+    pub fn subscribe_filtered(&self, codes: Vec<u16>) -> ReplySubscriber {
+        ReplySubscriber {
+            rx: self.hub.subscribe(),
+            codes: Some(codes),
+        }
+    }
+}
+
+/// A handle returned by [`ReplyDispatcher::subscribe`]/[`ReplyDispatcher::subscribe_filtered`].
+#[derive(Debug)]
+pub struct ReplySubscriber {
+    rx: broadcast::Receiver<Arc<Reply>>,
+    codes: Option<Vec<u16>>,
+}
+
+impl ReplySubscriber {
+    /// Waits for the next reply matching this subscriber's filter (if any),
+    /// transparently skipping over ones the filter excludes. Returns `Err`
+    /// if this subscriber lagged far enough behind the publisher that
+    /// events were overwritten before it could read them, or once every
+    /// [`ReplyDispatcher`] handle for this channel has been dropped.
+    pub async fn recv(&mut self) -> Result<Arc<Reply>, broadcast::error::RecvError> {
+        loop {
+            let reply = self.rx.recv().await?;
+            let wanted = self
+                .codes
+                .as_deref()
+                .map_or(true, |codes| codes.contains(&reply.code()));
+            if wanted {
+                return Ok(reply);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! [`HelpAccumulator`] folds the `RPL_HELPSTART`/`RPL_HELPTXT`/`RPL_ENDOFHELP`
+//! burst sent in response to a `HELP` request into a single [`HelpResponse`],
+//! short-circuiting to [`AccumulatorState::Unexpected`] if the server replies
+//! with `ERR_HELPNOTFOUND` instead.
+//!
+//! [`ReplyAccumulator`] is deliberately generic over its output type so the
+//! same start/row/end(/not-found) shape can later back a `WHOIS` or `LIST`
+//! accumulator without a new trait.
+
+use irctext::replies::Reply;
+
+/// The result of feeding one reply to a [`ReplyAccumulator`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccumulatorState<T> {
+    /// The burst hasn't finished yet; keep feeding replies.
+    Incomplete,
+    /// The burst is complete; here's the assembled value.
+    Complete(T),
+    /// `reply` isn't part of the burst this accumulator is tracking. This
+    /// also covers replies, like `ERR_HELPNOTFOUND`, that abort the burst
+    /// instead of completing it.
+    Unexpected,
+}
+
+/// A state machine that folds a stream of [`Reply`] values into a single
+/// composed value of type [`Output`](Self::Output).
+pub trait ReplyAccumulator {
+    type Output;
+
+    /// Feeds one reply to the accumulator.
+    fn feed(&mut self, reply: &Reply) -> AccumulatorState<Self::Output>;
+}
+
+/// The text returned by a `HELP` request, assembled by [`HelpAccumulator`]
+/// from `RPL_HELPSTART`, zero or more `RPL_HELPTXT`, and `RPL_ENDOFHELP`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HelpResponse {
+    subject: String,
+    lines: Vec<String>,
+}
+
+impl HelpResponse {
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Joins [`lines`](Self::lines) with `\n`.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Folds a single `HELP` response's reply burst into a [`HelpResponse`].
+///
+/// Feed every reply received after sending `HELP` to
+/// [`feed`](ReplyAccumulator::feed) until it returns
+/// [`AccumulatorState::Complete`] or [`AccumulatorState::Unexpected`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HelpAccumulator {
+    state: State,
+}
+
+impl HelpAccumulator {
+    pub fn new() -> HelpAccumulator {
+        HelpAccumulator::default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+enum State {
+    #[default]
+    AwaitingStart,
+    InProgress {
+        subject: String,
+        lines: Vec<String>,
+    },
+    Done,
+}
+
+impl ReplyAccumulator for HelpAccumulator {
+    type Output = HelpResponse;
+
+    fn feed(&mut self, reply: &Reply) -> AccumulatorState<HelpResponse> {
+        let state = std::mem::replace(&mut self.state, State::Done);
+        let (state, result) = match (state, reply) {
+            (State::AwaitingStart, Reply::HelpStart(r)) => (
+                State::InProgress {
+                    subject: r.subject().to_owned(),
+                    lines: Vec::new(),
+                },
+                AccumulatorState::Incomplete,
+            ),
+            (State::InProgress { subject, mut lines }, Reply::HelpTxt(r))
+                if r.subject() == subject =>
+            {
+                lines.push(r.message().to_owned());
+                (
+                    State::InProgress { subject, lines },
+                    AccumulatorState::Incomplete,
+                )
+            }
+            (State::InProgress { subject, lines }, Reply::EndOfHelp(r))
+                if r.subject() == subject =>
+            {
+                (
+                    State::Done,
+                    AccumulatorState::Complete(HelpResponse { subject, lines }),
+                )
+            }
+            (_, Reply::HelpNotFound(_)) => (State::Done, AccumulatorState::Unexpected),
+            (st, _) => (st, AccumulatorState::Unexpected),
+        };
+        self.state = state;
+        result
+    }
+}
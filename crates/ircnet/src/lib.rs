@@ -1,9 +1,37 @@
-pub mod autoresponders;
+pub mod aggregator;
+pub mod batch;
+#[cfg(feature = "serde")]
+pub mod binary_message_codec;
+mod cap;
 mod client;
 pub mod codecs;
+pub mod collectors;
 pub mod commands;
 mod connect;
 mod consts;
+pub mod dcc;
+pub mod dispatcher;
+mod help_accumulator;
+pub mod logformat;
+mod nick_negotiator;
+mod reply_router;
+mod sasl;
+pub use crate::batch::{BatchReassembler, BatchUnit, Reassembled};
+#[cfg(feature = "serde")]
+pub use crate::binary_message_codec::{BinaryMessageCodec, BinaryMessageCodecError};
+pub use crate::cap::{CapBufferError, CapListBuffer, CapLsBuffer, CapNegotiator};
 pub use crate::client::*;
 pub use crate::connect::*;
 pub use crate::consts::*;
+pub use crate::help_accumulator::{
+    AccumulatorState, HelpAccumulator, HelpResponse, ReplyAccumulator,
+};
+pub use crate::nick_negotiator::{NickAction, NickNegotiator};
+pub use crate::reply_router::{ReplyRouter, ReplyScope};
+pub use crate::sasl::{
+    AuthenticatorError, AuthenticatorOutcome, BlockingSaslAuthenticator, BlockingTransport,
+    NegotiationStep, ParseSaslMechanismError, SaslFailure, SaslInput, SaslMechanism,
+    SaslNegotiator, SaslNegotiatorError, SaslOutcome, SaslSession, SaslStep, classify_reply,
+};
+#[cfg(feature = "async")]
+pub use crate::sasl::AsyncSaslAuthenticator;
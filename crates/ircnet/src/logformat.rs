@@ -0,0 +1,667 @@
+//! Human-readable IRC client-log dialects: rendering a [`Message`] as one
+//! line of a weechat/irssi/energymech-style chat log, and reading such a
+//! line back into a `Message`.
+//!
+//! This is a distinct capability from the on-wire [`crate::codecs::message`]:
+//! a client log covers one channel (or query) at a time, so the
+//! channel/query context that a wire message carries explicitly is instead
+//! implicit in *which log file* a line lives in.  A [`ClientLog`] is
+//! therefore constructed with that channel baked in, and supplies it when
+//! reconstructing PRIVMSG/NOTICE/JOIN/PART messages out of a parsed line.
+//!
+//! The three dialects differ mainly in timestamp layout and the punctuation
+//! used to decorate nicknames, both of which are configurable via
+//! [`ClientLog::with_timestamp_format`] and [`ClientLog::with_decoration`];
+//! [`ClientLog::weechat`], [`ClientLog::irssi`], and
+//! [`ClientLog::energymech`] are just constructors preloaded with each
+//! client's own conventions.
+
+use irctext::types::{Channel, MsgTarget, Nickname};
+use irctext::{ClientMessage, ClientSource, FinalParam, Message, Payload, Source};
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::Zoned;
+use std::io::{self, Write};
+use thiserror::Error;
+
+/// Converts between [`Message`]s and lines of a human-readable IRC
+/// client-log format such as weechat's, irssi's, or energymech's.
+pub trait LogFormat {
+    /// Render `msg`, timestamped as `when`, as one line (including its
+    /// trailing newline) of this format to `w`.
+    ///
+    /// Writes nothing and returns `Ok(())` if `msg` isn't a kind of event
+    /// this format logs, or if it's a PRIVMSG/NOTICE/JOIN/PART addressed to
+    /// a channel other than the one this log covers.
+    fn write_event(&self, msg: &Message, when: &Zoned, w: &mut impl Write) -> io::Result<()>;
+
+    /// Parse one line of a client log (without its trailing newline) back
+    /// into the event it records and the timestamp the line carries.
+    ///
+    /// Returns `Ok(None)` for event lines that are well-formed but don't
+    /// record an event this format round-trips into a [`Message`] (e.g. an
+    /// irssi `-!-` or energymech `***` topic change).  Lines that fit
+    /// neither the dialect's chat layout nor its event layout (such as
+    /// log-open banners) are errors.
+    fn parse_line(&self, line: &str) -> Result<Option<(Zoned, Message)>, ParseLogLineError>;
+
+    /// Convenience wrapper around [`write_event`](LogFormat::write_event)
+    /// for callers who want the rendered line (without its trailing
+    /// newline) as a `String` rather than writing it to a [`Write`] sink.
+    /// Returns `None` under the same circumstances `write_event` would
+    /// write nothing.
+    fn format_line(&self, msg: &Message, when: &Zoned) -> Option<String> {
+        let mut buf = Vec::new();
+        self.write_event(msg, when, &mut buf).ok()?;
+        let mut line = String::from_utf8(buf).ok()?;
+        if line.is_empty() {
+            return None;
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Some(line)
+    }
+}
+
+/// Which client's log conventions a [`ClientLog`] follows.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Dialect {
+    Weechat,
+    Irssi,
+    EnergyMech,
+}
+
+/// How a [`ClientLog`] decorates the nickname column of a PRIVMSG or NOTICE
+/// line, e.g. `("<", ">")` for irssi's `<nick>` or `("-", "-")` for the
+/// `-nick-` convention used for notices by all three dialects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NickDecoration {
+    pub privmsg: (&'static str, &'static str),
+    pub notice: (&'static str, &'static str),
+}
+
+impl NickDecoration {
+    fn wrap(self, kind: EventKind, nick: &str) -> String {
+        let (open, close) = match kind {
+            EventKind::PrivMsg => self.privmsg,
+            EventKind::Notice => self.notice,
+        };
+        format!("{open}{nick}{close}")
+    }
+
+    fn unwrap<'a>(self, kind: EventKind, s: &'a str) -> Option<&'a str> {
+        let (open, close) = match kind {
+            EventKind::PrivMsg => self.privmsg,
+            EventKind::Notice => self.notice,
+        };
+        s.strip_prefix(open)?.strip_suffix(close)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EventKind {
+    PrivMsg,
+    Notice,
+}
+
+/// A weechat/irssi/energymech-style human-readable log of one channel (or
+/// query), for converting captured IRC traffic to readable transcripts and
+/// for re-ingesting previously captured logs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClientLog {
+    channel: Channel,
+    dialect: Dialect,
+    timestamp_format: String,
+    decoration: NickDecoration,
+    log_date: Date,
+}
+
+impl ClientLog {
+    /// A log in weechat's default `core.log` layout: a tab-separated
+    /// `<timestamp>\t<nick>\t<text>` line, with joins/parts/quits getting
+    /// `-->`/`<--` in the nick column instead of a nickname.
+    ///
+    /// `log_date` is used to fill in the date when parsing a line whose
+    /// timestamp is time-of-day only (weechat's own default includes a
+    /// date, but [`with_timestamp_format`](ClientLog::with_timestamp_format)
+    /// may be used to configure a shorter one).
+    pub fn weechat(channel: Channel, log_date: Date) -> ClientLog {
+        ClientLog {
+            channel,
+            dialect: Dialect::Weechat,
+            timestamp_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            decoration: NickDecoration {
+                privmsg: ("", ""),
+                notice: ("-", "-"),
+            },
+            log_date,
+        }
+    }
+
+    /// A log in irssi's default layout: `<timestamp> <nick> text`, with
+    /// joins/parts/quits introduced by `-!-`.
+    pub fn irssi(channel: Channel, log_date: Date) -> ClientLog {
+        ClientLog {
+            channel,
+            dialect: Dialect::Irssi,
+            timestamp_format: "%H:%M".to_string(),
+            decoration: NickDecoration {
+                privmsg: ("<", ">"),
+                notice: ("-", "-"),
+            },
+            log_date,
+        }
+    }
+
+    /// A log in the eggdrop/energymech layout: `[<timestamp>] <nick> text`,
+    /// with joins/parts introduced by `***` and quits by `*** Signoff:`.
+    pub fn energymech(channel: Channel, log_date: Date) -> ClientLog {
+        ClientLog {
+            channel,
+            dialect: Dialect::EnergyMech,
+            timestamp_format: "%H:%M:%S".to_string(),
+            decoration: NickDecoration {
+                privmsg: ("<", ">"),
+                notice: ("-", "-"),
+            },
+            log_date,
+        }
+    }
+
+    /// Overrides the `strftime`-style timestamp format used for this log.
+    pub fn with_timestamp_format(mut self, format: impl Into<String>) -> Self {
+        self.timestamp_format = format.into();
+        self
+    }
+
+    /// Overrides how nicknames are decorated in PRIVMSG/NOTICE lines.
+    pub fn with_decoration(mut self, decoration: NickDecoration) -> Self {
+        self.decoration = decoration;
+        self
+    }
+
+    fn format_timestamp(&self, when: &Zoned) -> String {
+        jiff::fmt::strtime::format(&self.timestamp_format, when)
+            .unwrap_or_else(|_| when.to_string())
+    }
+
+    fn parse_timestamp(&self, s: &str) -> Result<Zoned, ParseLogLineError> {
+        let bdt = jiff::fmt::strtime::BrokenDownTime::parse(self.timestamp_format.as_str(), s)
+            .map_err(|_| ParseLogLineError::Timestamp)?;
+        if let Ok(zoned) = bdt.to_zoned() {
+            return Ok(zoned);
+        }
+        if let Ok(datetime) = bdt.to_datetime() {
+            return datetime
+                .to_zoned(TimeZone::UTC)
+                .map_err(|_| ParseLogLineError::Timestamp);
+        }
+        let time = bdt.to_time().map_err(|_| ParseLogLineError::Timestamp)?;
+        self.log_date
+            .to_datetime(time)
+            .to_zoned(TimeZone::UTC)
+            .map_err(|_| ParseLogLineError::Timestamp)
+    }
+
+    /// Splits a full log line into its `(timestamp, rest-of-line)` parts
+    /// according to this dialect's column layout.
+    fn split_timestamp<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        match self.dialect {
+            Dialect::Weechat => line.split_once('\t'),
+            Dialect::Irssi => line.split_once(' '),
+            Dialect::EnergyMech => {
+                let rest = line.strip_prefix('[')?;
+                let (ts, rest) = rest.split_once(']')?;
+                Some((ts, rest.strip_prefix(' ').unwrap_or(rest)))
+            }
+        }
+    }
+
+    fn event_prefix(&self) -> &'static str {
+        match self.dialect {
+            Dialect::Weechat => "",
+            Dialect::Irssi => "-!- ",
+            Dialect::EnergyMech => "*** ",
+        }
+    }
+
+    fn targets_this_channel<'a, I: IntoIterator<Item = &'a MsgTarget>>(&self, targets: I) -> bool {
+        targets
+            .into_iter()
+            .any(|t| matches!(t, MsgTarget::Channel(c) if c == &self.channel))
+    }
+
+    fn write_chat_line(
+        &self,
+        when: &Zoned,
+        kind: EventKind,
+        nick: &Nickname,
+        text: &FinalParam,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let ts = self.format_timestamp(when);
+        let who = self.decoration.wrap(kind, nick.as_str());
+        match self.dialect {
+            Dialect::Weechat => writeln!(w, "{ts}\t{who}\t{text}"),
+            Dialect::Irssi => writeln!(w, "{ts} {who} {text}"),
+            Dialect::EnergyMech => writeln!(w, "[{ts}] {who} {text}"),
+        }
+    }
+
+    fn write_join(
+        &self,
+        when: &Zoned,
+        nick: &Nickname,
+        source: Option<&Source>,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let ts = self.format_timestamp(when);
+        let (user, host) = hostmask(source);
+        match self.dialect {
+            Dialect::Weechat => writeln!(
+                w,
+                "{ts}\t-->\t{nick} ({user}@{host}) has joined {}",
+                self.channel
+            ),
+            Dialect::Irssi => writeln!(
+                w,
+                "{ts} -!- {nick} [{user}@{host}] has joined {}",
+                self.channel
+            ),
+            Dialect::EnergyMech => writeln!(
+                w,
+                "[{ts}] *** {nick} ({user}@{host}) has joined {}",
+                self.channel
+            ),
+        }
+    }
+
+    fn write_part(
+        &self,
+        when: &Zoned,
+        nick: &Nickname,
+        source: Option<&Source>,
+        reason: Option<&FinalParam>,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let ts = self.format_timestamp(when);
+        let (user, host) = hostmask(source);
+        match self.dialect {
+            Dialect::Weechat => {
+                let reason = reason.map_or(String::new(), |r| format!(" ({r})"));
+                writeln!(
+                    w,
+                    "{ts}\t<--\t{nick} ({user}@{host}) has left {}{reason}",
+                    self.channel
+                )
+            }
+            Dialect::Irssi => {
+                let reason = reason.map_or(String::new(), |r| format!(" [{r}]"));
+                writeln!(
+                    w,
+                    "{ts} -!- {nick} [{user}@{host}] has left {}{reason}",
+                    self.channel
+                )
+            }
+            Dialect::EnergyMech => {
+                let reason = reason.map_or(String::new(), |r| format!(" ({r})"));
+                writeln!(
+                    w,
+                    "[{ts}] *** {nick} ({user}@{host}) has left {}{reason}",
+                    self.channel
+                )
+            }
+        }
+    }
+
+    fn write_quit(
+        &self,
+        when: &Zoned,
+        nick: &Nickname,
+        reason: Option<&FinalParam>,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let ts = self.format_timestamp(when);
+        match self.dialect {
+            Dialect::Weechat => {
+                let reason = reason.map_or(String::new(), |r| format!(" ({r})"));
+                writeln!(w, "{ts}\t<--\t{nick} has quit{reason}")
+            }
+            Dialect::Irssi => {
+                let reason = reason.map_or(String::new(), |r| format!(" [{r}]"));
+                writeln!(w, "{ts} -!- {nick} has quit{reason}")
+            }
+            Dialect::EnergyMech => {
+                let reason = reason.map_or(String::new(), |r| format!(" ({r})"));
+                writeln!(w, "[{ts}] *** Signoff: {nick}{reason}")
+            }
+        }
+    }
+
+    fn nickname_of(source: Option<&Source>) -> Option<&Nickname> {
+        match source {
+            Some(Source::Client(cs)) => Some(&cs.nickname),
+            _ => None,
+        }
+    }
+
+    fn client_source(nick: Nickname) -> Source {
+        Source::Client(ClientSource {
+            nickname: nick,
+            user: None,
+            host: None,
+        })
+    }
+}
+
+/// Renders a client source's user/host as the `user@host` pair used in
+/// join/part lines, falling back to `*` for whichever half is unknown.
+fn hostmask(source: Option<&Source>) -> (&str, &str) {
+    match source {
+        Some(Source::Client(cs)) => (
+            cs.user.as_ref().map_or("*", |u| u.as_str()),
+            cs.host.as_deref().unwrap_or("*"),
+        ),
+        _ => ("*", "*"),
+    }
+}
+
+impl LogFormat for ClientLog {
+    fn write_event(&self, msg: &Message, when: &Zoned, w: &mut impl Write) -> io::Result<()> {
+        let Payload::ClientMessage(cmsg) = &msg.payload else {
+            return Ok(());
+        };
+        let Some(nick) = Self::nickname_of(msg.source.as_ref()) else {
+            return Ok(());
+        };
+        match cmsg {
+            ClientMessage::PrivMsg(pm) if self.targets_this_channel(pm.targets()) => {
+                self.write_chat_line(when, EventKind::PrivMsg, nick, pm.text(), w)
+            }
+            ClientMessage::Notice(n) if self.targets_this_channel(n.targets()) => {
+                self.write_chat_line(when, EventKind::Notice, nick, n.text(), w)
+            }
+            ClientMessage::Join(j) if j.channels().contains(&self.channel) => {
+                self.write_join(when, nick, msg.source.as_ref(), w)
+            }
+            ClientMessage::Part(p) if p.channels().contains(&self.channel) => {
+                self.write_part(when, nick, msg.source.as_ref(), p.reason(), w)
+            }
+            ClientMessage::Quit(q) => self.write_quit(when, nick, q.reason(), w),
+            _ => Ok(()),
+        }
+    }
+
+    fn parse_line(&self, line: &str) -> Result<Option<(Zoned, Message)>, ParseLogLineError> {
+        let (ts, rest) = self.split_timestamp(line).ok_or(ParseLogLineError::Malformed)?;
+        let when = self.parse_timestamp(ts)?;
+        let prefix = self.event_prefix();
+        let event = if !prefix.is_empty() {
+            rest.strip_prefix(prefix)
+        } else {
+            rest.strip_prefix("-->\t").or_else(|| rest.strip_prefix("<--\t"))
+        };
+        if let Some(body) = event {
+            return match self.parse_event_body(body, when) {
+                Err(ParseLogLineError::Malformed) => Ok(None),
+                r => r.map(Some),
+            };
+        }
+        self.parse_chat_body(rest, when).map(Some)
+    }
+}
+
+impl ClientLog {
+    /// Parses the `nick (user@host) has joined/left ...` (or `Signoff:
+    /// nick (reason)`) portion of a JOIN/PART/QUIT line, common to all
+    /// three dialects modulo their bracket/paren conventions.
+    fn parse_event_body(
+        &self,
+        body: &str,
+        when: Zoned,
+    ) -> Result<(Zoned, Message), ParseLogLineError> {
+        if let Some(rest) = body.strip_prefix("Signoff: ") {
+            let (nick, reason) = rest.split_once(' ').unwrap_or((rest, ""));
+            let nick = nick.parse::<Nickname>()?;
+            let reason = strip_wrapped(reason, '(', ')');
+            return self.quit_message(when, nick, reason);
+        }
+        let Some((nick, rest)) = body.split_once(' ') else {
+            return Err(ParseLogLineError::Malformed);
+        };
+        let nick = nick.parse::<Nickname>()?;
+        let rest = match rest.split_once(' ') {
+            Some((mask, after))
+                if strip_wrapped(mask, '(', ')').is_some()
+                    || strip_wrapped(mask, '[', ']').is_some() =>
+            {
+                after
+            }
+            _ => rest,
+        };
+        if rest.strip_prefix("has joined ").is_some() {
+            self.join_message(when, nick)
+        } else if let Some(rest) = rest.strip_prefix("has left ") {
+            let reason = rest
+                .split_once(' ')
+                .and_then(|(_, r)| strip_wrapped(r, '(', ')').or_else(|| strip_wrapped(r, '[', ']')));
+            self.part_message(when, nick, reason)
+        } else if rest.starts_with("has quit") {
+            let reason = rest
+                .strip_prefix("has quit")
+                .map(str::trim_start)
+                .and_then(|r| strip_wrapped(r, '(', ')').or_else(|| strip_wrapped(r, '[', ']')));
+            self.quit_message(when, nick, reason)
+        } else {
+            Err(ParseLogLineError::Malformed)
+        }
+    }
+
+    fn parse_chat_body(&self, body: &str, when: Zoned) -> Result<(Zoned, Message), ParseLogLineError> {
+        let (who, text) = match self.dialect {
+            Dialect::Weechat => body.split_once('\t').ok_or(ParseLogLineError::Malformed)?,
+            _ => body.split_once(' ').ok_or(ParseLogLineError::Malformed)?,
+        };
+        let (kind, nick) = if let Some(nick) = self.decoration.unwrap(EventKind::Notice, who) {
+            (EventKind::Notice, nick)
+        } else if let Some(nick) = self.decoration.unwrap(EventKind::PrivMsg, who) {
+            (EventKind::PrivMsg, nick)
+        } else {
+            (EventKind::PrivMsg, who)
+        };
+        let nick = nick.parse::<Nickname>()?;
+        let text = FinalParam::try_from(text.to_string())?;
+        let payload = match kind {
+            EventKind::PrivMsg => {
+                ClientMessage::from(irctext::clientmsgs::PrivMsg::new(self.channel.clone(), text))
+            }
+            EventKind::Notice => {
+                ClientMessage::from(irctext::clientmsgs::Notice::new(self.channel.clone(), text))
+            }
+        };
+        Ok((
+            when,
+            Message {
+                tags: None,
+                source: Some(Self::client_source(nick)),
+                payload: Payload::ClientMessage(payload),
+            },
+        ))
+    }
+
+    fn join_message(&self, when: Zoned, nick: Nickname) -> Result<(Zoned, Message), ParseLogLineError> {
+        Ok((
+            when,
+            Message {
+                tags: None,
+                source: Some(Self::client_source(nick)),
+                payload: Payload::ClientMessage(ClientMessage::from(
+                    irctext::clientmsgs::Join::new(self.channel.clone()),
+                )),
+            },
+        ))
+    }
+
+    fn part_message(
+        &self,
+        when: Zoned,
+        nick: Nickname,
+        reason: Option<&str>,
+    ) -> Result<(Zoned, Message), ParseLogLineError> {
+        let part = match reason {
+            Some(r) => irctext::clientmsgs::Part::new_with_reason(
+                self.channel.clone(),
+                FinalParam::try_from(r.to_string())?,
+            ),
+            None => irctext::clientmsgs::Part::new(self.channel.clone()),
+        };
+        Ok((
+            when,
+            Message {
+                tags: None,
+                source: Some(Self::client_source(nick)),
+                payload: Payload::ClientMessage(ClientMessage::from(part)),
+            },
+        ))
+    }
+
+    fn quit_message(
+        &self,
+        when: Zoned,
+        nick: Nickname,
+        reason: Option<&str>,
+    ) -> Result<(Zoned, Message), ParseLogLineError> {
+        let quit = match reason {
+            Some(r) => {
+                irctext::clientmsgs::Quit::new_with_reason(FinalParam::try_from(r.to_string())?)
+            }
+            None => irctext::clientmsgs::Quit::new(),
+        };
+        Ok((
+            when,
+            Message {
+                tags: None,
+                source: Some(Self::client_source(nick)),
+                payload: Payload::ClientMessage(ClientMessage::from(quit)),
+            },
+        ))
+    }
+}
+
+fn strip_wrapped(s: &str, open: char, close: char) -> Option<&str> {
+    s.strip_prefix(open)?.strip_suffix(close)
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ParseLogLineError {
+    #[error("line does not match the expected log format")]
+    Malformed,
+    #[error("failed to parse timestamp")]
+    Timestamp,
+    #[error("invalid nickname in log line")]
+    Nickname(#[from] irctext::types::ParseNicknameError),
+    #[error("invalid message text in log line")]
+    FinalParam(#[from] irctext::ParseFinalParamError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    fn date() -> Date {
+        Date::constant(2024, 1, 1)
+    }
+
+    fn channel() -> Channel {
+        "#ircbits".parse().unwrap()
+    }
+
+    #[test]
+    fn weechat_privmsg_round_trip() {
+        let log = ClientLog::weechat(channel(), date());
+        let msg = Message {
+            tags: None,
+            source: Some(ClientLog::client_source("jwodder".parse().unwrap())),
+            payload: Payload::ClientMessage(ClientMessage::from(
+                irctext::clientmsgs::PrivMsg::new(channel(), "hello there".parse().unwrap()),
+            )),
+        };
+        let when = "2024-01-01T12:34:56[UTC]".parse::<Zoned>().unwrap();
+        let mut buf = Vec::new();
+        log.write_event(&msg, &when, &mut buf).unwrap();
+        let line = String::from_utf8(buf).unwrap();
+        let line = line.trim_end_matches('\n');
+        assert_eq!(line, "2024-01-01 12:34:56\tjwodder\thello there");
+        let (parsed_when, parsed) = log.parse_line(line).unwrap().unwrap();
+        assert_eq!(parsed_when, when);
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn format_line_matches_write_event() {
+        let log = ClientLog::weechat(channel(), date());
+        let msg = Message {
+            tags: None,
+            source: Some(ClientLog::client_source("jwodder".parse().unwrap())),
+            payload: Payload::ClientMessage(ClientMessage::from(
+                irctext::clientmsgs::PrivMsg::new(channel(), "hello there".parse().unwrap()),
+            )),
+        };
+        let when = "2024-01-01T12:34:56[UTC]".parse::<Zoned>().unwrap();
+        assert_eq!(
+            log.format_line(&msg, &when).unwrap(),
+            "2024-01-01 12:34:56\tjwodder\thello there"
+        );
+    }
+
+    #[test]
+    fn format_line_is_none_for_unlogged_event() {
+        let log = ClientLog::weechat(channel(), date());
+        let other = "#other".parse::<Channel>().unwrap();
+        let msg = Message {
+            tags: None,
+            source: Some(ClientLog::client_source("jwodder".parse().unwrap())),
+            payload: Payload::ClientMessage(ClientMessage::from(
+                irctext::clientmsgs::PrivMsg::new(other, "hi".parse().unwrap()),
+            )),
+        };
+        let when = "2024-01-01T12:34:56[UTC]".parse::<Zoned>().unwrap();
+        assert_eq!(log.format_line(&msg, &when), None);
+    }
+
+    #[test]
+    fn irssi_notice_and_join() {
+        let log = ClientLog::irssi(channel(), date());
+        assert_eq!(
+            log.decoration.wrap(EventKind::Notice, "jwodder"),
+            "-jwodder-"
+        );
+        let line = "12:34 -!- jwodder [~jwodder@example.com] has joined #ircbits";
+        let (_, msg) = log.parse_line(line).unwrap().unwrap();
+        assert_matches!(msg, Message {
+            source: Some(Source::Client(cs)),
+            payload: Payload::ClientMessage(ClientMessage::Join(_)),
+            ..
+        } => {
+            assert_eq!(cs.nickname, "jwodder".parse().unwrap());
+        });
+    }
+
+    #[test]
+    fn energymech_quit() {
+        let log = ClientLog::energymech(channel(), date());
+        let line = "[12:34:56] *** Signoff: jwodder (Ping timeout)";
+        let (_, msg) = log.parse_line(line).unwrap().unwrap();
+        assert_matches!(msg, Message {
+            payload: Payload::ClientMessage(ClientMessage::Quit(quit)),
+            ..
+        } => {
+            assert_eq!(quit.reason().unwrap(), "Ping timeout");
+        });
+    }
+}
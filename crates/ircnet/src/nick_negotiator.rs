@@ -0,0 +1,80 @@
+//! Automatic nickname negotiation for use alongside [`Login`](crate::commands::login::Login)
+//! or any other registration flow: feed it each [`Reply`] as it arrives and
+//! it tells you whether to retry `NICK` with the next candidate, whether the
+//! current nickname was accepted, or whether every candidate has been
+//! rejected.
+
+use irctext::Reply;
+use irctext::types::Nickname;
+
+/// What a caller should do in response to [`NickNegotiator::handle_reply`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NickAction {
+    /// Send a `NICK` command requesting the given nickname.
+    SendNick(Nickname),
+    /// The reply wasn't a nickname rejection, so the current candidate
+    /// stands; there is nothing more for the negotiator to do.
+    Done,
+    /// Every candidate nickname was rejected.
+    Exhausted,
+}
+
+/// Walks an ordered list of candidate nicknames, advancing to the next one
+/// each time the server rejects the current candidate with
+/// [`NicknameInUse`](irctext::replies::NicknameInUse),
+/// [`NickCollision`](irctext::replies::NickCollision), or
+/// [`ErroneousNickname`](irctext::replies::ErroneousNickname).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NickNegotiator {
+    candidates: Vec<Nickname>,
+    index: usize,
+}
+
+impl NickNegotiator {
+    /// Creates a negotiator that tries `candidates` in order, starting with
+    /// the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `candidates` is empty.
+    pub fn new(candidates: Vec<Nickname>) -> NickNegotiator {
+        assert!(
+            !candidates.is_empty(),
+            "NickNegotiator requires at least one candidate nickname"
+        );
+        NickNegotiator {
+            candidates,
+            index: 0,
+        }
+    }
+
+    /// The nickname currently being attempted.
+    pub fn current(&self) -> &Nickname {
+        &self.candidates[self.index]
+    }
+
+    /// Examines `reply` and, if it rejects the nickname currently being
+    /// attempted, advances to the next candidate.
+    pub fn handle_reply(&mut self, reply: &Reply) -> NickAction {
+        let rejected = match reply {
+            Reply::NicknameInUse(r) => Some(r.nickname().as_str()),
+            Reply::NickCollision(r) => Some(r.nickname().as_str()),
+            Reply::ErroneousNickname(r) => Some(r.nickname()),
+            _ => None,
+        };
+        let Some(rejected) = rejected else {
+            return NickAction::Done;
+        };
+        if rejected != self.current().as_str() {
+            // The rejection doesn't concern the nickname we're currently
+            // trying (e.g. a stale reply or another client's collision);
+            // leave our state alone.
+            return NickAction::Done;
+        }
+        self.index += 1;
+        match self.candidates.get(self.index) {
+            Some(nickname) => NickAction::SendNick(nickname.clone()),
+            None => NickAction::Exhausted,
+        }
+    }
+}
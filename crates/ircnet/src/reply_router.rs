@@ -0,0 +1,94 @@
+//! A dynamic, closure-based companion to [`irctext::ReplyHandler`] for bot
+//! frameworks that want to register handlers at runtime (one per numeric
+//! code) rather than implementing a trait up front, with the option to
+//! scope a handler to replies concerning "me" or a particular channel.
+
+use irctext::types::{Channel, Nickname};
+use irctext::{Reply, ReplyParts};
+use std::collections::HashMap;
+
+/// Which replies a handler registered with [`ReplyRouter`] should receive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReplyScope {
+    /// Every reply with the registered code.
+    All,
+    /// Only replies whose parameters mention the given nickname, e.g. a
+    /// `WHOIS` result about us or an `ERR_NICKNAMEINUSE` rejecting our own
+    /// nick.
+    Mine(Nickname),
+    /// Only replies whose parameters mention the given channel.
+    Channel(Channel),
+}
+
+impl ReplyScope {
+    fn matches(&self, reply: &Reply) -> bool {
+        match self {
+            ReplyScope::All => true,
+            ReplyScope::Mine(nickname) => Self::mentions(reply, nickname.as_str()),
+            ReplyScope::Channel(channel) => Self::mentions(reply, channel.as_str()),
+        }
+    }
+
+    fn mentions(reply: &Reply, needle: &str) -> bool {
+        reply.parameters().iter().any(|p| p.as_str() == needle)
+    }
+}
+
+type Handler = Box<dyn FnMut(&Reply) + Send>;
+
+/// A registry of closures, keyed by numeric reply code, that
+/// [`ReplyRouter::dispatch`] routes incoming replies to. Codes with no
+/// registered handler fall through to the catch-all handlers registered
+/// with [`ReplyRouter::on_unhandled`].
+#[allow(missing_debug_implementations)]
+#[derive(Default)]
+pub struct ReplyRouter {
+    by_code: HashMap<u16, Vec<(ReplyScope, Handler)>>,
+    unhandled: Vec<Handler>,
+}
+
+impl ReplyRouter {
+    /// Creates an empty router.
+    pub fn new() -> ReplyRouter {
+        ReplyRouter::default()
+    }
+
+    /// Registers `handler` to be called with every reply bearing `code`
+    /// that also satisfies `scope`.
+    pub fn on_code(
+        &mut self,
+        code: u16,
+        scope: ReplyScope,
+        handler: impl FnMut(&Reply) + Send + 'static,
+    ) {
+        self.by_code
+            .entry(code)
+            .or_default()
+            .push((scope, Box::new(handler)));
+    }
+
+    /// Registers a catch-all `handler` to be called with every reply whose
+    /// code has no handler registered via [`Self::on_code`].
+    pub fn on_unhandled(&mut self, handler: impl FnMut(&Reply) + Send + 'static) {
+        self.unhandled.push(Box::new(handler));
+    }
+
+    /// Routes `reply` to every matching handler registered for its code, or
+    /// to the catch-all handlers if no handler is registered for that code.
+    pub fn dispatch(&mut self, reply: &Reply) {
+        match self.by_code.get_mut(&reply.code()) {
+            Some(handlers) => {
+                for (scope, handler) in handlers {
+                    if scope.matches(reply) {
+                        handler(reply);
+                    }
+                }
+            }
+            None => {
+                for handler in &mut self.unhandled {
+                    handler(reply);
+                }
+            }
+        }
+    }
+}
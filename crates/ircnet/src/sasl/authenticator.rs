@@ -0,0 +1,182 @@
+//! Thin I/O adapters over [`SaslSession`], for callers who'd rather not
+//! hand-roll the send/receive loop themselves.
+//!
+//! [`BlockingSaslAuthenticator`] drives the handshake synchronously over
+//! any [`BlockingTransport`]; the `async` feature adds
+//! [`AsyncSaslAuthenticator`], which does the same over any `Sink`/`Stream`
+//! pair of the kind [`Client`](crate::Client) itself is built on. Both are
+//! thin wrappers around the same [`SaslSession`] state machine, so they
+//! classify server messages and report success/failure identically --
+//! only the transport differs.
+
+use super::{SaslError, SaslFailure, SaslInput, SaslMechanism, SaslSession, SaslStep};
+use irctext::types::Nickname;
+use irctext::{ClientMessage, Message, Payload};
+use thiserror::Error;
+
+/// The terminal, transport-independent outcome of a completed SASL
+/// authentication attempt.
+#[derive(Debug)]
+pub enum AuthenticatorOutcome {
+    /// SASL succeeded. `account`, if known, names the authenticated
+    /// account (see [`SaslStep::Authenticated`]).
+    Authenticated { account: Option<String> },
+}
+
+/// Why a [`BlockingSaslAuthenticator`] or `AsyncSaslAuthenticator` failed
+/// to authenticate. `E` is the underlying transport's error type.
+#[derive(Debug, Error)]
+pub enum AuthenticatorError<E> {
+    #[error("transport error")]
+    Transport(#[source] E),
+    #[error("connection closed before SASL completed")]
+    ConnectionClosed,
+    #[error("failed to start SASL session")]
+    Start(#[source] SaslError),
+    #[error("SASL failed: {0:?}")]
+    Sasl(SaslFailure),
+}
+
+/// Extracts the [`SaslInput`] relevant to a [`SaslSession`] from an
+/// incoming message, if any; messages unrelated to SASL (channel traffic
+/// received before registration completes, PING, etc.) are ignored.
+fn classify(msg: &Message) -> Option<SaslInput> {
+    match &msg.payload {
+        Payload::ClientMessage(ClientMessage::Authenticate(auth)) => {
+            Some(SaslInput::Authenticate(auth.clone()))
+        }
+        Payload::Reply(reply) => Some(SaslInput::Reply(reply.clone())),
+        Payload::ClientMessage(_) => None,
+    }
+}
+
+/// A blocking transport capable of exchanging [`Message`]s with a server,
+/// for use with [`BlockingSaslAuthenticator`].
+pub trait BlockingTransport {
+    type Error;
+
+    fn send(&mut self, msg: ClientMessage) -> Result<(), Self::Error>;
+
+    /// Returns `Ok(None)` once the connection has been closed.
+    fn recv(&mut self) -> Result<Option<Message>, Self::Error>;
+}
+
+/// Drives a [`SaslSession`] to completion over a [`BlockingTransport`].
+pub struct BlockingSaslAuthenticator;
+
+impl BlockingSaslAuthenticator {
+    /// Runs the SASL handshake for `mechanism` to completion, sending and
+    /// receiving messages over `transport` as needed, and returns the
+    /// terminal outcome.
+    ///
+    /// See [`SaslMechanism::new_flow`] for the meaning of `authzid`,
+    /// `password`, and `channel_binding`.
+    pub fn authenticate<T: BlockingTransport>(
+        transport: &mut T,
+        mechanism: SaslMechanism,
+        authzid: Option<&str>,
+        nickname: &Nickname,
+        password: &str,
+        channel_binding: Option<&[u8]>,
+    ) -> Result<AuthenticatorOutcome, AuthenticatorError<T::Error>> {
+        let mut session = SaslSession::new(mechanism, authzid, nickname, password, channel_binding)
+            .map_err(AuthenticatorError::Start)?;
+        for msg in session.take_pending() {
+            transport
+                .send(ClientMessage::from(msg))
+                .map_err(AuthenticatorError::Transport)?;
+        }
+        loop {
+            let Some(msg) = transport.recv().map_err(AuthenticatorError::Transport)? else {
+                return Err(AuthenticatorError::ConnectionClosed);
+            };
+            let Some(input) = classify(&msg) else {
+                continue;
+            };
+            match session.step(input) {
+                SaslStep::Send(msgs) => {
+                    for m in msgs {
+                        transport
+                            .send(ClientMessage::from(m))
+                            .map_err(AuthenticatorError::Transport)?;
+                    }
+                }
+                SaslStep::Authenticated { account } => {
+                    return Ok(AuthenticatorOutcome::Authenticated { account });
+                }
+                SaslStep::Failed(failure) => return Err(AuthenticatorError::Sasl(failure)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::AsyncSaslAuthenticator;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{classify, AuthenticatorError, AuthenticatorOutcome};
+    use crate::sasl::{SaslMechanism, SaslSession, SaslStep};
+    use futures_util::{Sink, SinkExt, Stream, TryStreamExt};
+    use irctext::types::Nickname;
+    use irctext::{ClientMessage, Message};
+
+    /// The `async` equivalent of
+    /// [`BlockingSaslAuthenticator`](super::BlockingSaslAuthenticator),
+    /// driving a [`SaslSession`] to completion over any `Sink`/`Stream` of
+    /// [`ClientMessage`]s/[`Message`]s sharing an error type -- the same
+    /// shape [`Client`](crate::Client)'s own connection is built on.
+    pub struct AsyncSaslAuthenticator;
+
+    impl AsyncSaslAuthenticator {
+        /// See [`BlockingSaslAuthenticator`](super::BlockingSaslAuthenticator)'s
+        /// method of the same name.
+        pub async fn authenticate<T, E>(
+            transport: &mut T,
+            mechanism: SaslMechanism,
+            authzid: Option<&str>,
+            nickname: &Nickname,
+            password: &str,
+            channel_binding: Option<&[u8]>,
+        ) -> Result<AuthenticatorOutcome, AuthenticatorError<E>>
+        where
+            T: Sink<ClientMessage, Error = E> + Stream<Item = Result<Message, E>> + Unpin,
+        {
+            let mut session =
+                SaslSession::new(mechanism, authzid, nickname, password, channel_binding)
+                    .map_err(AuthenticatorError::Start)?;
+            for msg in session.take_pending() {
+                transport
+                    .send(ClientMessage::from(msg))
+                    .await
+                    .map_err(AuthenticatorError::Transport)?;
+            }
+            loop {
+                let Some(msg) = transport
+                    .try_next()
+                    .await
+                    .map_err(AuthenticatorError::Transport)?
+                else {
+                    return Err(AuthenticatorError::ConnectionClosed);
+                };
+                let Some(input) = classify(&msg) else {
+                    continue;
+                };
+                match session.step(input) {
+                    SaslStep::Send(msgs) => {
+                        for m in msgs {
+                            transport
+                                .send(ClientMessage::from(m))
+                                .await
+                                .map_err(AuthenticatorError::Transport)?;
+                        }
+                    }
+                    SaslStep::Authenticated { account } => {
+                        return Ok(AuthenticatorOutcome::Authenticated { account });
+                    }
+                    SaslStep::Failed(failure) => return Err(AuthenticatorError::Sasl(failure)),
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,22 @@
+mod authenticator;
+mod external;
+mod negotiator;
 mod plain;
 mod scram;
+mod session;
+pub use self::authenticator::{
+    AuthenticatorError, AuthenticatorOutcome, BlockingSaslAuthenticator, BlockingTransport,
+};
+#[cfg(feature = "async")]
+pub use self::authenticator::AsyncSaslAuthenticator;
+pub use self::external::ExternalSasl;
+pub use self::negotiator::{NegotiationStep, SaslNegotiator, SaslNegotiatorError};
 pub use self::plain::PlainSasl;
 pub use self::scram::*;
+pub use self::session::{SaslFailure, SaslInput, SaslSession, SaslStep};
+use bytes::Bytes;
 use enum_dispatch::enum_dispatch;
-use irctext::{clientmsgs::Authenticate, types::Nickname};
+use irctext::{Reply, clientmsgs::Authenticate, types::Nickname};
 use thiserror::Error;
 
 /// A trait for sans IO state machines for authenticating with an IRC server
@@ -11,32 +24,28 @@ use thiserror::Error;
 ///
 /// A `SaslFlow` is intended to be used as follows:
 ///
-/// - The constructor for a `SaslFlow` value should return the new object
-///   alongside an `Authenticate` message.  Send this message to the server.
+/// - Call `get_output()` and send the returned messages to the server.
 ///
-/// - Whenever a message is received from the server:
+/// - Whenever an `AUTHENTICATE` message is received from the server, pass it
+///   to `handle_message()`, then call `get_output()` again and send the
+///   returned messages (if any) to the server.
 ///
-///     - If the message is an `AUTHENTICATE` command, pass it to
-///       `handle_message()`.
+///     - If `handle_message()` returns an error, then SASL has failed and the
+///       `SaslFlow` object should be discarded without calling any further
+///       methods on it.
 ///
-///         - If `Ok(msgs)` is returned, send `msgs` to the server, then call
-///           `is_done()`.  If it returns `true`, the `SaslFlow` has done all
-///           it can, and the object should be discarded without calling any
-///           further methods.  Success of the SASL operation should then be
-///           judged based on the replies returned by the server.
+///     - Afterwards, call `is_done()`.  If it returns `true`, the `SaslFlow`
+///       has done all it can, and the object should be discarded without
+///       calling any further methods.  Success of the SASL operation should
+///       then be judged based on the replies returned by the server.
 ///
-///         - If an error is returned, then SASL has failed and the `SaslFlow`
-///           object should be discarded without calling any further methods on
-///           it.
-///
-///     - If the message is anything else, it should be handled outside of the
-///       `SaslFlow`.  Error replies relating to the SASL process should result
-///       in the `SaslFlow` object being discarded.  Client messages other than
-///       `Authenticate` should not normally be received while SASL
-///       authentication is in progress.
+/// Messages other than `AUTHENTICATE` should be handled outside of the
+/// `SaslFlow`.  Error replies relating to the SASL process should result in
+/// the `SaslFlow` object being discarded.
 #[enum_dispatch]
 pub trait SaslFlow {
-    fn handle_message(&mut self, msg: &Authenticate) -> Result<Vec<Authenticate>, SaslError>;
+    fn handle_message(&mut self, msg: Authenticate) -> Result<(), SaslError>;
+    fn get_output(&mut self) -> Vec<Authenticate>;
     fn is_done(&self) -> bool;
 }
 
@@ -44,6 +53,7 @@ pub trait SaslFlow {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SaslMachine {
     Plain(PlainSasl),
+    External(ExternalSasl),
     Scram(ScramSasl),
 }
 
@@ -62,12 +72,26 @@ pub enum SaslMachine {
 pub enum SaslMechanism {
     #[strum(to_string = "PLAIN")]
     Plain,
+    #[strum(to_string = "EXTERNAL")]
+    External,
     #[strum(to_string = "SCRAM-SHA-1")]
     ScramSha1,
+    /// Like [`SaslMechanism::ScramSha1`], but additionally binds the
+    /// exchange to the TLS channel via RFC 5929 `tls-server-end-point`
+    /// channel binding.  Requires channel-binding data to be passed to
+    /// [`SaslMechanism::new_flow`].
+    #[strum(to_string = "SCRAM-SHA-1-PLUS")]
+    ScramSha1Plus,
     #[strum(to_string = "SCRAM-SHA-256")]
     ScramSha256,
+    /// See [`SaslMechanism::ScramSha1Plus`].
+    #[strum(to_string = "SCRAM-SHA-256-PLUS")]
+    ScramSha256Plus,
     #[strum(to_string = "SCRAM-SHA-512")]
     ScramSha512,
+    /// See [`SaslMechanism::ScramSha1Plus`].
+    #[strum(to_string = "SCRAM-SHA-512-PLUS")]
+    ScramSha512Plus,
 }
 
 impl SaslMechanism {
@@ -75,29 +99,92 @@ impl SaslMechanism {
         <SaslMechanism as strum::IntoEnumIterator>::iter()
     }
 
+    /// Builds the sub-state-machine for authenticating via this mechanism.
+    ///
+    /// `authzid`, if given, is the authorization identity to request;
+    /// currently only consulted by [`SaslMechanism::Plain`], since the other
+    /// mechanisms don't distinguish it from the authentication identity (or,
+    /// for [`SaslMechanism::External`], have their own convention — see
+    /// below).
+    ///
+    /// `password` is used as-is by mechanisms that need one; for
+    /// [`SaslMechanism::External`], which authenticates via a TLS client
+    /// certificate rather than a password, it is instead taken as the
+    /// authzid to request (pass an empty string to let the server derive it
+    /// from the certificate).
+    ///
+    /// `channel_binding`, if available, should be the connection's RFC 5929
+    /// `tls-server-end-point` data (see
+    /// [`Client::channel_binding`](crate::client::Client::channel_binding)).
+    /// It's required for the `-PLUS` SCRAM mechanisms; for the plain SCRAM
+    /// mechanisms, it's still consulted so the client can advertise
+    /// downgrade-attack protection (gs2-cbind-flag `y`) if it's available
+    /// but just not being used for this particular mechanism.
     pub fn new_flow(
         self,
+        authzid: Option<&str>,
         nickname: &Nickname,
         password: &str,
-    ) -> Result<(SaslMachine, Authenticate), SaslError> {
-        match self {
-            SaslMechanism::Plain => {
-                let (machine, msg1) = PlainSasl::new(nickname, password);
-                Ok((machine.into(), msg1))
-            }
+        channel_binding: Option<&[u8]>,
+    ) -> Result<(SaslMachine, Vec<Authenticate>), SaslError> {
+        let cb = channel_binding
+            .map(|b| ChannelBinding::TlsServerEndPoint(Bytes::copy_from_slice(b)))
+            .unwrap_or(ChannelBinding::None);
+        let mut machine: SaslMachine = match self {
+            SaslMechanism::Plain => PlainSasl::new(authzid, nickname, password).into(),
+            SaslMechanism::External => ExternalSasl::new(password).into(),
             SaslMechanism::ScramSha1 => {
-                let (machine, msg1) = ScramSasl::new(nickname, password, HashAlgo::Sha1)?;
-                Ok((machine.into(), msg1))
+                ScramSasl::new(nickname, password, HashAlgo::Sha1, false, cb)?.into()
+            }
+            SaslMechanism::ScramSha1Plus => {
+                ScramSasl::new(nickname, password, HashAlgo::Sha1, true, cb)?.into()
             }
             SaslMechanism::ScramSha256 => {
-                let (machine, msg1) = ScramSasl::new(nickname, password, HashAlgo::Sha256)?;
-                Ok((machine.into(), msg1))
+                ScramSasl::new(nickname, password, HashAlgo::Sha256, false, cb)?.into()
+            }
+            SaslMechanism::ScramSha256Plus => {
+                ScramSasl::new(nickname, password, HashAlgo::Sha256, true, cb)?.into()
             }
             SaslMechanism::ScramSha512 => {
-                let (machine, msg1) = ScramSasl::new(nickname, password, HashAlgo::Sha512)?;
-                Ok((machine.into(), msg1))
+                ScramSasl::new(nickname, password, HashAlgo::Sha512, false, cb)?.into()
             }
-        }
+            SaslMechanism::ScramSha512Plus => {
+                ScramSasl::new(nickname, password, HashAlgo::Sha512, true, cb)?.into()
+            }
+        };
+        let msgs = machine.get_output();
+        Ok((machine, msgs))
+    }
+}
+
+/// The terminal outcome of a SASL exchange, as signaled by the numeric
+/// replies this crate's sub-state-machines ([`SaslFlow`]) don't themselves
+/// watch for, since they only speak `AUTHENTICATE`. Pass every reply
+/// received while a [`SaslMachine`] is in flight to [`classify_reply`] to
+/// detect one of these.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SaslOutcome {
+    /// `RPL_SASLSUCCESS` or `RPL_LOGGEDIN`; `account` is `Some` only for the
+    /// latter, which names the authenticated account.
+    Authenticated { account: Option<String> },
+    /// `ERR_SASLFAIL` or `ERR_SASLTOOLONG`.
+    Failed,
+    /// `ERR_NICKLOCKED`: the requested nickname is locked by services and
+    /// can't be used to authenticate.
+    Aborted,
+}
+
+/// Classifies `reply` as a [`SaslOutcome`], or returns `None` if it isn't
+/// one of the replies that terminates a SASL exchange.
+pub fn classify_reply(reply: &Reply) -> Option<SaslOutcome> {
+    match reply {
+        Reply::LoggedIn(r) => Some(SaslOutcome::Authenticated {
+            account: Some(r.account().to_string()),
+        }),
+        Reply::SaslSuccess(_) => Some(SaslOutcome::Authenticated { account: None }),
+        Reply::SaslFail(_) | Reply::SaslTooLong(_) => Some(SaslOutcome::Failed),
+        Reply::NickLocked(_) => Some(SaslOutcome::Aborted),
+        _ => None,
     }
 }
 
@@ -164,7 +251,9 @@ pub enum SaslError {
     #[error("mismatch between signatures computed by client and server")]
     Signature,
     #[error("server returned error: {0:?}")]
-    Server(String),
+    Server(ServerErrorValue),
     #[error("failed to parse message from server")]
     Parse,
+    #[error("channel-binding data is required for a -PLUS SCRAM mechanism but wasn't available (connection not over TLS?)")]
+    ChannelBindingRequired,
 }
@@ -0,0 +1,142 @@
+//! [`SaslNegotiator`] wraps a [`SaslSession`] with a client-side mechanism
+//! preference list, so that a server rejecting the attempted mechanism --
+//! via `ERR_SASLFAIL` or `ERR_SASLMECHS` -- doesn't have to be treated as a
+//! terminal failure: the negotiator just restarts the exchange with the
+//! next mutually-supported mechanism, only giving up once the preference
+//! list (intersected with whatever the server last advertised) is
+//! exhausted.
+
+use super::{SaslError, SaslFailure, SaslInput, SaslMechanism, SaslSession, SaslStep};
+use irctext::clientmsgs::Authenticate;
+use irctext::types::Nickname;
+use std::collections::VecDeque;
+
+/// The outcome of feeding one [`SaslInput`] to a [`SaslNegotiator`].
+#[derive(Debug)]
+pub enum NegotiationStep {
+    /// Send these messages to the server and keep driving the negotiation.
+    Send(Vec<Authenticate>),
+    /// SASL succeeded with [`SaslNegotiator::current`].
+    Authenticated { account: Option<String> },
+    /// No mechanism in the preference list (intersected with what the
+    /// server would accept) succeeded; see [`SaslNegotiatorError`] for why
+    /// the last attempt failed and whether any were left to try.
+    Failed(SaslNegotiatorError),
+}
+
+/// Why a [`SaslNegotiator`] ended in [`NegotiationStep::Failed`].
+#[derive(Debug)]
+pub enum SaslNegotiatorError {
+    /// Every mechanism in the preference list was tried (or ruled out by
+    /// the server's `ERR_SASLMECHS` list) without success.  `last` is the
+    /// failure that exhausted the list.
+    Exhausted { last: Box<SaslFailure> },
+    /// The locally-supported preference list didn't overlap at all with
+    /// the mechanisms the server advertises.
+    NoSupportedMechanism,
+    /// Starting a [`SaslSession`] for a candidate mechanism failed locally
+    /// (e.g. SCRAM username/password preparation).
+    Local(SaslError),
+}
+
+enum State {
+    InProgress(SaslSession),
+    Done,
+}
+
+/// Drives a SASL exchange through a client's ordered mechanism preference
+/// list, falling back to the next candidate whenever the server rejects
+/// the one currently being attempted.
+pub struct SaslNegotiator<'a> {
+    state: State,
+    current: SaslMechanism,
+    queue: VecDeque<SaslMechanism>,
+    authzid: Option<&'a str>,
+    nickname: &'a Nickname,
+    password: &'a str,
+    channel_binding: Option<&'a [u8]>,
+}
+
+impl<'a> SaslNegotiator<'a> {
+    /// Starts negotiation, attempting `preferences[0]` first.
+    ///
+    /// See [`SaslMechanism::new_flow`] for the meaning of `authzid`,
+    /// `password`, and `channel_binding`.
+    pub fn new(
+        preferences: &[SaslMechanism],
+        authzid: Option<&'a str>,
+        nickname: &'a Nickname,
+        password: &'a str,
+        channel_binding: Option<&'a [u8]>,
+    ) -> Result<SaslNegotiator<'a>, SaslNegotiatorError> {
+        let mut queue = preferences.iter().copied().collect::<VecDeque<_>>();
+        let Some(current) = queue.pop_front() else {
+            return Err(SaslNegotiatorError::NoSupportedMechanism);
+        };
+        let session = SaslSession::new(current, authzid, nickname, password, channel_binding)
+            .map_err(SaslNegotiatorError::Local)?;
+        Ok(SaslNegotiator {
+            state: State::InProgress(session),
+            current,
+            queue,
+            authzid,
+            nickname,
+            password,
+            channel_binding,
+        })
+    }
+
+    /// The mechanism currently being attempted.
+    pub fn current(&self) -> SaslMechanism {
+        self.current
+    }
+
+    /// Feeds one event to the negotiator, returning the next messages to
+    /// send or a terminal outcome.
+    ///
+    /// SHOULD NOT be called again once a [`NegotiationStep::Authenticated`]
+    /// or [`NegotiationStep::Failed`] has been returned.
+    pub fn step(&mut self, input: SaslInput) -> NegotiationStep {
+        let State::InProgress(session) = &mut self.state else {
+            return NegotiationStep::Send(Vec::new());
+        };
+        match session.step(input) {
+            SaslStep::Send(msgs) => NegotiationStep::Send(msgs),
+            SaslStep::Authenticated { account } => {
+                self.state = State::Done;
+                NegotiationStep::Authenticated { account }
+            }
+            SaslStep::Failed(failure) => self.fall_back(failure),
+        }
+    }
+
+    fn fall_back(&mut self, failure: SaslFailure) -> NegotiationStep {
+        if let SaslFailure::UnsupportedMechanism { ref available, .. } = failure {
+            self.queue.retain(|m| available.contains(m));
+        }
+        let Some(next) = self.queue.pop_front() else {
+            self.state = State::Done;
+            return NegotiationStep::Failed(SaslNegotiatorError::Exhausted {
+                last: Box::new(failure),
+            });
+        };
+        self.current = next;
+        match SaslSession::new(
+            next,
+            self.authzid,
+            self.nickname,
+            self.password,
+            self.channel_binding,
+        ) {
+            Ok(mut session) => {
+                let msgs = session.take_pending();
+                self.state = State::InProgress(session);
+                NegotiationStep::Send(msgs)
+            }
+            Err(e) => {
+                self.state = State::Done;
+                NegotiationStep::Failed(SaslNegotiatorError::Local(e))
+            }
+        }
+    }
+}
@@ -12,12 +12,18 @@ pub struct PlainSasl {
 }
 
 impl PlainSasl {
-    pub fn new(nickname: &Nickname, password: &str) -> PlainSasl {
+    /// `authzid`, if given, is sent as the authorization identity; if
+    /// `None`, `nickname` is sent for both the authorization and
+    /// authentication identities, letting the server derive the former from
+    /// the latter.
+    pub fn new(authzid: Option<&str>, nickname: &Nickname, password: &str) -> PlainSasl {
         let Ok(plain) = "PLAIN".parse::<TrailingParam>() else {
             unreachable!(r#""PLAIN" should be valid trailing param"#);
         };
         let mech_msg = Authenticate::new(plain);
-        let auth_msgs = Authenticate::new_plain_sasl(nickname, nickname, password);
+        let nickname = nickname.as_str();
+        let auth_msgs =
+            Authenticate::new_plain_sasl(authzid.unwrap_or(nickname), nickname, password);
         PlainSasl {
             state: State::Start {
                 mech_msg,
@@ -102,7 +108,7 @@ mod tests {
 
     #[test]
     fn login() {
-        let mut flow = PlainSasl::new(&"jwodder".parse::<Nickname>().unwrap(), "hunter2");
+        let mut flow = PlainSasl::new(None, &"jwodder".parse::<Nickname>().unwrap(), "hunter2");
         let outgoing = flow
             .get_output()
             .into_iter()
@@ -120,4 +126,22 @@ mod tests {
         assert_eq!(outgoing, ["AUTHENTICATE :andvZGRlcgBqd29kZGVyAGh1bnRlcjI="]);
         assert!(flow.is_done());
     }
+
+    #[test]
+    fn login_with_distinct_authzid() {
+        let mut flow = PlainSasl::new(
+            Some("admin"),
+            &"jwodder".parse::<Nickname>().unwrap(),
+            "hunter2",
+        );
+        flow.get_output();
+        assert!(flow.handle_message(Authenticate::new_empty()).is_ok());
+        let outgoing = flow
+            .get_output()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, ["AUTHENTICATE :YWRtaW4AandvZGRlcgBodW50ZXIy"]);
+        assert!(flow.is_done());
+    }
 }
@@ -16,7 +16,7 @@ use rand::{
 };
 use replace_with::replace_with_and_return;
 use sha1::{Digest as _, Sha1};
-use sha2::Sha512;
+use sha2::{Sha256, Sha512};
 use std::fmt;
 use thiserror::Error;
 
@@ -25,20 +25,26 @@ const CLIENT_NONCE_LENGTH: usize = 24;
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum HashAlgo {
     Sha1,
+    Sha256,
     Sha512,
 }
 
 impl HashAlgo {
-    fn mechanism(self) -> SaslMechanism {
-        match self {
-            HashAlgo::Sha1 => SaslMechanism::ScramSha1,
-            HashAlgo::Sha512 => SaslMechanism::ScramSha512,
+    fn mechanism(self, channel_binding: bool) -> SaslMechanism {
+        match (self, channel_binding) {
+            (HashAlgo::Sha1, false) => SaslMechanism::ScramSha1,
+            (HashAlgo::Sha1, true) => SaslMechanism::ScramSha1Plus,
+            (HashAlgo::Sha256, false) => SaslMechanism::ScramSha256,
+            (HashAlgo::Sha256, true) => SaslMechanism::ScramSha256Plus,
+            (HashAlgo::Sha512, false) => SaslMechanism::ScramSha512,
+            (HashAlgo::Sha512, true) => SaslMechanism::ScramSha512Plus,
         }
     }
 
     fn hash(self, bs: &[u8]) -> Bytes {
         match self {
             HashAlgo::Sha1 => Bytes::from_iter(Sha1::digest(bs)),
+            HashAlgo::Sha256 => Bytes::from_iter(Sha256::digest(bs)),
             HashAlgo::Sha512 => Bytes::from_iter(Sha512::digest(bs)),
         }
     }
@@ -51,6 +57,12 @@ impl HashAlgo {
                 mac.update(s);
                 Bytes::from_iter(mac.finalize().into_bytes())
             }
+            HashAlgo::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key)
+                    .expect("any key length should be accepted");
+                mac.update(s);
+                Bytes::from_iter(mac.finalize().into_bytes())
+            }
             HashAlgo::Sha512 => {
                 let mut mac =
                     Hmac::<Sha512>::new_from_slice(key).expect("any key length should be accepted");
@@ -64,23 +76,73 @@ impl HashAlgo {
     fn iter_hash(self, s: &[u8], salt: &[u8], i: u32) -> Bytes {
         match self {
             HashAlgo::Sha1 => Bytes::from_iter(pbkdf2_hmac_array::<Sha1, 20>(s, salt, i)),
+            HashAlgo::Sha256 => Bytes::from_iter(pbkdf2_hmac_array::<Sha256, 32>(s, salt, i)),
             HashAlgo::Sha512 => Bytes::from_iter(pbkdf2_hmac_array::<Sha512, 64>(s, salt, i)),
         }
     }
 }
 
+/// The RFC 5929 channel-binding data to bind (or offer to bind) a
+/// `-PLUS` SCRAM exchange to, supplied by the caller from whatever its TLS
+/// stack makes available.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChannelBinding {
+    /// No channel-binding data is available for this connection.
+    None,
+    /// The `tls-server-end-point` binding type: a hash of the server's TLS
+    /// certificate.
+    TlsServerEndPoint(Bytes),
+    /// The `tls-unique` binding type: the first TLS Finished message of the
+    /// session.
+    TlsUnique(Bytes),
+}
+
+impl ChannelBinding {
+    fn is_available(&self) -> bool {
+        !matches!(self, ChannelBinding::None)
+    }
+
+    fn cbind_name(&self) -> Option<&'static str> {
+        match self {
+            ChannelBinding::None => None,
+            ChannelBinding::TlsServerEndPoint(_) => Some("tls-server-end-point"),
+            ChannelBinding::TlsUnique(_) => Some("tls-unique"),
+        }
+    }
+
+    fn data(&self) -> Option<&Bytes> {
+        match self {
+            ChannelBinding::None => None,
+            ChannelBinding::TlsServerEndPoint(b) | ChannelBinding::TlsUnique(b) => Some(b),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ScramSasl {
     state: State,
 }
 
 impl ScramSasl {
+    /// `bind` requests that the exchange actually be bound to
+    /// `channel_binding`, negotiating the `-PLUS` variant of `hash` instead
+    /// of the plain one; it requires `channel_binding` to not be
+    /// [`ChannelBinding::None`]. If `bind` is `false` but `channel_binding`
+    /// is available anyway, the client still advertises that it supports
+    /// channel binding (gs2-cbind-flag `y` rather than `n`), which lets the
+    /// server detect if a man-in-the-middle stripped the `-PLUS`
+    /// mechanisms from its advertised list.
     pub fn new(
         nickname: &Nickname,
         password: &str,
         hash: HashAlgo,
+        bind: bool,
+        channel_binding: ChannelBinding,
     ) -> Result<ScramSasl, SaslError> {
-        let Ok(mech) = hash.mechanism().as_ref().parse::<TrailingParam>() else {
+        if bind && !channel_binding.is_available() {
+            return Err(SaslError::ChannelBindingRequired);
+        }
+        let Ok(mech) = hash.mechanism(bind).as_ref().parse::<TrailingParam>() else {
             unreachable!("SaslMechanism strings should be valid trailing params");
         };
         let mech_msg = Authenticate::new(mech);
@@ -95,6 +157,8 @@ impl ScramSasl {
                 authzid: nickname.clone(),
                 username,
                 password,
+                bind,
+                channel_binding,
             }),
         })
     }
@@ -150,6 +214,8 @@ struct Start {
     authzid: AuthzId,
     username: Username,
     password: Password,
+    bind: bool,
+    channel_binding: ChannelBinding,
 }
 
 impl ScramState for Start {
@@ -166,6 +232,8 @@ impl ScramState for Start {
                 authzid: self.authzid,
                 username: self.username,
                 password: self.password,
+                bind: self.bind,
+                channel_binding: self.channel_binding,
             }
             .into(),
         )
@@ -183,6 +251,8 @@ struct AwaitingPlus {
     authzid: AuthzId,
     username: Username,
     password: Password,
+    bind: bool,
+    channel_binding: ChannelBinding,
 }
 
 impl ScramState for AwaitingPlus {
@@ -194,6 +264,8 @@ impl ScramState for AwaitingPlus {
                 authzid: self.authzid,
                 username: self.username,
                 password: self.password,
+                bind: self.bind,
+                channel_binding: self.channel_binding,
             }
             .into())
         } else {
@@ -221,6 +293,8 @@ struct GotPlus {
     authzid: AuthzId,
     username: Username,
     password: Password,
+    bind: bool,
+    channel_binding: ChannelBinding,
 }
 
 impl ScramState for GotPlus {
@@ -233,6 +307,8 @@ impl ScramState for GotPlus {
             authzid: &self.authzid,
             username: &self.username,
             nonce: &self.nonce,
+            bind: self.bind,
+            channel_binding: &self.channel_binding,
         }
         .to_auth_msgs();
         (
@@ -243,6 +319,8 @@ impl ScramState for GotPlus {
                 authzid: self.authzid,
                 username: self.username,
                 password: self.password,
+                bind: self.bind,
+                channel_binding: self.channel_binding,
                 input: String::new(),
             }
             .into(),
@@ -261,6 +339,8 @@ struct AwaitingServerFirstMsg {
     authzid: AuthzId,
     username: Username,
     password: Password,
+    bind: bool,
+    channel_binding: ChannelBinding,
     /// Undecoded base 64 formed by concatenating the payloads of the
     /// Authenticate messages received so far
     input: String,
@@ -268,67 +348,61 @@ struct AwaitingServerFirstMsg {
 
 impl ScramState for AwaitingServerFirstMsg {
     fn handle_message(mut self, msg: Authenticate) -> Result<State, SaslError> {
-        let payload = msg.parameter().as_str();
-        if payload != "+" {
-            self.input.push_str(payload);
-        }
-        if payload.len() < 400 {
-            let bs = STANDARD.decode(&self.input)?;
-            let s = std::str::from_utf8(&bs)?;
-            let server_first = s.parse::<ServerFirstMessage>()?;
-
-            // AuthMessage     := client-first-message-bare + "," +
-            //                        server-first-message + "," +
-            //                        client-final-message-without-proof
-            //
-            // client-first-message-bare = username "," nonce
-            //
-            // server-first-message =
-            //       [reserved-mext ","] nonce "," salt ","
-            //       iteration-count ["," extensions]
-            //
-            // client-final-message-without-proof =
-            //       channel-binding "," nonce
-            //
-            // channel-binding = "c=" base64
-            //       ;; base64 encoding of cbind-input.
-            //
-            // cbind-input   = gs2-header [ cbind-data ]
-            //       ;; cbind-data MUST be present for
-            //       ;; gs2-cbind-flag of "p" and MUST be absent
-            //       ;; for "y" or "n".
-
-            let client_nonce = self.nonce;
-            let final_nonce = server_first.nonce;
-            if !final_nonce.starts_with(&client_nonce) {
-                return Err(SaslError::Nonce);
-            }
-            let cbind_input = format!("n,a={},", Gs2Escaped(&self.authzid));
-            let auth_message = format!(
-                "n={username},r={client_nonce},{s},c={binding},r={final_nonce}",
-                username = Gs2Escaped(self.username.as_str()),
-                binding = STANDARD.encode(&cbind_input),
-            );
-            let Computation {
-                client_proof,
-                server_signature,
-            } = compute_scram(
-                self.hash,
-                &self.password,
-                &server_first.salt,
-                server_first.iteration_count,
-                &auth_message,
-            );
-            Ok(GotServerFirstMsg {
-                authzid: self.authzid,
-                nonce: final_nonce,
-                client_proof,
-                server_signature,
-            }
-            .into())
-        } else {
-            Ok(self.into())
+        let Some(bs) = Authenticate::reassemble(&mut self.input, &msg)? else {
+            return Ok(self.into());
+        };
+        let s = std::str::from_utf8(&bs)?;
+        let server_first = s.parse::<ServerFirstMessage>()?;
+
+        // AuthMessage     := client-first-message-bare + "," +
+        //                        server-first-message + "," +
+        //                        client-final-message-without-proof
+        //
+        // client-first-message-bare = username "," nonce
+        //
+        // server-first-message =
+        //       [reserved-mext ","] nonce "," salt ","
+        //       iteration-count ["," extensions]
+        //
+        // client-final-message-without-proof =
+        //       channel-binding "," nonce
+        //
+        // channel-binding = "c=" base64
+        //       ;; base64 encoding of cbind-input.
+        //
+        // cbind-input   = gs2-header [ cbind-data ]
+        //       ;; cbind-data MUST be present for
+        //       ;; gs2-cbind-flag of "p" and MUST be absent
+        //       ;; for "y" or "n".
+
+        let client_nonce = self.nonce;
+        let final_nonce = server_first.nonce;
+        if !final_nonce.starts_with(&client_nonce) {
+            return Err(SaslError::Nonce);
         }
+        let cbind_input = cbind_input(&self.authzid, self.bind, &self.channel_binding);
+        let auth_message = format!(
+            "n={username},r={client_nonce},{s},c={binding},r={final_nonce}",
+            username = Gs2Escaped(self.username.as_str()),
+            binding = STANDARD.encode(&cbind_input),
+        );
+        let Computation {
+            client_proof,
+            server_signature,
+        } = compute_scram(
+            self.hash,
+            &self.password,
+            &server_first.salt,
+            server_first.iteration_count,
+            &auth_message,
+        );
+        Ok(GotServerFirstMsg {
+            cbind_input,
+            nonce: final_nonce,
+            client_proof,
+            server_signature,
+        }
+        .into())
     }
 
     fn get_output(self) -> (Vec<Authenticate>, State) {
@@ -343,7 +417,10 @@ impl ScramState for AwaitingServerFirstMsg {
 // About to send client-final-message
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct GotServerFirstMsg {
-    authzid: AuthzId,
+    /// The `cbind-input` (GS2 header plus any channel-binding data)
+    /// computed by `AwaitingServerFirstMsg`, reused as-is for the
+    /// `c=` attribute of the client-final-message
+    cbind_input: Bytes,
     nonce: String,
     client_proof: Bytes,
     server_signature: Bytes,
@@ -356,7 +433,7 @@ impl ScramState for GotServerFirstMsg {
 
     fn get_output(self) -> (Vec<Authenticate>, State) {
         let msgs = ClientFinalMessage {
-            authzid: &self.authzid,
+            cbind_input: &self.cbind_input,
             nonce: &self.nonce,
             proof: &self.client_proof,
         }
@@ -386,25 +463,19 @@ struct AwaitingServerFinalMsg {
 
 impl ScramState for AwaitingServerFinalMsg {
     fn handle_message(mut self, msg: Authenticate) -> Result<State, SaslError> {
-        let payload = msg.parameter().as_str();
-        if payload != "+" {
-            self.input.push_str(payload);
-        }
-        if payload.len() < 400 {
-            let bs = STANDARD.decode(&self.input)?;
-            let s = std::str::from_utf8(&bs)?;
-            match s.parse::<ServerFinalMessage>()? {
-                ServerFinalMessage::Success { verifier } => {
-                    if verifier == self.server_signature {
-                        Ok(Finishing.into())
-                    } else {
-                        Err(SaslError::Signature)
-                    }
+        let Some(bs) = Authenticate::reassemble(&mut self.input, &msg)? else {
+            return Ok(self.into());
+        };
+        let s = std::str::from_utf8(&bs)?;
+        match s.parse::<ServerFinalMessage>()? {
+            ServerFinalMessage::Success { verifier } => {
+                if verifier == self.server_signature {
+                    Ok(Finishing.into())
+                } else {
+                    Err(SaslError::Signature)
                 }
-                ServerFinalMessage::Error { message } => Err(SaslError::Server(message)),
             }
-        } else {
-            Ok(self.into())
+            ServerFinalMessage::Error { error } => Err(SaslError::Server(error)),
         }
     }
 
@@ -483,6 +554,8 @@ struct ClientFirstMessage<'a> {
     authzid: &'a AuthzId,
     username: &'a Username,
     nonce: &'a str,
+    bind: bool,
+    channel_binding: &'a ChannelBinding,
 }
 
 impl ClientFirstMessage<'_> {
@@ -493,8 +566,11 @@ impl ClientFirstMessage<'_> {
 
 impl fmt::Display for ClientFirstMessage<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // gs2-header
-        write!(f, "n,a={},", Gs2Escaped(self.authzid))?;
+        write!(
+            f,
+            "{}",
+            gs2_header(self.authzid, self.bind, self.channel_binding)
+        )?;
         // client-first-message-bare
         write!(
             f,
@@ -543,7 +619,10 @@ impl std::str::FromStr for ServerFirstMessage {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct ClientFinalMessage<'a> {
-    authzid: &'a AuthzId,
+    /// The same `cbind-input` (GS2 header plus any channel-binding data)
+    /// used to compute the `AuthMessage` for the client proof; see
+    /// `cbind_input()`
+    cbind_input: &'a [u8],
     nonce: &'a str,
     proof: &'a [u8],
 }
@@ -556,21 +635,51 @@ impl ClientFinalMessage<'_> {
 
 impl fmt::Display for ClientFinalMessage<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let cbind_input = format!("n,a={},", Gs2Escaped(self.authzid));
         write!(
             f,
             "c={},r={},p={}",
-            STANDARD.encode(&cbind_input),
+            STANDARD.encode(self.cbind_input),
             self.nonce,
             STANDARD.encode(self.proof)
         )
     }
 }
 
+/// The `server-error-value` vocabulary from RFC 5802, §7, carried by a
+/// server-final-message's `e=` attribute. Any value other than the ones
+/// listed there parses into [`ServerErrorValue::Other`] rather than
+/// failing, so no information is lost.
+#[derive(strum::AsRefStr, Clone, Debug, strum::Display, strum::EnumString, Eq, Hash, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ServerErrorValue {
+    InvalidEncoding,
+    ExtensionsNotSupported,
+    InvalidProof,
+    ChannelBindingsDontMatch,
+    ServerDoesSupportChannelBinding,
+    ChannelBindingNotSupported,
+    UnsupportedChannelBindingType,
+    UnknownUser,
+    InvalidUsernameEncoding,
+    NoResources,
+    OtherError,
+    #[strum(default, transparent)]
+    Other(String),
+}
+
+impl From<&str> for ServerErrorValue {
+    fn from(s: &str) -> ServerErrorValue {
+        let Ok(value) = s.parse() else {
+            unreachable!("ServerErrorValue parsing should never fail");
+        };
+        value
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum ServerFinalMessage {
     Success { verifier: Bytes },
-    Error { message: String },
+    Error { error: ServerErrorValue },
 }
 
 impl std::str::FromStr for ServerFinalMessage {
@@ -580,7 +689,7 @@ impl std::str::FromStr for ServerFinalMessage {
         let mut ss = s;
         match parse_gs2_pair(&mut ss)? {
             Some(("e", value)) => Ok(ServerFinalMessage::Error {
-                message: value.to_owned(),
+                error: value.into(),
             }),
             Some(("v", b64)) => {
                 let verifier = Bytes::from(STANDARD.decode(b64)?);
@@ -665,6 +774,37 @@ impl fmt::Display for Gs2Escaped<'_> {
     }
 }
 
+// GS2 header := gs2-cbind-flag "," [ gs2-authzid ] ","
+//
+// gs2-cbind-flag is "p=<cb-name>" when binding to `channel_binding` over RFC
+// 5929, "y" when the client supports channel binding but isn't using it this
+// exchange (so the server can detect a downgrade attack if it does in fact
+// support a `-PLUS` mechanism), or "n" when the client has no
+// channel-binding data at all.
+fn gs2_header(authzid: &AuthzId, bind: bool, channel_binding: &ChannelBinding) -> String {
+    let flag = match (bind, channel_binding.cbind_name()) {
+        (true, Some(name)) => format!("p={name}"),
+        (true, None) => unreachable!("bind requires channel-binding data to be available"),
+        (false, Some(_)) => "y".to_owned(),
+        (false, None) => "n".to_owned(),
+    };
+    format!("{flag},a={},", Gs2Escaped(authzid.as_str()))
+}
+
+// cbind-input := gs2-header [ cbind-data ]
+//
+// `cbind-data` MUST be present for gs2-cbind-flag of "p" and MUST be absent
+// otherwise.
+fn cbind_input(authzid: &AuthzId, bind: bool, channel_binding: &ChannelBinding) -> Bytes {
+    let mut buf = gs2_header(authzid, bind, channel_binding).into_bytes();
+    if bind {
+        if let Some(cb) = channel_binding.data() {
+            buf.extend_from_slice(cb);
+        }
+    }
+    Bytes::from(buf)
+}
+
 fn generate_nonce() -> String {
     let mut rng = StdRng::from_os_rng();
     Alphanumeric
@@ -719,6 +859,165 @@ fn compute_scram(
     }
 }
 
+/// A lower-level, byte-oriented mechanism interface: drive a mechanism by
+/// handing it the server's raw (decoded) challenge bytes and getting back
+/// the client's raw response, rather than going through [`SaslFlow`]'s
+/// `AUTHENTICATE` messages, which additionally handle base64 encoding and
+/// 400-byte chunking.
+///
+/// [`SaslFlow`] (via [`ScramSasl`]) is what a [`Client`](crate::Client)
+/// actually drives; this is for callers -- tests, or code fronting a
+/// different `AUTHENTICATE`-chunking layer -- that would rather exchange
+/// plain bytes directly.
+pub trait Mechanism {
+    /// The mechanism's SASL name, as sent in `AUTHENTICATE <name>`.
+    fn name(&self) -> &'static str;
+
+    /// The client-first message to send as soon as the mechanism is
+    /// selected.
+    fn initial_response(&mut self) -> Vec<u8>;
+
+    /// Feeds the server's latest challenge to the mechanism and returns
+    /// the client's response.
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>, SaslError>;
+}
+
+/// A byte-oriented (see [`Mechanism`]) SCRAM-SHA-256 client, without
+/// channel binding.
+///
+/// This reimplements the same exchange as [`ScramSasl`] constructed with
+/// [`HashAlgo::Sha256`] and `bind: false`, just in terms of
+/// [`Mechanism::step`]'s raw bytes instead of `AUTHENTICATE` messages.
+pub struct ScramSha256Mechanism {
+    state: ByteState,
+}
+
+enum ByteState {
+    Start {
+        authzid: AuthzId,
+        username: Username,
+        password: Password,
+    },
+    AwaitingServerFirst {
+        authzid: AuthzId,
+        username: Username,
+        password: Password,
+        nonce: String,
+    },
+    AwaitingServerFinal {
+        server_signature: Bytes,
+    },
+    Done,
+}
+
+impl ScramSha256Mechanism {
+    pub fn new(username: &Nickname, password: &str) -> Result<ScramSha256Mechanism, SaslError> {
+        let authzid = username.clone();
+        let username = username.as_str().parse::<Username>()?;
+        let password = password.parse::<Password>()?;
+        Ok(ScramSha256Mechanism {
+            state: ByteState::Start {
+                authzid,
+                username,
+                password,
+            },
+        })
+    }
+}
+
+impl Mechanism for ScramSha256Mechanism {
+    fn name(&self) -> &'static str {
+        "SCRAM-SHA-256"
+    }
+
+    fn initial_response(&mut self) -> Vec<u8> {
+        let ByteState::Start {
+            authzid,
+            username,
+            password,
+        } = std::mem::replace(&mut self.state, ByteState::Done)
+        else {
+            panic!("initial_response() should only be called once, before any step() call");
+        };
+        let nonce = generate_nonce();
+        let msg = ClientFirstMessage {
+            authzid: &authzid,
+            username: &username,
+            nonce: &nonce,
+            bind: false,
+            channel_binding: &ChannelBinding::None,
+        }
+        .to_string();
+        self.state = ByteState::AwaitingServerFirst {
+            authzid,
+            username,
+            password,
+            nonce,
+        };
+        msg.into_bytes()
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>, SaslError> {
+        match std::mem::replace(&mut self.state, ByteState::Done) {
+            ByteState::AwaitingServerFirst {
+                authzid,
+                username,
+                password,
+                nonce: client_nonce,
+            } => {
+                let s = std::str::from_utf8(challenge)?;
+                let server_first = s.parse::<ServerFirstMessage>()?;
+                let final_nonce = server_first.nonce;
+                if !final_nonce.starts_with(&client_nonce) {
+                    return Err(SaslError::Nonce);
+                }
+                let cbind_input = cbind_input(&authzid, false, &ChannelBinding::None);
+                let auth_message = format!(
+                    "n={username},r={client_nonce},{s},c={binding},r={final_nonce}",
+                    username = Gs2Escaped(username.as_str()),
+                    binding = STANDARD.encode(&cbind_input),
+                );
+                let Computation {
+                    client_proof,
+                    server_signature,
+                } = compute_scram(
+                    HashAlgo::Sha256,
+                    &password,
+                    &server_first.salt,
+                    server_first.iteration_count,
+                    &auth_message,
+                );
+                let response = ClientFinalMessage {
+                    cbind_input: &cbind_input,
+                    nonce: &final_nonce,
+                    proof: &client_proof,
+                }
+                .to_string();
+                self.state = ByteState::AwaitingServerFinal { server_signature };
+                Ok(response.into_bytes())
+            }
+            ByteState::AwaitingServerFinal { server_signature } => {
+                let s = std::str::from_utf8(challenge)?;
+                match s.parse::<ServerFinalMessage>()? {
+                    ServerFinalMessage::Success { verifier } if verifier == server_signature => {
+                        self.state = ByteState::Done;
+                        Ok(Vec::new())
+                    }
+                    ServerFinalMessage::Success { .. } => Err(SaslError::Signature),
+                    ServerFinalMessage::Error { error } => Err(SaslError::Server(error)),
+                }
+            }
+            state @ (ByteState::Start { .. } | ByteState::Done) => {
+                self.state = state;
+                Err(SaslError::Unexpected {
+                    expecting: "a server challenge following initial_response()",
+                    msg: String::from_utf8_lossy(challenge).into_owned(),
+                })
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -800,9 +1099,192 @@ mod tests {
             assert_eq!(
                 msg,
                 ServerFinalMessage::Error {
-                    message: String::from("other-error")
+                    error: ServerErrorValue::OtherError
+                }
+            );
+        }
+
+        #[test]
+        fn parse_known_error_value() {
+            let msg = "e=invalid-proof".parse::<ServerFinalMessage>().unwrap();
+            assert_eq!(
+                msg,
+                ServerFinalMessage::Error {
+                    error: ServerErrorValue::InvalidProof
                 }
             );
         }
+
+        #[test]
+        fn parse_unrecognized_error_value() {
+            let msg = "e=some-future-value".parse::<ServerFinalMessage>().unwrap();
+            assert_eq!(
+                msg,
+                ServerFinalMessage::Error {
+                    error: ServerErrorValue::Other(String::from("some-future-value"))
+                }
+            );
+        }
+    }
+
+    mod compute_scram {
+        use super::*;
+
+        // RFC 7677, §3
+        #[test]
+        fn sha256_rfc_example() {
+            let password = "pencil".parse::<Password>().unwrap();
+            let salt = STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+            let auth_message = concat!(
+                "n=user,r=rOprNGfwEbeRWgbNEkqO,",
+                "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,",
+                "s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,",
+                "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0",
+            );
+            let Computation {
+                client_proof,
+                server_signature,
+            } = compute_scram(HashAlgo::Sha256, &password, &salt, 4096, auth_message);
+            assert_eq!(
+                client_proof,
+                Bytes::from(
+                    STANDARD
+                        .decode("dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")
+                        .unwrap()
+                )
+            );
+            assert_eq!(
+                server_signature,
+                Bytes::from(
+                    STANDARD
+                        .decode("6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+                        .unwrap()
+                )
+            );
+        }
+    }
+
+    mod channel_binding {
+        use super::*;
+
+        #[test]
+        fn gs2_header_without_binding() {
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            assert_eq!(
+                gs2_header(&authzid, false, &ChannelBinding::None),
+                "n,a=jwodder,"
+            );
+        }
+
+        #[test]
+        fn gs2_header_downgrade_protection() {
+            // Channel-binding data is available, but this exchange isn't
+            // using it (e.g. the server's mechanism list had no -PLUS
+            // variant), so the client sends "y" rather than "n".
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            let cb = ChannelBinding::TlsServerEndPoint(Bytes::from_static(b"\x01\x02\x03"));
+            assert_eq!(gs2_header(&authzid, false, &cb), "y,a=jwodder,");
+        }
+
+        #[test]
+        fn gs2_header_with_tls_server_end_point_binding() {
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            let cb = ChannelBinding::TlsServerEndPoint(Bytes::from_static(b"\x01\x02\x03"));
+            assert_eq!(
+                gs2_header(&authzid, true, &cb),
+                "p=tls-server-end-point,a=jwodder,"
+            );
+        }
+
+        #[test]
+        fn gs2_header_with_tls_unique_binding() {
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            let cb = ChannelBinding::TlsUnique(Bytes::from_static(b"\x01\x02\x03"));
+            assert_eq!(gs2_header(&authzid, true, &cb), "p=tls-unique,a=jwodder,");
+        }
+
+        #[test]
+        fn cbind_input_appends_channel_binding_data() {
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            let cb = ChannelBinding::TlsServerEndPoint(Bytes::from_static(b"\x01\x02\x03"));
+            let mut expected = b"p=tls-server-end-point,a=jwodder,".to_vec();
+            expected.extend_from_slice(b"\x01\x02\x03");
+            assert_eq!(cbind_input(&authzid, true, &cb), Bytes::from(expected));
+        }
+
+        #[test]
+        fn cbind_input_without_channel_binding_data() {
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            assert_eq!(
+                cbind_input(&authzid, false, &ChannelBinding::None),
+                Bytes::from_static(b"n,a=jwodder,")
+            );
+        }
+
+        #[test]
+        fn cbind_input_omits_data_when_not_bound() {
+            // Downgrade-protection "y": cbind-data must be absent even
+            // though binding data is available, since the flag isn't "p".
+            let authzid = "jwodder".parse::<AuthzId>().unwrap();
+            let cb = ChannelBinding::TlsServerEndPoint(Bytes::from_static(b"\x01\x02\x03"));
+            assert_eq!(
+                cbind_input(&authzid, false, &cb),
+                Bytes::from_static(b"y,a=jwodder,")
+            );
+        }
+    }
+
+    mod gs2_escaped {
+        use super::*;
+
+        #[test]
+        fn escapes_commas_and_equals() {
+            assert_eq!(Gs2Escaped("a,b=c").to_string(), "a=2Cb=3Dc");
+        }
+
+        #[test]
+        fn leaves_other_characters_unescaped() {
+            assert_eq!(Gs2Escaped("jwodder").to_string(), "jwodder");
+        }
+    }
+
+    mod scram_sha_256_mechanism {
+        use super::*;
+
+        fn nickname() -> Nickname {
+            "jwodder".parse().unwrap()
+        }
+
+        #[test]
+        fn name_is_scram_sha_256() {
+            let mech = ScramSha256Mechanism::new(&nickname(), "pencil").unwrap();
+            assert_eq!(mech.name(), "SCRAM-SHA-256");
+        }
+
+        #[test]
+        fn initial_response_is_unbound_client_first_message() {
+            let mut mech = ScramSha256Mechanism::new(&nickname(), "pencil").unwrap();
+            let msg = String::from_utf8(mech.initial_response()).unwrap();
+            let rest = msg
+                .strip_prefix("n,a=jwodder,n=jwodder,r=")
+                .expect("client-first message should start with an unbound GS2 header");
+            assert_eq!(rest.len(), CLIENT_NONCE_LENGTH);
+        }
+
+        #[test]
+        fn step_before_initial_response_is_an_error() {
+            let mut mech = ScramSha256Mechanism::new(&nickname(), "pencil").unwrap();
+            assert!(mech.step(b"r=x,s=AA==,i=4096").is_err());
+        }
+
+        #[test]
+        fn step_rejects_server_first_with_mismatched_nonce() {
+            let mut mech = ScramSha256Mechanism::new(&nickname(), "pencil").unwrap();
+            let _ = mech.initial_response();
+            let err = mech
+                .step(b"r=not-our-nonce,s=QSXCR+Q6sek8bf92,i=4096")
+                .unwrap_err();
+            assert!(matches!(err, SaslError::Nonce));
+        }
     }
 }
@@ -0,0 +1,180 @@
+//! [`SaslSession`] drives one SASL authentication attempt end to end: it
+//! turns a chosen [`SaslMechanism`] and credentials into outgoing
+//! `AUTHENTICATE` messages via the mechanism's [`SaslMachine`] sub-state-machine,
+//! and folds everything the server sends back -- `AUTHENTICATE` continuation
+//! lines as well as the terminal SASL numerics -- into a single [`SaslStep`].
+//!
+//! This is a lower-level building block than
+//! [`Login`](crate::commands::login::Login), which drives a whole
+//! registration (including a non-SASL fallback); `SaslSession` only knows
+//! about the SASL exchange itself, for callers -- like a reauthentication
+//! flow after registration -- that want just that.
+
+use super::{SaslError, SaslFlow, SaslMachine, SaslMechanism};
+use irctext::Reply;
+use irctext::clientmsgs::Authenticate;
+use irctext::types::Nickname;
+
+/// One incoming event to feed to [`SaslSession::step`]: either an
+/// `AUTHENTICATE` continuation from the server, or one of the replies that
+/// can terminate (or report progress on) a SASL exchange.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SaslInput {
+    Authenticate(Authenticate),
+    Reply(Reply),
+}
+
+/// The outcome of feeding one [`SaslInput`] to a [`SaslSession`].
+#[derive(Debug)]
+pub enum SaslStep {
+    /// Send these messages to the server and keep driving the session.
+    /// May be empty, e.g. while waiting on the server's terminal reply.
+    Send(Vec<Authenticate>),
+    /// SASL succeeded. `account`, if known, names the authenticated
+    /// account (populated from `RPL_LOGGEDIN`; `RPL_SASLSUCCESS` alone
+    /// doesn't carry one).
+    Authenticated { account: Option<String> },
+    /// SASL failed; see [`SaslFailure`] for why. The session is done and
+    /// should be discarded.
+    Failed(SaslFailure),
+}
+
+/// Why a [`SaslSession`] ended in [`SaslStep::Failed`].
+#[derive(Debug)]
+pub enum SaslFailure {
+    /// `ERR_SASLFAIL`: the server rejected the credentials/exchange.
+    Rejected { message: String },
+    /// `ERR_SASLABORTED`: the server confirmed the exchange was aborted.
+    Aborted { message: String },
+    /// This session's [`SaslFlow`] sub-state-machine reported an error
+    /// (malformed server payload, signature mismatch, etc.) before any
+    /// server-side reply was seen.
+    Local(SaslError),
+    /// `ERR_SASLALREADY`: the client is already authenticated.
+    AlreadyAuthenticated { message: String },
+    /// `ERR_SASLTOOLONG`: the base64 payload exceeded the server's limit.
+    TooLong { message: String },
+    /// `ERR_NICKLOCKED`: the requested nickname is locked by services and
+    /// can't be used to authenticate.
+    NickLocked { message: String },
+    /// `RPL_SASLMECHS`: the server doesn't support the requested
+    /// mechanism. `available` lists the mechanisms this crate recognizes
+    /// among the ones the server advertised; unrecognized ones are
+    /// silently dropped rather than treated as an error.
+    UnsupportedMechanism {
+        available: Vec<SaslMechanism>,
+        message: String,
+    },
+}
+
+enum State {
+    InProgress(SaslMachine),
+    Done,
+}
+
+/// Drives one SASL authentication attempt: turns a chosen
+/// [`SaslMechanism`] and credentials into the right `AUTHENTICATE`
+/// messages, and classifies everything the server sends back into a
+/// [`SaslStep`].
+pub struct SaslSession {
+    state: State,
+    pending: Vec<Authenticate>,
+}
+
+impl SaslSession {
+    /// Starts a SASL session for `mechanism`, queuing the initial
+    /// `AUTHENTICATE <mech>` line (and any payload the mechanism can
+    /// produce up front) for the next [`step`](Self::step) call to return.
+    ///
+    /// See [`SaslMechanism::new_flow`] for the meaning of `authzid`,
+    /// `password`, and `channel_binding`.
+    pub fn new(
+        mechanism: SaslMechanism,
+        authzid: Option<&str>,
+        nickname: &Nickname,
+        password: &str,
+        channel_binding: Option<&[u8]>,
+    ) -> Result<SaslSession, SaslError> {
+        let (machine, msgs) = mechanism.new_flow(authzid, nickname, password, channel_binding)?;
+        Ok(SaslSession {
+            state: State::InProgress(machine),
+            pending: msgs,
+        })
+    }
+
+    /// Takes the messages queued by [`new`](Self::new) for sending, without
+    /// waiting for a matching call to [`step`](Self::step).
+    ///
+    /// Useful for callers -- like [`SaslNegotiator`](super::SaslNegotiator)
+    /// -- that need a freshly-started session's opening `AUTHENTICATE`
+    /// lines right away, rather than only once some unrelated input is fed
+    /// back in.
+    pub fn take_pending(&mut self) -> Vec<Authenticate> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Feeds one event to the session, returning the next messages to send
+    /// or a terminal outcome.
+    ///
+    /// SHOULD NOT be called again once a [`SaslStep::Authenticated`] or
+    /// [`SaslStep::Failed`] has been returned.
+    pub fn step(&mut self, input: SaslInput) -> SaslStep {
+        if !self.pending.is_empty() {
+            return SaslStep::Send(std::mem::take(&mut self.pending));
+        }
+        match (&mut self.state, input) {
+            (State::InProgress(machine), SaslInput::Authenticate(msg)) => {
+                match machine.handle_message(msg) {
+                    Ok(()) => SaslStep::Send(machine.get_output()),
+                    Err(e) => {
+                        self.state = State::Done;
+                        SaslStep::Failed(SaslFailure::Local(e))
+                    }
+                }
+            }
+            (State::InProgress(_), SaslInput::Reply(reply)) => self.handle_reply(&reply),
+            (State::Done, _) => SaslStep::Send(Vec::new()),
+        }
+    }
+
+    fn handle_reply(&mut self, reply: &Reply) -> SaslStep {
+        let failure = match reply {
+            Reply::SaslSuccess(_) => {
+                self.state = State::Done;
+                return SaslStep::Authenticated { account: None };
+            }
+            Reply::LoggedIn(r) => {
+                self.state = State::Done;
+                return SaslStep::Authenticated {
+                    account: Some(r.account().to_string()),
+                };
+            }
+            Reply::SaslFail(r) => SaslFailure::Rejected {
+                message: r.message().to_owned(),
+            },
+            Reply::SaslAborted(r) => SaslFailure::Aborted {
+                message: r.message().to_owned(),
+            },
+            Reply::SaslAlready(r) => SaslFailure::AlreadyAuthenticated {
+                message: r.message().to_owned(),
+            },
+            Reply::SaslTooLong(r) => SaslFailure::TooLong {
+                message: r.message().to_owned(),
+            },
+            Reply::NickLocked(r) => SaslFailure::NickLocked {
+                message: r.message().to_owned(),
+            },
+            Reply::SaslMechs(r) => SaslFailure::UnsupportedMechanism {
+                available: r
+                    .mechanisms()
+                    .split(',')
+                    .filter_map(|m| m.trim().parse().ok())
+                    .collect(),
+                message: r.message().to_owned(),
+            },
+            _ => return SaslStep::Send(Vec::new()),
+        };
+        self.state = State::Done;
+        SaslStep::Failed(failure)
+    }
+}
@@ -1,16 +1,14 @@
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use ircnet::client::{
     SessionBuilder, SessionParams,
     autoresponders::{CtcpQueryResponder, PingResponder},
-    commands::{LuserStats, ServerInfo},
-};
-use irctext::{
-    ClientMessage, Message, Payload, Reply, ReplyParts, Verb,
-    clientmsgs::{Admin, Cap, CapLsRequest, Info, Links, Lusers, Quit, Version},
-    ctcp::CtcpParams,
-    types::ISupportParam,
+    commands::{
+        AdminInfo, AdminQuery, CapLsQuery, InfoQuery, Link, LinksQuery, LuserStats, LusersQuery,
+        Sequence, ServerInfo, VersionInfo, VersionQuery,
+    },
 };
+use irctext::{clientmsgs::Quit, ctcp::CtcpParams, types::ISupportParam};
 use mainutil::init_logging;
 use patharg::OutputArg;
 use serde::Serialize;
@@ -18,9 +16,14 @@ use std::collections::BTreeMap;
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use std::time::Duration;
-use tokio::time::timeout;
 use tracing::Level;
 
+/// The `CAP LS` version requested when `CAP` support wasn't already
+/// established during login
+const CAP_VERSION: u32 = 302;
+
+/// How long to wait for further replies to `LUSERS`, `VERSION`, and `ADMIN`
+/// before concluding the server has nothing more to say
 const NEXT_REPLY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Fetch various details about an IRC server
@@ -36,6 +39,10 @@ struct Arguments {
     #[arg(short = 'o', long, default_value_t)]
     outfile: OutputArg,
 
+    /// Select the output format
+    #[arg(short = 'f', long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
     /// Select the profile in the configuration file to use
     #[arg(short = 'P', long, default_value = "irc")]
     profile: String,
@@ -45,6 +52,33 @@ struct Arguments {
     trace: bool,
 }
 
+/// The output formats supported by [`Arguments::format`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum OutputFormat {
+    /// Pretty-printed JSON
+    Json,
+
+    /// YAML
+    Yaml,
+
+    /// TOML
+    Toml,
+
+    /// One `dotted.key.path=value` line per non-null scalar leaf in the
+    /// data (null leaves are omitted), greppable and diffable without a
+    /// parser for the other formats
+    Flat,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("OutputFormat has no skipped variants")
+            .get_name()
+            .fmt(f)
+    }
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> anyhow::Result<()> {
     let args = Arguments::parse();
@@ -67,6 +101,14 @@ async fn main() -> anyhow::Result<()> {
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
+                .with_userinfo(
+                    env!("CARGO_PKG_NAME")
+                        .parse::<CtcpParams>()
+                        .expect("Crate name should be valid CTCP params"),
+                )
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
@@ -84,210 +126,38 @@ async fn main() -> anyhow::Result<()> {
 
     let caplist = if !sasl {
         tracing::info!("Issuing CAP LS query …");
-        client.send(CapLsRequest::new_with_version(302)).await?;
-        let mut capabilities = Vec::new();
-        let mut unknown = false;
-        loop {
-            let Some(Message { payload, .. }) = client.recv().await? else {
-                anyhow::bail!("Server suddenly disconnected");
-            };
-            match payload {
-                Payload::ClientMessage(ClientMessage::Cap(Cap::LsResponse(r))) => {
-                    capabilities.extend(r.capabilities);
-                    if !r.continued {
-                        break;
-                    }
-                }
-                Payload::ClientMessage(ClientMessage::Error(e)) => {
-                    anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-                }
-                Payload::ClientMessage(_) => (),
-                Payload::Reply(Reply::UnknownCommand(r)) if r.command() == &Verb::Cap => {
-                    tracing::info!("Server does not support CAP command");
-                    unknown = true;
-                    break;
-                }
-                Payload::Reply(r) if r.is_error() => {
-                    anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-                }
-                Payload::Reply(_) => (),
-            }
-        }
-        (!unknown).then_some(capabilities)
+        client.run(CapLsQuery::new(CAP_VERSION)).await?
     } else {
-        login_output.capabilities
+        Some(login_output.capabilities)
     };
 
     let lusers = if login_output.luser_stats == LuserStats::default() {
         tracing::info!("Issuing LUSERS query …");
-        client.send(Lusers).await?;
-        let mut lusers = LuserStats::default();
-        while let Ok(r) = timeout(NEXT_REPLY_TIMEOUT, client.recv()).await {
-            let Some(Message { payload, .. }) = r? else {
-                anyhow::bail!("Server suddenly disconnected");
-            };
-            match payload {
-                Payload::Reply(Reply::LuserClient(r)) => {
-                    lusers.luserclient_msg = Some(r.message().to_owned());
-                }
-                Payload::Reply(Reply::LuserOp(r)) => {
-                    lusers.operators = Some(r.ops());
-                }
-                Payload::Reply(Reply::LuserUnknown(r)) => {
-                    lusers.unknown_connections = Some(r.connections());
-                }
-                Payload::Reply(Reply::LuserChannels(r)) => {
-                    lusers.channels = Some(r.channels());
-                }
-                Payload::Reply(Reply::LuserMe(r)) => {
-                    lusers.luserme_msg = Some(r.message().to_owned());
-                }
-                Payload::Reply(Reply::LocalUsers(r)) => {
-                    lusers.local_clients = r.current_users();
-                    lusers.max_local_clients = r.max_users();
-                }
-                Payload::Reply(Reply::GlobalUsers(r)) => {
-                    lusers.global_clients = r.current_users();
-                    lusers.max_global_clients = r.max_users();
-                }
-                Payload::Reply(Reply::StatsConn(r)) => {
-                    lusers.statsconn_msg = Some(r.message().to_owned());
-                }
-                Payload::ClientMessage(ClientMessage::Error(e)) => {
-                    anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-                }
-                Payload::ClientMessage(_) => (),
-                Payload::Reply(r) if r.is_error() => {
-                    anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-                }
-                Payload::Reply(_) => (),
-            }
-        }
-        if lusers == LuserStats::default() {
+        let stats = client.run(LusersQuery::new(NEXT_REPLY_TIMEOUT)).await?;
+        if stats == LuserStats::default() {
             tracing::info!("No LUSERS replies received in time");
             None
         } else {
-            Some(lusers)
+            Some(stats)
         }
     } else {
         Some(login_output.luser_stats)
     };
 
-    tracing::info!("Issuing VERSION query …");
-    client.send(Version::new()).await?;
-    let mut version = None;
-    while let Ok(r) = timeout(NEXT_REPLY_TIMEOUT, client.recv()).await {
-        let Some(Message { payload, .. }) = r? else {
-            anyhow::bail!("Server suddenly disconnected");
-        };
-        match payload {
-            Payload::Reply(Reply::Version(r)) => {
-                version = Some(VersionInfo {
-                    version: r.version().to_owned(),
-                    server: r.server().to_owned(),
-                    comments: r.comments().to_owned(),
-                });
-            }
-            Payload::Reply(Reply::ISupport(_)) => (),
-            Payload::ClientMessage(ClientMessage::Error(e)) => {
-                anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-            }
-            Payload::ClientMessage(_) => (),
-            Payload::Reply(r) if r.is_error() => {
-                anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-            }
-            Payload::Reply(_) => (),
-        }
-    }
+    tracing::info!("Issuing VERSION, ADMIN, LINKS, and INFO queries …");
+    let (version, admin, links, info) = client
+        .run(Sequence::new((
+            VersionQuery::new(NEXT_REPLY_TIMEOUT),
+            AdminQuery::new(NEXT_REPLY_TIMEOUT),
+            LinksQuery::new(),
+            InfoQuery::new(),
+        )))
+        .await?;
     if version.is_none() {
         tracing::info!("No RPL_VERSION reply received in time");
     }
-
-    tracing::info!("Issuing ADMIN query …");
-    client.send(Admin::new()).await?;
-    let mut admin = AdminInfo::default();
-    while let Ok(r) = timeout(NEXT_REPLY_TIMEOUT, client.recv()).await {
-        let Some(Message { payload, .. }) = r? else {
-            anyhow::bail!("Server suddenly disconnected");
-        };
-        match payload {
-            Payload::Reply(Reply::AdminMe(_)) => (),
-            Payload::Reply(Reply::AdminLoc1(r)) => admin.loc1 = Some(r.message().to_owned()),
-            Payload::Reply(Reply::AdminLoc2(r)) => admin.loc2 = Some(r.message().to_owned()),
-            Payload::Reply(Reply::AdminEmail(r)) => admin.email = Some(r.message().to_owned()),
-            Payload::ClientMessage(ClientMessage::Error(e)) => {
-                anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-            }
-            Payload::ClientMessage(_) => (),
-            Payload::Reply(r) if r.is_error() => {
-                anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-            }
-            Payload::Reply(_) => (),
-        }
-    }
-    let admin = if admin == AdminInfo::default() {
+    if admin.is_none() {
         tracing::info!("No ADMIN replies received in time");
-        None
-    } else {
-        Some(admin)
-    };
-
-    tracing::info!("Issuing LINKS query …");
-    client.send(Links).await?;
-    let mut links = Vec::new();
-    let mut unknown = false;
-    loop {
-        let Some(Message { payload, .. }) = client.recv().await? else {
-            anyhow::bail!("Server suddenly disconnected");
-        };
-        match payload {
-            Payload::Reply(Reply::Links(r)) => {
-                links.push(Link {
-                    server1: r.server1().to_owned(),
-                    server2: r.server2().to_owned(),
-                    hopcount: r.hopcount(),
-                    server_info: r.server_info().to_owned(),
-                });
-            }
-            Payload::Reply(Reply::EndOfLinks(_)) => break,
-            Payload::ClientMessage(ClientMessage::Error(e)) => {
-                anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-            }
-            Payload::ClientMessage(_) => (),
-            Payload::Reply(Reply::UnknownCommand(r)) if r.command() == &Verb::Links => {
-                tracing::info!("Server does not support LINKS command");
-                unknown = true;
-                break;
-            }
-            Payload::Reply(r) if r.is_error() => {
-                anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-            }
-            Payload::Reply(_) => (),
-        }
-    }
-    let links = (!unknown).then_some(links);
-
-    tracing::info!("Issuing INFO query …");
-    client.send(Info).await?;
-    let mut info = Vec::new();
-    loop {
-        let Some(Message { payload, .. }) = client.recv().await? else {
-            anyhow::bail!("Server suddenly disconnected");
-        };
-        match payload {
-            Payload::Reply(Reply::Info(r)) => {
-                info.push(r.message().to_owned());
-            }
-            Payload::Reply(Reply::EndOfInfo(_)) => break,
-            Payload::ClientMessage(ClientMessage::Error(e)) => {
-                anyhow::bail!("Server sent ERROR message: {:?}", e.reason())
-            }
-            Payload::ClientMessage(_) => (),
-            Payload::Reply(r) if r.is_error() => {
-                anyhow::bail!("Server returned error: {:?}", r.to_irc_line());
-            }
-            Payload::Reply(_) => (),
-        }
     }
 
     tracing::info!("Quitting …");
@@ -319,20 +189,86 @@ async fn main() -> anyhow::Result<()> {
         version,
         admin,
         links,
-        info,
+        info: info.unwrap_or_default(),
     };
 
+    let value = serde_json::to_value(&output).context("failed to serialize output")?;
     let mut out = BufWriter::new(
         args.outfile
             .create()
             .context("failed to open output file")?,
     );
-    serde_json::to_writer_pretty(&mut out, &output).context("failed to serialize output")?;
-    out.write_all(b"\n")?;
+    write_output(&mut out, args.format, &value)?;
     out.flush()?;
     Ok(())
 }
 
+/// Render `value` — the one-true serialization of [`IrcInfo`] that every
+/// format is derived from — in the given `format` and write it to `out`.
+fn write_output(
+    out: &mut impl Write,
+    format: OutputFormat,
+    value: &serde_json::Value,
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, value)
+                .context("failed to serialize output")?;
+            out.write_all(b"\n")?;
+        }
+        OutputFormat::Yaml => {
+            serde_yaml::to_writer(&mut *out, value).context("failed to serialize output")?;
+        }
+        OutputFormat::Toml => {
+            let s = toml::to_string_pretty(value).context("failed to serialize output")?;
+            out.write_all(s.as_bytes())?;
+        }
+        OutputFormat::Flat => {
+            let mut path = String::new();
+            flatten(value, &mut path, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Recursively walks `value`, writing one `path=value` line per scalar leaf
+/// (null leaves write nothing), with `path` built up as a dotted key path (array indices included as path
+/// segments) reflecting the leaf's position in the original JSON tree.
+fn flatten(
+    value: &serde_json::Value,
+    path: &mut String,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                flatten(v, path, out)?;
+                path.truncate(len);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(&i.to_string());
+                flatten(v, path, out)?;
+                path.truncate(len);
+            }
+        }
+        serde_json::Value::Null => {}
+        serde_json::Value::String(s) => writeln!(out, "{path}={s}")?,
+        other => writeln!(out, "{path}={other}")?,
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 struct IrcInfo {
     capabilities: Option<BTreeMap<String, Option<String>>>,
@@ -352,25 +288,3 @@ enum ISupportValue {
     Str(String),
     Bool(bool),
 }
-
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
-struct VersionInfo {
-    version: String,
-    server: String,
-    comments: String,
-}
-
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
-struct AdminInfo {
-    loc1: Option<String>,
-    loc2: Option<String>,
-    email: Option<String>,
-}
-
-#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
-struct Link {
-    server1: String,
-    server2: String,
-    hopcount: u32,
-    server_info: String,
-}
@@ -0,0 +1,19 @@
+/// The standard (non-TLS) IRC port, used as the default by [`Address::socket_addr_spec`]
+/// when an implementor doesn't specify one.
+pub const DEFAULT_PORT: u16 = 6667;
+
+/// A value that names a server to connect to, such as a [`Connect`][crate::clientmsgs::Connect]
+/// message.
+pub trait Address {
+    /// The hostname or address of the target server.
+    fn host(&self) -> &str;
+
+    /// The port to connect to, if one was specified.
+    fn port(&self) -> Option<u16>;
+
+    /// A `(host, port)` pair usable with [`std::net::ToSocketAddrs`], with
+    /// `port()` defaulted to [`DEFAULT_PORT`] when unset.
+    fn socket_addr_spec(&self) -> (&str, u16) {
+        (self.host(), self.port().unwrap_or(DEFAULT_PORT))
+    }
+}
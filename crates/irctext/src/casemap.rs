@@ -0,0 +1,214 @@
+//! Casemapping-aware collections for tracking entities (channels,
+//! nicknames, …) whose identity on the wire is case-insensitive.
+//!
+//! IRC casefolding is not plain ASCII lowercasing: under the default
+//! `rfc1459` [`CaseMapping`], `[]\~` additionally fold to `{}|^` (and
+//! `rfc1459-strict` folds `[]\` but not `~`), per
+//! <https://modern.ircdocs.horse/#casemapping-parameter>. [`CaseMap`] and
+//! [`CaseSet`] apply [`CaseMapping::lowercase_str`] (via [`CaseFold`])
+//! instead of `str::to_lowercase` so that lookups agree with what the
+//! server considers the same name.
+use crate::CaseMapping;
+use crate::types::{Channel, Nickname};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A type whose IRC-casefolded form can be computed under a [`CaseMapping`],
+/// making it usable as a [`CaseMap`]/[`CaseSet`] key.
+pub trait CaseFold: Clone + Eq + Hash {
+    /// Returns the casefolded form of `self` under `cm`.
+    fn casefold(&self, cm: CaseMapping) -> Self;
+}
+
+impl CaseFold for Channel {
+    fn casefold(&self, cm: CaseMapping) -> Channel {
+        self.to_lowercase(cm)
+    }
+}
+
+impl CaseFold for Nickname {
+    fn casefold(&self, cm: CaseMapping) -> Nickname {
+        self.to_lowercase(cm)
+    }
+}
+
+/// A map from entities of type `K` (e.g. [`Channel`] or [`Nickname`]) to
+/// values of type `V`, keyed by each entity's casefolded form so that
+/// lookups succeed regardless of the case variant the caller has on hand.
+/// This generalizes the ad hoc `ChannelCanonicalizer` bots used to write by
+/// hand into a reusable type that also carries a value per entity.
+///
+/// If the server's `CASEMAPPING` is learned or changes after entries have
+/// already been inserted — e.g. because `ISUPPORT` is still being parsed
+/// when the first few messages arrive — call [`CaseMap::rekey`] to
+/// re-derive every entry's casefolded key under the new mapping.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseMap<K, V> {
+    casemapping: CaseMapping,
+    entries: HashMap<K, (K, V)>,
+}
+
+impl<K: CaseFold, V> CaseMap<K, V> {
+    pub fn new(casemapping: CaseMapping) -> CaseMap<K, V> {
+        CaseMap {
+            casemapping,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn casemapping(&self) -> CaseMapping {
+        self.casemapping
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts `key` (in whatever case variant the caller has) with `value`,
+    /// returning the previous value for an equivalent (under the current
+    /// `CaseMapping`) key, if any. The newly given `key` becomes the
+    /// canonical form returned by future [`CaseMap::get_key_value`] calls,
+    /// replacing any previously-inserted canonical form.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let folded = key.casefold(self.casemapping);
+        self.entries.insert(folded, (key, value)).map(|(_, v)| v)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Looks up `key` and, if present, returns its previously-inserted
+    /// canonical-case form alongside its value.
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        let folded = key.casefold(self.casemapping);
+        self.entries.get(&folded).map(|(k, v)| (k, v))
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        let folded = key.casefold(self.casemapping);
+        self.entries.contains_key(&folded)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let folded = key.casefold(self.casemapping);
+        self.entries.remove(&folded).map(|(_, v)| v)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.values().map(|(k, _)| k)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.values().map(|(k, v)| (k, v))
+    }
+
+    /// Re-derives every entry's casefolded key under `cm`, keeping each
+    /// entry's canonical key and value intact. Use this when the server's
+    /// `CASEMAPPING` is discovered or changes after entries were already
+    /// inserted under the previous (e.g. default) mapping.
+    pub fn rekey(&mut self, cm: CaseMapping) {
+        self.casemapping = cm;
+        self.entries = std::mem::take(&mut self.entries)
+            .into_values()
+            .map(|(k, v)| (k.casefold(cm), (k, v)))
+            .collect();
+    }
+}
+
+/// A set of entities of type `K` (e.g. [`Channel`] or [`Nickname`]),
+/// tracked by their casefolded form under a [`CaseMapping`] — the direct
+/// generalization of the private `ChannelCanonicalizer` in ircevents and
+/// msgtimes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseSet<K>(CaseMap<K, ()>);
+
+impl<K: CaseFold> CaseSet<K> {
+    pub fn new(casemapping: CaseMapping) -> CaseSet<K> {
+        CaseSet(CaseMap::new(casemapping))
+    }
+
+    pub fn casemapping(&self) -> CaseMapping {
+        self.0.casemapping()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts `key`, returning `true` if an equivalent key was not already
+    /// present.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.0.insert(key, ()).is_none()
+    }
+
+    /// Returns the previously-inserted canonical-case form of `key`, if
+    /// present.
+    pub fn get(&self, key: &K) -> Option<&K> {
+        self.0.get_key_value(key).map(|(k, _)| k)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.0.remove(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.0.keys()
+    }
+
+    /// Re-derives every entry's casefolded key under `cm`; see
+    /// [`CaseMap::rekey`].
+    pub fn rekey(&mut self, cm: CaseMapping) {
+        self.0.rekey(cm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_get_case_insensitively() {
+        let mut set = CaseSet::new(CaseMapping::Rfc1459);
+        let chan = "#Foo".parse::<Channel>().unwrap();
+        assert!(set.insert(chan.clone()));
+        let other = "#foo".parse::<Channel>().unwrap();
+        assert!(!set.insert(other.clone()));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.get(&other), Some(&chan));
+        assert!(set.contains(&"#FOO".parse::<Channel>().unwrap()));
+    }
+
+    #[test]
+    fn rfc1459_additional_folding() {
+        let mut set = CaseSet::new(CaseMapping::Rfc1459);
+        let chan = "#a{b}c|d~e".parse::<Channel>().unwrap();
+        set.insert(chan.clone());
+        let folded = "#a[b]c\\d^e".parse::<Channel>().unwrap();
+        assert_eq!(set.get(&folded), Some(&chan));
+    }
+
+    #[test]
+    fn rekey_on_late_casemapping() {
+        let mut map = CaseMap::new(CaseMapping::Ascii);
+        let chan = "#a{b}".parse::<Channel>().unwrap();
+        map.insert(chan.clone(), 1);
+        let folded = "#a[b]".parse::<Channel>().unwrap();
+        assert_eq!(map.get(&folded), None);
+        map.rekey(CaseMapping::Rfc1459);
+        assert_eq!(map.get(&folded), Some(&1));
+        assert_eq!(map.get(&chan), Some(&1));
+    }
+}
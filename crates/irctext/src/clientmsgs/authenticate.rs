@@ -1,8 +1,10 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
 use crate::{FinalParam, Message, ParameterList, RawMessage, Verb};
-use base64::{Engine, engine::general_purpose::STANDARD};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Authenticate {
     parameter: FinalParam,
 }
@@ -17,10 +19,7 @@ impl Authenticate {
         let mut msgs = Vec::with_capacity(b64.len() / 400 + 1);
         loop {
             if b64.is_empty() {
-                let Ok(param) = "+".parse::<FinalParam>() else {
-                    unreachable!(r#""+" should be valid final param"#);
-                };
-                msgs.push(Authenticate::new(param));
+                msgs.push(Authenticate::new_empty());
                 return msgs;
             } else {
                 let end = b64.len().min(400);
@@ -51,6 +50,30 @@ impl Authenticate {
         Authenticate::new(param)
     }
 
+    /// Builds the empty-payload/continuation sentinel message, `AUTHENTICATE
+    /// +`, as sent by the server to prompt for a response and by the client
+    /// to close out a chunked payload whose final chunk was exactly 400
+    /// bytes (or whose payload was empty).
+    pub fn new_empty() -> Authenticate {
+        let Ok(param) = "+".parse::<FinalParam>() else {
+            unreachable!(r#""+" should be valid final param"#);
+        };
+        Authenticate::new(param)
+    }
+
+    /// Tests whether this message's parameter is the `+` sentinel, as sent
+    /// by the server to prompt for a response after `AUTHENTICATE
+    /// <mechanism>`.
+    pub fn is_continue(&self) -> bool {
+        self.parameter.as_str() == "+"
+    }
+
+    /// Decodes this message's parameter as a base64-encoded SASL challenge
+    /// or response payload.
+    pub fn decode_payload(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        STANDARD.decode(self.parameter.as_str())
+    }
+
     pub fn parameter(&self) -> &FinalParam {
         &self.parameter
     }
@@ -58,6 +81,32 @@ impl Authenticate {
     pub fn into_parameter(self) -> FinalParam {
         self.parameter
     }
+
+    /// Feeds one incoming `AUTHENTICATE` message into `buf`, a reassembly
+    /// buffer of undecoded base64 accumulated from the messages seen so
+    /// far, returning the decoded payload once the final chunk has arrived.
+    ///
+    /// Per the chunking scheme used by [`new_encoded`](Authenticate::new_encoded),
+    /// a chunk shorter than 400 characters (including the bare `+` sentinel
+    /// for an empty payload) ends the sequence; anything else is a full
+    /// 400-character chunk with more to come, in which case this returns
+    /// `Ok(None)` and leaves `buf` for the next call.
+    pub fn reassemble(
+        buf: &mut String,
+        msg: &Authenticate,
+    ) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        let payload = msg.parameter().as_str();
+        if payload != "+" {
+            buf.push_str(payload);
+        }
+        if payload.len() < 400 {
+            let decoded = STANDARD.decode(&buf)?;
+            buf.clear();
+            Ok(Some(decoded))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 impl ClientMessageParts for Authenticate {
@@ -93,3 +142,169 @@ impl TryFrom<ParameterList> for Authenticate {
         Ok(Authenticate { parameter })
     }
 }
+
+/// A SASL `PLAIN` exchange (<https://ircv3.net/specs/extensions/sasl-3.1>):
+/// authenticates via an authentication identity and password, optionally
+/// acting as a different authorization identity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaslPlain {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub authzid: Option<String>,
+    pub authcid: String,
+    pub password: String,
+}
+
+impl SaslPlain {
+    pub fn new(authcid: impl Into<String>, password: impl Into<String>) -> SaslPlain {
+        SaslPlain {
+            authzid: None,
+            authcid: authcid.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Builds the full `AUTHENTICATE` message sequence for this exchange:
+    /// the initial `AUTHENTICATE PLAIN`, followed by the base64-encoded,
+    /// 400-byte-chunked `authzid\0authcid\0password` payload.
+    ///
+    /// Fails if `authzid`, `authcid`, or `password` contains a NUL byte, as
+    /// that would be indistinguishable from the delimiters between the
+    /// three fields.
+    pub fn to_messages(&self) -> Result<Vec<Authenticate>, SaslPlainError> {
+        let authzid = self.authzid.as_deref().unwrap_or("");
+        if authzid.contains('\0') || self.authcid.contains('\0') || self.password.contains('\0') {
+            return Err(SaslPlainError);
+        }
+        let Ok(mechanism) = "PLAIN".parse::<FinalParam>() else {
+            unreachable!(r#""PLAIN" should be valid final param"#);
+        };
+        let mut msgs = vec![Authenticate::new(mechanism)];
+        msgs.extend(Authenticate::new_plain_sasl(
+            authzid,
+            &self.authcid,
+            &self.password,
+        ));
+        Ok(msgs)
+    }
+}
+
+/// Error returned by [`SaslPlain::to_messages`] when one of the identity or
+/// password fields contains a NUL byte.
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("SASL PLAIN authzid, authcid, and password cannot contain NUL")]
+pub struct SaslPlainError;
+
+/// A SASL `EXTERNAL` exchange (<https://ircv3.net/specs/extensions/sasl-3.1>):
+/// authenticates via an out-of-band mechanism such as a TLS client
+/// certificate, optionally requesting a specific authorization identity
+/// (leave unset to let the server derive one from the certificate).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaslExternal {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub authzid: Option<String>,
+}
+
+impl SaslExternal {
+    pub fn new() -> SaslExternal {
+        SaslExternal::default()
+    }
+
+    /// Builds the full `AUTHENTICATE` message sequence for this exchange:
+    /// the initial `AUTHENTICATE EXTERNAL`, followed by the base64-encoded,
+    /// 400-byte-chunked `authzid` payload (empty if none was set).
+    pub fn to_messages(&self) -> Vec<Authenticate> {
+        let Ok(mechanism) = "EXTERNAL".parse::<FinalParam>() else {
+            unreachable!(r#""EXTERNAL" should be valid final param"#);
+        };
+        let mut msgs = vec![Authenticate::new(mechanism)];
+        msgs.extend(Authenticate::new_encoded(
+            self.authzid.as_deref().unwrap_or("").as_bytes(),
+        ));
+        msgs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sasl_plain_to_messages() {
+        let sasl = SaslPlain::new("jwodder", "hunter2");
+        let lines = sasl
+            .to_messages()
+            .unwrap()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "AUTHENTICATE :PLAIN",
+                "AUTHENTICATE :AGp3b2RkZXIAaHVudGVyMg==",
+            ]
+        );
+    }
+
+    #[test]
+    fn sasl_external_to_messages() {
+        let sasl = SaslExternal::new();
+        let lines = sasl
+            .to_messages()
+            .into_iter()
+            .map(|msg| msg.to_irc_line())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["AUTHENTICATE :EXTERNAL", "AUTHENTICATE :+"]);
+    }
+
+    #[test]
+    fn sasl_plain_rejects_embedded_nul() {
+        let sasl = SaslPlain::new("jwo\0dder", "hunter2");
+        assert_eq!(sasl.to_messages(), Err(SaslPlainError));
+    }
+
+    #[test]
+    fn reassemble_round_trips_short_payload() {
+        let payload = b"hello world";
+        let msgs = Authenticate::new_encoded(payload);
+        assert_eq!(msgs.len(), 1);
+        let mut buf = String::new();
+        let decoded = Authenticate::reassemble(&mut buf, &msgs[0]).unwrap();
+        assert_eq!(decoded, Some(payload.to_vec()));
+    }
+
+    #[test]
+    fn reassemble_round_trips_chunked_payload() {
+        let payload = vec![b'x'; 1000];
+        let msgs = Authenticate::new_encoded(&payload);
+        assert!(msgs.len() > 1);
+        let mut buf = String::new();
+        let mut decoded = None;
+        for (i, msg) in msgs.iter().enumerate() {
+            let result = Authenticate::reassemble(&mut buf, msg).unwrap();
+            if i + 1 < msgs.len() {
+                assert_eq!(result, None);
+            } else {
+                decoded = result;
+            }
+        }
+        assert_eq!(decoded, Some(payload));
+    }
+
+    #[test]
+    fn reassemble_round_trips_empty_payload() {
+        let msgs = Authenticate::new_encoded(b"");
+        assert_eq!(msgs.len(), 1);
+        let mut buf = String::new();
+        let decoded = Authenticate::reassemble(&mut buf, &msgs[0]).unwrap();
+        assert_eq!(decoded, Some(Vec::new()));
+    }
+}
@@ -0,0 +1,156 @@
+use super::{ClientMessage, ClientMessageError, ClientMessageParts};
+use crate::{MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage, Verb};
+
+/// An IRCv3 `BATCH` line, per
+/// <https://ircv3.net/specs/extensions/batch>.
+///
+/// This covers the `BATCH` line itself: where a batch starts and ends, its
+/// reference tag, and, for a start line, the batch type and its remaining
+/// arguments (as a raw [`ParameterList`]).  Grouping the messages that
+/// belong to a batch is not done here.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Batch {
+    Start(BatchStart),
+    End(BatchEnd),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchStart {
+    reference_tag: MedialParam,
+    batch_type: MedialParam,
+    arguments: ParameterList,
+}
+
+impl BatchStart {
+    pub fn new(reference_tag: MedialParam, batch_type: MedialParam, arguments: ParameterList) -> BatchStart {
+        BatchStart {
+            reference_tag,
+            batch_type,
+            arguments,
+        }
+    }
+
+    pub fn reference_tag(&self) -> &MedialParam {
+        &self.reference_tag
+    }
+
+    pub fn batch_type(&self) -> &MedialParam {
+        &self.batch_type
+    }
+
+    pub fn arguments(&self) -> &ParameterList {
+        &self.arguments
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchEnd {
+    reference_tag: MedialParam,
+}
+
+impl BatchEnd {
+    pub fn new(reference_tag: MedialParam) -> BatchEnd {
+        BatchEnd { reference_tag }
+    }
+
+    pub fn reference_tag(&self) -> &MedialParam {
+        &self.reference_tag
+    }
+}
+
+impl ClientMessageParts for Batch {
+    fn into_parts(self) -> (Verb, ParameterList) {
+        match self {
+            Batch::Start(start) => {
+                let tag = format!("+{}", start.reference_tag);
+                let Ok(tag) = MedialParam::try_from(tag) else {
+                    unreachable!("\"+\" followed by a valid reference tag should itself be valid");
+                };
+                (
+                    Verb::Batch,
+                    ParameterList::builder()
+                        .with_medial(tag)
+                        .with_medial(start.batch_type)
+                        .with_list(start.arguments),
+                )
+            }
+            Batch::End(end) => {
+                let tag = format!("-{}", end.reference_tag);
+                let Ok(tag) = MedialParam::try_from(tag) else {
+                    unreachable!("\"-\" followed by a valid reference tag should itself be valid");
+                };
+                (Verb::Batch, ParameterList::builder().with_medial(tag).finish())
+            }
+        }
+    }
+
+    fn to_irc_line(&self) -> String {
+        match self {
+            Batch::Start(start) => format!(
+                "BATCH +{} {}{}",
+                start.reference_tag,
+                start.batch_type,
+                DisplayArguments(&start.arguments)
+            ),
+            Batch::End(end) => format!("BATCH -{}", end.reference_tag),
+        }
+    }
+}
+
+struct DisplayArguments<'a>(&'a ParameterList);
+
+impl std::fmt::Display for DisplayArguments<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.0.is_empty() {
+            write!(f, " {}", self.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Batch> for Message {
+    fn from(value: Batch) -> Message {
+        Message::from(ClientMessage::from(value))
+    }
+}
+
+impl From<Batch> for RawMessage {
+    fn from(value: Batch) -> RawMessage {
+        RawMessage::from(ClientMessage::from(value))
+    }
+}
+
+impl TryFrom<ParameterList> for Batch {
+    type Error = ClientMessageError;
+
+    fn try_from(params: ParameterList) -> Result<Batch, ClientMessageError> {
+        let mut iter = params.into_iter();
+        let p1 = iter.next().ok_or(ParameterListSizeError::Exact {
+            required: 1,
+            received: 0,
+        })?;
+        let tag = p1.into_inner();
+        if let Some(reference_tag) = tag.strip_prefix('+') {
+            let reference_tag = MedialParam::try_from(reference_tag.to_owned())?;
+            let p2 = iter.next().ok_or(ParameterListSizeError::Range {
+                min_required: 2,
+                max_required: usize::MAX,
+                received: 1,
+            })?;
+            let batch_type = MedialParam::try_from(p2.into_inner())?;
+            let arguments = iter.into_parameter_list();
+            Ok(Batch::Start(BatchStart::new(reference_tag, batch_type, arguments)))
+        } else if let Some(reference_tag) = tag.strip_prefix('-') {
+            let reference_tag = MedialParam::try_from(reference_tag.to_owned())?;
+            Ok(Batch::End(BatchEnd::new(reference_tag)))
+        } else {
+            Err(ClientMessageError::ParamValue {
+                got: tag,
+                expected: "a reference tag prefixed with '+' or '-'",
+            })
+        }
+    }
+}
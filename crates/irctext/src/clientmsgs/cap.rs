@@ -1,6 +1,6 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
 use crate::types::ReplyTarget;
-use crate::util::{join_with_space, split_spaces};
+use crate::util::{join_with_commas, join_with_space, split_spaces};
 use crate::{
     FinalParam, MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage,
     TryFromStringError, Verb,
@@ -26,6 +26,7 @@ use thiserror::Error;
 // parsed has a `<nick-or-star>` parameter.
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cap {
     LsRequest(CapLsRequest),
     LsResponse(CapLsResponse),
@@ -312,6 +313,7 @@ impl TryFrom<ParameterList> for Cap {
 }
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapLsRequest {
     pub version: Option<u32>,
 }
@@ -378,6 +380,7 @@ impl From<CapLsRequest> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapLsResponse {
     pub target: ReplyTarget,
     // Whether there's an asterisk parameter between the subcommand and the
@@ -460,6 +463,7 @@ impl From<CapLsResponse> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapListRequest;
 
 impl ClientMessageParts for CapListRequest {
@@ -503,6 +507,7 @@ impl From<CapListRequest> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapListResponse {
     pub target: ReplyTarget,
     // Whether there's an asterisk parameter between the subcommand and the
@@ -567,6 +572,7 @@ impl From<CapListResponse> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapReq {
     pub capabilities: Vec<CapabilityRequest>,
 }
@@ -618,6 +624,7 @@ impl From<CapReq> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapAck {
     pub target: ReplyTarget,
     pub capabilities: Vec<CapabilityRequest>,
@@ -672,6 +679,7 @@ impl From<CapAck> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapNak {
     pub target: ReplyTarget,
     pub capabilities: Vec<Capability>,
@@ -726,6 +734,7 @@ impl From<CapNak> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapEnd;
 
 impl ClientMessageParts for CapEnd {
@@ -769,6 +778,7 @@ impl From<CapEnd> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapNew {
     pub target: ReplyTarget,
     pub capabilities: Vec<Capability>,
@@ -823,6 +833,7 @@ impl From<CapNew> for RawMessage {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapDel {
     pub target: ReplyTarget,
     pub capabilities: Vec<Capability>,
@@ -880,6 +891,7 @@ impl From<CapDel> for RawMessage {
 pub struct Capability(String);
 
 validstr!(Capability, ParseCapabilityError, validate_capability);
+strserde!(Capability, "an IRCv3 capability name");
 
 fn validate_capability(s: &str) -> Result<(), ParseCapabilityError> {
     if s.is_empty() {
@@ -923,6 +935,7 @@ validstr!(
     ParseCapabilityValueError,
     validate_capability_value
 );
+strserde!(CapabilityValue, "an IRCv3 capability value");
 
 fn validate_capability_value(s: &str) -> Result<(), ParseCapabilityValueError> {
     if s.contains(['\0', '\r', '\n', ' ']) {
@@ -932,6 +945,142 @@ fn validate_capability_value(s: &str) -> Result<(), ParseCapabilityValueError> {
     }
 }
 
+impl Capability {
+    /// Classifies this capability as one of the registered IRCv3
+    /// capabilities in [`KnownCapability`], or returns `None` if it isn't
+    /// one this library has built-in knowledge of (including vendor/
+    /// extension capabilities, which are namespaced with a `/`).
+    pub fn known(&self) -> Option<KnownCapability> {
+        let Ok(known) = self.as_str().parse::<KnownCapability>() else {
+            unreachable!("KnownCapability parsing should never fail");
+        };
+        match known {
+            KnownCapability::Other(_) => None,
+            known => Some(known),
+        }
+    }
+}
+
+/// The set of IRCv3 capabilities registered at
+/// <https://ircv3.net/registration> that this library has built-in
+/// knowledge of, for branching on a [`Capability`]'s meaning instead of
+/// comparing strings. Any other name — including vendor/extension
+/// capabilities namespaced with a `/` — parses into [`KnownCapability::Other`]
+/// rather than failing, so no information is lost round-tripping back to a
+/// [`Capability`] via [`From`].
+#[derive(strum::AsRefStr, Clone, Debug, strum::Display, strum::EnumString, Eq, Hash, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum KnownCapability {
+    AccountNotify,
+    AccountTag,
+    AwayNotify,
+    Batch,
+    CapNotify,
+    #[strum(serialize = "chghost")]
+    ChgHost,
+    EchoMessage,
+    ExtendedJoin,
+    InviteNotify,
+    LabeledResponse,
+    MessageTags,
+    MultiPrefix,
+    Sasl,
+    ServerTime,
+    #[strum(serialize = "setname")]
+    SetName,
+    StandardReplies,
+    Sts,
+    UserhostInNames,
+    #[strum(default, transparent)]
+    Other(Capability),
+}
+
+impl From<KnownCapability> for Capability {
+    fn from(value: KnownCapability) -> Capability {
+        match value {
+            KnownCapability::Other(cap) => cap,
+            known => known
+                .to_string()
+                .parse()
+                .expect("KnownCapability should be valid Capability"),
+        }
+    }
+}
+
+impl CapabilityValue {
+    /// Splits this value on `,` into a list of tokens, as used by
+    /// list-style values such as `sasl`'s (e.g. `EXTERNAL,PLAIN`).
+    pub fn mechanisms(&self) -> Vec<&str> {
+        self.split_comma()
+    }
+
+    /// Parses this value as a comma-separated `token=value,...` list, as
+    /// used by values such as `sts`'s (e.g.
+    /// `duration=604800,port=6697`). A token with no `=value` part of its
+    /// own is paired with an empty string.
+    pub fn key_value_pairs(&self) -> Vec<(&str, &str)> {
+        self.split_comma()
+            .into_iter()
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .collect()
+    }
+
+    /// Splits this value on `,`, the separator used by every comma-list-
+    /// structured capability value defined so far (`sasl`, `sts`, and
+    /// others). Useful for interpreting a capability value this library
+    /// doesn't have a more specific parser for.
+    pub fn split_comma(&self) -> Vec<&str> {
+        self.as_str().split(',').collect()
+    }
+
+    /// Parses this value as a comma-separated list of SASL mechanisms, as
+    /// carried by the `sasl` capability's value.
+    pub fn sasl_mechanisms(&self) -> Vec<SaslMechanism> {
+        self.split_comma()
+            .into_iter()
+            .map(SaslMechanism::from)
+            .collect()
+    }
+
+    /// Builds a `CapabilityValue` from a list of SASL mechanisms, as the
+    /// reverse of [`CapabilityValue::sasl_mechanisms`].
+    #[expect(clippy::missing_panics_doc)]
+    pub fn from_sasl_mechanisms<'a, I>(mechanisms: I) -> CapabilityValue
+    where
+        I: IntoIterator<Item = &'a SaslMechanism>,
+    {
+        let s = join_with_commas(mechanisms.into_iter().map(SaslMechanism::to_string));
+        CapabilityValue::try_from(s)
+            .expect("joined SASL mechanisms should be valid CapabilityValue")
+    }
+}
+
+/// A SASL mechanism name, as carried by the `sasl` capability's value (see
+/// [`CapabilityValue::sasl_mechanisms`]). Any mechanism name other than the
+/// three listed here parses into [`SaslMechanism::Other`] rather than
+/// failing, so no information is lost round-tripping back to a
+/// `CapabilityValue` via [`CapabilityValue::from_sasl_mechanisms`].
+#[derive(strum::AsRefStr, Clone, Debug, strum::Display, strum::EnumString, Eq, Hash, PartialEq)]
+pub enum SaslMechanism {
+    #[strum(serialize = "PLAIN")]
+    Plain,
+    #[strum(serialize = "EXTERNAL")]
+    External,
+    #[strum(serialize = "SCRAM-SHA-256")]
+    ScramSha256,
+    #[strum(default, transparent)]
+    Other(String),
+}
+
+impl From<&str> for SaslMechanism {
+    fn from(s: &str) -> SaslMechanism {
+        let Ok(mechanism) = s.parse() else {
+            unreachable!("SaslMechanism parsing should never fail");
+        };
+        mechanism
+    }
+}
+
 impl From<CapabilityValue> for MedialParam {
     fn from(value: CapabilityValue) -> MedialParam {
         MedialParam::try_from(value.into_inner())
@@ -950,6 +1099,7 @@ impl From<CapabilityValue> for FinalParam {
 pub struct ParseCapabilityValueError;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CapabilityRequest {
     pub capability: Capability,
     pub disable: bool,
@@ -1223,4 +1373,104 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn capability_value_mechanisms() {
+        let value = "EXTERNAL,DH-AES,PLAIN".parse::<CapabilityValue>().unwrap();
+        assert_eq!(value.mechanisms(), ["EXTERNAL", "DH-AES", "PLAIN"]);
+    }
+
+    #[test]
+    fn capability_value_mechanisms_single() {
+        let value = "PLAIN".parse::<CapabilityValue>().unwrap();
+        assert_eq!(value.mechanisms(), ["PLAIN"]);
+    }
+
+    #[test]
+    fn capability_value_key_value_pairs() {
+        let value = "duration=604800,port=6697"
+            .parse::<CapabilityValue>()
+            .unwrap();
+        assert_eq!(
+            value.key_value_pairs(),
+            [("duration", "604800"), ("port", "6697")]
+        );
+    }
+
+    #[test]
+    fn capability_value_key_value_pairs_bare_token() {
+        let value = "a=1,secure,b=2".parse::<CapabilityValue>().unwrap();
+        assert_eq!(
+            value.key_value_pairs(),
+            [("a", "1"), ("secure", ""), ("b", "2")]
+        );
+    }
+
+    #[test]
+    fn capability_known_registered() {
+        let cap = "server-time".parse::<Capability>().unwrap();
+        assert_eq!(cap.known(), Some(KnownCapability::ServerTime));
+    }
+
+    #[test]
+    fn capability_known_vendor_is_none() {
+        let cap = "example.org/dummy-cap".parse::<Capability>().unwrap();
+        assert_eq!(cap.known(), None);
+    }
+
+    #[test]
+    fn capability_known_unrecognized_is_none() {
+        let cap = "some-future-cap".parse::<Capability>().unwrap();
+        assert_eq!(cap.known(), None);
+    }
+
+    #[test]
+    fn known_capability_round_trips_through_capability() {
+        assert_eq!(
+            Capability::from(KnownCapability::MultiPrefix),
+            "multi-prefix".parse::<Capability>().unwrap()
+        );
+    }
+
+    #[test]
+    fn known_capability_other_round_trips() {
+        let cap = "example.org/dummy-cap".parse::<Capability>().unwrap();
+        let Ok(known) = cap.as_str().parse::<KnownCapability>() else {
+            unreachable!("KnownCapability parsing should never fail");
+        };
+        assert_eq!(known, KnownCapability::Other(cap.clone()));
+        assert_eq!(Capability::from(known), cap);
+    }
+
+    #[test]
+    fn capability_value_split_comma() {
+        let value = "EXTERNAL,PLAIN".parse::<CapabilityValue>().unwrap();
+        assert_eq!(value.split_comma(), ["EXTERNAL", "PLAIN"]);
+    }
+
+    #[test]
+    fn capability_value_sasl_mechanisms() {
+        let value = "EXTERNAL,DH-AES,PLAIN,SCRAM-SHA-256"
+            .parse::<CapabilityValue>()
+            .unwrap();
+        assert_eq!(
+            value.sasl_mechanisms(),
+            [
+                SaslMechanism::External,
+                SaslMechanism::Other(String::from("DH-AES")),
+                SaslMechanism::Plain,
+                SaslMechanism::ScramSha256,
+            ]
+        );
+    }
+
+    #[test]
+    fn capability_value_from_sasl_mechanisms() {
+        let mechanisms = [
+            SaslMechanism::Plain,
+            SaslMechanism::Other(String::from("DH-AES")),
+        ];
+        let value = CapabilityValue::from_sasl_mechanisms(&mechanisms);
+        assert_eq!(value, "PLAIN,DH-AES".parse::<CapabilityValue>().unwrap());
+    }
 }
@@ -0,0 +1,303 @@
+use super::{ClientMessage, ClientMessageError, ClientMessageParts};
+use crate::types::MsgTarget;
+use crate::{MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage, Verb};
+use std::fmt;
+
+/// A `CHATHISTORY` request, per
+/// <https://ircv3.net/specs/extensions/chathistory>.
+///
+/// The subcommand (`BEFORE`, `AFTER`, `LATEST`, `AROUND`, `BETWEEN`, or
+/// `TARGETS`) determines how many arguments follow it and what they mean, so
+/// — as with [`super::Mode`]'s mode arguments — the remaining parameters are
+/// kept as a raw [`ParameterList`] rather than broken out into named fields.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChatHistory {
+    subcommand: MedialParam,
+    arguments: ParameterList,
+}
+
+impl ChatHistory {
+    pub fn new(subcommand: MedialParam, arguments: ParameterList) -> ChatHistory {
+        ChatHistory {
+            subcommand,
+            arguments,
+        }
+    }
+
+    pub fn subcommand(&self) -> &MedialParam {
+        &self.subcommand
+    }
+
+    pub fn arguments(&self) -> &ParameterList {
+        &self.arguments
+    }
+
+    fn with_args<const N: usize>(subcommand: &str, args: [MedialParam; N]) -> ChatHistory {
+        let Ok(subcommand) = subcommand.parse::<MedialParam>() else {
+            unreachable!("CHATHISTORY subcommand name should always be a valid medial parameter");
+        };
+        let mut builder = ParameterList::builder();
+        for arg in args {
+            builder = builder.with_medial(arg);
+        }
+        ChatHistory {
+            subcommand,
+            arguments: builder.finish(),
+        }
+    }
+
+    /// Requests the most recent messages to `target`, either the latest
+    /// `limit` messages overall (if `reference` is `None`) or the latest
+    /// `limit` messages after `reference`.
+    pub fn latest(target: MsgTarget, reference: Option<MessageRef>, limit: u32) -> ChatHistory {
+        let reference = match reference {
+            Some(r) => r.into_param(),
+            None => "*"
+                .parse::<MedialParam>()
+                .expect("\"*\" should be a valid medial parameter"),
+        };
+        ChatHistory::with_args("LATEST", [target.into(), reference, limit_param(limit)])
+    }
+
+    /// Requests up to `limit` messages sent to `target` before `reference`.
+    pub fn before(target: MsgTarget, reference: MessageRef, limit: u32) -> ChatHistory {
+        ChatHistory::with_args(
+            "BEFORE",
+            [target.into(), reference.into_param(), limit_param(limit)],
+        )
+    }
+
+    /// Requests up to `limit` messages sent to `target` after `reference`.
+    pub fn after(target: MsgTarget, reference: MessageRef, limit: u32) -> ChatHistory {
+        ChatHistory::with_args(
+            "AFTER",
+            [target.into(), reference.into_param(), limit_param(limit)],
+        )
+    }
+
+    /// Requests up to `limit` messages sent to `target` around `reference`.
+    pub fn around(target: MsgTarget, reference: MessageRef, limit: u32) -> ChatHistory {
+        ChatHistory::with_args(
+            "AROUND",
+            [target.into(), reference.into_param(), limit_param(limit)],
+        )
+    }
+
+    /// Requests up to `limit` messages sent to `target` between `start` and
+    /// `end`.
+    pub fn between(
+        target: MsgTarget,
+        start: MessageRef,
+        end: MessageRef,
+        limit: u32,
+    ) -> ChatHistory {
+        ChatHistory::with_args(
+            "BETWEEN",
+            [
+                target.into(),
+                start.into_param(),
+                end.into_param(),
+                limit_param(limit),
+            ],
+        )
+    }
+
+    /// Requests up to `limit` targets with history between `start` and
+    /// `end`.
+    pub fn targets(start: MessageRef, end: MessageRef, limit: u32) -> ChatHistory {
+        ChatHistory::with_args(
+            "TARGETS",
+            [start.into_param(), end.into_param(), limit_param(limit)],
+        )
+    }
+}
+
+fn limit_param(limit: u32) -> MedialParam {
+    let Ok(p) = MedialParam::try_from(limit.to_string()) else {
+        unreachable!("a number should always be a valid medial parameter");
+    };
+    p
+}
+
+/// A message-reference selector for a [`ChatHistory`] request: either a
+/// message ID or a timestamp, per
+/// <https://ircv3.net/specs/extensions/chathistory#message-reference>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessageRef {
+    Msgid(String),
+    Timestamp(String),
+}
+
+impl MessageRef {
+    pub fn msgid<S: Into<String>>(id: S) -> MessageRef {
+        MessageRef::Msgid(id.into())
+    }
+
+    pub fn timestamp<S: Into<String>>(ts: S) -> MessageRef {
+        MessageRef::Timestamp(ts.into())
+    }
+
+    fn into_param(self) -> MedialParam {
+        let Ok(p) = MedialParam::try_from(self.to_string()) else {
+            unreachable!("a msgid=/timestamp= selector should always be a valid medial parameter");
+        };
+        p
+    }
+}
+
+impl fmt::Display for MessageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageRef::Msgid(id) => write!(f, "msgid={id}"),
+            MessageRef::Timestamp(ts) => write!(f, "timestamp={ts}"),
+        }
+    }
+}
+
+impl ClientMessageParts for ChatHistory {
+    fn into_parts(self) -> (Verb, ParameterList) {
+        (
+            Verb::ChatHistory,
+            ParameterList::builder()
+                .with_medial(self.subcommand)
+                .with_list(self.arguments),
+        )
+    }
+
+    fn to_irc_line(&self) -> String {
+        if self.arguments.is_empty() {
+            format!("CHATHISTORY {}", self.subcommand)
+        } else {
+            format!("CHATHISTORY {} {}", self.subcommand, self.arguments)
+        }
+    }
+}
+
+impl From<ChatHistory> for Message {
+    fn from(value: ChatHistory) -> Message {
+        Message::from(ClientMessage::from(value))
+    }
+}
+
+impl From<ChatHistory> for RawMessage {
+    fn from(value: ChatHistory) -> RawMessage {
+        RawMessage::from(ClientMessage::from(value))
+    }
+}
+
+impl TryFrom<ParameterList> for ChatHistory {
+    type Error = ClientMessageError;
+
+    fn try_from(params: ParameterList) -> Result<ChatHistory, ClientMessageError> {
+        let mut iter = params.into_iter();
+        let p1 = iter.next().ok_or(ParameterListSizeError::Exact {
+            required: 1,
+            received: 0,
+        })?;
+        let subcommand = MedialParam::try_from(p1.into_inner())?;
+        let arguments = iter.into_parameter_list();
+        Ok(ChatHistory {
+            subcommand,
+            arguments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(s: &str) -> MsgTarget {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn to_irc_line_latest_without_reference() {
+        let ch = ChatHistory::latest(target("#chan"), None, 50);
+        assert_eq!(ch.to_irc_line(), "CHATHISTORY LATEST #chan * 50");
+    }
+
+    #[test]
+    fn to_irc_line_latest_with_reference() {
+        let ch = ChatHistory::latest(target("#chan"), Some(MessageRef::msgid("abc123")), 50);
+        assert_eq!(ch.to_irc_line(), "CHATHISTORY LATEST #chan msgid=abc123 50");
+    }
+
+    #[test]
+    fn to_irc_line_before() {
+        let ch = ChatHistory::before(
+            target("#chan"),
+            MessageRef::timestamp("2023-01-01T00:00:00.000Z"),
+            50,
+        );
+        assert_eq!(
+            ch.to_irc_line(),
+            "CHATHISTORY BEFORE #chan timestamp=2023-01-01T00:00:00.000Z 50"
+        );
+    }
+
+    #[test]
+    fn to_irc_line_after() {
+        let ch = ChatHistory::after(target("#chan"), MessageRef::msgid("abc123"), 50);
+        assert_eq!(ch.to_irc_line(), "CHATHISTORY AFTER #chan msgid=abc123 50");
+    }
+
+    #[test]
+    fn to_irc_line_around() {
+        let ch = ChatHistory::around(target("#chan"), MessageRef::msgid("abc123"), 50);
+        assert_eq!(ch.to_irc_line(), "CHATHISTORY AROUND #chan msgid=abc123 50");
+    }
+
+    #[test]
+    fn to_irc_line_between() {
+        let ch = ChatHistory::between(
+            target("#chan"),
+            MessageRef::msgid("abc123"),
+            MessageRef::msgid("def456"),
+            50,
+        );
+        assert_eq!(
+            ch.to_irc_line(),
+            "CHATHISTORY BETWEEN #chan msgid=abc123 msgid=def456 50"
+        );
+    }
+
+    #[test]
+    fn to_irc_line_targets() {
+        let ch = ChatHistory::targets(
+            MessageRef::timestamp("2023-01-01T00:00:00.000Z"),
+            MessageRef::timestamp("2023-01-02T00:00:00.000Z"),
+            10,
+        );
+        assert_eq!(
+            ch.to_irc_line(),
+            "CHATHISTORY TARGETS timestamp=2023-01-01T00:00:00.000Z timestamp=2023-01-02T00:00:00.000Z 10"
+        );
+    }
+
+    #[test]
+    fn try_from_params_round_trips() {
+        let ch = ChatHistory::latest(target("#chan"), Some(MessageRef::msgid("abc123")), 50);
+        let line = ch.to_irc_line();
+        let (_, params) = ch.into_parts();
+        let ch2 = ChatHistory::try_from(params).unwrap();
+        assert_eq!(ch2.to_irc_line(), line);
+    }
+
+    #[test]
+    fn try_from_params_missing_subcommand_is_error() {
+        let params = ParameterList::builder().finish();
+        assert!(ChatHistory::try_from(params).is_err());
+    }
+
+    #[test]
+    fn message_ref_display() {
+        assert_eq!(MessageRef::msgid("abc123").to_string(), "msgid=abc123");
+        assert_eq!(
+            MessageRef::timestamp("2023-01-01T00:00:00.000Z").to_string(),
+            "timestamp=2023-01-01T00:00:00.000Z"
+        );
+    }
+}
@@ -1,11 +1,22 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
-use crate::{MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage, Verb};
+use crate::{
+    Address, MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage, Verb,
+};
 use std::fmt::Write;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Connect {
     target_server: MedialParam,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     port: Option<u16>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     remote_server: Option<MedialParam>,
 }
 
@@ -51,6 +62,16 @@ impl Connect {
     }
 }
 
+impl Address for Connect {
+    fn host(&self) -> &str {
+        self.target_server.as_str()
+    }
+
+    fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
 impl ClientMessageParts for Connect {
     fn into_parts(self) -> (Verb, ParameterList) {
         let mut builder = ParameterList::builder().with_medial(self.target_server);
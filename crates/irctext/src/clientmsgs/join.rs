@@ -1,14 +1,16 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
 use crate::types::{Channel, Key};
-use crate::util::{DisplayMaybeFinal, join_with_commas, split_param};
+use crate::util::{join_with_commas, split_param, DisplayMaybeFinal};
 use crate::{
     FinalParam, MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage, Verb,
 };
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Join(InnerJoin);
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum InnerJoin {
     Channels {
         channels: Vec<Channel>,
@@ -96,6 +98,65 @@ impl Join {
         }
     }
 
+    /// Splits this `Join` into one or more `Join`s, greedily packing
+    /// channels (keeping each one paired with its key, if any) into as few
+    /// lines as possible such that each line's `to_irc_line()` stays within
+    /// `max_line_len` bytes and, if `chanlimit` is given, its channel count
+    /// stays within that limit.
+    ///
+    /// A channel that alone exceeds `max_line_len` is still returned, as its
+    /// own single-channel `Join`, rather than silently dropped.
+    ///
+    /// [`Join::new_zero`] is returned unchanged, as a single-element `Vec`.
+    pub fn split(&self, max_line_len: usize, chanlimit: Option<usize>) -> Vec<Join> {
+        let (channels, keys) = match &self.0 {
+            InnerJoin::Channels { channels, keys } => (channels, keys),
+            InnerJoin::Zero => return vec![self.clone()],
+        };
+        let mut out = Vec::new();
+        let mut pending_channels = Vec::new();
+        let mut pending_keys = Vec::new();
+        for (i, channel) in channels.iter().enumerate() {
+            let key = keys.get(i);
+            let mut candidate_channels = pending_channels.clone();
+            candidate_channels.push(channel.clone());
+            let mut candidate_keys = pending_keys.clone();
+            if let Some(key) = key {
+                candidate_keys.push(key.clone());
+            }
+            let fits = chanlimit.map_or(true, |limit| candidate_channels.len() <= limit)
+                && Join(InnerJoin::Channels {
+                    channels: candidate_channels.clone(),
+                    keys: candidate_keys.clone(),
+                })
+                .to_irc_line()
+                .len()
+                    <= max_line_len;
+            if fits {
+                pending_channels = candidate_channels;
+                pending_keys = candidate_keys;
+            } else {
+                if !pending_channels.is_empty() {
+                    out.push(Join(InnerJoin::Channels {
+                        channels: std::mem::take(&mut pending_channels),
+                        keys: std::mem::take(&mut pending_keys),
+                    }));
+                }
+                pending_channels.push(channel.clone());
+                if let Some(key) = key {
+                    pending_keys.push(key.clone());
+                }
+            }
+        }
+        if !pending_channels.is_empty() {
+            out.push(Join(InnerJoin::Channels {
+                channels: pending_channels,
+                keys: pending_keys,
+            }));
+        }
+        out
+    }
+
     fn keys_param(&self) -> Option<FinalParam> {
         let keys = self.keys();
         if keys.is_empty() {
@@ -167,3 +228,92 @@ impl TryFrom<ParameterList> for Join {
         }
     }
 }
+
+// `InnerJoin::Channels.channels` is deserialized via the derived `InnerJoin`
+// impl rather than deriving `Deserialize` directly on `Join`, so that an
+// empty list -- which every public constructor forbids and
+// `channels_param()` assumes can't happen -- is rejected here instead of
+// panicking later. Likewise, every public constructor either leaves `keys`
+// empty or gives it exactly one key per channel, so a `keys` list of any
+// other length is rejected too.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Join {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let inner = <InnerJoin as serde::Deserialize>::deserialize(deserializer)?;
+        if let InnerJoin::Channels {
+            ref channels,
+            ref keys,
+        } = inner
+        {
+            if channels.is_empty() {
+                return Err(serde::de::Error::custom("Join channels must not be empty"));
+            }
+            if !keys.is_empty() && keys.len() != channels.len() {
+                return Err(serde::de::Error::custom(
+                    "Join keys must be empty or match channels in length",
+                ));
+            }
+        }
+        Ok(Join(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chan(s: &str) -> Channel {
+        s.parse().unwrap()
+    }
+
+    fn key(s: &str) -> Key {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn split_fits_on_one_line() {
+        let join = Join::new_multi([chan("#a"), chan("#b")]).unwrap();
+        let parts = join.split(512, None);
+        assert_eq!(parts, [join]);
+    }
+
+    #[test]
+    fn split_by_chanlimit() {
+        let join = Join::new_multi([chan("#a"), chan("#b"), chan("#c")]).unwrap();
+        let parts = join.split(512, Some(2));
+        assert_eq!(
+            parts,
+            [
+                Join::new_multi([chan("#a"), chan("#b")]).unwrap(),
+                Join::new_multi([chan("#c")]).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_by_line_length_keeps_keys_paired() {
+        let join =
+            Join::new_multi_with_keys([(chan("#alpha"), key("k1")), (chan("#beta"), key("k2"))])
+                .unwrap();
+        let parts = join.split("JOIN #alpha k1".len(), None);
+        assert_eq!(
+            parts,
+            [
+                Join::new_with_key(chan("#alpha"), key("k1")),
+                Join::new_with_key(chan("#beta"), key("k2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_oversized_channel_kept_alone() {
+        let big = chan("#aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let join = Join::new_multi([big.clone(), chan("#b")]).unwrap();
+        let parts = join.split(10, None);
+        assert_eq!(parts, [Join::new(big), Join::new(chan("#b"))]);
+    }
+}
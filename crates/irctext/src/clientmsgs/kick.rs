@@ -4,9 +4,14 @@ use crate::util::{DisplayMaybeFinal, join_with_commas, split_param};
 use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Kick {
     channel: Channel,
     users: Vec<Nickname>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     comment: Option<FinalParam>,
 }
 
@@ -131,3 +136,35 @@ impl TryFrom<ParameterList> for Kick {
         })
     }
 }
+
+// `users` is deserialized via a repr struct rather than derived directly so
+// that an empty list -- which every public constructor forbids and
+// `users_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct KickRepr {
+    channel: Channel,
+    users: Vec<Nickname>,
+    #[serde(default)]
+    comment: Option<FinalParam>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Kick {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <KickRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.users.is_empty() {
+            return Err(serde::de::Error::custom("Kick.users must not be empty"));
+        }
+        Ok(Kick {
+            channel: repr.channel,
+            users: repr.users,
+            comment: repr.comment,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use crate::types::Nickname;
 use crate::{FinalParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Kill {
     nickname: Nickname,
     comment: FinalParam,
@@ -1,12 +1,14 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
-use crate::channel::channel_prefixed;
+use crate::types::ParseEListCondError;
+use crate::types::channel::channel_prefixed;
 use crate::util::{join_with_commas, split_param};
 use crate::{
-    Channel, EListCond, MedialParam, Message, ParameterList, ParameterListSizeError, RawMessage,
-    Verb,
+    Channel, EListCond, FinalParam, MedialParam, Message, ParameterList, ParameterListSizeError,
+    RawMessage, Verb,
 };
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct List {
     channels: Vec<Channel>,
     elistconds: Vec<EListCond>,
@@ -45,6 +47,34 @@ impl List {
         }
     }
 
+    /// Like `new_with_elistconds`, but building each condition from a typed
+    /// [`EListFilter`] instead of a raw [`EListCond`] string.
+    pub fn new_with_filters<I: IntoIterator<Item = EListFilter>>(
+        filters: I,
+    ) -> Result<List, ParseEListCondError> {
+        List::new_with_channels_and_filters(std::iter::empty(), filters)
+    }
+
+    /// Like `new_with_channels_and_elistconds`, but building each condition
+    /// from a typed [`EListFilter`] instead of a raw [`EListCond`] string.
+    pub fn new_with_channels_and_filters<I, J>(
+        channels: I,
+        filters: J,
+    ) -> Result<List, ParseEListCondError>
+    where
+        I: IntoIterator<Item = Channel>,
+        J: IntoIterator<Item = EListFilter>,
+    {
+        let elistconds = filters
+            .into_iter()
+            .map(EListFilter::into_elistcond)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(List {
+            channels: Vec::from_iter(channels),
+            elistconds,
+        })
+    }
+
     pub fn channels(&self) -> &[Channel] {
         &self.channels
     }
@@ -53,6 +83,16 @@ impl List {
         &self.elistconds
     }
 
+    /// Re-parses each of `elistconds()` into a typed [`EListFilter`], the
+    /// reverse of [`List::new_with_filters`]/
+    /// [`List::new_with_channels_and_filters`]. Any condition this crate
+    /// doesn't recognize is returned as [`EListFilter::Other`] rather than
+    /// dropped, so a server-specific `ELIST` extension this crate predates
+    /// round-trips unchanged.
+    pub fn elist_filters(&self) -> Vec<EListFilter> {
+        self.elistconds.iter().map(EListFilter::from_elistcond).collect()
+    }
+
     fn channels_param(&self) -> Option<MedialParam> {
         if self.channels.is_empty() {
             None
@@ -139,7 +179,7 @@ impl TryFrom<ParameterList> for List {
                     if iter.next().is_some() {
                         return Err(ClientMessageError::ParamQty(
                             ParameterListSizeError::Exact {
-                                requested: 1,
+                                required: 1,
                                 received: len,
                             },
                         ));
@@ -156,11 +196,191 @@ impl TryFrom<ParameterList> for List {
         } else {
             Err(ClientMessageError::ParamQty(
                 ParameterListSizeError::Range {
-                    min_requested: 0,
-                    max_requested: 2,
+                    min_required: 0,
+                    max_required: 2,
                     received: len,
                 },
             ))
         }
     }
 }
+
+/// A typed `ELIST` search filter, built into an [`EListCond`] token by
+/// [`List::new_with_filters`]/[`List::new_with_channels_and_filters`]; see
+/// <https://modern.ircdocs.horse/#elist-parameter>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EListFilter {
+    /// `>n`: channels with more than `n` users (flag `U`).
+    MinUsers(u32),
+    /// `<n`: channels with fewer than `n` users (flag `U`).
+    MaxUsers(u32),
+    /// `C<n`: channels created less than `n` seconds ago (flag `C`).
+    CreatedWithin(u64),
+    /// `C>n`: channels created more than `n` seconds ago (flag `C`).
+    CreatedOlderThan(u64),
+    /// `T<n`: channels whose topic was set less than `n` seconds ago (flag
+    /// `T`).
+    TopicChangedWithin(u64),
+    /// `T>n`: channels whose topic was set more than `n` seconds ago (flag
+    /// `T`).
+    TopicChangedOlderThan(u64),
+    /// `*mask*`: channels whose name matches `mask` (`*`/`?` wildcards
+    /// allowed) (flag `M`).
+    Mask(String),
+    /// `!mask`: channels whose name does *not* match `mask` (flag `N`).
+    NotMask(String),
+    /// A condition this crate doesn't recognize, preserved as-is (by
+    /// [`List::elist_filters`]) for forward compatibility with
+    /// server-specific `ELIST` extensions.
+    Other(EListCond),
+}
+
+impl EListFilter {
+    fn into_elistcond(self) -> Result<EListCond, ParseEListCondError> {
+        let s = match self {
+            EListFilter::MinUsers(n) => format!(">{n}"),
+            EListFilter::MaxUsers(n) => format!("<{n}"),
+            EListFilter::CreatedWithin(secs) => format!("C<{secs}"),
+            EListFilter::CreatedOlderThan(secs) => format!("C>{secs}"),
+            EListFilter::TopicChangedWithin(secs) => format!("T<{secs}"),
+            EListFilter::TopicChangedOlderThan(secs) => format!("T>{secs}"),
+            EListFilter::Mask(mask) => mask,
+            EListFilter::NotMask(mask) => format!("!{mask}"),
+            EListFilter::Other(cond) => return Ok(cond),
+        };
+        EListCond::try_from(s)
+    }
+
+    /// Parses a raw [`EListCond`] into a typed `EListFilter`, falling back
+    /// to [`EListFilter::Other`] for any token that looks like a
+    /// recognized shape (`>`/`<`/`C>`/`C<`/`T>`/`T<`) but doesn't parse, or
+    /// that uses a flag letter this crate doesn't know about.
+    fn from_elistcond(cond: &EListCond) -> EListFilter {
+        let s = cond.as_str();
+        if let Some(mask) = s.strip_prefix('!') {
+            return EListFilter::NotMask(mask.to_owned());
+        }
+        if let Some(rest) = s.strip_prefix('>') {
+            return rest
+                .parse()
+                .map(EListFilter::MinUsers)
+                .unwrap_or_else(|_| EListFilter::Other(cond.clone()));
+        }
+        if let Some(rest) = s.strip_prefix('<') {
+            return rest
+                .parse()
+                .map(EListFilter::MaxUsers)
+                .unwrap_or_else(|_| EListFilter::Other(cond.clone()));
+        }
+        let bytes = s.as_bytes();
+        if bytes.len() >= 2 && bytes[0].is_ascii_uppercase() && matches!(bytes[1], b'<' | b'>') {
+            let rest = &s[2..];
+            return match (bytes[0], bytes[1], rest.parse::<u64>()) {
+                (b'C', b'<', Ok(secs)) => EListFilter::CreatedWithin(secs),
+                (b'C', b'>', Ok(secs)) => EListFilter::CreatedOlderThan(secs),
+                (b'T', b'<', Ok(secs)) => EListFilter::TopicChangedWithin(secs),
+                (b'T', b'>', Ok(secs)) => EListFilter::TopicChangedOlderThan(secs),
+                _ => EListFilter::Other(cond.clone()),
+            };
+        }
+        EListFilter::Mask(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chan(s: &str) -> Channel {
+        s.parse().unwrap()
+    }
+
+    fn cond(s: &str) -> EListCond {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn to_irc_line_bare() {
+        assert_eq!(List::new().to_irc_line(), "LIST");
+    }
+
+    #[test]
+    fn to_irc_line_channels_only() {
+        let list = List::new_with_channels([chan("#a"), chan("#b")]);
+        assert_eq!(list.to_irc_line(), "LIST #a,#b");
+    }
+
+    #[test]
+    fn to_irc_line_channels_and_conditions() {
+        let list =
+            List::new_with_channels_and_elistconds([chan("#a")], [cond("<10"), cond("C>120")]);
+        assert_eq!(list.to_irc_line(), "LIST #a <10,C>120");
+    }
+
+    #[test]
+    fn try_from_params_channels_and_conditions() {
+        let params = ParameterList::builder()
+            .with_medial(MedialParam::try_from("#a,#b").unwrap())
+            .with_final(FinalParam::try_from("<10,C>120").unwrap());
+        let list = List::try_from(params).unwrap();
+        assert_eq!(list.channels(), [chan("#a"), chan("#b")]);
+        assert_eq!(list.elistconds(), [cond("<10"), cond("C>120")]);
+    }
+
+    #[test]
+    fn try_from_params_conditions_only() {
+        let params = ParameterList::builder().with_final(FinalParam::try_from("*foo*").unwrap());
+        let list = List::try_from(params).unwrap();
+        assert!(list.channels().is_empty());
+        assert_eq!(list.elistconds(), [cond("*foo*")]);
+    }
+
+    #[test]
+    fn new_with_filters_builds_expected_conds() {
+        let list = List::new_with_filters([
+            EListFilter::MaxUsers(10),
+            EListFilter::CreatedOlderThan(120),
+            EListFilter::NotMask("spam*".to_owned()),
+        ])
+        .unwrap();
+        assert_eq!(
+            list.elistconds(),
+            [cond("<10"), cond("C>120"), cond("!spam*")]
+        );
+    }
+
+    #[test]
+    fn elist_filters_round_trips_known_conds() {
+        let list = List::new_with_filters([
+            EListFilter::MinUsers(5),
+            EListFilter::MaxUsers(10),
+            EListFilter::CreatedWithin(60),
+            EListFilter::CreatedOlderThan(120),
+            EListFilter::TopicChangedWithin(30),
+            EListFilter::TopicChangedOlderThan(90),
+            EListFilter::Mask("*foo*".to_owned()),
+            EListFilter::NotMask("spam*".to_owned()),
+        ])
+        .unwrap();
+        assert_eq!(
+            list.elist_filters(),
+            [
+                EListFilter::MinUsers(5),
+                EListFilter::MaxUsers(10),
+                EListFilter::CreatedWithin(60),
+                EListFilter::CreatedOlderThan(120),
+                EListFilter::TopicChangedWithin(30),
+                EListFilter::TopicChangedOlderThan(90),
+                EListFilter::Mask("*foo*".to_owned()),
+                EListFilter::NotMask("spam*".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn elist_filters_preserves_unrecognized_flag() {
+        let list = List::new_with_elistconds([cond("Z>5")]);
+        assert_eq!(list.elist_filters(), [EListFilter::Other(cond("Z>5"))]);
+    }
+}
@@ -1,7 +1,9 @@
 mod admin;
 mod authenticate;
 mod away;
+mod batch;
 mod cap;
+mod chathistory;
 mod connect;
 mod error;
 mod help;
@@ -28,6 +30,7 @@ mod quit;
 mod rehash;
 mod restart;
 mod squit;
+mod starttls;
 mod stats;
 mod time;
 mod topic;
@@ -41,7 +44,9 @@ mod whowas;
 pub use self::admin::*;
 pub use self::authenticate::*;
 pub use self::away::*;
+pub use self::batch::*;
 pub use self::cap::*;
+pub use self::chathistory::*;
 pub use self::connect::*;
 pub use self::error::*;
 pub use self::help::*;
@@ -68,6 +73,7 @@ pub use self::quit::*;
 pub use self::rehash::*;
 pub use self::restart::*;
 pub use self::squit::*;
+pub use self::starttls::*;
 pub use self::stats::*;
 pub use self::time::*;
 pub use self::topic::*;
@@ -98,13 +104,24 @@ pub trait ClientMessageParts {
     fn to_irc_line(&self) -> String;
 }
 
+/// A parsed client-to-server IRC message, dispatched to one of the
+/// command-specific structs/enums below based on its verb.
+///
+/// With the `serde` feature enabled, this (de)serializes directly from the
+/// wrapped command type's own fields -- e.g. a `PRIVMSG` is
+/// `{"PrivMsg": {"targets": [...], "text": "..."}}` -- rather than going
+/// through [`RawMessage`]'s undecoded `tags`/`source`/`command`/`params`
+/// representation.
 #[enum_dispatch(ClientMessageParts)] // This also gives us From and TryInto
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClientMessage {
     Admin,
     Authenticate,
     Away,
+    Batch,
     Cap,
+    ChatHistory,
     Connect,
     Error,
     Help,
@@ -131,6 +148,7 @@ pub enum ClientMessage {
     Rehash,
     Restart,
     Squit,
+    StartTls,
     Stats,
     Time,
     Topic,
@@ -152,7 +170,9 @@ impl ClientMessage {
             Verb::Admin => Admin::try_from(params).map(ClientMessage::Admin),
             Verb::Authenticate => Authenticate::try_from(params).map(ClientMessage::Authenticate),
             Verb::Away => Away::try_from(params).map(ClientMessage::Away),
+            Verb::Batch => Batch::try_from(params).map(ClientMessage::Batch),
             Verb::Cap => Cap::try_from(params).map(ClientMessage::Cap),
+            Verb::ChatHistory => ChatHistory::try_from(params).map(ClientMessage::ChatHistory),
             Verb::Connect => Connect::try_from(params).map(ClientMessage::Connect),
             Verb::Error => Error::try_from(params).map(ClientMessage::Error),
             Verb::Help => Help::try_from(params).map(ClientMessage::Help),
@@ -179,6 +199,7 @@ impl ClientMessage {
             Verb::Rehash => Rehash::try_from(params).map(ClientMessage::Rehash),
             Verb::Restart => Restart::try_from(params).map(ClientMessage::Restart),
             Verb::Squit => Squit::try_from(params).map(ClientMessage::Squit),
+            Verb::StartTls => StartTls::try_from(params).map(ClientMessage::StartTls),
             Verb::Stats => Stats::try_from(params).map(ClientMessage::Stats),
             Verb::Time => Time::try_from(params).map(ClientMessage::Time),
             Verb::Topic => Topic::try_from(params).map(ClientMessage::Topic),
@@ -197,6 +218,7 @@ impl ClientMessage {
 impl From<ClientMessage> for Message {
     fn from(value: ClientMessage) -> Message {
         Message {
+            tags: None,
             source: None,
             payload: Payload::ClientMessage(value),
         }
@@ -256,6 +278,9 @@ pub enum ClientMessageError {
     #[error("failed to parse username string")]
     Username(#[from] TryFromStringError<ParseUsernameError>),
 
+    #[error("failed to parse WHOX query string")]
+    Whox(#[from] TryFromStringError<ParseWhoxQueryError>),
+
     #[error("failed to parse integer string {string:?}: {inner}")]
     Int {
         string: String,
@@ -4,8 +4,13 @@ use crate::{Message, ParameterList, ParameterListSizeError, RawMessage, Verb};
 use std::fmt::Write;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mode {
     target: ModeTarget,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     modestring: Option<ModeString>,
     arguments: ParameterList,
 }
@@ -93,7 +98,7 @@ impl TryFrom<ParameterList> for Mode {
     fn try_from(params: ParameterList) -> Result<Mode, ClientMessageError> {
         let mut iter = params.into_iter();
         let p1 = iter.next().ok_or(ParameterListSizeError::Exact {
-            requested: 1,
+            required: 1,
             received: 0,
         })?;
         let target = ModeTarget::try_from(p1.into_inner())?;
@@ -110,3 +115,62 @@ impl TryFrom<ParameterList> for Mode {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(s: &str) -> ModeTarget {
+        s.parse().unwrap()
+    }
+
+    fn modestring(s: &str) -> ModeString {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn to_irc_line_query() {
+        let mode = Mode::new(target("#chan"));
+        assert_eq!(mode.to_irc_line(), "MODE #chan");
+    }
+
+    #[test]
+    fn to_irc_line_without_arguments() {
+        let mode = Mode::new_with_modestring(target("#chan"), modestring("+nt"));
+        assert_eq!(mode.to_irc_line(), "MODE #chan +nt");
+    }
+
+    #[test]
+    fn to_irc_line_with_arguments() {
+        let mut builder = ParameterList::builder();
+        builder = builder.with_medial("alice".parse().unwrap());
+        let mode = Mode::new_with_arguments(target("#chan"), modestring("+o"), builder.finish());
+        assert_eq!(mode.to_irc_line(), "MODE #chan +o alice");
+    }
+
+    #[test]
+    fn try_from_params_round_trips() {
+        let mut builder = ParameterList::builder();
+        builder = builder.with_medial("alice".parse().unwrap());
+        let mode = Mode::new_with_arguments(target("#chan"), modestring("+o"), builder.finish());
+        let line = mode.to_irc_line();
+        let (_, params) = mode.into_parts();
+        let mode2 = Mode::try_from(params).unwrap();
+        assert_eq!(mode2.to_irc_line(), line);
+    }
+
+    #[test]
+    fn try_from_params_query_only() {
+        let mut builder = ParameterList::builder();
+        builder = builder.with_medial("#chan".parse().unwrap());
+        let mode = Mode::try_from(builder.finish()).unwrap();
+        assert_eq!(mode.target(), &target("#chan"));
+        assert_eq!(mode.modestring(), None);
+    }
+
+    #[test]
+    fn try_from_params_empty_is_error() {
+        let params = ParameterList::builder().finish();
+        assert!(Mode::try_from(params).is_err());
+    }
+}
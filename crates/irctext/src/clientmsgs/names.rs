@@ -1,16 +1,32 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
-use crate::util::{join_with_commas, split_param};
+use crate::util::{join_with_commas, split_param, DisplayMaybeFinal};
 use crate::{Channel, FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Names {
     channels: Vec<Channel>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    target: Option<FinalParam>,
 }
 
 impl Names {
     pub fn new(channel: Channel) -> Names {
         Names {
             channels: vec![channel],
+            target: None,
+        }
+    }
+
+    /// Like `new`, but forwarding the query to the given server, per RFC
+    /// 2812's `NAMES <channel>{,<channel>} [<target>]`.
+    pub fn new_with_target(channel: Channel, target: FinalParam) -> Names {
+        Names {
+            channels: vec![channel],
+            target: Some(target),
         }
     }
 
@@ -19,7 +35,26 @@ impl Names {
         if channels.is_empty() {
             None
         } else {
-            Some(Names { channels })
+            Some(Names {
+                channels,
+                target: None,
+            })
+        }
+    }
+
+    /// Like `new_many`, but forwarding the query to the given server.
+    pub fn new_many_with_target<I: IntoIterator<Item = Channel>>(
+        channels: I,
+        target: FinalParam,
+    ) -> Option<Names> {
+        let channels = channels.into_iter().collect::<Vec<_>>();
+        if channels.is_empty() {
+            None
+        } else {
+            Some(Names {
+                channels,
+                target: Some(target),
+            })
         }
     }
 
@@ -31,6 +66,10 @@ impl Names {
         self.channels
     }
 
+    pub fn target(&self) -> Option<&FinalParam> {
+        self.target.as_ref()
+    }
+
     fn channels_param(&self) -> MedialParam {
         assert!(
             !self.channels.is_empty(),
@@ -43,16 +82,18 @@ impl Names {
 
 impl ClientMessageParts for Names {
     fn into_parts(self) -> (Verb, ParameterList) {
-        (
-            Verb::Names,
-            ParameterList::builder()
-                .with_medial(self.channels_param())
-                .finish(),
-        )
+        let params = ParameterList::builder()
+            .with_medial(self.channels_param())
+            .maybe_with_final(self.target);
+        (Verb::Names, params)
     }
 
     fn to_irc_line(&self) -> String {
-        format!("NAMES {}", self.channels_param())
+        format!(
+            "NAMES {}{}",
+            self.channels_param(),
+            DisplayMaybeFinal(self.target.as_ref())
+        )
     }
 }
 
@@ -72,8 +113,82 @@ impl TryFrom<ParameterList> for Names {
     type Error = ClientMessageError;
 
     fn try_from(params: ParameterList) -> Result<Names, ClientMessageError> {
-        let (p,): (FinalParam,) = params.try_into()?;
+        let (p, target): (MedialParam, Option<FinalParam>) = params.try_into()?;
         let channels = split_param::<Channel>(p.as_str())?;
-        Ok(Names { channels })
+        Ok(Names { channels, target })
+    }
+}
+
+// `channels` is deserialized via a repr struct rather than derived directly
+// so that an empty list -- which every public constructor forbids and
+// `channels_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct NamesRepr {
+    channels: Vec<Channel>,
+    #[serde(default)]
+    target: Option<FinalParam>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Names {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <NamesRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.channels.is_empty() {
+            return Err(serde::de::Error::custom("Names.channels must not be empty"));
+        }
+        Ok(Names {
+            channels: repr.channels,
+            target: repr.target,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chan(s: &str) -> Channel {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn to_irc_line_without_target() {
+        let names = Names::new(chan("#chan"));
+        assert_eq!(names.to_irc_line(), "NAMES #chan");
+    }
+
+    #[test]
+    fn to_irc_line_with_target() {
+        let names = Names::new_with_target(chan("#chan"), "irc.example.com".parse().unwrap());
+        assert_eq!(names.to_irc_line(), "NAMES #chan irc.example.com");
+    }
+
+    #[test]
+    fn try_from_params_round_trips_with_target() {
+        let names = Names::new_many_with_target(
+            [chan("#chan1"), chan("#chan2")],
+            "irc.example.com".parse().unwrap(),
+        )
+        .unwrap();
+        let line = names.to_irc_line();
+        let (_, params) = names.clone().into_parts();
+        assert_eq!(Names::try_from(params).unwrap(), names);
+        assert_eq!(line, "NAMES #chan1,#chan2 irc.example.com");
+    }
+
+    #[test]
+    fn try_from_params_without_target() {
+        let params = ParameterList::builder()
+            .with_medial("#chan".parse().unwrap())
+            .finish();
+        let names = Names::try_from(params).unwrap();
+        assert_eq!(names.channels(), [chan("#chan")]);
+        assert_eq!(names.target(), None);
     }
 }
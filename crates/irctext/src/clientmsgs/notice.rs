@@ -1,9 +1,11 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
+use crate::ctcp::CtcpMessage;
 use crate::types::MsgTarget;
 use crate::util::{join_with_commas, split_param};
 use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Notice {
     targets: Vec<MsgTarget>,
     text: FinalParam,
@@ -38,6 +40,13 @@ impl Notice {
         &self.text
     }
 
+    /// Returns the CTCP message carried in [`text`](Notice::text), if any
+    /// -- typically a reply to a CTCP query received in a `PRIVMSG`.
+    /// Equivalent to `self.text().as_ctcp()`.
+    pub fn as_ctcp(&self) -> Option<CtcpMessage> {
+        self.text.as_ctcp()
+    }
+
     fn targets_param(&self) -> MedialParam {
         assert!(
             !self.targets.is_empty(),
@@ -89,3 +98,32 @@ impl TryFrom<ParameterList> for Notice {
         Ok(Notice { targets, text })
     }
 }
+
+// `targets` is deserialized via a repr struct rather than derived directly
+// so that an empty list -- which every public constructor forbids and
+// `targets_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct NoticeRepr {
+    targets: Vec<MsgTarget>,
+    text: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Notice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <NoticeRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.targets.is_empty() {
+            return Err(serde::de::Error::custom("Notice.targets must not be empty"));
+        }
+        Ok(Notice {
+            targets: repr.targets,
+            text: repr.text,
+        })
+    }
+}
@@ -3,8 +3,13 @@ use crate::util::{join_with_commas, split_param, DisplayMaybeFinal};
 use crate::{Channel, FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Part {
     channels: Vec<Channel>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     reason: Option<FinalParam>,
 }
 
@@ -110,3 +115,33 @@ impl TryFrom<ParameterList> for Part {
         Ok(Part { channels, reason })
     }
 }
+
+// `channels` is deserialized via a repr struct rather than derived directly
+// so that an empty list -- which every public constructor forbids and
+// `channels_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PartRepr {
+    channels: Vec<Channel>,
+    #[serde(default)]
+    reason: Option<FinalParam>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Part {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <PartRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.channels.is_empty() {
+            return Err(serde::de::Error::custom("Part.channels must not be empty"));
+        }
+        Ok(Part {
+            channels: repr.channels,
+            reason: repr.reason,
+        })
+    }
+}
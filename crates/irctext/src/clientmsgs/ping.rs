@@ -2,6 +2,7 @@ use super::{ClientMessage, ClientMessageError, ClientMessageParts, Pong};
 use crate::{FinalParam, Message, ParameterList, RawMessage, ToIrcLine, Verb};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ping {
     token: FinalParam,
 }
@@ -1,9 +1,11 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
+use crate::ctcp::{CtcpMessage, ParseCtcpParamsError};
 use crate::types::MsgTarget;
 use crate::util::{join_with_commas, split_param};
 use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PrivMsg {
     targets: Vec<MsgTarget>,
     text: FinalParam,
@@ -30,6 +32,16 @@ impl PrivMsg {
         }
     }
 
+    /// Constructs a `PRIVMSG` containing a CTCP `ACTION` (a.k.a. `/me`) with
+    /// the given text.
+    pub fn new_action<T: Into<MsgTarget>>(
+        target: T,
+        text: &str,
+    ) -> Result<PrivMsg, ParseCtcpParamsError> {
+        let ctcp = CtcpMessage::new_action(text)?;
+        Ok(PrivMsg::new(target, ctcp.into()))
+    }
+
     pub fn targets(&self) -> &[MsgTarget] {
         &self.targets
     }
@@ -38,6 +50,12 @@ impl PrivMsg {
         &self.text
     }
 
+    /// Returns the CTCP message carried in [`text`](PrivMsg::text), if any.
+    /// Equivalent to `self.text().as_ctcp()`.
+    pub fn as_ctcp(&self) -> Option<CtcpMessage> {
+        self.text.as_ctcp()
+    }
+
     fn targets_param(&self) -> MedialParam {
         assert!(
             !self.targets.is_empty(),
@@ -47,6 +65,77 @@ impl PrivMsg {
         MedialParam::try_from(s)
             .expect("comma-separated channels and/or nicknames should be a valid MedialParam")
     }
+
+    fn with_text(&self, text: &str) -> PrivMsg {
+        PrivMsg {
+            targets: self.targets.clone(),
+            text: FinalParam::try_from(text.to_owned())
+                .expect("substring of a FinalParam should be one too"),
+        }
+    }
+
+    /// Splits this message into one or more `PrivMsg`s, each reusing the
+    /// same [`targets`](PrivMsg::targets), such that each one's
+    /// `to_irc_line()` plus the trailing CRLF fits within `max_len` bytes
+    /// (pass [`DEFAULT_MAX_LINE_LEN`] for the usual 512-byte IRC line
+    /// budget).
+    ///
+    /// [`text`](PrivMsg::text) is packed greedily, preferring to break at
+    /// the last ASCII space before the budget runs out; a single "word"
+    /// too long to fit on its own line is hard-split on a UTF-8 char
+    /// boundary instead, the same way [`Join::split`](super::Join::split)
+    /// keeps an oversized channel rather than dropping it.
+    ///
+    /// An empty message, or one that already fits, is returned unchanged
+    /// as a single-element `Vec`.  So is any message when `max_len` leaves
+    /// no room for text at all; and a single character wider than the
+    /// remaining budget still goes out whole, so those lines exceed
+    /// `max_len`.
+    pub fn split_for_wire(&self, max_len: usize) -> Vec<PrivMsg> {
+        let overhead =
+            "PRIVMSG ".len() + self.targets_param().as_str().len() + " :".len() + "\r\n".len();
+        let budget = max_len.saturating_sub(overhead);
+        let text = self.text.as_str();
+        if budget == 0 || text.len() <= budget {
+            return vec![self.clone()];
+        }
+        let mut out = Vec::new();
+        let mut rest = text;
+        while rest.len() > budget {
+            let boundary = floor_char_boundary(rest, budget)
+                .max(rest.chars().next().map_or(0, char::len_utf8));
+            let split_at = rest[..boundary]
+                .rfind(' ')
+                .map(|i| i + 1)
+                .filter(|&i| i > 0)
+                .unwrap_or(boundary);
+            let (chunk, remainder) = rest.split_at(split_at);
+            out.push(self.with_text(chunk));
+            rest = remainder;
+        }
+        if !rest.is_empty() {
+            out.push(self.with_text(rest));
+        }
+        out
+    }
+}
+
+/// The default line-length budget used by [`PrivMsg::split_for_wire`]: the
+/// traditional 512-byte IRC line limit, including the trailing CRLF.
+pub const DEFAULT_MAX_LINE_LEN: usize = 512;
+
+/// Like the nightly-only `str::floor_char_boundary`: the largest byte index
+/// `<= index` that lies on a UTF-8 char boundary of `s`.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        s.len()
+    } else {
+        let mut i = index;
+        while i > 0 && !s.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    }
 }
 
 impl ClientMessageParts for PrivMsg {
@@ -89,3 +178,83 @@ impl TryFrom<ParameterList> for PrivMsg {
         Ok(PrivMsg { targets, text })
     }
 }
+
+// `targets` is deserialized via a repr struct rather than derived directly
+// so that an empty list -- which every public constructor forbids and
+// `targets_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PrivMsgRepr {
+    targets: Vec<MsgTarget>,
+    text: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for PrivMsg {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <PrivMsgRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.targets.is_empty() {
+            return Err(serde::de::Error::custom(
+                "PrivMsg.targets must not be empty",
+            ));
+        }
+        Ok(PrivMsg {
+            targets: repr.targets,
+            text: repr.text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(text: &str) -> PrivMsg {
+        let target = "#chan".parse::<crate::types::Channel>().unwrap();
+        PrivMsg::new(target, text.parse().unwrap())
+    }
+
+    #[test]
+    fn split_for_wire_fits_on_one_line() {
+        let m = msg("hello there");
+        assert_eq!(m.split_for_wire(DEFAULT_MAX_LINE_LEN), [m.clone()]);
+    }
+
+    #[test]
+    fn split_for_wire_empty_text_fits() {
+        let m = msg("");
+        assert_eq!(m.split_for_wire(DEFAULT_MAX_LINE_LEN), [m.clone()]);
+    }
+
+    #[test]
+    fn split_for_wire_breaks_at_last_space() {
+        let m = msg("one two three four");
+        let parts = m.split_for_wire("PRIVMSG #chan :".len() + 10 + "\r\n".len());
+        assert_eq!(parts, [msg("one two "), msg("three four")]);
+    }
+
+    #[test]
+    fn split_for_wire_hard_splits_oversized_word() {
+        let m = msg("aaaaaaaaaaaaaaaa");
+        let budget = "PRIVMSG #chan :".len() + 5 + "\r\n".len();
+        let parts = m.split_for_wire(budget);
+        assert_eq!(parts, [msg("aaaaa"), msg("aaaaa"), msg("aaaaa"), msg("a")]);
+    }
+
+    #[test]
+    fn split_for_wire_rejoins_to_original_text() {
+        let m = msg("the quick brown fox jumps over the lazy dog");
+        let parts = m.split_for_wire("PRIVMSG #chan :".len() + 12 + "\r\n".len());
+        assert!(parts.len() > 1);
+        let rejoined: String = parts
+            .iter()
+            .map(|p| p.text().as_str().to_owned())
+            .collect();
+        assert_eq!(rejoined, m.text().as_str());
+    }
+}
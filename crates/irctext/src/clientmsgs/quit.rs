@@ -3,7 +3,12 @@ use crate::util::DisplayMaybeFinal;
 use crate::{FinalParam, Message, ParameterList, RawMessage, Verb};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quit {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     reason: Option<FinalParam>,
 }
 
@@ -74,6 +79,7 @@ mod tests {
         assert_matches!(msg, Message {
             source: Some(Source::Client(clisrc)),
             payload: Payload::ClientMessage(ClientMessage::Quit(quit)),
+            ..
         } => {
             assert_eq!(clisrc.nickname, "Spawns_Carpeting");
             assert_eq!(clisrc.user.as_ref().unwrap(), "~mobile");
@@ -89,6 +95,7 @@ mod tests {
         assert_matches!(msg, Message {
             source: Some(Source::Client(clisrc)),
             payload: Payload::ClientMessage(ClientMessage::Quit(quit)),
+            ..
         } => {
             assert_eq!(clisrc.nickname, "Spawns_Carpeting");
             assert_eq!(clisrc.user.as_ref().unwrap(), "~mobile");
@@ -0,0 +1,45 @@
+use super::{ClientMessage, ClientMessageError, ClientMessageParts};
+use crate::{Message, ParameterList, RawMessage, ToIrcLine, Verb};
+
+/// The `STARTTLS` command, by which a client requests that the server
+/// upgrade the current plaintext connection to TLS in place.
+///
+/// A successful request is answered with `RPL_STARTTLS`, after which the
+/// client and server both perform a TLS handshake over the existing
+/// connection; a failed request is answered with `ERR_STARTTLS`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StartTls;
+
+impl ClientMessageParts for StartTls {
+    fn into_parts(self) -> (Verb, ParameterList) {
+        (Verb::StartTls, ParameterList::default())
+    }
+}
+
+impl ToIrcLine for StartTls {
+    fn to_irc_line(&self) -> String {
+        String::from("STARTTLS")
+    }
+}
+
+impl From<StartTls> for Message {
+    fn from(value: StartTls) -> Message {
+        Message::from(ClientMessage::from(value))
+    }
+}
+
+impl From<StartTls> for RawMessage {
+    fn from(value: StartTls) -> RawMessage {
+        RawMessage::from(ClientMessage::from(value))
+    }
+}
+
+impl TryFrom<ParameterList> for StartTls {
+    type Error = ClientMessageError;
+
+    fn try_from(params: ParameterList) -> Result<StartTls, ClientMessageError> {
+        let () = params.try_into()?;
+        Ok(StartTls)
+    }
+}
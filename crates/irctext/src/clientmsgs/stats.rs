@@ -1,10 +1,18 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
 use crate::util::DisplayMaybeFinal;
-use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, Verb};
+use crate::{
+    FinalParam, MedialParam, Message, ParameterList, ParseMedialParamError, RawMessage,
+    TryFromStringError, Verb,
+};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stats {
     query: MedialParam,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     server: Option<FinalParam>,
 }
 
@@ -23,6 +31,26 @@ impl Stats {
         }
     }
 
+    /// Like [`Stats::new`], but accepts the query as a single `char`
+    /// instead of requiring the caller to construct a [`MedialParam`]
+    /// first.
+    pub fn try_new(query: char) -> Result<Stats, TryFromStringError<ParseMedialParamError>> {
+        Ok(Stats::new(MedialParam::try_from(query.to_string())?))
+    }
+
+    /// Like [`Stats::new_with_server`], but accepts the query as a single
+    /// `char` instead of requiring the caller to construct a
+    /// [`MedialParam`] first.
+    pub fn try_new_with_server(
+        query: char,
+        server: FinalParam,
+    ) -> Result<Stats, TryFromStringError<ParseMedialParamError>> {
+        Ok(Stats::new_with_server(
+            MedialParam::try_from(query.to_string())?,
+            server,
+        ))
+    }
+
     pub fn query(&self) -> &MedialParam {
         &self.query
     }
@@ -67,7 +95,13 @@ impl TryFrom<ParameterList> for Stats {
     type Error = ClientMessageError;
 
     fn try_from(params: ParameterList) -> Result<Stats, ClientMessageError> {
-        let (query, server) = params.try_into()?;
+        let (query, server): (MedialParam, Option<FinalParam>) = params.try_into()?;
+        if query.as_str().chars().count() != 1 {
+            return Err(ClientMessageError::ParamValue {
+                got: query.to_string(),
+                expected: "a single character",
+            });
+        }
         Ok(Stats { query, server })
     }
 }
@@ -4,7 +4,12 @@ use crate::util::{join_with_commas, split_param};
 use crate::{Message, MiddleParam, ParameterList, RawMessage, TrailingParam, Verb};
 
 // <https://ircv3.net/specs/extensions/message-tags.html#the-tagmsg-tag-only-message>
+//
+// A TAGMSG carries no payload of its own besides its tags, which live on
+// the enclosing `Message` rather than here; convert a `TagMsg` to a
+// `Message` and then attach tags with `Message::with_tags()`/`with_tag()`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TagMsg {
     targets: Vec<MsgTarget>,
 }
@@ -88,3 +93,30 @@ impl TryFrom<ParameterList> for TagMsg {
         Ok(TagMsg { targets })
     }
 }
+
+// `targets` is deserialized via a repr struct rather than derived directly
+// so that an empty list -- which every public constructor forbids and
+// `targets_param()` assumes can't happen -- is rejected here instead of
+// panicking later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TagMsgRepr {
+    targets: Vec<MsgTarget>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for TagMsg {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <TagMsgRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if repr.targets.is_empty() {
+            return Err(serde::de::Error::custom("TagMsg.targets must not be empty"));
+        }
+        Ok(TagMsg {
+            targets: repr.targets,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use super::{ClientMessage, ClientMessageError, ClientMessageParts};
 use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, Username, Verb};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct User {
     username: Username,
     realname: FinalParam,
@@ -4,6 +4,7 @@ use crate::{Message, ParameterList, ParameterListSizeError, RawMessage, Verb};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UserHost {
     nicknames: Vec<Nickname>,
 }
@@ -83,3 +84,32 @@ impl TryFrom<ParameterList> for UserHost {
 #[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
 #[error("UserHost takes 1 to 5 nicknames, but {0} were supplied")]
 pub struct UserHostError(pub usize);
+
+// `nicknames` is deserialized via a repr struct rather than derived directly
+// so that a list outside the 1-to-5 range [`UserHost::new`] enforces is
+// rejected here instead of producing a wire-invalid `USERHOST` line later.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UserHostRepr {
+    nicknames: Vec<Nickname>,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for UserHost {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <UserHostRepr as serde::Deserialize>::deserialize(deserializer)?;
+        if !(1..=5).contains(&repr.nicknames.len()) {
+            return Err(serde::de::Error::custom(format!(
+                "UserHost takes 1 to 5 nicknames, but {} were supplied",
+                repr.nicknames.len()
+            )));
+        }
+        Ok(UserHost {
+            nicknames: repr.nicknames,
+        })
+    }
+}
@@ -1,14 +1,49 @@
 use super::{ClientMessage, ClientMessageError, ClientMessageParts};
-use crate::{FinalParam, MedialParam, Message, ParameterList, RawMessage, ToIrcLine, Verb};
+use crate::util::DisplayMaybeFinal;
+use crate::{
+    FinalParam, MedialParam, Message, ParameterList, RawMessage, ToIrcLine, TryFromStringError,
+    Verb,
+};
+use std::fmt;
+use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Who {
     mask: MedialParam,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    whox: Option<WhoxQuery>,
 }
 
 impl Who {
     pub fn new<P: Into<MedialParam>>(mask: P) -> Who {
-        Who { mask: mask.into() }
+        Who {
+            mask: mask.into(),
+            whox: None,
+        }
+    }
+
+    /// Constructs a WHOX-extended `WHO` query, which requests a specific set
+    /// of reply fields (via `fields`) and, if `token` is given, tags the
+    /// query so the corresponding `RPL_WHOSPCRPL` replies can be matched
+    /// back to it.
+    pub fn new_whox<P: Into<MedialParam>>(
+        mask: P,
+        flags: WhoxFlags,
+        fields: WhoxFields,
+        token: Option<u16>,
+    ) -> Who {
+        Who {
+            mask: mask.into(),
+            whox: Some(WhoxQuery {
+                flags,
+                fields,
+                token,
+            }),
+        }
     }
 
     pub fn mask(&self) -> &MedialParam {
@@ -18,20 +53,35 @@ impl Who {
     pub fn into_mask(self) -> MedialParam {
         self.mask
     }
+
+    pub fn whox(&self) -> Option<&WhoxQuery> {
+        self.whox.as_ref()
+    }
+
+    pub fn into_whox(self) -> Option<WhoxQuery> {
+        self.whox
+    }
+
+    fn whox_param(&self) -> Option<FinalParam> {
+        self.whox
+            .as_ref()
+            .map(|whox| FinalParam::from(whox.clone()))
+    }
 }
 
 impl ClientMessageParts for Who {
     fn into_parts(self) -> (Verb, ParameterList) {
-        (
-            Verb::Who,
-            ParameterList::builder().with_medial(self.mask).finish(),
-        )
+        let whox_param = self.whox_param();
+        let params = ParameterList::builder()
+            .with_medial(self.mask)
+            .maybe_with_final(whox_param);
+        (Verb::Who, params)
     }
 }
 
 impl ToIrcLine for Who {
     fn to_irc_line(&self) -> String {
-        format!("WHO {}", self.mask)
+        format!("WHO {}{}", self.mask, DisplayMaybeFinal(self.whox_param()))
     }
 }
 
@@ -51,14 +101,235 @@ impl TryFrom<ParameterList> for Who {
     type Error = ClientMessageError;
 
     fn try_from(params: ParameterList) -> Result<Who, ClientMessageError> {
-        let (p,): (FinalParam,) = params.try_into()?;
-        match p.as_str().parse::<MedialParam>() {
-            Ok(mask) => Ok(Who { mask }),
-            Err(source) => Err(ClientMessageError::ParseParam {
-                index: 0,
-                raw: p.into_inner(),
-                source: Box::new(source),
-            }),
+        let (p1, p2): (MedialParam, Option<FinalParam>) = params.try_into()?;
+        let mask = p1;
+        let whox = match p2 {
+            Some(p) => Some(WhoxQuery::try_from(p.into_inner())?),
+            None => None,
+        };
+        Ok(Who { mask, whox })
+    }
+}
+
+/// The second parameter of a WHOX-extended `WHO` query: the requested
+/// [`WhoxFlags`], the [`WhoxFields`] selecting which reply fields the
+/// server should send back, and an optional token for matching the
+/// resulting `RPL_WHOSPCRPL` replies to this query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhoxQuery {
+    flags: WhoxFlags,
+    fields: WhoxFields,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    token: Option<u16>,
+}
+
+impl WhoxQuery {
+    pub fn flags(&self) -> &WhoxFlags {
+        &self.flags
+    }
+
+    pub fn fields(&self) -> &WhoxFields {
+        &self.fields
+    }
+
+    pub fn token(&self) -> Option<u16> {
+        self.token
+    }
+}
+
+impl fmt::Display for WhoxQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%{}", self.flags, self.fields)?;
+        if let Some(token) = self.token {
+            write!(f, ",{token}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for WhoxQuery {
+    type Err = ParseWhoxQueryError;
+
+    fn from_str(s: &str) -> Result<WhoxQuery, ParseWhoxQueryError> {
+        let (main, fields) = s.split_once('%').ok_or(ParseWhoxQueryError::NoFields)?;
+        let flags = main.parse::<WhoxFlags>()?;
+        let (fields, token) = match fields.split_once(',') {
+            Some((fields, token)) => (
+                fields,
+                Some(
+                    token
+                        .parse::<u16>()
+                        .map_err(|_| ParseWhoxQueryError::BadToken)?,
+                ),
+            ),
+            None => (fields, None),
+        };
+        let fields = fields.parse::<WhoxFields>()?;
+        Ok(WhoxQuery {
+            flags,
+            fields,
+            token,
+        })
+    }
+}
+
+impl TryFrom<String> for WhoxQuery {
+    type Error = TryFromStringError<ParseWhoxQueryError>;
+
+    fn try_from(string: String) -> Result<WhoxQuery, TryFromStringError<ParseWhoxQueryError>> {
+        match string.parse() {
+            Ok(query) => Ok(query),
+            Err(inner) => Err(TryFromStringError { inner, string }),
+        }
+    }
+}
+
+impl From<WhoxQuery> for FinalParam {
+    fn from(value: WhoxQuery) -> FinalParam {
+        FinalParam::try_from(value.to_string()).expect("WhoxQuery should be valid FinalParam")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum ParseWhoxQueryError {
+    #[error("WHOX query is missing its %fields portion")]
+    NoFields,
+    #[error(transparent)]
+    Flags(#[from] ParseWhoxFlagsError),
+    #[error("WHOX query token is not a valid u16")]
+    BadToken,
+    #[error(transparent)]
+    Fields(#[from] ParseWhoxFieldsError),
+}
+
+/// The flags portion of a WHOX-extended `WHO` query (the part before the
+/// `%`): whether to restrict the query to channel operators, plus any other
+/// server-specific flag characters the caller wants to pass through as-is.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhoxFlags {
+    pub ops_only: bool,
+    pub extra: String,
+}
+
+impl fmt::Display for WhoxFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ops_only {
+            write!(f, "o")?;
         }
+        write!(f, "{}", self.extra)
+    }
+}
+
+impl std::str::FromStr for WhoxFlags {
+    type Err = ParseWhoxFlagsError;
+
+    fn from_str(s: &str) -> Result<WhoxFlags, ParseWhoxFlagsError> {
+        if s.contains(['\0', '\r', '\n', ' ', '%', ',']) {
+            return Err(ParseWhoxFlagsError::BadCharacter);
+        }
+        let (ops_only, extra) = match s.strip_prefix('o') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        Ok(WhoxFlags {
+            ops_only,
+            extra: extra.to_owned(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum ParseWhoxFlagsError {
+    #[error("WHOX flags cannot contain NUL, CR, LF, SPACE, %, or ,")]
+    BadCharacter,
+}
+
+/// The set of reply fields requested in the `%fields` portion of a
+/// WHOX-extended `WHO` query, drawn from the standard letters
+/// `tcuihsnfdlaor` (token, channel, username, ip, hostname, server, nick,
+/// flags, distance, idle time, account, oplevel, realname).
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct WhoxFields(String);
+
+validstr!(WhoxFields, ParseWhoxFieldsError, validate_whox_fields);
+strserde!(WhoxFields, "a WHOX %fields selector");
+
+fn validate_whox_fields(s: &str) -> Result<(), ParseWhoxFieldsError> {
+    if s.is_empty() {
+        Err(ParseWhoxFieldsError::Empty)
+    } else if s.contains(|c: char| !"tcuihsnfdlaor".contains(c)) {
+        Err(ParseWhoxFieldsError::BadCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+impl From<WhoxFields> for FinalParam {
+    fn from(value: WhoxFields) -> FinalParam {
+        FinalParam::try_from(value.into_inner()).expect("WhoxFields should be valid FinalParam")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum ParseWhoxFieldsError {
+    #[error("WHOX fields cannot be empty")]
+    Empty,
+    #[error("WHOX fields must be drawn from tcuihsnfdlaor")]
+    BadCharacter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_who_round_trips() {
+        let who = Who::new("#chan".parse::<MedialParam>().unwrap());
+        assert_eq!(who.to_irc_line(), "WHO #chan");
+        let (_, params) = who.clone().into_parts();
+        assert_eq!(Who::try_from(params).unwrap(), who);
+    }
+
+    #[test]
+    fn whox_query_round_trips() {
+        let fields = "tcuihsnfdlaor".parse::<WhoxFields>().unwrap();
+        let flags = WhoxFlags {
+            ops_only: true,
+            extra: String::new(),
+        };
+        let who = Who::new_whox(
+            "#chan".parse::<MedialParam>().unwrap(),
+            flags,
+            fields,
+            Some(42),
+        );
+        assert_eq!(who.to_irc_line(), "WHO #chan o%tcuihsnfdlaor,42");
+        let (_, params) = who.clone().into_parts();
+        assert_eq!(Who::try_from(params).unwrap(), who);
+    }
+
+    #[test]
+    fn whox_query_without_token() {
+        let fields = "n".parse::<WhoxFields>().unwrap();
+        let who = Who::new_whox(
+            "#chan".parse::<MedialParam>().unwrap(),
+            WhoxFlags::default(),
+            fields,
+            None,
+        );
+        assert_eq!(who.to_irc_line(), "WHO #chan %n");
+    }
+
+    #[test]
+    fn whox_fields_rejects_unknown_letter() {
+        assert_eq!(
+            "tx".parse::<WhoxFields>(),
+            Err(ParseWhoxFieldsError::BadCharacter)
+        );
     }
 }
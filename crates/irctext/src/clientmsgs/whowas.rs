@@ -5,8 +5,13 @@ use crate::{FinalParam, Message, ParameterList, RawMessage, Verb};
 use std::num::NonZeroUsize;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WhoWas {
     nickname: Nickname,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     count: Option<NonZeroUsize>,
 }
 
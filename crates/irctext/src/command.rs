@@ -1,11 +1,13 @@
 use super::verb::Verb;
+use crate::Numeric;
 use std::fmt;
+use std::fmt::Write;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Command {
     Verb(Verb),
-    Reply(u16),
+    Reply(ReplyCode),
 }
 
 impl Command {
@@ -16,13 +18,25 @@ impl Command {
     pub fn is_reply(&self) -> bool {
         matches!(self, Command::Reply(_))
     }
+
+    /// Upgrades a `Command::Reply` into its well-known [`Numeric`], if any.
+    ///
+    /// Returns `None` for `Command::Verb` and for reply codes that don't
+    /// correspond to a standard or modern-IRC numeric; in either case, the
+    /// original code remains available unchanged from the `Command` itself.
+    pub fn as_numeric(&self) -> Option<Numeric> {
+        match self {
+            Command::Reply(code) => Numeric::try_from(code.as_u16()).ok(),
+            Command::Verb(_) => None,
+        }
+    }
 }
 
 impl fmt::Display for Command {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Command::Verb(name) => write!(f, "{name}"),
-            Command::Reply(code) => write!(f, "{code:03}"),
+            Command::Reply(code) => write!(f, "{code}"),
         }
     }
 }
@@ -39,11 +53,10 @@ impl TryFrom<String> for Command {
     type Error = ParseCommandError;
 
     fn try_from(s: String) -> Result<Command, ParseCommandError> {
-        if s.len() == 3 && s.chars().all(|ch| ch.is_ascii_digit()) {
-            let code = s
-                .parse::<u16>()
-                .expect("Three-digit number should be valid u16");
-            Ok(Command::Reply(code))
+        if s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()) {
+            let mut raw = [0u8; 3];
+            raw.copy_from_slice(s.as_bytes());
+            Ok(Command::Reply(ReplyCode::from_raw(raw)))
         } else {
             Ok(Command::Verb(Verb::from(s)))
         }
@@ -58,10 +71,22 @@ impl From<Verb> for Command {
 
 impl From<u16> for Command {
     fn from(value: u16) -> Command {
+        Command::Reply(ReplyCode::from(value))
+    }
+}
+
+impl From<ReplyCode> for Command {
+    fn from(value: ReplyCode) -> Command {
         Command::Reply(value)
     }
 }
 
+impl From<Numeric> for Command {
+    fn from(value: Numeric) -> Command {
+        Command::Reply(ReplyCode::from(u16::from(value)))
+    }
+}
+
 impl PartialEq<Verb> for Command {
     fn eq(&self, other: &Verb) -> bool {
         matches!(self, Command::Verb(v) if v == other)
@@ -70,10 +95,277 @@ impl PartialEq<Verb> for Command {
 
 impl PartialEq<u16> for Command {
     fn eq(&self, other: &u16) -> bool {
-        *self == Command::Reply(*other)
+        matches!(self, Command::Reply(code) if code.as_u16() == *other)
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
 #[error("invalid command")]
 pub struct ParseCommandError;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Command {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Command {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Command;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("an IRC command (verb or three-digit reply code)")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Command, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// A three-digit numeric reply code, preserving the exact three bytes it was
+/// parsed from alongside the parsed `u16` value, so that a code like `007`
+/// doesn't become indistinguishable from the integer `7` and re-emission can
+/// reproduce the original wire bytes exactly — which matters for proxies and
+/// test harnesses that must relay server output byte-faithfully.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ReplyCode {
+    value: u16,
+    raw: [u8; 3],
+}
+
+impl ReplyCode {
+    /// Builds a `ReplyCode` from its original three-digit-ASCII wire form.
+    fn from_raw(raw: [u8; 3]) -> ReplyCode {
+        let value = digits_to_u16(&raw);
+        ReplyCode { value, raw }
+    }
+
+    /// Returns the reply code as a `u16`.
+    pub fn as_u16(&self) -> u16 {
+        self.value
+    }
+
+    /// Returns the original three-digit-ASCII wire form of the reply code.
+    pub fn as_bytes(&self) -> &[u8; 3] {
+        &self.raw
+    }
+}
+
+impl fmt::Display for ReplyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            std::str::from_utf8(&self.raw).expect("reply code bytes should always be ASCII"),
+        )
+    }
+}
+
+/// Builds a `ReplyCode` from a plain `u16`, synthesizing its wire bytes as
+/// the zero-padded decimal digits of `value % 1000` (reply codes are always
+/// three digits; values of 1000 or more can't be represented byte-faithfully
+/// and are truncated).
+impl From<u16> for ReplyCode {
+    fn from(value: u16) -> ReplyCode {
+        let v = value % 1000;
+        let raw = [
+            b'0' + (v / 100) as u8,
+            b'0' + (v / 10 % 10) as u8,
+            b'0' + (v % 10) as u8,
+        ];
+        ReplyCode { value: v, raw }
+    }
+}
+
+/// A borrowing counterpart to [`Command`] for classifying a command token
+/// straight off the wire without requiring it to be valid UTF-8, as raw IRC
+/// lines frequently are not.
+///
+/// Unlike [`Command::Verb`], `CommandRef::Verb` retains the original byte
+/// slice rather than decoding it, so construction can never fail on
+/// malformed input; decoding (with `U+FFFD` substituted for invalid byte
+/// sequences) only happens on demand, in the [`Display`](fmt::Display) impl.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CommandRef<'a> {
+    Verb(&'a [u8]),
+    Reply(u16),
+}
+
+impl CommandRef<'_> {
+    pub fn is_verb(&self) -> bool {
+        matches!(self, CommandRef::Verb(_))
+    }
+
+    pub fn is_reply(&self) -> bool {
+        matches!(self, CommandRef::Reply(_))
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CommandRef<'a> {
+    type Error = ParseCommandError;
+
+    fn try_from(s: &'a [u8]) -> Result<CommandRef<'a>, ParseCommandError> {
+        if s.len() == 3 && s.iter().all(u8::is_ascii_digit) {
+            Ok(CommandRef::Reply(digits_to_u16(s)))
+        } else {
+            Ok(CommandRef::Verb(s))
+        }
+    }
+}
+
+/// Folds a slice of ASCII digit bytes into the `u16` they spell out.
+///
+/// Callers are responsible for ensuring `digits` consists solely of ASCII
+/// digits; this is not re-validated here.
+fn digits_to_u16(digits: &[u8]) -> u16 {
+    digits
+        .iter()
+        .fold(0u16, |acc, &b| acc * 10 + u16::from(b - b'0'))
+}
+
+impl fmt::Display for CommandRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandRef::Verb(bytes) => write_lossy(f, bytes),
+            CommandRef::Reply(code) => write!(f, "{code:03}"),
+        }
+    }
+}
+
+/// Writes `bytes` to `f`, copying valid UTF-8 runs verbatim and substituting
+/// `U+FFFD` for each invalid byte sequence encountered along the way.
+fn write_lossy(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    let mut rest = bytes;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(s) => return f.write_str(s),
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                f.write_str(
+                    std::str::from_utf8(&rest[..valid_up_to])
+                        .expect("prefix preceding a UTF-8 error should itself be valid UTF-8"),
+                )?;
+                f.write_char('\u{FFFD}')?;
+                let invalid_len = e.error_len().unwrap_or(rest.len() - valid_up_to);
+                rest = &rest[(valid_up_to + invalid_len)..];
+                if rest.is_empty() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn reply_code_preserves_leading_zero() {
+        let cmd = Command::try_from(String::from("007")).unwrap();
+        assert_eq!(cmd.to_string(), "007");
+        assert_matches!(cmd, Command::Reply(code) => {
+            assert_eq!(code.as_u16(), 7);
+            assert_eq!(code.as_bytes(), b"007");
+        });
+    }
+
+    #[test]
+    fn reply_code_roundtrips_through_command() {
+        for code in ["000", "007", "432", "999"] {
+            let cmd = Command::try_from(code.to_string()).unwrap();
+            assert_eq!(cmd.to_string(), code);
+        }
+    }
+
+    #[test]
+    fn reply_code_from_u16_distinguishes_from_leading_zero_form() {
+        let from_int = ReplyCode::from(7u16);
+        let from_wire = Command::try_from(String::from("007")).unwrap();
+        assert_eq!(from_int.as_bytes(), b"007");
+        assert_matches!(from_wire, Command::Reply(code) => {
+            assert_eq!(code, from_int);
+        });
+    }
+
+    #[test]
+    fn as_numeric_recognizes_known_code() {
+        let cmd = Command::from(Numeric::RplWelcome);
+        assert_eq!(cmd.as_numeric(), Some(Numeric::RplWelcome));
+    }
+
+    #[test]
+    fn as_numeric_is_none_for_unknown_code() {
+        let cmd = Command::from(999u16);
+        assert_eq!(cmd.as_numeric(), None);
+    }
+
+    #[test]
+    fn as_numeric_is_none_for_verb() {
+        let cmd = Command::from(Verb::from(String::from("PRIVMSG")));
+        assert_eq!(cmd.as_numeric(), None);
+    }
+
+    #[test]
+    fn command_ref_reply() {
+        let cmd = CommandRef::try_from(&b"001"[..]).unwrap();
+        assert_eq!(cmd, CommandRef::Reply(1));
+        assert!(cmd.is_reply());
+        assert_eq!(cmd.to_string(), "001");
+    }
+
+    #[test]
+    fn command_ref_ascii_verb() {
+        let cmd = CommandRef::try_from(&b"PRIVMSG"[..]).unwrap();
+        assert_eq!(cmd, CommandRef::Verb(b"PRIVMSG"));
+        assert!(cmd.is_verb());
+        assert_eq!(cmd.to_string(), "PRIVMSG");
+    }
+
+    #[test]
+    fn command_ref_non_digit_three_bytes_is_verb() {
+        let cmd = CommandRef::try_from(&b"A0B"[..]).unwrap();
+        assert_eq!(cmd, CommandRef::Verb(b"A0B"));
+        assert!(cmd.is_verb());
+    }
+
+    #[test]
+    fn command_ref_invalid_utf8_in_middle() {
+        let bytes = b"FO\xFFO";
+        let cmd = CommandRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(cmd.to_string(), "FO\u{FFFD}O");
+    }
+
+    #[test]
+    fn command_ref_truncated_utf8_at_end() {
+        let bytes = b"FOO\xE2\x82";
+        let cmd = CommandRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(cmd.to_string(), "FOO\u{FFFD}");
+    }
+
+    #[test]
+    fn command_ref_all_invalid() {
+        let bytes = b"\xFF\xFE";
+        let cmd = CommandRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(cmd.to_string(), "\u{FFFD}\u{FFFD}");
+    }
+}
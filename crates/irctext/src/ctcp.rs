@@ -2,13 +2,14 @@
 /// <https://datatracker.ietf.org/doc/html/draft-oakley-irc-ctcp-02>
 use super::FinalParam;
 use std::borrow::Cow;
+use std::net::Ipv4Addr;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum CtcpMessage {
     Action(Option<CtcpParams>),
     ClientInfo(Option<CtcpParams>),
-    Dcc(Option<CtcpParams>),
+    Dcc(Option<DccMessage>),
     Finger(Option<CtcpParams>),
     Ping(Option<CtcpParams>),
     Source(Option<CtcpParams>),
@@ -67,49 +68,159 @@ impl CtcpMessage {
     pub fn is_plain(&self) -> bool {
         matches!(self, CtcpMessage::Plain(_))
     }
-}
 
-impl From<FinalParam> for CtcpMessage {
-    fn from(p: FinalParam) -> CtcpMessage {
-        let Some(txt) = p.as_str().strip_prefix('\x01') else {
-            return CtcpMessage::Plain(p);
-        };
-        let txt = txt.strip_suffix('\x01').unwrap_or(txt);
-        let (cmd, params) = txt.split_once(' ').unwrap_or((txt, ""));
-        let Ok(cmd) = cmd.parse::<CtcpCommand>() else {
-            return CtcpMessage::Plain(p);
+    /// Constructs an outbound `ACTION` message (a.k.a. `/me`) with the given
+    /// text, for sending in a [`PrivMsg`](crate::clientmsgs::PrivMsg).
+    pub fn new_action(text: &str) -> Result<CtcpMessage, ParseCtcpParamsError> {
+        let params = if text.is_empty() {
+            None
+        } else {
+            Some(CtcpParams::try_from(text.to_owned())?)
         };
+        Ok(CtcpMessage::Action(params))
+    }
+
+    /// Constructs an outbound CTCP message for the given `command` (e.g.
+    /// `"VERSION"`, `"PING"`, or any nonstandard command) with the given
+    /// `params`, or no parameters if `params` is empty.
+    ///
+    /// The wire format for a CTCP query (carried in a
+    /// [`PrivMsg`](crate::clientmsgs::PrivMsg)) and a CTCP reply (carried in
+    /// a [`Notice`](crate::clientmsgs::Notice)) is identical, so this one
+    /// constructor covers both; which message type the result ends up in is
+    /// entirely up to the caller.
+    pub fn new(command: &str, params: &str) -> Result<CtcpMessage, NewCtcpMessageError> {
+        let cmd = command.parse::<CtcpCommand>()?;
         let params = if params.is_empty() {
             None
-        } else if let Ok(ps) = params.parse::<CtcpParams>() {
-            Some(ps)
         } else {
-            return CtcpMessage::Plain(p);
+            Some(CtcpParams::try_from(params.to_owned())?)
         };
-        if cmd.as_str().eq_ignore_ascii_case("ACTION") {
-            CtcpMessage::Action(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("CLIENTINFO") {
-            CtcpMessage::ClientInfo(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("DCC") {
-            CtcpMessage::Dcc(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("FINGER") {
-            CtcpMessage::Finger(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("PING") {
-            CtcpMessage::Ping(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("SOURCE") {
-            CtcpMessage::Source(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("TIME") {
-            CtcpMessage::Time(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("USERINFO") {
-            CtcpMessage::UserInfo(params)
-        } else if cmd.as_str().eq_ignore_ascii_case("VERSION") {
-            CtcpMessage::Version(params)
-        } else {
-            CtcpMessage::Other {
-                command: cmd,
-                params,
+        Ok(build_ctcp_message(cmd, params))
+    }
+
+    /// Splits `p` into the sequence of CTCP messages and interleaved plain
+    /// text it's made up of, as the CTCP draft permits several
+    /// `\x01`-delimited segments to share a single PRIVMSG/NOTICE. Each
+    /// `\x01...\x01` region is parsed the same way as converting a whole
+    /// `FinalParam` into a `CtcpMessage` does; runs of ordinary text before,
+    /// between, or after them come back as
+    /// [`CtcpMessage::Plain`]. A lone trailing `\x01` with no closing match
+    /// runs to the end of the string, same as a single unterminated CTCP
+    /// message. Re-serializing and concatenating the returned messages
+    /// reproduces `p`.
+    pub fn parse_all(p: &FinalParam) -> Vec<CtcpMessage> {
+        let mut messages = Vec::new();
+        let mut rest = p.as_str();
+        while !rest.is_empty() {
+            let Some(start) = rest.find('\x01') else {
+                messages.push(plain_ctcp(rest));
+                break;
+            };
+            if start > 0 {
+                messages.push(plain_ctcp(&rest[..start]));
+                rest = &rest[start..];
+                continue;
             }
+            let after_delim = &rest[1..];
+            let (body, remainder, had_close) = match after_delim.find('\x01') {
+                Some(i) => (&after_delim[..i], &after_delim[i + 1..], true),
+                None => (after_delim, "", false),
+            };
+            let msg = parse_ctcp_body(body).unwrap_or_else(|| {
+                plain_ctcp(&if had_close {
+                    format!("\x01{body}\x01")
+                } else {
+                    format!("\x01{body}")
+                })
+            });
+            messages.push(msg);
+            rest = remainder;
         }
+        messages
+    }
+}
+
+/// Wraps `s` (a substring of an already-validated [`FinalParam`]) in
+/// [`CtcpMessage::Plain`].
+fn plain_ctcp(s: &str) -> CtcpMessage {
+    CtcpMessage::Plain(
+        FinalParam::try_from(s.to_owned()).expect("substring of a FinalParam should be one too"),
+    )
+}
+
+/// Parses the body of a single `\x01`-delimited CTCP segment, with the
+/// delimiters already stripped, into the [`CtcpMessage`] it names, or
+/// `None` if the body doesn't look like a valid CTCP message.
+fn parse_ctcp_body(body: &str) -> Option<CtcpMessage> {
+    let (cmd, params) = body.split_once(' ').unwrap_or((body, ""));
+    let cmd = cmd.parse::<CtcpCommand>().ok()?;
+    let params = if params.is_empty() {
+        None
+    } else {
+        Some(CtcpParams::try_from(CtcpParams::dequote(params)).ok()?)
+    };
+    Some(build_ctcp_message(cmd, params))
+}
+
+/// Builds the [`CtcpMessage`] variant named by `cmd`, dispatching to
+/// [`CtcpMessage::Other`] for anything that isn't one of the standard
+/// commands.
+fn build_ctcp_message(cmd: CtcpCommand, params: Option<CtcpParams>) -> CtcpMessage {
+    if cmd.as_str().eq_ignore_ascii_case("ACTION") {
+        CtcpMessage::Action(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("CLIENTINFO") {
+        CtcpMessage::ClientInfo(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("DCC") {
+        CtcpMessage::Dcc(params.map(DccMessage::from))
+    } else if cmd.as_str().eq_ignore_ascii_case("FINGER") {
+        CtcpMessage::Finger(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("PING") {
+        CtcpMessage::Ping(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("SOURCE") {
+        CtcpMessage::Source(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("TIME") {
+        CtcpMessage::Time(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("USERINFO") {
+        CtcpMessage::UserInfo(params)
+    } else if cmd.as_str().eq_ignore_ascii_case("VERSION") {
+        CtcpMessage::Version(params)
+    } else {
+        CtcpMessage::Other {
+            command: cmd,
+            params,
+        }
+    }
+}
+
+impl From<FinalParam> for CtcpMessage {
+    fn from(p: FinalParam) -> CtcpMessage {
+        let Some(txt) = p.as_str().strip_prefix('\x01') else {
+            return CtcpMessage::Plain(p);
+        };
+        let txt = txt.strip_suffix('\x01').unwrap_or(txt);
+        parse_ctcp_body(txt).unwrap_or(CtcpMessage::Plain(p))
+    }
+}
+
+impl FinalParam {
+    /// Returns `Some` if this parameter is (or starts with) a recognized
+    /// `\x01`-delimited CTCP payload, or `None` if it's just plain text.
+    /// Equivalent to `CtcpMessage::from(self.clone())`, except that a
+    /// [`CtcpMessage::Plain`] result (no CTCP structure found) comes back as
+    /// `None` instead.
+    pub fn as_ctcp(&self) -> Option<CtcpMessage> {
+        let msg = CtcpMessage::from(self.clone());
+        (!msg.is_plain()).then_some(msg)
+    }
+}
+
+impl CtcpMessage {
+    /// Serializes this message to the [`FinalParam`] that should be sent as
+    /// the final parameter of a `PRIVMSG`/`NOTICE`. Equivalent to
+    /// `FinalParam::from(self)`.
+    pub fn into_final_param(self) -> FinalParam {
+        FinalParam::from(self)
     }
 }
 
@@ -118,7 +229,7 @@ impl From<CtcpMessage> for FinalParam {
         let (cmd, params) = match msg {
             CtcpMessage::Action(params) => (Cow::from("ACTION"), params),
             CtcpMessage::ClientInfo(params) => (Cow::from("CLIENTINFO"), params),
-            CtcpMessage::Dcc(params) => (Cow::from("DCC"), params),
+            CtcpMessage::Dcc(params) => (Cow::from("DCC"), params.map(CtcpParams::from)),
             CtcpMessage::Finger(params) => (Cow::from("FINGER"), params),
             CtcpMessage::Ping(params) => (Cow::from("PING"), params),
             CtcpMessage::Source(params) => (Cow::from("SOURCE"), params),
@@ -129,7 +240,7 @@ impl From<CtcpMessage> for FinalParam {
             CtcpMessage::Plain(fp) => return fp,
         };
         let s = if let Some(ps) = params {
-            format!("\x01{cmd} {ps}\x01")
+            format!("\x01{cmd} {}\x01", CtcpParams::quote(ps.as_str()))
         } else {
             format!("\x01{cmd}\x01")
         };
@@ -171,16 +282,82 @@ pub struct CtcpParams(String);
 
 validstr!(CtcpParams, ParseCtcpParamsError, validate_params);
 
+impl CtcpParams {
+    /// Returns the parameters' underlying bytes.  Since `CtcpParams` is
+    /// currently `String`-backed, this is just `as_str().as_bytes()`; it
+    /// exists as a stable byte-level entry point for callers building on
+    /// [`MaybeUtf8`](crate::MaybeUtf8) (e.g. to re-decode non-UTF-8 wire
+    /// data with a configured charset) without depending on the fact that
+    /// the parameters happen to already be valid UTF-8.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Applies the low-level quoting scheme from [the CTCP
+    /// draft](https://datatracker.ietf.org/doc/html/draft-oakley-irc-ctcp-02)
+    /// so that `s` can be embedded in a CTCP message body without its bytes
+    /// being mistaken for message structure: `\x10` (DLE) becomes `\x10\x10`,
+    /// NUL becomes `\x10` + `0`, CR becomes `\x10` + `r`, and LF becomes
+    /// `\x10` + `n`. This is applied automatically when serializing a
+    /// [`CtcpMessage`] to a [`FinalParam`]; it's exposed here for callers
+    /// that build raw CTCP text themselves.
+    pub fn quote(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '\x10' => out.push_str("\x10\x10"),
+                '\0' => out.push_str("\x10\x30"),
+                '\r' => out.push_str("\x10r"),
+                '\n' => out.push_str("\x10n"),
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Reverses [`CtcpParams::quote`]. An unrecognized character following
+    /// `\x10` is passed through unquoted (with the `\x10` dropped), and a
+    /// trailing unpaired `\x10` is dropped, matching the draft's guidance
+    /// for handling malformed quoting. This is applied automatically when
+    /// parsing a [`CtcpMessage`] out of a [`FinalParam`]; it's exposed here
+    /// for callers that need to dequote raw CTCP text themselves.
+    pub fn dequote(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c != '\x10' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('\x10') => out.push('\x10'),
+                Some('\x30') => out.push('\0'),
+                Some('r') => out.push('\r'),
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        }
+        out
+    }
+}
+
 impl From<CtcpParams> for FinalParam {
     fn from(value: CtcpParams) -> FinalParam {
         FinalParam::try_from(value.into_inner()).expect("CTCP params should be valid FinalParam")
     }
 }
 
+/// `CtcpParams` holds the *logical* (already-dequoted) parameter text, which
+/// may contain NUL, CR, or LF: those bytes are quoted per
+/// [`CtcpParams::quote`] whenever they'd otherwise corrupt the message
+/// structure on the wire. `\x01` (Ctrl-A) is still rejected outright, since
+/// it's the CTCP delimiter itself and the low-level quoting scheme doesn't
+/// cover it.
 fn validate_params(s: &str) -> Result<(), ParseCtcpParamsError> {
     if s.is_empty() {
         Err(ParseCtcpParamsError::Empty)
-    } else if s.contains(['\0', '\x01', '\r', '\n']) {
+    } else if s.contains('\x01') {
         Err(ParseCtcpParamsError::BadCharacter)
     } else {
         Ok(())
@@ -191,10 +368,277 @@ fn validate_params(s: &str) -> Result<(), ParseCtcpParamsError> {
 pub enum ParseCtcpParamsError {
     #[error("CTCP parameters cannot be empty")]
     Empty,
-    #[error("CTCP parameters cannot contain NUL, Ctrl-A, CR, or LF")]
+    #[error("CTCP parameters cannot contain Ctrl-A")]
     BadCharacter,
 }
 
+/// Error returned by [`CtcpMessage::new`].
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum NewCtcpMessageError {
+    #[error("invalid CTCP command")]
+    Command(#[from] ParseCtcpCommandError),
+    #[error("invalid CTCP parameters")]
+    Params(#[from] ParseCtcpParamsError),
+}
+
+/// A parsed `DCC CHAT` or `DCC SEND` offer, as carried in the parameters of
+/// a [`CtcpMessage::Dcc`] message.
+///
+/// Per the long-standing DCC convention, the address is encoded on the wire
+/// as a plain 32-bit integer (the IPv4 address in network byte order) rather
+/// than dotted-quad notation.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DccOffer {
+    Chat {
+        address: Ipv4Addr,
+        port: u16,
+    },
+    Send {
+        filename: String,
+        address: Ipv4Addr,
+        port: u16,
+        size: Option<u64>,
+    },
+}
+
+impl DccOffer {
+    pub fn address(&self) -> Ipv4Addr {
+        match self {
+            DccOffer::Chat { address, .. } | DccOffer::Send { address, .. } => *address,
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        match self {
+            DccOffer::Chat { port, .. } | DccOffer::Send { port, .. } => *port,
+        }
+    }
+
+    /// Render this offer as the parameters of an outgoing `DCC` CTCP
+    /// message.
+    pub fn to_ctcp_params(&self) -> CtcpParams {
+        let s = match self {
+            DccOffer::Chat { address, port } => {
+                format!("CHAT chat {} {port}", u32::from(*address))
+            }
+            DccOffer::Send {
+                filename,
+                address,
+                port,
+                size,
+            } => {
+                let filename = quote_dcc_filename(filename);
+                let mut s = format!("SEND {filename} {} {port}", u32::from(*address));
+                if let Some(sz) = size {
+                    s.push(' ');
+                    s.push_str(&sz.to_string());
+                }
+                s
+            }
+        };
+        CtcpParams::try_from(s).expect("a formatted DCC offer should be valid CtcpParams")
+    }
+}
+
+impl From<DccOffer> for CtcpMessage {
+    fn from(offer: DccOffer) -> CtcpMessage {
+        CtcpMessage::Dcc(Some(DccMessage::Offer(offer)))
+    }
+}
+
+impl TryFrom<&CtcpParams> for DccOffer {
+    type Error = ParseDccOfferError;
+
+    fn try_from(params: &CtcpParams) -> Result<DccOffer, ParseDccOfferError> {
+        let s = params.as_str();
+        let (kind, rest) = s.split_once(' ').unwrap_or((s, ""));
+        if kind.eq_ignore_ascii_case("CHAT") {
+            let mut words = rest.split(' ');
+            let _protocol = words
+                .next()
+                .ok_or(ParseDccOfferError::MissingField("protocol"))?;
+            let address = parse_dcc_address(
+                words
+                    .next()
+                    .ok_or(ParseDccOfferError::MissingField("address"))?,
+            )?;
+            let port = parse_dcc_port(
+                words
+                    .next()
+                    .ok_or(ParseDccOfferError::MissingField("port"))?,
+            )?;
+            Ok(DccOffer::Chat { address, port })
+        } else if kind.eq_ignore_ascii_case("SEND") {
+            let (filename, rest) =
+                split_dcc_filename(rest).ok_or(ParseDccOfferError::MissingField("filename"))?;
+            let mut words = rest.split(' ').filter(|w| !w.is_empty());
+            let address = parse_dcc_address(
+                words
+                    .next()
+                    .ok_or(ParseDccOfferError::MissingField("address"))?,
+            )?;
+            let port = parse_dcc_port(
+                words
+                    .next()
+                    .ok_or(ParseDccOfferError::MissingField("port"))?,
+            )?;
+            let size = match words.next() {
+                Some(sz) => Some(
+                    sz.parse::<u64>()
+                        .map_err(|_| ParseDccOfferError::InvalidField("size"))?,
+                ),
+                None => None,
+            };
+            Ok(DccOffer::Send {
+                filename,
+                address,
+                port,
+                size,
+            })
+        } else {
+            Err(ParseDccOfferError::UnknownType(kind.to_string()))
+        }
+    }
+}
+
+/// Split a DCC filename token off the front of `s`, honoring the common
+/// convention of wrapping filenames containing spaces in double quotes, and
+/// return it along with the (trimmed) remainder of `s`.
+fn split_dcc_filename(s: &str) -> Option<(String, &str)> {
+    if let Some(rest) = s.strip_prefix('"') {
+        let (filename, rest) = rest.split_once('"')?;
+        Some((filename.to_string(), rest.trim_start()))
+    } else {
+        let (filename, rest) = s.split_once(' ').unwrap_or((s, ""));
+        if filename.is_empty() {
+            None
+        } else {
+            Some((filename.to_string(), rest))
+        }
+    }
+}
+
+/// Wrap `filename` in double quotes if it contains a space, per the common
+/// DCC filename-quoting convention; otherwise return it unchanged.
+fn quote_dcc_filename(filename: &str) -> Cow<'_, str> {
+    if filename.contains(' ') {
+        Cow::from(format!("\"{filename}\""))
+    } else {
+        Cow::from(filename)
+    }
+}
+
+fn parse_dcc_address(s: &str) -> Result<Ipv4Addr, ParseDccOfferError> {
+    s.parse::<u32>()
+        .map(Ipv4Addr::from)
+        .map_err(|_| ParseDccOfferError::InvalidField("address"))
+}
+
+fn parse_dcc_port(s: &str) -> Result<u16, ParseDccOfferError> {
+    s.parse::<u16>()
+        .map_err(|_| ParseDccOfferError::InvalidField("port"))
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ParseDccOfferError {
+    #[error("DCC offer parameters were empty")]
+    Empty,
+    #[error("DCC offer is missing its {0} field")]
+    MissingField(&'static str),
+    #[error("DCC offer has an invalid {0} field")]
+    InvalidField(&'static str),
+    #[error("unknown DCC offer type {0:?}")]
+    UnknownType(String),
+}
+
+/// The parameters of a [`CtcpMessage::Dcc`] message, parsed into one of the
+/// standard `DCC` sub-commands.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DccMessage {
+    /// A `DCC CHAT` or `DCC SEND` offer.
+    Offer(DccOffer),
+    /// A `DCC RESUME` request, asking the sender to restart a stalled
+    /// `DCC SEND` transfer of `filename` at byte `position`.
+    Resume {
+        filename: String,
+        port: u16,
+        position: u64,
+    },
+    /// A `DCC ACCEPT` reply, confirming a `DCC RESUME` request.
+    Accept {
+        filename: String,
+        port: u16,
+        position: u64,
+    },
+    /// A `DCC` sub-command this crate doesn't model, kept verbatim so no
+    /// data is lost.
+    Unknown(CtcpParams),
+}
+
+impl From<CtcpParams> for DccMessage {
+    fn from(params: CtcpParams) -> DccMessage {
+        if let Ok(offer) = DccOffer::try_from(&params) {
+            return DccMessage::Offer(offer);
+        }
+        let s = params.as_str();
+        let (kind, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let parsed = if kind.eq_ignore_ascii_case("RESUME") {
+            parse_resume_or_accept(rest, true)
+        } else if kind.eq_ignore_ascii_case("ACCEPT") {
+            parse_resume_or_accept(rest, false)
+        } else {
+            None
+        };
+        parsed.unwrap_or(DccMessage::Unknown(params))
+    }
+}
+
+fn parse_resume_or_accept(rest: &str, is_resume: bool) -> Option<DccMessage> {
+    let (filename, rest) = split_dcc_filename(rest)?;
+    let mut words = rest.split(' ').filter(|w| !w.is_empty());
+    let port = words.next()?.parse::<u16>().ok()?;
+    let position = words.next()?.parse::<u64>().ok()?;
+    Some(if is_resume {
+        DccMessage::Resume {
+            filename,
+            port,
+            position,
+        }
+    } else {
+        DccMessage::Accept {
+            filename,
+            port,
+            position,
+        }
+    })
+}
+
+impl From<DccMessage> for CtcpParams {
+    fn from(msg: DccMessage) -> CtcpParams {
+        let s = match msg {
+            DccMessage::Offer(offer) => return offer.to_ctcp_params(),
+            DccMessage::Resume {
+                filename,
+                port,
+                position,
+            } => {
+                let filename = quote_dcc_filename(&filename);
+                format!("RESUME {filename} {port} {position}")
+            }
+            DccMessage::Accept {
+                filename,
+                port,
+                position,
+            } => {
+                let filename = quote_dcc_filename(&filename);
+                format!("ACCEPT {filename} {port} {position}")
+            }
+            DccMessage::Unknown(params) => return params,
+        };
+        CtcpParams::try_from(s).expect("a formatted DCC message should be valid CtcpParams")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +694,336 @@ mod tests {
         let ctcp = CtcpMessage::from(p);
         assert_eq!(ctcp, CtcpMessage::Action(None));
     }
+
+    #[test]
+    fn new_action() {
+        let ctcp = CtcpMessage::new_action("writes some specs!").unwrap();
+        assert_matches!(ctcp, CtcpMessage::Action(Some(ps)) => {
+            assert_eq!(ps, "writes some specs!");
+        });
+        let fp = FinalParam::from(ctcp);
+        assert_eq!(fp, "\x01ACTION writes some specs!\x01");
+    }
+
+    #[test]
+    fn new_action_empty() {
+        let ctcp = CtcpMessage::new_action("").unwrap();
+        assert_eq!(ctcp, CtcpMessage::Action(None));
+    }
+
+    #[test]
+    fn new_with_known_command() {
+        let ctcp = CtcpMessage::new("VERSION", "").unwrap();
+        assert_eq!(ctcp, CtcpMessage::Version(None));
+        let ctcp = CtcpMessage::new("PING", "1473523796 918320").unwrap();
+        assert_matches!(ctcp, CtcpMessage::Ping(Some(ps)) => {
+            assert_eq!(ps, "1473523796 918320");
+        });
+    }
+
+    #[test]
+    fn new_with_unknown_command() {
+        let ctcp = CtcpMessage::new("FOOBAR", "baz").unwrap();
+        assert_matches!(ctcp, CtcpMessage::Other { command, params: Some(ps) } => {
+            assert_eq!(command, "FOOBAR");
+            assert_eq!(ps, "baz");
+        });
+        let fp = FinalParam::from(ctcp);
+        assert_eq!(fp, "\x01FOOBAR baz\x01");
+    }
+
+    #[test]
+    fn new_rejects_invalid_command() {
+        assert_matches!(
+            CtcpMessage::new("BAD CMD", ""),
+            Err(NewCtcpMessageError::Command(ParseCtcpCommandError::BadCharacter))
+        );
+    }
+
+    #[test]
+    fn dcc_chat_offer() {
+        let params = "CHAT chat 3232235521 5000".parse::<CtcpParams>().unwrap();
+        let offer = DccOffer::try_from(&params).unwrap();
+        assert_eq!(
+            offer,
+            DccOffer::Chat {
+                address: Ipv4Addr::new(192, 168, 0, 1),
+                port: 5000,
+            }
+        );
+        assert_eq!(offer.to_ctcp_params(), params);
+    }
+
+    #[test]
+    fn dcc_send_offer() {
+        let params = "SEND filename.txt 3232235521 5000 1024"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let offer = DccOffer::try_from(&params).unwrap();
+        assert_eq!(
+            offer,
+            DccOffer::Send {
+                filename: "filename.txt".to_string(),
+                address: Ipv4Addr::new(192, 168, 0, 1),
+                port: 5000,
+                size: Some(1024),
+            }
+        );
+        assert_eq!(offer.to_ctcp_params(), params);
+    }
+
+    #[test]
+    fn dcc_send_offer_no_size() {
+        let params = "SEND filename.txt 3232235521 5000"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let offer = DccOffer::try_from(&params).unwrap();
+        assert_eq!(
+            offer,
+            DccOffer::Send {
+                filename: "filename.txt".to_string(),
+                address: Ipv4Addr::new(192, 168, 0, 1),
+                port: 5000,
+                size: None,
+            }
+        );
+    }
+
+    #[test]
+    fn dcc_offer_unknown_type() {
+        let params = "XMIT filename.txt 3232235521 5000"
+            .parse::<CtcpParams>()
+            .unwrap();
+        assert_matches!(
+            DccOffer::try_from(&params),
+            Err(ParseDccOfferError::UnknownType(kind)) => {
+                assert_eq!(kind, "XMIT");
+            }
+        );
+    }
+
+    #[test]
+    fn dcc_send_offer_quoted_filename() {
+        let params = "SEND \"my file.txt\" 3232235521 5000 1024"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let offer = DccOffer::try_from(&params).unwrap();
+        assert_eq!(
+            offer,
+            DccOffer::Send {
+                filename: "my file.txt".to_string(),
+                address: Ipv4Addr::new(192, 168, 0, 1),
+                port: 5000,
+                size: Some(1024),
+            }
+        );
+        assert_eq!(offer.to_ctcp_params(), params);
+    }
+
+    #[test]
+    fn dcc_message_from_chat() {
+        let p = "\x01DCC CHAT chat 3232235521 5000\x01"
+            .parse::<FinalParam>()
+            .unwrap();
+        let ctcp = CtcpMessage::from(p);
+        assert_matches!(ctcp, CtcpMessage::Dcc(Some(DccMessage::Offer(offer))) => {
+            assert_eq!(offer, DccOffer::Chat {
+                address: Ipv4Addr::new(192, 168, 0, 1),
+                port: 5000,
+            });
+        });
+    }
+
+    #[test]
+    fn dcc_message_resume() {
+        let params = "RESUME \"my file.txt\" 5000 1024"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let msg = DccMessage::from(params.clone());
+        assert_eq!(
+            msg,
+            DccMessage::Resume {
+                filename: "my file.txt".to_string(),
+                port: 5000,
+                position: 1024,
+            }
+        );
+        assert_eq!(CtcpParams::from(msg), params);
+    }
+
+    #[test]
+    fn dcc_message_accept() {
+        let params = "ACCEPT filename.txt 5000 1024"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let msg = DccMessage::from(params.clone());
+        assert_eq!(
+            msg,
+            DccMessage::Accept {
+                filename: "filename.txt".to_string(),
+                port: 5000,
+                position: 1024,
+            }
+        );
+        assert_eq!(CtcpParams::from(msg), params);
+    }
+
+    #[test]
+    fn dcc_message_unknown_subcommand_preserves_params() {
+        let params = "XMIT filename.txt 3232235521 5000"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let msg = DccMessage::from(params.clone());
+        assert_eq!(msg, DccMessage::Unknown(params.clone()));
+        assert_eq!(CtcpParams::from(msg), params);
+    }
+
+    #[test]
+    fn dcc_message_malformed_resume_is_unknown() {
+        let params = "RESUME filename.txt notaport"
+            .parse::<CtcpParams>()
+            .unwrap();
+        let msg = DccMessage::from(params.clone());
+        assert_eq!(msg, DccMessage::Unknown(params));
+    }
+
+    #[test]
+    fn parse_all_single_ctcp_message() {
+        let p = "\x01PING 12345\x01".parse::<FinalParam>().unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        assert_eq!(messages.len(), 1);
+        assert_matches!(&messages[0], CtcpMessage::Ping(Some(ps)) => {
+            assert_eq!(ps, "12345");
+        });
+    }
+
+    #[test]
+    fn parse_all_plain_text_only() {
+        let p = "just chatting".parse::<FinalParam>().unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        assert_eq!(messages, vec![CtcpMessage::Plain(p)]);
+    }
+
+    #[test]
+    fn parse_all_interleaved_segments() {
+        let p = "hi \x01ACTION waves\x01 bye \x01VERSION\x01"
+            .parse::<FinalParam>()
+            .unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        assert_eq!(
+            messages,
+            vec![
+                CtcpMessage::Plain("hi ".parse().unwrap()),
+                CtcpMessage::Action(Some("waves".parse().unwrap())),
+                CtcpMessage::Plain(" bye ".parse().unwrap()),
+                CtcpMessage::Version(None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_unterminated_trailing_delimiter() {
+        let p = "before \x01ACTION waves".parse::<FinalParam>().unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        assert_eq!(
+            messages,
+            vec![
+                CtcpMessage::Plain("before ".parse().unwrap()),
+                CtcpMessage::Action(Some("waves".parse().unwrap())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_malformed_segment_is_plain() {
+        let p = "\x01\x01 after".parse::<FinalParam>().unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        assert_eq!(
+            messages,
+            vec![
+                CtcpMessage::Plain("\x01\x01".parse().unwrap()),
+                CtcpMessage::Plain(" after".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_round_trips() {
+        // Only a trailing, properly closed `\x01...\x01` segment round-trips
+        // exactly: like a standalone CtcpMessage, a segment that was parsed
+        // from an *unterminated* trailing delimiter always gets one added
+        // back when re-serialized, since CtcpMessage has no way to remember
+        // that the original was missing its closing `\x01`.
+        let p = "hi \x01ACTION waves\x01 bye \x01VERSION\x01"
+            .parse::<FinalParam>()
+            .unwrap();
+        let messages = CtcpMessage::parse_all(&p);
+        let rejoined: String = messages
+            .into_iter()
+            .map(|m| FinalParam::from(m).into_inner())
+            .collect();
+        assert_eq!(rejoined, p.as_str());
+    }
+
+    #[test]
+    fn quote_escapes_dle_nul_cr_lf() {
+        assert_eq!(
+            CtcpParams::quote("a\x10b\0c\rd\ne"),
+            "a\x10\x10b\x10\x30c\x10rd\x10ne"
+        );
+    }
+
+    #[test]
+    fn dequote_reverses_quote() {
+        let raw = "a\x10b\0c\rd\ne";
+        assert_eq!(CtcpParams::dequote(&CtcpParams::quote(raw)), raw);
+    }
+
+    #[test]
+    fn dequote_drops_trailing_unpaired_dle() {
+        assert_eq!(CtcpParams::dequote("abc\x10"), "abc");
+    }
+
+    #[test]
+    fn dequote_passes_through_unrecognized_quoted_char() {
+        assert_eq!(CtcpParams::dequote("a\x10zb"), "azb");
+    }
+
+    #[test]
+    fn ctcp_message_round_trips_embedded_control_bytes() {
+        let text = "line one\r\nline two\0with NUL and \x10 DLE";
+        let msg = CtcpMessage::Finger(Some(CtcpParams::try_from(text.to_owned()).unwrap()));
+        let p = FinalParam::from(msg.clone());
+        assert_eq!(CtcpMessage::from(p), msg);
+    }
+
+    #[test]
+    fn as_ctcp_recognizes_ctcp_payload() {
+        let p = "\x01ACTION waves\x01".parse::<FinalParam>().unwrap();
+        let ctcp = p.as_ctcp().unwrap();
+        assert_matches!(ctcp, CtcpMessage::Action(Some(ps)) => {
+            assert_eq!(ps, "waves");
+        });
+    }
+
+    #[test]
+    fn as_ctcp_is_none_for_plain_text() {
+        let p = "just chatting".parse::<FinalParam>().unwrap();
+        assert_eq!(p.as_ctcp(), None);
+    }
+
+    #[test]
+    fn into_final_param_round_trips_with_as_ctcp() {
+        let ctcp = CtcpMessage::new_action("waves").unwrap();
+        let p = ctcp.clone().into_final_param();
+        assert_eq!(p.as_ctcp(), Some(ctcp));
+    }
+
+    #[test]
+    fn validate_params_still_rejects_raw_ctrl_a() {
+        assert_eq!(
+            CtcpParams::try_from("has\x01delim".to_owned()),
+            Err(ParseCtcpParamsError::BadCharacter)
+        );
+    }
 }
@@ -153,6 +153,26 @@ impl From<AttributeSet> for anstyle::Effects {
     }
 }
 
+#[cfg(feature = "anstyle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+impl From<anstyle::Effects> for AttributeSet {
+    /// Convert an [`anstyle::Effects`] to an `AttributeSet`, discarding any
+    /// effects (e.g. dimmed, hidden) that have no IRC equivalent.
+    fn from(value: anstyle::Effects) -> AttributeSet {
+        [
+            (anstyle::Effects::BOLD, Attribute::Bold),
+            (anstyle::Effects::ITALIC, Attribute::Italic),
+            (anstyle::Effects::UNDERLINE, Attribute::Underline),
+            (anstyle::Effects::STRIKETHROUGH, Attribute::Strikethrough),
+            (anstyle::Effects::INVERT, Attribute::Reverse),
+        ]
+        .into_iter()
+        .filter(|&(effect, _)| value.contains(effect))
+        .map(|(_, attr)| attr)
+        .collect()
+    }
+}
+
 impl<A: Into<AttributeSet>> std::ops::BitAnd<A> for AttributeSet {
     type Output = AttributeSet;
 
@@ -288,6 +288,33 @@ impl Color100 {
     pub fn to_anstyle(self) -> Option<anstyle::Ansi256Color> {
         self.to_ansi_index().map(anstyle::Ansi256Color)
     }
+
+    /// Finds the closest color among IRC colors 0 through 15 (the "basic 16"
+    /// supported by legacy clients), for downgrading a message that uses the
+    /// extended 16-98 palette.  Colors already in 0..=15, as well as
+    /// [`Color100::DEFAULT`], are returned unchanged.
+    ///
+    /// Closeness is measured as the absolute difference between the ANSI
+    /// 256-color palette indices that [`Color100::to_ansi_index`] maps each
+    /// color to, not true color distance, so the result is only a rough
+    /// approximation.
+    pub fn remap_to_basic16(self) -> Color100 {
+        if self.0 <= 15 || self == Color100::DEFAULT {
+            return self;
+        }
+        let Some(index) = self.to_ansi_index() else {
+            return self;
+        };
+        (0..=15u8)
+            .map(Color100)
+            .min_by_key(|c| {
+                let other = c
+                    .to_ansi_index()
+                    .expect("basic 16 colors always have an ANSI index");
+                (i32::from(index) - i32::from(other)).abs()
+            })
+            .expect("range 0..=15 is non-empty")
+    }
 }
 
 impl Default for Color100 {
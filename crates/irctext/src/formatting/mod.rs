@@ -1,9 +1,64 @@
+//! Parsing and rendering of IRC's inline text formatting control codes
+//! (bold, italic, underline, strikethrough, monospace, reverse, and the
+//! mIRC palette/24-bit hex color codes), per
+//! <https://modern.ircdocs.horse/formatting>.
+//!
+//! [`StyledLine::parse`] turns a raw line into a sequence of [`StyledSpan`]s
+//! carrying the active [`Style`] (built from [`AttributeSet`] and
+//! [`Color`]), and [`StyledLine::format`] serializes spans back to
+//! control-code text.  With the `anstyle` feature enabled, a span's style
+//! can also be rendered as an [`anstyle::Style`] via
+//! [`StyledSpan::render_ansi`], and `Style`/`anstyle::Style` convert in
+//! both directions (the reverse mapping back from a terminal style is
+//! necessarily lossy; see `impl From<anstyle::Style> for Style`).
+//! [`StyledLine::to_ansi`] is the eager, owned-`String` counterpart to
+//! [`StyledLine::render_ansi`] for callers that don't need a `Display`
+//! wrapper. For terminals with a more limited color range, the
+//! `_with_depth` variants of both take a [`ColorDepth`] and quantize colors
+//! down to the nearest entry the requested depth supports.
+//!
+//! Since the color control byte (`0x03`) only ever takes up to two ASCII
+//! digits, a parsed foreground/background number is always in `0..=99` and
+//! never needs separate clamping against [`Color100`]'s range.
+//!
+//! For callers that just want to stream over a line without collecting a
+//! `Vec<StyledSpan>`, [`SpanIter`] yields the same `(text, Style)` runs
+//! lazily, and [`strip_formatting`] removes control bytes outright,
+//! borrowing the input unchanged when there are none to remove.  With the
+//! `unicode-width` feature enabled, [`display_width`] additionally measures
+//! a line's rendered terminal width for column alignment.
+//!
+//! [`extract_colors`] classifies a line's runs by resolved
+//! foreground/background [`Color100`], for callers that want to detect or
+//! downgrade colored text; [`Color100::remap_to_basic16`] helps with the
+//! latter by mapping the extended 16-98 palette down to the basic 16 colors.
+//!
+//! With the `anstyle` feature enabled, [`StyledLine::from_ansi`] is the
+//! inverse of [`StyledLine::render_ansi`]: it scans `ESC [ ... m` SGR
+//! sequences out of captured terminal output and turns them back into
+//! [`StyledSpan`]s.
+//!
+//! [`LineBuilder`] offers a chainable alternative to [`StyledLine::parse`]
+//! for callers that want to construct a styled line directly, without
+//! hand-writing control bytes.
+//!
+//! [`StyledLine::parse_section`]/[`StyledLine::format_section`] translate to
+//! and from the legacy Minecraft-style section-sign (`§`) formatting scheme,
+//! for bridges that relay between IRC and chat systems using that encoding.
+//!
+//! [`Theme`] loads a category-to-[`Style`] mapping from a compact
+//! `LS_COLORS`-style spec string (optionally read straight from an
+//! environment variable via [`Theme::from_env`]), for clients that want to
+//! let users configure how nicks, URLs, or keywords are highlighted without
+//! recompiling.
 mod attributes;
 mod color100;
 mod rgbcolor;
+mod theme;
 pub use self::attributes::*;
 pub use self::color100::*;
 pub use self::rgbcolor::*;
+pub use self::theme::*;
 use std::borrow::Cow;
 use std::fmt::Write;
 use std::ops::Range;
@@ -31,13 +86,55 @@ impl Color {
     #[cfg(feature = "anstyle")]
     #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
     pub fn to_anstyle(self) -> Option<anstyle::Color> {
-        match self {
-            Color::Color100(c) => c.to_anstyle().map(anstyle::Color::from),
-            Color::Rgb(c) => Some(anstyle::Color::from(c.to_anstyle())),
+        self.to_anstyle_with_depth(ColorDepth::TrueColor)
+    }
+
+    /// Like [`Color::to_anstyle`], but quantizes the color down to the
+    /// nearest entry in the palette supported by `depth` rather than always
+    /// emitting a 24-bit or (for [`Color100`]) exact 256-color escape, for
+    /// rendering to terminals with a more limited color range.
+    #[cfg(feature = "anstyle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+    pub fn to_anstyle_with_depth(self, depth: ColorDepth) -> Option<anstyle::Color> {
+        match depth {
+            ColorDepth::TrueColor => match self {
+                Color::Color100(c) => c.to_anstyle().map(anstyle::Color::from),
+                Color::Rgb(c) => Some(anstyle::Color::from(c.to_anstyle())),
+            },
+            ColorDepth::Indexed256 => match self {
+                Color::Color100(c) => c.to_anstyle().map(anstyle::Color::from),
+                Color::Rgb(c) => Some(anstyle::Color::Ansi256(anstyle::Ansi256Color(
+                    quantize_indexed256(c),
+                ))),
+            },
+            ColorDepth::Basic16 => {
+                let rgb = match self {
+                    Color::Color100(c) => RgbColor::from(c),
+                    Color::Rgb(c) => c,
+                };
+                Some(anstyle::Color::Ansi(quantize_basic16(rgb)))
+            }
         }
     }
 }
 
+/// The color range a terminal supports, for quantizing [`Color`]s down to
+/// the nearest supported entry when rendering via
+/// [`StyledLine::render_ansi_with_depth`]/[`StyledLine::to_ansi_with_depth`].
+#[cfg(feature = "anstyle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ColorDepth {
+    /// The eight standard ANSI colors and their eight bright variants.
+    Basic16,
+    /// The xterm 256-color palette: the basic 16, a 6×6×6 color cube, and a
+    /// 24-step grayscale ramp.
+    Indexed256,
+    /// 24-bit RGB, rendered exactly with no quantization.
+    #[default]
+    TrueColor,
+}
+
 impl Default for Color {
     fn default() -> Color {
         Color::Color100(Color100::default())
@@ -77,14 +174,52 @@ impl Style {
 #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
 impl From<Style> for anstyle::Style {
     fn from(style: Style) -> anstyle::Style {
+        style.to_anstyle_with_depth(ColorDepth::TrueColor)
+    }
+}
+
+#[cfg(feature = "anstyle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+impl Style {
+    /// Like `anstyle::Style::from(style)`, but quantizes both colors to the
+    /// nearest entry supported by `depth`; see [`Color::to_anstyle_with_depth`].
+    pub fn to_anstyle_with_depth(self, depth: ColorDepth) -> anstyle::Style {
         anstyle::Style::new()
-            .fg_color(style.foreground.to_anstyle())
-            .bg_color(style.background.to_anstyle())
-            .effects(style.attributes.into())
+            .fg_color(self.foreground.to_anstyle_with_depth(depth))
+            .bg_color(self.background.to_anstyle_with_depth(depth))
+            .effects(self.attributes.into())
+    }
+}
+
+#[cfg(feature = "anstyle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+impl From<anstyle::Style> for Style {
+    /// Convert an [`anstyle::Style`] to the closest IRC `Style`, via
+    /// [`Color100::try_from_ansi_index`] for any 256-color components.
+    /// Colors with no IRC equivalent (the default, a basic 4-bit
+    /// [`anstyle::AnsiColor`], or 24-bit RGB) fall back to
+    /// `Color::default()`.
+    fn from(style: anstyle::Style) -> Style {
+        Style {
+            foreground: color_from_ansi(style.get_fg_color()),
+            background: color_from_ansi(style.get_bg_color()),
+            attributes: AttributeSet::from(style.get_effects()),
+        }
+    }
+}
+
+#[cfg(feature = "anstyle")]
+fn color_from_ansi(color: Option<anstyle::Color>) -> Color {
+    match color {
+        Some(anstyle::Color::Ansi256(anstyle::Ansi256Color(index))) => {
+            Color100::try_from_ansi_index(index).map_or_else(Color::default, Color::from)
+        }
+        _ => Color::default(),
     }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[doc(alias = "Span")]
 pub struct StyledSpan<'a> {
     pub style: Style,
     pub content: Cow<'a, str>,
@@ -94,7 +229,13 @@ pub struct StyledSpan<'a> {
 #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
 impl<'a> StyledSpan<'a> {
     pub fn render_ansi(&self) -> RenderStyledSpan<'_, 'a> {
-        RenderStyledSpan(self)
+        self.render_ansi_with_depth(ColorDepth::default())
+    }
+
+    /// Like [`StyledSpan::render_ansi`], but quantizes colors to the nearest
+    /// entry supported by `depth`.
+    pub fn render_ansi_with_depth(&self, depth: ColorDepth) -> RenderStyledSpan<'_, 'a> {
+        RenderStyledSpan(self, depth)
     }
 }
 
@@ -119,12 +260,12 @@ impl<'a> From<&'a str> for StyledSpan<'a> {
 #[cfg(feature = "anstyle")]
 #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct RenderStyledSpan<'a, 'b>(&'a StyledSpan<'b>);
+pub struct RenderStyledSpan<'a, 'b>(&'a StyledSpan<'b>, ColorDepth);
 
 #[cfg(feature = "anstyle")]
 impl fmt::Display for RenderStyledSpan<'_, '_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let style = anstyle::Style::from(self.0.style);
+        let style = self.0.style.to_anstyle_with_depth(self.1);
         write!(f, "{style}{}{style:#}", self.0.content)
     }
 }
@@ -288,23 +429,306 @@ impl<'a> StyledLine<'a> {
         self.into_iter()
     }
 
+    /// Serialize this line the same way as [`StyledLine::format`], except
+    /// that any [`Color::Rgb`] foreground/background is first quantized to
+    /// its nearest [`Color100`] via [`RgbColor::to_nearest_color100`], so
+    /// the output only ever uses `\x03`/[`Color100`] codes and never the
+    /// hex-color (`\x04`) code, for clients that don't understand it.
+    pub fn format_basic(&self) -> String {
+        self.iter()
+            .cloned()
+            .map(|span| StyledSpan {
+                style: Style {
+                    foreground: quantize_color(span.style.foreground),
+                    background: quantize_color(span.style.background),
+                    ..span.style
+                },
+                content: span.content,
+            })
+            .collect::<StyledLine>()
+            .format()
+    }
+
     #[cfg(feature = "anstyle")]
     #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
     pub fn render_ansi<'b>(&'b self) -> RenderStyledLine<'b, 'a> {
-        RenderStyledLine(self)
+        self.render_ansi_with_depth(ColorDepth::default())
+    }
+
+    /// Like [`StyledLine::render_ansi`], but quantizes colors to the nearest
+    /// entry supported by `depth`, for rendering to terminals with a more
+    /// limited color range; see [`ColorDepth`].
+    #[cfg(feature = "anstyle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+    pub fn render_ansi_with_depth<'b>(&'b self, depth: ColorDepth) -> RenderStyledLine<'b, 'a> {
+        RenderStyledLine(self, depth)
+    }
+
+    /// Render this line to an owned `String` of ANSI SGR escape sequences,
+    /// the eager counterpart to [`StyledLine::render_ansi`] for callers that
+    /// just want a `String` rather than a `Display` wrapper.
+    #[cfg(feature = "anstyle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+    pub fn to_ansi(&self) -> String {
+        self.render_ansi().to_string()
+    }
+
+    /// The eager, owned-`String` counterpart to
+    /// [`StyledLine::render_ansi_with_depth`].
+    #[cfg(feature = "anstyle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+    pub fn to_ansi_with_depth(&self, depth: ColorDepth) -> String {
+        self.render_ansi_with_depth(depth).to_string()
+    }
+
+    /// Parse a string containing ANSI `ESC [ ... m` (SGR) escape sequences
+    /// into a [`StyledLine`], the inverse of [`StyledLine::render_ansi`].
+    ///
+    /// Bytes outside of SGR sequences, and any escape sequence that is cut
+    /// off before a terminating `m`, are treated as literal text.
+    /// Unrecognized or malformed SGR parameters are ignored.
+    #[cfg(feature = "anstyle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
+    pub fn from_ansi(s: &'a str) -> StyledLine<'a> {
+        let mut builder = StyledLineBuilder::new();
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == 0x1B && bytes.get(i + 1) == Some(&b'[') {
+                if let Some(m_index) = find_sgr_terminator(bytes, i + 2) {
+                    apply_sgr_params(&mut builder, &s[i + 2..m_index], i);
+                    i = m_index + 1;
+                    continue;
+                }
+            }
+            builder.push_char(i);
+            i += 1;
+        }
+        StyledLine::from_iter(builder.finish(s.len()).map(|(style, range)| StyledSpan {
+            style,
+            content: Cow::from(&s[range]),
+        }))
+    }
+
+    /// Parse a string using the legacy Minecraft-style section-sign (`§`)
+    /// formatting scheme into a [`StyledLine`], the inverse of
+    /// [`StyledLine::format_section`].
+    ///
+    /// The 16 color codes `§0`-`§9`/`§a`-`§f` (case-insensitive) map to the
+    /// closest [`Color100`] in the classic IRC palette; `§l`/`§o`/`§n`/`§m`
+    /// set [`Attribute::Bold`]/[`Attribute::Italic`]/[`Attribute::Underline`]/
+    /// [`Attribute::Strikethrough`]; and `§r` resets to [`Style::default`].
+    /// A `§` not followed by a recognized code is treated as literal text.
+    pub fn parse_section(s: &'a str) -> StyledLine<'a> {
+        let mut builder = StyledLineBuilder::new();
+        let mut iter = s.char_indices().peekable();
+        while let Some((i, ch)) = iter.next() {
+            if ch != SECTION_CHAR {
+                builder.push_char(i);
+                continue;
+            }
+            let Some(&(_, code)) = iter.peek() else {
+                builder.push_char(i);
+                continue;
+            };
+            let code = code.to_ascii_lowercase();
+            if code == 'r' {
+                iter.next();
+                builder.reset(i);
+            } else if let Some(color) = section_color(code) {
+                iter.next();
+                builder.set_foreground(color, i);
+            } else if let Some(attr) = section_attribute(code) {
+                iter.next();
+                builder.set_attribute(attr, true, i);
+            } else {
+                builder.push_char(i);
+            }
+        }
+        StyledLine::from_iter(builder.finish(s.len()).map(|(style, range)| StyledSpan {
+            style,
+            content: Cow::from(&s[range]),
+        }))
+    }
+
+    /// Serialize this line using the legacy Minecraft-style section-sign
+    /// (`§`) formatting scheme, the inverse of [`StyledLine::parse_section`].
+    ///
+    /// Any foreground color, whether [`Color::Color100`] or [`Color::Rgb`],
+    /// is downgraded to the nearest of the 16 classic section-sign colors.
+    /// Background colors, [`Attribute::Monospace`], and [`Attribute::Reverse`]
+    /// have no section-sign equivalent and are silently dropped.
+    pub fn format_section(&self) -> String {
+        let mut s = String::new();
+        let mut prev_color = None;
+        let mut prev_attrs = AttributeSet::EMPTY;
+        for span in self {
+            if span.content.is_empty() {
+                continue;
+            }
+            let cur_color = section_char_for(span.style.foreground);
+            let cur_attrs = minecraft_attrs(span.style.attributes);
+            let color_needs_clearing = prev_color.is_some() && cur_color.is_none();
+            let attrs_need_clearing = !(prev_attrs - cur_attrs).is_empty();
+            if cur_color.is_none() && cur_attrs.is_empty() {
+                if prev_color.is_some() || !prev_attrs.is_empty() {
+                    s.push(SECTION_CHAR);
+                    s.push('r');
+                }
+            } else if color_needs_clearing || attrs_need_clearing {
+                s.push(SECTION_CHAR);
+                s.push('r');
+                if let Some(ch) = cur_color {
+                    s.push(SECTION_CHAR);
+                    s.push(ch);
+                }
+                for attr in cur_attrs {
+                    s.push(SECTION_CHAR);
+                    s.push(section_code(attr));
+                }
+            } else {
+                if cur_color != prev_color {
+                    if let Some(ch) = cur_color {
+                        s.push(SECTION_CHAR);
+                        s.push(ch);
+                    }
+                }
+                for attr in cur_attrs - prev_attrs {
+                    s.push(SECTION_CHAR);
+                    s.push(section_code(attr));
+                }
+            }
+            s.push_str(&span.content);
+            prev_color = cur_color;
+            prev_attrs = cur_attrs;
+        }
+        s
+    }
+}
+
+/// A chainable builder for constructing a [`StyledLine`] programmatically,
+/// without hand-writing control bytes.
+///
+/// ```
+/// use irctext::formatting::{Color100, LineBuilder};
+///
+/// let line = LineBuilder::new()
+///     .bold()
+///     .fg(Color100::RED)
+///     .text("so ")
+///     .reset()
+///     .text("great")
+///     .build();
+/// ```
+///
+/// Consecutive [`LineBuilder::text`] calls made under the same style are
+/// coalesced into a single [`StyledSpan`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineBuilder<'a> {
+    spans: Vec<StyledSpan<'a>>,
+    style: Style,
+}
+
+impl<'a> LineBuilder<'a> {
+    pub fn new() -> LineBuilder<'a> {
+        LineBuilder::default()
+    }
+
+    /// Append text under the builder's current style
+    pub fn text(mut self, content: impl Into<Cow<'a, str>>) -> LineBuilder<'a> {
+        let content = content.into();
+        if content.is_empty() {
+            return self;
+        }
+        match self.spans.last_mut() {
+            Some(last) if last.style == self.style => last.content.to_mut().push_str(&content),
+            _ => self.spans.push(StyledSpan {
+                style: self.style,
+                content,
+            }),
+        }
+        self
+    }
+
+    /// Toggle a single [`Attribute`] on or off
+    pub fn toggle(mut self, attr: Attribute) -> LineBuilder<'a> {
+        self.style.attributes ^= attr;
+        self
+    }
+
+    /// Toggle [`Attribute::Bold`]
+    pub fn bold(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Bold)
+    }
+
+    /// Toggle [`Attribute::Italic`]
+    pub fn italic(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Italic)
+    }
+
+    /// Toggle [`Attribute::Underline`]
+    pub fn underline(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Underline)
+    }
+
+    /// Toggle [`Attribute::Strikethrough`]
+    pub fn strikethrough(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Strikethrough)
+    }
+
+    /// Toggle [`Attribute::Monospace`]
+    pub fn monospace(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Monospace)
+    }
+
+    /// Toggle [`Attribute::Reverse`]
+    pub fn reverse(self) -> LineBuilder<'a> {
+        self.toggle(Attribute::Reverse)
+    }
+
+    /// Set the foreground color, either a [`Color100`] or an [`RgbColor`]
+    pub fn fg(mut self, color: impl Into<Color>) -> LineBuilder<'a> {
+        self.style.foreground = color.into();
+        self
+    }
+
+    /// Set the background color, either a [`Color100`] or an [`RgbColor`]
+    pub fn bg(mut self, color: impl Into<Color>) -> LineBuilder<'a> {
+        self.style.background = color.into();
+        self
+    }
+
+    /// Reset the foreground and background colors to the default, leaving
+    /// attributes untouched
+    pub fn reset_colors(mut self) -> LineBuilder<'a> {
+        self.style.foreground = Color::default();
+        self.style.background = Color::default();
+        self
+    }
+
+    /// Reset the style entirely: no attributes, default foreground and
+    /// background
+    pub fn reset(mut self) -> LineBuilder<'a> {
+        self.style = Style::default();
+        self
+    }
+
+    /// Consume the builder, producing the resulting [`StyledLine`]
+    pub fn build(self) -> StyledLine<'a> {
+        StyledLine(self.spans)
     }
 }
 
 #[cfg(feature = "anstyle")]
 #[cfg_attr(docsrs, doc(cfg(feature = "anstyle")))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct RenderStyledLine<'a, 'b>(&'a StyledLine<'b>);
+pub struct RenderStyledLine<'a, 'b>(&'a StyledLine<'b>, ColorDepth);
 
 #[cfg(feature = "anstyle")]
 impl fmt::Display for RenderStyledLine<'_, '_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for s in &self.0.0 {
-            write!(f, "{}", s.render_ansi())?;
+            write!(f, "{}", s.render_ansi_with_depth(self.1))?;
         }
         Ok(())
     }
@@ -396,6 +820,198 @@ impl ExactSizeIterator for StyledLineIter<'_, '_> {}
 
 impl std::iter::FusedIterator for StyledLineIter<'_, '_> {}
 
+/// A lazy, non-allocating counterpart to [`StyledLine::parse`]: borrows a
+/// `&str` and yields `(text, Style)` pairs one style run at a time, parsing
+/// just enough of the intervening control bytes on each [`Iterator::next`]
+/// call to update the running [`Style`], without ever collecting a
+/// `Vec<StyledSpan>`.
+#[derive(Clone, Debug)]
+pub struct SpanIter<'a> {
+    rest: &'a str,
+    style: Style,
+}
+
+impl<'a> SpanIter<'a> {
+    pub fn new(s: &'a str) -> SpanIter<'a> {
+        SpanIter {
+            rest: s,
+            style: Style::default(),
+        }
+    }
+
+    /// Applies the control byte `ch` (at byte index `i` in `self.rest`) to
+    /// the running style, consuming any digits/hex digits it takes as
+    /// arguments from `iter`.  Returns the byte index at which the next
+    /// plain-text run actually starts, for the rare case (a lone `,` after
+    /// a foreground color, or a truncated hex color) where a byte consumed
+    /// while scanning the control code's arguments turns out to belong to
+    /// plain text after all.
+    fn apply_control(
+        &mut self,
+        ch: char,
+        i: usize,
+        iter: &mut std::iter::Peekable<std::str::CharIndices<'a>>,
+    ) -> Option<usize> {
+        match ch {
+            BOLD_CHAR => self.style.attributes ^= Attribute::Bold,
+            ITALIC_CHAR => self.style.attributes ^= Attribute::Italic,
+            UNDERLINE_CHAR => self.style.attributes ^= Attribute::Underline,
+            STRIKETHROUGH_CHAR => self.style.attributes ^= Attribute::Strikethrough,
+            MONOSPACE_CHAR => self.style.attributes ^= Attribute::Monospace,
+            REVERSE_CHAR => self.style.attributes ^= Attribute::Reverse,
+            RESET_CHAR => self.style = Style::default(),
+            COLOR_CHAR => {
+                if let Some(fg) = scan_color100(iter) {
+                    self.style.foreground = Color::from(fg);
+                    let comma_index = iter.next_if(|&(_, ch)| ch == ',').map(|(i, _)| i);
+                    if let Some(bg) = scan_color100(iter) {
+                        self.style.background = Color::from(bg);
+                    } else if let Some(ci) = comma_index {
+                        return Some(ci);
+                    }
+                } else {
+                    self.style.foreground = Color::default();
+                    self.style.background = Color::default();
+                }
+            }
+            HEX_COLOR_CHAR => {
+                if let Some(fg) = scan_rgbcolor(iter) {
+                    self.style.foreground = Color::from(fg);
+                    let comma_index = iter.next_if(|&(_, ch)| ch == ',').map(|(i, _)| i);
+                    if let Some(bg) = scan_rgbcolor(iter) {
+                        self.style.background = Color::from(bg);
+                    } else if let Some(ci) = comma_index {
+                        return Some(ci);
+                    }
+                } else {
+                    self.style.foreground = Color::default();
+                    self.style.background = Color::default();
+                    return Some(i + 1);
+                }
+            }
+            _ => unreachable!("apply_control() should only be called on control bytes"),
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for SpanIter<'a> {
+    type Item = (&'a str, Style);
+
+    fn next(&mut self) -> Option<(&'a str, Style)> {
+        let mut iter = self.rest.char_indices().peekable();
+        let mut text_start = None;
+        while let Some(&(i, ch)) = iter.peek() {
+            match ch {
+                BOLD_CHAR | ITALIC_CHAR | UNDERLINE_CHAR | STRIKETHROUGH_CHAR | MONOSPACE_CHAR
+                | COLOR_CHAR | HEX_COLOR_CHAR | REVERSE_CHAR | RESET_CHAR => {
+                    if let Some(start) = text_start {
+                        let text = &self.rest[start..i];
+                        self.rest = &self.rest[i..];
+                        return Some((text, self.style));
+                    }
+                    iter.next();
+                    if let Some(start) = self.apply_control(ch, i, &mut iter) {
+                        text_start = Some(start);
+                    }
+                }
+                _ => {
+                    if text_start.is_none() {
+                        text_start = Some(i);
+                    }
+                    iter.next();
+                }
+            }
+        }
+        let start = text_start?;
+        let text = &self.rest[start..];
+        self.rest = "";
+        Some((text, self.style))
+    }
+}
+
+impl std::iter::FusedIterator for SpanIter<'_> {}
+
+/// Returns `s` with all IRC formatting control bytes removed, borrowing the
+/// original string unchanged when it contains none.  Useful for logging,
+/// search indexing, or nick-highlight matching, where the formatting itself
+/// is irrelevant.
+pub fn strip_formatting(s: &str) -> Cow<'_, str> {
+    if !s.contains([
+        BOLD_CHAR,
+        ITALIC_CHAR,
+        UNDERLINE_CHAR,
+        STRIKETHROUGH_CHAR,
+        MONOSPACE_CHAR,
+        COLOR_CHAR,
+        HEX_COLOR_CHAR,
+        REVERSE_CHAR,
+        RESET_CHAR,
+    ]) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    for (text, _) in SpanIter::new(s) {
+        out.push_str(text);
+    }
+    Cow::Owned(out)
+}
+
+/// Returns the rendered terminal width of `s`, ignoring all formatting
+/// control bytes and measuring each grapheme cluster (so combining marks
+/// and joined sequences aren't double-counted) with its Unicode East Asian
+/// width.  Useful for padding nicks or aligning columns in a status window,
+/// where `str::len()` over-counts both the invisible control bytes and any
+/// double-width CJK characters.
+#[cfg(feature = "unicode-width")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unicode-width")))]
+pub fn display_width(s: &str) -> usize {
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+    SpanIter::new(s)
+        .flat_map(|(text, _)| text.graphemes(true))
+        .map(UnicodeWidthStr::width)
+        .sum()
+}
+
+/// Splits `s` into its formatted runs, resolving each run's foreground and
+/// background to a [`Color100`] (defaulting to [`Color100::DEFAULT`] for
+/// runs with no color set, as well as for runs using a 24-bit [`Color::Rgb`]
+/// color, which has no `Color100` equivalent).
+///
+/// Useful for a bot or bridge that needs to detect, rewrite, or downgrade
+/// colored text, e.g. by stripping background colors or remapping colors
+/// 16-98 down to the 0-15 range supported by legacy clients via
+/// [`Color100::remap_to_basic16`].
+pub fn extract_colors(s: &str) -> Vec<(Color100, Color100, String)> {
+    SpanIter::new(s)
+        .map(|(text, style)| {
+            (
+                to_color100(style.foreground),
+                to_color100(style.background),
+                text.to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn to_color100(color: Color) -> Color100 {
+    match color {
+        Color::Color100(c) => c,
+        Color::Rgb(_) => Color100::DEFAULT,
+    }
+}
+
+/// Like [`to_color100`], but quantizes an RGB color to its nearest
+/// [`Color100`] via [`RgbColor::to_nearest_color100`] instead of discarding
+/// it, for [`StyledLine::format_basic`].
+fn quantize_color(color: Color) -> Color {
+    match color {
+        Color::Color100(_) => color,
+        Color::Rgb(rgb) => Color::from(rgb.to_nearest_color100()),
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct StyledLineBuilder {
     closed: Vec<(Style, Range<usize>)>,
@@ -443,6 +1059,15 @@ impl StyledLineBuilder {
         self.set_background(Color::default(), index);
     }
 
+    fn set_attribute(&mut self, attr: Attribute, enabled: bool, index: usize) {
+        let style = self.get_open_style(index);
+        if enabled {
+            style.attributes |= attr;
+        } else {
+            style.attributes -= attr;
+        }
+    }
+
     fn push_char(&mut self, index: usize) {
         if let OpenStyledSpan::Styling(style) = self.open {
             self.open = OpenStyledSpan::Spanning {
@@ -580,6 +1205,300 @@ where
         .and_then(|d| u8::try_from(d).ok())
 }
 
+/// Scan an SGR escape sequence's parameters (the part between `ESC [` and
+/// the terminating `m`), starting at byte index `start`.  Returns the byte
+/// index of the `m` on success, or `None` if a byte other than an ASCII
+/// digit or `;` is encountered first (an unterminated/malformed escape).
+#[cfg(feature = "anstyle")]
+fn find_sgr_terminator(bytes: &[u8], mut start: usize) -> Option<usize> {
+    while start < bytes.len() {
+        match bytes[start] {
+            b'm' => return Some(start),
+            b'0'..=b'9' | b';' => start += 1,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Apply the effects of an SGR escape sequence's `;`-separated parameters
+/// (as matched by [`find_sgr_terminator`]) to `builder`'s currently open
+/// style, as of text position `index`.
+#[cfg(feature = "anstyle")]
+fn apply_sgr_params(builder: &mut StyledLineBuilder, params: &str, index: usize) {
+    let codes: Vec<&str> = if params.is_empty() {
+        vec!["0"]
+    } else {
+        params.split(';').collect()
+    };
+    let mut iter = codes.into_iter();
+    while let Some(code) = iter.next() {
+        let Ok(n) = code.parse::<u16>() else {
+            continue;
+        };
+        match n {
+            0 => builder.reset(index),
+            1 => builder.set_attribute(Attribute::Bold, true, index),
+            3 => builder.set_attribute(Attribute::Italic, true, index),
+            4 => builder.set_attribute(Attribute::Underline, true, index),
+            7 => builder.set_attribute(Attribute::Reverse, true, index),
+            9 => builder.set_attribute(Attribute::Strikethrough, true, index),
+            22 => builder.set_attribute(Attribute::Bold, false, index),
+            23 => builder.set_attribute(Attribute::Italic, false, index),
+            24 => builder.set_attribute(Attribute::Underline, false, index),
+            27 => builder.set_attribute(Attribute::Reverse, false, index),
+            29 => builder.set_attribute(Attribute::Strikethrough, false, index),
+            30..=37 => {
+                let color = Color::from(color100_for_ansi256(n as u8 - 30));
+                builder.set_foreground(color, index);
+            }
+            40..=47 => {
+                let color = Color::from(color100_for_ansi256(n as u8 - 40));
+                builder.set_background(color, index);
+            }
+            90..=97 => {
+                let color = Color::from(color100_for_ansi256(n as u8 - 90 + 8));
+                builder.set_foreground(color, index);
+            }
+            100..=107 => {
+                let color = Color::from(color100_for_ansi256(n as u8 - 100 + 8));
+                builder.set_background(color, index);
+            }
+            38 | 48 => {
+                let Some(color) = scan_extended_sgr_color(&mut iter) else {
+                    continue;
+                };
+                if n == 38 {
+                    builder.set_foreground(color, index);
+                } else {
+                    builder.set_background(color, index);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Consume the remaining parameters of an extended (38/48) SGR color code,
+/// i.e. either `5;n` (8-bit palette) or `2;r;g;b` (24-bit RGB).
+#[cfg(feature = "anstyle")]
+fn scan_extended_sgr_color<'a, I>(iter: &mut I) -> Option<Color>
+where
+    I: Iterator<Item = &'a str>,
+{
+    match iter.next()? {
+        "5" => {
+            let n = iter.next()?.parse::<u8>().ok()?;
+            Some(Color::from(color100_for_ansi256(n)))
+        }
+        "2" => {
+            let r = iter.next()?.parse::<u8>().ok()?;
+            let g = iter.next()?.parse::<u8>().ok()?;
+            let b = iter.next()?.parse::<u8>().ok()?;
+            Some(Color::from(RgbColor(r, g, b)))
+        }
+        _ => None,
+    }
+}
+
+const SECTION_CHAR: char = '§';
+
+/// RGB values for the 16 classic Minecraft/legacy-chat section-sign colors,
+/// indexed by their lowercase code character, per
+/// <https://minecraft.wiki/w/Formatting_codes#Color_codes>.
+const SECTION_COLORS: [(char, u8, u8, u8); 16] = [
+    ('0', 0x00, 0x00, 0x00),
+    ('1', 0x00, 0x00, 0xAA),
+    ('2', 0x00, 0xAA, 0x00),
+    ('3', 0x00, 0xAA, 0xAA),
+    ('4', 0xAA, 0x00, 0x00),
+    ('5', 0xAA, 0x00, 0xAA),
+    ('6', 0xFF, 0xAA, 0x00),
+    ('7', 0xAA, 0xAA, 0xAA),
+    ('8', 0x55, 0x55, 0x55),
+    ('9', 0x55, 0x55, 0xFF),
+    ('a', 0x55, 0xFF, 0x55),
+    ('b', 0x55, 0xFF, 0xFF),
+    ('c', 0xFF, 0x55, 0x55),
+    ('d', 0xFF, 0x55, 0xFF),
+    ('e', 0xFF, 0xFF, 0x55),
+    ('f', 0xFF, 0xFF, 0xFF),
+];
+
+/// Look up a section-sign color code's RGB value and convert it to the
+/// nearest [`Color100`], for [`StyledLine::parse_section`].
+fn section_color(code: char) -> Option<Color> {
+    SECTION_COLORS
+        .iter()
+        .find(|&&(ch, ..)| ch == code)
+        .map(|&(_, r, g, b)| Color::from(RgbColor(r, g, b).to_nearest_color100()))
+}
+
+/// Map a section-sign formatting code to its [`Attribute`], for
+/// [`StyledLine::parse_section`].
+fn section_attribute(code: char) -> Option<Attribute> {
+    match code {
+        'l' => Some(Attribute::Bold),
+        'o' => Some(Attribute::Italic),
+        'n' => Some(Attribute::Underline),
+        'm' => Some(Attribute::Strikethrough),
+        _ => None,
+    }
+}
+
+/// The inverse of [`section_attribute`], for [`StyledLine::format_section`].
+fn section_code(attr: Attribute) -> char {
+    match attr {
+        Attribute::Bold => 'l',
+        Attribute::Italic => 'o',
+        Attribute::Underline => 'n',
+        Attribute::Strikethrough => 'm',
+        Attribute::Monospace | Attribute::Reverse => {
+            unreachable!("section_code() should only be called on minecraft_attrs() output")
+        }
+    }
+}
+
+/// Only [`Attribute::Bold`], [`Attribute::Italic`], [`Attribute::Underline`],
+/// and [`Attribute::Strikethrough`] have section-sign equivalents.
+fn minecraft_attrs(attrs: AttributeSet) -> AttributeSet {
+    attrs - Attribute::Monospace - Attribute::Reverse
+}
+
+/// Find the nearest of the 16 section-sign colors to `color`, or `None` if
+/// `color` is [`Color::default`] (no color set), for
+/// [`StyledLine::format_section`].
+fn section_char_for(color: Color) -> Option<char> {
+    if color == Color::default() {
+        return None;
+    }
+    let rgb = match color {
+        Color::Rgb(rgb) => rgb,
+        Color::Color100(c) => RgbColor::from(c),
+    };
+    SECTION_COLORS
+        .iter()
+        .min_by_key(|&&(_, r, g, b)| {
+            let dr = i32::from(rgb.red()) - i32::from(r);
+            let dg = i32::from(rgb.green()) - i32::from(g);
+            let db = i32::from(rgb.blue()) - i32::from(b);
+            dr * dr + dg * dg + db * db
+        })
+        .map(|&(ch, ..)| ch)
+}
+
+/// Map an 8-bit ANSI-256 palette index to the nearest [`Color100`].  Exact
+/// matches (as per [`Color100::try_from_ansi_index`]) are preferred;
+/// otherwise, the [`Color100`] whose own palette index is numerically
+/// closest is used, since `Color100` cannot represent every ANSI-256 index.
+#[cfg(feature = "anstyle")]
+fn color100_for_ansi256(index: u8) -> Color100 {
+    if let Some(c) = Color100::try_from_ansi_index(index) {
+        return c;
+    }
+    (0..=98u8)
+        .filter_map(|v| Color100::try_from(v).ok())
+        .min_by_key(|c| {
+            let other = c
+                .to_ansi_index()
+                .expect("Color100 values 0-98 always have an ANSI index");
+            (i32::from(index) - i32::from(other)).abs()
+        })
+        .unwrap_or_default()
+}
+
+/// RGB values of the eight standard ANSI colors and their eight bright
+/// variants, indexed in `anstyle::AnsiColor`'s declaration order (the
+/// conventional xterm defaults), for quantizing down to
+/// [`ColorDepth::Basic16`].
+#[cfg(feature = "anstyle")]
+const BASIC16: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00), // Black
+    (0xCD, 0x00, 0x00), // Red
+    (0x00, 0xCD, 0x00), // Green
+    (0xCD, 0xCD, 0x00), // Yellow
+    (0x00, 0x00, 0xEE), // Blue
+    (0xCD, 0x00, 0xCD), // Magenta
+    (0x00, 0xCD, 0xCD), // Cyan
+    (0xE5, 0xE5, 0xE5), // White
+    (0x7F, 0x7F, 0x7F), // BrightBlack
+    (0xFF, 0x00, 0x00), // BrightRed
+    (0x00, 0xFF, 0x00), // BrightGreen
+    (0xFF, 0xFF, 0x00), // BrightYellow
+    (0x5C, 0x5C, 0xFF), // BrightBlue
+    (0xFF, 0x00, 0xFF), // BrightMagenta
+    (0x00, 0xFF, 0xFF), // BrightCyan
+    (0xFF, 0xFF, 0xFF), // BrightWhite
+];
+
+#[cfg(feature = "anstyle")]
+const ANSI_COLORS: [anstyle::AnsiColor; 16] = [
+    anstyle::AnsiColor::Black,
+    anstyle::AnsiColor::Red,
+    anstyle::AnsiColor::Green,
+    anstyle::AnsiColor::Yellow,
+    anstyle::AnsiColor::Blue,
+    anstyle::AnsiColor::Magenta,
+    anstyle::AnsiColor::Cyan,
+    anstyle::AnsiColor::White,
+    anstyle::AnsiColor::BrightBlack,
+    anstyle::AnsiColor::BrightRed,
+    anstyle::AnsiColor::BrightGreen,
+    anstyle::AnsiColor::BrightYellow,
+    anstyle::AnsiColor::BrightBlue,
+    anstyle::AnsiColor::BrightMagenta,
+    anstyle::AnsiColor::BrightCyan,
+    anstyle::AnsiColor::BrightWhite,
+];
+
+/// Squared Euclidean distance between two RGB colors, for nearest-palette-
+/// entry lookups.
+#[cfg(feature = "anstyle")]
+fn rgb_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let dr = i32::from(a.0) - i32::from(b.0);
+    let dg = i32::from(a.1) - i32::from(b.1);
+    let db = i32::from(a.2) - i32::from(b.2);
+    dr * dr + dg * dg + db * db
+}
+
+/// Quantize `rgb` to the nearest of the 16 [`ColorDepth::Basic16`] colors by
+/// squared RGB distance.
+#[cfg(feature = "anstyle")]
+fn quantize_basic16(rgb: RgbColor) -> anstyle::AnsiColor {
+    let target = (rgb.red(), rgb.green(), rgb.blue());
+    let (index, _) = BASIC16
+        .into_iter()
+        .enumerate()
+        .min_by_key(|&(_, entry)| rgb_distance(target, entry))
+        .expect("BASIC16 is non-empty");
+    ANSI_COLORS[index]
+}
+
+/// Quantize `rgb` to the nearest entry of the xterm 256-color palette's
+/// 6×6×6 color cube (indices 16-231) or 24-step grayscale ramp (indices
+/// 232-255), for [`ColorDepth::Indexed256`].
+#[cfg(feature = "anstyle")]
+fn quantize_indexed256(rgb: RgbColor) -> u8 {
+    const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let target = (rgb.red(), rgb.green(), rgb.blue());
+    let cube_entries = (0..216u8).map(|i| {
+        let r = CUBE_STEPS[usize::from(i / 36)];
+        let g = CUBE_STEPS[usize::from((i / 6) % 6)];
+        let b = CUBE_STEPS[usize::from(i % 6)];
+        (16 + i, (r, g, b))
+    });
+    let gray_entries = (0..24u8).map(|i| {
+        let v = 8 + 10 * i;
+        (232 + i, (v, v, v))
+    });
+    cube_entries
+        .chain(gray_entries)
+        .min_by_key(|&(_, entry)| rgb_distance(target, entry))
+        .expect("cube and grayscale ramp are non-empty")
+        .0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -733,32 +1652,83 @@ mod tests {
                 ])
             );
         }
-    }
-
-    #[test]
-    fn color_comma_end() {
-        let s = "\x03,";
-        let sline = StyledLine::parse(s);
-        assert_eq!(sline, StyledLine::from(StyledSpan::from(",")));
-    }
-
-    #[test]
-    fn color_comma_not_digit() {
-        let s = "\x034,a";
-        let sline = StyledLine::parse(s);
-        assert_eq!(
-            sline,
-            StyledLine(vec![StyledSpan {
-                style: Style {
-                    foreground: Color100::RED.into(),
-                    ..Style::default()
-                },
-                content: ",a".into(),
-            }])
-        );
-    }
 
-    #[test]
+        #[test]
+        fn example5() {
+            // Strikethrough, monospace, and reverse get far less exercise in
+            // the wild than bold/italic/underline/color, but they toggle the
+            // same way and should parse identically.
+            let s = "plain \x1Estruck\x1E \x11mono\x11 \x16flipped\x16 plain";
+            let sline = StyledLine::parse(s);
+            assert_eq!(
+                sline,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style::default(),
+                        content: "plain ".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Strikethrough.into(),
+                            ..Style::default()
+                        },
+                        content: "struck".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: " ".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Monospace.into(),
+                            ..Style::default()
+                        },
+                        content: "mono".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: " ".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Reverse.into(),
+                            ..Style::default()
+                        },
+                        content: "flipped".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: " plain".into(),
+                    },
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn color_comma_end() {
+        let s = "\x03,";
+        let sline = StyledLine::parse(s);
+        assert_eq!(sline, StyledLine::from(StyledSpan::from(",")));
+    }
+
+    #[test]
+    fn color_comma_not_digit() {
+        let s = "\x034,a";
+        let sline = StyledLine::parse(s);
+        assert_eq!(
+            sline,
+            StyledLine(vec![StyledSpan {
+                style: Style {
+                    foreground: Color100::RED.into(),
+                    ..Style::default()
+                },
+                content: ",a".into(),
+            }])
+        );
+    }
+
+    #[test]
     fn short_hex() {
         let s = "\x04ff00glarch";
         let sline = StyledLine::parse(s);
@@ -844,4 +1814,425 @@ mod tests {
             assert_eq!(StyledLine::parse(&sline.format()), sline);
         }
     }
+
+    mod line_builder {
+        use super::*;
+
+        #[test]
+        fn builder_example() {
+            let line = LineBuilder::new()
+                .bold()
+                .fg(Color100::RED)
+                .text("so ")
+                .reset()
+                .text("great")
+                .build();
+            assert_eq!(
+                line,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style {
+                            foreground: Color100::RED.into(),
+                            attributes: Attribute::Bold.into(),
+                            ..Style::default()
+                        },
+                        content: "so ".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: "great".into(),
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn coalesces_same_style() {
+            let line = LineBuilder::new().text("a").text("b").build();
+            assert_eq!(
+                line,
+                StyledLine(vec![StyledSpan {
+                    style: Style::default(),
+                    content: "ab".into(),
+                }])
+            );
+        }
+
+        #[test]
+        fn rgb_background() {
+            let line = LineBuilder::new()
+                .bg(RgbColor(0x36, 0x36, 0x36))
+                .text("dark")
+                .build();
+            assert_eq!(
+                line,
+                StyledLine(vec![StyledSpan {
+                    style: Style {
+                        background: RgbColor(0x36, 0x36, 0x36).into(),
+                        ..Style::default()
+                    },
+                    content: "dark".into(),
+                }])
+            );
+        }
+
+        #[test]
+        fn empty_text_is_skipped() {
+            let line = LineBuilder::new()
+                .text("a")
+                .bold()
+                .text("")
+                .text("b")
+                .build();
+            assert_eq!(
+                line,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style::default(),
+                        content: "a".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Bold.into(),
+                            ..Style::default()
+                        },
+                        content: "b".into(),
+                    },
+                ])
+            );
+        }
+    }
+
+    mod format_basic {
+        use super::*;
+
+        #[test]
+        fn quantizes_rgb_foreground() {
+            let sline = StyledLine::from(StyledSpan {
+                style: Style {
+                    foreground: RgbColor(0xFF, 0x00, 0x00).into(),
+                    ..Style::default()
+                },
+                content: "fire".into(),
+            });
+            let s = sline.format_basic();
+            assert!(!s.contains(HEX_COLOR_CHAR));
+            assert_eq!(
+                StyledLine::parse(&s),
+                StyledLine::from(StyledSpan {
+                    style: Style {
+                        foreground: Color100::RED.into(),
+                        ..Style::default()
+                    },
+                    content: "fire".into(),
+                })
+            );
+        }
+
+        #[test]
+        fn leaves_color100_untouched() {
+            let sline = StyledLine::from(StyledSpan {
+                style: Style {
+                    foreground: Color100::GREEN.into(),
+                    ..Style::default()
+                },
+                content: "go".into(),
+            });
+            assert_eq!(sline.format_basic(), sline.format());
+        }
+    }
+
+    mod section {
+        use super::*;
+
+        #[test]
+        fn parse_color_and_format_codes() {
+            let sline = StyledLine::parse_section("§4§lDanger§r: §9water");
+            assert_eq!(
+                sline,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style {
+                            foreground: Color100::try_from(40).unwrap().into(),
+                            attributes: Attribute::Bold.into(),
+                            ..Style::default()
+                        },
+                        content: "Danger".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: ": ".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            foreground: Color100::try_from(72).unwrap().into(),
+                            ..Style::default()
+                        },
+                        content: "water".into(),
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn unrecognized_code_is_literal() {
+            let sline = StyledLine::parse_section("§zfoo");
+            assert_eq!(sline, StyledLine::from(StyledSpan::from("§zfoo")));
+        }
+
+        #[test]
+        fn format_quantizes_rgb_to_nearest_section_color() {
+            let sline = StyledLine::from(StyledSpan {
+                style: Style {
+                    foreground: RgbColor(0xFF, 0x00, 0x00).into(),
+                    ..Style::default()
+                },
+                content: "fire".into(),
+            });
+            assert_eq!(sline.format_section(), "§4fire");
+        }
+
+        #[test]
+        fn format_drops_background_and_reset_on_turn_off() {
+            let sline = StyledLine(vec![
+                StyledSpan {
+                    style: Style {
+                        foreground: Color100::RED.into(),
+                        background: Color100::WHITE.into(),
+                        attributes: Attribute::Bold | Attribute::Italic,
+                    },
+                    content: "both".into(),
+                },
+                StyledSpan {
+                    style: Style {
+                        foreground: Color100::RED.into(),
+                        attributes: Attribute::Bold.into(),
+                        ..Style::default()
+                    },
+                    content: "bold only".into(),
+                },
+            ]);
+            assert_eq!(sline.format_section(), "§4§l§oboth§r§4§lbold only");
+        }
+
+        #[test]
+        fn roundtrip_through_section() {
+            let sline = StyledLine::parse_section("§4§lDanger§r: §9water");
+            assert_eq!(StyledLine::parse_section(&sline.format_section()), sline);
+        }
+    }
+
+    #[cfg(feature = "anstyle")]
+    mod to_ansi {
+        use super::*;
+
+        #[test]
+        fn matches_render_ansi() {
+            let sline = StyledLine::from(StyledSpan {
+                style: Style {
+                    foreground: Color100::RED.into(),
+                    attributes: Attribute::Bold.into(),
+                    ..Style::default()
+                },
+                content: "hi".into(),
+            });
+            assert_eq!(sline.to_ansi(), sline.render_ansi().to_string());
+        }
+    }
+
+    #[cfg(feature = "anstyle")]
+    mod color_depth {
+        use super::*;
+
+        #[test]
+        fn true_color_is_unchanged() {
+            let color = Color::from(RgbColor(0x12, 0x34, 0x56));
+            assert_eq!(
+                color.to_anstyle_with_depth(ColorDepth::TrueColor),
+                color.to_anstyle()
+            );
+        }
+
+        #[test]
+        fn basic16_quantizes_pure_red_to_bright_red() {
+            let color = Color::from(RgbColor(0xFF, 0x00, 0x00));
+            assert_eq!(
+                color.to_anstyle_with_depth(ColorDepth::Basic16),
+                Some(anstyle::Color::Ansi(anstyle::AnsiColor::BrightRed))
+            );
+        }
+
+        #[test]
+        fn indexed256_quantizes_into_color_cube() {
+            let color = Color::from(RgbColor(0xFF, 0x00, 0x00));
+            assert_eq!(
+                color.to_anstyle_with_depth(ColorDepth::Indexed256),
+                Some(anstyle::Color::Ansi256(anstyle::Ansi256Color(196)))
+            );
+        }
+
+        #[test]
+        fn indexed256_quantizes_gray_into_grayscale_ramp() {
+            let color = Color::from(RgbColor(0x80, 0x80, 0x80));
+            assert_eq!(
+                color.to_anstyle_with_depth(ColorDepth::Indexed256),
+                Some(anstyle::Color::Ansi256(anstyle::Ansi256Color(244)))
+            );
+        }
+
+        #[test]
+        fn render_ansi_with_depth_downgrades_truecolor_span() {
+            let sline = StyledLine::from(StyledSpan {
+                style: Style {
+                    foreground: RgbColor(0xFF, 0x00, 0x00).into(),
+                    ..Style::default()
+                },
+                content: "fire".into(),
+            });
+            let rendered = sline.to_ansi_with_depth(ColorDepth::Basic16);
+            assert!(rendered.contains("\x1b[91m"));
+        }
+    }
+
+    #[cfg(feature = "anstyle")]
+    mod from_ansi {
+        use super::*;
+
+        #[test]
+        fn bold_and_reset() {
+            let sline = StyledLine::from_ansi("\x1b[1mbold\x1b[0m plain");
+            assert_eq!(
+                sline,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Bold.into(),
+                            ..Style::default()
+                        },
+                        content: "bold".into(),
+                    },
+                    StyledSpan {
+                        style: Style::default(),
+                        content: " plain".into(),
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn clear_specific_attribute() {
+            let sline = StyledLine::from_ansi("\x1b[1;4mboth\x1b[24monly bold");
+            assert_eq!(
+                sline,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Bold | Attribute::Underline,
+                            ..Style::default()
+                        },
+                        content: "both".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            attributes: Attribute::Bold.into(),
+                            ..Style::default()
+                        },
+                        content: "only bold".into(),
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn basic_16_colors() {
+            // SGR 31/91 map to ANSI-256 palette indices 1/9, which
+            // Color100::try_from_ansi_index resolves to BROWN/RED
+            // respectively.
+            let sline = StyledLine::from_ansi("\x1b[31mdim\x1b[91mbright");
+            assert_eq!(
+                sline,
+                StyledLine(vec![
+                    StyledSpan {
+                        style: Style {
+                            foreground: Color100::BROWN.into(),
+                            ..Style::default()
+                        },
+                        content: "dim".into(),
+                    },
+                    StyledSpan {
+                        style: Style {
+                            foreground: Color100::RED.into(),
+                            ..Style::default()
+                        },
+                        content: "bright".into(),
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn truecolor_background() {
+            let sline = StyledLine::from_ansi("\x1b[48;2;54;54;54mdark");
+            assert_eq!(
+                sline,
+                StyledLine(vec![StyledSpan {
+                    style: Style {
+                        background: RgbColor(0x36, 0x36, 0x36).into(),
+                        ..Style::default()
+                    },
+                    content: "dark".into(),
+                }])
+            );
+        }
+
+        #[test]
+        fn palette_color_with_no_exact_color100() {
+            // ANSI 256-color index 196 ("red") has no exact Color100
+            // equivalent, so the nearest Color100 is used instead.
+            let sline = StyledLine::from_ansi("\x1b[38;5;196mred");
+            assert_eq!(sline.0.len(), 1);
+            assert_ne!(sline.0[0].style.foreground, Color::default());
+        }
+
+        #[test]
+        fn extended_fg_and_bg_in_one_sequence() {
+            // A single SGR sequence mixing an 8-bit-palette foreground,
+            // a 24-bit-RGB background, and a trailing attribute code must
+            // split each extended color's own parameters off the shared
+            // iterator without swallowing the unrelated code after it.
+            let sline = StyledLine::from_ansi("\x1b[38;5;196;48;2;0;0;0;1mboth");
+            assert_eq!(sline.0.len(), 1);
+            let style = &sline.0[0].style;
+            assert_ne!(style.foreground, Color::default());
+            assert_eq!(style.background, RgbColor(0, 0, 0).into());
+            assert!(style.attributes.contains(Attribute::Bold));
+        }
+
+        #[test]
+        fn non_csi_text_passes_through() {
+            let sline = StyledLine::from_ansi("plain text, no escapes");
+            assert_eq!(
+                sline,
+                StyledLine(vec![StyledSpan {
+                    style: Style::default(),
+                    content: "plain text, no escapes".into(),
+                }])
+            );
+        }
+
+        #[test]
+        fn unterminated_escape_is_literal() {
+            let sline = StyledLine::from_ansi("\x1b[1mbold\x1b[unterminated");
+            assert_eq!(
+                sline,
+                StyledLine(vec![StyledSpan {
+                    style: Style {
+                        attributes: Attribute::Bold.into(),
+                        ..Style::default()
+                    },
+                    content: "bold\x1b[unterminated".into(),
+                }])
+            );
+        }
+    }
 }
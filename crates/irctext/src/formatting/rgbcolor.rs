@@ -1,3 +1,112 @@
+use super::Color100;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// RGB values for IRC colors 0 through 98, per
+/// <https://modern.ircdocs.horse/formatting#colors-16-98>, used by
+/// [`RgbColor::to_nearest_color100`].
+const PALETTE: [(u8, u8, u8); 99] = [
+    (0xFF, 0xFF, 0xFF), // 0 White
+    (0x00, 0x00, 0x00), // 1 Black
+    (0x00, 0x00, 0x7F), // 2 Blue
+    (0x00, 0x93, 0x00), // 3 Green
+    (0xFF, 0x00, 0x00), // 4 Red
+    (0x7F, 0x00, 0x00), // 5 Brown
+    (0x9C, 0x00, 0x9C), // 6 Magenta
+    (0xFC, 0x7F, 0x00), // 7 Orange
+    (0xFF, 0xFF, 0x00), // 8 Yellow
+    (0x00, 0xFC, 0x00), // 9 Light green
+    (0x00, 0x93, 0x93), // 10 Cyan
+    (0x00, 0xFF, 0xFF), // 11 Light cyan
+    (0x00, 0x00, 0xFC), // 12 Light blue
+    (0xFF, 0x00, 0xFF), // 13 Pink
+    (0x7F, 0x7F, 0x7F), // 14 Grey
+    (0xD2, 0xD2, 0xD2), // 15 Light grey
+    (0x47, 0x00, 0x00),
+    (0x47, 0x21, 0x00),
+    (0x47, 0x47, 0x00),
+    (0x32, 0x47, 0x00),
+    (0x00, 0x47, 0x00),
+    (0x00, 0x47, 0x2C),
+    (0x00, 0x47, 0x47),
+    (0x00, 0x27, 0x47),
+    (0x00, 0x00, 0x47),
+    (0x2C, 0x00, 0x47),
+    (0x47, 0x00, 0x47),
+    (0x47, 0x00, 0x2C),
+    (0x74, 0x00, 0x00),
+    (0x74, 0x3A, 0x00),
+    (0x74, 0x74, 0x00),
+    (0x51, 0x74, 0x00),
+    (0x00, 0x74, 0x00),
+    (0x00, 0x74, 0x49),
+    (0x00, 0x74, 0x74),
+    (0x00, 0x40, 0x74),
+    (0x00, 0x00, 0x74),
+    (0x4B, 0x00, 0x74),
+    (0x74, 0x00, 0x74),
+    (0x74, 0x00, 0x45),
+    (0xB5, 0x00, 0x00),
+    (0xB5, 0x63, 0x00),
+    (0xB5, 0xB5, 0x00),
+    (0x7D, 0xB5, 0x00),
+    (0x00, 0xB5, 0x00),
+    (0x00, 0xB5, 0x71),
+    (0x00, 0xB5, 0xB5),
+    (0x00, 0x63, 0xB5),
+    (0x00, 0x00, 0xB5),
+    (0x75, 0x00, 0xB5),
+    (0xB5, 0x00, 0xB5),
+    (0xB5, 0x00, 0x6B),
+    (0xFF, 0x00, 0x00),
+    (0xFF, 0x8C, 0x00),
+    (0xFF, 0xFF, 0x00),
+    (0xB2, 0xFF, 0x00),
+    (0x00, 0xFF, 0x00),
+    (0x00, 0xFF, 0xA0),
+    (0x00, 0xFF, 0xFF),
+    (0x00, 0x8C, 0xFF),
+    (0x00, 0x00, 0xFF),
+    (0xA5, 0x00, 0xFF),
+    (0xFF, 0x00, 0xFF),
+    (0xFF, 0x00, 0x98),
+    (0xFF, 0x59, 0x59),
+    (0xFF, 0xB4, 0x59),
+    (0xFF, 0xFF, 0x71),
+    (0xCF, 0xFF, 0x60),
+    (0x6F, 0xFF, 0x6F),
+    (0x65, 0xFF, 0xC9),
+    (0x6D, 0xFF, 0xFF),
+    (0x59, 0xB4, 0xFF),
+    (0x59, 0x59, 0xFF),
+    (0xC4, 0x59, 0xFF),
+    (0xFF, 0x66, 0xFF),
+    (0xFF, 0x59, 0xBC),
+    (0xFF, 0x9C, 0x9C),
+    (0xFF, 0xD3, 0x9C),
+    (0xFF, 0xFF, 0x9C),
+    (0xE2, 0xFF, 0x9C),
+    (0x9C, 0xFF, 0x9C),
+    (0x9C, 0xFF, 0xDB),
+    (0x9C, 0xFF, 0xFF),
+    (0x9C, 0xD3, 0xFF),
+    (0x9C, 0x9C, 0xFF),
+    (0xDC, 0x9C, 0xFF),
+    (0xFF, 0x9C, 0xFF),
+    (0xFF, 0x94, 0xD3),
+    (0x00, 0x00, 0x00), // 88
+    (0x13, 0x13, 0x13),
+    (0x28, 0x28, 0x28),
+    (0x36, 0x36, 0x36),
+    (0x4D, 0x4D, 0x4D),
+    (0x65, 0x65, 0x65),
+    (0x81, 0x81, 0x81),
+    (0x9F, 0x9F, 0x9F),
+    (0xBC, 0xBC, 0xBC),
+    (0xE2, 0xE2, 0xE2),
+    (0xFF, 0xFF, 0xFF), // 98
+];
+
 /// A 24-bit color composed of red, green, and blue components
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct RgbColor(
@@ -30,6 +139,282 @@ impl RgbColor {
     pub fn to_anstyle(self) -> anstyle::RgbColor {
         self.into()
     }
+
+    /// Find the closest entry in the IRC 0-98 color palette to this RGB
+    /// color, for downgrading truecolor output for clients that don't
+    /// understand the hex-color (`\x04`) code.
+    ///
+    /// Closeness is measured as a redmean-style weighted squared Euclidean
+    /// distance, `2*(dr)^2 + 4*(dg)^2 + 3*(db)^2`, which tracks human color
+    /// perception better than plain Euclidean distance.  Ties are broken in
+    /// favor of the lowest color number.
+    pub fn to_nearest_color100(self) -> Color100 {
+        PALETTE
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &(r, g, b))| {
+                let dr = i32::from(self.0) - i32::from(r);
+                let dg = i32::from(self.1) - i32::from(g);
+                let db = i32::from(self.2) - i32::from(b);
+                2 * dr * dr + 4 * dg * dg + 3 * db * db
+            })
+            .map(|(i, _)| {
+                #[allow(clippy::cast_possible_truncation)]
+                Color100::try_from(i as u8).expect("index 0..=98 is always a valid Color100")
+            })
+            .expect("PALETTE is non-empty")
+    }
+
+    /// Look up a CSS/X11 color name (case-insensitively), e.g. `"red"` or
+    /// `"cornflowerblue"`, returning `None` if the name is unrecognized.
+    pub fn from_name(name: &str) -> Option<RgbColor> {
+        named_color(&name.to_ascii_lowercase())
+    }
+}
+
+impl From<Color100> for RgbColor {
+    /// Look up the approximate RGB value of a [`Color100`] in the same
+    /// palette used by [`RgbColor::to_nearest_color100`].
+    /// [`Color100::DEFAULT`] has no RGB equivalent and maps to black.
+    fn from(value: Color100) -> RgbColor {
+        let (r, g, b) = PALETTE
+            .get(usize::from(u8::from(value)))
+            .copied()
+            .unwrap_or_default();
+        RgbColor(r, g, b)
+    }
+}
+
+/// Parse a string in one of the forms accepted by the X `XParseColor`
+/// function: `#rrggbb`, `#rgb` shorthand, or `rgb:RRRR/GGGG/BBBB` (each
+/// component 1-4 hexadecimal digits, scaled to 8 bits).
+impl FromStr for RgbColor {
+    type Err = ParseRgbColorError;
+
+    fn from_str(s: &str) -> Result<RgbColor, ParseRgbColorError> {
+        if let Some(hex) = s.strip_prefix('#') {
+            parse_hex_shorthand(hex)
+        } else if let Some(rest) = s.strip_prefix("rgb:") {
+            parse_x11_rgb(rest)
+        } else {
+            Err(ParseRgbColorError::InvalidFormat)
+        }
+    }
+}
+
+fn parse_hex_shorthand(hex: &str) -> Result<RgbColor, ParseRgbColorError> {
+    match hex.len() {
+        3 => {
+            let digits = hex
+                .chars()
+                .map(|ch| ch.to_digit(16).ok_or(ParseRgbColorError::InvalidHexDigit))
+                .collect::<Result<Vec<u32>, ParseRgbColorError>>()?;
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(RgbColor(
+                (digits[0] * 17) as u8,
+                (digits[1] * 17) as u8,
+                (digits[2] * 17) as u8,
+            ))
+        }
+        6 => {
+            let r = u8::from_str_radix(&hex[0..2], 16)
+                .map_err(|_| ParseRgbColorError::InvalidHexDigit)?;
+            let g = u8::from_str_radix(&hex[2..4], 16)
+                .map_err(|_| ParseRgbColorError::InvalidHexDigit)?;
+            let b = u8::from_str_radix(&hex[4..6], 16)
+                .map_err(|_| ParseRgbColorError::InvalidHexDigit)?;
+            Ok(RgbColor(r, g, b))
+        }
+        _ => Err(ParseRgbColorError::InvalidLength),
+    }
+}
+
+fn parse_x11_rgb(s: &str) -> Result<RgbColor, ParseRgbColorError> {
+    let parts = s.split('/').collect::<Vec<_>>();
+    let [r, g, b] = parts.as_slice() else {
+        return Err(ParseRgbColorError::WrongComponentCount);
+    };
+    Ok(RgbColor(
+        parse_x11_component(r)?,
+        parse_x11_component(g)?,
+        parse_x11_component(b)?,
+    ))
+}
+
+fn parse_x11_component(part: &str) -> Result<u8, ParseRgbColorError> {
+    let len = part.len();
+    if !(1..=4).contains(&len) {
+        return Err(ParseRgbColorError::InvalidComponent);
+    }
+    let value = u32::from_str_radix(part, 16).map_err(|_| ParseRgbColorError::InvalidHexDigit)?;
+    let max = 16u32.pow(u32::try_from(len).expect("len is 1..=4")) - 1;
+    #[allow(clippy::cast_possible_truncation)]
+    Ok((value * 255 / max) as u8)
+}
+
+/// Look up one of the 147 standard CSS/X11 color names (already
+/// lowercased) and return its RGB triple.
+fn named_color(name: &str) -> Option<RgbColor> {
+    let rgb = match name {
+        "aliceblue" => (0xF0, 0xF8, 0xFF),
+        "antiquewhite" => (0xFA, 0xEB, 0xD7),
+        "aqua" => (0x00, 0xFF, 0xFF),
+        "aquamarine" => (0x7F, 0xFF, 0xD4),
+        "azure" => (0xF0, 0xFF, 0xFF),
+        "beige" => (0xF5, 0xF5, 0xDC),
+        "bisque" => (0xFF, 0xE4, 0xC4),
+        "black" => (0x00, 0x00, 0x00),
+        "blanchedalmond" => (0xFF, 0xEB, 0xCD),
+        "blue" => (0x00, 0x00, 0xFF),
+        "blueviolet" => (0x8A, 0x2B, 0xE2),
+        "brown" => (0xA5, 0x2A, 0x2A),
+        "burlywood" => (0xDE, 0xB8, 0x87),
+        "cadetblue" => (0x5F, 0x9E, 0xA0),
+        "chartreuse" => (0x7F, 0xFF, 0x00),
+        "chocolate" => (0xD2, 0x69, 0x1E),
+        "coral" => (0xFF, 0x7F, 0x50),
+        "cornflowerblue" => (0x64, 0x95, 0xED),
+        "cornsilk" => (0xFF, 0xF8, 0xDC),
+        "crimson" => (0xDC, 0x14, 0x3C),
+        "cyan" => (0x00, 0xFF, 0xFF),
+        "darkblue" => (0x00, 0x00, 0x8B),
+        "darkcyan" => (0x00, 0x8B, 0x8B),
+        "darkgoldenrod" => (0xB8, 0x86, 0x0B),
+        "darkgray" | "darkgrey" => (0xA9, 0xA9, 0xA9),
+        "darkgreen" => (0x00, 0x64, 0x00),
+        "darkkhaki" => (0xBD, 0xB7, 0x6B),
+        "darkmagenta" => (0x8B, 0x00, 0x8B),
+        "darkolivegreen" => (0x55, 0x6B, 0x2F),
+        "darkorange" => (0xFF, 0x8C, 0x00),
+        "darkorchid" => (0x99, 0x32, 0xCC),
+        "darkred" => (0x8B, 0x00, 0x00),
+        "darksalmon" => (0xE9, 0x96, 0x7A),
+        "darkseagreen" => (0x8F, 0xBC, 0x8F),
+        "darkslateblue" => (0x48, 0x3D, 0x8B),
+        "darkslategray" | "darkslategrey" => (0x2F, 0x4F, 0x4F),
+        "darkturquoise" => (0x00, 0xCE, 0xD1),
+        "darkviolet" => (0x94, 0x00, 0xD3),
+        "deeppink" => (0xFF, 0x14, 0x93),
+        "deepskyblue" => (0x00, 0xBF, 0xFF),
+        "dimgray" | "dimgrey" => (0x69, 0x69, 0x69),
+        "dodgerblue" => (0x1E, 0x90, 0xFF),
+        "firebrick" => (0xB2, 0x22, 0x22),
+        "floralwhite" => (0xFF, 0xFA, 0xF0),
+        "forestgreen" => (0x22, 0x8B, 0x22),
+        "fuchsia" => (0xFF, 0x00, 0xFF),
+        "gainsboro" => (0xDC, 0xDC, 0xDC),
+        "ghostwhite" => (0xF8, 0xF8, 0xFF),
+        "gold" => (0xFF, 0xD7, 0x00),
+        "goldenrod" => (0xDA, 0xA5, 0x20),
+        "gray" | "grey" => (0x80, 0x80, 0x80),
+        "green" => (0x00, 0x80, 0x00),
+        "greenyellow" => (0xAD, 0xFF, 0x2F),
+        "honeydew" => (0xF0, 0xFF, 0xF0),
+        "hotpink" => (0xFF, 0x69, 0xB4),
+        "indianred" => (0xCD, 0x5C, 0x5C),
+        "indigo" => (0x4B, 0x00, 0x82),
+        "ivory" => (0xFF, 0xFF, 0xF0),
+        "khaki" => (0xF0, 0xE6, 0x8C),
+        "lavender" => (0xE6, 0xE6, 0xFA),
+        "lavenderblush" => (0xFF, 0xF0, 0xF5),
+        "lawngreen" => (0x7C, 0xFC, 0x00),
+        "lemonchiffon" => (0xFF, 0xFA, 0xCD),
+        "lightblue" => (0xAD, 0xD8, 0xE6),
+        "lightcoral" => (0xF0, 0x80, 0x80),
+        "lightcyan" => (0xE0, 0xFF, 0xFF),
+        "lightgoldenrodyellow" => (0xFA, 0xFA, 0xD2),
+        "lightgray" | "lightgrey" => (0xD3, 0xD3, 0xD3),
+        "lightgreen" => (0x90, 0xEE, 0x90),
+        "lightpink" => (0xFF, 0xB6, 0xC1),
+        "lightsalmon" => (0xFF, 0xA0, 0x7A),
+        "lightseagreen" => (0x20, 0xB2, 0xAA),
+        "lightskyblue" => (0x87, 0xCE, 0xFA),
+        "lightslategray" | "lightslategrey" => (0x77, 0x88, 0x99),
+        "lightsteelblue" => (0xB0, 0xC4, 0xDE),
+        "lightyellow" => (0xFF, 0xFF, 0xE0),
+        "lime" => (0x00, 0xFF, 0x00),
+        "limegreen" => (0x32, 0xCD, 0x32),
+        "linen" => (0xFA, 0xF0, 0xE6),
+        "magenta" => (0xFF, 0x00, 0xFF),
+        "maroon" => (0x80, 0x00, 0x00),
+        "mediumaquamarine" => (0x66, 0xCD, 0xAA),
+        "mediumblue" => (0x00, 0x00, 0xCD),
+        "mediumorchid" => (0xBA, 0x55, 0xD3),
+        "mediumpurple" => (0x93, 0x70, 0xDB),
+        "mediumseagreen" => (0x3C, 0xB3, 0x71),
+        "mediumslateblue" => (0x7B, 0x68, 0xEE),
+        "mediumspringgreen" => (0x00, 0xFA, 0x9A),
+        "mediumturquoise" => (0x48, 0xD1, 0xCC),
+        "mediumvioletred" => (0xC7, 0x15, 0x85),
+        "midnightblue" => (0x19, 0x19, 0x70),
+        "mintcream" => (0xF5, 0xFF, 0xFA),
+        "mistyrose" => (0xFF, 0xE4, 0xE1),
+        "moccasin" => (0xFF, 0xE4, 0xB5),
+        "navajowhite" => (0xFF, 0xDE, 0xAD),
+        "navy" => (0x00, 0x00, 0x80),
+        "oldlace" => (0xFD, 0xF5, 0xE6),
+        "olive" => (0x80, 0x80, 0x00),
+        "olivedrab" => (0x6B, 0x8E, 0x23),
+        "orange" => (0xFF, 0xA5, 0x00),
+        "orangered" => (0xFF, 0x45, 0x00),
+        "orchid" => (0xDA, 0x70, 0xD6),
+        "palegoldenrod" => (0xEE, 0xE8, 0xAA),
+        "palegreen" => (0x98, 0xFB, 0x98),
+        "paleturquoise" => (0xAF, 0xEE, 0xEE),
+        "palevioletred" => (0xDB, 0x70, 0x93),
+        "papayawhip" => (0xFF, 0xEF, 0xD5),
+        "peachpuff" => (0xFF, 0xDA, 0xB9),
+        "peru" => (0xCD, 0x85, 0x3F),
+        "pink" => (0xFF, 0xC0, 0xCB),
+        "plum" => (0xDD, 0xA0, 0xDD),
+        "powderblue" => (0xB0, 0xE0, 0xE6),
+        "purple" => (0x80, 0x00, 0x80),
+        "rebeccapurple" => (0x66, 0x33, 0x99),
+        "red" => (0xFF, 0x00, 0x00),
+        "rosybrown" => (0xBC, 0x8F, 0x8F),
+        "royalblue" => (0x41, 0x69, 0xE1),
+        "saddlebrown" => (0x8B, 0x45, 0x13),
+        "salmon" => (0xFA, 0x80, 0x72),
+        "sandybrown" => (0xF4, 0xA4, 0x60),
+        "seagreen" => (0x2E, 0x8B, 0x57),
+        "seashell" => (0xFF, 0xF5, 0xEE),
+        "sienna" => (0xA0, 0x52, 0x2D),
+        "silver" => (0xC0, 0xC0, 0xC0),
+        "skyblue" => (0x87, 0xCE, 0xEB),
+        "slateblue" => (0x6A, 0x5A, 0xCD),
+        "slategray" | "slategrey" => (0x70, 0x80, 0x90),
+        "snow" => (0xFF, 0xFA, 0xFA),
+        "springgreen" => (0x00, 0xFF, 0x7F),
+        "steelblue" => (0x46, 0x82, 0xB4),
+        "tan" => (0xD2, 0xB4, 0x8C),
+        "teal" => (0x00, 0x80, 0x80),
+        "thistle" => (0xD8, 0xBF, 0xD8),
+        "tomato" => (0xFF, 0x63, 0x47),
+        "turquoise" => (0x40, 0xE0, 0xD0),
+        "violet" => (0xEE, 0x82, 0xEE),
+        "wheat" => (0xF5, 0xDE, 0xB3),
+        "white" => (0xFF, 0xFF, 0xFF),
+        "whitesmoke" => (0xF5, 0xF5, 0xF5),
+        "yellow" => (0xFF, 0xFF, 0x00),
+        "yellowgreen" => (0x9A, 0xCD, 0x32),
+        _ => return None,
+    };
+    Some(RgbColor::from(rgb))
+}
+
+/// An error parsing an [`RgbColor`] from a string via [`RgbColor::from_str`]
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum ParseRgbColorError {
+    #[error("color string must start with '#' or \"rgb:\"")]
+    InvalidFormat,
+    #[error("color string contains a non-hexadecimal digit")]
+    InvalidHexDigit,
+    #[error("'#' color strings must be 3 or 6 hexadecimal digits long")]
+    InvalidLength,
+    #[error("\"rgb:\" color components must be 1 to 4 hexadecimal digits long")]
+    InvalidComponent,
+    #[error("\"rgb:\" color strings must have exactly 3 components")]
+    WrongComponentCount,
 }
 
 impl From<(u8, u8, u8)> for RgbColor {
@@ -52,3 +437,124 @@ impl From<RgbColor> for anstyle::RgbColor {
         anstyle::RgbColor(value.0, value.1, value.2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex6() {
+        assert_eq!(
+            "#ffffff".parse::<RgbColor>().unwrap(),
+            RgbColor(0xFF, 0xFF, 0xFF)
+        );
+        assert_eq!(
+            "#1a2b3c".parse::<RgbColor>().unwrap(),
+            RgbColor(0x1A, 0x2B, 0x3C)
+        );
+    }
+
+    #[test]
+    fn parse_hex3() {
+        assert_eq!(
+            "#fff".parse::<RgbColor>().unwrap(),
+            RgbColor(0xFF, 0xFF, 0xFF)
+        );
+        assert_eq!(
+            "#a0f".parse::<RgbColor>().unwrap(),
+            RgbColor(0xAA, 0x00, 0xFF)
+        );
+    }
+
+    #[test]
+    fn parse_x11_rgb() {
+        assert_eq!(
+            "rgb:ffff/0000/0000".parse::<RgbColor>().unwrap(),
+            RgbColor(0xFF, 0x00, 0x00)
+        );
+        assert_eq!(
+            "rgb:f/0/0".parse::<RgbColor>().unwrap(),
+            RgbColor(0xFF, 0x00, 0x00)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_format() {
+        assert_eq!(
+            "notacolor".parse::<RgbColor>(),
+            Err(ParseRgbColorError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_length() {
+        assert_eq!(
+            "#ff".parse::<RgbColor>(),
+            Err(ParseRgbColorError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_hex_digit() {
+        assert_eq!(
+            "#gggggg".parse::<RgbColor>(),
+            Err(ParseRgbColorError::InvalidHexDigit)
+        );
+    }
+
+    #[test]
+    fn parse_wrong_component_count() {
+        assert_eq!(
+            "rgb:f/0".parse::<RgbColor>(),
+            Err(ParseRgbColorError::WrongComponentCount)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_component() {
+        assert_eq!(
+            "rgb:fffff/0/0".parse::<RgbColor>(),
+            Err(ParseRgbColorError::InvalidComponent)
+        );
+    }
+
+    #[test]
+    fn from_name_known() {
+        assert_eq!(
+            RgbColor::from_name("cornflowerblue"),
+            Some(RgbColor(0x64, 0x95, 0xED))
+        );
+        assert_eq!(RgbColor::from_name("RED"), Some(RgbColor(0xFF, 0x00, 0x00)));
+        assert_eq!(RgbColor::from_name("gray"), RgbColor::from_name("grey"));
+    }
+
+    #[test]
+    fn from_name_unknown() {
+        assert_eq!(RgbColor::from_name("notacolor"), None);
+    }
+
+    #[test]
+    fn from_color100() {
+        assert_eq!(RgbColor::from(Color100::RED), RgbColor(0xFF, 0x00, 0x00));
+        assert_eq!(
+            RgbColor::from(Color100::DEFAULT),
+            RgbColor(0x00, 0x00, 0x00)
+        );
+    }
+
+    #[test]
+    fn color100_roundtrips_through_rgb() {
+        // Not every Color100 survives the roundtrip exactly, since several
+        // palette entries are close enough in RGB space to collide under
+        // to_nearest_color100()'s distance metric; check a representative
+        // sample of distinct, unambiguous colors instead.
+        for c in [
+            Color100::WHITE,
+            Color100::BLACK,
+            Color100::RED,
+            Color100::GREEN,
+        ] {
+            assert_eq!(RgbColor::from(c).to_nearest_color100(), c);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::{Attribute, AttributeSet, Color, Color100, Style};
+use std::collections::HashMap;
+
+/// A mapping from user-defined category names (e.g. `"nick"`, `"url"`,
+/// `"highlight"`) to the [`Style`] a client should use when rendering them,
+/// loaded from a compact spec string in the spirit of `LS_COLORS`.
+///
+/// A spec is a comma-separated list of `category=value` entries, e.g.
+/// `"nick=02,url=04;4,highlight=08"`. Each value is a foreground
+/// [`Color100`] index (the same two-digit token used by the `\x03` color
+/// control code) optionally followed by a `;`-separated bitmask of
+/// [`Attribute`] values to set; either half may be omitted, but a value of
+/// just `;` is pointless. There is no way to set a background color or
+/// distinguish "unset" from "not mentioned" through this spec, so themes
+/// that need either should be built up directly via [`Theme::insert`]
+/// instead. Entries that are malformed, or whose color index is out of the
+/// `Color100` range, are skipped rather than rejecting the whole spec.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Theme(HashMap<String, Style>);
+
+impl Theme {
+    /// Construct an empty theme.
+    pub fn new() -> Theme {
+        Theme(HashMap::new())
+    }
+
+    /// Parse a `category=value,...` spec string into a [`Theme`], silently
+    /// skipping any entry that isn't a recognized `category=value` pair.
+    pub fn from_spec(spec: &str) -> Theme {
+        let mut theme = Theme::new();
+        for entry in spec.split(',') {
+            let Some((category, value)) = entry.split_once('=') else {
+                continue;
+            };
+            let Some(style) = parse_style_spec(value) else {
+                continue;
+            };
+            theme.insert(category, style);
+        }
+        theme
+    }
+
+    /// Load a [`Theme`] from the spec string in the environment variable
+    /// `var`, or an empty theme if `var` is unset or not valid Unicode.
+    pub fn from_env(var: &str) -> Theme {
+        std::env::var(var)
+            .ok()
+            .map_or_else(Theme::new, |spec| Theme::from_spec(&spec))
+    }
+
+    /// Register a [`Style`] for `category`, overwriting any previous value.
+    pub fn insert(&mut self, category: impl Into<String>, style: Style) {
+        self.0.insert(category.into(), style);
+    }
+
+    /// Look up the [`Style`] registered for `category`, if any.
+    pub fn style_for(&self, category: &str) -> Option<Style> {
+        self.0.get(category).copied()
+    }
+}
+
+/// Parse one `Theme` value: an optional [`Color100`] index, optionally
+/// followed by `;` and a bitmask of [`Attribute`] values.
+fn parse_style_spec(value: &str) -> Option<Style> {
+    let mut style = Style::default();
+    let mut parts = value.splitn(2, ';');
+    let color_part = parts.next().unwrap_or_default();
+    if !color_part.is_empty() {
+        let n: u8 = color_part.parse().ok()?;
+        style.foreground = Color::from(Color100::try_from(n).ok()?);
+    }
+    if let Some(attr_part) = parts.next() {
+        let bits: u8 = attr_part.parse().ok()?;
+        style.attributes = Attribute::iter()
+            .filter(|&attr| bits & (attr as u8) != 0)
+            .collect::<AttributeSet>();
+    }
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_spec_parses_color_and_attributes() {
+        let theme = Theme::from_spec("nick=02,url=04;4,highlight=08");
+        assert_eq!(
+            theme.style_for("nick"),
+            Some(Style {
+                foreground: Color100::try_from(2).unwrap().into(),
+                ..Style::default()
+            })
+        );
+        assert_eq!(
+            theme.style_for("url"),
+            Some(Style {
+                foreground: Color100::try_from(4).unwrap().into(),
+                attributes: Attribute::Underline.into(),
+                ..Style::default()
+            })
+        );
+        assert_eq!(
+            theme.style_for("highlight"),
+            Some(Style {
+                foreground: Color100::try_from(8).unwrap().into(),
+                ..Style::default()
+            })
+        );
+    }
+
+    #[test]
+    fn from_spec_skips_malformed_entries() {
+        let theme = Theme::from_spec("nick=02,malformed,outofrange=200,nonnumeric=xx");
+        assert_eq!(theme.style_for("malformed"), None);
+        assert_eq!(theme.style_for("outofrange"), None);
+        assert_eq!(theme.style_for("nonnumeric"), None);
+        assert_eq!(
+            theme.style_for("nick"),
+            Some(Style {
+                foreground: Color100::try_from(2).unwrap().into(),
+                ..Style::default()
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_category_is_none() {
+        let theme = Theme::from_spec("nick=02");
+        assert_eq!(theme.style_for("url"), None);
+    }
+
+    #[test]
+    fn empty_spec_is_empty_theme() {
+        assert_eq!(Theme::from_spec(""), Theme::new());
+    }
+
+    #[test]
+    fn insert_overwrites() {
+        let mut theme = Theme::from_spec("nick=02");
+        theme.insert("nick", Style::default());
+        assert_eq!(theme.style_for("nick"), Some(Style::default()));
+    }
+}
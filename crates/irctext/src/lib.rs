@@ -2,13 +2,18 @@
 #[macro_use]
 mod validstr;
 
+mod address;
+mod casemap;
 mod casemapping;
 pub mod clientmsgs;
 mod command;
 mod consts;
 pub mod ctcp;
 pub mod formatting;
+mod maybeutf8;
 mod message;
+mod messagetags;
+mod numeric;
 mod parameters;
 mod raw_message;
 pub mod replies;
@@ -16,14 +21,21 @@ mod source;
 pub mod types;
 mod util;
 mod verb;
+pub use crate::address::*;
+pub use crate::casemap::*;
 pub use crate::casemapping::*;
 pub use crate::clientmsgs::{ClientMessage, ClientMessageError, ClientMessageParts};
 pub use crate::command::*;
 pub use crate::consts::*;
+pub use crate::maybeutf8::*;
 pub use crate::message::*;
+pub use crate::messagetags::*;
+pub use crate::numeric::*;
 pub use crate::parameters::*;
 pub use crate::raw_message::*;
-pub use crate::replies::{Reply, ReplyError, ReplyParts};
+pub use crate::replies::{
+    JoinError, Reply, ReplyError, ReplyHandler, ReplyParts, ReplyRef, ReplyVisitor, ServerError,
+};
 pub use crate::source::*;
 pub use crate::validstr::TryFromStringError;
 pub use crate::verb::*;
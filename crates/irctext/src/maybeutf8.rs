@@ -0,0 +1,111 @@
+//! A byte-preserving text representation for IRC parameters.
+//!
+//! IRC is byte-oriented, and servers/clients on legacy networks still send
+//! Latin-1, CP1251, Shift-JIS, or other non-UTF-8 encodings in message
+//! parameters. [`MaybeUtf8`] follows the design used by other IRC libraries
+//! (e.g. irsc): try to decode incoming bytes as UTF-8, and if that fails,
+//! retain the raw bytes unchanged rather than losing or mangling them,
+//! leaving decoding into a specific charset as an opt-in, on-demand step.
+#[cfg(feature = "encoding_rs")]
+use std::borrow::Cow;
+
+/// Text that was successfully decoded as UTF-8, or the raw bytes of text
+/// that wasn't, preserved as-is so re-serializing never loses or corrupts
+/// the original octets.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MaybeUtf8 {
+    Utf8(String),
+    Bytes(Vec<u8>),
+}
+
+impl MaybeUtf8 {
+    /// Wraps `bytes`, decoding as UTF-8 if possible and falling back to the
+    /// raw bytes otherwise.
+    pub fn from_bytes(bytes: Vec<u8>) -> MaybeUtf8 {
+        match String::from_utf8(bytes) {
+            Ok(s) => MaybeUtf8::Utf8(s),
+            Err(e) => MaybeUtf8::Bytes(e.into_bytes()),
+        }
+    }
+
+    /// Returns the underlying bytes, UTF-8-encoded if this value decoded
+    /// successfully.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            MaybeUtf8::Utf8(s) => s.as_bytes(),
+            MaybeUtf8::Bytes(b) => b,
+        }
+    }
+
+    /// Returns the decoded text, if this value is valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MaybeUtf8::Utf8(s) => Some(s),
+            MaybeUtf8::Bytes(_) => None,
+        }
+    }
+
+    /// Returns `true` if the original bytes were valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        matches!(self, MaybeUtf8::Utf8(_))
+    }
+
+    /// Decodes the underlying bytes using `encoding`, returning the result
+    /// of [`Encoding::decode`](encoding_rs::Encoding::decode) directly (a
+    /// lossy `Cow<str>` plus whether the input was malformed for that
+    /// encoding). This is the escape hatch for legacy networks that don't
+    /// send UTF-8 at all; it does not affect [`MaybeUtf8::as_bytes`], so
+    /// the original wire bytes are always available for re-serialization
+    /// regardless of which charset the decoded text came from.
+    #[cfg(feature = "encoding_rs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding_rs")))]
+    pub fn decode(&self, encoding: &'static encoding_rs::Encoding) -> (Cow<'_, str>, bool) {
+        let (text, _, malformed) = encoding.decode(self.as_bytes());
+        (text, malformed)
+    }
+}
+
+impl From<String> for MaybeUtf8 {
+    fn from(s: String) -> MaybeUtf8 {
+        MaybeUtf8::Utf8(s)
+    }
+}
+
+impl From<Vec<u8>> for MaybeUtf8 {
+    fn from(bytes: Vec<u8>) -> MaybeUtf8 {
+        MaybeUtf8::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_valid_utf8() {
+        let m = MaybeUtf8::from_bytes(b"hello".to_vec());
+        assert_eq!(m, MaybeUtf8::Utf8(String::from("hello")));
+        assert_eq!(m.as_str(), Some("hello"));
+        assert_eq!(m.as_bytes(), b"hello");
+        assert!(m.is_utf8());
+    }
+
+    #[test]
+    fn from_bytes_invalid_utf8_preserves_bytes() {
+        // 0xE9 alone is Latin-1 for "é" but isn't valid UTF-8.
+        let raw = vec![b'h', b'i', 0xE9];
+        let m = MaybeUtf8::from_bytes(raw.clone());
+        assert_eq!(m, MaybeUtf8::Bytes(raw.clone()));
+        assert_eq!(m.as_str(), None);
+        assert_eq!(m.as_bytes(), raw.as_slice());
+        assert!(!m.is_utf8());
+    }
+
+    #[test]
+    fn roundtrip_preserves_original_bytes() {
+        for raw in [b"plain ascii".to_vec(), vec![b'x', 0xFF, 0xFE]] {
+            let m = MaybeUtf8::from_bytes(raw.clone());
+            assert_eq!(m.as_bytes(), raw.as_slice());
+        }
+    }
+}
@@ -1,18 +1,81 @@
+use crate::types::{TagKey, TagValue};
 use crate::{
-    ClientMessage, ClientMessageError, ClientMessageParts, Command, ParameterList,
-    ParseRawMessageError, RawMessage, Reply, ReplyError, ReplyParts, Source, TryFromStringError,
+    ClientMessage, ClientMessageError, ClientMessageParts, Command, MessageTags, ParameterList,
+    ParseRawMessageError, RawMessage, Reply, ReplyCode, ReplyError, ReplyParts, Source,
+    TryFromStringError,
 };
 use std::fmt;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Message {
+    pub tags: Option<MessageTags>,
     pub source: Option<Source>,
     pub payload: Payload,
 }
 
+// `Message` (de)serializes by going through `RawMessage`'s structured
+// `tags`/`source`/`command`/`params` representation rather than deriving
+// directly, since `Payload` (a decoded `ClientMessage` or `Reply`) has no
+// JSON shape of its own — only the undecoded command/parameters do.  This
+// means a `Message` that fails to decode can't be deserialized (unlike
+// `RawMessage`, which accepts any command/parameter shape); see
+// [`RawMessage`]'s docs for why logging pipelines should generally prefer
+// serializing at that level instead.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Message {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serde::Serialize::serialize(&RawMessage::from(self.clone()), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Message {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let raw = <RawMessage as serde::Deserialize>::deserialize(deserializer)?;
+        Message::try_from(raw).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Message {
+    /// Returns `self` with `tags` attached, replacing any tags already set.
+    ///
+    /// This is the general way to attach client-only tags (such as
+    /// `+typing`) to an outgoing message: build the
+    /// [`ClientMessage`](crate::clientmsgs) as usual, convert it to a
+    /// `Message`, then call `with_tags()`/`with_tag()` before sending, since
+    /// tags belong to the line as a whole rather than to any one
+    /// [`ClientMessage`](crate::clientmsgs) variant.
+    pub fn with_tags(mut self, tags: MessageTags) -> Message {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Returns `self` with `key`/`value` inserted into its tags, creating an
+    /// empty [`MessageTags`] first if none are set yet.  Useful for
+    /// attaching a single tag, such as the client-only `+typing` tag,
+    /// without having to build a whole [`MessageTags`] by hand.
+    pub fn with_tag(mut self, key: TagKey, value: Option<TagValue>) -> Message {
+        self.tags
+            .get_or_insert_with(MessageTags::new)
+            .insert(key, value);
+        self
+    }
+}
+
 impl fmt::Display for Message {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(tags) = self.tags.as_ref() {
+            write!(f, "@{tags} ")?;
+        }
         if let Some(source) = self.source.as_ref() {
             write!(f, ":{source} ")?;
         }
@@ -52,6 +115,7 @@ impl TryFrom<String> for Message {
 impl From<Payload> for Message {
     fn from(payload: Payload) -> Message {
         Message {
+            tags: None,
             source: None,
             payload,
         }
@@ -62,17 +126,24 @@ impl TryFrom<RawMessage> for Message {
     type Error = MessageError;
 
     fn try_from(msg: RawMessage) -> Result<Message, MessageError> {
+        let tags = msg.tags;
         let source = msg.source;
         let payload = Payload::from_parts(msg.command, msg.parameters)?;
-        Ok(Message { source, payload })
+        Ok(Message {
+            tags,
+            source,
+            payload,
+        })
     }
 }
 
 impl From<Message> for RawMessage {
     fn from(msg: Message) -> RawMessage {
+        let tags = msg.tags;
         let source = msg.source;
         let (command, parameters) = msg.payload.into_parts();
         RawMessage {
+            tags,
             source,
             command,
             parameters,
@@ -102,7 +173,7 @@ impl Payload {
             Command::Verb(v) => Ok(Payload::ClientMessage(ClientMessage::from_parts(
                 v, params,
             )?)),
-            Command::Reply(code) => Ok(Payload::Reply(Reply::from_parts(code, params)?)),
+            Command::Reply(code) => Ok(Payload::Reply(Reply::from_parts(code.as_u16(), params)?)),
         }
     }
 
@@ -114,7 +185,7 @@ impl Payload {
             }
             Payload::Reply(r) => {
                 let (code, params) = r.into_parts();
-                (Command::Reply(code), params)
+                (Command::Reply(ReplyCode::from(code)), params)
             }
         }
     }
@@ -159,3 +230,52 @@ pub enum ParseMessageError {
     #[error(transparent)]
     Convert(#[from] MessageError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_tags() {
+        let msg = "@msgid=123;+draft/reply=321;account=jwodder :jess!~user@localhost PRIVMSG #test :hello"
+            .parse::<Message>()
+            .unwrap();
+        let tags = msg.tags.as_ref().unwrap();
+        assert_eq!(tags.get("msgid").unwrap().unwrap().as_str(), "123");
+        assert_eq!(tags.get("+draft/reply").unwrap().unwrap().as_str(), "321");
+        assert_eq!(tags.get("account").unwrap().unwrap().as_str(), "jwodder");
+        assert_eq!(msg.source.unwrap().to_string(), "jess!~user@localhost");
+    }
+
+    #[test]
+    fn without_tags() {
+        let msg = ":jess!~user@localhost PRIVMSG #test :hello"
+            .parse::<Message>()
+            .unwrap();
+        assert!(msg.tags.is_none());
+    }
+
+    #[test]
+    fn tags_roundtrip() {
+        let s = "@aaa=bbb;ccc;example.com/ddd=eee :nick!ident@host.com PRIVMSG me :Hello";
+        let msg = s.parse::<Message>().unwrap();
+        assert_eq!(msg.to_string(), s);
+    }
+
+    #[test]
+    fn with_tag_attaches_client_only_tag() {
+        let msg = "PRIVMSG #chan :hi".parse::<Message>().unwrap();
+        assert!(msg.tags.is_none());
+        let msg = msg.with_tag("+typing".parse().unwrap(), Some("active".parse().unwrap()));
+        assert_eq!(msg.to_string(), "@+typing=active PRIVMSG #chan :hi");
+    }
+
+    #[test]
+    fn with_tags_replaces_existing_tags() {
+        let msg = "@id=1 PRIVMSG #chan :hi".parse::<Message>().unwrap();
+        let mut tags = MessageTags::new();
+        tags.insert("id".parse().unwrap(), Some("2".parse().unwrap()));
+        let msg = msg.with_tags(tags);
+        assert_eq!(msg.to_string(), "@id=2 PRIVMSG #chan :hi");
+    }
+}
@@ -0,0 +1,249 @@
+use crate::types::{ParseTagKeyError, ParseTagValueError, TagKey, TagValue};
+use std::fmt;
+use thiserror::Error;
+
+/// The IRCv3 [message-tags](https://ircv3.net/specs/extensions/message-tags.html)
+/// segment of a line: the `@tag=value;tag2=value2` prefix that, if present,
+/// comes before the optional source and the command.
+///
+/// Tag keys are unique; [`MessageTags::insert()`] on an already-present key
+/// overwrites its value in place (rather than appending a second entry),
+/// matching the IRCv3 rule that, should a key appear more than once on the
+/// wire, the last occurrence wins.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MessageTags(Vec<(TagKey, Option<TagValue>)>);
+
+impl MessageTags {
+    pub fn new() -> MessageTags {
+        MessageTags::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<Option<&TagValue>> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v.as_ref())
+    }
+
+    /// Insert `key`/`value`, overwriting `key`'s existing value (if any)
+    /// without disturbing its position among the other tags.
+    pub fn insert(&mut self, key: TagKey, value: Option<TagValue>) {
+        if let Some(entry) = self.0.iter_mut().find(|(k, _)| *k == key) {
+            entry.1 = value;
+        } else {
+            self.0.push((key, value));
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&TagKey, Option<&TagValue>)> + '_ {
+        self.0.iter().map(|(k, v)| (k, v.as_ref()))
+    }
+
+    fn str_value(&self, key: &str) -> Option<&str> {
+        self.get(key).flatten().map(TagValue::as_str)
+    }
+
+    /// The server time at which the message was received/generated, from
+    /// the `time` tag (<https://ircv3.net/specs/extensions/server-time>).
+    pub fn time(&self) -> Option<&str> {
+        self.str_value("time")
+    }
+
+    /// The unique ID the server assigned the message, from the `msgid` tag
+    /// (<https://ircv3.net/specs/extensions/message-ids>).
+    pub fn msgid(&self) -> Option<&str> {
+        self.str_value("msgid")
+    }
+
+    /// The account name of the message's sender, from the `account` tag
+    /// (<https://ircv3.net/specs/extensions/account-tag>).
+    pub fn account(&self) -> Option<&str> {
+        self.str_value("account")
+    }
+
+    /// The label correlating this message with the client command that
+    /// triggered it, from the `label` tag
+    /// (<https://ircv3.net/specs/extensions/labeled-response>).
+    pub fn label(&self) -> Option<&str> {
+        self.str_value("label")
+    }
+
+    /// The reference tag of the `BATCH` this message belongs to, from the
+    /// `batch` tag (<https://ircv3.net/specs/extensions/batch>).
+    pub fn batch(&self) -> Option<&str> {
+        self.str_value("batch")
+    }
+}
+
+impl IntoIterator for MessageTags {
+    type Item = (TagKey, Option<TagValue>);
+    type IntoIter = std::vec::IntoIter<(TagKey, Option<TagValue>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl std::str::FromStr for MessageTags {
+    type Err = ParseMessageTagsError;
+
+    fn from_str(s: &str) -> Result<MessageTags, ParseMessageTagsError> {
+        let mut tags = MessageTags::new();
+        for item in s.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+            let (key, value) = match item.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (item, None),
+            };
+            let key = key.parse::<TagKey>()?;
+            let value = value.map(TagValue::from_escaped).transpose()?;
+            tags.insert(key, value);
+        }
+        Ok(tags)
+    }
+}
+
+impl fmt::Display for MessageTags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (key, value) in self.iter() {
+            if !std::mem::replace(&mut first, false) {
+                write!(f, ";")?;
+            }
+            write!(f, "{key}")?;
+            if let Some(value) = value {
+                write!(f, "={}", value.escaped())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for MessageTags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for MessageTags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = MessageTags;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("an IRCv3 message-tags segment")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<MessageTags, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum ParseMessageTagsError {
+    #[error("invalid tag key")]
+    Key(#[from] ParseTagKeyError),
+    #[error("invalid tag value")]
+    Value(#[from] ParseTagValueError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let tags = "".parse::<MessageTags>().unwrap();
+        assert!(tags.is_empty());
+        assert_eq!(tags.to_string(), "");
+    }
+
+    #[test]
+    fn roundtrip() {
+        let s = r"aaa=bbb;ccc;example.com/ddd=eee";
+        let tags = s.parse::<MessageTags>().unwrap();
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags.get("aaa").unwrap().unwrap().as_str(), "bbb");
+        assert_eq!(tags.get("ccc").unwrap(), None);
+        assert_eq!(tags.get("example.com/ddd").unwrap().unwrap().as_str(), "eee");
+        assert_eq!(tags.get("zzz"), None);
+        assert_eq!(tags.to_string(), s);
+    }
+
+    #[test]
+    fn escaped_value() {
+        let tags = r"esc=a\sb\:c\\d\nd\re"
+            .parse::<MessageTags>()
+            .unwrap();
+        assert_eq!(
+            tags.get("esc").unwrap().unwrap().as_str(),
+            "a b;c\\d\nd\re"
+        );
+        assert_eq!(tags.to_string(), r"esc=a\sb\:c\\d\nd\re");
+    }
+
+    #[test]
+    fn escaped_value_trailing_lone_backslash_is_dropped() {
+        let tags = r"esc=a\sb\".parse::<MessageTags>().unwrap();
+        assert_eq!(tags.get("esc").unwrap().unwrap().as_str(), "a b");
+    }
+
+    #[test]
+    fn typed_accessors() {
+        let tags = "time=2023-01-01T00:00:00.000Z;msgid=abc123;account=jwodder;label=5;batch=ref1"
+            .parse::<MessageTags>()
+            .unwrap();
+        assert_eq!(tags.time(), Some("2023-01-01T00:00:00.000Z"));
+        assert_eq!(tags.msgid(), Some("abc123"));
+        assert_eq!(tags.account(), Some("jwodder"));
+        assert_eq!(tags.label(), Some("5"));
+        assert_eq!(tags.batch(), Some("ref1"));
+    }
+
+    #[test]
+    fn typed_accessors_absent() {
+        let tags = "aaa=bbb".parse::<MessageTags>().unwrap();
+        assert_eq!(tags.time(), None);
+        assert_eq!(tags.msgid(), None);
+        assert_eq!(tags.account(), None);
+        assert_eq!(tags.label(), None);
+        assert_eq!(tags.batch(), None);
+    }
+
+    #[test]
+    fn duplicate_key_last_wins() {
+        let tags = "a=1;b=2;a=3".parse::<MessageTags>().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.get("a").unwrap().unwrap().as_str(), "3");
+        assert_eq!(tags.to_string(), "a=3;b=2");
+    }
+}
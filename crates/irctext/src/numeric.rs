@@ -0,0 +1,523 @@
+//! A typed view over the numeric reply codes in [`Command::Reply`](crate::Command::Reply)
+use thiserror::Error;
+
+/// A named RFC 1459/2812 and modern-IRC numeric reply or error code.
+///
+/// `Numeric` is a lookup table over the raw numeric code carried by
+/// [`Command::Reply`](crate::Command::Reply), giving callers exhaustive
+/// `match` handling of well-known server replies instead of scattering
+/// magic numbers through client code. It does not replace `Command::Reply`
+/// or [`ReplyCode`](crate::ReplyCode) — a numeric code that isn't recognized
+/// here is simply absent from this table, while the raw code is still
+/// available unchanged from the `Command`/`ReplyCode` it came from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Numeric {
+    RplWelcome,
+    RplYourHost,
+    RplCreated,
+    RplMyInfo,
+    RplISupport,
+    RplBounce,
+    RplRemoteISupport,
+    RplStatsCommands,
+    RplEndOfStats,
+    RplUModeIs,
+    RplStatsUptime,
+    RplLuserClient,
+    RplLuserOp,
+    RplLuserUnknown,
+    RplLuserChannels,
+    RplLuserMe,
+    RplAdminMe,
+    RplAdminLoc1,
+    RplAdminLoc2,
+    RplAdminEmail,
+    RplTryAgain,
+    RplLocalUsers,
+    RplGlobalUsers,
+    RplWhoIsCertFP,
+    RplNone,
+    RplAway,
+    RplUserHostRpl,
+    RplUnAway,
+    RplNowAway,
+    RplWhoIsRegNick,
+    RplWhoIsUser,
+    RplWhoIsServer,
+    RplWhoIsOperator,
+    RplWhoWasUser,
+    RplEndOfWho,
+    RplWhoIsIdle,
+    RplEndOfWhoIs,
+    RplWhoIsChannels,
+    RplWhoIsSpecial,
+    RplListStart,
+    RplList,
+    RplListEnd,
+    RplChannelModeIs,
+    RplCreationTime,
+    RplWhoIsAccount,
+    RplNoTopic,
+    RplTopic,
+    RplTopicWhoTime,
+    RplInviteList,
+    RplEndOfInviteList,
+    RplWhoIsActually,
+    RplInviting,
+    RplInvExList,
+    RplEndOfInvExList,
+    RplExceptList,
+    RplEndOfExceptList,
+    RplVersion,
+    RplWhoReply,
+    RplNamReply,
+    RplLinks,
+    RplEndOfLinks,
+    RplEndOfNames,
+    RplBanList,
+    RplEndOfBanList,
+    RplEndOfWhoWas,
+    RplInfo,
+    RplMotd,
+    RplEndOfInfo,
+    RplMotdStart,
+    RplEndOfMotd,
+    RplWhoIsHost,
+    RplWhoIsModes,
+    RplYoureOper,
+    RplRehashing,
+    RplTime,
+    ErrUnknownError,
+    ErrNoSuchNick,
+    ErrNoSuchServer,
+    ErrNoSuchChannel,
+    ErrCannotSendToChan,
+    ErrTooManyChannels,
+    ErrWasNoSuchNick,
+    ErrNoOrigin,
+    ErrNoRecipient,
+    ErrNoTextToSend,
+    ErrInputTooLong,
+    ErrUnknownCommand,
+    ErrNoMotd,
+    ErrNoNicknameGiven,
+    ErrErroneousNickname,
+    ErrNicknameInUse,
+    ErrNickCollision,
+    ErrUserNotInChannel,
+    ErrNotOnChannel,
+    ErrUserOnChannel,
+    ErrNotRegistered,
+    ErrNeedMoreParams,
+    ErrAlreadyRegistered,
+    ErrPasswdMismatch,
+    ErrYoureBannedCreep,
+    ErrChannelIsFull,
+    ErrUnknownMode,
+    ErrInviteOnlyChan,
+    ErrBannedFromChan,
+    ErrBadChannelKey,
+    ErrBadChanMask,
+    ErrNoPrivileges,
+    ErrChanOPrivsNeeded,
+    ErrCantKillServer,
+    ErrNoOperHost,
+    ErrUmodeUnknownFlag,
+    ErrUsersDontMatch,
+    ErrHelpNotFound,
+    ErrInvalidKey,
+    RplStartTLS,
+    RplWhoIsSecure,
+    ErrStartTLSError,
+    ErrInvalidModeParam,
+    RplHelpStart,
+    RplHelpTxt,
+    RplEndOfHelp,
+    ErrNoPrivs,
+    RplLoggedIn,
+    RplLoggedOut,
+    ErrNickLocked,
+    RplSaslSuccess,
+    ErrSaslFail,
+    ErrSaslTooLong,
+    ErrSaslAborted,
+    ErrSaslAlready,
+    RplSaslMechs,
+}
+
+impl Numeric {
+    /// Returns `true` if the numeric falls in the 400-599 range reserved for
+    /// error replies.
+    ///
+    /// This is determined per-variant (matching the RFC's classification of
+    /// each numeric) rather than purely by range, since a handful of error
+    /// replies added by later extensions (e.g. `ErrNickLocked`, `ErrSaslFail`)
+    /// fall outside the 400-599 block reserved by the original RFC.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Numeric::ErrUnknownError
+                | Numeric::ErrNoSuchNick
+                | Numeric::ErrNoSuchServer
+                | Numeric::ErrNoSuchChannel
+                | Numeric::ErrCannotSendToChan
+                | Numeric::ErrTooManyChannels
+                | Numeric::ErrWasNoSuchNick
+                | Numeric::ErrNoOrigin
+                | Numeric::ErrNoRecipient
+                | Numeric::ErrNoTextToSend
+                | Numeric::ErrInputTooLong
+                | Numeric::ErrUnknownCommand
+                | Numeric::ErrNoMotd
+                | Numeric::ErrNoNicknameGiven
+                | Numeric::ErrErroneousNickname
+                | Numeric::ErrNicknameInUse
+                | Numeric::ErrNickCollision
+                | Numeric::ErrUserNotInChannel
+                | Numeric::ErrNotOnChannel
+                | Numeric::ErrUserOnChannel
+                | Numeric::ErrNotRegistered
+                | Numeric::ErrNeedMoreParams
+                | Numeric::ErrAlreadyRegistered
+                | Numeric::ErrPasswdMismatch
+                | Numeric::ErrYoureBannedCreep
+                | Numeric::ErrChannelIsFull
+                | Numeric::ErrUnknownMode
+                | Numeric::ErrInviteOnlyChan
+                | Numeric::ErrBannedFromChan
+                | Numeric::ErrBadChannelKey
+                | Numeric::ErrBadChanMask
+                | Numeric::ErrNoPrivileges
+                | Numeric::ErrChanOPrivsNeeded
+                | Numeric::ErrCantKillServer
+                | Numeric::ErrNoOperHost
+                | Numeric::ErrUmodeUnknownFlag
+                | Numeric::ErrUsersDontMatch
+                | Numeric::ErrHelpNotFound
+                | Numeric::ErrInvalidKey
+                | Numeric::ErrStartTLSError
+                | Numeric::ErrInvalidModeParam
+                | Numeric::ErrNoPrivs
+                | Numeric::ErrNickLocked
+                | Numeric::ErrSaslFail
+                | Numeric::ErrSaslTooLong
+                | Numeric::ErrSaslAborted
+                | Numeric::ErrSaslAlready
+        )
+    }
+}
+
+impl TryFrom<u16> for Numeric {
+    type Error = ParseNumericError;
+
+    fn try_from(code: u16) -> Result<Numeric, ParseNumericError> {
+        match code {
+            1 => Ok(Numeric::RplWelcome),
+            2 => Ok(Numeric::RplYourHost),
+            3 => Ok(Numeric::RplCreated),
+            4 => Ok(Numeric::RplMyInfo),
+            5 => Ok(Numeric::RplISupport),
+            10 => Ok(Numeric::RplBounce),
+            105 => Ok(Numeric::RplRemoteISupport),
+            212 => Ok(Numeric::RplStatsCommands),
+            219 => Ok(Numeric::RplEndOfStats),
+            221 => Ok(Numeric::RplUModeIs),
+            242 => Ok(Numeric::RplStatsUptime),
+            251 => Ok(Numeric::RplLuserClient),
+            252 => Ok(Numeric::RplLuserOp),
+            253 => Ok(Numeric::RplLuserUnknown),
+            254 => Ok(Numeric::RplLuserChannels),
+            255 => Ok(Numeric::RplLuserMe),
+            256 => Ok(Numeric::RplAdminMe),
+            257 => Ok(Numeric::RplAdminLoc1),
+            258 => Ok(Numeric::RplAdminLoc2),
+            259 => Ok(Numeric::RplAdminEmail),
+            263 => Ok(Numeric::RplTryAgain),
+            265 => Ok(Numeric::RplLocalUsers),
+            266 => Ok(Numeric::RplGlobalUsers),
+            276 => Ok(Numeric::RplWhoIsCertFP),
+            300 => Ok(Numeric::RplNone),
+            301 => Ok(Numeric::RplAway),
+            302 => Ok(Numeric::RplUserHostRpl),
+            305 => Ok(Numeric::RplUnAway),
+            306 => Ok(Numeric::RplNowAway),
+            307 => Ok(Numeric::RplWhoIsRegNick),
+            311 => Ok(Numeric::RplWhoIsUser),
+            312 => Ok(Numeric::RplWhoIsServer),
+            313 => Ok(Numeric::RplWhoIsOperator),
+            314 => Ok(Numeric::RplWhoWasUser),
+            315 => Ok(Numeric::RplEndOfWho),
+            317 => Ok(Numeric::RplWhoIsIdle),
+            318 => Ok(Numeric::RplEndOfWhoIs),
+            319 => Ok(Numeric::RplWhoIsChannels),
+            320 => Ok(Numeric::RplWhoIsSpecial),
+            321 => Ok(Numeric::RplListStart),
+            322 => Ok(Numeric::RplList),
+            323 => Ok(Numeric::RplListEnd),
+            324 => Ok(Numeric::RplChannelModeIs),
+            329 => Ok(Numeric::RplCreationTime),
+            330 => Ok(Numeric::RplWhoIsAccount),
+            331 => Ok(Numeric::RplNoTopic),
+            332 => Ok(Numeric::RplTopic),
+            333 => Ok(Numeric::RplTopicWhoTime),
+            336 => Ok(Numeric::RplInviteList),
+            337 => Ok(Numeric::RplEndOfInviteList),
+            338 => Ok(Numeric::RplWhoIsActually),
+            341 => Ok(Numeric::RplInviting),
+            346 => Ok(Numeric::RplInvExList),
+            347 => Ok(Numeric::RplEndOfInvExList),
+            348 => Ok(Numeric::RplExceptList),
+            349 => Ok(Numeric::RplEndOfExceptList),
+            351 => Ok(Numeric::RplVersion),
+            352 => Ok(Numeric::RplWhoReply),
+            353 => Ok(Numeric::RplNamReply),
+            364 => Ok(Numeric::RplLinks),
+            365 => Ok(Numeric::RplEndOfLinks),
+            366 => Ok(Numeric::RplEndOfNames),
+            367 => Ok(Numeric::RplBanList),
+            368 => Ok(Numeric::RplEndOfBanList),
+            369 => Ok(Numeric::RplEndOfWhoWas),
+            371 => Ok(Numeric::RplInfo),
+            372 => Ok(Numeric::RplMotd),
+            374 => Ok(Numeric::RplEndOfInfo),
+            375 => Ok(Numeric::RplMotdStart),
+            376 => Ok(Numeric::RplEndOfMotd),
+            378 => Ok(Numeric::RplWhoIsHost),
+            379 => Ok(Numeric::RplWhoIsModes),
+            381 => Ok(Numeric::RplYoureOper),
+            382 => Ok(Numeric::RplRehashing),
+            391 => Ok(Numeric::RplTime),
+            400 => Ok(Numeric::ErrUnknownError),
+            401 => Ok(Numeric::ErrNoSuchNick),
+            402 => Ok(Numeric::ErrNoSuchServer),
+            403 => Ok(Numeric::ErrNoSuchChannel),
+            404 => Ok(Numeric::ErrCannotSendToChan),
+            405 => Ok(Numeric::ErrTooManyChannels),
+            406 => Ok(Numeric::ErrWasNoSuchNick),
+            409 => Ok(Numeric::ErrNoOrigin),
+            411 => Ok(Numeric::ErrNoRecipient),
+            412 => Ok(Numeric::ErrNoTextToSend),
+            417 => Ok(Numeric::ErrInputTooLong),
+            421 => Ok(Numeric::ErrUnknownCommand),
+            422 => Ok(Numeric::ErrNoMotd),
+            431 => Ok(Numeric::ErrNoNicknameGiven),
+            432 => Ok(Numeric::ErrErroneousNickname),
+            433 => Ok(Numeric::ErrNicknameInUse),
+            436 => Ok(Numeric::ErrNickCollision),
+            441 => Ok(Numeric::ErrUserNotInChannel),
+            442 => Ok(Numeric::ErrNotOnChannel),
+            443 => Ok(Numeric::ErrUserOnChannel),
+            451 => Ok(Numeric::ErrNotRegistered),
+            461 => Ok(Numeric::ErrNeedMoreParams),
+            462 => Ok(Numeric::ErrAlreadyRegistered),
+            464 => Ok(Numeric::ErrPasswdMismatch),
+            465 => Ok(Numeric::ErrYoureBannedCreep),
+            471 => Ok(Numeric::ErrChannelIsFull),
+            472 => Ok(Numeric::ErrUnknownMode),
+            473 => Ok(Numeric::ErrInviteOnlyChan),
+            474 => Ok(Numeric::ErrBannedFromChan),
+            475 => Ok(Numeric::ErrBadChannelKey),
+            476 => Ok(Numeric::ErrBadChanMask),
+            481 => Ok(Numeric::ErrNoPrivileges),
+            482 => Ok(Numeric::ErrChanOPrivsNeeded),
+            483 => Ok(Numeric::ErrCantKillServer),
+            491 => Ok(Numeric::ErrNoOperHost),
+            501 => Ok(Numeric::ErrUmodeUnknownFlag),
+            502 => Ok(Numeric::ErrUsersDontMatch),
+            524 => Ok(Numeric::ErrHelpNotFound),
+            525 => Ok(Numeric::ErrInvalidKey),
+            670 => Ok(Numeric::RplStartTLS),
+            671 => Ok(Numeric::RplWhoIsSecure),
+            691 => Ok(Numeric::ErrStartTLSError),
+            696 => Ok(Numeric::ErrInvalidModeParam),
+            704 => Ok(Numeric::RplHelpStart),
+            705 => Ok(Numeric::RplHelpTxt),
+            706 => Ok(Numeric::RplEndOfHelp),
+            723 => Ok(Numeric::ErrNoPrivs),
+            900 => Ok(Numeric::RplLoggedIn),
+            901 => Ok(Numeric::RplLoggedOut),
+            902 => Ok(Numeric::ErrNickLocked),
+            903 => Ok(Numeric::RplSaslSuccess),
+            904 => Ok(Numeric::ErrSaslFail),
+            905 => Ok(Numeric::ErrSaslTooLong),
+            906 => Ok(Numeric::ErrSaslAborted),
+            907 => Ok(Numeric::ErrSaslAlready),
+            908 => Ok(Numeric::RplSaslMechs),
+            _ => Err(ParseNumericError),
+        }
+    }
+}
+
+impl From<Numeric> for u16 {
+    fn from(value: Numeric) -> u16 {
+        match value {
+            Numeric::RplWelcome => 1,
+            Numeric::RplYourHost => 2,
+            Numeric::RplCreated => 3,
+            Numeric::RplMyInfo => 4,
+            Numeric::RplISupport => 5,
+            Numeric::RplBounce => 10,
+            Numeric::RplRemoteISupport => 105,
+            Numeric::RplStatsCommands => 212,
+            Numeric::RplEndOfStats => 219,
+            Numeric::RplUModeIs => 221,
+            Numeric::RplStatsUptime => 242,
+            Numeric::RplLuserClient => 251,
+            Numeric::RplLuserOp => 252,
+            Numeric::RplLuserUnknown => 253,
+            Numeric::RplLuserChannels => 254,
+            Numeric::RplLuserMe => 255,
+            Numeric::RplAdminMe => 256,
+            Numeric::RplAdminLoc1 => 257,
+            Numeric::RplAdminLoc2 => 258,
+            Numeric::RplAdminEmail => 259,
+            Numeric::RplTryAgain => 263,
+            Numeric::RplLocalUsers => 265,
+            Numeric::RplGlobalUsers => 266,
+            Numeric::RplWhoIsCertFP => 276,
+            Numeric::RplNone => 300,
+            Numeric::RplAway => 301,
+            Numeric::RplUserHostRpl => 302,
+            Numeric::RplUnAway => 305,
+            Numeric::RplNowAway => 306,
+            Numeric::RplWhoIsRegNick => 307,
+            Numeric::RplWhoIsUser => 311,
+            Numeric::RplWhoIsServer => 312,
+            Numeric::RplWhoIsOperator => 313,
+            Numeric::RplWhoWasUser => 314,
+            Numeric::RplEndOfWho => 315,
+            Numeric::RplWhoIsIdle => 317,
+            Numeric::RplEndOfWhoIs => 318,
+            Numeric::RplWhoIsChannels => 319,
+            Numeric::RplWhoIsSpecial => 320,
+            Numeric::RplListStart => 321,
+            Numeric::RplList => 322,
+            Numeric::RplListEnd => 323,
+            Numeric::RplChannelModeIs => 324,
+            Numeric::RplCreationTime => 329,
+            Numeric::RplWhoIsAccount => 330,
+            Numeric::RplNoTopic => 331,
+            Numeric::RplTopic => 332,
+            Numeric::RplTopicWhoTime => 333,
+            Numeric::RplInviteList => 336,
+            Numeric::RplEndOfInviteList => 337,
+            Numeric::RplWhoIsActually => 338,
+            Numeric::RplInviting => 341,
+            Numeric::RplInvExList => 346,
+            Numeric::RplEndOfInvExList => 347,
+            Numeric::RplExceptList => 348,
+            Numeric::RplEndOfExceptList => 349,
+            Numeric::RplVersion => 351,
+            Numeric::RplWhoReply => 352,
+            Numeric::RplNamReply => 353,
+            Numeric::RplLinks => 364,
+            Numeric::RplEndOfLinks => 365,
+            Numeric::RplEndOfNames => 366,
+            Numeric::RplBanList => 367,
+            Numeric::RplEndOfBanList => 368,
+            Numeric::RplEndOfWhoWas => 369,
+            Numeric::RplInfo => 371,
+            Numeric::RplMotd => 372,
+            Numeric::RplEndOfInfo => 374,
+            Numeric::RplMotdStart => 375,
+            Numeric::RplEndOfMotd => 376,
+            Numeric::RplWhoIsHost => 378,
+            Numeric::RplWhoIsModes => 379,
+            Numeric::RplYoureOper => 381,
+            Numeric::RplRehashing => 382,
+            Numeric::RplTime => 391,
+            Numeric::ErrUnknownError => 400,
+            Numeric::ErrNoSuchNick => 401,
+            Numeric::ErrNoSuchServer => 402,
+            Numeric::ErrNoSuchChannel => 403,
+            Numeric::ErrCannotSendToChan => 404,
+            Numeric::ErrTooManyChannels => 405,
+            Numeric::ErrWasNoSuchNick => 406,
+            Numeric::ErrNoOrigin => 409,
+            Numeric::ErrNoRecipient => 411,
+            Numeric::ErrNoTextToSend => 412,
+            Numeric::ErrInputTooLong => 417,
+            Numeric::ErrUnknownCommand => 421,
+            Numeric::ErrNoMotd => 422,
+            Numeric::ErrNoNicknameGiven => 431,
+            Numeric::ErrErroneousNickname => 432,
+            Numeric::ErrNicknameInUse => 433,
+            Numeric::ErrNickCollision => 436,
+            Numeric::ErrUserNotInChannel => 441,
+            Numeric::ErrNotOnChannel => 442,
+            Numeric::ErrUserOnChannel => 443,
+            Numeric::ErrNotRegistered => 451,
+            Numeric::ErrNeedMoreParams => 461,
+            Numeric::ErrAlreadyRegistered => 462,
+            Numeric::ErrPasswdMismatch => 464,
+            Numeric::ErrYoureBannedCreep => 465,
+            Numeric::ErrChannelIsFull => 471,
+            Numeric::ErrUnknownMode => 472,
+            Numeric::ErrInviteOnlyChan => 473,
+            Numeric::ErrBannedFromChan => 474,
+            Numeric::ErrBadChannelKey => 475,
+            Numeric::ErrBadChanMask => 476,
+            Numeric::ErrNoPrivileges => 481,
+            Numeric::ErrChanOPrivsNeeded => 482,
+            Numeric::ErrCantKillServer => 483,
+            Numeric::ErrNoOperHost => 491,
+            Numeric::ErrUmodeUnknownFlag => 501,
+            Numeric::ErrUsersDontMatch => 502,
+            Numeric::ErrHelpNotFound => 524,
+            Numeric::ErrInvalidKey => 525,
+            Numeric::RplStartTLS => 670,
+            Numeric::RplWhoIsSecure => 671,
+            Numeric::ErrStartTLSError => 691,
+            Numeric::ErrInvalidModeParam => 696,
+            Numeric::RplHelpStart => 704,
+            Numeric::RplHelpTxt => 705,
+            Numeric::RplEndOfHelp => 706,
+            Numeric::ErrNoPrivs => 723,
+            Numeric::RplLoggedIn => 900,
+            Numeric::RplLoggedOut => 901,
+            Numeric::ErrNickLocked => 902,
+            Numeric::RplSaslSuccess => 903,
+            Numeric::ErrSaslFail => 904,
+            Numeric::ErrSaslTooLong => 905,
+            Numeric::ErrSaslAborted => 906,
+            Numeric::ErrSaslAlready => 907,
+            Numeric::RplSaslMechs => 908,
+        }
+    }
+}
+
+/// Error returned when a numeric code has no corresponding [`Numeric`] variant
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error("unrecognized numeric reply code")]
+pub struct ParseNumericError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_code_round_trips() {
+        let numeric = Numeric::try_from(1).unwrap();
+        assert_eq!(numeric, Numeric::RplWelcome);
+        assert_eq!(u16::from(numeric), 1);
+    }
+
+    #[test]
+    fn unknown_code_is_err() {
+        assert_eq!(Numeric::try_from(999), Err(ParseNumericError));
+    }
+
+    #[test]
+    fn is_error_reports_true_for_error_replies() {
+        assert!(!Numeric::try_from(376).unwrap().is_error());
+        assert!(Numeric::try_from(401).unwrap().is_error());
+        assert!(Numeric::try_from(525).unwrap().is_error());
+        assert!(!Numeric::try_from(908).unwrap().is_error());
+    }
+
+    #[test]
+    fn is_error_reports_true_outside_the_400_to_599_range() {
+        assert!(Numeric::try_from(902).unwrap().is_error());
+        assert!(Numeric::try_from(904).unwrap().is_error());
+    }
+}
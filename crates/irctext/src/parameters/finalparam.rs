@@ -13,7 +13,7 @@ impl From<MedialParam> for FinalParam {
     }
 }
 
-fn validate(s: &str) -> Result<(), ParseFinalParamError> {
+pub(crate) fn validate(s: &str) -> Result<(), ParseFinalParamError> {
     if s.contains(['\0', '\r', '\n']) {
         Err(ParseFinalParamError)
     } else {
@@ -6,7 +6,7 @@ pub struct MedialParam(String);
 validstr!(MedialParam, ParseMedialParamError, validate);
 strserde!(MedialParam, "an IRC middle parameter");
 
-fn validate(s: &str) -> Result<(), ParseMedialParamError> {
+pub(crate) fn validate(s: &str) -> Result<(), ParseMedialParamError> {
     if s.is_empty() {
         Err(ParseMedialParamError::Empty)
     } else if s.starts_with(':') {
@@ -117,214 +117,156 @@ impl TryFrom<String> for ParameterList {
     }
 }
 
-impl TryFrom<ParameterList> for () {
-    type Error = ParameterListSizeError;
-
-    fn try_from(params: ParameterList) -> Result<(), ParameterListSizeError> {
-        if params.is_empty() {
-            Ok(())
-        } else {
-            Err(ParameterListSizeError::Exact {
-                required: 0,
-                received: params.len(),
-            })
-        }
-    }
+// A `ParameterList` is serialized as an object with separate `medial` and
+// `final` fields (rather than as a flat array, or reusing `Display`) so that
+// the medial/trailing distinction — which affects how a parameter with
+// spaces or a leading colon round-trips back through `Display` — survives
+// a trip through JSON.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ParameterListRepr {
+    medial: Vec<MedialParam>,
+    #[serde(rename = "final", default, skip_serializing_if = "Option::is_none")]
+    finalp: Option<FinalParam>,
 }
 
-impl TryFrom<ParameterList> for (FinalParam,) {
-    type Error = ParameterListSizeError;
-
-    fn try_from(mut params: ParameterList) -> Result<(FinalParam,), ParameterListSizeError> {
-        if params.len() == 1 {
-            let p = params
-                .medial
-                .pop()
-                .map(FinalParam::from)
-                .or(params.finalp)
-                .expect("There should be something to unwrap when len is 1");
-            Ok((p,))
-        } else {
-            Err(ParameterListSizeError::Exact {
-                required: 1,
-                received: params.len(),
-            })
-        }
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for ParameterList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serde::Serialize::serialize(
+            &ParameterListRepr {
+                medial: self.medial.clone(),
+                finalp: self.finalp.clone(),
+            },
+            serializer,
+        )
     }
 }
 
-impl TryFrom<ParameterList> for (Option<FinalParam>,) {
-    type Error = ParameterListSizeError;
-
-    fn try_from(params: ParameterList) -> Result<(Option<FinalParam>,), ParameterListSizeError> {
-        match (params.len(), params.finalp.is_some()) {
-            (1, false) => Ok((params.medial.into_iter().next().map(FinalParam::from),)),
-            (0, _) => Ok((params.finalp,)),
-            _ => Err(ParameterListSizeError::Range {
-                min_required: 0,
-                max_required: 1,
-                received: params.len(),
-            }),
-        }
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for ParameterList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let repr = <ParameterListRepr as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(ParameterList {
+            medial: repr.medial,
+            finalp: repr.finalp,
+        })
     }
 }
 
-impl TryFrom<ParameterList> for (MedialParam, FinalParam) {
+impl TryFrom<ParameterList> for () {
     type Error = ParameterListSizeError;
 
-    fn try_from(
-        params: ParameterList,
-    ) -> Result<(MedialParam, FinalParam), ParameterListSizeError> {
-        if params.len() == 2 {
-            let mut medials = params.medial.into_iter();
-            let p1 = medials
-                .next()
-                .expect("First element should exist when len is 2");
-            let p2 = medials
-                .next()
-                .map(FinalParam::from)
-                .or(params.finalp)
-                .expect("Second element should exist when len is 2");
-            Ok((p1, p2))
+    fn try_from(params: ParameterList) -> Result<(), ParameterListSizeError> {
+        if params.is_empty() {
+            Ok(())
         } else {
             Err(ParameterListSizeError::Exact {
-                required: 2,
+                required: 0,
                 received: params.len(),
             })
         }
     }
 }
 
-impl TryFrom<ParameterList> for (MedialParam, Option<FinalParam>) {
-    type Error = ParameterListSizeError;
-
-    fn try_from(
-        params: ParameterList,
-    ) -> Result<(MedialParam, Option<FinalParam>), ParameterListSizeError> {
-        match (params.len(), params.finalp.is_some()) {
-            (2, false) => {
-                let mut medials = params.medial.into_iter();
-                let p1 = medials
-                    .next()
-                    .expect("First element should exist when len is 2");
-                let p2 = medials.next().map(FinalParam::from);
-                Ok((p1, p2))
-            }
-            (1, _) => {
-                let mut medials = params.medial.into_iter();
-                let p1 = medials
-                    .next()
-                    .expect("First element should exist when len is 1");
-                let p2 = params.finalp;
-                Ok((p1, p2))
+// Generates the three `TryFrom<ParameterList>` tuple shapes for a given
+// arity: `$lead` is the (possibly empty) list of leading, always-`MedialParam`
+// slots, and `$last` names the trailing slot, which varies by shape
+// (required `FinalParam`, optional `FinalParam`, or plain `MedialParam`).
+// This replaces what used to be a hand-written impl per arity/shape.
+macro_rules! impl_tryfrom_paramlist {
+    (@medial_ty $slot:ident) => {
+        MedialParam
+    };
+    (@count) => {
+        0usize
+    };
+    (@count $head:ident $(, $tail:ident)*) => {
+        1usize + impl_tryfrom_paramlist!(@count $($tail),*)
+    };
+    ($($lead:ident),* ; $last:ident) => {
+        impl TryFrom<ParameterList> for ($(impl_tryfrom_paramlist!(@medial_ty $lead),)* FinalParam,) {
+            type Error = ParameterListSizeError;
+
+            fn try_from(params: ParameterList) -> Result<Self, ParameterListSizeError> {
+                let required = impl_tryfrom_paramlist!(@count $($lead),*) + 1;
+                if params.len() == required {
+                    let mut medials = params.medial.into_iter();
+                    $(let $lead = medials.next().expect("slot should exist when len matches");)*
+                    let $last = medials
+                        .next()
+                        .map(FinalParam::from)
+                        .or(params.finalp)
+                        .expect("slot should exist when len matches");
+                    Ok(($($lead,)* $last,))
+                } else {
+                    Err(ParameterListSizeError::Exact { required, received: params.len() })
+                }
             }
-            _ => Err(ParameterListSizeError::Range {
-                min_required: 1,
-                max_required: 2,
-                received: params.len(),
-            }),
         }
-    }
-}
-
-impl TryFrom<ParameterList> for (MedialParam, MedialParam, Option<FinalParam>) {
-    type Error = ParameterListSizeError;
 
-    fn try_from(
-        params: ParameterList,
-    ) -> Result<(MedialParam, MedialParam, Option<FinalParam>), ParameterListSizeError> {
-        match (params.len(), params.finalp.is_some()) {
-            (3, false) => {
-                let mut medials = params.medial.into_iter();
-                let p1 = medials
-                    .next()
-                    .expect("First element should exist when len is 3");
-                let p2 = medials
-                    .next()
-                    .expect("Second element should exist when len is 3");
-                let p3 = medials.next().map(FinalParam::from);
-                Ok((p1, p2, p3))
-            }
-            (2, _) => {
-                let mut medials = params.medial.into_iter();
-                let p1 = medials
-                    .next()
-                    .expect("First element should exist when len is 2");
-                let p2 = medials
-                    .next()
-                    .expect("Second element should exist when len is 2");
-                let p3 = params.finalp;
-                Ok((p1, p2, p3))
+        impl TryFrom<ParameterList> for ($(impl_tryfrom_paramlist!(@medial_ty $lead),)* Option<FinalParam>,) {
+            type Error = ParameterListSizeError;
+
+            fn try_from(params: ParameterList) -> Result<Self, ParameterListSizeError> {
+                let min_required = impl_tryfrom_paramlist!(@count $($lead),*);
+                let max_required = min_required + 1;
+                match (params.len(), params.finalp.is_some()) {
+                    (n, false) if n == max_required => {
+                        let mut medials = params.medial.into_iter();
+                        $(let $lead = medials.next().expect("slot should exist when len matches");)*
+                        let $last = medials.next().map(FinalParam::from);
+                        Ok(($($lead,)* $last,))
+                    }
+                    (n, _) if n == min_required => {
+                        let mut medials = params.medial.into_iter();
+                        $(let $lead = medials.next().expect("slot should exist when len matches");)*
+                        let $last = params.finalp;
+                        Ok(($($lead,)* $last,))
+                    }
+                    (received, _) => Err(ParameterListSizeError::Range { min_required, max_required, received }),
+                }
             }
-            _ => Err(ParameterListSizeError::Range {
-                min_required: 2,
-                max_required: 3,
-                received: params.len(),
-            }),
         }
-    }
-}
-
-impl TryFrom<ParameterList> for (MedialParam, MedialParam, FinalParam) {
-    type Error = ParameterListSizeError;
 
-    fn try_from(
-        params: ParameterList,
-    ) -> Result<(MedialParam, MedialParam, FinalParam), ParameterListSizeError> {
-        if params.len() == 3 {
-            let mut medials = params.medial.into_iter();
-            let p1 = medials
-                .next()
-                .expect("First element should exist when len is 3");
-            let p2 = medials
-                .next()
-                .expect("Second element should exist when len is 3");
-            let p3 = medials
-                .next()
-                .map(FinalParam::from)
-                .or(params.finalp)
-                .expect("Third element should exist when len is 3");
-            Ok((p1, p2, p3))
-        } else {
-            Err(ParameterListSizeError::Exact {
-                required: 3,
-                received: params.len(),
-            })
+        impl TryFrom<ParameterList> for ($(impl_tryfrom_paramlist!(@medial_ty $lead),)* MedialParam,) {
+            type Error = ParameterListSizeError;
+
+            fn try_from(params: ParameterList) -> Result<Self, ParameterListSizeError> {
+                let required = impl_tryfrom_paramlist!(@count $($lead),*) + 1;
+                if params.len() == required && params.finalp.is_none() {
+                    let mut medials = params.medial.into_iter();
+                    $(let $lead = medials.next().expect("slot should exist when len matches");)*
+                    let $last = medials.next().expect("slot should exist when len matches");
+                    Ok(($($lead,)* $last,))
+                } else {
+                    Err(ParameterListSizeError::Exact { required, received: params.len() })
+                }
+            }
         }
-    }
+    };
 }
 
-impl TryFrom<ParameterList> for (MedialParam, MedialParam, MedialParam, FinalParam) {
-    type Error = ParameterListSizeError;
-
-    fn try_from(
-        params: ParameterList,
-    ) -> Result<(MedialParam, MedialParam, MedialParam, FinalParam), ParameterListSizeError> {
-        if params.len() == 4 {
-            let mut medials = params.medial.into_iter();
-            let p1 = medials
-                .next()
-                .expect("First element should exist when len is 4");
-            let p2 = medials
-                .next()
-                .expect("Second element should exist when len is 4");
-            let p3 = medials
-                .next()
-                .expect("Third element should exist when len is 4");
-            let p4 = medials
-                .next()
-                .map(FinalParam::from)
-                .or(params.finalp)
-                .expect("Fourth element should exist when len is 4");
-            Ok((p1, p2, p3, p4))
-        } else {
-            Err(ParameterListSizeError::Exact {
-                required: 4,
-                received: params.len(),
-            })
-        }
-    }
-}
+impl_tryfrom_paramlist!(; p1);
+impl_tryfrom_paramlist!(p1; p2);
+impl_tryfrom_paramlist!(p1, p2; p3);
+impl_tryfrom_paramlist!(p1, p2, p3; p4);
+impl_tryfrom_paramlist!(p1, p2, p3, p4; p5);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5; p6);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6; p7);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6, p7; p8);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6, p7, p8; p9);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6, p7, p8, p9; p10);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6, p7, p8, p9, p10; p11);
+impl_tryfrom_paramlist!(p1, p2, p3, p4, p5, p6, p7, p8, p9, p10, p11; p12);
 
 #[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
 pub enum ParseParameterListError {
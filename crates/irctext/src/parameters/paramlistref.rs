@@ -0,0 +1,107 @@
+use super::{
+    paramstrref::ParamStrRef, FinalParam, MedialParam, ParameterList, ParseParameterListError,
+};
+use crate::util::split_word;
+use std::cmp::Ordering;
+
+/// A borrowed view of a [`ParameterList`], parsed out of a `&'a str` without
+/// allocating: each medial word and the final trailer are kept as slices
+/// into the original line rather than owned [`MedialParam`]/[`FinalParam`]
+/// values.
+///
+/// This is meant for the hot path of inspecting an inbound line (routing by
+/// verb, counting args, matching against `[&str; N]`) without paying for a
+/// `Vec<MedialParam>` that will just be discarded. Call [`to_owned()`][Self::to_owned]
+/// to promote into an owned [`ParameterList`] once you know you need to keep
+/// it around.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParameterListRef<'a> {
+    medial: Vec<&'a str>,
+    finalp: Option<&'a str>,
+}
+
+impl<'a> ParameterListRef<'a> {
+    pub fn parse(s: &'a str) -> Result<ParameterListRef<'a>, ParseParameterListError> {
+        let mut s = s;
+        let mut medial = Vec::new();
+        let mut finalp = None;
+        while !s.is_empty() {
+            if let Some(trail) = s.strip_prefix(':') {
+                super::finalparam::validate(trail)?;
+                finalp = Some(trail);
+                s = "";
+            } else {
+                let (param, rest) = split_word(s);
+                super::medialparam::validate(param)?;
+                medial.push(param);
+                s = rest;
+            }
+        }
+        Ok(ParameterListRef { medial, finalp })
+    }
+
+    pub fn len(&self) -> usize {
+        self.medial
+            .len()
+            .saturating_add(usize::from(self.finalp.is_some()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.medial.is_empty() && self.finalp.is_none()
+    }
+
+    pub fn get(&self, index: usize) -> Option<ParamStrRef<'a>> {
+        match index.cmp(&self.medial.len()) {
+            Ordering::Less => self.medial.get(index).copied().map(ParamStrRef::Medial),
+            Ordering::Equal => self.finalp.map(ParamStrRef::Final),
+            Ordering::Greater => None,
+        }
+    }
+
+    pub fn last(&self) -> Option<ParamStrRef<'a>> {
+        if let Some(p) = self.finalp {
+            Some(ParamStrRef::Final(p))
+        } else {
+            self.medial.last().copied().map(ParamStrRef::Medial)
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ParamStrRef<'a>> + '_ {
+        self.medial
+            .iter()
+            .copied()
+            .map(ParamStrRef::Medial)
+            .chain(self.finalp.map(ParamStrRef::Final))
+    }
+
+    /// Promote this borrowed view into an owned [`ParameterList`].
+    #[expect(clippy::missing_panics_doc)]
+    pub fn to_owned(&self) -> ParameterList {
+        let mut builder = ParameterList::builder();
+        for &p in &self.medial {
+            builder.push_medial(
+                MedialParam::try_from(p.to_owned())
+                    .expect("already-validated medial parameter should still be valid"),
+            );
+        }
+        match self.finalp {
+            Some(p) => builder.with_final(
+                FinalParam::try_from(p.to_owned())
+                    .expect("already-validated final parameter should still be valid"),
+            ),
+            None => builder.finish(),
+        }
+    }
+}
+
+impl<const N: usize> PartialEq<[&str; N]> for ParameterListRef<'_> {
+    fn eq(&self, other: &[&str; N]) -> bool {
+        N == self.len() && std::iter::zip(self.iter(), other).all(|(param, &s)| param == s)
+    }
+}
+
+impl<const N: usize> PartialEq<[&str; N]> for &ParameterListRef<'_> {
+    fn eq(&self, other: &[&str; N]) -> bool {
+        *self == other
+    }
+}
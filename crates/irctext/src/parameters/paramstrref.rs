@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// A single parameter yielded by [`ParameterListRef`][super::ParameterListRef],
+/// borrowing directly from the line it was parsed out of rather than from an
+/// owned [`MedialParam`][super::MedialParam]/[`FinalParam`][super::FinalParam],
+/// so no allocation is needed to read it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParamStrRef<'a> {
+    Medial(&'a str),
+    Final(&'a str),
+}
+
+impl<'a> ParamStrRef<'a> {
+    pub fn is_medial(&self) -> bool {
+        matches!(self, ParamStrRef::Medial(_))
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(self, ParamStrRef::Final(_))
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        match *self {
+            ParamStrRef::Medial(s) | ParamStrRef::Final(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for ParamStrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl AsRef<str> for ParamStrRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<String> for ParamStrRef<'_> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for ParamStrRef<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for ParamStrRef<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl From<ParamStrRef<'_>> for String {
+    fn from(value: ParamStrRef<'_>) -> String {
+        value.as_str().to_owned()
+    }
+}
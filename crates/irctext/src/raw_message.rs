@@ -1,20 +1,45 @@
 use crate::util::split_word;
 use crate::{
-    Command, ParameterList, ParseCommandError, ParseParameterListError, ParseSourceError, Source,
-    TryFromStringError,
+    Command, MessageTags, ParameterList, ParseCommandError, ParseMessageTagsError,
+    ParseParameterListError, ParseSourceError, Source, TryFromStringError,
 };
 use std::fmt;
 use thiserror::Error;
 
+/// A parsed-but-undecoded IRC line.  `RawMessage` never fails to convert a
+/// recognized-but-not-yet-understood command or parameter shape — unlike
+/// [`Message`](crate::Message), which requires the command and parameters to
+/// decode into a known [`ClientMessage`](crate::clientmsgs)/[`Reply`](crate::Reply)
+/// — which makes it the right level to serialize for a logging pipeline or
+/// bridge that wants to emit *every* line as a structured event, including
+/// ones this library doesn't otherwise understand.  With the `serde`
+/// feature enabled, `RawMessage` (de)serializes to/from a JSON object with
+/// `tags`, `source`, `command`, and `params` fields, the last distinguishing
+/// medial from trailing parameters so `Display`/`TryFrom<String>` can
+/// reproduce the exact wire form after a round trip through JSON.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawMessage {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tags: Option<MessageTags>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub source: Option<Source>,
     pub command: Command,
+    #[cfg_attr(feature = "serde", serde(rename = "params"))]
     pub parameters: ParameterList,
 }
 
 impl fmt::Display for RawMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(tags) = self.tags.as_ref() {
+            write!(f, "@{tags} ")?;
+        }
         if let Some(source) = self.source.as_ref() {
             write!(f, ":{source} ")?;
         }
@@ -37,6 +62,13 @@ impl std::str::FromStr for RawMessage {
     fn from_str(s: &str) -> Result<RawMessage, ParseRawMessageError> {
         let mut s = s.strip_suffix('\n').unwrap_or(s);
         s = s.strip_suffix('\r').unwrap_or(s);
+        let tags = if let Some(s2) = s.strip_prefix('@') {
+            let (tags_str, rest) = split_word(s2);
+            s = rest;
+            Some(tags_str.parse::<MessageTags>()?)
+        } else {
+            None
+        };
         let source = if let Some(s2) = s.strip_prefix(':') {
             let (source_str, rest) = split_word(s2);
             s = rest;
@@ -48,6 +80,7 @@ impl std::str::FromStr for RawMessage {
         let command = cmd_str.parse::<Command>()?;
         let parameters = params.parse::<ParameterList>()?;
         Ok(RawMessage {
+            tags,
             source,
             command,
             parameters,
@@ -68,6 +101,8 @@ impl TryFrom<String> for RawMessage {
 
 #[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
 pub enum ParseRawMessageError {
+    #[error("invalid message tags")]
+    Tags(#[from] ParseMessageTagsError),
     #[error("invalid source prefix")]
     Source(#[from] ParseSourceError),
     #[error("invalid command")]
@@ -80,6 +115,7 @@ pub enum ParseRawMessageError {
 mod parser_tests {
     // Test cases from <https://github.com/ircdocs/parser-tests/blob/6b417e666de20ba677b14e0189213b3706009df6/tests/msg-split.yaml>
     use super::*;
+    use crate::ReplyCode;
     use assert_matches::assert_matches;
 
     #[test]
@@ -296,7 +332,7 @@ mod parser_tests {
             .parse::<RawMessage>()
             .unwrap();
         assert_eq!(msg.source.unwrap().to_string(), "gravel.mozilla.org");
-        assert_eq!(msg.command, Command::Reply(432));
+        assert_eq!(msg.command, Command::Reply(ReplyCode::from(432)));
         assert_eq!(
             msg.parameters,
             ["#momo", "Erroneous Nickname: Illegal characters"]
@@ -351,4 +387,69 @@ mod parser_tests {
             ["#channel", "+oo", "SomeUser", "AnotherUser"]
         );
     }
+
+    #[test]
+    fn with_tags() {
+        let msg = "@time=2023-04-05T12:00:00.000Z;msgid=abc :coolguy PRIVMSG #chan :hi there"
+            .parse::<RawMessage>()
+            .unwrap();
+        let tags = msg.tags.as_ref().unwrap();
+        assert_eq!(
+            tags.get("time").unwrap().unwrap().as_str(),
+            "2023-04-05T12:00:00.000Z"
+        );
+        assert_eq!(tags.get("msgid").unwrap().unwrap().as_str(), "abc");
+        assert_eq!(msg.source.unwrap().to_string(), "coolguy");
+        assert_matches!(msg.command, Command::Verb(v) => {
+            assert_eq!(v, "PRIVMSG");
+        });
+        assert_eq!(msg.parameters, ["#chan", "hi there"]);
+    }
+
+    #[test]
+    fn tags_roundtrip() {
+        let s = "@aaa=bbb;ccc;example.com/ddd=eee :nick!ident@host.com PRIVMSG me :Hello";
+        let msg = s.parse::<RawMessage>().unwrap();
+        assert_eq!(msg.to_string(), s);
+    }
+
+    #[test]
+    fn without_tags() {
+        let msg = ":src JOIN #chan".parse::<RawMessage>().unwrap();
+        assert!(msg.tags.is_none());
+    }
+
+    #[test]
+    fn tags_with_client_and_vendor_keys() {
+        // Test case from <https://github.com/ircdocs/parser-tests/blob/6b417e666de20ba677b14e0189213b3706009df6/tests/msg-tags.yaml>
+        let msg =
+            "@+example-client-tag=example-value;vendor.example.com/tag2 NOTICE #channel :Message"
+                .parse::<RawMessage>()
+                .unwrap();
+        let tags = msg.tags.as_ref().unwrap();
+        assert_eq!(
+            tags.get("+example-client-tag").unwrap().unwrap().as_str(),
+            "example-value"
+        );
+        assert_eq!(tags.get("vendor.example.com/tag2").unwrap(), None);
+        assert!(msg.source.is_none());
+    }
+
+    #[test]
+    fn tags_with_escaped_semicolons_and_spaces() {
+        let msg = r"@id=234AB\:\saB;rose :dan!d@localhost PRIVMSG #chan :Hey!"
+            .parse::<RawMessage>()
+            .unwrap();
+        let tags = msg.tags.as_ref().unwrap();
+        assert_eq!(tags.get("id").unwrap().unwrap().as_str(), "234AB; aB");
+        assert_eq!(tags.get("rose").unwrap(), None);
+        assert_eq!(msg.source.unwrap().to_string(), "dan!d@localhost");
+    }
+
+    #[test]
+    fn tags_roundtrip_with_client_tag() {
+        let s = "@+example-client-tag=example-value :nick!ident@host.com PRIVMSG me :Hello";
+        let msg = s.parse::<RawMessage>().unwrap();
+        assert_eq!(msg.to_string(), s);
+    }
 }
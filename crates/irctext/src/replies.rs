@@ -1,26 +1,86 @@
+//! Parsed typed replies do not offer per-field charset-fallback decoding.
+//! Every text-bearing accessor here (`Welcome::message`, `Topic::topic`,
+//! `Motd::message`, `WhoIsUser::realname`, `Away::message`, etc.) returns
+//! `&str` carved out of a [`Parameter`](crate::Parameter), which is itself
+//! `String`-backed: by the time a line reaches [`Reply::from_parts`], its
+//! bytes have already been decoded to UTF-8 (with Latin-1/`encoding_rs`
+//! fallback) by the network layer's line codec
+//! (`ircnet::codec::IrcLinesCodec`), so the original non-UTF-8 octets are
+//! gone and there is nothing left here for a second, per-field decode pass
+//! to recover. Charset selection therefore belongs at that layer, not this
+//! one; see [`MaybeUtf8`](crate::MaybeUtf8) for the lower-level byte-preserving
+//! representation the codec's fallback is built on.
+
 use crate::types::{
-    Channel, ChannelStatus, ISupportParam, ModeString, ModeTarget, MsgTarget, Nickname,
-    ParseChannelError, ParseChannelStatusError, ParseISupportParamError, ParseModeStringError,
-    ParseModeTargetError, ParseMsgTargetError, ParseNicknameError, ParseReplyTargetError,
-    ParseUserHostReplyError, ParseUsernameError, ParseWhoFlagsError, ReplyTarget, UserHostReply,
-    Username, WhoFlags,
+    Channel, ChanModes, ChannelStatus, ISupportParam, ModeChange, ModeString, ModeTarget,
+    MsgTarget, Nickname, ParseChannelError, ParseChannelStatusError, ParseISupportParamError,
+    ParseModeStringError, ParseModeTargetError, ParseMsgTargetError, ParseNicknameError,
+    ParseReplyTargetError, ParseUserHostReplyError, ParseUsernameError, ParseWhoFlagsError,
+    PrefixTable, ReplyTarget, ResolveModeStringError, UserHostReply, Username, WhoFlags,
 };
-use crate::util::{pop_channel_membership, split_spaces, split_word};
+use crate::util::{pop_channel_membership, pop_channel_memberships, split_spaces, split_word};
 use crate::{
-    ClientSource, Message, ParameterList, ParseClientSourceError, ParseVerbError, Payload,
-    RawMessage, TryFromStringError, Verb,
+    ClientSource, Command, Message, Parameter, ParameterList, ParseClientSourceError,
+    ParseFinalParamError, ParseVerbError, Payload, RawMessage, TryFromStringError, Verb,
 };
+use bytes::BytesMut;
 use enum_dispatch::enum_dispatch;
+use std::fmt;
 use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use url::Host;
 
+/// Common accessors every parsed reply type in this module exposes. Each
+/// implementor is also paired with a `new()` constructor (taking the same
+/// typed fields its accessors return) that assembles a wire-correct
+/// [`ParameterList`], so the read direction this trait covers and the write
+/// direction needed to have a bouncer or test server emit these numerics
+/// share one set of types. [`encode`](ReplyParts::encode) covers the same
+/// write direction without the intermediate `String`/[`RawMessage`](crate::RawMessage)
+/// allocation, for callers on a hot send path.
 #[enum_dispatch]
 pub trait ReplyParts {
     fn code(&self) -> u16;
     fn parameters(&self) -> &ParameterList;
     fn is_error(&self) -> bool;
     fn into_parts(self) -> (u16, ParameterList);
+
+    /// The number of bytes [`encode`](ReplyParts::encode) will write.
+    ///
+    /// Note that this doesn't include any `:prefix ` a caller may want to
+    /// prepend, as that's a property of the enclosing
+    /// [`Message`](crate::Message), not of the reply itself.
+    fn encoded_len(&self) -> usize {
+        let mut len = 3 + 2; // 3-digit code + "\r\n"
+        for p in self.parameters().iter() {
+            len += 1 + usize::from(p.is_final()) + p.as_str().len();
+        }
+        len
+    }
+
+    /// Appends this reply's wire form -- `NNN param1 param2 :trailing\r\n`
+    /// -- to `dst`, reserving [`encoded_len`](ReplyParts::encoded_len)
+    /// bytes of capacity first.
+    ///
+    /// This is meant for high-throughput servers and bouncers that want to
+    /// reuse one buffer across many outgoing replies instead of allocating
+    /// an intermediate `String` per message via
+    /// [`RawMessage`](crate::RawMessage)'s `Display` impl.
+    fn encode(&self, dst: &mut BytesMut) {
+        use std::io::Write as _;
+
+        dst.reserve(self.encoded_len());
+        write!(dst, "{:03}", self.code() % 1000).expect("writing to a BytesMut should not fail");
+        for p in self.parameters().iter() {
+            dst.extend_from_slice(b" ");
+            if p.is_final() {
+                dst.extend_from_slice(b":");
+            }
+            dst.extend_from_slice(p.as_str().as_bytes());
+        }
+        dst.extend_from_slice(b"\r\n");
+    }
 }
 
 #[enum_dispatch(ReplyParts)] // This also gives us From and TryInto
@@ -84,6 +144,7 @@ pub enum Reply {
     EndOfExceptList,
     Version,
     WhoReply,
+    WhoSpcRpl,
     NamReply,
     Links,
     EndOfLinks,
@@ -157,6 +218,7 @@ pub enum Reply {
     SaslAborted,
     SaslAlready,
     SaslMechs,
+    Unknown,
 }
 
 impl Reply {
@@ -220,6 +282,7 @@ impl Reply {
             349 => EndOfExceptList::try_from(params).map(Into::into),
             351 => Version::try_from(params).map(Into::into),
             352 => WhoReply::try_from(params).map(Into::into),
+            354 => WhoSpcRpl::try_from(params).map(Into::into),
             353 => NamReply::try_from(params).map(Into::into),
             364 => Links::try_from(params).map(Into::into),
             365 => EndOfLinks::try_from(params).map(Into::into),
@@ -293,14 +356,39 @@ impl Reply {
             906 => SaslAborted::try_from(params).map(Into::into),
             907 => SaslAlready::try_from(params).map(Into::into),
             908 => SaslMechs::try_from(params).map(Into::into),
-            _ => Err(ReplyError::Unknown(code)),
+            _ => Ok(Unknown {
+                code,
+                parameters: params,
+            }
+            .into()),
         }
     }
+
+    /// Returns the symbolic name of this reply's numeric code (e.g.
+    /// `"RPL_WELCOME"`), or `None` for a [`Reply::Unknown`] whose code
+    /// [`codes::is_known`] doesn't recognize.
+    pub fn name(&self) -> Option<&'static str> {
+        codes::name_for(self.code())
+    }
+
+    /// Routes this reply to `handler`'s matching [`ReplyHandler`] method.
+    /// Equivalent to calling the free function [`dispatch`], but as a
+    /// method on the value being routed.
+    pub fn dispatch(&self, handler: &mut impl ReplyHandler) {
+        dispatch(handler, self);
+    }
+
+    /// Like [`Reply::dispatch`], but consumes `self`, for callers that
+    /// don't need the reply after it's been routed.
+    pub fn into_dispatch(self, handler: &mut impl ReplyHandler) {
+        dispatch(handler, &self);
+    }
 }
 
 impl From<Reply> for Message {
     fn from(value: Reply) -> Message {
         Message {
+            tags: None,
             source: None,
             payload: Payload::Reply(value),
         }
@@ -313,11 +401,62 @@ impl From<Reply> for RawMessage {
     }
 }
 
+/// A borrowed, allocation-free view of a numeric reply's code and raw
+/// parameters, for filtering traffic before paying for [`Reply::from_parts`]'s
+/// per-field parsing.
+///
+/// [`Reply::from_parts`] always allocates every typed field of whichever
+/// variant matches `code`, even when the caller only wants to, say, drop
+/// every `RPL_WHOREPLY` in a netsplit-sized burst that doesn't match a
+/// pending `WHO` request. `ReplyRef` borrows the code and a `&ParameterList`
+/// straight out of an already-parsed [`RawMessage`] instead, mirroring the
+/// borrowed/owned split [`CommandRef`](crate::CommandRef) uses for commands;
+/// [`Self::to_owned`] upgrades a kept `ReplyRef` into a fully-typed, owned
+/// `Reply` only once the caller has decided the message is worth that cost.
+#[derive(Clone, Copy, Debug)]
+pub struct ReplyRef<'a> {
+    code: u16,
+    parameters: &'a ParameterList,
+}
+
+impl<'a> ReplyRef<'a> {
+    /// Borrows `msg`'s code and parameters, or returns `None` if `msg` isn't
+    /// a numeric reply.
+    pub fn from_raw_message(msg: &'a RawMessage) -> Option<ReplyRef<'a>> {
+        match &msg.command {
+            Command::Reply(code) => Some(ReplyRef {
+                code: code.as_u16(),
+                parameters: &msg.parameters,
+            }),
+            Command::Verb(_) => None,
+        }
+    }
+
+    /// The reply's three-digit numeric code.
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// The reply's raw, untyped parameters.
+    pub fn parameters(&self) -> &'a ParameterList {
+        self.parameters
+    }
+
+    /// Returns `true` if `code` falls in the conventional `4xx`/`5xx` error
+    /// range, the same rule [`ReplyParts::is_error`] applies once parsed.
+    pub fn is_error(&self) -> bool {
+        (400..600).contains(&self.code)
+    }
+
+    /// Parses this view into a fully-typed, owned [`Reply`], cloning its
+    /// parameters. This is the point at which parsing actually allocates.
+    pub fn to_owned(self) -> Result<Reply, ReplyError> {
+        Reply::from_parts(self.code, self.parameters.clone())
+    }
+}
+
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 pub enum ReplyError {
-    #[error("unknown/unrecognized reply code {0:03}")]
-    Unknown(u16),
-
     #[error("invalid number of parameters: at least {min_required} required, {received} received")]
     ParamQty {
         min_required: usize,
@@ -383,6 +522,35 @@ pub enum ReplyError {
 
     #[error("invalid user@host string: {0:?}: expected '@'")]
     NoAt(String),
+
+    #[cfg(feature = "serde")]
+    #[error("expected numeric code {expected}, got {received}")]
+    CodeMismatch { expected: u16, received: u16 },
+
+    #[cfg(feature = "serde")]
+    #[error("RPL_BANLIST set_ts requires who to also be given")]
+    BanListSetTsWithoutWho,
+}
+
+/// A non-fatal defect found while lenient-parsing a reply (see e.g.
+/// [`LocalUsers::parse_lax`]), where [`ReplyError`] would otherwise either
+/// drop the field or reject the whole message. Collected instead of
+/// returned as an error, so a bouncer or bridge talking to a nonconforming
+/// IRCd can still make use of the fields that did parse.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ReplyWarning {
+    #[error("expected parameter {index} ({field:?}) to be an integer, got {string:?}")]
+    NonInteger {
+        index: usize,
+        field: &'static str,
+        string: String,
+    },
+
+    #[error("expected field {field:?} but message had too few parameters")]
+    MissingField { field: &'static str },
+
+    #[error("{count} trailing parameter(s) beyond what this reply expects")]
+    ExtraParams { count: usize },
 }
 
 pub mod codes {
@@ -445,6 +613,7 @@ pub mod codes {
     pub const RPL_VERSION: u16 = 351;
     pub const RPL_WHOREPLY: u16 = 352;
     pub const RPL_NAMREPLY: u16 = 353;
+    pub const RPL_WHOSPCRPL: u16 = 354;
     pub const RPL_LINKS: u16 = 364;
     pub const RPL_ENDOFLINKS: u16 = 365;
     pub const RPL_ENDOFNAMES: u16 = 366;
@@ -517,6 +686,166 @@ pub mod codes {
     pub const ERR_SASLABORTED: u16 = 906;
     pub const ERR_SASLALREADY: u16 = 907;
     pub const RPL_SASLMECHS: u16 = 908;
+
+    /// Every numeric this module defines, paired with its symbolic name,
+    /// in the same order as the `pub const` declarations above. Kept in
+    /// sync with them by hand; a mismatch here is a bug in this module,
+    /// not in a caller.
+    const ALL: &[(u16, &str)] = &[
+        (RPL_WELCOME, "RPL_WELCOME"),
+        (RPL_YOURHOST, "RPL_YOURHOST"),
+        (RPL_CREATED, "RPL_CREATED"),
+        (RPL_MYINFO, "RPL_MYINFO"),
+        (RPL_ISUPPORT, "RPL_ISUPPORT"),
+        (RPL_REMOTEISUPPORT, "RPL_REMOTEISUPPORT"),
+        (RPL_BOUNCE, "RPL_BOUNCE"),
+        (RPL_STATSCOMMANDS, "RPL_STATSCOMMANDS"),
+        (RPL_ENDOFSTATS, "RPL_ENDOFSTATS"),
+        (RPL_UMODEIS, "RPL_UMODEIS"),
+        (RPL_STATSUPTIME, "RPL_STATSUPTIME"),
+        (RPL_LUSERCLIENT, "RPL_LUSERCLIENT"),
+        (RPL_LUSEROP, "RPL_LUSEROP"),
+        (RPL_LUSERUNKNOWN, "RPL_LUSERUNKNOWN"),
+        (RPL_LUSERCHANNELS, "RPL_LUSERCHANNELS"),
+        (RPL_LUSERME, "RPL_LUSERME"),
+        (RPL_ADMINME, "RPL_ADMINME"),
+        (RPL_ADMINLOC1, "RPL_ADMINLOC1"),
+        (RPL_ADMINLOC2, "RPL_ADMINLOC2"),
+        (RPL_ADMINEMAIL, "RPL_ADMINEMAIL"),
+        (RPL_TRYAGAIN, "RPL_TRYAGAIN"),
+        (RPL_LOCALUSERS, "RPL_LOCALUSERS"),
+        (RPL_GLOBALUSERS, "RPL_GLOBALUSERS"),
+        (RPL_WHOISCERTFP, "RPL_WHOISCERTFP"),
+        (RPL_NONE, "RPL_NONE"),
+        (RPL_AWAY, "RPL_AWAY"),
+        (RPL_USERHOST, "RPL_USERHOST"),
+        (RPL_UNAWAY, "RPL_UNAWAY"),
+        (RPL_NOWAWAY, "RPL_NOWAWAY"),
+        (RPL_WHOISREGNICK, "RPL_WHOISREGNICK"),
+        (RPL_WHOISUSER, "RPL_WHOISUSER"),
+        (RPL_WHOISSERVER, "RPL_WHOISSERVER"),
+        (RPL_WHOISOPERATOR, "RPL_WHOISOPERATOR"),
+        (RPL_WHOWASUSER, "RPL_WHOWASUSER"),
+        (RPL_ENDOFWHO, "RPL_ENDOFWHO"),
+        (RPL_WHOISIDLE, "RPL_WHOISIDLE"),
+        (RPL_ENDOFWHOIS, "RPL_ENDOFWHOIS"),
+        (RPL_WHOISCHANNELS, "RPL_WHOISCHANNELS"),
+        (RPL_WHOISSPECIAL, "RPL_WHOISSPECIAL"),
+        (RPL_LISTSTART, "RPL_LISTSTART"),
+        (RPL_LIST, "RPL_LIST"),
+        (RPL_LISTEND, "RPL_LISTEND"),
+        (RPL_CHANNELMODEIS, "RPL_CHANNELMODEIS"),
+        (RPL_CREATIONTIME, "RPL_CREATIONTIME"),
+        (RPL_WHOISACCOUNT, "RPL_WHOISACCOUNT"),
+        (RPL_NOTOPIC, "RPL_NOTOPIC"),
+        (RPL_TOPIC, "RPL_TOPIC"),
+        (RPL_TOPICWHOTIME, "RPL_TOPICWHOTIME"),
+        (RPL_INVITELIST, "RPL_INVITELIST"),
+        (RPL_ENDOFINVITELIST, "RPL_ENDOFINVITELIST"),
+        (RPL_WHOISACTUALLY, "RPL_WHOISACTUALLY"),
+        (RPL_INVITING, "RPL_INVITING"),
+        (RPL_INVEXLIST, "RPL_INVEXLIST"),
+        (RPL_ENDOFINVEXLIST, "RPL_ENDOFINVEXLIST"),
+        (RPL_EXCEPTLIST, "RPL_EXCEPTLIST"),
+        (RPL_ENDOFEXCEPTLIST, "RPL_ENDOFEXCEPTLIST"),
+        (RPL_VERSION, "RPL_VERSION"),
+        (RPL_WHOREPLY, "RPL_WHOREPLY"),
+        (RPL_NAMREPLY, "RPL_NAMREPLY"),
+        (RPL_WHOSPCRPL, "RPL_WHOSPCRPL"),
+        (RPL_LINKS, "RPL_LINKS"),
+        (RPL_ENDOFLINKS, "RPL_ENDOFLINKS"),
+        (RPL_ENDOFNAMES, "RPL_ENDOFNAMES"),
+        (RPL_BANLIST, "RPL_BANLIST"),
+        (RPL_ENDOFBANLIST, "RPL_ENDOFBANLIST"),
+        (RPL_ENDOFWHOWAS, "RPL_ENDOFWHOWAS"),
+        (RPL_INFO, "RPL_INFO"),
+        (RPL_MOTD, "RPL_MOTD"),
+        (RPL_ENDOFINFO, "RPL_ENDOFINFO"),
+        (RPL_MOTDSTART, "RPL_MOTDSTART"),
+        (RPL_ENDOFMOTD, "RPL_ENDOFMOTD"),
+        (RPL_WHOISHOST, "RPL_WHOISHOST"),
+        (RPL_WHOISMODES, "RPL_WHOISMODES"),
+        (RPL_YOUREOPER, "RPL_YOUREOPER"),
+        (RPL_REHASHING, "RPL_REHASHING"),
+        (RPL_TIME, "RPL_TIME"),
+        (ERR_UNKNOWNERROR, "ERR_UNKNOWNERROR"),
+        (ERR_NOSUCHNICK, "ERR_NOSUCHNICK"),
+        (ERR_NOSUCHSERVER, "ERR_NOSUCHSERVER"),
+        (ERR_NOSUCHCHANNEL, "ERR_NOSUCHCHANNEL"),
+        (ERR_CANNOTSENDTOCHAN, "ERR_CANNOTSENDTOCHAN"),
+        (ERR_TOOMANYCHANNELS, "ERR_TOOMANYCHANNELS"),
+        (ERR_WASNOSUCHNICK, "ERR_WASNOSUCHNICK"),
+        (ERR_NOORIGIN, "ERR_NOORIGIN"),
+        (ERR_NORECIPIENT, "ERR_NORECIPIENT"),
+        (ERR_NOTEXTTOSEND, "ERR_NOTEXTTOSEND"),
+        (ERR_INPUTTOOLONG, "ERR_INPUTTOOLONG"),
+        (ERR_UNKNOWNCOMMAND, "ERR_UNKNOWNCOMMAND"),
+        (ERR_NOMOTD, "ERR_NOMOTD"),
+        (ERR_NONICKNAMEGIVEN, "ERR_NONICKNAMEGIVEN"),
+        (ERR_ERRONEUSNICKNAME, "ERR_ERRONEUSNICKNAME"),
+        (ERR_NICKNAMEINUSE, "ERR_NICKNAMEINUSE"),
+        (ERR_NICKCOLLISION, "ERR_NICKCOLLISION"),
+        (ERR_USERNOTINCHANNEL, "ERR_USERNOTINCHANNEL"),
+        (ERR_NOTONCHANNEL, "ERR_NOTONCHANNEL"),
+        (ERR_USERONCHANNEL, "ERR_USERONCHANNEL"),
+        (ERR_NOTREGISTERED, "ERR_NOTREGISTERED"),
+        (ERR_NEEDMOREPARAMS, "ERR_NEEDMOREPARAMS"),
+        (ERR_ALREADYREGISTERED, "ERR_ALREADYREGISTERED"),
+        (ERR_PASSWDMISMATCH, "ERR_PASSWDMISMATCH"),
+        (ERR_YOUREBANNEDCREEP, "ERR_YOUREBANNEDCREEP"),
+        (ERR_CHANNELISFULL, "ERR_CHANNELISFULL"),
+        (ERR_UNKNOWNMODE, "ERR_UNKNOWNMODE"),
+        (ERR_INVITEONLYCHAN, "ERR_INVITEONLYCHAN"),
+        (ERR_BANNEDFROMCHAN, "ERR_BANNEDFROMCHAN"),
+        (ERR_BADCHANNELKEY, "ERR_BADCHANNELKEY"),
+        (ERR_BADCHANMASK, "ERR_BADCHANMASK"),
+        (ERR_NOPRIVILEGES, "ERR_NOPRIVILEGES"),
+        (ERR_CHANOPRIVSNEEDED, "ERR_CHANOPRIVSNEEDED"),
+        (ERR_CANTKILLSERVER, "ERR_CANTKILLSERVER"),
+        (ERR_NOOPERHOST, "ERR_NOOPERHOST"),
+        (ERR_UMODEUNKNOWNFLAG, "ERR_UMODEUNKNOWNFLAG"),
+        (ERR_USERSDONTMATCH, "ERR_USERSDONTMATCH"),
+        (ERR_HELPNOTFOUND, "ERR_HELPNOTFOUND"),
+        (ERR_INVALIDKEY, "ERR_INVALIDKEY"),
+        (RPL_STARTTLS, "RPL_STARTTLS"),
+        (RPL_WHOISSECURE, "RPL_WHOISSECURE"),
+        (ERR_STARTTLSERROR, "ERR_STARTTLSERROR"),
+        (ERR_INVALIDMODEPARAM, "ERR_INVALIDMODEPARAM"),
+        (RPL_HELPSTART, "RPL_HELPSTART"),
+        (RPL_HELPTXT, "RPL_HELPTXT"),
+        (RPL_ENDOFHELP, "RPL_ENDOFHELP"),
+        (RPL_NOPRIVS, "RPL_NOPRIVS"),
+        (RPL_LOGGEDIN, "RPL_LOGGEDIN"),
+        (RPL_LOGGEDOUT, "RPL_LOGGEDOUT"),
+        (ERR_NICKLOCKED, "ERR_NICKLOCKED"),
+        (RPL_SASLSUCCESS, "RPL_SASLSUCCESS"),
+        (ERR_SASLFAIL, "ERR_SASLFAIL"),
+        (ERR_SASLTOOLONG, "ERR_SASLTOOLONG"),
+        (ERR_SASLABORTED, "ERR_SASLABORTED"),
+        (ERR_SASLALREADY, "ERR_SASLALREADY"),
+        (RPL_SASLMECHS, "RPL_SASLMECHS"),
+    ];
+
+    /// Returns every numeric [`Reply::from_parts`] recognizes, paired with
+    /// its symbolic name (e.g. `(1, "RPL_WELCOME")`), in ascending
+    /// declaration order. Useful for logging, fuzzing, or building a
+    /// code-to-name lookup without re-deriving it from [`Reply::from_parts`]'s
+    /// match arms by hand.
+    pub fn all() -> impl Iterator<Item = (u16, &'static str)> {
+        ALL.iter().copied()
+    }
+
+    /// Returns the symbolic name of `code` (e.g. `"RPL_WELCOME"`), or
+    /// `None` if `code` isn't one [`Reply::from_parts`] has a parser for.
+    pub fn name_for(code: u16) -> Option<&'static str> {
+        ALL.iter().find(|(c, _)| *c == code).map(|(_, name)| *name)
+    }
+
+    /// Returns `true` if `code` is one [`Reply::from_parts`] has a typed
+    /// parser for, i.e. one that won't fall back to [`super::Unknown`].
+    pub fn is_known(code: u16) -> bool {
+        ALL.iter().any(|(c, _)| *c == code)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -526,6 +855,16 @@ pub struct Welcome {
 }
 
 impl Welcome {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> Welcome {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        Welcome::try_from(parameters).expect("Welcome::new should produce a valid Welcome")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -594,6 +933,16 @@ pub struct YourHost {
 }
 
 impl YourHost {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> YourHost {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        YourHost::try_from(parameters).expect("YourHost::new should produce a valid YourHost")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -662,6 +1011,16 @@ pub struct Created {
 }
 
 impl Created {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> Created {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        Created::try_from(parameters).expect("Created::new should produce a valid Created")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -730,6 +1089,27 @@ pub struct MyInfo {
 }
 
 impl MyInfo {
+    pub fn new(
+        client: ReplyTarget,
+        servername: MedialParam,
+        version: MedialParam,
+        available_user_modes: MedialParam,
+        available_channel_modes: MedialParam,
+        channel_modes_with_param: Option<FinalParam>,
+    ) -> MyInfo {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(servername)
+            .with_medial(version)
+            .with_medial(available_user_modes)
+            .with_medial(available_channel_modes)
+            .maybe_with_final(channel_modes_with_param);
+        MyInfo::try_from(parameters).expect("MyInfo::new should produce a valid MyInfo")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -824,6 +1204,21 @@ pub struct ISupport {
 }
 
 impl ISupport {
+    pub fn new<I>(client: ReplyTarget, tokens: I, message: FinalParam) -> ISupport
+    where
+        I: IntoIterator<Item = ISupportParam>,
+    {
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        for token in tokens {
+            builder = builder.with_medial(token);
+        }
+        let parameters = builder.with_final(message);
+        ISupport::try_from(parameters).expect("ISupport::new should produce a valid ISupport")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -907,6 +1302,22 @@ pub struct RemoteISupport {
 }
 
 impl RemoteISupport {
+    pub fn new<I>(client: ReplyTarget, tokens: I, message: FinalParam) -> RemoteISupport
+    where
+        I: IntoIterator<Item = ISupportParam>,
+    {
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        for token in tokens {
+            builder = builder.with_medial(token);
+        }
+        let parameters = builder.with_final(message);
+        RemoteISupport::try_from(parameters)
+            .expect("RemoteISupport::new should produce a valid RemoteISupport")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -990,6 +1401,26 @@ pub struct Bounce {
 }
 
 impl Bounce {
+    pub fn new(
+        client: ReplyTarget,
+        hostname: MedialParam,
+        port: u16,
+        message: FinalParam,
+    ) -> Bounce {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(hostname)
+            .with_medial(
+                MedialParam::try_from(port.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(message);
+        Bounce::try_from(parameters).expect("Bounce::new should produce a valid Bounce")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1088,6 +1519,47 @@ pub struct StatsCommands {
 }
 
 impl StatsCommands {
+    /// `remote_count` may only be given when `byte_count` is, matching the
+    /// positional layout this reply actually parses.
+    pub fn new(
+        client: ReplyTarget,
+        command: MedialParam,
+        count: u64,
+        byte_count: Option<u64>,
+        remote_count: Option<u64>,
+    ) -> StatsCommands {
+        assert!(
+            byte_count.is_some() || remote_count.is_none(),
+            "StatsCommands::new: remote_count requires byte_count"
+        );
+        let mut builder = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(command)
+            .with_medial(
+                MedialParam::try_from(count.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        if let Some(byte_count) = byte_count {
+            builder = builder.with_medial(
+                MedialParam::try_from(byte_count.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        let parameters = if let Some(remote_count) = remote_count {
+            builder.with_final(
+                FinalParam::try_from(remote_count.to_string())
+                    .expect("a formatted number should be a valid final parameter"),
+            )
+        } else {
+            builder.finish()
+        };
+        StatsCommands::try_from(parameters)
+            .expect("StatsCommands::new should produce a valid StatsCommands")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1206,6 +1678,17 @@ pub struct EndOfStats {
 }
 
 impl EndOfStats {
+    pub fn new(client: ReplyTarget, stats_letter: MedialParam, message: FinalParam) -> EndOfStats {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(stats_letter)
+            .with_final(message);
+        EndOfStats::try_from(parameters).expect("EndOfStats::new should produce a valid EndOfStats")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1281,6 +1764,17 @@ pub struct UModeIs {
 }
 
 impl UModeIs {
+    pub fn new(client: ReplyTarget, user_modes: MedialParam) -> UModeIs {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(user_modes)
+            .finish();
+        UModeIs::try_from(parameters).expect("UModeIs::new should produce a valid UModeIs")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1346,9 +1840,21 @@ impl TryFrom<ParameterList> for UModeIs {
 pub struct StatsUptime {
     parameters: ParameterList,
     client: ReplyTarget,
+    uptime: Option<Duration>,
 }
 
 impl StatsUptime {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> StatsUptime {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        StatsUptime::try_from(parameters)
+            .expect("StatsUptime::new should produce a valid StatsUptime")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1359,6 +1865,29 @@ impl StatsUptime {
         };
         p.as_str()
     }
+
+    /// The server's reported uptime, parsed out of [`StatsUptime::message`]
+    /// (conventionally `"Server Up 15 days, 3:47:20"`), or `None` if the
+    /// text doesn't match that convention — there's no standard format for
+    /// this field, so unrecognized servers just lose the typed value, not
+    /// the reply.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.uptime
+    }
+}
+
+/// Parses the conventional ircd `STATS u` uptime text, `"Server Up <days>
+/// days, <H>:<MM>:<SS>"`, into a [`Duration`].
+fn parse_uptime(message: &str) -> Option<Duration> {
+    let rest = message.strip_prefix("Server Up ")?;
+    let (days, rest) = rest.split_once(" days, ")?;
+    let days: u64 = days.parse().ok()?;
+    let mut fields = rest.splitn(3, ':');
+    let hours: u64 = fields.next()?.parse().ok()?;
+    let minutes: u64 = fields.next()?.parse().ok()?;
+    let seconds: u64 = fields.next()?.parse().ok()?;
+    let total_seconds = ((days * 24 + hours) * 60 + minutes) * 60 + seconds;
+    Some(Duration::from_secs(total_seconds))
 }
 
 impl ReplyParts for StatsUptime {
@@ -1406,7 +1935,15 @@ impl TryFrom<ParameterList> for StatsUptime {
             .get(0)
             .expect("Parameter 0 should exist when list length is at least 2");
         let client = ReplyTarget::try_from(String::from(p))?;
-        Ok(StatsUptime { parameters, client })
+        let Some(p) = parameters.last() else {
+            unreachable!("reply parameters should be nonempty");
+        };
+        let uptime = parse_uptime(p.as_str());
+        Ok(StatsUptime {
+            parameters,
+            client,
+            uptime,
+        })
     }
 }
 
@@ -1417,6 +1954,17 @@ pub struct LuserClient {
 }
 
 impl LuserClient {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> LuserClient {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LuserClient::try_from(parameters)
+            .expect("LuserClient::new should produce a valid LuserClient")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1486,6 +2034,20 @@ pub struct LuserOp {
 }
 
 impl LuserOp {
+    pub fn new(client: ReplyTarget, ops: u64, message: FinalParam) -> LuserOp {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(ops.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LuserOp::try_from(parameters).expect("LuserOp::new should produce a valid LuserOp")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1575,6 +2137,21 @@ pub struct LuserUnknown {
 }
 
 impl LuserUnknown {
+    pub fn new(client: ReplyTarget, connections: u64, message: FinalParam) -> LuserUnknown {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(connections.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LuserUnknown::try_from(parameters)
+            .expect("LuserUnknown::new should produce a valid LuserUnknown")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1664,6 +2241,21 @@ pub struct LuserChannels {
 }
 
 impl LuserChannels {
+    pub fn new(client: ReplyTarget, channels: u64, message: FinalParam) -> LuserChannels {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(channels.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LuserChannels::try_from(parameters)
+            .expect("LuserChannels::new should produce a valid LuserChannels")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1752,6 +2344,16 @@ pub struct LuserMe {
 }
 
 impl LuserMe {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> LuserMe {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LuserMe::try_from(parameters).expect("LuserMe::new should produce a valid LuserMe")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1820,6 +2422,18 @@ pub struct AdminMe {
 }
 
 impl AdminMe {
+    pub fn new(client: ReplyTarget, server: Option<MedialParam>, message: FinalParam) -> AdminMe {
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        if let Some(server) = server {
+            builder = builder.with_medial(server);
+        }
+        let parameters = builder.with_final(message);
+        AdminMe::try_from(parameters).expect("AdminMe::new should produce a valid AdminMe")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1892,6 +2506,16 @@ pub struct AdminLoc1 {
 }
 
 impl AdminLoc1 {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> AdminLoc1 {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        AdminLoc1::try_from(parameters).expect("AdminLoc1::new should produce a valid AdminLoc1")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -1960,6 +2584,16 @@ pub struct AdminLoc2 {
 }
 
 impl AdminLoc2 {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> AdminLoc2 {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        AdminLoc2::try_from(parameters).expect("AdminLoc2::new should produce a valid AdminLoc2")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2028,6 +2662,16 @@ pub struct AdminEmail {
 }
 
 impl AdminEmail {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> AdminEmail {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        AdminEmail::try_from(parameters).expect("AdminEmail::new should produce a valid AdminEmail")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2097,6 +2741,20 @@ pub struct TryAgain {
 }
 
 impl TryAgain {
+    pub fn new(client: ReplyTarget, command: Verb, message: FinalParam) -> TryAgain {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(command.to_string())
+                    .expect("Verb Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        TryAgain::try_from(parameters).expect("TryAgain::new should produce a valid TryAgain")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2179,6 +2837,38 @@ pub struct LocalUsers {
 }
 
 impl LocalUsers {
+    /// `max_users` may only be given when `current_users` is, matching the
+    /// positional layout this reply actually parses.
+    pub fn new(
+        client: ReplyTarget,
+        current_users: Option<u64>,
+        max_users: Option<u64>,
+        message: FinalParam,
+    ) -> LocalUsers {
+        assert!(
+            current_users.is_some() || max_users.is_none(),
+            "LocalUsers::new: max_users requires current_users"
+        );
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        if let Some(current_users) = current_users {
+            builder = builder.with_medial(
+                MedialParam::try_from(current_users.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        if let Some(max_users) = max_users {
+            builder = builder.with_medial(
+                MedialParam::try_from(max_users.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        let parameters = builder.with_final(message);
+        LocalUsers::try_from(parameters).expect("LocalUsers::new should produce a valid LocalUsers")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2197,6 +2887,64 @@ impl LocalUsers {
         };
         p.as_str()
     }
+
+    /// A lenient alternative to [`TryFrom<ParameterList>`](LocalUsers#impl-TryFrom%3CParameterList%3E-for-LocalUsers)
+    /// for nonconforming servers that send a non-numeric `current_users` or
+    /// `max_users` where this reply conventionally puts a count. Rather than
+    /// failing the whole message the way the strict `TryFrom` impl does, an
+    /// unparseable count is dropped to `None` and recorded as a
+    /// [`ReplyWarning`]; only a too-short parameter list (no `client`) is
+    /// still a hard error, since there is no field to fall back to there.
+    pub fn parse_lax(parameters: ParameterList) -> Result<(LocalUsers, Vec<ReplyWarning>), ReplyError> {
+        if parameters.len() < 2 {
+            return Err(ReplyError::ParamQty {
+                min_required: 2,
+                received: parameters.len(),
+            });
+        }
+        let mut warnings = Vec::new();
+        let p = parameters
+            .get(0)
+            .expect("Parameter 0 should exist when list length is at least 2");
+        let client = ReplyTarget::try_from(String::from(p))?;
+        let current_users = (parameters.len() > 2)
+            .then(|| parameters.get(1))
+            .flatten()
+            .and_then(|p| match p.as_str().parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    warnings.push(ReplyWarning::NonInteger {
+                        index: 1,
+                        field: "current_users",
+                        string: String::from(p),
+                    });
+                    None
+                }
+            });
+        let max_users = (current_users.is_some() && parameters.len() > 3)
+            .then(|| parameters.get(2))
+            .flatten()
+            .and_then(|p| match p.as_str().parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    warnings.push(ReplyWarning::NonInteger {
+                        index: 2,
+                        field: "max_users",
+                        string: String::from(p),
+                    });
+                    None
+                }
+            });
+        Ok((
+            LocalUsers {
+                parameters,
+                client,
+                current_users,
+                max_users,
+            },
+            warnings,
+        ))
+    }
 }
 
 impl ReplyParts for LocalUsers {
@@ -2281,6 +3029,56 @@ impl TryFrom<ParameterList> for LocalUsers {
     }
 }
 
+/// A borrowed, allocation-free view of an `RPL_LOCALUSERS` reply's fields,
+/// for callers that want typed field access without paying for
+/// [`LocalUsers`]'s owned `ReplyTarget` and `Parameter` allocations. `client`
+/// and `message` are returned as plain `&str` slices of the backing
+/// [`ParameterList`] rather than parsed into a [`ReplyTarget`]; the numeric
+/// fields are parsed on each call instead of being stored, since there's
+/// nowhere cheaper to cache them without allocating.
+#[derive(Clone, Copy, Debug)]
+pub struct LocalUsersRef<'a> {
+    parameters: &'a ParameterList,
+}
+
+impl<'a> LocalUsersRef<'a> {
+    /// Borrows `parameters` without validating them; accessors fall back to
+    /// sensible defaults (an empty `client`, `None` for the numeric fields)
+    /// if `parameters` doesn't actually conform to `RPL_LOCALUSERS`'s shape.
+    pub fn new(parameters: &'a ParameterList) -> LocalUsersRef<'a> {
+        LocalUsersRef { parameters }
+    }
+
+    pub fn client(&self) -> &'a str {
+        self.parameters.get(0).map_or("", |p| p.as_str())
+    }
+
+    pub fn current_users(&self) -> Option<u64> {
+        (self.parameters.len() > 2)
+            .then(|| self.parameters.get(1))
+            .flatten()
+            .and_then(|p| p.as_str().parse().ok())
+    }
+
+    pub fn max_users(&self) -> Option<u64> {
+        (self.parameters.len() > 3)
+            .then(|| self.parameters.get(2))
+            .flatten()
+            .and_then(|p| p.as_str().parse().ok())
+    }
+
+    pub fn message(&self) -> &'a str {
+        self.parameters.last().map_or("", |p| p.as_str())
+    }
+
+    /// Upgrades this borrowed view into an owned, fully-typed [`LocalUsers`],
+    /// paying the allocation and validation cost [`LocalUsersRef`] exists to
+    /// defer.
+    pub fn to_owned(&self) -> Result<LocalUsers, ReplyError> {
+        LocalUsers::try_from(self.parameters.clone())
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GlobalUsers {
     parameters: ParameterList,
@@ -2290,6 +3088,39 @@ pub struct GlobalUsers {
 }
 
 impl GlobalUsers {
+    /// `max_users` may only be given when `current_users` is, matching the
+    /// positional layout this reply actually parses.
+    pub fn new(
+        client: ReplyTarget,
+        current_users: Option<u64>,
+        max_users: Option<u64>,
+        message: FinalParam,
+    ) -> GlobalUsers {
+        assert!(
+            current_users.is_some() || max_users.is_none(),
+            "GlobalUsers::new: max_users requires current_users"
+        );
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        if let Some(current_users) = current_users {
+            builder = builder.with_medial(
+                MedialParam::try_from(current_users.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        if let Some(max_users) = max_users {
+            builder = builder.with_medial(
+                MedialParam::try_from(max_users.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        let parameters = builder.with_final(message);
+        GlobalUsers::try_from(parameters)
+            .expect("GlobalUsers::new should produce a valid GlobalUsers")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2308,6 +3139,61 @@ impl GlobalUsers {
         };
         p.as_str()
     }
+
+    /// A lenient alternative to [`TryFrom<ParameterList>`](GlobalUsers#impl-TryFrom%3CParameterList%3E-for-GlobalUsers),
+    /// identical in behavior to [`LocalUsers::parse_lax`]: an unparseable
+    /// `current_users`/`max_users` is dropped to `None` and recorded as a
+    /// [`ReplyWarning`] instead of failing the whole message.
+    pub fn parse_lax(parameters: ParameterList) -> Result<(GlobalUsers, Vec<ReplyWarning>), ReplyError> {
+        if parameters.len() < 2 {
+            return Err(ReplyError::ParamQty {
+                min_required: 2,
+                received: parameters.len(),
+            });
+        }
+        let mut warnings = Vec::new();
+        let p = parameters
+            .get(0)
+            .expect("Parameter 0 should exist when list length is at least 2");
+        let client = ReplyTarget::try_from(String::from(p))?;
+        let current_users = (parameters.len() > 2)
+            .then(|| parameters.get(1))
+            .flatten()
+            .and_then(|p| match p.as_str().parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    warnings.push(ReplyWarning::NonInteger {
+                        index: 1,
+                        field: "current_users",
+                        string: String::from(p),
+                    });
+                    None
+                }
+            });
+        let max_users = (current_users.is_some() && parameters.len() > 3)
+            .then(|| parameters.get(2))
+            .flatten()
+            .and_then(|p| match p.as_str().parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    warnings.push(ReplyWarning::NonInteger {
+                        index: 2,
+                        field: "max_users",
+                        string: String::from(p),
+                    });
+                    None
+                }
+            });
+        Ok((
+            GlobalUsers {
+                parameters,
+                client,
+                current_users,
+                max_users,
+            },
+            warnings,
+        ))
+    }
 }
 
 impl ReplyParts for GlobalUsers {
@@ -2400,6 +3286,18 @@ pub struct WhoIsCertFP {
 }
 
 impl WhoIsCertFP {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsCertFP {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsCertFP::try_from(parameters)
+            .expect("WhoIsCertFP::new should produce a valid WhoIsCertFP")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2478,6 +3376,14 @@ pub struct None {
     parameters: ParameterList,
 }
 
+impl None {
+    pub fn new() -> None {
+        None {
+            parameters: ParameterList::default(),
+        }
+    }
+}
+
 impl ReplyParts for None {
     fn code(&self) -> u16 {
         codes::RPL_NONE
@@ -2525,6 +3431,17 @@ pub struct Away {
 }
 
 impl Away {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> Away {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        Away::try_from(parameters).expect("Away::new should produce a valid Away")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2606,6 +3523,28 @@ pub struct UserHostRpl {
 }
 
 impl UserHostRpl {
+    pub fn new<I>(client: ReplyTarget, replies: I) -> UserHostRpl
+    where
+        I: IntoIterator<Item = UserHostReply>,
+    {
+        let text = replies
+            .into_iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(
+                FinalParam::try_from(text)
+                    .expect("space-joined UserHostReply list should be a valid final parameter"),
+            );
+        UserHostRpl::try_from(parameters)
+            .expect("UserHostRpl::new should produce a valid UserHostRpl")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2681,6 +3620,16 @@ pub struct UnAway {
 }
 
 impl UnAway {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> UnAway {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        UnAway::try_from(parameters).expect("UnAway::new should produce a valid UnAway")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2749,6 +3698,16 @@ pub struct NowAway {
 }
 
 impl NowAway {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NowAway {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NowAway::try_from(parameters).expect("NowAway::new should produce a valid NowAway")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2818,6 +3777,18 @@ pub struct WhoIsRegNick {
 }
 
 impl WhoIsRegNick {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsRegNick {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsRegNick::try_from(parameters)
+            .expect("WhoIsRegNick::new should produce a valid WhoIsRegNick")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2900,6 +3871,29 @@ pub struct WhoIsUser {
 }
 
 impl WhoIsUser {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        username: Username,
+        host: MedialParam,
+        realname: FinalParam,
+    ) -> WhoIsUser {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(MedialParam::from(username))
+            .with_medial(host)
+            .with_medial(
+                MedialParam::try_from("*".to_owned())
+                    .expect("\"*\" should be a valid medial parameter"),
+            )
+            .with_final(realname);
+        WhoIsUser::try_from(parameters).expect("WhoIsUser::new should produce a valid WhoIsUser")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -2997,6 +3991,24 @@ pub struct WhoIsServer {
 }
 
 impl WhoIsServer {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        server: MedialParam,
+        server_info: FinalParam,
+    ) -> WhoIsServer {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(server)
+            .with_final(server_info);
+        WhoIsServer::try_from(parameters)
+            .expect("WhoIsServer::new should produce a valid WhoIsServer")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3085,6 +4097,18 @@ pub struct WhoIsOperator {
 }
 
 impl WhoIsOperator {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsOperator {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsOperator::try_from(parameters)
+            .expect("WhoIsOperator::new should produce a valid WhoIsOperator")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3167,6 +4191,29 @@ pub struct WhoWasUser {
 }
 
 impl WhoWasUser {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        username: Username,
+        host: MedialParam,
+        realname: FinalParam,
+    ) -> WhoWasUser {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(MedialParam::from(username))
+            .with_medial(host)
+            .with_medial(
+                MedialParam::try_from("*".to_owned())
+                    .expect("\"*\" should be a valid medial parameter"),
+            )
+            .with_final(realname);
+        WhoWasUser::try_from(parameters).expect("WhoWasUser::new should produce a valid WhoWasUser")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3263,6 +4310,17 @@ pub struct EndOfWho {
 }
 
 impl EndOfWho {
+    pub fn new(client: ReplyTarget, mask: MedialParam, message: FinalParam) -> EndOfWho {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(mask)
+            .with_final(message);
+        EndOfWho::try_from(parameters).expect("EndOfWho::new should produce a valid EndOfWho")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3341,6 +4399,31 @@ pub struct WhoIsIdle {
 }
 
 impl WhoIsIdle {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        secs: u64,
+        signon: u64,
+        message: FinalParam,
+    ) -> WhoIsIdle {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(
+                MedialParam::try_from(secs.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(signon.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(message);
+        WhoIsIdle::try_from(parameters).expect("WhoIsIdle::new should produce a valid WhoIsIdle")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3357,6 +4440,25 @@ impl WhoIsIdle {
         self.signon
     }
 
+    /// [`Self::secs`] as a [`Duration`], so the idle interval can't be
+    /// confused with [`Self::signon_time`]'s epoch timestamp at the call
+    /// site.
+    pub fn idle_duration(&self) -> Duration {
+        Duration::from_secs(self.secs)
+    }
+
+    /// [`Self::signon`] as a [`SystemTime`], computed as
+    /// `UNIX_EPOCH + Duration::from_secs(signon)`. Saturates to
+    /// [`SystemTime`]'s maximum representable value instead of panicking,
+    /// since `signon` comes straight off the wire and a hostile or buggy
+    /// server can send an arbitrarily large number.
+    pub fn signon_time(&self) -> SystemTime {
+        let secs = self.signon.min(i64::MAX as u64);
+        SystemTime::UNIX_EPOCH
+            .checked_add(Duration::from_secs(secs))
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
     pub fn message(&self) -> &str {
         let Some(p) = self.parameters.last() else {
             unreachable!("reply parameters should be nonempty");
@@ -3456,6 +4558,17 @@ pub struct EndOfWhoIs {
 }
 
 impl EndOfWhoIs {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> EndOfWhoIs {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        EndOfWhoIs::try_from(parameters).expect("EndOfWhoIs::new should produce a valid EndOfWhoIs")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3534,10 +4647,40 @@ pub struct WhoIsChannels {
     parameters: ParameterList,
     client: ReplyTarget,
     nickname: Nickname,
-    channels: Vec<(Option<char>, Channel)>,
+    channels: Vec<(Vec<char>, Channel)>,
 }
 
 impl WhoIsChannels {
+    /// `prefixes` for each channel should be given in rank order
+    /// (highest-privileged first), matching what [`Self::channels`] returns;
+    /// an empty `Vec` means the nickname has no special membership in that
+    /// channel.
+    pub fn new<I>(client: ReplyTarget, nickname: Nickname, channels: I) -> WhoIsChannels
+    where
+        I: IntoIterator<Item = (Vec<char>, Channel)>,
+    {
+        let text = channels
+            .into_iter()
+            .map(|(prefixes, chan)| {
+                let prefixes: String = prefixes.into_iter().collect();
+                format!("{prefixes}{chan}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(
+                FinalParam::try_from(text)
+                    .expect("space-joined channel list should be a valid final parameter"),
+            );
+        WhoIsChannels::try_from(parameters)
+            .expect("WhoIsChannels::new should produce a valid WhoIsChannels")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3546,7 +4689,10 @@ impl WhoIsChannels {
         &self.nickname
     }
 
-    pub fn channels(&self) -> &[(Option<char>, Channel)] {
+    /// Each channel the nickname is on, paired with its stacked membership
+    /// prefixes in rank order (highest-privileged first); a channel with no
+    /// special membership has an empty `Vec`, not `None`.
+    pub fn channels(&self) -> &[(Vec<char>, Channel)] {
         &self.channels
     }
 }
@@ -3605,8 +4751,8 @@ impl TryFrom<ParameterList> for WhoIsChannels {
             .expect("Parameter list should be nonempty when list length is at least 3");
         let channels = split_spaces(p.as_str())
             .map(|s| {
-                let (prefix, s) = pop_channel_membership(s);
-                Channel::try_from(s.to_owned()).map(|chan| (prefix, chan))
+                let (prefixes, s) = pop_channel_memberships(s);
+                Channel::try_from(s.to_owned()).map(|chan| (prefixes, chan))
             })
             .collect::<Result<Vec<_>, _>>()?;
         Ok(WhoIsChannels {
@@ -3626,6 +4772,18 @@ pub struct WhoIsSpecial {
 }
 
 impl WhoIsSpecial {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsSpecial {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsSpecial::try_from(parameters)
+            .expect("WhoIsSpecial::new should produce a valid WhoIsSpecial")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3705,6 +4863,20 @@ pub struct ListStart {
 }
 
 impl ListStart {
+    pub fn new(client: MedialParam) -> ListStart {
+        let parameters = ParameterList::builder()
+            .with_medial(client)
+            .with_medial(
+                MedialParam::try_from("Channel".to_owned())
+                    .expect("\"Channel\" should be a valid medial parameter"),
+            )
+            .with_final(
+                FinalParam::try_from("Users  Name".to_owned())
+                    .expect("\"Users  Name\" should be a valid final parameter"),
+            );
+        ListStart::try_from(parameters).expect("ListStart::new should produce a valid ListStart")
+    }
+
     pub fn client(&self) -> &str {
         let Some(p) = self.parameters.get(0) else {
             unreachable!("index 0 should exist in reply parameters");
@@ -3767,6 +4939,21 @@ pub struct List {
 }
 
 impl List {
+    pub fn new(client: ReplyTarget, channel: Channel, clients: u64, topic: FinalParam) -> List {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(
+                MedialParam::try_from(clients.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .with_final(topic);
+        List::try_from(parameters).expect("List::new should produce a valid List")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3863,6 +5050,13 @@ pub struct ListEnd {
 }
 
 impl ListEnd {
+    pub fn new(client: MedialParam, message: FinalParam) -> ListEnd {
+        let parameters = ParameterList::builder()
+            .with_medial(client)
+            .with_final(message);
+        ListEnd::try_from(parameters).expect("ListEnd::new should produce a valid ListEnd")
+    }
+
     pub fn client(&self) -> &str {
         let Some(p) = self.parameters.get(0) else {
             unreachable!("index 0 should exist in reply parameters");
@@ -3926,6 +5120,24 @@ pub struct ChannelModeIs {
 }
 
 impl ChannelModeIs {
+    pub fn new(
+        client: ReplyTarget,
+        channel: Channel,
+        modestring: ModeString,
+        arguments: ParameterList,
+    ) -> ChannelModeIs {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(MedialParam::from(modestring))
+            .with_list(arguments);
+        ChannelModeIs::try_from(parameters)
+            .expect("ChannelModeIs::new should produce a valid ChannelModeIs")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -3941,6 +5153,28 @@ impl ChannelModeIs {
     pub fn arguments(&self) -> &ParameterList {
         &self.arguments
     }
+
+    /// Pairs [`modestring()`](ChannelModeIs::modestring) with
+    /// [`arguments()`](ChannelModeIs::arguments), walking the mode string
+    /// left to right and, per `chanmodes` (normally read off the server's
+    /// `CHANMODES` `ISUPPORT` token), consuming an argument for each letter
+    /// that needs one. `RPL_CHANNELMODEIS` never reports prefix-style
+    /// (`PREFIX`) modes, so no `PrefixTable` is needed here; a letter
+    /// `chanmodes` doesn't recognize is assumed to take no argument, per
+    /// [`ModeString::resolve`]. Arguments left unconsumed once every letter
+    /// has taken what it needs are returned alongside the changes rather
+    /// than silently dropped.
+    pub fn modes(
+        &self,
+        chanmodes: &ChanModes,
+    ) -> Result<(Vec<ModeChange>, Vec<Parameter>), ResolveModeStringError> {
+        let args = self.arguments.clone().into_iter().collect::<Vec<_>>();
+        let changes = self
+            .modestring
+            .resolve(args.clone(), chanmodes, &PrefixTable::default())?;
+        let consumed = changes.iter().filter(|c| c.arg.is_some()).count();
+        Ok((changes, args.into_iter().skip(consumed).collect()))
+    }
 }
 
 impl ReplyParts for ChannelModeIs {
@@ -4020,6 +5254,22 @@ pub struct CreationTime {
 }
 
 impl CreationTime {
+    pub fn new(client: ReplyTarget, channel: Channel, creationtime: u64) -> CreationTime {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(
+                MedialParam::try_from(creationtime.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .finish();
+        CreationTime::try_from(parameters)
+            .expect("CreationTime::new should produce a valid CreationTime")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4031,6 +5281,18 @@ impl CreationTime {
     pub fn creationtime(&self) -> u64 {
         self.creationtime
     }
+
+    /// Interprets [`creationtime()`](CreationTime::creationtime) as seconds
+    /// since the Unix epoch, returning `None` if the value doesn't fit in
+    /// a representable [`OffsetDateTime`](time::OffsetDateTime) (e.g. it
+    /// overflows `i64`).
+    #[cfg(feature = "time")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn created_at(&self) -> Option<time::OffsetDateTime> {
+        i64::try_from(self.creationtime)
+            .ok()
+            .and_then(|secs| time::OffsetDateTime::from_unix_timestamp(secs).ok())
+    }
 }
 
 impl ReplyParts for CreationTime {
@@ -4111,6 +5373,24 @@ pub struct WhoIsAccount {
 }
 
 impl WhoIsAccount {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        account: MedialParam,
+        message: FinalParam,
+    ) -> WhoIsAccount {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(account)
+            .with_final(message);
+        WhoIsAccount::try_from(parameters)
+            .expect("WhoIsAccount::new should produce a valid WhoIsAccount")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4199,6 +5479,17 @@ pub struct NoTopic {
 }
 
 impl NoTopic {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> NoTopic {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        NoTopic::try_from(parameters).expect("NoTopic::new should produce a valid NoTopic")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4280,6 +5571,17 @@ pub struct Topic {
 }
 
 impl Topic {
+    pub fn new(client: ReplyTarget, channel: Channel, topic: FinalParam) -> Topic {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(topic);
+        Topic::try_from(parameters).expect("Topic::new should produce a valid Topic")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4363,6 +5665,28 @@ pub struct TopicWhoTime {
 }
 
 impl TopicWhoTime {
+    pub fn new(
+        client: ReplyTarget,
+        channel: Channel,
+        nickname: Nickname,
+        setat: u64,
+    ) -> TopicWhoTime {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(
+                MedialParam::try_from(setat.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            )
+            .finish();
+        TopicWhoTime::try_from(parameters)
+            .expect("TopicWhoTime::new should produce a valid TopicWhoTime")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4378,6 +5702,18 @@ impl TopicWhoTime {
     pub fn setat(&self) -> u64 {
         self.setat
     }
+
+    /// Interprets [`setat()`](TopicWhoTime::setat) as seconds since the
+    /// Unix epoch, returning `None` if the value doesn't fit in a
+    /// representable [`OffsetDateTime`](time::OffsetDateTime) (e.g. it
+    /// overflows `i64`).
+    #[cfg(feature = "time")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn set_at(&self) -> Option<time::OffsetDateTime> {
+        i64::try_from(self.setat)
+            .ok()
+            .and_then(|secs| time::OffsetDateTime::from_unix_timestamp(secs).ok())
+    }
 }
 
 impl ReplyParts for TopicWhoTime {
@@ -4463,6 +5799,17 @@ pub struct InviteList {
 }
 
 impl InviteList {
+    pub fn new(client: ReplyTarget, channel: Channel) -> InviteList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .finish();
+        InviteList::try_from(parameters).expect("InviteList::new should produce a valid InviteList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4536,6 +5883,17 @@ pub struct EndOfInviteList {
 }
 
 impl EndOfInviteList {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> EndOfInviteList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        EndOfInviteList::try_from(parameters)
+            .expect("EndOfInviteList::new should produce a valid EndOfInviteList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4608,6 +5966,62 @@ pub struct WhoIsActually {
 }
 
 impl WhoIsActually {
+    /// Builds a reply conveying `nickname`'s actual connection info.
+    ///
+    /// `username`/`host`/`ip` must follow one of the combinations
+    /// [`TryFrom<ParameterList>`](#impl-TryFrom<ParameterList>-for-WhoIsActually)
+    /// can parse back: all absent, `host` alone, `ip` alone, or `username`
+    /// together with both `host` and `ip`.
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        username: Option<Username>,
+        host: Option<Host>,
+        ip: Option<IpAddr>,
+        message: FinalParam,
+    ) -> WhoIsActually {
+        let mut builder = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname));
+        match (username, host, ip) {
+            (None, None, None) => {}
+            (None, Some(host), None) => {
+                builder = builder.with_medial(
+                    MedialParam::try_from(host.to_string())
+                        .expect("Host Display output should be a valid medial parameter"),
+                );
+            }
+            (None, None, Some(ip)) => {
+                builder = builder.with_medial(
+                    MedialParam::try_from(ip.to_string())
+                        .expect("IpAddr Display output should be a valid medial parameter"),
+                );
+            }
+            (Some(username), Some(host), Some(ip)) => {
+                builder = builder
+                    .with_medial(
+                        MedialParam::try_from(format!("{username}@{host}"))
+                            .expect("username@host should be a valid medial parameter"),
+                    )
+                    .with_medial(
+                        MedialParam::try_from(ip.to_string())
+                            .expect("IpAddr Display output should be a valid medial parameter"),
+                    );
+            }
+            (username, host, ip) => {
+                panic!(
+                    "unsupported WhoIsActually field combination: username={username:?}, host={host:?}, ip={ip:?}"
+                )
+            }
+        }
+        let parameters = builder.with_final(message);
+        WhoIsActually::try_from(parameters)
+            .expect("WhoIsActually::new should produce a valid WhoIsActually")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4757,6 +6171,18 @@ pub struct Inviting {
 }
 
 impl Inviting {
+    pub fn new(client: ReplyTarget, nickname: Nickname, channel: Channel) -> Inviting {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(MedialParam::from(channel))
+            .finish();
+        Inviting::try_from(parameters).expect("Inviting::new should produce a valid Inviting")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4840,6 +6266,18 @@ pub struct InvExList {
 }
 
 impl InvExList {
+    pub fn new(client: ReplyTarget, channel: Channel, mask: MedialParam) -> InvExList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(mask)
+            .finish();
+        InvExList::try_from(parameters).expect("InvExList::new should produce a valid InvExList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -4921,6 +6359,18 @@ pub struct EndOfInvExList {
 }
 
 impl EndOfInvExList {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> EndOfInvExList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        EndOfInvExList::try_from(parameters)
+            .expect("EndOfInvExList::new should produce a valid EndOfInvExList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5002,6 +6452,18 @@ pub struct ExceptList {
 }
 
 impl ExceptList {
+    pub fn new(client: ReplyTarget, channel: Channel, mask: MedialParam) -> ExceptList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(mask)
+            .finish();
+        ExceptList::try_from(parameters).expect("ExceptList::new should produce a valid ExceptList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5083,6 +6545,18 @@ pub struct EndOfExceptList {
 }
 
 impl EndOfExceptList {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> EndOfExceptList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        EndOfExceptList::try_from(parameters)
+            .expect("EndOfExceptList::new should produce a valid EndOfExceptList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5163,6 +6637,23 @@ pub struct Version {
 }
 
 impl Version {
+    pub fn new(
+        client: ReplyTarget,
+        version: MedialParam,
+        server: MedialParam,
+        comments: FinalParam,
+    ) -> Version {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(version)
+            .with_medial(server)
+            .with_final(comments);
+        Version::try_from(parameters).expect("Version::new should produce a valid Version")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5250,6 +6741,42 @@ pub struct WhoReply {
 }
 
 impl WhoReply {
+    /// Packs `hopcount` and `realname` into the trailing `"<hopcount>
+    /// <realname>"` parameter, the same packing
+    /// [`TryFrom<ParameterList>`](WhoReply::try_from) expects, so
+    /// `WhoReply::new(..., hopcount, realname).hopcount()` and `.realname()`
+    /// round-trip back to the values passed in here.
+    pub fn new(
+        client: ReplyTarget,
+        channel: Channel,
+        username: Username,
+        host: MedialParam,
+        server: MedialParam,
+        nickname: Nickname,
+        flags: WhoFlags,
+        hopcount: u32,
+        realname: impl Into<String>,
+    ) -> WhoReply {
+        let last = FinalParam::try_from(format!("{hopcount} {}", realname.into()))
+            .expect("formatted hopcount and realname should be a valid final parameter");
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(MedialParam::from(username))
+            .with_medial(host)
+            .with_medial(server)
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(
+                MedialParam::try_from(flags.to_string())
+                    .expect("WhoFlags Display output should be a valid medial parameter"),
+            )
+            .with_final(last);
+        WhoReply::try_from(parameters).expect("WhoReply::new should produce a valid WhoReply")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5382,18 +6909,343 @@ impl TryFrom<ParameterList> for WhoReply {
     }
 }
 
+/// The set of WHOX fields a `WHO <target> %<fields>` request asked for, in
+/// the canonical order the server lays them out in a `RPL_WHOSPCRPL`
+/// reply: token (`t`), channel (`c`), username (`u`), IP (`i`), host
+/// (`h`), server (`s`), nick (`n`), flags (`f`), hopcount (`d`), idle
+/// seconds (`l`), account (`a`), op-level (`o`), realname (`r`). A
+/// `RPL_WHOSPCRPL` reply carries no labels of its own, so which positional
+/// parameter means what is determined entirely by what the original `WHO`
+/// request asked for — this type is how a caller tells [`WhoSpcRpl`] that.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WhoxFields {
+    pub token: bool,
+    pub channel: bool,
+    pub username: bool,
+    pub ip: bool,
+    pub host: bool,
+    pub server: bool,
+    pub nickname: bool,
+    pub flags: bool,
+    pub hopcount: bool,
+    pub idle: bool,
+    pub account: bool,
+    pub oplevel: bool,
+    pub realname: bool,
+}
+
+impl WhoxFields {
+    /// The full canonical set, as assumed by [`WhoSpcRpl`]'s plain
+    /// `TryFrom<ParameterList>` impl when the original request isn't known.
+    pub fn all() -> WhoxFields {
+        WhoxFields {
+            token: true,
+            channel: true,
+            username: true,
+            ip: true,
+            host: true,
+            server: true,
+            nickname: true,
+            flags: true,
+            hopcount: true,
+            idle: true,
+            account: true,
+            oplevel: true,
+            realname: true,
+        }
+    }
+
+    /// Parses the letters following the `%` in a `WHO` request's final
+    /// parameter (e.g. `tcuihsnfdlaor` in `WHO #chan %tcuihsnfdlaor`),
+    /// ignoring any trailing `,<token>` and any letter this module doesn't
+    /// recognize.
+    pub fn parse(spec: &str) -> WhoxFields {
+        let spec = spec.split_once(',').map_or(spec, |(letters, _)| letters);
+        let mut fields = WhoxFields::default();
+        for ch in spec.chars() {
+            match ch {
+                't' => fields.token = true,
+                'c' => fields.channel = true,
+                'u' => fields.username = true,
+                'i' => fields.ip = true,
+                'h' => fields.host = true,
+                's' => fields.server = true,
+                'n' => fields.nickname = true,
+                'f' => fields.flags = true,
+                'd' => fields.hopcount = true,
+                'l' => fields.idle = true,
+                'a' => fields.account = true,
+                'o' => fields.oplevel = true,
+                'r' => fields.realname = true,
+                _ => (),
+            }
+        }
+        fields
+    }
+
+    fn canonical_order(&self) -> [bool; 13] {
+        [
+            self.token,
+            self.channel,
+            self.username,
+            self.ip,
+            self.host,
+            self.server,
+            self.nickname,
+            self.flags,
+            self.hopcount,
+            self.idle,
+            self.account,
+            self.oplevel,
+            self.realname,
+        ]
+    }
+
+    /// Returns the position within a `RPL_WHOSPCRPL` reply's values that
+    /// the canonical-order field at `which` (0 = token, ..., 12 =
+    /// realname) occupies, or `None` if that field wasn't requested.
+    fn position_of(&self, which: usize) -> Option<usize> {
+        let order = self.canonical_order();
+        order[which].then(|| order[..which].iter().filter(|&&b| b).count())
+    }
+}
+
+/// A WHOX (`WHO #chan %tcuihsnfdlaor`-style) reply, numeric 354.
+///
+/// Unlike [`WhoReply`], the set and order of fields present is whatever the
+/// client's `%`-flags requested, always in [`WhoxFields`]'s canonical order
+/// restricted to the requested letters — the reply itself doesn't name
+/// them, so parsing needs the same [`WhoxFields`] the original request was
+/// built from. [`Self::values`] exposes the raw values positionally as
+/// requested; the typed getters below consult `fields` to find each one's
+/// position (or report `None` if it wasn't requested at all).
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct NamReply {
+pub struct WhoSpcRpl {
     parameters: ParameterList,
     client: ReplyTarget,
-    channel_status: ChannelStatus,
-    channel: Channel,
-    clients: Vec<(Option<char>, Nickname)>,
+    fields: WhoxFields,
+    values: Vec<String>,
 }
 
-impl NamReply {
-    pub fn client(&self) -> &ReplyTarget {
-        &self.client
+impl WhoSpcRpl {
+    pub fn new<I>(client: ReplyTarget, fields: WhoxFields, values: I) -> WhoSpcRpl
+    where
+        I: IntoIterator<Item = MedialParam>,
+    {
+        let mut builder = ParameterList::builder().with_medial(
+            MedialParam::try_from(client.to_string())
+                .expect("ReplyTarget Display output should be a valid medial parameter"),
+        );
+        for value in values {
+            builder = builder.with_medial(value);
+        }
+        let parameters = builder.finish();
+        WhoSpcRpl::try_from_with_fields(parameters, &fields)
+            .expect("WhoSpcRpl::new should produce a valid WhoSpcRpl")
+    }
+
+    pub fn client(&self) -> &ReplyTarget {
+        &self.client
+    }
+
+    /// The fields this reply was parsed as carrying, per the `WHO` request
+    /// that produced it.
+    pub fn requested_fields(&self) -> &WhoxFields {
+        &self.fields
+    }
+
+    /// The raw field values following the client target, in the order the
+    /// server sent them.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    fn field(&self, which: usize) -> Option<&str> {
+        self.fields
+            .position_of(which)
+            .and_then(|i| self.values.get(i))
+            .map(String::as_str)
+    }
+
+    /// The caller-chosen query tag (field `t`), if requested.
+    pub fn token(&self) -> Option<&str> {
+        self.field(0)
+    }
+
+    pub fn channel(&self) -> Option<&str> {
+        self.field(1)
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.field(2)
+    }
+
+    pub fn ip(&self) -> Option<&str> {
+        self.field(3)
+    }
+
+    pub fn host(&self) -> Option<&str> {
+        self.field(4)
+    }
+
+    pub fn server(&self) -> Option<&str> {
+        self.field(5)
+    }
+
+    pub fn nickname(&self) -> Option<&str> {
+        self.field(6)
+    }
+
+    pub fn flags(&self) -> Option<&str> {
+        self.field(7)
+    }
+
+    pub fn hopcount(&self) -> Option<&str> {
+        self.field(8)
+    }
+
+    pub fn idle(&self) -> Option<&str> {
+        self.field(9)
+    }
+
+    pub fn account(&self) -> Option<&str> {
+        self.field(10)
+    }
+
+    pub fn oplevel(&self) -> Option<&str> {
+        self.field(11)
+    }
+
+    pub fn realname(&self) -> Option<&str> {
+        self.field(12)
+    }
+}
+
+impl ReplyParts for WhoSpcRpl {
+    fn code(&self) -> u16 {
+        codes::RPL_WHOSPCRPL
+    }
+
+    fn parameters(&self) -> &ParameterList {
+        &self.parameters
+    }
+
+    fn is_error(&self) -> bool {
+        false
+    }
+
+    fn into_parts(self) -> (u16, ParameterList) {
+        let code = self.code();
+        (code, self.parameters)
+    }
+}
+
+impl From<WhoSpcRpl> for Message {
+    fn from(value: WhoSpcRpl) -> Message {
+        Message::from(Reply::from(value))
+    }
+}
+
+impl From<WhoSpcRpl> for RawMessage {
+    fn from(value: WhoSpcRpl) -> RawMessage {
+        RawMessage::from(Reply::from(value))
+    }
+}
+
+impl WhoSpcRpl {
+    /// Parses a `RPL_WHOSPCRPL` reply whose positional layout is known
+    /// because the caller still has `fields`, the [`WhoxFields`] the
+    /// triggering `WHO ... %...` request was built from. Any values beyond
+    /// what `fields` asks for are preserved in [`Self::values`] rather than
+    /// rejected, since a server is free to send more than was requested.
+    pub fn try_from_with_fields(
+        parameters: ParameterList,
+        fields: &WhoxFields,
+    ) -> Result<WhoSpcRpl, ReplyError> {
+        if parameters.is_empty() {
+            return Err(ReplyError::ParamQty {
+                min_required: 1,
+                received: parameters.len(),
+            });
+        }
+        let p = parameters
+            .get(0)
+            .expect("Parameter 0 should exist when list is nonempty");
+        let client = ReplyTarget::try_from(String::from(p))?;
+        let values = parameters
+            .iter()
+            .skip(1)
+            .map(|p| p.as_str().to_string())
+            .collect();
+        Ok(WhoSpcRpl {
+            parameters,
+            client,
+            fields: *fields,
+            values,
+        })
+    }
+}
+
+impl TryFrom<ParameterList> for WhoSpcRpl {
+    type Error = ReplyError;
+
+    /// Best-effort parse assuming the triggering `WHO` request asked for
+    /// [`WhoxFields::all`]. Use [`Self::try_from_with_fields`] when the
+    /// actual request is known, since a partial request otherwise shifts
+    /// every typed getter here to the wrong value.
+    fn try_from(parameters: ParameterList) -> Result<WhoSpcRpl, ReplyError> {
+        WhoSpcRpl::try_from_with_fields(parameters, &WhoxFields::all())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NamReply {
+    parameters: ParameterList,
+    client: ReplyTarget,
+    channel_status: ChannelStatus,
+    channel: Channel,
+    clients: Vec<(Option<char>, Nickname)>,
+}
+
+impl NamReply {
+    /// Space-joins each client's optional membership-prefix character with
+    /// its nickname into the trailing parameter, the same packing
+    /// [`TryFrom<ParameterList>`](NamReply::try_from) expects, so
+    /// `NamReply::new(..., clients).clients()` round-trips back to
+    /// `clients`.
+    pub fn new<I>(
+        client: ReplyTarget,
+        channel_status: ChannelStatus,
+        channel: Channel,
+        clients: I,
+    ) -> NamReply
+    where
+        I: IntoIterator<Item = (Option<char>, Nickname)>,
+    {
+        let text = clients
+            .into_iter()
+            .map(|(prefix, nick)| format!("{}{nick}", prefix.map(String::from).unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(channel_status.to_string())
+                    .expect("ChannelStatus Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(
+                FinalParam::try_from(text)
+                    .expect("space-joined nickname list should be a valid final parameter"),
+            );
+        NamReply::try_from(parameters).expect("NamReply::new should produce a valid NamReply")
+    }
+
+    pub fn client(&self) -> &ReplyTarget {
+        &self.client
     }
 
     pub fn channel_status(&self) -> &ChannelStatus {
@@ -5407,6 +7259,73 @@ impl NamReply {
     pub fn clients(&self) -> &[(Option<char>, Nickname)] {
         &self.clients
     }
+
+    /// Splits this reply into one or more, greedily packing
+    /// `(membership prefix, nickname)` pairs into each line's trailing
+    /// parameter until the next one would push its serialized
+    /// [`RawMessage`] past `max_line_len` bytes, then starts a new line
+    /// reusing this reply's `client`, `channel_status`, and `channel`. A
+    /// pair that alone would exceed the limit is still emitted on its own
+    /// line rather than dropped.
+    pub fn split(&self, max_line_len: usize) -> Vec<NamReply> {
+        let mut out = Vec::new();
+        let mut pending: Vec<(Option<char>, Nickname)> = Vec::new();
+        for entry in &self.clients {
+            let mut candidate = pending.clone();
+            candidate.push(entry.clone());
+            let fits = RawMessage::from(NamReply::new(
+                self.client.clone(),
+                self.channel_status.clone(),
+                self.channel.clone(),
+                candidate.clone(),
+            ))
+            .to_string()
+            .len()
+                <= max_line_len;
+            if fits {
+                pending = candidate;
+            } else {
+                if !pending.is_empty() {
+                    out.push(NamReply::new(
+                        self.client.clone(),
+                        self.channel_status.clone(),
+                        self.channel.clone(),
+                        std::mem::take(&mut pending),
+                    ));
+                }
+                pending.push(entry.clone());
+            }
+        }
+        if !pending.is_empty() || out.is_empty() {
+            out.push(NamReply::new(
+                self.client.clone(),
+                self.channel_status.clone(),
+                self.channel.clone(),
+                pending,
+            ));
+        }
+        out
+    }
+
+    /// Splits this reply across as many lines as needed (see [`Self::split`])
+    /// and appends the terminating `RPL_ENDOFNAMES` built from
+    /// `end_message`, so every element of the returned `Vec` is ready to
+    /// hand to a connection writer as-is.
+    pub fn into_messages(self, max_line_len: usize, end_message: FinalParam) -> Vec<RawMessage> {
+        let client = self.client.clone();
+        let channel = self.channel.clone();
+        let mut out = self
+            .split(max_line_len)
+            .into_iter()
+            .map(RawMessage::from)
+            .collect::<Vec<_>>();
+        out.push(RawMessage::from(EndOfNames::new(
+            client,
+            channel,
+            end_message,
+        )));
+        out
+    }
 }
 
 impl ReplyParts for NamReply {
@@ -5489,6 +7408,26 @@ pub struct Links {
 }
 
 impl Links {
+    pub fn new(
+        client: ReplyTarget,
+        server1: MedialParam,
+        server2: MedialParam,
+        hopcount: u32,
+        server_info: impl Into<String>,
+    ) -> Links {
+        let last = FinalParam::try_from(format!("{hopcount} {}", server_info.into()))
+            .expect("formatted hopcount and server info should be a valid final parameter");
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(server1)
+            .with_medial(server2)
+            .with_final(last);
+        Links::try_from(parameters).expect("Links::new should produce a valid Links")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5592,6 +7531,16 @@ pub struct EndOfLinks {
 }
 
 impl EndOfLinks {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> EndOfLinks {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        EndOfLinks::try_from(parameters).expect("EndOfLinks::new should produce a valid EndOfLinks")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5661,6 +7610,17 @@ pub struct EndOfNames {
 }
 
 impl EndOfNames {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> EndOfNames {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        EndOfNames::try_from(parameters).expect("EndOfNames::new should produce a valid EndOfNames")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5734,6 +7694,72 @@ impl TryFrom<ParameterList> for EndOfNames {
     }
 }
 
+/// The semantic fields of an [`EndOfNames`], used for (de)serialization
+/// instead of its raw [`ParameterList`]. Deserializing routes back through
+/// [`EndOfNames::new`] (and so [`TryFrom<ParameterList>`](EndOfNames) under
+/// the hood), so a round-tripped value can't violate the `unreachable!`
+/// invariant [`EndOfNames::message`] relies on.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct EndOfNamesData {
+    code: u16,
+    client: ReplyTarget,
+    channel: Channel,
+    message: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+impl From<&EndOfNames> for EndOfNamesData {
+    fn from(value: &EndOfNames) -> EndOfNamesData {
+        EndOfNamesData {
+            code: value.code(),
+            client: value.client().clone(),
+            channel: value.channel().clone(),
+            message: FinalParam::try_from(value.message().to_owned())
+                .expect("an existing reply's message should be a valid final parameter"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EndOfNamesData> for EndOfNames {
+    type Error = ReplyError;
+
+    fn try_from(data: EndOfNamesData) -> Result<EndOfNames, ReplyError> {
+        if data.code != codes::RPL_ENDOFNAMES {
+            return Err(ReplyError::CodeMismatch {
+                expected: codes::RPL_ENDOFNAMES,
+                received: data.code,
+            });
+        }
+        Ok(EndOfNames::new(data.client, data.channel, data.message))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for EndOfNames {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        EndOfNamesData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for EndOfNames {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        EndOfNamesData::deserialize(deserializer)
+            .and_then(|data| EndOfNames::try_from(data).map_err(serde::de::Error::custom))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BanList {
     parameters: ParameterList,
@@ -5743,6 +7769,41 @@ pub struct BanList {
 }
 
 impl BanList {
+    /// `who` may only be omitted alongside `set_ts`, and `set_ts` may only be
+    /// given when `who` is, matching the positional layout this reply
+    /// actually parses.
+    pub fn new(
+        client: ReplyTarget,
+        channel: Channel,
+        mask: MedialParam,
+        who: Option<MedialParam>,
+        set_ts: Option<u64>,
+    ) -> BanList {
+        assert!(
+            who.is_some() || set_ts.is_none(),
+            "BanList::new: set_ts requires who"
+        );
+        let mut builder = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_medial(mask);
+        if let Some(who) = who {
+            builder = builder.with_medial(who);
+        }
+        let parameters = if let Some(set_ts) = set_ts {
+            builder.with_final(
+                FinalParam::try_from(set_ts.to_string())
+                    .expect("a formatted number should be a valid final parameter"),
+            )
+        } else {
+            builder.finish()
+        };
+        BanList::try_from(parameters).expect("BanList::new should produce a valid BanList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5765,6 +7826,18 @@ impl BanList {
     pub fn set_ts(&self) -> Option<u64> {
         self.set_ts
     }
+
+    /// [`Self::set_ts`] as an [`OffsetDateTime`](time::OffsetDateTime),
+    /// returning `None` if the reply didn't report a timestamp or if the
+    /// stored value doesn't fit in one (e.g. it overflows `i64`) rather than
+    /// panicking.
+    #[cfg(feature = "time")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn set_at(&self) -> Option<time::OffsetDateTime> {
+        i64::try_from(self.set_ts?)
+            .ok()
+            .and_then(|secs| time::OffsetDateTime::from_unix_timestamp(secs).ok())
+    }
 }
 
 impl ReplyParts for BanList {
@@ -5835,6 +7908,90 @@ impl TryFrom<ParameterList> for BanList {
     }
 }
 
+/// The semantic fields of a [`BanList`], used for (de)serialization instead
+/// of its raw [`ParameterList`]. Deserializing routes back through
+/// [`BanList::new`] (and so [`TryFrom<ParameterList>`](BanList) under the
+/// hood), so a round-tripped value can't violate the `unreachable!`
+/// invariants [`BanList::mask`] and [`BanList::who`] rely on, and can't
+/// resurrect `new`'s documented "`set_ts` requires `who`" precondition
+/// either.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct BanListData {
+    code: u16,
+    client: ReplyTarget,
+    channel: Channel,
+    mask: MedialParam,
+    who: Option<MedialParam>,
+    set_ts: Option<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<&BanList> for BanListData {
+    fn from(value: &BanList) -> BanListData {
+        BanListData {
+            code: value.code(),
+            client: value.client().clone(),
+            channel: value.channel().clone(),
+            mask: MedialParam::try_from(value.mask().to_owned())
+                .expect("an existing reply's mask should be a valid medial parameter"),
+            who: value.who().map(|s| {
+                MedialParam::try_from(s.to_owned())
+                    .expect("an existing reply's who should be a valid medial parameter")
+            }),
+            set_ts: value.set_ts(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BanListData> for BanList {
+    type Error = ReplyError;
+
+    fn try_from(data: BanListData) -> Result<BanList, ReplyError> {
+        if data.code != codes::RPL_BANLIST {
+            return Err(ReplyError::CodeMismatch {
+                expected: codes::RPL_BANLIST,
+                received: data.code,
+            });
+        }
+        if data.who.is_none() && data.set_ts.is_some() {
+            return Err(ReplyError::BanListSetTsWithoutWho);
+        }
+        Ok(BanList::new(
+            data.client,
+            data.channel,
+            data.mask,
+            data.who,
+            data.set_ts,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for BanList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        BanListData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for BanList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        BanListData::deserialize(deserializer)
+            .and_then(|data| BanList::try_from(data).map_err(serde::de::Error::custom))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EndOfBanList {
     parameters: ParameterList,
@@ -5843,6 +8000,18 @@ pub struct EndOfBanList {
 }
 
 impl EndOfBanList {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> EndOfBanList {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        EndOfBanList::try_from(parameters)
+            .expect("EndOfBanList::new should produce a valid EndOfBanList")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -5924,6 +8093,18 @@ pub struct EndOfWhoWas {
 }
 
 impl EndOfWhoWas {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> EndOfWhoWas {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        EndOfWhoWas::try_from(parameters)
+            .expect("EndOfWhoWas::new should produce a valid EndOfWhoWas")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6004,6 +8185,16 @@ pub struct Info {
 }
 
 impl Info {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> Info {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        Info::try_from(parameters).expect("Info::new should produce a valid Info")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6072,6 +8263,19 @@ pub struct Motd {
 }
 
 impl Motd {
+    /// Builds the `ParameterList` in the order
+    /// [`TryFrom<ParameterList>`](Motd) expects, so a server or bot emitting
+    /// `RPL_MOTD` lines doesn't have to hand-assemble parameters itself.
+    pub fn new(client: ReplyTarget, message: FinalParam) -> Motd {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        Motd::try_from(parameters).expect("Motd::new should produce a valid Motd")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6133,6 +8337,70 @@ impl TryFrom<ParameterList> for Motd {
     }
 }
 
+/// The semantic fields of a [`Motd`], used for (de)serialization instead of
+/// its raw [`ParameterList`]. Deserializing routes back through
+/// [`Motd::new`] (and so [`TryFrom<ParameterList>`](Motd) under the hood),
+/// so a round-tripped value can't violate the `unreachable!` invariant
+/// [`Motd::message`] relies on.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct MotdData {
+    code: u16,
+    client: ReplyTarget,
+    message: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+impl From<&Motd> for MotdData {
+    fn from(value: &Motd) -> MotdData {
+        MotdData {
+            code: value.code(),
+            client: value.client().clone(),
+            message: FinalParam::try_from(value.message().to_owned())
+                .expect("an existing reply's message should be a valid final parameter"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MotdData> for Motd {
+    type Error = ReplyError;
+
+    fn try_from(data: MotdData) -> Result<Motd, ReplyError> {
+        if data.code != codes::RPL_MOTD {
+            return Err(ReplyError::CodeMismatch {
+                expected: codes::RPL_MOTD,
+                received: data.code,
+            });
+        }
+        Ok(Motd::new(data.client, data.message))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Motd {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        MotdData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Motd {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        MotdData::deserialize(deserializer)
+            .and_then(|data| Motd::try_from(data).map_err(serde::de::Error::custom))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EndOfInfo {
     parameters: ParameterList,
@@ -6140,6 +8408,16 @@ pub struct EndOfInfo {
 }
 
 impl EndOfInfo {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> EndOfInfo {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        EndOfInfo::try_from(parameters).expect("EndOfInfo::new should produce a valid EndOfInfo")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6208,6 +8486,16 @@ pub struct MotdStart {
 }
 
 impl MotdStart {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> MotdStart {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        MotdStart::try_from(parameters).expect("MotdStart::new should produce a valid MotdStart")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6276,6 +8564,16 @@ pub struct EndOfMotd {
 }
 
 impl EndOfMotd {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> EndOfMotd {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        EndOfMotd::try_from(parameters).expect("EndOfMotd::new should produce a valid EndOfMotd")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6345,6 +8643,21 @@ pub struct WhoIsHost {
 }
 
 impl WhoIsHost {
+    /// Builds the `ParameterList` in the order
+    /// [`TryFrom<ParameterList>`](WhoIsHost) expects, so a server or bot
+    /// emitting `RPL_WHOISHOST` doesn't have to hand-assemble parameters
+    /// itself.
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsHost {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsHost::try_from(parameters).expect("WhoIsHost::new should produce a valid WhoIsHost")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6418,6 +8731,72 @@ impl TryFrom<ParameterList> for WhoIsHost {
     }
 }
 
+/// The semantic fields of a [`WhoIsHost`], used for (de)serialization
+/// instead of its raw [`ParameterList`]. Deserializing routes back through
+/// [`WhoIsHost::new`] (and so [`TryFrom<ParameterList>`](WhoIsHost) under
+/// the hood), so a round-tripped value can't violate the `unreachable!`
+/// invariant [`WhoIsHost::message`] relies on.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct WhoIsHostData {
+    code: u16,
+    client: ReplyTarget,
+    nickname: Nickname,
+    message: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+impl From<&WhoIsHost> for WhoIsHostData {
+    fn from(value: &WhoIsHost) -> WhoIsHostData {
+        WhoIsHostData {
+            code: value.code(),
+            client: value.client().clone(),
+            nickname: value.nickname().clone(),
+            message: FinalParam::try_from(value.message().to_owned())
+                .expect("an existing reply's message should be a valid final parameter"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<WhoIsHostData> for WhoIsHost {
+    type Error = ReplyError;
+
+    fn try_from(data: WhoIsHostData) -> Result<WhoIsHost, ReplyError> {
+        if data.code != codes::RPL_WHOISHOST {
+            return Err(ReplyError::CodeMismatch {
+                expected: codes::RPL_WHOISHOST,
+                received: data.code,
+            });
+        }
+        Ok(WhoIsHost::new(data.client, data.nickname, data.message))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for WhoIsHost {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        WhoIsHostData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for WhoIsHost {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        WhoIsHostData::deserialize(deserializer)
+            .and_then(|data| WhoIsHost::try_from(data).map_err(serde::de::Error::custom))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WhoIsModes {
     parameters: ParameterList,
@@ -6426,6 +8805,17 @@ pub struct WhoIsModes {
 }
 
 impl WhoIsModes {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsModes {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsModes::try_from(parameters).expect("WhoIsModes::new should produce a valid WhoIsModes")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6506,6 +8896,20 @@ pub struct YoureOper {
 }
 
 impl YoureOper {
+    /// Builds the `ParameterList` in the order
+    /// [`TryFrom<ParameterList>`](YoureOper) expects, so a server or bot
+    /// emitting `RPL_YOUREOPER` doesn't have to hand-assemble parameters
+    /// itself.
+    pub fn new(client: ReplyTarget, message: FinalParam) -> YoureOper {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        YoureOper::try_from(parameters).expect("YoureOper::new should produce a valid YoureOper")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6574,6 +8978,17 @@ pub struct Rehashing {
 }
 
 impl Rehashing {
+    pub fn new(client: ReplyTarget, config_file: MedialParam, message: FinalParam) -> Rehashing {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(config_file)
+            .with_final(message);
+        Rehashing::try_from(parameters).expect("Rehashing::new should produce a valid Rehashing")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6649,7 +9064,58 @@ pub struct Time {
     timestamp: Option<u64>,
 }
 
+/// Parses an `RPL_TIME` `ts_offset` token, in either of the two forms
+/// servers commonly send: a signed `±HHMM` token (e.g. `+0000`, `-0530`) or
+/// a bare signed integer of seconds.
+#[cfg(feature = "time")]
+fn parse_ts_offset(s: &str) -> Option<time::UtcOffset> {
+    let total_seconds = if s.len() == 5
+        && (s.starts_with('+') || s.starts_with('-'))
+        && s[1..].bytes().all(|b| b.is_ascii_digit())
+    {
+        let sign: i32 = if s.starts_with('-') { -1 } else { 1 };
+        let hours: i32 = s[1..3].parse().ok()?;
+        let minutes: i32 = s[3..5].parse().ok()?;
+        sign * (hours * 3600 + minutes * 60)
+    } else {
+        s.parse::<i32>().ok()?
+    };
+    time::UtcOffset::from_whole_seconds(total_seconds).ok()
+}
+
 impl Time {
+    /// `timestamp` must be given whenever the server actually knows its Unix
+    /// time, matching the positional layout this reply actually parses.
+    pub fn new(
+        client: ReplyTarget,
+        server: MedialParam,
+        timestamp: Option<u64>,
+        ts_offset: Option<MedialParam>,
+        human_time: FinalParam,
+    ) -> Time {
+        assert!(
+            timestamp.is_some() || ts_offset.is_none(),
+            "Time::new: ts_offset requires timestamp"
+        );
+        let mut builder = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(server);
+        if let Some(timestamp) = timestamp {
+            builder = builder.with_medial(
+                MedialParam::try_from(timestamp.to_string())
+                    .expect("a formatted number should be a valid medial parameter"),
+            );
+        }
+        if let Some(ts_offset) = ts_offset {
+            builder = builder.with_medial(ts_offset);
+        }
+        let parameters = builder.with_final(human_time);
+        Time::try_from(parameters).expect("Time::new should produce a valid Time")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6665,10 +9131,35 @@ impl Time {
         self.timestamp
     }
 
+    /// [`Self::timestamp`] as an [`OffsetDateTime`](time::OffsetDateTime),
+    /// returning `None` if the reply didn't report one or if the stored
+    /// value doesn't fit in one (e.g. it overflows `i64`) rather than
+    /// panicking.
+    #[cfg(feature = "time")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn timestamp_at(&self) -> Option<time::OffsetDateTime> {
+        i64::try_from(self.timestamp?)
+            .ok()
+            .and_then(|secs| time::OffsetDateTime::from_unix_timestamp(secs).ok())
+    }
+
     pub fn ts_offset(&self) -> Option<&str> {
         self.parameters.get(3).map(|p| p.as_str())
     }
 
+    /// Combines [`Self::timestamp`] with [`Self::ts_offset`] into a single
+    /// zoned value. Returns `None` if either field is absent, the offset
+    /// doesn't parse in either of the forms [`parse_ts_offset`] accepts, or
+    /// the timestamp doesn't fit in an
+    /// [`OffsetDateTime`](time::OffsetDateTime), rather than panicking.
+    #[cfg(feature = "time")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn datetime(&self) -> Option<time::OffsetDateTime> {
+        let dt = self.timestamp_at()?;
+        let offset = parse_ts_offset(self.ts_offset()?)?;
+        Some(dt.to_offset(offset))
+    }
+
     pub fn human_time(&self) -> &str {
         let Some(p) = self.parameters.last() else {
             unreachable!("reply parameters should be nonempty");
@@ -6753,6 +9244,32 @@ pub struct UnknownError {
 }
 
 impl UnknownError {
+    pub fn new<I>(
+        client: ReplyTarget,
+        command: Verb,
+        subcommands: I,
+        message: FinalParam,
+    ) -> UnknownError
+    where
+        I: IntoIterator<Item = MedialParam>,
+    {
+        let mut builder = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(command.to_string())
+                    .expect("Verb Display output should be a valid medial parameter"),
+            );
+        for subcommand in subcommands {
+            builder = builder.with_medial(subcommand);
+        }
+        let parameters = builder.with_final(message);
+        UnknownError::try_from(parameters)
+            .expect("UnknownError::new should produce a valid UnknownError")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6845,6 +9362,21 @@ pub struct NoSuchNick {
 }
 
 impl NoSuchNick {
+    /// Builds the `ParameterList` in the order
+    /// [`TryFrom<ParameterList>`](NoSuchNick) expects, so a server or test
+    /// fixture emitting `ERR_NOSUCHNICK` doesn't have to hand-assemble
+    /// parameters itself.
+    pub fn new(client: ReplyTarget, target: MsgTarget, message: FinalParam) -> NoSuchNick {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(target))
+            .with_final(message);
+        NoSuchNick::try_from(parameters).expect("NoSuchNick::new should produce a valid NoSuchNick")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -6925,6 +9457,18 @@ pub struct NoSuchServer {
 }
 
 impl NoSuchServer {
+    pub fn new(client: ReplyTarget, server: MedialParam, message: FinalParam) -> NoSuchServer {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(server)
+            .with_final(message);
+        NoSuchServer::try_from(parameters)
+            .expect("NoSuchServer::new should produce a valid NoSuchServer")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7001,6 +9545,18 @@ pub struct NoSuchChannel {
 }
 
 impl NoSuchChannel {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> NoSuchChannel {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        NoSuchChannel::try_from(parameters)
+            .expect("NoSuchChannel::new should produce a valid NoSuchChannel")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7082,6 +9638,18 @@ pub struct CannotSendToChan {
 }
 
 impl CannotSendToChan {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> CannotSendToChan {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        CannotSendToChan::try_from(parameters)
+            .expect("CannotSendToChan::new should produce a valid CannotSendToChan")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7163,6 +9731,18 @@ pub struct TooManyChannels {
 }
 
 impl TooManyChannels {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> TooManyChannels {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        TooManyChannels::try_from(parameters)
+            .expect("TooManyChannels::new should produce a valid TooManyChannels")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7244,6 +9824,18 @@ pub struct WasNoSuchNick {
 }
 
 impl WasNoSuchNick {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WasNoSuchNick {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WasNoSuchNick::try_from(parameters)
+            .expect("WasNoSuchNick::new should produce a valid WasNoSuchNick")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7324,6 +9916,16 @@ pub struct NoOrigin {
 }
 
 impl NoOrigin {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoOrigin {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoOrigin::try_from(parameters).expect("NoOrigin::new should produce a valid NoOrigin")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7392,6 +9994,17 @@ pub struct NoRecipient {
 }
 
 impl NoRecipient {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoRecipient {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoRecipient::try_from(parameters)
+            .expect("NoRecipient::new should produce a valid NoRecipient")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7460,6 +10073,17 @@ pub struct NoTextToSend {
 }
 
 impl NoTextToSend {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoTextToSend {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoTextToSend::try_from(parameters)
+            .expect("NoTextToSend::new should produce a valid NoTextToSend")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7528,6 +10152,17 @@ pub struct InputTooLong {
 }
 
 impl InputTooLong {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> InputTooLong {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        InputTooLong::try_from(parameters)
+            .expect("InputTooLong::new should produce a valid InputTooLong")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7597,6 +10232,21 @@ pub struct UnknownCommand {
 }
 
 impl UnknownCommand {
+    pub fn new(client: ReplyTarget, command: Verb, message: FinalParam) -> UnknownCommand {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(command.to_string())
+                    .expect("Verb Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        UnknownCommand::try_from(parameters)
+            .expect("UnknownCommand::new should produce a valid UnknownCommand")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7638,6 +10288,70 @@ impl From<UnknownCommand> for Message {
     }
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct UnknownCommandData {
+    code: u16,
+    client: ReplyTarget,
+    command: String,
+    message: FinalParam,
+}
+
+#[cfg(feature = "serde")]
+impl From<&UnknownCommand> for UnknownCommandData {
+    fn from(value: &UnknownCommand) -> UnknownCommandData {
+        UnknownCommandData {
+            code: value.code(),
+            client: value.client().clone(),
+            command: value.command().to_string(),
+            message: FinalParam::try_from(value.message().to_owned())
+                .expect("an existing reply's message should be a valid final parameter"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UnknownCommandData> for UnknownCommand {
+    type Error = ReplyError;
+
+    fn try_from(data: UnknownCommandData) -> Result<UnknownCommand, ReplyError> {
+        if data.code != codes::ERR_UNKNOWNCOMMAND {
+            return Err(ReplyError::CodeMismatch {
+                expected: codes::ERR_UNKNOWNCOMMAND,
+                received: data.code,
+            });
+        }
+        Ok(UnknownCommand::new(
+            data.client,
+            Verb::from(data.command),
+            data.message,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for UnknownCommand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        UnknownCommandData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for UnknownCommand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        UnknownCommandData::deserialize(deserializer)
+            .and_then(|data| UnknownCommand::try_from(data).map_err(serde::de::Error::custom))
+    }
+}
+
 impl From<UnknownCommand> for RawMessage {
     fn from(value: UnknownCommand) -> RawMessage {
         RawMessage::from(Reply::from(value))
@@ -7677,6 +10391,16 @@ pub struct NoMotd {
 }
 
 impl NoMotd {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoMotd {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoMotd::try_from(parameters).expect("NoMotd::new should produce a valid NoMotd")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7745,6 +10469,17 @@ pub struct NoNicknameGiven {
 }
 
 impl NoNicknameGiven {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoNicknameGiven {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoNicknameGiven::try_from(parameters)
+            .expect("NoNicknameGiven::new should produce a valid NoNicknameGiven")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7813,6 +10548,22 @@ pub struct ErroneousNickname {
 }
 
 impl ErroneousNickname {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: MedialParam,
+        message: FinalParam,
+    ) -> ErroneousNickname {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(nickname)
+            .with_final(message);
+        ErroneousNickname::try_from(parameters)
+            .expect("ErroneousNickname::new should produce a valid ErroneousNickname")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7889,6 +10640,30 @@ pub struct NicknameInUse {
 }
 
 impl NicknameInUse {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> NicknameInUse {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        NicknameInUse::try_from(parameters)
+            .expect("NicknameInUse::new should produce a valid NicknameInUse")
+    }
+
+    /// Like [`NicknameInUse::new`], but uses the canonical English message
+    /// ("Nickname is already in use") instead of requiring the caller to
+    /// supply one.
+    pub fn new_default(client: ReplyTarget, nickname: Nickname) -> NicknameInUse {
+        NicknameInUse::new(
+            client,
+            nickname,
+            FinalParam::try_from("Nickname is already in use".to_owned())
+                .expect("string literal should be a valid final parameter"),
+        )
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -7970,6 +10745,18 @@ pub struct NickCollision {
 }
 
 impl NickCollision {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> NickCollision {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        NickCollision::try_from(parameters)
+            .expect("NickCollision::new should produce a valid NickCollision")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8052,6 +10839,41 @@ pub struct UserNotInChannel {
 }
 
 impl UserNotInChannel {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        channel: Channel,
+        message: FinalParam,
+    ) -> UserNotInChannel {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        UserNotInChannel::try_from(parameters)
+            .expect("UserNotInChannel::new should produce a valid UserNotInChannel")
+    }
+
+    /// Like [`UserNotInChannel::new`], but uses the canonical English
+    /// message ("They aren't on that channel") instead of requiring the
+    /// caller to supply one.
+    pub fn new_default(
+        client: ReplyTarget,
+        nickname: Nickname,
+        channel: Channel,
+    ) -> UserNotInChannel {
+        UserNotInChannel::new(
+            client,
+            nickname,
+            channel,
+            FinalParam::try_from("They aren't on that channel".to_owned())
+                .expect("string literal should be a valid final parameter"),
+        )
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8142,6 +10964,18 @@ pub struct NotOnChannel {
 }
 
 impl NotOnChannel {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> NotOnChannel {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        NotOnChannel::try_from(parameters)
+            .expect("NotOnChannel::new should produce a valid NotOnChannel")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8224,6 +11058,24 @@ pub struct UserOnChannel {
 }
 
 impl UserOnChannel {
+    pub fn new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        channel: Channel,
+        message: FinalParam,
+    ) -> UserOnChannel {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        UserOnChannel::try_from(parameters)
+            .expect("UserOnChannel::new should produce a valid UserOnChannel")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8313,6 +11165,17 @@ pub struct NotRegistered {
 }
 
 impl NotRegistered {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NotRegistered {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NotRegistered::try_from(parameters)
+            .expect("NotRegistered::new should produce a valid NotRegistered")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8382,6 +11245,33 @@ pub struct NeedMoreParams {
 }
 
 impl NeedMoreParams {
+    pub fn new(client: ReplyTarget, command: Verb, message: FinalParam) -> NeedMoreParams {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(command.to_string())
+                    .expect("Verb Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NeedMoreParams::try_from(parameters)
+            .expect("NeedMoreParams::new should produce a valid NeedMoreParams")
+    }
+
+    /// Like [`NeedMoreParams::new`], but uses the canonical English message
+    /// ("Not enough parameters") instead of requiring the caller to supply
+    /// one.
+    pub fn new_default(client: ReplyTarget, command: Verb) -> NeedMoreParams {
+        NeedMoreParams::new(
+            client,
+            command,
+            FinalParam::try_from("Not enough parameters".to_owned())
+                .expect("string literal should be a valid final parameter"),
+        )
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8462,6 +11352,17 @@ pub struct AlreadyRegistered {
 }
 
 impl AlreadyRegistered {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> AlreadyRegistered {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        AlreadyRegistered::try_from(parameters)
+            .expect("AlreadyRegistered::new should produce a valid AlreadyRegistered")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8530,6 +11431,17 @@ pub struct PasswdMismatch {
 }
 
 impl PasswdMismatch {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> PasswdMismatch {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        PasswdMismatch::try_from(parameters)
+            .expect("PasswdMismatch::new should produce a valid PasswdMismatch")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8598,6 +11510,17 @@ pub struct YoureBannedCreep {
 }
 
 impl YoureBannedCreep {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> YoureBannedCreep {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        YoureBannedCreep::try_from(parameters)
+            .expect("YoureBannedCreep::new should produce a valid YoureBannedCreep")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8667,6 +11590,32 @@ pub struct ChannelIsFull {
 }
 
 impl ChannelIsFull {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> ChannelIsFull {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        ChannelIsFull::try_from(parameters)
+            .expect("ChannelIsFull::new should produce a valid ChannelIsFull")
+    }
+
+    /// Like [`ChannelIsFull::new`], but accepts any string-like `message`
+    /// instead of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        channel: Channel,
+        message: impl Into<String>,
+    ) -> Result<ChannelIsFull, TryFromStringError<ParseFinalParamError>> {
+        Ok(ChannelIsFull::new(
+            client,
+            channel,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8747,6 +11696,18 @@ pub struct UnknownMode {
 }
 
 impl UnknownMode {
+    pub fn new(client: ReplyTarget, modechar: MedialParam, message: FinalParam) -> UnknownMode {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(modechar)
+            .with_final(message);
+        UnknownMode::try_from(parameters)
+            .expect("UnknownMode::new should produce a valid UnknownMode")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8823,6 +11784,18 @@ pub struct InviteOnlyChan {
 }
 
 impl InviteOnlyChan {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> InviteOnlyChan {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        InviteOnlyChan::try_from(parameters)
+            .expect("InviteOnlyChan::new should produce a valid InviteOnlyChan")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8904,6 +11877,18 @@ pub struct BannedFromChan {
 }
 
 impl BannedFromChan {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> BannedFromChan {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        BannedFromChan::try_from(parameters)
+            .expect("BannedFromChan::new should produce a valid BannedFromChan")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -8985,6 +11970,18 @@ pub struct BadChannelKey {
 }
 
 impl BadChannelKey {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> BadChannelKey {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        BadChannelKey::try_from(parameters)
+            .expect("BadChannelKey::new should produce a valid BadChannelKey")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9065,6 +12062,18 @@ pub struct BadChanMask {
 }
 
 impl BadChanMask {
+    pub fn new(client: ReplyTarget, channel: MedialParam, message: FinalParam) -> BadChanMask {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(channel)
+            .with_final(message);
+        BadChanMask::try_from(parameters)
+            .expect("BadChanMask::new should produce a valid BadChanMask")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9140,6 +12149,17 @@ pub struct NoPrivileges {
 }
 
 impl NoPrivileges {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoPrivileges {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoPrivileges::try_from(parameters)
+            .expect("NoPrivileges::new should produce a valid NoPrivileges")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9209,6 +12229,32 @@ pub struct ChanOPrivsNeeded {
 }
 
 impl ChanOPrivsNeeded {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> ChanOPrivsNeeded {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        ChanOPrivsNeeded::try_from(parameters)
+            .expect("ChanOPrivsNeeded::new should produce a valid ChanOPrivsNeeded")
+    }
+
+    /// Like [`ChanOPrivsNeeded::new`], but accepts any string-like `message`
+    /// instead of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        channel: Channel,
+        message: impl Into<String>,
+    ) -> Result<ChanOPrivsNeeded, TryFromStringError<ParseFinalParamError>> {
+        Ok(ChanOPrivsNeeded::new(
+            client,
+            channel,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9289,6 +12335,17 @@ pub struct CantKillServer {
 }
 
 impl CantKillServer {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> CantKillServer {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        CantKillServer::try_from(parameters)
+            .expect("CantKillServer::new should produce a valid CantKillServer")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9357,6 +12414,16 @@ pub struct NoOperHost {
 }
 
 impl NoOperHost {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NoOperHost {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NoOperHost::try_from(parameters).expect("NoOperHost::new should produce a valid NoOperHost")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9425,6 +12492,17 @@ pub struct UmodeUnknownFlag {
 }
 
 impl UmodeUnknownFlag {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> UmodeUnknownFlag {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        UmodeUnknownFlag::try_from(parameters)
+            .expect("UmodeUnknownFlag::new should produce a valid UmodeUnknownFlag")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9493,6 +12571,17 @@ pub struct UsersDontMatch {
 }
 
 impl UsersDontMatch {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> UsersDontMatch {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        UsersDontMatch::try_from(parameters)
+            .expect("UsersDontMatch::new should produce a valid UsersDontMatch")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9561,6 +12650,18 @@ pub struct HelpNotFound {
 }
 
 impl HelpNotFound {
+    pub fn new(client: ReplyTarget, subject: MedialParam, message: FinalParam) -> HelpNotFound {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(subject)
+            .with_final(message);
+        HelpNotFound::try_from(parameters)
+            .expect("HelpNotFound::new should produce a valid HelpNotFound")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9637,6 +12738,31 @@ pub struct InvalidKey {
 }
 
 impl InvalidKey {
+    pub fn new(client: ReplyTarget, channel: Channel, message: FinalParam) -> InvalidKey {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(channel))
+            .with_final(message);
+        InvalidKey::try_from(parameters).expect("InvalidKey::new should produce a valid InvalidKey")
+    }
+
+    /// Like [`InvalidKey::new`], but accepts any string-like `message`
+    /// instead of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        channel: Channel,
+        message: impl Into<String>,
+    ) -> Result<InvalidKey, TryFromStringError<ParseFinalParamError>> {
+        Ok(InvalidKey::new(
+            client,
+            channel,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9717,6 +12843,16 @@ pub struct StartTLS {
 }
 
 impl StartTLS {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> StartTLS {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        StartTLS::try_from(parameters).expect("StartTLS::new should produce a valid StartTLS")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9786,6 +12922,32 @@ pub struct WhoIsSecure {
 }
 
 impl WhoIsSecure {
+    pub fn new(client: ReplyTarget, nickname: Nickname, message: FinalParam) -> WhoIsSecure {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(MedialParam::from(nickname))
+            .with_final(message);
+        WhoIsSecure::try_from(parameters)
+            .expect("WhoIsSecure::new should produce a valid WhoIsSecure")
+    }
+
+    /// Like [`WhoIsSecure::new`], but accepts any string-like `message`
+    /// instead of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        nickname: Nickname,
+        message: impl Into<String>,
+    ) -> Result<WhoIsSecure, TryFromStringError<ParseFinalParamError>> {
+        Ok(WhoIsSecure::new(
+            client,
+            nickname,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9866,6 +13028,17 @@ pub struct StartTLSError {
 }
 
 impl StartTLSError {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> StartTLSError {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        StartTLSError::try_from(parameters)
+            .expect("StartTLSError::new should produce a valid StartTLSError")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -9935,6 +13108,47 @@ pub struct InvalidModeParam {
 }
 
 impl InvalidModeParam {
+    pub fn new(
+        client: ReplyTarget,
+        target: ModeTarget,
+        modechar: MedialParam,
+        parameter: MedialParam,
+        message: FinalParam,
+    ) -> InvalidModeParam {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(target.to_string())
+                    .expect("ModeTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(modechar)
+            .with_medial(parameter)
+            .with_final(message);
+        InvalidModeParam::try_from(parameters)
+            .expect("InvalidModeParam::new should produce a valid InvalidModeParam")
+    }
+
+    /// Like [`InvalidModeParam::new`], but accepts any string-like `message`
+    /// instead of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        target: ModeTarget,
+        modechar: MedialParam,
+        parameter: MedialParam,
+        message: impl Into<String>,
+    ) -> Result<InvalidModeParam, TryFromStringError<ParseFinalParamError>> {
+        Ok(InvalidModeParam::new(
+            client,
+            target,
+            modechar,
+            parameter,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10029,6 +13243,17 @@ pub struct HelpStart {
 }
 
 impl HelpStart {
+    pub fn new(client: ReplyTarget, subject: MedialParam, message: FinalParam) -> HelpStart {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(subject)
+            .with_final(message);
+        HelpStart::try_from(parameters).expect("HelpStart::new should produce a valid HelpStart")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10104,6 +13329,17 @@ pub struct HelpTxt {
 }
 
 impl HelpTxt {
+    pub fn new(client: ReplyTarget, subject: MedialParam, message: FinalParam) -> HelpTxt {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(subject)
+            .with_final(message);
+        HelpTxt::try_from(parameters).expect("HelpTxt::new should produce a valid HelpTxt")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10179,6 +13415,17 @@ pub struct EndOfHelp {
 }
 
 impl EndOfHelp {
+    pub fn new(client: ReplyTarget, subject: MedialParam, message: FinalParam) -> EndOfHelp {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(subject)
+            .with_final(message);
+        EndOfHelp::try_from(parameters).expect("EndOfHelp::new should produce a valid EndOfHelp")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10254,6 +13501,31 @@ pub struct NoPrivs {
 }
 
 impl NoPrivs {
+    pub fn new(client: ReplyTarget, privilege: MedialParam, message: FinalParam) -> NoPrivs {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(privilege)
+            .with_final(message);
+        NoPrivs::try_from(parameters).expect("NoPrivs::new should produce a valid NoPrivs")
+    }
+
+    /// Like [`NoPrivs::new`], but accepts any string-like `message` instead
+    /// of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        privilege: MedialParam,
+        message: impl Into<String>,
+    ) -> Result<NoPrivs, TryFromStringError<ParseFinalParamError>> {
+        Ok(NoPrivs::new(
+            client,
+            privilege,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10330,6 +13602,42 @@ pub struct LoggedIn {
 }
 
 impl LoggedIn {
+    pub fn new(
+        client: ReplyTarget,
+        your_source: ClientSource,
+        account: MedialParam,
+        message: FinalParam,
+    ) -> LoggedIn {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(your_source.to_string())
+                    .expect("ClientSource Display output should be a valid medial parameter"),
+            )
+            .with_medial(account)
+            .with_final(message);
+        LoggedIn::try_from(parameters).expect("LoggedIn::new should produce a valid LoggedIn")
+    }
+
+    /// Like [`LoggedIn::new`], but accepts any string-like `message` instead
+    /// of requiring the caller to construct a [`FinalParam`] first.
+    pub fn try_new(
+        client: ReplyTarget,
+        your_source: ClientSource,
+        account: MedialParam,
+        message: impl Into<String>,
+    ) -> Result<LoggedIn, TryFromStringError<ParseFinalParamError>> {
+        Ok(LoggedIn::new(
+            client,
+            your_source,
+            account,
+            FinalParam::try_from(message.into())?,
+        ))
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10418,6 +13726,20 @@ pub struct LoggedOut {
 }
 
 impl LoggedOut {
+    pub fn new(client: ReplyTarget, your_source: ClientSource, message: FinalParam) -> LoggedOut {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(
+                MedialParam::try_from(your_source.to_string())
+                    .expect("ClientSource Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        LoggedOut::try_from(parameters).expect("LoggedOut::new should produce a valid LoggedOut")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10498,6 +13820,16 @@ pub struct NickLocked {
 }
 
 impl NickLocked {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> NickLocked {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        NickLocked::try_from(parameters).expect("NickLocked::new should produce a valid NickLocked")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10566,6 +13898,17 @@ pub struct SaslSuccess {
 }
 
 impl SaslSuccess {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> SaslSuccess {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        SaslSuccess::try_from(parameters)
+            .expect("SaslSuccess::new should produce a valid SaslSuccess")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10634,6 +13977,16 @@ pub struct SaslFail {
 }
 
 impl SaslFail {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> SaslFail {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        SaslFail::try_from(parameters).expect("SaslFail::new should produce a valid SaslFail")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10702,6 +14055,17 @@ pub struct SaslTooLong {
 }
 
 impl SaslTooLong {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> SaslTooLong {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        SaslTooLong::try_from(parameters)
+            .expect("SaslTooLong::new should produce a valid SaslTooLong")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10770,6 +14134,17 @@ pub struct SaslAborted {
 }
 
 impl SaslAborted {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> SaslAborted {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        SaslAborted::try_from(parameters)
+            .expect("SaslAborted::new should produce a valid SaslAborted")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10838,6 +14213,17 @@ pub struct SaslAlready {
 }
 
 impl SaslAlready {
+    pub fn new(client: ReplyTarget, message: FinalParam) -> SaslAlready {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_final(message);
+        SaslAlready::try_from(parameters)
+            .expect("SaslAlready::new should produce a valid SaslAlready")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10906,6 +14292,17 @@ pub struct SaslMechs {
 }
 
 impl SaslMechs {
+    pub fn new(client: ReplyTarget, mechanisms: MedialParam, message: FinalParam) -> SaslMechs {
+        let parameters = ParameterList::builder()
+            .with_medial(
+                MedialParam::try_from(client.to_string())
+                    .expect("ReplyTarget Display output should be a valid medial parameter"),
+            )
+            .with_medial(mechanisms)
+            .with_final(message);
+        SaslMechs::try_from(parameters).expect("SaslMechs::new should produce a valid SaslMechs")
+    }
+
     pub fn client(&self) -> &ReplyTarget {
         &self.client
     }
@@ -10973,3 +14370,1486 @@ impl TryFrom<ParameterList> for SaslMechs {
         Ok(SaslMechs { parameters, client })
     }
 }
+
+/// A numeric reply with no corresponding typed variant — a vendor numeric, a
+/// newer IRCv3 numeric, or a server-specific code this crate doesn't yet
+/// model. [`Reply::from_parts`] falls back to this instead of erroring, so
+/// parsing a reply never loses the message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unknown {
+    code: u16,
+    parameters: ParameterList,
+}
+
+impl Unknown {
+    /// Builds an `Unknown` reply directly from a numeric code and
+    /// parameters, for servers/bouncers that need to relay a vendor or
+    /// not-yet-modeled numeric verbatim without this crate rejecting it for
+    /// lacking a typed variant.
+    pub fn new(code: u16, parameters: ParameterList) -> Unknown {
+        Unknown { code, parameters }
+    }
+}
+
+impl ReplyParts for Unknown {
+    fn code(&self) -> u16 {
+        self.code
+    }
+
+    fn parameters(&self) -> &ParameterList {
+        &self.parameters
+    }
+
+    fn is_error(&self) -> bool {
+        (400..600).contains(&self.code)
+    }
+
+    fn into_parts(self) -> (u16, ParameterList) {
+        (self.code, self.parameters)
+    }
+}
+
+impl From<Unknown> for Message {
+    fn from(value: Unknown) -> Message {
+        Message::from(Reply::from(value))
+    }
+}
+
+impl From<Unknown> for RawMessage {
+    fn from(value: Unknown) -> RawMessage {
+        RawMessage::from(Reply::from(value))
+    }
+}
+
+/// A per-numeric callback interface for reacting to parsed replies without
+/// writing a giant match over [`Reply`] or raw numeric codes. Every method
+/// defaults to a no-op, so implementors only override the numerics they
+/// actually care about; [`Self::on_reply`] additionally fires for every
+/// reply regardless of which typed method also ran, for callers that want a
+/// catch-all (logging, metrics) alongside their specific handlers.
+pub trait ReplyHandler {
+    /// Called for every reply, in addition to whichever typed method below
+    /// also fires for it.
+    fn on_reply(&mut self, reply: &Reply) {}
+
+    fn on_welcome(&mut self, r: &Welcome) {}
+    fn on_your_host(&mut self, r: &YourHost) {}
+    fn on_created(&mut self, r: &Created) {}
+    fn on_my_info(&mut self, r: &MyInfo) {}
+    fn on_isupport(&mut self, r: &ISupport) {}
+    fn on_remote_isupport(&mut self, r: &RemoteISupport) {}
+    fn on_bounce(&mut self, r: &Bounce) {}
+    fn on_stats_commands(&mut self, r: &StatsCommands) {}
+    fn on_end_of_stats(&mut self, r: &EndOfStats) {}
+    fn on_umode_is(&mut self, r: &UModeIs) {}
+    fn on_stats_uptime(&mut self, r: &StatsUptime) {}
+    fn on_luser_client(&mut self, r: &LuserClient) {}
+    fn on_luser_op(&mut self, r: &LuserOp) {}
+    fn on_luser_unknown(&mut self, r: &LuserUnknown) {}
+    fn on_luser_channels(&mut self, r: &LuserChannels) {}
+    fn on_luser_me(&mut self, r: &LuserMe) {}
+    fn on_admin_me(&mut self, r: &AdminMe) {}
+    fn on_admin_loc1(&mut self, r: &AdminLoc1) {}
+    fn on_admin_loc2(&mut self, r: &AdminLoc2) {}
+    fn on_admin_email(&mut self, r: &AdminEmail) {}
+    fn on_try_again(&mut self, r: &TryAgain) {}
+    fn on_local_users(&mut self, r: &LocalUsers) {}
+    fn on_global_users(&mut self, r: &GlobalUsers) {}
+    fn on_who_is_cert_fp(&mut self, r: &WhoIsCertFP) {}
+    fn on_none(&mut self, r: &None) {}
+    fn on_away(&mut self, r: &Away) {}
+    fn on_user_host_rpl(&mut self, r: &UserHostRpl) {}
+    fn on_un_away(&mut self, r: &UnAway) {}
+    fn on_now_away(&mut self, r: &NowAway) {}
+    fn on_who_is_reg_nick(&mut self, r: &WhoIsRegNick) {}
+    fn on_who_is_user(&mut self, r: &WhoIsUser) {}
+    fn on_who_is_server(&mut self, r: &WhoIsServer) {}
+    fn on_who_is_operator(&mut self, r: &WhoIsOperator) {}
+    fn on_who_was_user(&mut self, r: &WhoWasUser) {}
+    fn on_end_of_who(&mut self, r: &EndOfWho) {}
+    fn on_who_is_idle(&mut self, r: &WhoIsIdle) {}
+    fn on_end_of_who_is(&mut self, r: &EndOfWhoIs) {}
+    fn on_who_is_channels(&mut self, r: &WhoIsChannels) {}
+    fn on_who_is_special(&mut self, r: &WhoIsSpecial) {}
+    fn on_list_start(&mut self, r: &ListStart) {}
+    fn on_list(&mut self, r: &List) {}
+    fn on_list_end(&mut self, r: &ListEnd) {}
+    fn on_channel_mode_is(&mut self, r: &ChannelModeIs) {}
+    fn on_creation_time(&mut self, r: &CreationTime) {}
+    fn on_who_is_account(&mut self, r: &WhoIsAccount) {}
+    fn on_no_topic(&mut self, r: &NoTopic) {}
+    fn on_topic(&mut self, r: &Topic) {}
+    fn on_topic_who_time(&mut self, r: &TopicWhoTime) {}
+    fn on_invite_list(&mut self, r: &InviteList) {}
+    fn on_end_of_invite_list(&mut self, r: &EndOfInviteList) {}
+    fn on_who_is_actually(&mut self, r: &WhoIsActually) {}
+    fn on_inviting(&mut self, r: &Inviting) {}
+    fn on_inv_ex_list(&mut self, r: &InvExList) {}
+    fn on_end_of_inv_ex_list(&mut self, r: &EndOfInvExList) {}
+    fn on_except_list(&mut self, r: &ExceptList) {}
+    fn on_end_of_except_list(&mut self, r: &EndOfExceptList) {}
+    fn on_version(&mut self, r: &Version) {}
+    fn on_who_reply(&mut self, r: &WhoReply) {}
+    fn on_who_spc_rpl(&mut self, r: &WhoSpcRpl) {}
+    fn on_nam_reply(&mut self, r: &NamReply) {}
+    fn on_links(&mut self, r: &Links) {}
+    fn on_end_of_links(&mut self, r: &EndOfLinks) {}
+    fn on_end_of_names(&mut self, r: &EndOfNames) {}
+    fn on_ban_list(&mut self, r: &BanList) {}
+    fn on_end_of_ban_list(&mut self, r: &EndOfBanList) {}
+    fn on_end_of_who_was(&mut self, r: &EndOfWhoWas) {}
+    fn on_info(&mut self, r: &Info) {}
+    fn on_motd(&mut self, r: &Motd) {}
+    fn on_end_of_info(&mut self, r: &EndOfInfo) {}
+    fn on_motd_start(&mut self, r: &MotdStart) {}
+    fn on_end_of_motd(&mut self, r: &EndOfMotd) {}
+    fn on_who_is_host(&mut self, r: &WhoIsHost) {}
+    fn on_who_is_modes(&mut self, r: &WhoIsModes) {}
+    fn on_youre_oper(&mut self, r: &YoureOper) {}
+    fn on_rehashing(&mut self, r: &Rehashing) {}
+    fn on_time(&mut self, r: &Time) {}
+    fn on_unknown_error(&mut self, r: &UnknownError) {}
+    fn on_no_such_nick(&mut self, r: &NoSuchNick) {}
+    fn on_no_such_server(&mut self, r: &NoSuchServer) {}
+    fn on_no_such_channel(&mut self, r: &NoSuchChannel) {}
+    fn on_cannot_send_to_chan(&mut self, r: &CannotSendToChan) {}
+    fn on_too_many_channels(&mut self, r: &TooManyChannels) {}
+    fn on_was_no_such_nick(&mut self, r: &WasNoSuchNick) {}
+    fn on_no_origin(&mut self, r: &NoOrigin) {}
+    fn on_no_recipient(&mut self, r: &NoRecipient) {}
+    fn on_no_text_to_send(&mut self, r: &NoTextToSend) {}
+    fn on_input_too_long(&mut self, r: &InputTooLong) {}
+    fn on_unknown_command(&mut self, r: &UnknownCommand) {}
+    fn on_no_motd(&mut self, r: &NoMotd) {}
+    fn on_no_nickname_given(&mut self, r: &NoNicknameGiven) {}
+    fn on_erroneous_nickname(&mut self, r: &ErroneousNickname) {}
+    fn on_nickname_in_use(&mut self, r: &NicknameInUse) {}
+    fn on_nick_collision(&mut self, r: &NickCollision) {}
+    fn on_user_not_in_channel(&mut self, r: &UserNotInChannel) {}
+    fn on_not_on_channel(&mut self, r: &NotOnChannel) {}
+    fn on_user_on_channel(&mut self, r: &UserOnChannel) {}
+    fn on_not_registered(&mut self, r: &NotRegistered) {}
+    fn on_need_more_params(&mut self, r: &NeedMoreParams) {}
+    fn on_already_registered(&mut self, r: &AlreadyRegistered) {}
+    fn on_passwd_mismatch(&mut self, r: &PasswdMismatch) {}
+    fn on_youre_banned_creep(&mut self, r: &YoureBannedCreep) {}
+    fn on_channel_is_full(&mut self, r: &ChannelIsFull) {}
+    fn on_unknown_mode(&mut self, r: &UnknownMode) {}
+    fn on_invite_only_chan(&mut self, r: &InviteOnlyChan) {}
+    fn on_banned_from_chan(&mut self, r: &BannedFromChan) {}
+    fn on_bad_channel_key(&mut self, r: &BadChannelKey) {}
+    fn on_bad_chan_mask(&mut self, r: &BadChanMask) {}
+    fn on_no_privileges(&mut self, r: &NoPrivileges) {}
+    fn on_chan_oprivs_needed(&mut self, r: &ChanOPrivsNeeded) {}
+    fn on_cant_kill_server(&mut self, r: &CantKillServer) {}
+    fn on_no_oper_host(&mut self, r: &NoOperHost) {}
+    fn on_umode_unknown_flag(&mut self, r: &UmodeUnknownFlag) {}
+    fn on_users_dont_match(&mut self, r: &UsersDontMatch) {}
+    fn on_help_not_found(&mut self, r: &HelpNotFound) {}
+    fn on_invalid_key(&mut self, r: &InvalidKey) {}
+    fn on_start_tls(&mut self, r: &StartTLS) {}
+    fn on_who_is_secure(&mut self, r: &WhoIsSecure) {}
+    fn on_start_tls_error(&mut self, r: &StartTLSError) {}
+    fn on_invalid_mode_param(&mut self, r: &InvalidModeParam) {}
+    fn on_help_start(&mut self, r: &HelpStart) {}
+    fn on_help_txt(&mut self, r: &HelpTxt) {}
+    fn on_end_of_help(&mut self, r: &EndOfHelp) {}
+    fn on_no_privs(&mut self, r: &NoPrivs) {}
+    fn on_logged_in(&mut self, r: &LoggedIn) {}
+    fn on_logged_out(&mut self, r: &LoggedOut) {}
+    fn on_nick_locked(&mut self, r: &NickLocked) {}
+    fn on_sasl_success(&mut self, r: &SaslSuccess) {}
+    fn on_sasl_fail(&mut self, r: &SaslFail) {}
+    fn on_sasl_too_long(&mut self, r: &SaslTooLong) {}
+    fn on_sasl_aborted(&mut self, r: &SaslAborted) {}
+    fn on_sasl_already(&mut self, r: &SaslAlready) {}
+    fn on_sasl_mechs(&mut self, r: &SaslMechs) {}
+    fn on_unknown(&mut self, r: &Unknown) {}
+}
+
+/// Routes `reply` to the matching [`ReplyHandler`] method, after first
+/// calling [`ReplyHandler::on_reply`].
+pub fn dispatch(handler: &mut impl ReplyHandler, reply: &Reply) {
+    handler.on_reply(reply);
+    match reply {
+        Reply::Welcome(r) => handler.on_welcome(r),
+        Reply::YourHost(r) => handler.on_your_host(r),
+        Reply::Created(r) => handler.on_created(r),
+        Reply::MyInfo(r) => handler.on_my_info(r),
+        Reply::ISupport(r) => handler.on_isupport(r),
+        Reply::RemoteISupport(r) => handler.on_remote_isupport(r),
+        Reply::Bounce(r) => handler.on_bounce(r),
+        Reply::StatsCommands(r) => handler.on_stats_commands(r),
+        Reply::EndOfStats(r) => handler.on_end_of_stats(r),
+        Reply::UModeIs(r) => handler.on_umode_is(r),
+        Reply::StatsUptime(r) => handler.on_stats_uptime(r),
+        Reply::LuserClient(r) => handler.on_luser_client(r),
+        Reply::LuserOp(r) => handler.on_luser_op(r),
+        Reply::LuserUnknown(r) => handler.on_luser_unknown(r),
+        Reply::LuserChannels(r) => handler.on_luser_channels(r),
+        Reply::LuserMe(r) => handler.on_luser_me(r),
+        Reply::AdminMe(r) => handler.on_admin_me(r),
+        Reply::AdminLoc1(r) => handler.on_admin_loc1(r),
+        Reply::AdminLoc2(r) => handler.on_admin_loc2(r),
+        Reply::AdminEmail(r) => handler.on_admin_email(r),
+        Reply::TryAgain(r) => handler.on_try_again(r),
+        Reply::LocalUsers(r) => handler.on_local_users(r),
+        Reply::GlobalUsers(r) => handler.on_global_users(r),
+        Reply::WhoIsCertFP(r) => handler.on_who_is_cert_fp(r),
+        Reply::None(r) => handler.on_none(r),
+        Reply::Away(r) => handler.on_away(r),
+        Reply::UserHostRpl(r) => handler.on_user_host_rpl(r),
+        Reply::UnAway(r) => handler.on_un_away(r),
+        Reply::NowAway(r) => handler.on_now_away(r),
+        Reply::WhoIsRegNick(r) => handler.on_who_is_reg_nick(r),
+        Reply::WhoIsUser(r) => handler.on_who_is_user(r),
+        Reply::WhoIsServer(r) => handler.on_who_is_server(r),
+        Reply::WhoIsOperator(r) => handler.on_who_is_operator(r),
+        Reply::WhoWasUser(r) => handler.on_who_was_user(r),
+        Reply::EndOfWho(r) => handler.on_end_of_who(r),
+        Reply::WhoIsIdle(r) => handler.on_who_is_idle(r),
+        Reply::EndOfWhoIs(r) => handler.on_end_of_who_is(r),
+        Reply::WhoIsChannels(r) => handler.on_who_is_channels(r),
+        Reply::WhoIsSpecial(r) => handler.on_who_is_special(r),
+        Reply::ListStart(r) => handler.on_list_start(r),
+        Reply::List(r) => handler.on_list(r),
+        Reply::ListEnd(r) => handler.on_list_end(r),
+        Reply::ChannelModeIs(r) => handler.on_channel_mode_is(r),
+        Reply::CreationTime(r) => handler.on_creation_time(r),
+        Reply::WhoIsAccount(r) => handler.on_who_is_account(r),
+        Reply::NoTopic(r) => handler.on_no_topic(r),
+        Reply::Topic(r) => handler.on_topic(r),
+        Reply::TopicWhoTime(r) => handler.on_topic_who_time(r),
+        Reply::InviteList(r) => handler.on_invite_list(r),
+        Reply::EndOfInviteList(r) => handler.on_end_of_invite_list(r),
+        Reply::WhoIsActually(r) => handler.on_who_is_actually(r),
+        Reply::Inviting(r) => handler.on_inviting(r),
+        Reply::InvExList(r) => handler.on_inv_ex_list(r),
+        Reply::EndOfInvExList(r) => handler.on_end_of_inv_ex_list(r),
+        Reply::ExceptList(r) => handler.on_except_list(r),
+        Reply::EndOfExceptList(r) => handler.on_end_of_except_list(r),
+        Reply::Version(r) => handler.on_version(r),
+        Reply::WhoReply(r) => handler.on_who_reply(r),
+        Reply::WhoSpcRpl(r) => handler.on_who_spc_rpl(r),
+        Reply::NamReply(r) => handler.on_nam_reply(r),
+        Reply::Links(r) => handler.on_links(r),
+        Reply::EndOfLinks(r) => handler.on_end_of_links(r),
+        Reply::EndOfNames(r) => handler.on_end_of_names(r),
+        Reply::BanList(r) => handler.on_ban_list(r),
+        Reply::EndOfBanList(r) => handler.on_end_of_ban_list(r),
+        Reply::EndOfWhoWas(r) => handler.on_end_of_who_was(r),
+        Reply::Info(r) => handler.on_info(r),
+        Reply::Motd(r) => handler.on_motd(r),
+        Reply::EndOfInfo(r) => handler.on_end_of_info(r),
+        Reply::MotdStart(r) => handler.on_motd_start(r),
+        Reply::EndOfMotd(r) => handler.on_end_of_motd(r),
+        Reply::WhoIsHost(r) => handler.on_who_is_host(r),
+        Reply::WhoIsModes(r) => handler.on_who_is_modes(r),
+        Reply::YoureOper(r) => handler.on_youre_oper(r),
+        Reply::Rehashing(r) => handler.on_rehashing(r),
+        Reply::Time(r) => handler.on_time(r),
+        Reply::UnknownError(r) => handler.on_unknown_error(r),
+        Reply::NoSuchNick(r) => handler.on_no_such_nick(r),
+        Reply::NoSuchServer(r) => handler.on_no_such_server(r),
+        Reply::NoSuchChannel(r) => handler.on_no_such_channel(r),
+        Reply::CannotSendToChan(r) => handler.on_cannot_send_to_chan(r),
+        Reply::TooManyChannels(r) => handler.on_too_many_channels(r),
+        Reply::WasNoSuchNick(r) => handler.on_was_no_such_nick(r),
+        Reply::NoOrigin(r) => handler.on_no_origin(r),
+        Reply::NoRecipient(r) => handler.on_no_recipient(r),
+        Reply::NoTextToSend(r) => handler.on_no_text_to_send(r),
+        Reply::InputTooLong(r) => handler.on_input_too_long(r),
+        Reply::UnknownCommand(r) => handler.on_unknown_command(r),
+        Reply::NoMotd(r) => handler.on_no_motd(r),
+        Reply::NoNicknameGiven(r) => handler.on_no_nickname_given(r),
+        Reply::ErroneousNickname(r) => handler.on_erroneous_nickname(r),
+        Reply::NicknameInUse(r) => handler.on_nickname_in_use(r),
+        Reply::NickCollision(r) => handler.on_nick_collision(r),
+        Reply::UserNotInChannel(r) => handler.on_user_not_in_channel(r),
+        Reply::NotOnChannel(r) => handler.on_not_on_channel(r),
+        Reply::UserOnChannel(r) => handler.on_user_on_channel(r),
+        Reply::NotRegistered(r) => handler.on_not_registered(r),
+        Reply::NeedMoreParams(r) => handler.on_need_more_params(r),
+        Reply::AlreadyRegistered(r) => handler.on_already_registered(r),
+        Reply::PasswdMismatch(r) => handler.on_passwd_mismatch(r),
+        Reply::YoureBannedCreep(r) => handler.on_youre_banned_creep(r),
+        Reply::ChannelIsFull(r) => handler.on_channel_is_full(r),
+        Reply::UnknownMode(r) => handler.on_unknown_mode(r),
+        Reply::InviteOnlyChan(r) => handler.on_invite_only_chan(r),
+        Reply::BannedFromChan(r) => handler.on_banned_from_chan(r),
+        Reply::BadChannelKey(r) => handler.on_bad_channel_key(r),
+        Reply::BadChanMask(r) => handler.on_bad_chan_mask(r),
+        Reply::NoPrivileges(r) => handler.on_no_privileges(r),
+        Reply::ChanOPrivsNeeded(r) => handler.on_chan_oprivs_needed(r),
+        Reply::CantKillServer(r) => handler.on_cant_kill_server(r),
+        Reply::NoOperHost(r) => handler.on_no_oper_host(r),
+        Reply::UmodeUnknownFlag(r) => handler.on_umode_unknown_flag(r),
+        Reply::UsersDontMatch(r) => handler.on_users_dont_match(r),
+        Reply::HelpNotFound(r) => handler.on_help_not_found(r),
+        Reply::InvalidKey(r) => handler.on_invalid_key(r),
+        Reply::StartTLS(r) => handler.on_start_tls(r),
+        Reply::WhoIsSecure(r) => handler.on_who_is_secure(r),
+        Reply::StartTLSError(r) => handler.on_start_tls_error(r),
+        Reply::InvalidModeParam(r) => handler.on_invalid_mode_param(r),
+        Reply::HelpStart(r) => handler.on_help_start(r),
+        Reply::HelpTxt(r) => handler.on_help_txt(r),
+        Reply::EndOfHelp(r) => handler.on_end_of_help(r),
+        Reply::NoPrivs(r) => handler.on_no_privs(r),
+        Reply::LoggedIn(r) => handler.on_logged_in(r),
+        Reply::LoggedOut(r) => handler.on_logged_out(r),
+        Reply::NickLocked(r) => handler.on_nick_locked(r),
+        Reply::SaslSuccess(r) => handler.on_sasl_success(r),
+        Reply::SaslFail(r) => handler.on_sasl_fail(r),
+        Reply::SaslTooLong(r) => handler.on_sasl_too_long(r),
+        Reply::SaslAborted(r) => handler.on_sasl_aborted(r),
+        Reply::SaslAlready(r) => handler.on_sasl_already(r),
+        Reply::SaslMechs(r) => handler.on_sasl_mechs(r),
+        Reply::Unknown(r) => handler.on_unknown(r),
+    }
+}
+
+/// Like [`dispatch`], but takes `reply` by value, for callers that don't
+/// need the reply after it's been routed.
+pub fn dispatch_owned(handler: &mut impl ReplyHandler, reply: Reply) {
+    dispatch(handler, &reply);
+}
+
+/// A visitor over parsed replies, with one default-no-op method per reply
+/// type named after the type itself (e.g. [`Self::who_reply`] for
+/// [`WhoReply`], [`Self::nam_reply`] for [`NamReply`]), plus
+/// [`Self::on_reply`] as a catch-all that fires for every reply regardless
+/// of which typed method also ran. This is an alternative to
+/// [`ReplyHandler`]/[`dispatch`] for callers who'd rather name their
+/// handler methods after the struct they receive than after an `on_`
+/// prefix; [`Reply::accept`] is its dispatcher, the visitor-pattern
+/// counterpart to [`dispatch`].
+pub trait ReplyVisitor {
+    /// Called for every reply, in addition to whichever typed method below
+    /// also fires for it.
+    fn on_reply(&mut self, reply: &Reply) {}
+
+    fn welcome(&mut self, r: &Welcome) {}
+    fn your_host(&mut self, r: &YourHost) {}
+    fn created(&mut self, r: &Created) {}
+    fn my_info(&mut self, r: &MyInfo) {}
+    fn isupport(&mut self, r: &ISupport) {}
+    fn remote_isupport(&mut self, r: &RemoteISupport) {}
+    fn bounce(&mut self, r: &Bounce) {}
+    fn stats_commands(&mut self, r: &StatsCommands) {}
+    fn end_of_stats(&mut self, r: &EndOfStats) {}
+    fn umode_is(&mut self, r: &UModeIs) {}
+    fn stats_uptime(&mut self, r: &StatsUptime) {}
+    fn luser_client(&mut self, r: &LuserClient) {}
+    fn luser_op(&mut self, r: &LuserOp) {}
+    fn luser_unknown(&mut self, r: &LuserUnknown) {}
+    fn luser_channels(&mut self, r: &LuserChannels) {}
+    fn luser_me(&mut self, r: &LuserMe) {}
+    fn admin_me(&mut self, r: &AdminMe) {}
+    fn admin_loc1(&mut self, r: &AdminLoc1) {}
+    fn admin_loc2(&mut self, r: &AdminLoc2) {}
+    fn admin_email(&mut self, r: &AdminEmail) {}
+    fn try_again(&mut self, r: &TryAgain) {}
+    fn local_users(&mut self, r: &LocalUsers) {}
+    fn global_users(&mut self, r: &GlobalUsers) {}
+    fn who_is_cert_fp(&mut self, r: &WhoIsCertFP) {}
+    fn none(&mut self, r: &None) {}
+    fn away(&mut self, r: &Away) {}
+    fn user_host_rpl(&mut self, r: &UserHostRpl) {}
+    fn un_away(&mut self, r: &UnAway) {}
+    fn now_away(&mut self, r: &NowAway) {}
+    fn who_is_reg_nick(&mut self, r: &WhoIsRegNick) {}
+    fn who_is_user(&mut self, r: &WhoIsUser) {}
+    fn who_is_server(&mut self, r: &WhoIsServer) {}
+    fn who_is_operator(&mut self, r: &WhoIsOperator) {}
+    fn who_was_user(&mut self, r: &WhoWasUser) {}
+    fn end_of_who(&mut self, r: &EndOfWho) {}
+    fn who_is_idle(&mut self, r: &WhoIsIdle) {}
+    fn end_of_who_is(&mut self, r: &EndOfWhoIs) {}
+    fn who_is_channels(&mut self, r: &WhoIsChannels) {}
+    fn who_is_special(&mut self, r: &WhoIsSpecial) {}
+    fn list_start(&mut self, r: &ListStart) {}
+    fn list(&mut self, r: &List) {}
+    fn list_end(&mut self, r: &ListEnd) {}
+    fn channel_mode_is(&mut self, r: &ChannelModeIs) {}
+    fn creation_time(&mut self, r: &CreationTime) {}
+    fn who_is_account(&mut self, r: &WhoIsAccount) {}
+    fn no_topic(&mut self, r: &NoTopic) {}
+    fn topic(&mut self, r: &Topic) {}
+    fn topic_who_time(&mut self, r: &TopicWhoTime) {}
+    fn invite_list(&mut self, r: &InviteList) {}
+    fn end_of_invite_list(&mut self, r: &EndOfInviteList) {}
+    fn who_is_actually(&mut self, r: &WhoIsActually) {}
+    fn inviting(&mut self, r: &Inviting) {}
+    fn inv_ex_list(&mut self, r: &InvExList) {}
+    fn end_of_inv_ex_list(&mut self, r: &EndOfInvExList) {}
+    fn except_list(&mut self, r: &ExceptList) {}
+    fn end_of_except_list(&mut self, r: &EndOfExceptList) {}
+    fn version(&mut self, r: &Version) {}
+    fn who_reply(&mut self, r: &WhoReply) {}
+    fn who_spc_rpl(&mut self, r: &WhoSpcRpl) {}
+    fn nam_reply(&mut self, r: &NamReply) {}
+    fn links(&mut self, r: &Links) {}
+    fn end_of_links(&mut self, r: &EndOfLinks) {}
+    fn end_of_names(&mut self, r: &EndOfNames) {}
+    fn ban_list(&mut self, r: &BanList) {}
+    fn end_of_ban_list(&mut self, r: &EndOfBanList) {}
+    fn end_of_who_was(&mut self, r: &EndOfWhoWas) {}
+    fn info(&mut self, r: &Info) {}
+    fn motd(&mut self, r: &Motd) {}
+    fn end_of_info(&mut self, r: &EndOfInfo) {}
+    fn motd_start(&mut self, r: &MotdStart) {}
+    fn end_of_motd(&mut self, r: &EndOfMotd) {}
+    fn who_is_host(&mut self, r: &WhoIsHost) {}
+    fn who_is_modes(&mut self, r: &WhoIsModes) {}
+    fn youre_oper(&mut self, r: &YoureOper) {}
+    fn rehashing(&mut self, r: &Rehashing) {}
+    fn time(&mut self, r: &Time) {}
+    fn unknown_error(&mut self, r: &UnknownError) {}
+    fn no_such_nick(&mut self, r: &NoSuchNick) {}
+    fn no_such_server(&mut self, r: &NoSuchServer) {}
+    fn no_such_channel(&mut self, r: &NoSuchChannel) {}
+    fn cannot_send_to_chan(&mut self, r: &CannotSendToChan) {}
+    fn too_many_channels(&mut self, r: &TooManyChannels) {}
+    fn was_no_such_nick(&mut self, r: &WasNoSuchNick) {}
+    fn no_origin(&mut self, r: &NoOrigin) {}
+    fn no_recipient(&mut self, r: &NoRecipient) {}
+    fn no_text_to_send(&mut self, r: &NoTextToSend) {}
+    fn input_too_long(&mut self, r: &InputTooLong) {}
+    fn unknown_command(&mut self, r: &UnknownCommand) {}
+    fn no_motd(&mut self, r: &NoMotd) {}
+    fn no_nickname_given(&mut self, r: &NoNicknameGiven) {}
+    fn erroneous_nickname(&mut self, r: &ErroneousNickname) {}
+    fn nickname_in_use(&mut self, r: &NicknameInUse) {}
+    fn nick_collision(&mut self, r: &NickCollision) {}
+    fn user_not_in_channel(&mut self, r: &UserNotInChannel) {}
+    fn not_on_channel(&mut self, r: &NotOnChannel) {}
+    fn user_on_channel(&mut self, r: &UserOnChannel) {}
+    fn not_registered(&mut self, r: &NotRegistered) {}
+    fn need_more_params(&mut self, r: &NeedMoreParams) {}
+    fn already_registered(&mut self, r: &AlreadyRegistered) {}
+    fn passwd_mismatch(&mut self, r: &PasswdMismatch) {}
+    fn youre_banned_creep(&mut self, r: &YoureBannedCreep) {}
+    fn channel_is_full(&mut self, r: &ChannelIsFull) {}
+    fn unknown_mode(&mut self, r: &UnknownMode) {}
+    fn invite_only_chan(&mut self, r: &InviteOnlyChan) {}
+    fn banned_from_chan(&mut self, r: &BannedFromChan) {}
+    fn bad_channel_key(&mut self, r: &BadChannelKey) {}
+    fn bad_chan_mask(&mut self, r: &BadChanMask) {}
+    fn no_privileges(&mut self, r: &NoPrivileges) {}
+    fn chan_oprivs_needed(&mut self, r: &ChanOPrivsNeeded) {}
+    fn cant_kill_server(&mut self, r: &CantKillServer) {}
+    fn no_oper_host(&mut self, r: &NoOperHost) {}
+    fn umode_unknown_flag(&mut self, r: &UmodeUnknownFlag) {}
+    fn users_dont_match(&mut self, r: &UsersDontMatch) {}
+    fn help_not_found(&mut self, r: &HelpNotFound) {}
+    fn invalid_key(&mut self, r: &InvalidKey) {}
+    fn start_tls(&mut self, r: &StartTLS) {}
+    fn who_is_secure(&mut self, r: &WhoIsSecure) {}
+    fn start_tls_error(&mut self, r: &StartTLSError) {}
+    fn invalid_mode_param(&mut self, r: &InvalidModeParam) {}
+    fn help_start(&mut self, r: &HelpStart) {}
+    fn help_txt(&mut self, r: &HelpTxt) {}
+    fn end_of_help(&mut self, r: &EndOfHelp) {}
+    fn no_privs(&mut self, r: &NoPrivs) {}
+    fn logged_in(&mut self, r: &LoggedIn) {}
+    fn logged_out(&mut self, r: &LoggedOut) {}
+    fn nick_locked(&mut self, r: &NickLocked) {}
+    fn sasl_success(&mut self, r: &SaslSuccess) {}
+    fn sasl_fail(&mut self, r: &SaslFail) {}
+    fn sasl_too_long(&mut self, r: &SaslTooLong) {}
+    fn sasl_aborted(&mut self, r: &SaslAborted) {}
+    fn sasl_already(&mut self, r: &SaslAlready) {}
+    fn sasl_mechs(&mut self, r: &SaslMechs) {}
+    fn unknown(&mut self, r: &Unknown) {}
+}
+
+impl Reply {
+    /// Routes this reply to `visitor`'s matching [`ReplyVisitor`] method,
+    /// after first calling [`ReplyVisitor::on_reply`].
+    pub fn accept(&self, visitor: &mut impl ReplyVisitor) {
+        visitor.on_reply(self);
+        match self {
+            Reply::Welcome(r) => visitor.welcome(r),
+            Reply::YourHost(r) => visitor.your_host(r),
+            Reply::Created(r) => visitor.created(r),
+            Reply::MyInfo(r) => visitor.my_info(r),
+            Reply::ISupport(r) => visitor.isupport(r),
+            Reply::RemoteISupport(r) => visitor.remote_isupport(r),
+            Reply::Bounce(r) => visitor.bounce(r),
+            Reply::StatsCommands(r) => visitor.stats_commands(r),
+            Reply::EndOfStats(r) => visitor.end_of_stats(r),
+            Reply::UModeIs(r) => visitor.umode_is(r),
+            Reply::StatsUptime(r) => visitor.stats_uptime(r),
+            Reply::LuserClient(r) => visitor.luser_client(r),
+            Reply::LuserOp(r) => visitor.luser_op(r),
+            Reply::LuserUnknown(r) => visitor.luser_unknown(r),
+            Reply::LuserChannels(r) => visitor.luser_channels(r),
+            Reply::LuserMe(r) => visitor.luser_me(r),
+            Reply::AdminMe(r) => visitor.admin_me(r),
+            Reply::AdminLoc1(r) => visitor.admin_loc1(r),
+            Reply::AdminLoc2(r) => visitor.admin_loc2(r),
+            Reply::AdminEmail(r) => visitor.admin_email(r),
+            Reply::TryAgain(r) => visitor.try_again(r),
+            Reply::LocalUsers(r) => visitor.local_users(r),
+            Reply::GlobalUsers(r) => visitor.global_users(r),
+            Reply::WhoIsCertFP(r) => visitor.who_is_cert_fp(r),
+            Reply::None(r) => visitor.none(r),
+            Reply::Away(r) => visitor.away(r),
+            Reply::UserHostRpl(r) => visitor.user_host_rpl(r),
+            Reply::UnAway(r) => visitor.un_away(r),
+            Reply::NowAway(r) => visitor.now_away(r),
+            Reply::WhoIsRegNick(r) => visitor.who_is_reg_nick(r),
+            Reply::WhoIsUser(r) => visitor.who_is_user(r),
+            Reply::WhoIsServer(r) => visitor.who_is_server(r),
+            Reply::WhoIsOperator(r) => visitor.who_is_operator(r),
+            Reply::WhoWasUser(r) => visitor.who_was_user(r),
+            Reply::EndOfWho(r) => visitor.end_of_who(r),
+            Reply::WhoIsIdle(r) => visitor.who_is_idle(r),
+            Reply::EndOfWhoIs(r) => visitor.end_of_who_is(r),
+            Reply::WhoIsChannels(r) => visitor.who_is_channels(r),
+            Reply::WhoIsSpecial(r) => visitor.who_is_special(r),
+            Reply::ListStart(r) => visitor.list_start(r),
+            Reply::List(r) => visitor.list(r),
+            Reply::ListEnd(r) => visitor.list_end(r),
+            Reply::ChannelModeIs(r) => visitor.channel_mode_is(r),
+            Reply::CreationTime(r) => visitor.creation_time(r),
+            Reply::WhoIsAccount(r) => visitor.who_is_account(r),
+            Reply::NoTopic(r) => visitor.no_topic(r),
+            Reply::Topic(r) => visitor.topic(r),
+            Reply::TopicWhoTime(r) => visitor.topic_who_time(r),
+            Reply::InviteList(r) => visitor.invite_list(r),
+            Reply::EndOfInviteList(r) => visitor.end_of_invite_list(r),
+            Reply::WhoIsActually(r) => visitor.who_is_actually(r),
+            Reply::Inviting(r) => visitor.inviting(r),
+            Reply::InvExList(r) => visitor.inv_ex_list(r),
+            Reply::EndOfInvExList(r) => visitor.end_of_inv_ex_list(r),
+            Reply::ExceptList(r) => visitor.except_list(r),
+            Reply::EndOfExceptList(r) => visitor.end_of_except_list(r),
+            Reply::Version(r) => visitor.version(r),
+            Reply::WhoReply(r) => visitor.who_reply(r),
+            Reply::WhoSpcRpl(r) => visitor.who_spc_rpl(r),
+            Reply::NamReply(r) => visitor.nam_reply(r),
+            Reply::Links(r) => visitor.links(r),
+            Reply::EndOfLinks(r) => visitor.end_of_links(r),
+            Reply::EndOfNames(r) => visitor.end_of_names(r),
+            Reply::BanList(r) => visitor.ban_list(r),
+            Reply::EndOfBanList(r) => visitor.end_of_ban_list(r),
+            Reply::EndOfWhoWas(r) => visitor.end_of_who_was(r),
+            Reply::Info(r) => visitor.info(r),
+            Reply::Motd(r) => visitor.motd(r),
+            Reply::EndOfInfo(r) => visitor.end_of_info(r),
+            Reply::MotdStart(r) => visitor.motd_start(r),
+            Reply::EndOfMotd(r) => visitor.end_of_motd(r),
+            Reply::WhoIsHost(r) => visitor.who_is_host(r),
+            Reply::WhoIsModes(r) => visitor.who_is_modes(r),
+            Reply::YoureOper(r) => visitor.youre_oper(r),
+            Reply::Rehashing(r) => visitor.rehashing(r),
+            Reply::Time(r) => visitor.time(r),
+            Reply::UnknownError(r) => visitor.unknown_error(r),
+            Reply::NoSuchNick(r) => visitor.no_such_nick(r),
+            Reply::NoSuchServer(r) => visitor.no_such_server(r),
+            Reply::NoSuchChannel(r) => visitor.no_such_channel(r),
+            Reply::CannotSendToChan(r) => visitor.cannot_send_to_chan(r),
+            Reply::TooManyChannels(r) => visitor.too_many_channels(r),
+            Reply::WasNoSuchNick(r) => visitor.was_no_such_nick(r),
+            Reply::NoOrigin(r) => visitor.no_origin(r),
+            Reply::NoRecipient(r) => visitor.no_recipient(r),
+            Reply::NoTextToSend(r) => visitor.no_text_to_send(r),
+            Reply::InputTooLong(r) => visitor.input_too_long(r),
+            Reply::UnknownCommand(r) => visitor.unknown_command(r),
+            Reply::NoMotd(r) => visitor.no_motd(r),
+            Reply::NoNicknameGiven(r) => visitor.no_nickname_given(r),
+            Reply::ErroneousNickname(r) => visitor.erroneous_nickname(r),
+            Reply::NicknameInUse(r) => visitor.nickname_in_use(r),
+            Reply::NickCollision(r) => visitor.nick_collision(r),
+            Reply::UserNotInChannel(r) => visitor.user_not_in_channel(r),
+            Reply::NotOnChannel(r) => visitor.not_on_channel(r),
+            Reply::UserOnChannel(r) => visitor.user_on_channel(r),
+            Reply::NotRegistered(r) => visitor.not_registered(r),
+            Reply::NeedMoreParams(r) => visitor.need_more_params(r),
+            Reply::AlreadyRegistered(r) => visitor.already_registered(r),
+            Reply::PasswdMismatch(r) => visitor.passwd_mismatch(r),
+            Reply::YoureBannedCreep(r) => visitor.youre_banned_creep(r),
+            Reply::ChannelIsFull(r) => visitor.channel_is_full(r),
+            Reply::UnknownMode(r) => visitor.unknown_mode(r),
+            Reply::InviteOnlyChan(r) => visitor.invite_only_chan(r),
+            Reply::BannedFromChan(r) => visitor.banned_from_chan(r),
+            Reply::BadChannelKey(r) => visitor.bad_channel_key(r),
+            Reply::BadChanMask(r) => visitor.bad_chan_mask(r),
+            Reply::NoPrivileges(r) => visitor.no_privileges(r),
+            Reply::ChanOPrivsNeeded(r) => visitor.chan_oprivs_needed(r),
+            Reply::CantKillServer(r) => visitor.cant_kill_server(r),
+            Reply::NoOperHost(r) => visitor.no_oper_host(r),
+            Reply::UmodeUnknownFlag(r) => visitor.umode_unknown_flag(r),
+            Reply::UsersDontMatch(r) => visitor.users_dont_match(r),
+            Reply::HelpNotFound(r) => visitor.help_not_found(r),
+            Reply::InvalidKey(r) => visitor.invalid_key(r),
+            Reply::StartTLS(r) => visitor.start_tls(r),
+            Reply::WhoIsSecure(r) => visitor.who_is_secure(r),
+            Reply::StartTLSError(r) => visitor.start_tls_error(r),
+            Reply::InvalidModeParam(r) => visitor.invalid_mode_param(r),
+            Reply::HelpStart(r) => visitor.help_start(r),
+            Reply::HelpTxt(r) => visitor.help_txt(r),
+            Reply::EndOfHelp(r) => visitor.end_of_help(r),
+            Reply::NoPrivs(r) => visitor.no_privs(r),
+            Reply::LoggedIn(r) => visitor.logged_in(r),
+            Reply::LoggedOut(r) => visitor.logged_out(r),
+            Reply::NickLocked(r) => visitor.nick_locked(r),
+            Reply::SaslSuccess(r) => visitor.sasl_success(r),
+            Reply::SaslFail(r) => visitor.sasl_fail(r),
+            Reply::SaslTooLong(r) => visitor.sasl_too_long(r),
+            Reply::SaslAborted(r) => visitor.sasl_aborted(r),
+            Reply::SaslAlready(r) => visitor.sasl_already(r),
+            Reply::SaslMechs(r) => visitor.sasl_mechs(r),
+            Reply::Unknown(r) => visitor.unknown(r),
+        }
+    }
+}
+
+/// Wraps every reply whose [`ReplyParts::is_error`] returns `true`, so a
+/// numeric failure can be propagated as a Rust error with `?` (see
+/// [`Reply::into_result`]) instead of hand-matching codes. `Display` renders
+/// the wrapped reply's target and trailing message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ServerError {
+    UnknownError(UnknownError),
+    NoSuchNick(NoSuchNick),
+    NoSuchServer(NoSuchServer),
+    NoSuchChannel(NoSuchChannel),
+    CannotSendToChan(CannotSendToChan),
+    TooManyChannels(TooManyChannels),
+    WasNoSuchNick(WasNoSuchNick),
+    NoOrigin(NoOrigin),
+    NoRecipient(NoRecipient),
+    NoTextToSend(NoTextToSend),
+    InputTooLong(InputTooLong),
+    UnknownCommand(UnknownCommand),
+    NoMotd(NoMotd),
+    NoNicknameGiven(NoNicknameGiven),
+    ErroneousNickname(ErroneousNickname),
+    NicknameInUse(NicknameInUse),
+    NickCollision(NickCollision),
+    UserNotInChannel(UserNotInChannel),
+    NotOnChannel(NotOnChannel),
+    UserOnChannel(UserOnChannel),
+    NotRegistered(NotRegistered),
+    NeedMoreParams(NeedMoreParams),
+    AlreadyRegistered(AlreadyRegistered),
+    PasswdMismatch(PasswdMismatch),
+    YoureBannedCreep(YoureBannedCreep),
+    ChannelIsFull(ChannelIsFull),
+    UnknownMode(UnknownMode),
+    InviteOnlyChan(InviteOnlyChan),
+    BannedFromChan(BannedFromChan),
+    BadChannelKey(BadChannelKey),
+    BadChanMask(BadChanMask),
+    NoPrivileges(NoPrivileges),
+    ChanOPrivsNeeded(ChanOPrivsNeeded),
+    CantKillServer(CantKillServer),
+    NoOperHost(NoOperHost),
+    UmodeUnknownFlag(UmodeUnknownFlag),
+    UsersDontMatch(UsersDontMatch),
+    HelpNotFound(HelpNotFound),
+    InvalidKey(InvalidKey),
+    StartTLSError(StartTLSError),
+    InvalidModeParam(InvalidModeParam),
+    NoPrivs(NoPrivs),
+    NickLocked(NickLocked),
+    SaslFail(SaslFail),
+    SaslTooLong(SaslTooLong),
+    SaslAborted(SaslAborted),
+    SaslAlready(SaslAlready),
+}
+
+impl ServerError {
+    /// The wrapped reply's numeric code.
+    pub fn code(&self) -> u16 {
+        match self {
+            ServerError::UnknownError(r) => r.code(),
+            ServerError::NoSuchNick(r) => r.code(),
+            ServerError::NoSuchServer(r) => r.code(),
+            ServerError::NoSuchChannel(r) => r.code(),
+            ServerError::CannotSendToChan(r) => r.code(),
+            ServerError::TooManyChannels(r) => r.code(),
+            ServerError::WasNoSuchNick(r) => r.code(),
+            ServerError::NoOrigin(r) => r.code(),
+            ServerError::NoRecipient(r) => r.code(),
+            ServerError::NoTextToSend(r) => r.code(),
+            ServerError::InputTooLong(r) => r.code(),
+            ServerError::UnknownCommand(r) => r.code(),
+            ServerError::NoMotd(r) => r.code(),
+            ServerError::NoNicknameGiven(r) => r.code(),
+            ServerError::ErroneousNickname(r) => r.code(),
+            ServerError::NicknameInUse(r) => r.code(),
+            ServerError::NickCollision(r) => r.code(),
+            ServerError::UserNotInChannel(r) => r.code(),
+            ServerError::NotOnChannel(r) => r.code(),
+            ServerError::UserOnChannel(r) => r.code(),
+            ServerError::NotRegistered(r) => r.code(),
+            ServerError::NeedMoreParams(r) => r.code(),
+            ServerError::AlreadyRegistered(r) => r.code(),
+            ServerError::PasswdMismatch(r) => r.code(),
+            ServerError::YoureBannedCreep(r) => r.code(),
+            ServerError::ChannelIsFull(r) => r.code(),
+            ServerError::UnknownMode(r) => r.code(),
+            ServerError::InviteOnlyChan(r) => r.code(),
+            ServerError::BannedFromChan(r) => r.code(),
+            ServerError::BadChannelKey(r) => r.code(),
+            ServerError::BadChanMask(r) => r.code(),
+            ServerError::NoPrivileges(r) => r.code(),
+            ServerError::ChanOPrivsNeeded(r) => r.code(),
+            ServerError::CantKillServer(r) => r.code(),
+            ServerError::NoOperHost(r) => r.code(),
+            ServerError::UmodeUnknownFlag(r) => r.code(),
+            ServerError::UsersDontMatch(r) => r.code(),
+            ServerError::HelpNotFound(r) => r.code(),
+            ServerError::InvalidKey(r) => r.code(),
+            ServerError::StartTLSError(r) => r.code(),
+            ServerError::InvalidModeParam(r) => r.code(),
+            ServerError::NoPrivs(r) => r.code(),
+            ServerError::NickLocked(r) => r.code(),
+            ServerError::SaslFail(r) => r.code(),
+            ServerError::SaslTooLong(r) => r.code(),
+            ServerError::SaslAborted(r) => r.code(),
+            ServerError::SaslAlready(r) => r.code(),
+        }
+    }
+
+    /// The wrapped reply's target client.
+    pub fn client(&self) -> &ReplyTarget {
+        match self {
+            ServerError::UnknownError(r) => r.client(),
+            ServerError::NoSuchNick(r) => r.client(),
+            ServerError::NoSuchServer(r) => r.client(),
+            ServerError::NoSuchChannel(r) => r.client(),
+            ServerError::CannotSendToChan(r) => r.client(),
+            ServerError::TooManyChannels(r) => r.client(),
+            ServerError::WasNoSuchNick(r) => r.client(),
+            ServerError::NoOrigin(r) => r.client(),
+            ServerError::NoRecipient(r) => r.client(),
+            ServerError::NoTextToSend(r) => r.client(),
+            ServerError::InputTooLong(r) => r.client(),
+            ServerError::UnknownCommand(r) => r.client(),
+            ServerError::NoMotd(r) => r.client(),
+            ServerError::NoNicknameGiven(r) => r.client(),
+            ServerError::ErroneousNickname(r) => r.client(),
+            ServerError::NicknameInUse(r) => r.client(),
+            ServerError::NickCollision(r) => r.client(),
+            ServerError::UserNotInChannel(r) => r.client(),
+            ServerError::NotOnChannel(r) => r.client(),
+            ServerError::UserOnChannel(r) => r.client(),
+            ServerError::NotRegistered(r) => r.client(),
+            ServerError::NeedMoreParams(r) => r.client(),
+            ServerError::AlreadyRegistered(r) => r.client(),
+            ServerError::PasswdMismatch(r) => r.client(),
+            ServerError::YoureBannedCreep(r) => r.client(),
+            ServerError::ChannelIsFull(r) => r.client(),
+            ServerError::UnknownMode(r) => r.client(),
+            ServerError::InviteOnlyChan(r) => r.client(),
+            ServerError::BannedFromChan(r) => r.client(),
+            ServerError::BadChannelKey(r) => r.client(),
+            ServerError::BadChanMask(r) => r.client(),
+            ServerError::NoPrivileges(r) => r.client(),
+            ServerError::ChanOPrivsNeeded(r) => r.client(),
+            ServerError::CantKillServer(r) => r.client(),
+            ServerError::NoOperHost(r) => r.client(),
+            ServerError::UmodeUnknownFlag(r) => r.client(),
+            ServerError::UsersDontMatch(r) => r.client(),
+            ServerError::HelpNotFound(r) => r.client(),
+            ServerError::InvalidKey(r) => r.client(),
+            ServerError::StartTLSError(r) => r.client(),
+            ServerError::InvalidModeParam(r) => r.client(),
+            ServerError::NoPrivs(r) => r.client(),
+            ServerError::NickLocked(r) => r.client(),
+            ServerError::SaslFail(r) => r.client(),
+            ServerError::SaslTooLong(r) => r.client(),
+            ServerError::SaslAborted(r) => r.client(),
+            ServerError::SaslAlready(r) => r.client(),
+        }
+    }
+
+    /// The wrapped reply's trailing human-readable message.
+    pub fn message(&self) -> &str {
+        match self {
+            ServerError::UnknownError(r) => r.message(),
+            ServerError::NoSuchNick(r) => r.message(),
+            ServerError::NoSuchServer(r) => r.message(),
+            ServerError::NoSuchChannel(r) => r.message(),
+            ServerError::CannotSendToChan(r) => r.message(),
+            ServerError::TooManyChannels(r) => r.message(),
+            ServerError::WasNoSuchNick(r) => r.message(),
+            ServerError::NoOrigin(r) => r.message(),
+            ServerError::NoRecipient(r) => r.message(),
+            ServerError::NoTextToSend(r) => r.message(),
+            ServerError::InputTooLong(r) => r.message(),
+            ServerError::UnknownCommand(r) => r.message(),
+            ServerError::NoMotd(r) => r.message(),
+            ServerError::NoNicknameGiven(r) => r.message(),
+            ServerError::ErroneousNickname(r) => r.message(),
+            ServerError::NicknameInUse(r) => r.message(),
+            ServerError::NickCollision(r) => r.message(),
+            ServerError::UserNotInChannel(r) => r.message(),
+            ServerError::NotOnChannel(r) => r.message(),
+            ServerError::UserOnChannel(r) => r.message(),
+            ServerError::NotRegistered(r) => r.message(),
+            ServerError::NeedMoreParams(r) => r.message(),
+            ServerError::AlreadyRegistered(r) => r.message(),
+            ServerError::PasswdMismatch(r) => r.message(),
+            ServerError::YoureBannedCreep(r) => r.message(),
+            ServerError::ChannelIsFull(r) => r.message(),
+            ServerError::UnknownMode(r) => r.message(),
+            ServerError::InviteOnlyChan(r) => r.message(),
+            ServerError::BannedFromChan(r) => r.message(),
+            ServerError::BadChannelKey(r) => r.message(),
+            ServerError::BadChanMask(r) => r.message(),
+            ServerError::NoPrivileges(r) => r.message(),
+            ServerError::ChanOPrivsNeeded(r) => r.message(),
+            ServerError::CantKillServer(r) => r.message(),
+            ServerError::NoOperHost(r) => r.message(),
+            ServerError::UmodeUnknownFlag(r) => r.message(),
+            ServerError::UsersDontMatch(r) => r.message(),
+            ServerError::HelpNotFound(r) => r.message(),
+            ServerError::InvalidKey(r) => r.message(),
+            ServerError::StartTLSError(r) => r.message(),
+            ServerError::InvalidModeParam(r) => r.message(),
+            ServerError::NoPrivs(r) => r.message(),
+            ServerError::NickLocked(r) => r.message(),
+            ServerError::SaslFail(r) => r.message(),
+            ServerError::SaslTooLong(r) => r.message(),
+            ServerError::SaslAborted(r) => r.message(),
+            ServerError::SaslAlready(r) => r.message(),
+        }
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (numeric {}): {}", self.client(), self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+impl From<ServerError> for Reply {
+    fn from(value: ServerError) -> Reply {
+        match value {
+            ServerError::UnknownError(r) => Reply::UnknownError(r),
+            ServerError::NoSuchNick(r) => Reply::NoSuchNick(r),
+            ServerError::NoSuchServer(r) => Reply::NoSuchServer(r),
+            ServerError::NoSuchChannel(r) => Reply::NoSuchChannel(r),
+            ServerError::CannotSendToChan(r) => Reply::CannotSendToChan(r),
+            ServerError::TooManyChannels(r) => Reply::TooManyChannels(r),
+            ServerError::WasNoSuchNick(r) => Reply::WasNoSuchNick(r),
+            ServerError::NoOrigin(r) => Reply::NoOrigin(r),
+            ServerError::NoRecipient(r) => Reply::NoRecipient(r),
+            ServerError::NoTextToSend(r) => Reply::NoTextToSend(r),
+            ServerError::InputTooLong(r) => Reply::InputTooLong(r),
+            ServerError::UnknownCommand(r) => Reply::UnknownCommand(r),
+            ServerError::NoMotd(r) => Reply::NoMotd(r),
+            ServerError::NoNicknameGiven(r) => Reply::NoNicknameGiven(r),
+            ServerError::ErroneousNickname(r) => Reply::ErroneousNickname(r),
+            ServerError::NicknameInUse(r) => Reply::NicknameInUse(r),
+            ServerError::NickCollision(r) => Reply::NickCollision(r),
+            ServerError::UserNotInChannel(r) => Reply::UserNotInChannel(r),
+            ServerError::NotOnChannel(r) => Reply::NotOnChannel(r),
+            ServerError::UserOnChannel(r) => Reply::UserOnChannel(r),
+            ServerError::NotRegistered(r) => Reply::NotRegistered(r),
+            ServerError::NeedMoreParams(r) => Reply::NeedMoreParams(r),
+            ServerError::AlreadyRegistered(r) => Reply::AlreadyRegistered(r),
+            ServerError::PasswdMismatch(r) => Reply::PasswdMismatch(r),
+            ServerError::YoureBannedCreep(r) => Reply::YoureBannedCreep(r),
+            ServerError::ChannelIsFull(r) => Reply::ChannelIsFull(r),
+            ServerError::UnknownMode(r) => Reply::UnknownMode(r),
+            ServerError::InviteOnlyChan(r) => Reply::InviteOnlyChan(r),
+            ServerError::BannedFromChan(r) => Reply::BannedFromChan(r),
+            ServerError::BadChannelKey(r) => Reply::BadChannelKey(r),
+            ServerError::BadChanMask(r) => Reply::BadChanMask(r),
+            ServerError::NoPrivileges(r) => Reply::NoPrivileges(r),
+            ServerError::ChanOPrivsNeeded(r) => Reply::ChanOPrivsNeeded(r),
+            ServerError::CantKillServer(r) => Reply::CantKillServer(r),
+            ServerError::NoOperHost(r) => Reply::NoOperHost(r),
+            ServerError::UmodeUnknownFlag(r) => Reply::UmodeUnknownFlag(r),
+            ServerError::UsersDontMatch(r) => Reply::UsersDontMatch(r),
+            ServerError::HelpNotFound(r) => Reply::HelpNotFound(r),
+            ServerError::InvalidKey(r) => Reply::InvalidKey(r),
+            ServerError::StartTLSError(r) => Reply::StartTLSError(r),
+            ServerError::InvalidModeParam(r) => Reply::InvalidModeParam(r),
+            ServerError::NoPrivs(r) => Reply::NoPrivs(r),
+            ServerError::NickLocked(r) => Reply::NickLocked(r),
+            ServerError::SaslFail(r) => Reply::SaslFail(r),
+            ServerError::SaslTooLong(r) => Reply::SaslTooLong(r),
+            ServerError::SaslAborted(r) => Reply::SaslAborted(r),
+            ServerError::SaslAlready(r) => Reply::SaslAlready(r),
+        }
+    }
+}
+
+impl TryFrom<Reply> for ServerError {
+    type Error = Reply;
+
+    fn try_from(reply: Reply) -> Result<ServerError, Reply> {
+        match reply {
+            Reply::UnknownError(r) => Ok(ServerError::UnknownError(r)),
+            Reply::NoSuchNick(r) => Ok(ServerError::NoSuchNick(r)),
+            Reply::NoSuchServer(r) => Ok(ServerError::NoSuchServer(r)),
+            Reply::NoSuchChannel(r) => Ok(ServerError::NoSuchChannel(r)),
+            Reply::CannotSendToChan(r) => Ok(ServerError::CannotSendToChan(r)),
+            Reply::TooManyChannels(r) => Ok(ServerError::TooManyChannels(r)),
+            Reply::WasNoSuchNick(r) => Ok(ServerError::WasNoSuchNick(r)),
+            Reply::NoOrigin(r) => Ok(ServerError::NoOrigin(r)),
+            Reply::NoRecipient(r) => Ok(ServerError::NoRecipient(r)),
+            Reply::NoTextToSend(r) => Ok(ServerError::NoTextToSend(r)),
+            Reply::InputTooLong(r) => Ok(ServerError::InputTooLong(r)),
+            Reply::UnknownCommand(r) => Ok(ServerError::UnknownCommand(r)),
+            Reply::NoMotd(r) => Ok(ServerError::NoMotd(r)),
+            Reply::NoNicknameGiven(r) => Ok(ServerError::NoNicknameGiven(r)),
+            Reply::ErroneousNickname(r) => Ok(ServerError::ErroneousNickname(r)),
+            Reply::NicknameInUse(r) => Ok(ServerError::NicknameInUse(r)),
+            Reply::NickCollision(r) => Ok(ServerError::NickCollision(r)),
+            Reply::UserNotInChannel(r) => Ok(ServerError::UserNotInChannel(r)),
+            Reply::NotOnChannel(r) => Ok(ServerError::NotOnChannel(r)),
+            Reply::UserOnChannel(r) => Ok(ServerError::UserOnChannel(r)),
+            Reply::NotRegistered(r) => Ok(ServerError::NotRegistered(r)),
+            Reply::NeedMoreParams(r) => Ok(ServerError::NeedMoreParams(r)),
+            Reply::AlreadyRegistered(r) => Ok(ServerError::AlreadyRegistered(r)),
+            Reply::PasswdMismatch(r) => Ok(ServerError::PasswdMismatch(r)),
+            Reply::YoureBannedCreep(r) => Ok(ServerError::YoureBannedCreep(r)),
+            Reply::ChannelIsFull(r) => Ok(ServerError::ChannelIsFull(r)),
+            Reply::UnknownMode(r) => Ok(ServerError::UnknownMode(r)),
+            Reply::InviteOnlyChan(r) => Ok(ServerError::InviteOnlyChan(r)),
+            Reply::BannedFromChan(r) => Ok(ServerError::BannedFromChan(r)),
+            Reply::BadChannelKey(r) => Ok(ServerError::BadChannelKey(r)),
+            Reply::BadChanMask(r) => Ok(ServerError::BadChanMask(r)),
+            Reply::NoPrivileges(r) => Ok(ServerError::NoPrivileges(r)),
+            Reply::ChanOPrivsNeeded(r) => Ok(ServerError::ChanOPrivsNeeded(r)),
+            Reply::CantKillServer(r) => Ok(ServerError::CantKillServer(r)),
+            Reply::NoOperHost(r) => Ok(ServerError::NoOperHost(r)),
+            Reply::UmodeUnknownFlag(r) => Ok(ServerError::UmodeUnknownFlag(r)),
+            Reply::UsersDontMatch(r) => Ok(ServerError::UsersDontMatch(r)),
+            Reply::HelpNotFound(r) => Ok(ServerError::HelpNotFound(r)),
+            Reply::InvalidKey(r) => Ok(ServerError::InvalidKey(r)),
+            Reply::StartTLSError(r) => Ok(ServerError::StartTLSError(r)),
+            Reply::InvalidModeParam(r) => Ok(ServerError::InvalidModeParam(r)),
+            Reply::NoPrivs(r) => Ok(ServerError::NoPrivs(r)),
+            Reply::NickLocked(r) => Ok(ServerError::NickLocked(r)),
+            Reply::SaslFail(r) => Ok(ServerError::SaslFail(r)),
+            Reply::SaslTooLong(r) => Ok(ServerError::SaslTooLong(r)),
+            Reply::SaslAborted(r) => Ok(ServerError::SaslAborted(r)),
+            Reply::SaslAlready(r) => Ok(ServerError::SaslAlready(r)),
+            other => Err(other),
+        }
+    }
+}
+
+impl Reply {
+    /// Converts an error reply ([`ReplyParts::is_error`] `true`) into
+    /// `Err(ServerError)`, leaving every other reply as `Ok(self)`, so a
+    /// connection's read loop can propagate numeric failures with `?`.
+    pub fn into_result(self) -> Result<Reply, ServerError> {
+        match ServerError::try_from(self) {
+            Ok(err) => Err(err),
+            Err(reply) => Ok(reply),
+        }
+    }
+}
+
+/// Each error-reply struct (every `ReplyParts::is_error` `true` type --
+/// also collected under [`ServerError`]) implements `Display`/`Error`
+/// directly too, rendering as `"<code> <message>"`, so one can be used as a
+/// `Box<dyn Error>` on its own without first wrapping it in `ServerError`.
+impl fmt::Display for UnknownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UnknownError {}
+
+impl fmt::Display for NoSuchNick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoSuchNick {}
+
+impl fmt::Display for NoSuchServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoSuchServer {}
+
+impl fmt::Display for NoSuchChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoSuchChannel {}
+
+impl fmt::Display for CannotSendToChan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for CannotSendToChan {}
+
+impl fmt::Display for TooManyChannels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for TooManyChannels {}
+
+impl fmt::Display for WasNoSuchNick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for WasNoSuchNick {}
+
+impl fmt::Display for NoOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoOrigin {}
+
+impl fmt::Display for NoRecipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoRecipient {}
+
+impl fmt::Display for NoTextToSend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoTextToSend {}
+
+impl fmt::Display for InputTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for InputTooLong {}
+
+impl fmt::Display for UnknownCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UnknownCommand {}
+
+impl fmt::Display for NoMotd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoMotd {}
+
+impl fmt::Display for NoNicknameGiven {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoNicknameGiven {}
+
+impl fmt::Display for ErroneousNickname {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ErroneousNickname {}
+
+impl fmt::Display for NicknameInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NicknameInUse {}
+
+impl fmt::Display for NickCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NickCollision {}
+
+impl fmt::Display for UserNotInChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UserNotInChannel {}
+
+impl fmt::Display for NotOnChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NotOnChannel {}
+
+impl fmt::Display for UserOnChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UserOnChannel {}
+
+impl fmt::Display for NotRegistered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NotRegistered {}
+
+impl fmt::Display for NeedMoreParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NeedMoreParams {}
+
+impl fmt::Display for AlreadyRegistered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for AlreadyRegistered {}
+
+impl fmt::Display for PasswdMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for PasswdMismatch {}
+
+impl fmt::Display for YoureBannedCreep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for YoureBannedCreep {}
+
+impl fmt::Display for ChannelIsFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ChannelIsFull {}
+
+impl fmt::Display for UnknownMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UnknownMode {}
+
+impl fmt::Display for InviteOnlyChan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for InviteOnlyChan {}
+
+impl fmt::Display for BannedFromChan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for BannedFromChan {}
+
+impl fmt::Display for BadChannelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for BadChannelKey {}
+
+impl fmt::Display for BadChanMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for BadChanMask {}
+
+impl fmt::Display for NoPrivileges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoPrivileges {}
+
+impl fmt::Display for ChanOPrivsNeeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ChanOPrivsNeeded {}
+
+impl fmt::Display for CantKillServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for CantKillServer {}
+
+impl fmt::Display for NoOperHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoOperHost {}
+
+impl fmt::Display for UmodeUnknownFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UmodeUnknownFlag {}
+
+impl fmt::Display for UsersDontMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for UsersDontMatch {}
+
+impl fmt::Display for HelpNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for HelpNotFound {}
+
+impl fmt::Display for InvalidKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for InvalidKey {}
+
+impl fmt::Display for StartTLSError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for StartTLSError {}
+
+impl fmt::Display for InvalidModeParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for InvalidModeParam {}
+
+impl fmt::Display for NoPrivs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NoPrivs {}
+
+impl fmt::Display for NickLocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for NickLocked {}
+
+impl fmt::Display for SaslFail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for SaslFail {}
+
+impl fmt::Display for SaslTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for SaslTooLong {}
+
+impl fmt::Display for SaslAborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for SaslAborted {}
+
+impl fmt::Display for SaslAlready {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for SaslAlready {}
+
+
+/// The reasons a `JOIN` (or a subsequent channel-mode change) can be
+/// rejected by the server, unified into a single type so a client doesn't
+/// have to re-derive the code-to-recovery mapping itself: prompt for a key
+/// on [`Self::needs_key`], request an invite on [`Self::is_invite_only`],
+/// back off on [`Self::is_banned`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JoinError {
+    ChannelIsFull(ChannelIsFull),
+    InviteOnlyChan(InviteOnlyChan),
+    BannedFromChan(BannedFromChan),
+    BadChannelKey(BadChannelKey),
+    BadChanMask(BadChanMask),
+    ChanOPrivsNeeded(ChanOPrivsNeeded),
+}
+
+impl JoinError {
+    pub fn code(&self) -> u16 {
+        match self {
+            JoinError::ChannelIsFull(r) => r.code(),
+            JoinError::InviteOnlyChan(r) => r.code(),
+            JoinError::BannedFromChan(r) => r.code(),
+            JoinError::BadChannelKey(r) => r.code(),
+            JoinError::BadChanMask(r) => r.code(),
+            JoinError::ChanOPrivsNeeded(r) => r.code(),
+        }
+    }
+
+    pub fn client(&self) -> &ReplyTarget {
+        match self {
+            JoinError::ChannelIsFull(r) => r.client(),
+            JoinError::InviteOnlyChan(r) => r.client(),
+            JoinError::BannedFromChan(r) => r.client(),
+            JoinError::BadChannelKey(r) => r.client(),
+            JoinError::BadChanMask(r) => r.client(),
+            JoinError::ChanOPrivsNeeded(r) => r.client(),
+        }
+    }
+
+    pub fn channel(&self) -> &str {
+        match self {
+            JoinError::ChannelIsFull(r) => r.channel().as_str(),
+            JoinError::InviteOnlyChan(r) => r.channel().as_str(),
+            JoinError::BannedFromChan(r) => r.channel().as_str(),
+            JoinError::BadChannelKey(r) => r.channel().as_str(),
+            JoinError::BadChanMask(r) => r.channel(),
+            JoinError::ChanOPrivsNeeded(r) => r.channel().as_str(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            JoinError::ChannelIsFull(r) => r.message(),
+            JoinError::InviteOnlyChan(r) => r.message(),
+            JoinError::BannedFromChan(r) => r.message(),
+            JoinError::BadChannelKey(r) => r.message(),
+            JoinError::BadChanMask(r) => r.message(),
+            JoinError::ChanOPrivsNeeded(r) => r.message(),
+        }
+    }
+
+    /// True if rejoining would require supplying (or correcting) a channel key.
+    pub fn needs_key(&self) -> bool {
+        matches!(self, JoinError::BadChannelKey(_))
+    }
+
+    /// True if the channel is invite-only and an invite is needed before
+    /// joining can succeed.
+    pub fn is_invite_only(&self) -> bool {
+        matches!(self, JoinError::InviteOnlyChan(_))
+    }
+
+    /// True if the client is banned from the channel.
+    pub fn is_banned(&self) -> bool {
+        matches!(self, JoinError::BannedFromChan(_))
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (numeric {}): {}",
+            self.channel(),
+            self.code(),
+            self.message()
+        )
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+impl From<JoinError> for Reply {
+    fn from(value: JoinError) -> Reply {
+        match value {
+            JoinError::ChannelIsFull(r) => Reply::ChannelIsFull(r),
+            JoinError::InviteOnlyChan(r) => Reply::InviteOnlyChan(r),
+            JoinError::BannedFromChan(r) => Reply::BannedFromChan(r),
+            JoinError::BadChannelKey(r) => Reply::BadChannelKey(r),
+            JoinError::BadChanMask(r) => Reply::BadChanMask(r),
+            JoinError::ChanOPrivsNeeded(r) => Reply::ChanOPrivsNeeded(r),
+        }
+    }
+}
+
+impl TryFrom<Reply> for JoinError {
+    type Error = Reply;
+
+    fn try_from(reply: Reply) -> Result<JoinError, Reply> {
+        match reply {
+            Reply::ChannelIsFull(r) => Ok(JoinError::ChannelIsFull(r)),
+            Reply::InviteOnlyChan(r) => Ok(JoinError::InviteOnlyChan(r)),
+            Reply::BannedFromChan(r) => Ok(JoinError::BannedFromChan(r)),
+            Reply::BadChannelKey(r) => Ok(JoinError::BadChannelKey(r)),
+            Reply::BadChanMask(r) => Ok(JoinError::BadChanMask(r)),
+            Reply::ChanOPrivsNeeded(r) => Ok(JoinError::ChanOPrivsNeeded(r)),
+            other => Err(other),
+        }
+    }
+}
@@ -14,6 +14,7 @@ mod whoisactually {
         assert_matches!(msg, Message {
             source: Some(Source::Server(host)),
             payload: Payload::Reply(Reply::WhoIsActually(r)),
+            ..
         } => {
             assert_eq!(host, Host::Domain("molybdenum.libera.chat"));
             assert_eq!(r.client(), "jwodder");
@@ -33,6 +34,7 @@ mod whoisactually {
         assert_matches!(msg, Message {
             source: Some(Source::Server(host)),
             payload: Payload::Reply(Reply::TopicWhoTime(r)),
+            ..
         } => {
             assert_eq!(host, Host::Domain("calcium.libera.chat"));
             assert_eq!(r.client(), "jwodder");
@@ -51,6 +53,7 @@ mod whoisactually {
         assert_matches!(msg, Message {
             source: Some(Source::Server(host)),
             payload: Payload::Reply(Reply::NamReply(r)),
+            ..
         } => {
             assert_eq!(host, Host::Domain("silver.libera.chat"));
             assert_eq!(r.client(), "jwodder");
@@ -76,6 +79,7 @@ mod whoisactually {
         assert_matches!(msg, Message {
             source: Some(Source::Server(host)),
             payload: Payload::Reply(Reply::Unknown(r)),
+            ..
         } => {
             assert_eq!(host, Host::Domain("weber.oftc.net"));
             assert_eq!(r.code, 42);
@@ -9,12 +9,30 @@
 // Based on <https://github.com/ircdocs/modern-irc/issues/168>, no validation
 // should be performed on host segments — for now.
 
-use crate::TryFromStringError;
 use crate::types::{Nickname, ParseNicknameError, ParseUsernameError, Username};
+use crate::util::glob_match;
+use crate::TryFromStringError;
 use std::fmt;
 use thiserror::Error;
 use url::Host;
 
+/// Splits a raw `nick!user@host`-shaped string into its (up to) three
+/// components, without validating or parsing any of them.  Used both by
+/// [`ClientSource::from_str`] (on well-formed input) and by
+/// [`ClientSource::matches`] (on a glob mask, whose `*`/`?` characters
+/// wouldn't survive validation as a [`Nickname`] or [`Username`]).
+fn split_prefix(mut s: &str) -> (&str, Option<&str>, Option<&str>) {
+    let host = s.rsplit_once('@').map(|(pre, h)| {
+        s = pre;
+        h
+    });
+    let user = s.rsplit_once('!').map(|(pre, u)| {
+        s = pre;
+        u
+    });
+    (s, user, host)
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Source {
     Server(Host),
@@ -29,6 +47,34 @@ impl Source {
     pub fn is_client(&self) -> bool {
         matches!(self, Source::Client(_))
     }
+
+    /// Tests whether the username component of this source (if any) was
+    /// verified by the server via ident, as opposed to being taken as-is
+    /// from the client's `USER` command.
+    ///
+    /// [`Source::Server`] sources have no username component and so are
+    /// trivially considered verified; see [`ClientSource::is_ident_verified`]
+    /// for the actual logic.
+    pub fn is_ident_verified(&self) -> bool {
+        match self {
+            Source::Server(_) => true,
+            Source::Client(client) => client.is_ident_verified(),
+        }
+    }
+
+    /// Tests whether this source matches an IRC ban-mask-style pattern.
+    ///
+    /// For [`Source::Client`], this defers to
+    /// [`ClientSource::matches`], matching each of the mask's `nick`/`user`/
+    /// `host` segments independently. For [`Source::Server`], the mask is
+    /// matched as a whole against the server's hostname, since server
+    /// sources have no `nick!user@host` structure to decompose.
+    pub fn matches(&self, mask: &str) -> bool {
+        match self {
+            Source::Server(host) => glob_match(mask, &host.to_string()),
+            Source::Client(client) => client.matches(mask),
+        }
+    }
 }
 
 impl fmt::Display for Source {
@@ -93,7 +139,8 @@ pub struct ClientSource {
     pub nickname: Nickname,
     // Note that the user component may begin with a tilde if the IRC server
     // failed to look up the username using ident and is instead reporting a
-    // username supplied with `USER`.
+    // username supplied with `USER`.  See `is_ident_verified()` for a typed
+    // way to query this signal instead of inspecting the string directly.
     pub user: Option<Username>,
     pub host: Option<String>,
 }
@@ -114,16 +161,9 @@ impl fmt::Display for ClientSource {
 impl std::str::FromStr for ClientSource {
     type Err = ParseClientSourceError;
 
-    fn from_str(mut s: &str) -> Result<ClientSource, ParseClientSourceError> {
-        let host_str = s.rsplit_once('@').map(|(pre, h)| {
-            s = pre;
-            h
-        });
-        let user_str = s.rsplit_once('!').map(|(pre, u)| {
-            s = pre;
-            u
-        });
-        let nickname = s.parse::<Nickname>()?;
+    fn from_str(s: &str) -> Result<ClientSource, ParseClientSourceError> {
+        let (nick_str, user_str, host_str) = split_prefix(s);
+        let nickname = nick_str.parse::<Nickname>()?;
         let user = user_str.map(str::parse::<Username>).transpose()?;
         let host = host_str.map(String::from);
         Ok(ClientSource {
@@ -134,6 +174,40 @@ impl std::str::FromStr for ClientSource {
     }
 }
 
+impl ClientSource {
+    /// Tests whether `user` was looked up by the server via ident, rather
+    /// than being reported as-is from the client's `USER` command: a
+    /// username beginning with `~` indicates a failed ident lookup, so this
+    /// returns `false` in that case. Returns `true` if `user` is `None`,
+    /// since there's no unverified username to report.
+    pub fn is_ident_verified(&self) -> bool {
+        !self
+            .user
+            .as_ref()
+            .is_some_and(|user| user.as_str().starts_with('~'))
+    }
+
+    /// Tests whether this source matches an IRC ban-mask-style pattern in
+    /// `nick!user@host` form, where `*` matches any run of characters
+    /// (including none) and `?` matches exactly one character.
+    ///
+    /// Each of the three mask segments is matched independently against the
+    /// corresponding field of this source; a segment missing from `mask`
+    /// (e.g. a bare `nick` mask with no `!user@host`) defaults to `*` and so
+    /// matches unconditionally. A field of this source that's unknown (`user`
+    /// or `host` being `None`) is matched against as an empty string, so only
+    /// a mask segment of `*` (or `""`) matches it.
+    pub fn matches(&self, mask: &str) -> bool {
+        let (nick_mask, user_mask, host_mask) = split_prefix(mask);
+        glob_match(nick_mask, self.nickname.as_str())
+            && glob_match(
+                user_mask.unwrap_or("*"),
+                self.user.as_ref().map_or("", Username::as_str),
+            )
+            && glob_match(host_mask.unwrap_or("*"), self.host.as_deref().unwrap_or(""))
+    }
+}
+
 impl TryFrom<String> for ClientSource {
     type Error = TryFromStringError<ParseClientSourceError>;
 
@@ -155,6 +229,45 @@ pub enum ParseSourceError {
     Client(#[from] ParseClientSourceError),
 }
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Source {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Source {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Source;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("an IRC message source prefix")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Source, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
 pub enum ParseClientSourceError {
     #[error("invalid nickname")]
@@ -267,3 +380,80 @@ mod parser_tests {
         });
     }
 }
+
+#[cfg(test)]
+mod matches_tests {
+    use super::*;
+
+    #[test]
+    fn exact_mask() {
+        let source = "coolguy!ag@localhost".parse::<Source>().unwrap();
+        assert!(source.matches("coolguy!ag@localhost"));
+        assert!(!source.matches("coolguy!ag@example.com"));
+    }
+
+    #[test]
+    fn wildcard_mask() {
+        let source = "coolguy!ag@localhost".parse::<Source>().unwrap();
+        assert!(source.matches("*!*@*"));
+        assert!(source.matches("cool*!*@*host"));
+        assert!(!source.matches("*!*@example.com"));
+    }
+
+    #[test]
+    fn question_mark_mask() {
+        let source = "coolguy!ag@localhost".parse::<Source>().unwrap();
+        assert!(source.matches("coolgu?!ag@localhost"));
+        assert!(!source.matches("coolguyy?!ag@localhost"));
+    }
+
+    #[test]
+    fn partial_mask_segments_default_to_wildcard() {
+        let source = "coolguy!ag@localhost".parse::<Source>().unwrap();
+        assert!(source.matches("coolguy"));
+        assert!(source.matches("coolguy!ag"));
+    }
+
+    #[test]
+    fn missing_fields_only_match_wildcard() {
+        let source = "coolguy".parse::<Source>().unwrap();
+        assert!(source.matches("coolguy!*@*"));
+        assert!(!source.matches("coolguy!ag@*"));
+    }
+
+    #[test]
+    fn server_source_matches_as_a_whole() {
+        let source = "irc.example.com".parse::<Source>().unwrap();
+        assert!(source.matches("irc.*.com"));
+        assert!(!source.matches("irc.example.com!*@*"));
+    }
+}
+
+#[cfg(test)]
+mod ident_verified_tests {
+    use super::*;
+
+    #[test]
+    fn verified_user() {
+        let source = "coolguy!ag@localhost".parse::<Source>().unwrap();
+        assert!(source.is_ident_verified());
+    }
+
+    #[test]
+    fn unverified_user() {
+        let source = "coolguy!~ag@localhost".parse::<Source>().unwrap();
+        assert!(!source.is_ident_verified());
+    }
+
+    #[test]
+    fn no_user() {
+        let source = "coolguy@localhost".parse::<Source>().unwrap();
+        assert!(source.is_ident_verified());
+    }
+
+    #[test]
+    fn server_source_is_always_verified() {
+        let source = "irc.example.com".parse::<Source>().unwrap();
+        assert!(source.is_ident_verified());
+    }
+}
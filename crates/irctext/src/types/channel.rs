@@ -9,7 +9,7 @@
 // Note that the set of valid channel type prefixes varies from server to
 // server, but for now, to keep things simple, this library treats '#' and '&'
 // — and only those characters — as channel type prefixes.
-use crate::types::{ModeTarget, MsgTarget};
+use crate::types::{ISupport, ModeTarget, MsgTarget};
 use crate::{CaseMapping, FinalParam, MedialParam};
 use std::borrow::Cow;
 use thiserror::Error;
@@ -23,7 +23,13 @@ strserde!(Channel, "an IRC channel name");
 fn validate(s: &str) -> Result<(), ParseChannelError> {
     if !channel_prefixed(s) {
         Err(ParseChannelError::BadStart)
-    } else if s.contains(['\0', '\r', '\n', ' ', '\x07', ',']) {
+    } else {
+        validate_chars(s)
+    }
+}
+
+fn validate_chars(s: &str) -> Result<(), ParseChannelError> {
+    if s.contains(['\0', '\r', '\n', ' ', '\x07', ',']) {
         Err(ParseChannelError::BadCharacter)
     } else {
         Ok(())
@@ -46,6 +52,76 @@ impl Channel {
             }
         }
     }
+
+    /// Tests whether `self` and `other` are the same channel under `cm`,
+    /// i.e. whether they casefold to the same string.  Servers treat
+    /// channel names as case-insensitive, so this (rather than `==`) is the
+    /// right comparison for matching a channel against one learned from the
+    /// server.
+    pub fn eq_ignore_case(&self, other: &Channel, cm: CaseMapping) -> bool {
+        cm.eq_ignore_case(self.as_str(), other.as_str())
+    }
+
+    /// Parses `s` as a channel name using the given [`ChannelSyntax`]
+    /// (typically built from the server's actual `CHANTYPES`/`CHANNELLEN`
+    /// ISUPPORT tokens via [`ChannelSyntax::from_isupport`]) instead of this
+    /// type's context-free `FromStr`/`TryFrom<String>` impls, which assume
+    /// the `#`/`&` default.
+    pub fn parse_with(s: &str, syntax: &ChannelSyntax) -> Result<Channel, ParseChannelError> {
+        if !syntax.prefixed(s) {
+            return Err(ParseChannelError::UnsupportedPrefix {
+                allowed: syntax.prefixes.iter().collect(),
+            });
+        }
+        if let Some(max_len) = syntax.max_len
+            && s.chars().count() > max_len
+        {
+            return Err(ParseChannelError::TooLong { max_len });
+        }
+        validate_chars(s)?;
+        Ok(Channel(s.to_owned()))
+    }
+}
+
+/// The set of channel-type prefixes and (optionally) the maximum channel
+/// name length a server actually advertises via its `CHANTYPES` and
+/// `CHANNELLEN` [`ISupport`] tokens, for validating channel names against
+/// what the network really supports instead of this library's hardcoded
+/// `#`/`&` default; see [`Channel::parse_with`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChannelSyntax {
+    prefixes: Vec<char>,
+    max_len: Option<usize>,
+}
+
+impl ChannelSyntax {
+    /// Builds a `ChannelSyntax` from the server's `CHANTYPES` and
+    /// `CHANNELLEN` ISUPPORT tokens, falling back to this library's default
+    /// `#`/`&` prefix set (and no length limit) for whichever token the
+    /// server didn't advertise.
+    pub fn from_isupport(isupport: &ISupport) -> ChannelSyntax {
+        let prefixes = isupport
+            .chantypes()
+            .map(|s| s.chars().collect())
+            .unwrap_or_else(|| crate::CHANNEL_PREFIXES.into());
+        let max_len = isupport.channellen().map(|n| n as usize);
+        ChannelSyntax { prefixes, max_len }
+    }
+
+    fn prefixed(&self, s: &str) -> bool {
+        s.starts_with(self.prefixes.as_slice())
+    }
+}
+
+impl Default for ChannelSyntax {
+    /// The context-free `#`/`&` default used by [`Channel`]'s own
+    /// `FromStr`/`TryFrom<String>` impls, with no length limit.
+    fn default() -> ChannelSyntax {
+        ChannelSyntax {
+            prefixes: crate::CHANNEL_PREFIXES.into(),
+            max_len: None,
+        }
+    }
 }
 
 impl From<Channel> for MedialParam {
@@ -72,12 +148,16 @@ impl PartialEq<MsgTarget> for Channel {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Error, Hash, PartialEq)]
 pub enum ParseChannelError {
     #[error("channels must start with '#' or '&'")]
     BadStart,
     #[error("channels cannot contain NUL, CR, LF, SPACE, BELL, or comma")]
     BadCharacter,
+    #[error("channel name does not start with one of the server's advertised prefixes {allowed:?}")]
+    UnsupportedPrefix { allowed: String },
+    #[error("channel name exceeds the server's advertised maximum length of {max_len}")]
+    TooLong { max_len: usize },
 }
 
 /// Returns `true` if `s` starts with one of the channel type prefixes
@@ -6,6 +6,7 @@ use thiserror::Error;
 pub struct EListCond(String);
 
 validstr!(EListCond, ParseEListCondError, validate);
+strserde!(EListCond, "an ELIST condition");
 
 fn validate(s: &str) -> Result<(), ParseEListCondError> {
     if s.is_empty() {
@@ -19,6 +20,26 @@ fn validate(s: &str) -> Result<(), ParseEListCondError> {
     }
 }
 
+impl EListCond {
+    /// Returns the `ELIST` ISUPPORT flag character (`U`, `C`, `T`, `M`, or
+    /// `N`) a server must advertise for this condition to be honored; see
+    /// <https://modern.ircdocs.horse/#elist-parameter>.
+    pub fn flag(&self) -> char {
+        let s = self.as_str();
+        if s.starts_with(['<', '>']) {
+            'U'
+        } else if s.starts_with("C<") || s.starts_with("C>") {
+            'C'
+        } else if s.starts_with("T<") || s.starts_with("T>") {
+            'T'
+        } else if s.starts_with('!') {
+            'N'
+        } else {
+            'M'
+        }
+    }
+}
+
 impl From<EListCond> for MedialParam {
     fn from(value: EListCond) -> MedialParam {
         MedialParam::try_from(value.into_inner()).expect("EListCond should be valid MedialParam")
@@ -1,4 +1,5 @@
-use crate::{FinalParam, MedialParam, TryFromStringError};
+use super::server_capabilities::{ChanModes, ModeLimit, PrefixTable, ServerCapabilities};
+use crate::{CaseMapping, FinalParam, MedialParam, TryFromStringError};
 use std::fmt;
 use thiserror::Error;
 
@@ -100,6 +101,129 @@ pub enum ParseISupportParamError {
     Value(#[from] ParseISupportValueError),
 }
 
+/// Accumulates the tokens advertised via one or more `RPL_ISUPPORT` (005)
+/// replies, applying `-KEY` negation tokens to remove a previously
+/// advertised value, and exposes typed accessors for the well-known tokens
+/// alongside raw [`ISupportParam`] access for everything else.
+///
+/// The four comma-separated groups of `CHANMODES`, the `PREFIX` mapping, the
+/// `CHANLIMIT`/`TARGMAX` group lists, and `CASEMAPPING` are delegated to
+/// [`ServerCapabilities`], which already knows how to parse those
+/// mini-grammars.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ISupport {
+    tokens: Vec<ISupportParam>,
+}
+
+impl ISupport {
+    pub fn new() -> ISupport {
+        ISupport::default()
+    }
+
+    /// Applies a single token, as parsed from an `RPL_ISUPPORT` (005)
+    /// message.  A `Set`/`Eq` token overwrites any earlier token advertised
+    /// for the same key; an `Unset` token (`-KEY`) removes it instead of
+    /// being stored itself.
+    pub fn apply(&mut self, token: ISupportParam) {
+        self.tokens.retain(|t| t.key() != token.key());
+        if !token.is_unset() {
+            self.tokens.push(token);
+        }
+    }
+
+    /// Returns the raw tokens currently in effect.
+    pub fn tokens(&self) -> &[ISupportParam] {
+        &self.tokens
+    }
+
+    /// Returns the raw token currently in effect for `key`, or `None` if the
+    /// server hasn't advertised it (or has since negated it).
+    pub fn get(&self, key: &str) -> Option<&ISupportParam> {
+        self.tokens.iter().find(|t| t.key().as_str() == key)
+    }
+
+    fn str_value(&self, key: &str) -> Option<&str> {
+        self.get(key)
+            .and_then(ISupportParam::value)
+            .map(ISupportValue::as_str)
+    }
+
+    fn parsed_value<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.str_value(key).and_then(|v| v.parse().ok())
+    }
+
+    pub fn chantypes(&self) -> Option<&str> {
+        self.str_value("CHANTYPES")
+    }
+
+    pub fn nicklen(&self) -> Option<u32> {
+        self.parsed_value("NICKLEN")
+    }
+
+    pub fn channellen(&self) -> Option<u32> {
+        self.parsed_value("CHANNELLEN")
+    }
+
+    pub fn topiclen(&self) -> Option<u32> {
+        self.parsed_value("TOPICLEN")
+    }
+
+    pub fn modes(&self) -> Option<u32> {
+        self.parsed_value("MODES")
+    }
+
+    pub fn network(&self) -> Option<&str> {
+        self.str_value("NETWORK")
+    }
+
+    pub fn statusmsg(&self) -> Option<&str> {
+        self.str_value("STATUSMSG")
+    }
+
+    /// The set of `ELIST` search-filter flags the server supports (e.g.
+    /// `"CFLMNRTU"`), for validating filters before sending a `LIST`; see
+    /// <https://modern.ircdocs.horse/#elist-parameter>.
+    pub fn elist(&self) -> Option<&str> {
+        self.str_value("ELIST")
+    }
+
+    pub fn chanmodes(&self) -> Option<ChanModes> {
+        ServerCapabilities::from_isupport(&self.tokens).chanmodes
+    }
+
+    pub fn prefix(&self) -> Option<PrefixTable> {
+        ServerCapabilities::from_isupport(&self.tokens).prefix
+    }
+
+    pub fn chanlimit(&self) -> Vec<ModeLimit> {
+        ServerCapabilities::from_isupport(&self.tokens).chanlimit
+    }
+
+    pub fn maxlist(&self) -> Vec<ModeLimit> {
+        ServerCapabilities::from_isupport(&self.tokens).maxlist
+    }
+
+    pub fn targmax(&self) -> Vec<(String, Option<u32>)> {
+        ServerCapabilities::from_isupport(&self.tokens).targmax
+    }
+
+    pub fn casemapping(&self) -> CaseMapping {
+        ServerCapabilities::from_isupport(&self.tokens).casemapping
+    }
+}
+
+/// Applies each token in turn via [`ISupport::apply`], so a whole
+/// `RPL_ISUPPORT` line's tokens (e.g. from
+/// [`replies::ISupport::tokens`](crate::replies::ISupport::tokens)) can be
+/// folded in with one call instead of a manual loop.
+impl Extend<ISupportParam> for ISupport {
+    fn extend<I: IntoIterator<Item = ISupportParam>>(&mut self, iter: I) {
+        for token in iter {
+            self.apply(token);
+        }
+    }
+}
+
 // modern.ircdocs.horse says that ISUPPORT keys should be limited to 20
 // characters, but I'm not going to enforce that.
 #[derive(Clone, Eq, PartialEq)]
@@ -319,4 +443,72 @@ mod tests {
         assert_eq!(value.to_string(), "foo=bar\\baz quux");
         assert_eq!(value.escaped().to_string(), r"foo\x3Dbar\x5Cbaz\x20quux");
     }
+
+    #[test]
+    fn isupport_apply_and_negate() {
+        let mut isupport = ISupport::new();
+        isupport.apply("CHANTYPES=#&".parse().unwrap());
+        isupport.apply("NETWORK=Test".parse().unwrap());
+        assert_eq!(isupport.chantypes(), Some("#&"));
+        assert_eq!(isupport.network(), Some("Test"));
+        isupport.apply("-NETWORK".parse().unwrap());
+        assert_eq!(isupport.network(), None);
+        assert!(isupport.get("NETWORK").is_none());
+    }
+
+    #[test]
+    fn isupport_extend_applies_each_token() {
+        let mut isupport = ISupport::new();
+        isupport.extend(
+            ["CHANTYPES=#&", "NETWORK=Test", "EXCEPTS"]
+                .into_iter()
+                .map(|t| t.parse().unwrap()),
+        );
+        assert_eq!(isupport.chantypes(), Some("#&"));
+        assert_eq!(isupport.network(), Some("Test"));
+        assert!(isupport.get("EXCEPTS").is_some());
+    }
+
+    #[test]
+    fn isupport_overwrites_earlier_value() {
+        let mut isupport = ISupport::new();
+        isupport.apply("NICKLEN=9".parse().unwrap());
+        isupport.apply("NICKLEN=16".parse().unwrap());
+        assert_eq!(isupport.nicklen(), Some(16));
+    }
+
+    #[test]
+    fn isupport_raw_fallback_for_unknown_key() {
+        let mut isupport = ISupport::new();
+        isupport.apply("EXTBAN=$,agjrxz".parse().unwrap());
+        assert_eq!(
+            isupport.get("EXTBAN").and_then(ISupportParam::value),
+            Some(&"$,agjrxz".parse::<ISupportValue>().unwrap())
+        );
+    }
+
+    #[test]
+    fn isupport_typed_accessors_delegate_to_server_capabilities() {
+        let mut isupport = ISupport::new();
+        for token in [
+            "CHANMODES=eIbq,k,flj,CFLMPQRSTcgimnprstuz",
+            "PREFIX=(ov)@+",
+            "CASEMAPPING=rfc1459",
+        ] {
+            isupport.apply(token.parse().unwrap());
+        }
+        assert!(isupport.chanmodes().is_some());
+        assert_eq!(isupport.prefix().unwrap().prefix_for_mode('o'), Some('@'));
+        assert_eq!(isupport.casemapping(), CaseMapping::Rfc1459);
+    }
+
+    #[test]
+    fn isupport_maxlist() {
+        let mut isupport = ISupport::new();
+        isupport.apply("MAXLIST=bqeI:100".parse().unwrap());
+        assert_eq!(
+            ModeLimit::limit_for(&isupport.maxlist(), 'b'),
+            Some(Some(100))
+        );
+    }
 }
@@ -7,6 +7,7 @@ use thiserror::Error;
 pub struct Key(String);
 
 validstr!(Key, ParseKeyError, validate);
+strserde!(Key, "a channel key");
 
 fn validate(s: &str) -> Result<(), ParseKeyError> {
     if s.contains(['\0', '\r', '\n', ',']) {
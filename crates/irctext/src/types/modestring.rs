@@ -1,10 +1,12 @@
-use crate::{FinalParam, MedialParam};
+use super::server_capabilities::{ChanModes, PrefixTable};
+use crate::{FinalParam, MedialParam, Parameter};
 use thiserror::Error;
 
 #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ModeString(String);
 
 validstr!(ModeString, ParseModeStringError, validate);
+strserde!(ModeString, "an IRC mode string");
 
 fn validate(s: &str) -> Result<(), ParseModeStringError> {
     if !s.starts_with(['+', '-']) {
@@ -16,6 +18,97 @@ fn validate(s: &str) -> Result<(), ParseModeStringError> {
     }
 }
 
+impl ModeString {
+    /// Iterates over each mode letter in this mode string along with
+    /// whether it's being set (`true`) or unset (`false`), e.g. `+ov-b`
+    /// yields `(true, 'o')`, `(true, 'v')`, `(false, 'b')`.
+    pub fn signs(&self) -> impl Iterator<Item = (bool, char)> + '_ {
+        let mut adding = true;
+        self.0.chars().filter_map(move |c| match c {
+            '+' => {
+                adding = true;
+                None
+            }
+            '-' => {
+                adding = false;
+                None
+            }
+            c => Some((adding, c)),
+        })
+    }
+
+    /// Pairs each mode letter in this mode string with the argument it
+    /// consumes from `args`, a `MODE` command's trailing parameters.
+    /// `chanmodes` and `prefixes` — normally read straight off the server's
+    /// `CHANMODES`/`PREFIX` ISUPPORT tokens — decide which letters take a
+    /// parameter: a `PREFIX` letter always takes one, and otherwise it's
+    /// whatever [`ChanModes::takes_param`] says for that letter and
+    /// polarity. A mode letter that's unrecognized by both is assumed to
+    /// take no parameter.
+    ///
+    /// Errors if a mode letter that needs a parameter finds `args`
+    /// exhausted.
+    pub fn resolve(
+        &self,
+        args: impl IntoIterator<Item = Parameter>,
+        chanmodes: &ChanModes,
+        prefixes: &PrefixTable,
+    ) -> Result<Vec<ModeChange>, ResolveModeStringError> {
+        let mut args = args.into_iter();
+        let mut changes = Vec::new();
+        for (adding, mode) in self.signs() {
+            let takes_param = chanmodes
+                .takes_param(mode, adding)
+                .unwrap_or_else(|| prefixes.prefix_for_mode(mode).is_some());
+            let arg = if takes_param {
+                Some(
+                    args.next()
+                        .ok_or(ResolveModeStringError::MissingArgument { mode })?,
+                )
+            } else {
+                None
+            };
+            changes.push(ModeChange { adding, mode, arg });
+        }
+        Ok(changes)
+    }
+
+    /// Like [`resolve`](ModeString::resolve), but also rejects mode letters
+    /// that appear in neither `chanmodes` nor `prefixes`, instead of
+    /// defaulting them to taking no parameter.
+    pub fn resolve_strict(
+        &self,
+        args: impl IntoIterator<Item = Parameter>,
+        chanmodes: &ChanModes,
+        prefixes: &PrefixTable,
+    ) -> Result<Vec<ModeChange>, ResolveModeStringError> {
+        for (adding, mode) in self.signs() {
+            if chanmodes.takes_param(mode, adding).is_none() && prefixes.prefix_for_mode(mode).is_none() {
+                return Err(ResolveModeStringError::UnknownMode { mode });
+            }
+        }
+        self.resolve(args, chanmodes, prefixes)
+    }
+}
+
+/// One mode letter parsed out of a `MODE` command's mode string, paired
+/// with whether it's being set or unset and, if its category required one,
+/// the argument consumed for it. See [`ModeString::resolve`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModeChange {
+    pub adding: bool,
+    pub mode: char,
+    pub arg: Option<Parameter>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum ResolveModeStringError {
+    #[error("mode {mode:?} requires an argument, but none remain")]
+    MissingArgument { mode: char },
+    #[error("mode {mode:?} is not a recognized CHANMODES or PREFIX letter")]
+    UnknownMode { mode: char },
+}
+
 impl From<ModeString> for MedialParam {
     fn from(value: ModeString) -> MedialParam {
         MedialParam::try_from(value.into_inner()).expect("Mode string should be valid MedialParam")
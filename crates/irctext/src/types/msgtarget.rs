@@ -5,12 +5,38 @@ use crate::{FinalParam, MedialParam, TryFromStringError};
 use std::fmt;
 use thiserror::Error;
 
+/// Strips a single leading `STATUSMSG` membership-prefix character (drawn
+/// from [`crate::CHANNEL_MEMBERSHIPS`]) from `s`, returning it along with
+/// the rest of `s`, but only if what remains is itself channel-prefixed —
+/// this is what distinguishes e.g. `@#chan` from a nickname that happens to
+/// start with `@`.
+fn pop_status_prefix(s: &str) -> Option<(char, &str)> {
+    let mut chars = s.chars();
+    let prefix = chars.next()?;
+    let rest = chars.as_str();
+    (crate::CHANNEL_MEMBERSHIPS.contains(&prefix) && channel_prefixed(rest))
+        .then_some((prefix, rest))
+}
+
 /// The target of a `PRIVMSG` or `NOTICE` message
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MsgTarget {
     Channel(Channel),
     Nick(Nickname),
     Star,
+
+    /// A channel target restricted to members holding at least a given
+    /// status, per the `STATUSMSG`/`WALLCHOPS` IRCv3 extensions, e.g. `@#chan`
+    /// to message only the ops of `#chan`.
+    StatusMsg(StatusMsgTarget),
+
+    /// A server-name mask target for an operator broadcast, e.g. `$$*.fi`
+    /// to message every server matching `*.fi`.
+    ServerMask(ServerMaskTarget),
+
+    /// A hostname mask target for an operator broadcast, e.g. `$#*.edu` to
+    /// message every user whose host matches `*.edu`.
+    HostMask(HostMaskTarget),
 }
 
 impl MsgTarget {
@@ -26,11 +52,26 @@ impl MsgTarget {
         matches!(self, MsgTarget::Star)
     }
 
+    pub fn is_statusmsg(&self) -> bool {
+        matches!(self, MsgTarget::StatusMsg(_))
+    }
+
+    pub fn is_server_mask(&self) -> bool {
+        matches!(self, MsgTarget::ServerMask(_))
+    }
+
+    pub fn is_host_mask(&self) -> bool {
+        matches!(self, MsgTarget::HostMask(_))
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             MsgTarget::Channel(chan) => chan.as_str(),
             MsgTarget::Nick(nick) => nick.as_str(),
             MsgTarget::Star => "*",
+            MsgTarget::StatusMsg(sm) => sm.as_str(),
+            MsgTarget::ServerMask(sm) => sm.as_str(),
+            MsgTarget::HostMask(hm) => hm.as_str(),
         }
     }
 }
@@ -47,6 +88,19 @@ impl std::str::FromStr for MsgTarget {
     fn from_str(s: &str) -> Result<MsgTarget, ParseMsgTargetError> {
         if s == "*" {
             Ok(MsgTarget::Star)
+        } else if let Some(mask) = s.strip_prefix("$$") {
+            if mask.is_empty() {
+                return Err(ParseMsgTargetError::EmptyMask);
+            }
+            Ok(MsgTarget::ServerMask(ServerMaskTarget::new(mask)))
+        } else if let Some(mask) = s.strip_prefix("$#") {
+            if mask.is_empty() {
+                return Err(ParseMsgTargetError::EmptyMask);
+            }
+            Ok(MsgTarget::HostMask(HostMaskTarget::new(mask)))
+        } else if let Some((prefix, rest)) = pop_status_prefix(s) {
+            let channel = rest.parse::<Channel>()?;
+            Ok(MsgTarget::StatusMsg(StatusMsgTarget::new(prefix, channel)))
         } else if channel_prefixed(s) {
             let channel = s.parse::<Channel>()?;
             Ok(MsgTarget::Channel(channel))
@@ -63,6 +117,30 @@ impl TryFrom<String> for MsgTarget {
     fn try_from(value: String) -> Result<MsgTarget, TryFromStringError<ParseMsgTargetError>> {
         if value == "*" {
             Ok(MsgTarget::Star)
+        } else if let Some(mask) = value.strip_prefix("$$") {
+            if mask.is_empty() {
+                return Err(TryFromStringError {
+                    inner: ParseMsgTargetError::EmptyMask,
+                    string: value,
+                });
+            }
+            Ok(MsgTarget::ServerMask(ServerMaskTarget::new(mask)))
+        } else if let Some(mask) = value.strip_prefix("$#") {
+            if mask.is_empty() {
+                return Err(TryFromStringError {
+                    inner: ParseMsgTargetError::EmptyMask,
+                    string: value,
+                });
+            }
+            Ok(MsgTarget::HostMask(HostMaskTarget::new(mask)))
+        } else if let Some((prefix, rest)) = pop_status_prefix(&value) {
+            match Channel::try_from(rest.to_owned()) {
+                Ok(channel) => Ok(MsgTarget::StatusMsg(StatusMsgTarget::new(prefix, channel))),
+                Err(TryFromStringError { inner, .. }) => Err(TryFromStringError {
+                    inner: ParseMsgTargetError::Channel(inner),
+                    string: value,
+                }),
+            }
         } else if channel_prefixed(&value) {
             match Channel::try_from(value) {
                 Ok(channel) => Ok(MsgTarget::Channel(channel)),
@@ -101,12 +179,33 @@ impl From<Nickname> for MsgTarget {
     }
 }
 
+impl From<StatusMsgTarget> for MsgTarget {
+    fn from(value: StatusMsgTarget) -> MsgTarget {
+        MsgTarget::StatusMsg(value)
+    }
+}
+
+impl From<ServerMaskTarget> for MsgTarget {
+    fn from(value: ServerMaskTarget) -> MsgTarget {
+        MsgTarget::ServerMask(value)
+    }
+}
+
+impl From<HostMaskTarget> for MsgTarget {
+    fn from(value: HostMaskTarget) -> MsgTarget {
+        MsgTarget::HostMask(value)
+    }
+}
+
 impl From<MsgTarget> for String {
     fn from(value: MsgTarget) -> String {
         match value {
             MsgTarget::Channel(chan) => chan.into(),
             MsgTarget::Nick(nick) => nick.into(),
             MsgTarget::Star => String::from("*"),
+            MsgTarget::StatusMsg(sm) => sm.into(),
+            MsgTarget::ServerMask(sm) => sm.into(),
+            MsgTarget::HostMask(hm) => hm.into(),
         }
     }
 }
@@ -160,4 +259,323 @@ pub enum ParseMsgTargetError {
     Channel(#[from] ParseChannelError),
     #[error(transparent)]
     Nickname(#[from] ParseNicknameError),
+    #[error("server/host mask cannot be empty")]
+    EmptyMask,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for MsgTarget {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for MsgTarget {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = MsgTarget;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a PRIVMSG/NOTICE target")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<MsgTarget, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// A channel target carrying a leading `STATUSMSG` membership-prefix
+/// character, e.g. `@#chan`; see [`MsgTarget::StatusMsg`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StatusMsgTarget(String);
+
+impl StatusMsgTarget {
+    /// Combines `prefix` and `channel` into a `StatusMsgTarget`, e.g.
+    /// `StatusMsgTarget::new('@', "#chan".parse().unwrap())` for `@#chan`.
+    ///
+    /// `prefix` is not validated against [`crate::CHANNEL_MEMBERSHIPS`] here,
+    /// since a server may advertise other membership prefixes via its
+    /// `PREFIX` ISUPPORT token; only parsing (`FromStr`/`TryFrom<String>`)
+    /// restricts itself to the static set, to distinguish a prefixed channel
+    /// from a nickname.
+    pub fn new(prefix: char, channel: Channel) -> StatusMsgTarget {
+        StatusMsgTarget(format!("{prefix}{channel}"))
+    }
+
+    pub fn prefix(&self) -> char {
+        self.0
+            .chars()
+            .next()
+            .expect("StatusMsgTarget should be nonempty")
+    }
+
+    #[expect(clippy::missing_panics_doc)]
+    pub fn channel(&self) -> Channel {
+        self.0[self.prefix().len_utf8()..]
+            .parse()
+            .expect("StatusMsgTarget should contain a valid Channel after its prefix")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for StatusMsgTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<StatusMsgTarget> for String {
+    fn from(value: StatusMsgTarget) -> String {
+        value.0
+    }
+}
+
+/// A server-name mask carrying its leading `$$`, e.g. `$$*.fi`; see
+/// [`MsgTarget::ServerMask`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ServerMaskTarget(String);
+
+impl ServerMaskTarget {
+    /// Combines the `$$` sigil with `mask`, e.g.
+    /// `ServerMaskTarget::new("*.fi")` for `$$*.fi`.
+    pub fn new(mask: &str) -> ServerMaskTarget {
+        ServerMaskTarget(format!("$${mask}"))
+    }
+
+    pub fn mask(&self) -> &str {
+        &self.0[2..]
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ServerMaskTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<ServerMaskTarget> for String {
+    fn from(value: ServerMaskTarget) -> String {
+        value.0
+    }
+}
+
+/// A hostname mask carrying its leading `$#`, e.g. `$#*.edu`; see
+/// [`MsgTarget::HostMask`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct HostMaskTarget(String);
+
+impl HostMaskTarget {
+    /// Combines the `$#` sigil with `mask`, e.g.
+    /// `HostMaskTarget::new("*.edu")` for `$#*.edu`.
+    pub fn new(mask: &str) -> HostMaskTarget {
+        HostMaskTarget(format!("$#{mask}"))
+    }
+
+    pub fn mask(&self) -> &str {
+        &self.0[2..]
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for HostMaskTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<HostMaskTarget> for String {
+    fn from(value: HostMaskTarget) -> String {
+        value.0
+    }
+}
+
+/// A non-empty, comma-separated list of [`MsgTarget`]s, as used for the
+/// `<target>{,<target>}` portion of `PRIVMSG`/`NOTICE`/`TAGMSG`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MsgTargetList(Vec<MsgTarget>);
+
+impl MsgTargetList {
+    /// Returns `None` if `targets` is empty.
+    pub fn new<I: IntoIterator<Item = MsgTarget>>(targets: I) -> Option<MsgTargetList> {
+        let targets = Vec::from_iter(targets);
+        if targets.is_empty() {
+            None
+        } else {
+            Some(MsgTargetList(targets))
+        }
+    }
+
+    pub fn as_slice(&self) -> &[MsgTarget] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<MsgTarget> {
+        self.0
+    }
+}
+
+impl fmt::Display for MsgTargetList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, target) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{target}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for MsgTargetList {
+    type Err = ParseMsgTargetError;
+
+    fn from_str(s: &str) -> Result<MsgTargetList, ParseMsgTargetError> {
+        let targets = s
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MsgTargetList(targets))
+    }
+}
+
+impl TryFrom<String> for MsgTargetList {
+    type Error = TryFromStringError<ParseMsgTargetError>;
+
+    fn try_from(value: String) -> Result<MsgTargetList, TryFromStringError<ParseMsgTargetError>> {
+        value.parse().map_err(|inner| TryFromStringError {
+            inner,
+            string: value,
+        })
+    }
+}
+
+impl AsRef<[MsgTarget]> for MsgTargetList {
+    fn as_ref(&self) -> &[MsgTarget] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_channel() {
+        let target = "#chan".parse::<MsgTarget>().unwrap();
+        assert_eq!(target, MsgTarget::Channel("#chan".parse().unwrap()));
+        assert_eq!(target.as_str(), "#chan");
+        assert!(!target.is_statusmsg());
+    }
+
+    #[test]
+    fn parse_statusmsg_channel() {
+        let target = "@#chan".parse::<MsgTarget>().unwrap();
+        assert!(target.is_statusmsg());
+        assert_eq!(target.as_str(), "@#chan");
+        assert_eq!(target.to_string(), "@#chan");
+        let MsgTarget::StatusMsg(sm) = target else {
+            panic!("expected a StatusMsg target");
+        };
+        assert_eq!(sm.prefix(), '@');
+        assert_eq!(sm.channel(), "#chan".parse().unwrap());
+    }
+
+    #[test]
+    fn parse_statusmsg_channel_try_from_string() {
+        let target = MsgTarget::try_from(String::from("+#chan")).unwrap();
+        assert!(target.is_statusmsg());
+        assert_eq!(target.as_str(), "+#chan");
+    }
+
+    #[test]
+    fn nick_starting_with_membership_char_is_not_statusmsg() {
+        // `nickname.rs` excludes CHANNEL_MEMBERSHIPS characters from valid
+        // nickname starts, so this should fail to parse as a nick, and since
+        // the rest isn't channel-prefixed either, it should fail outright.
+        assert!("@nope".parse::<MsgTarget>().is_err());
+    }
+
+    #[test]
+    fn msgtargetlist_parses_and_displays_multiple_targets() {
+        let list = "#chan,@#ops,nick".parse::<MsgTargetList>().unwrap();
+        assert_eq!(list.as_slice().len(), 3);
+        assert_eq!(list.to_string(), "#chan,@#ops,nick");
+    }
+
+    #[test]
+    fn msgtargetlist_new_rejects_empty() {
+        assert!(MsgTargetList::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn msgtargetlist_rejects_empty_string() {
+        assert!("".parse::<MsgTargetList>().is_err());
+    }
+
+    #[test]
+    fn parse_server_mask() {
+        let target = "$$*.fi".parse::<MsgTarget>().unwrap();
+        assert!(target.is_server_mask());
+        assert_eq!(target.as_str(), "$$*.fi");
+        assert_eq!(target.to_string(), "$$*.fi");
+        let MsgTarget::ServerMask(sm) = target else {
+            panic!("expected a ServerMask target");
+        };
+        assert_eq!(sm.mask(), "*.fi");
+    }
+
+    #[test]
+    fn parse_host_mask() {
+        let target = MsgTarget::try_from(String::from("$#*.edu")).unwrap();
+        assert!(target.is_host_mask());
+        assert_eq!(target.as_str(), "$#*.edu");
+        let MsgTarget::HostMask(hm) = target else {
+            panic!("expected a HostMask target");
+        };
+        assert_eq!(hm.mask(), "*.edu");
+    }
+
+    #[test]
+    fn empty_server_mask_is_error() {
+        assert_eq!(
+            "$$".parse::<MsgTarget>(),
+            Err(ParseMsgTargetError::EmptyMask)
+        );
+    }
+
+    #[test]
+    fn empty_host_mask_is_error() {
+        assert_eq!(
+            "$#".parse::<MsgTarget>(),
+            Err(ParseMsgTargetError::EmptyMask)
+        );
+    }
 }
@@ -28,12 +28,12 @@
 // In addition to the above, in order to be sent in messages, nicknames cannot
 // contain NUL, CR, or LF.
 
-use crate::types::{ModeTarget, MsgTarget, ReplyTarget};
+use crate::types::{ISupport, ModeTarget, MsgTarget, ReplyTarget};
 use crate::{CaseMapping, FinalParam, MedialParam};
 use std::borrow::Cow;
 use thiserror::Error;
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, Hash, PartialEq)]
 pub struct Nickname(String);
 
 validstr!(Nickname, ParseNicknameError, validate);
@@ -44,7 +44,13 @@ fn validate(s: &str) -> Result<(), ParseNicknameError> {
         Err(ParseNicknameError::Empty)
     } else if s.starts_with(['$', ':', '#', '&', '~', '@', '%', '+']) {
         Err(ParseNicknameError::BadStart)
-    } else if s.contains(['\0', '\r', '\n', ' ', ',', '*', '?', '!', '@']) {
+    } else {
+        validate_chars(s)
+    }
+}
+
+fn validate_chars(s: &str) -> Result<(), ParseNicknameError> {
+    if s.contains(['\0', '\r', '\n', ' ', ',', '*', '?', '!', '@']) {
         Err(ParseNicknameError::BadCharacter)
     } else {
         Ok(())
@@ -67,6 +73,92 @@ impl Nickname {
             }
         }
     }
+
+    /// Tests whether `self` and `other` are the same nickname under `cm`,
+    /// i.e. whether they casefold to the same string.  Servers treat
+    /// nicknames as case-insensitive, so this (rather than `==`) is the
+    /// right comparison for matching a nickname against one learned from
+    /// the server.
+    pub fn eq_ignore_case(&self, other: &Nickname, cm: CaseMapping) -> bool {
+        cm.eq_ignore_case(self.as_str(), other.as_str())
+    }
+
+    /// Parses `s` as a nickname using the given [`NicknameSyntax`]
+    /// (typically built from the server's actual `CHANTYPES`/`PREFIX`/
+    /// `NICKLEN` ISUPPORT tokens via [`NicknameSyntax::from_isupport`])
+    /// instead of this type's context-free `FromStr`/`TryFrom<String>`
+    /// impls, which assume the common `#&~@%+` default.
+    pub fn parse_with(s: &str, syntax: &NicknameSyntax) -> Result<Nickname, ParseNicknameError> {
+        if s.is_empty() {
+            return Err(ParseNicknameError::Empty);
+        }
+        if syntax.bad_start(s) {
+            return Err(ParseNicknameError::UnsupportedStart {
+                forbidden: syntax.bad_start.iter().collect(),
+            });
+        }
+        if let Some(max_len) = syntax.max_len
+            && s.chars().count() > max_len
+        {
+            return Err(ParseNicknameError::TooLong { max_len });
+        }
+        validate_chars(s)?;
+        Ok(Nickname(s.to_owned()))
+    }
+}
+
+/// The set of characters a nickname may not start with — `$`/`:` plus the
+/// channel-type and channel-membership prefixes the server actually
+/// advertises via its `CHANTYPES` and `PREFIX` [`ISupport`] tokens — and
+/// (optionally) the maximum nickname length from `NICKLEN`, for validating
+/// nicknames against what the network really supports instead of this
+/// library's hardcoded `#&~@%+` default; see [`Nickname::parse_with`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NicknameSyntax {
+    bad_start: Vec<char>,
+    max_len: Option<usize>,
+}
+
+impl NicknameSyntax {
+    /// Builds a `NicknameSyntax` from the server's `CHANTYPES`, `PREFIX`,
+    /// and `NICKLEN` ISUPPORT tokens, falling back to this library's
+    /// default channel-type/membership prefix sets (and no length limit)
+    /// for whichever token the server didn't advertise.
+    pub fn from_isupport(isupport: &ISupport) -> NicknameSyntax {
+        let mut bad_start = vec!['$', ':'];
+        bad_start.extend(
+            isupport
+                .chantypes()
+                .map(|s| s.chars().collect::<Vec<_>>())
+                .unwrap_or_else(|| crate::CHANNEL_PREFIXES.into()),
+        );
+        bad_start.extend(
+            isupport
+                .prefix()
+                .map(|pt| pt.iter().map(|(_, prefix)| prefix).collect::<Vec<_>>())
+                .unwrap_or_else(|| crate::CHANNEL_MEMBERSHIPS.into()),
+        );
+        let max_len = isupport.nicklen().map(|n| n as usize);
+        NicknameSyntax { bad_start, max_len }
+    }
+
+    fn bad_start(&self, s: &str) -> bool {
+        s.starts_with(self.bad_start.as_slice())
+    }
+}
+
+impl Default for NicknameSyntax {
+    /// The context-free `$`/`:`/`#&`/`~@%+` default used by [`Nickname`]'s
+    /// own `FromStr`/`TryFrom<String>` impls, with no length limit.
+    fn default() -> NicknameSyntax {
+        let mut bad_start = vec!['$', ':'];
+        bad_start.extend(crate::CHANNEL_PREFIXES);
+        bad_start.extend(crate::CHANNEL_MEMBERSHIPS);
+        NicknameSyntax {
+            bad_start,
+            max_len: None,
+        }
+    }
 }
 
 impl From<Nickname> for MedialParam {
@@ -99,7 +191,7 @@ impl PartialEq<ReplyTarget> for Nickname {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
 pub enum ParseNicknameError {
     #[error("nicknames cannot be empty")]
     Empty,
@@ -107,4 +199,8 @@ pub enum ParseNicknameError {
     BadStart,
     #[error("nicknames cannot contain NUL, CR, LF, space, comma, *, ?, !, or @")]
     BadCharacter,
+    #[error("nickname starts with a character the server forbids {forbidden:?}")]
+    UnsupportedStart { forbidden: String },
+    #[error("nickname exceeds the server's advertised maximum length of {max_len}")]
+    TooLong { max_len: usize },
 }
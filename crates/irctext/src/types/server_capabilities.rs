@@ -0,0 +1,278 @@
+use crate::types::ISupportParam;
+use crate::CaseMapping;
+use std::cmp::Ordering;
+
+/// A structured view over the tokens advertised via `RPL_ISUPPORT` (005),
+/// decoding the handful of values (`CHANMODES`, `PREFIX`, `CHANLIMIT`,
+/// `MAXLIST`, `TARGMAX`, `CASEMAPPING`) that are themselves miniature
+/// sub-grammars rather than plain strings or numbers.
+///
+/// Unrecognized or absent tokens are simply left at their default (empty or
+/// [`CaseMapping::default()`]); this type never errors, since a server is
+/// free to omit any `ISUPPORT` token or advertise it in a form we don't
+/// recognize.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ServerCapabilities {
+    pub chanmodes: Option<ChanModes>,
+    pub prefix: Option<PrefixTable>,
+    pub chanlimit: Vec<ModeLimit>,
+    pub maxlist: Vec<ModeLimit>,
+    pub targmax: Vec<(String, Option<u32>)>,
+    pub casemapping: CaseMapping,
+}
+
+impl ServerCapabilities {
+    pub fn from_isupport<'a, I>(params: I) -> ServerCapabilities
+    where
+        I: IntoIterator<Item = &'a ISupportParam>,
+    {
+        let mut caps = ServerCapabilities::default();
+        for param in params {
+            let ISupportParam::Eq(key, value) = param else {
+                continue;
+            };
+            match key.as_str() {
+                "CHANMODES" => caps.chanmodes = Some(ChanModes::parse(value.as_str())),
+                "PREFIX" => caps.prefix = PrefixTable::parse(value.as_str()),
+                "CHANLIMIT" => caps.chanlimit = ModeLimit::parse_groups(value.as_str()),
+                "MAXLIST" => caps.maxlist = ModeLimit::parse_groups(value.as_str()),
+                "TARGMAX" => caps.targmax = parse_targmax(value.as_str()),
+                "CASEMAPPING" => {
+                    if let Ok(cm) = value.as_str().parse::<CaseMapping>() {
+                        caps.casemapping = cm;
+                    }
+                }
+                _ => {}
+            }
+        }
+        caps
+    }
+
+    /// Returns the limit advertised for `command` via `TARGMAX`: `None` if
+    /// the command wasn't listed at all, `Some(None)` if it was listed as
+    /// unlimited, and `Some(Some(n))` if it was listed with a cap of `n`.
+    pub fn targmax_limit(&self, command: &str) -> Option<Option<u32>> {
+        self.targmax
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(command))
+            .map(|(_, limit)| *limit)
+    }
+}
+
+/// The four classes of channel mode, as advertised via the `CHANMODES`
+/// `ISUPPORT` token (`A,B,C,D`), governing whether a mode takes a parameter
+/// when it's set and/or unset
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChanModes {
+    /// Modes that add or remove an item from a list (e.g. `b`, `e`, `I`);
+    /// always takes a parameter, whether setting or unsetting
+    pub type_a: Vec<char>,
+    /// Modes that always take a parameter (e.g. `k`)
+    pub type_b: Vec<char>,
+    /// Modes that take a parameter only when being set (e.g. `l`)
+    pub type_c: Vec<char>,
+    /// Modes that never take a parameter (e.g. `m`, `n`, `t`)
+    pub type_d: Vec<char>,
+}
+
+impl ChanModes {
+    fn parse(value: &str) -> ChanModes {
+        let mut groups = value.split(',').map(|s| s.chars().collect::<Vec<char>>());
+        ChanModes {
+            type_a: groups.next().unwrap_or_default(),
+            type_b: groups.next().unwrap_or_default(),
+            type_c: groups.next().unwrap_or_default(),
+            type_d: groups.next().unwrap_or_default(),
+        }
+    }
+
+    /// Returns whether `mode` takes a parameter when applied with the given
+    /// polarity (`true` for setting, `false` for unsetting), or `None` if
+    /// `mode` isn't a recognized channel mode letter
+    pub fn takes_param(&self, mode: char, setting: bool) -> Option<bool> {
+        if self.type_a.contains(&mode) || self.type_b.contains(&mode) {
+            Some(true)
+        } else if self.type_c.contains(&mode) {
+            Some(setting)
+        } else if self.type_d.contains(&mode) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// The ordered mapping between channel mode letters and their status
+/// prefixes, as advertised via the `PREFIX` `ISUPPORT` token (e.g.
+/// `(ov)@+`).  Entries are kept in the order advertised, from the most
+/// privileged status to the least.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PrefixTable(Vec<(char, char)>);
+
+impl PrefixTable {
+    fn parse(value: &str) -> Option<PrefixTable> {
+        let rest = value.strip_prefix('(')?;
+        let (modes, prefixes) = rest.split_once(')')?;
+        if modes.chars().count() != prefixes.chars().count() {
+            return None;
+        }
+        Some(PrefixTable(
+            modes.chars().zip(prefixes.chars()).collect::<Vec<_>>(),
+        ))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, char)> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn mode_for_prefix(&self, prefix: char) -> Option<char> {
+        self.0
+            .iter()
+            .find_map(|&(mode, pfx)| (pfx == prefix).then_some(mode))
+    }
+
+    pub fn prefix_for_mode(&self, mode: char) -> Option<char> {
+        self.0
+            .iter()
+            .find_map(|&(m, pfx)| (m == mode).then_some(pfx))
+    }
+
+    /// Returns the rank of `prefix` (0 being the highest-privileged status),
+    /// or `None` if `prefix` isn't a recognized status prefix
+    pub fn prefix_rank(&self, prefix: char) -> Option<usize> {
+        self.0.iter().position(|&(_, pfx)| pfx == prefix)
+    }
+
+    /// Returns the rank of `mode` (0 being the highest-privileged status),
+    /// or `None` if `mode` isn't a recognized channel mode letter
+    pub fn mode_rank(&self, mode: char) -> Option<usize> {
+        self.0.iter().position(|&(m, _)| m == mode)
+    }
+
+    /// Compares the privilege of two status prefixes, with a higher-ranked
+    /// (more privileged) prefix comparing `Greater`.  Returns `None` if
+    /// either prefix isn't recognized.
+    pub fn cmp_prefixes(&self, a: char, b: char) -> Option<Ordering> {
+        let ra = self.prefix_rank(a)?;
+        let rb = self.prefix_rank(b)?;
+        Some(rb.cmp(&ra))
+    }
+}
+
+/// A per-character numeric limit, as advertised via the `CHANLIMIT` and
+/// `MAXLIST` `ISUPPORT` tokens (e.g. `#:250` or `bqeI:100`).  `limit` is
+/// `None` when the server advertised no limit for these characters (an
+/// empty value after the colon).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModeLimit {
+    pub chars: Vec<char>,
+    pub limit: Option<u32>,
+}
+
+impl ModeLimit {
+    fn parse_groups(value: &str) -> Vec<ModeLimit> {
+        value
+            .split(',')
+            .filter_map(|group| {
+                let (chars, limit) = group.split_once(':')?;
+                Some(ModeLimit {
+                    chars: chars.chars().collect(),
+                    limit: limit.parse::<u32>().ok(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the limit that applies to `ch`: `None` if `ch` isn't covered
+    /// by any group, `Some(None)` if its group has no limit, and
+    /// `Some(Some(n))` if its group is capped at `n`
+    pub fn limit_for(groups: &[ModeLimit], ch: char) -> Option<Option<u32>> {
+        groups
+            .iter()
+            .find(|g| g.chars.contains(&ch))
+            .map(|g| g.limit)
+    }
+}
+
+fn parse_targmax(value: &str) -> Vec<(String, Option<u32>)> {
+    value
+        .split(',')
+        .filter_map(|group| {
+            let (command, limit) = group.split_once(':')?;
+            let limit = if limit.is_empty() {
+                None
+            } else {
+                limit.parse::<u32>().ok()
+            };
+            Some((command.to_owned(), limit))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chanmodes() {
+        let params = ["CHANMODES=eIbq,k,flj,CFLMPQRSTcgimnprstuz"
+            .parse::<ISupportParam>()
+            .unwrap()];
+        let caps = ServerCapabilities::from_isupport(&params);
+        let cm = caps.chanmodes.unwrap();
+        assert_eq!(cm.takes_param('b', true), Some(true));
+        assert_eq!(cm.takes_param('b', false), Some(true));
+        assert_eq!(cm.takes_param('k', true), Some(true));
+        assert_eq!(cm.takes_param('k', false), Some(true));
+        assert_eq!(cm.takes_param('l', true), Some(true));
+        assert_eq!(cm.takes_param('l', false), Some(false));
+        assert_eq!(cm.takes_param('m', true), Some(false));
+        assert_eq!(cm.takes_param('m', false), Some(false));
+        assert_eq!(cm.takes_param('x', true), None);
+    }
+
+    #[test]
+    fn prefix() {
+        let params = ["PREFIX=(ov)@+".parse::<ISupportParam>().unwrap()];
+        let caps = ServerCapabilities::from_isupport(&params);
+        let prefix = caps.prefix.unwrap();
+        assert_eq!(prefix.mode_for_prefix('@'), Some('o'));
+        assert_eq!(prefix.mode_for_prefix('+'), Some('v'));
+        assert_eq!(prefix.prefix_for_mode('o'), Some('@'));
+        assert_eq!(prefix.cmp_prefixes('@', '+'), Some(Ordering::Greater));
+        assert_eq!(prefix.cmp_prefixes('+', '@'), Some(Ordering::Less));
+        assert_eq!(prefix.cmp_prefixes('@', '@'), Some(Ordering::Equal));
+        assert_eq!(prefix.cmp_prefixes('@', '%'), None);
+    }
+
+    #[test]
+    fn chanlimit_and_maxlist() {
+        let params = [
+            "CHANLIMIT=#:250".parse::<ISupportParam>().unwrap(),
+            "MAXLIST=bqeI:100".parse::<ISupportParam>().unwrap(),
+        ];
+        let caps = ServerCapabilities::from_isupport(&params);
+        assert_eq!(ModeLimit::limit_for(&caps.chanlimit, '#'), Some(Some(250)));
+        assert_eq!(ModeLimit::limit_for(&caps.chanlimit, '&'), None);
+        assert_eq!(ModeLimit::limit_for(&caps.maxlist, 'b'), Some(Some(100)));
+    }
+
+    #[test]
+    fn targmax() {
+        let params = ["TARGMAX=PRIVMSG:4,NOTICE:4,ACCEPT:,MONITOR:"
+            .parse::<ISupportParam>()
+            .unwrap()];
+        let caps = ServerCapabilities::from_isupport(&params);
+        assert_eq!(caps.targmax_limit("PRIVMSG"), Some(Some(4)));
+        assert_eq!(caps.targmax_limit("privmsg"), Some(Some(4)));
+        assert_eq!(caps.targmax_limit("ACCEPT"), Some(None));
+        assert_eq!(caps.targmax_limit("WHOIS"), None);
+    }
+
+    #[test]
+    fn casemapping() {
+        let params = ["CASEMAPPING=rfc1459".parse::<ISupportParam>().unwrap()];
+        let caps = ServerCapabilities::from_isupport(&params);
+        assert_eq!(caps.casemapping, CaseMapping::Rfc1459);
+    }
+}
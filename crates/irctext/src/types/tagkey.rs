@@ -22,6 +22,28 @@ fn validate(s: &str) -> Result<(), ParseTagKeyError> {
     }
 }
 
+impl TagKey {
+    /// Tests whether this key carries the `+` prefix IRCv3 uses to mark a
+    /// tag as client-only (set by clients rather than the server; see
+    /// <https://ircv3.net/specs/extensions/message-tags.html#rules>).
+    pub fn is_client_only(&self) -> bool {
+        self.as_str().starts_with('+')
+    }
+
+    /// Returns this key's vendor prefix and bare name, split on the first
+    /// `/`, e.g. `example.com/foo` becomes `(Some("example.com"), "foo")`.
+    /// A key with no `/` (or a client-only key with none after the `+`)
+    /// returns `(None, ...)`. The `+` client-only marker, if present, is
+    /// stripped from both the input consulted and the returned name.
+    pub fn vendor(&self) -> (Option<&str>, &str) {
+        let s = self.as_str().strip_prefix('+').unwrap_or(self.as_str());
+        match s.split_once('/') {
+            Some((vendor, name)) => (Some(vendor), name),
+            None => (None, s),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
 pub enum ParseTagKeyError {
     #[error("tag key names cannot be empty")]
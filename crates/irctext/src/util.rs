@@ -1,3 +1,4 @@
+use crate::types::ISupport;
 use crate::ClientMessageError;
 use std::fmt;
 
@@ -37,12 +38,56 @@ where
 }
 
 pub(crate) fn pop_channel_membership(s: &str) -> (Option<char>, &str) {
-    for ch in crate::CHANNEL_MEMBERSHIPS {
-        if let Some(rest) = s.strip_suffix(ch) {
-            return (Some(ch), rest);
+    pop_channel_membership_with(s, None)
+}
+
+/// Like `pop_channel_membership`, but consults the server's actual `PREFIX`
+/// ISUPPORT token (highest-privileged prefix first) instead of this
+/// library's hardcoded `CHANNEL_MEMBERSHIPS` default when `isupport` is
+/// given and advertises a `PREFIX`.
+pub(crate) fn pop_channel_membership_with<'a>(
+    s: &'a str,
+    isupport: Option<&ISupport>,
+) -> (Option<char>, &'a str) {
+    match isupport.and_then(ISupport::prefix) {
+        Some(prefixes) => {
+            for (_, ch) in prefixes.iter() {
+                if let Some(rest) = s.strip_suffix(ch) {
+                    return (Some(ch), rest);
+                }
+            }
+            (None, s)
+        }
+        None => {
+            for ch in crate::CHANNEL_MEMBERSHIPS {
+                if let Some(rest) = s.strip_suffix(ch) {
+                    return (Some(ch), rest);
+                }
+            }
+            (None, s)
+        }
+    }
+}
+
+/// Like `pop_channel_membership`, but pops every stacked leading prefix
+/// character instead of at most one, for servers advertising `multi-prefix`
+/// that send e.g. `@+#chan` for a user who is both an op and voiced. The
+/// returned `Vec` preserves rank order (highest-privileged first); a channel
+/// with no prefix at all yields an empty `Vec` rather than `None`, since
+/// there's no single "no membership" sentinel to distinguish from an empty
+/// set of memberships.
+pub(crate) fn pop_channel_memberships(s: &str) -> (Vec<char>, &str) {
+    let mut prefixes = Vec::new();
+    let mut rest = s;
+    while let Some(ch) = rest.chars().next() {
+        if crate::CHANNEL_MEMBERSHIPS.contains(&ch) {
+            prefixes.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        } else {
+            break;
         }
     }
-    (None, s)
+    (prefixes, rest)
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -87,10 +132,74 @@ impl<'a> Iterator for SplitSpaces<'a> {
 
 impl std::iter::FusedIterator for SplitSpaces<'_> {}
 
+/// Tests whether `text` matches `mask`, an IRC ban-mask-style glob pattern in
+/// which `*` matches any run of characters (including none) and `?` matches
+/// exactly one character.  Matching is case-sensitive, as no casemapping is
+/// available at this layer.
+pub(crate) fn glob_match(mask: &str, text: &str) -> bool {
+    let mask = mask.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut mi, mut ti) = (0, 0);
+    let mut backtrack = None;
+    while ti < text.len() {
+        if mask.get(mi).is_some_and(|&c| c == '?' || c == text[ti]) {
+            mi += 1;
+            ti += 1;
+        } else if mask.get(mi) == Some(&'*') {
+            backtrack = Some((mi, ti));
+            mi += 1;
+        } else if let Some((star, matched)) = backtrack {
+            mi = star + 1;
+            ti = matched + 1;
+            backtrack = Some((star, ti));
+        } else {
+            return false;
+        }
+    }
+    mask[mi..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod glob_match {
+        use super::*;
+
+        #[test]
+        fn exact_match() {
+            assert!(glob_match("foo", "foo"));
+            assert!(!glob_match("foo", "bar"));
+        }
+
+        #[test]
+        fn question_mark_matches_one_char() {
+            assert!(glob_match("f?o", "foo"));
+            assert!(!glob_match("f?o", "fo"));
+            assert!(!glob_match("f?o", "fooo"));
+        }
+
+        #[test]
+        fn star_matches_any_run() {
+            assert!(glob_match("f*o", "fo"));
+            assert!(glob_match("f*o", "foo"));
+            assert!(glob_match("f*o", "fooooo"));
+            assert!(glob_match("*", ""));
+            assert!(glob_match("*", "anything"));
+        }
+
+        #[test]
+        fn star_backtracks_past_false_starts() {
+            assert!(glob_match("*oo*bar", "foobarbar"));
+            assert!(!glob_match("*oo*bar", "foobarbaz"));
+        }
+
+        #[test]
+        fn trailing_stars_match_empty_remainder() {
+            assert!(glob_match("foo**", "foo"));
+        }
+    }
+
     mod split_spaces {
         use super::*;
 
@@ -6,7 +6,9 @@ pub enum Verb {
     Admin,
     Authenticate,
     Away,
+    Batch,
     Cap,
+    ChatHistory,
     Connect,
     Error,
     Help,
@@ -33,6 +35,7 @@ pub enum Verb {
     Rehash,
     Restart,
     Squit,
+    StartTls,
     Stats,
     Time,
     Topic,
@@ -76,3 +79,43 @@ impl<'a> PartialEq<&'a str> for Verb {
         self.as_ref() == *other
     }
 }
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Verb {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Verb {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Verb;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("an IRC command verb")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Verb, E>
+            where
+                E: serde::de::Error,
+            {
+                // Infallible: unrecognized verbs fall back to `Unknown`.
+                Ok(Verb::from(value.to_owned()))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
@@ -82,6 +82,9 @@ async fn main() -> anyhow::Result<()> {
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
@@ -331,7 +334,7 @@ fn format_msgtext(sender: &str, text: FinalParam) -> String {
         CtcpMessage::Action(Some(p)) => format!("* {sender} {}", ircfmt_to_ansi(p.as_str())),
         // TODO: Should the following messages be parsed for IRC formatting?
         CtcpMessage::ClientInfo(optp) => fmt_ctcp(sender, "CLIENTINFO", optp),
-        CtcpMessage::Dcc(optp) => fmt_ctcp(sender, "DCC", optp),
+        CtcpMessage::Dcc(optp) => fmt_ctcp(sender, "DCC", optp.map(CtcpParams::from)),
         CtcpMessage::Finger(optp) => fmt_ctcp(sender, "FINGER", optp),
         CtcpMessage::Ping(optp) => fmt_ctcp(sender, "PING", optp),
         CtcpMessage::Source(optp) => fmt_ctcp(sender, "SOURCE", optp),
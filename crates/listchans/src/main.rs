@@ -73,6 +73,9 @@ async fn run(args: Arguments) -> anyhow::Result<()> {
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
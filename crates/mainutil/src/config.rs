@@ -0,0 +1,88 @@
+//! A reusable configuration loader for bot/client binaries, replacing the
+//! ad-hoc per-binary combination of [`clap::Parser`](https://docs.rs/clap)
+//! arguments and hand-rolled `toml::from_slice` calls with a single
+//! [`Config::from_path`] that picks the file format from the extension and
+//! a [`Config::build`] that produces everything a binary needs to connect,
+//! log in, and join its starting channels.
+use crate::ChannelSet;
+use ircnet::client::{ConnectionParams, LoginParams, SessionParams};
+use ircnet::{ClientCert, ConnectionError};
+use irctext::types::{CaseMapping, Channel};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Connection, registration, and starting-channel settings for an IRC
+/// bot/client, loadable from a TOML or JSON file via [`Config::from_path`].
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub connect: ConnectionParams,
+
+    /// Path to a PEM-encoded client certificate chain, for CertFP
+    /// authentication; must be paired with `key_path`.  An alternative to
+    /// setting `connect.client_cert` directly with embedded PEM content,
+    /// for configurations that keep credentials on disk.  Loaded via
+    /// [`ClientCert::from_files`] by [`Config::build`].
+    #[serde(default)]
+    pub cert_path: Option<PathBuf>,
+    /// Path to the PEM-encoded private key matching `cert_path`.
+    #[serde(default)]
+    pub key_path: Option<PathBuf>,
+
+    #[serde(flatten)]
+    pub login: LoginParams,
+
+    /// Channels to seed a [`ChannelSet`] with once the client connects.
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+}
+
+impl Config {
+    /// Reads and parses the configuration file at `path`, picking the TOML
+    /// or JSON deserializer based on its extension (`.toml` or `.json`).
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(ConfigError::Read)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_slice::<Config>(&data).map_err(ConfigError::Toml),
+            Some("json") => serde_json::from_slice::<Config>(&data).map_err(ConfigError::Json),
+            _ => Err(ConfigError::UnknownFormat(path.to_owned())),
+        }
+    }
+
+    /// Loads `cert_path`/`key_path` (if both are given) into
+    /// `connect.client_cert` and returns the resulting [`SessionParams`]
+    /// together with a [`ChannelSet`] seeded with `channels`.  The
+    /// `ChannelSet` is canonicalized under the default case mapping, since
+    /// the server's actual `CASEMAPPING` isn't known until login completes;
+    /// call [`ChannelSet::rekey`] once it is.
+    pub fn build(mut self) -> Result<(SessionParams, ChannelSet), ConfigError> {
+        if let (Some(cert_path), Some(key_path)) = (&self.cert_path, &self.key_path) {
+            self.connect.client_cert =
+                Some(ClientCert::from_files(cert_path, key_path).map_err(ConfigError::Cert)?);
+        }
+        let mut channels = ChannelSet::new(CaseMapping::default());
+        for chan in self.channels {
+            channels.add(chan);
+        }
+        let params = SessionParams {
+            connect: self.connect,
+            login: self.login,
+        };
+        Ok((params, channels))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read configuration file: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("configuration file {0:?} has an unrecognized extension (expected .toml or .json)")]
+    UnknownFormat(PathBuf),
+    #[error("failed to parse TOML configuration: {0}")]
+    Toml(#[source] toml::de::Error),
+    #[error("failed to parse JSON configuration: {0}")]
+    Json(#[source] serde_json::Error),
+    #[error("failed to load client certificate: {0}")]
+    Cert(#[source] ConnectionError),
+}
@@ -1,3 +1,5 @@
+pub mod config;
+
 use irctext::types::{CaseMapping, Channel};
 use std::collections::HashMap;
 use std::io::{IsTerminal, stderr};
@@ -100,4 +102,18 @@ impl ChannelSet {
     pub fn is_empty(&self) -> bool {
         self.lower2canon.is_empty()
     }
+
+    /// Re-lowercases every stored channel under `new_mapping`, replacing the
+    /// canonicalization table built under the previous mapping.  Call this
+    /// once the server's actual `CASEMAPPING` is learned from
+    /// `RPL_ISUPPORT` (via [`irctext::types::ISupport::casemapping`]), since
+    /// any channels added beforehand were canonicalized under an assumed
+    /// default.
+    pub fn rekey(&mut self, new_mapping: CaseMapping) {
+        self.casemapping = new_mapping;
+        self.lower2canon = std::mem::take(&mut self.lower2canon)
+            .into_values()
+            .map(|chan| (chan.to_lowercase(new_mapping), chan))
+            .collect();
+    }
 }
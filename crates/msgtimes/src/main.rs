@@ -4,12 +4,12 @@ use either::Either;
 use ircnet::client::{
     SessionBuilder, SessionParams,
     autoresponders::{CtcpQueryResponder, PingResponder},
-    commands::JoinCommand,
+    commands::{ChatHistory, HistoricalMessage, JoinCommand},
 };
 use irctext::{
-    CaseMapping, ClientMessage, Message, Payload, TrailingParam,
-    clientmsgs::{Away, Quit},
-    ctcp::CtcpParams,
+    CaseMapping, ClientMessage, FinalParam, Message, MessageTags, Payload, Source, TrailingParam,
+    clientmsgs::{Away, Capability, ChatHistory as ChatHistoryMsg, Quit},
+    ctcp::{CtcpMessage, CtcpParams},
     types::{Channel, ISupportParam, MsgTarget},
 };
 use mainutil::{init_logging, run_until_stopped};
@@ -57,6 +57,25 @@ struct Profile {
 struct ProgramParams {
     channels: Vec<Channel>,
     away: Option<TrailingParam>,
+
+    /// Number of messages to request via `CHATHISTORY LATEST` on joining
+    /// each channel, backfilling the gap since this logger was last online.
+    /// Only takes effect if the server advertises `draft/chathistory`; has
+    /// no effect otherwise.
+    #[serde(default = "default_backfill")]
+    backfill: u32,
+
+    /// Include the sender's nick and CTCP-decoded message body in
+    /// `"message"` and `"history"` events, and record whether each one was a
+    /// `PRIVMSG` or a `NOTICE`. Off by default, since the minimal format
+    /// (just timing and channel) avoids persisting message content for
+    /// privacy-conscious deployments.
+    #[serde(default)]
+    log_content: bool,
+}
+
+fn default_backfill() -> u32 {
+    50
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -92,11 +111,20 @@ async fn main() -> anyhow::Result<()> {
     };
     let mut log = EventLogger::new(outfile);
 
+    let mut session_params = profile.session_params;
+    session_params
+        .login
+        .capabilities
+        .extend([server_time_cap(), chathistory_cap()]);
+
     tracing::info!("Connecting to IRC …");
-    let (mut client, login_output) = SessionBuilder::new(profile.session_params)
+    let (mut client, login_output) = SessionBuilder::new(session_params)
         .with_autoresponder(PingResponder::new())
         .with_autoresponder(
             CtcpQueryResponder::new()
+                .with_clientinfo()
+                .with_ping()
+                .with_time()
                 .with_version(
                     env!("CARGO_CRATE_NAME")
                         .parse::<CtcpParams>()
@@ -111,6 +139,14 @@ async fn main() -> anyhow::Result<()> {
         .build()
         .await?;
 
+    // ERROR never appears inside a CHATHISTORY batch, so unlike PRIVMSG/
+    // NOTICE there's no risk of a dispatch handler double-logging a
+    // backfilled message; register it as a handler instead of matching on
+    // it in the main loop below.
+    client.on_error(|m, _source| {
+        tracing::info!(reason = m.reason().to_string(), "Server sent ERROR message");
+    });
+
     let casemapping = login_output
         .isupport
         .iter()
@@ -126,6 +162,12 @@ async fn main() -> anyhow::Result<()> {
         })
         .unwrap_or_default();
     let me = login_output.my_nick;
+    let chathistory_enabled = login_output
+        .capabilities
+        .iter()
+        .any(|(cap, _)| *cap == chathistory_cap());
+    let backfill = profile.msgtimes.backfill;
+    let log_content = profile.msgtimes.log_content;
 
     if let Some(p) = profile.msgtimes.away {
         client.send(Away::new(p)).await?;
@@ -140,32 +182,83 @@ async fn main() -> anyhow::Result<()> {
             &network,
             Some(chan.clone().into_inner()),
             "joined",
+            None,
         ))?;
-        canon_channels.add(chan);
+        canon_channels.add(chan.clone());
+
+        if chathistory_enabled {
+            tracing::info!("Backfilling history for {chan} …");
+            let target = MsgTarget::Channel(chan.clone());
+            let request = ChatHistoryMsg::latest(target, None, backfill);
+            let history = client.run(ChatHistory::new(request)).await?;
+            for HistoricalMessage { time, message, .. } in history {
+                let (kind, sender, content) = if log_content {
+                    match message_fields(&message) {
+                        Some((kind, sender, content)) => (Some(kind), sender, Some(content)),
+                        None => (None, None, None),
+                    }
+                } else {
+                    (None, None, None)
+                };
+                log.log(Event::history(
+                    &network,
+                    chan.clone().into_inner(),
+                    time.as_deref(),
+                    kind,
+                    sender,
+                    content,
+                ))?;
+            }
+        }
     }
 
     loop {
         match run_until_stopped(client.recv()).await {
             Some(Ok(Some(Message {
+                tags,
+                source,
                 payload: Payload::ClientMessage(climsg),
-                ..
             }))) => {
                 match climsg {
                     ClientMessage::PrivMsg(m) => {
+                        let (sender, content) = if log_content {
+                            (sender_nick(source.as_ref()), Some(message_body(m.text())))
+                        } else {
+                            (None, None)
+                        };
                         for t in m.targets() {
                             if let MsgTarget::Channel(c0) = t
                                 && let Some(c) = canon_channels.get(c0).cloned()
                             {
-                                log.log(Event::new(&network, Some(c.into_inner()), "message"))?;
+                                log.log(Event::message(
+                                    &network,
+                                    c.into_inner(),
+                                    tags.as_ref(),
+                                    log_content.then_some(MessageKind::PrivMsg),
+                                    sender.clone(),
+                                    content.clone(),
+                                ))?;
                             }
                         }
                     }
                     ClientMessage::Notice(m) => {
+                        let (sender, content) = if log_content {
+                            (sender_nick(source.as_ref()), Some(message_body(m.text())))
+                        } else {
+                            (None, None)
+                        };
                         for t in m.targets() {
                             if let MsgTarget::Channel(c0) = t
                                 && let Some(c) = canon_channels.get(c0).cloned()
                             {
-                                log.log(Event::new(&network, Some(c.into_inner()), "message"))?;
+                                log.log(Event::message(
+                                    &network,
+                                    c.into_inner(),
+                                    tags.as_ref(),
+                                    log_content.then_some(MessageKind::Notice),
+                                    sender.clone(),
+                                    content.clone(),
+                                ))?;
                             }
                         }
                     }
@@ -183,6 +276,7 @@ async fn main() -> anyhow::Result<()> {
                                 &network,
                                 Some(chan.as_str().to_owned()),
                                 "kicked",
+                                tags.as_ref(),
                             ))?;
                             let chan = chan.to_owned(); // Stop borrowing from canon_channels so we can mutate it
                             canon_channels.remove(&chan);
@@ -192,25 +286,19 @@ async fn main() -> anyhow::Result<()> {
                             }
                         }
                     }
-                    ClientMessage::Error(m) => {
-                        tracing::info!(
-                            reason = String::from(m.into_reason()),
-                            "Server sent ERROR message"
-                        );
-                    }
                     _ => (),
                 }
             }
             Some(Ok(Some(_))) => (),
             Some(Ok(None)) => {
                 tracing::info!("Connection closed");
-                log.log(Event::new(&network, None, "disconnected"))?;
+                log.log(Event::new(&network, None, "disconnected", None))?;
                 break;
             }
             Some(Err(e)) => {
                 let e = anyhow::Error::new(e);
                 tracing::error!(?e, "Error communicating with server");
-                log.log(Event::new(&network, None, "error"))?;
+                log.log(Event::new(&network, None, "error", None))?;
                 return Err(e);
             }
             None => {
@@ -252,18 +340,172 @@ struct Event {
     channel: Option<String>,
     event: String,
     timestamp: String,
+    time_source: TimeSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<MessageKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+/// Whether a `"message"`/`"history"` [`Event`] originated from a `PRIVMSG`
+/// or a `NOTICE`. Only populated when [`ProgramParams::log_content`] is
+/// enabled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum MessageKind {
+    PrivMsg,
+    Notice,
+}
+
+/// Whether an [`Event`]'s `timestamp` came from the originating message's
+/// `server-time` tag or was assigned locally at receive time (because the
+/// tag was absent, unparseable, or the event has no originating message at
+/// all, e.g. a disconnect).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TimeSource {
+    Server,
+    Local,
 }
 
 impl Event {
-    fn new(network: &str, channel: Option<String>, event: &str) -> Event {
-        let timestamp = jiff::Timestamp::now().to_string();
+    /// Builds an event, preferring `server_time` (the IRCv3 `time` message
+    /// tag off the `Message` that triggered this event, if any) over the
+    /// current time as the timestamp, since a replayed or otherwise delayed
+    /// message's receive time isn't when it actually happened. Pass `None`
+    /// for events with no originating tagged message (joins, disconnects).
+    fn new(
+        network: &str,
+        channel: Option<String>,
+        event: &str,
+        tags: Option<&MessageTags>,
+    ) -> Event {
+        let server_time = tags.and_then(MessageTags::time);
+        let (timestamp, time_source) = resolve_timestamp(server_time);
         Event {
             network: network.to_owned(),
             channel,
             event: event.to_owned(),
             timestamp,
+            time_source,
+            kind: None,
+            sender: None,
+            content: None,
+        }
+    }
+
+    /// Builds a `"message"` event for a live `PRIVMSG`/`NOTICE`, optionally
+    /// attaching the sender's nick and CTCP-decoded content; pass `None` for
+    /// `kind`/`sender`/`content` when [`ProgramParams::log_content`] is
+    /// disabled, since the message body may be sensitive.
+    fn message(
+        network: &str,
+        channel: String,
+        tags: Option<&MessageTags>,
+        kind: Option<MessageKind>,
+        sender: Option<String>,
+        content: Option<String>,
+    ) -> Event {
+        let server_time = tags.and_then(MessageTags::time);
+        let (timestamp, time_source) = resolve_timestamp(server_time);
+        Event {
+            network: network.to_owned(),
+            channel: Some(channel),
+            event: "message".to_owned(),
+            timestamp,
+            time_source,
+            kind,
+            sender,
+            content,
         }
     }
+
+    /// Builds a `"history"` event for a message backfilled via
+    /// `CHATHISTORY`, using the message's own `time` tag (if any) as its
+    /// timestamp instead of the current time, since it happened well
+    /// before this backfill request. See [`Event::message`] regarding
+    /// `kind`/`sender`/`content`.
+    fn history(
+        network: &str,
+        channel: String,
+        time: Option<&str>,
+        kind: Option<MessageKind>,
+        sender: Option<String>,
+        content: Option<String>,
+    ) -> Event {
+        let (timestamp, time_source) = resolve_timestamp(time);
+        Event {
+            network: network.to_owned(),
+            channel: Some(channel),
+            event: "history".to_owned(),
+            timestamp,
+            time_source,
+            kind,
+            sender,
+            content,
+        }
+    }
+}
+
+/// Returns the `(kind, sender, content)` fields for a `"message"`/`"history"`
+/// event derived from `msg`, or `None` if `msg`'s payload isn't a
+/// `PRIVMSG`/`NOTICE` (e.g. a `TAGMSG` returned by `CHATHISTORY`).
+fn message_fields(msg: &Message) -> Option<(MessageKind, Option<String>, String)> {
+    let (kind, text) = match &msg.payload {
+        Payload::ClientMessage(ClientMessage::PrivMsg(m)) => (MessageKind::PrivMsg, m.text()),
+        Payload::ClientMessage(ClientMessage::Notice(m)) => (MessageKind::Notice, m.text()),
+        _ => return None,
+    };
+    Some((kind, sender_nick(msg.source.as_ref()), message_body(text)))
+}
+
+/// Returns the sending client's nick, or `None` if `source` is absent or is
+/// a server source (e.g. a message relayed by the server itself rather than
+/// a client).
+fn sender_nick(source: Option<&Source>) -> Option<String> {
+    match source {
+        Some(Source::Client(client)) => Some(client.nickname.to_string()),
+        _ => None,
+    }
+}
+
+/// Renders a `PRIVMSG`/`NOTICE` text parameter for logging: an `ACTION`
+/// CTCP payload is rendered in the conventional `"* <text>"` /me form, and
+/// any other content (plain text or another CTCP query) is rendered as its
+/// raw text.
+fn message_body(text: &FinalParam) -> String {
+    match text.as_ctcp() {
+        Some(CtcpMessage::Action(params)) => {
+            format!("* {}", params.as_ref().map_or("", CtcpParams::as_str))
+        }
+        _ => text.as_str().to_owned(),
+    }
+}
+
+fn resolve_timestamp(server_time: Option<&str>) -> (String, TimeSource) {
+    match server_time.and_then(|t| t.parse::<jiff::Timestamp>().ok()) {
+        Some(ts) => (ts.to_string(), TimeSource::Server),
+        None => (jiff::Timestamp::now().to_string(), TimeSource::Local),
+    }
+}
+
+/// The `server-time` capability, requested so the server tags messages with
+/// their actual occurrence time instead of leaving event timestamps to
+/// depend on when this bot happened to receive them.
+fn server_time_cap() -> Capability {
+    "server-time"
+        .parse()
+        .expect(r#""server-time" should be a valid Capability"#)
+}
+
+/// The `draft/chathistory` capability, requested so `CHATHISTORY` requests
+/// are meaningful; see [`ChatHistory`].
+fn chathistory_cap() -> Capability {
+    "draft/chathistory"
+        .parse()
+        .expect(r#""draft/chathistory" should be a valid Capability"#)
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]